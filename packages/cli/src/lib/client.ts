@@ -1,8 +1,10 @@
 import { NetworkError, NotFoundError, NonJsonResponseError } from "./errors.js";
+import { showNotice } from "./notice.js";
 import type {
   TransactionExplanation,
   AccountExplanation,
   HealthResponse,
+  ResponseEnvelope,
 } from "../types/index.js";
 
 export interface ClientOptions {
@@ -44,7 +46,8 @@ async function requestOnce<T>(
       throw new NonJsonResponseError(res.status, text.slice(0, 120));
     }
 
-    return res.json() as Promise<T>;
+    const body = (await res.json()) as T | ResponseEnvelope<T>;
+    return unwrap<T>(body);
   } catch (err) {
     if (err instanceof NotFoundError || err instanceof NetworkError || err instanceof NonJsonResponseError) throw err;
     if ((err as Error).name === "AbortError")
@@ -55,6 +58,23 @@ async function requestOnce<T>(
   }
 }
 
+function isEnvelope<T>(body: unknown): body is ResponseEnvelope<T> {
+  return (
+    typeof body === "object" &&
+    body !== null &&
+    "data" in body &&
+    "meta" in body &&
+    typeof (body as { meta: unknown }).meta === "object"
+  );
+}
+
+/** The payload of an enveloped response, showing any service notice it carries. */
+function unwrap<T>(body: T | ResponseEnvelope<T>): T {
+  if (!isEnvelope<T>(body)) return body;
+  if (body.meta.notice) showNotice(body.meta.notice);
+  return body.data;
+}
+
 async function request<T>(url: string, opts: ClientOptions): Promise<T> {
   const retries = Number.isFinite(opts.retries) && opts.retries >= 0 ? opts.retries : 0;
   let lastError: unknown;
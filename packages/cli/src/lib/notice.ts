@@ -0,0 +1,14 @@
+import type { ServiceNotice } from "../types/index.js";
+
+const shown = new Set<string>();
+
+/**
+ * Print an operator's service notice to stderr, once per message, so it
+ * never mixes with JSON on stdout and batch runs do not repeat it.
+ */
+export function showNotice(notice: ServiceNotice): void {
+  if (shown.has(notice.message)) return;
+  shown.add(notice.message);
+  const label = notice.severity === "info" ? "Notice" : `Notice (${notice.severity})`;
+  process.stderr.write(`${label}: ${notice.message}\n`);
+}
@@ -47,6 +47,25 @@ export interface HealthResponse {
   version: string;
 }
 
+export interface ServiceNotice {
+  message: string;
+  severity: "info" | "warning" | "critical";
+  set_at: string;
+}
+
+/** Standard envelope the server wraps successful JSON responses in. */
+export interface ResponseEnvelope<T> {
+  data: T;
+  meta: {
+    request_id: string | null;
+    network: string;
+    generated_at: string;
+    cache: "hit" | "miss";
+    api_version: string;
+    notice?: ServiceNotice;
+  };
+}
+
 export interface ApiSuccessResponse<T> {
   success: true;
   data: T;
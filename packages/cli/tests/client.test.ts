@@ -92,6 +92,44 @@ describe("createClient", () => {
     });
   });
 
+  // ── Response envelope ──────────────────────────────────────────
+
+  describe("enveloped response", () => {
+    it("returns the payload and shows the service notice once", async () => {
+      const writeSpy = vi.spyOn(process.stderr, "write").mockImplementation(() => true);
+      const health = { status: "ok", horizon_reachable: true, version: "1.0.0" };
+      vi.stubGlobal(
+        "fetch",
+        mockFetchResponse(200, {
+          data: health,
+          meta: {
+            request_id: "req-1",
+            network: "public",
+            generated_at: "2026-01-02T10:15:00Z",
+            cache: "miss",
+            api_version: "v1",
+            notice: {
+              message: "Horizon mainnet is degraded; data may lag",
+              severity: "warning",
+              set_at: "2026-01-02T10:00:00Z",
+            },
+          },
+        }),
+      );
+
+      const client = createClient(defaultOpts);
+      expect(await client.getHealth()).toEqual(health);
+      await client.getHealth();
+
+      expect(writeSpy).toHaveBeenCalledTimes(1);
+      expect(writeSpy).toHaveBeenCalledWith(
+        "Notice (warning): Horizon mainnet is degraded; data may lag\n",
+      );
+
+      writeSpy.mockRestore();
+    });
+  });
+
   // ── 404 Not Found ──────────────────────────────────────────────
 
   describe("404 response", () => {
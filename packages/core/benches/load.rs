@@ -0,0 +1,310 @@
+//! Load test for `/tx/:hash` against a local instance.
+//!
+//! Starts the router on an ephemeral port with a wiremock Horizon serving
+//! the snapshot fixtures in `tests/fixtures/horizon`, then replays the
+//! traffic in `benches/load_traffic.txt` from several concurrent clients.
+//! It runs twice: once with no explanation index, so every request is a miss
+//! that goes through Horizon, and once with every fixture already indexed,
+//! so every request takes the cache hit path. Latency percentiles are
+//! printed for both, and the run fails if the hit path's p99 is over budget
+//! or any request fails.
+//!
+//! Run with `cargo bench --bench load`. Settings are read from the
+//! environment:
+//!
+//! | variable               | default                    |
+//! |------------------------|----------------------------|
+//! | `LOADTEST_TRAFFIC`     | `benches/load_traffic.txt` |
+//! | `LOADTEST_CONCURRENCY` | 8                          |
+//! | `LOADTEST_ROUNDS`      | 50                         |
+//! | `LOADTEST_HIT_P99_MS`  | 25                         |
+//!
+//! Under `cargo test` it replays the traffic once as a smoke test.
+
+use std::env;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use axum::{Extension, Router, middleware, routing::get};
+use serde_json::{Value, json};
+use stellar_explain_core::explain::transaction::explain_transaction_with_ledger;
+use stellar_explain_core::middleware::request_id::request_id_middleware;
+use stellar_explain_core::routes::tx::get_tx_explanation;
+use stellar_explain_core::services::explain::map_transaction_to_domain;
+use stellar_explain_core::services::horizon::HorizonClient;
+use stellar_explain_core::services::storage::{ExplanationStore, StoredExplanation};
+use tokio::net::TcpListener;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/horizon");
+const TRAFFIC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/benches/load_traffic.txt");
+
+struct Settings {
+    traffic: PathBuf,
+    concurrency: usize,
+    rounds: usize,
+    hit_p99_budget: Duration,
+}
+
+impl Settings {
+    /// Read the settings from the environment. `smoke` replays the traffic
+    /// once instead of the configured number of rounds.
+    fn from_env(smoke: bool) -> Self {
+        let number = |name: &str, default: u64| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|&v| v > 0)
+                .unwrap_or(default)
+        };
+        Self {
+            traffic: env::var("LOADTEST_TRAFFIC")
+                .map(PathBuf::from)
+                .unwrap_or_else(|_| PathBuf::from(TRAFFIC)),
+            concurrency: number("LOADTEST_CONCURRENCY", 8) as usize,
+            rounds: if smoke {
+                1
+            } else {
+                number("LOADTEST_ROUNDS", 50) as usize
+            },
+            hit_p99_budget: Duration::from_millis(number("LOADTEST_HIT_P99_MS", 25)),
+        }
+    }
+}
+
+/// A fixture as served by Horizon: the transaction and its operations.
+struct Fixture {
+    transaction: Value,
+    operations: Value,
+}
+
+fn load_fixtures() -> Vec<Fixture> {
+    let mut paths: Vec<_> = std::fs::read_dir(FIXTURES)
+        .expect("fixtures directory is missing")
+        .map(|entry| entry.unwrap().path())
+        .collect();
+    paths.sort();
+    paths
+        .iter()
+        .map(|path| {
+            let text = std::fs::read_to_string(path).unwrap();
+            let mut value: Value = serde_json::from_str(&text)
+                .unwrap_or_else(|err| panic!("{}: {err}", path.display()));
+            Fixture {
+                transaction: value["transaction"].take(),
+                operations: value["operations"].take(),
+            }
+        })
+        .collect()
+}
+
+fn load_traffic(path: &PathBuf) -> Vec<String> {
+    std::fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("{}: {err}", path.display()))
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_string)
+        .collect()
+}
+
+async fn mock_horizon(fixtures: &[Fixture]) -> MockServer {
+    let server = MockServer::start().await;
+    for fixture in fixtures {
+        let hash = fixture.transaction["hash"].as_str().unwrap();
+        Mock::given(method("GET"))
+            .and(path(format!("/transactions/{hash}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(&fixture.transaction))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("/transactions/{hash}/operations")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "_embedded": { "records": fixture.operations }
+            })))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/fee_stats"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "last_ledger_base_fee": "100",
+            "fee_charged": { "min": "100", "max": "1000", "mode": "100", "p90": "250" }
+        })))
+        .mount(&server)
+        .await;
+    server
+}
+
+/// An explanation index holding every fixture, as the ingestion worker
+/// would leave it.
+fn indexed(fixtures: &[Fixture]) -> ExplanationStore {
+    let store = ExplanationStore::open_in_memory().unwrap();
+    for fixture in fixtures {
+        let transaction = serde_json::from_value(fixture.transaction.clone()).unwrap();
+        let operations = serde_json::from_value(fixture.operations.clone()).unwrap();
+        let created_at = fixture.transaction["created_at"]
+            .as_str()
+            .map(str::to_string);
+        let ledger = fixture.transaction["ledger"].as_u64();
+        let domain = map_transaction_to_domain(transaction, operations);
+        let explanation =
+            explain_transaction_with_ledger(&domain, None, created_at.as_deref(), ledger).unwrap();
+        store
+            .put_explanation(&StoredExplanation {
+                ledger: ledger.unwrap_or_default(),
+                created_at,
+                fee_charged: domain.fee_charged,
+                explanation,
+            })
+            .unwrap();
+    }
+    store
+}
+
+async fn spawn_app(horizon_base_url: String, store: Option<ExplanationStore>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let mut app = Router::new()
+        .route("/tx/:hash", get(get_tx_explanation))
+        .with_state(Arc::new(HorizonClient::new(horizon_base_url)));
+    if let Some(store) = store {
+        app = app.layer(Extension(Arc::new(store)));
+    }
+    let app = app.layer(middleware::from_fn(request_id_middleware));
+
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    format!("http://{addr}")
+}
+
+/// Latencies of one replay, sorted.
+struct Report {
+    latencies: Vec<Duration>,
+    failures: usize,
+}
+
+impl Report {
+    /// Nearest-rank percentile.
+    fn percentile(&self, p: f64) -> Duration {
+        if self.latencies.is_empty() {
+            return Duration::ZERO;
+        }
+        let rank = (p / 100.0 * self.latencies.len() as f64).ceil() as usize;
+        self.latencies[rank.clamp(1, self.latencies.len()) - 1]
+    }
+
+    fn print(&self, name: &str) {
+        let ms = |d: Duration| format!("{:.2}ms", d.as_secs_f64() * 1000.0);
+        println!(
+            "{name:<6} {:>8} {:>8} {:>10} {:>10} {:>10}",
+            self.latencies.len(),
+            self.failures,
+            ms(self.percentile(50.0)),
+            ms(self.percentile(95.0)),
+            ms(self.percentile(99.0)),
+        );
+    }
+}
+
+/// Send every path `rounds` times from `concurrency` clients, each taking the
+/// next request in traffic order as soon as its previous one completes.
+async fn replay(base_url: &str, traffic: &[String], settings: &Settings) -> Report {
+    let client = reqwest::Client::new();
+    let urls: Arc<Vec<String>> = Arc::new(
+        (0..settings.rounds)
+            .flat_map(|_| traffic.iter().map(|path| format!("{base_url}{path}")))
+            .collect(),
+    );
+    let next = Arc::new(AtomicUsize::new(0));
+
+    let workers: Vec<_> = (0..settings.concurrency)
+        .map(|_| {
+            let client = client.clone();
+            let urls = Arc::clone(&urls);
+            let next = Arc::clone(&next);
+            tokio::spawn(async move {
+                let mut latencies = Vec::new();
+                let mut failures = 0;
+                while let Some(url) = urls.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let started = Instant::now();
+                    let ok = match client.get(url).send().await {
+                        Ok(res) => res.status().is_success() && res.bytes().await.is_ok(),
+                        Err(_) => false,
+                    };
+                    latencies.push(started.elapsed());
+                    if !ok {
+                        failures += 1;
+                    }
+                }
+                (latencies, failures)
+            })
+        })
+        .collect();
+
+    let mut report = Report {
+        latencies: Vec::with_capacity(urls.len()),
+        failures: 0,
+    };
+    for worker in workers {
+        let (latencies, failures) = worker.await.unwrap();
+        report.latencies.extend(latencies);
+        report.failures += failures;
+    }
+    report.latencies.sort();
+    report
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    // `cargo bench` passes --bench; `cargo test` runs this as a smoke test.
+    let smoke = !env::args().any(|arg| arg == "--bench");
+    let settings = Settings::from_env(smoke);
+    let fixtures = load_fixtures();
+    let traffic = load_traffic(&settings.traffic);
+
+    let horizon = mock_horizon(&fixtures).await;
+    let miss_url = spawn_app(horizon.uri(), None).await;
+    // Nothing is mounted on this Horizon, so a request that misses the
+    // index fails instead of quietly measuring the slow path.
+    let empty_horizon = MockServer::start().await;
+    let hit_url = spawn_app(empty_horizon.uri(), Some(indexed(&fixtures))).await;
+
+    println!(
+        "replaying {} requests x {} rounds from {} clients",
+        traffic.len(),
+        settings.rounds,
+        settings.concurrency
+    );
+    println!(
+        "{:<6} {:>8} {:>8} {:>10} {:>10} {:>10}",
+        "path", "requests", "failed", "p50", "p95", "p99"
+    );
+    let miss = replay(&miss_url, &traffic, &settings).await;
+    miss.print("miss");
+    let hit = replay(&hit_url, &traffic, &settings).await;
+    hit.print("hit");
+
+    let mut passed = true;
+    if miss.failures + hit.failures > 0 {
+        eprintln!("{} requests failed", miss.failures + hit.failures);
+        passed = false;
+    }
+    let hit_p99 = hit.percentile(99.0);
+    if hit_p99 > settings.hit_p99_budget {
+        eprintln!(
+            "cache hit p99 {:?} is over the {:?} budget (LOADTEST_HIT_P99_MS)",
+            hit_p99, settings.hit_p99_budget
+        );
+        passed = false;
+    }
+    if passed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,119 @@
+//! Throughput of `TransactionCache` under a 95/5 read/write mix.
+//!
+//! Each iteration runs the mix on several threads at once over a key space
+//! that is only half populated, so reads see a realistic share of misses and
+//! expired entries as well as hits.
+//!
+//! Run with `cargo bench --bench transaction_cache`.
+//!
+//! Replacing the single `RwLock` with sharded locks and a read-only `get`
+//! measured, in Melem/s for 1 / 4 / 8 threads on a 1-vCPU machine:
+//!
+//! | design                     | 1    | 4    | 8    |
+//! |----------------------------|------|------|------|
+//! | single lock, write on miss | 5.36 | 5.11 | 6.19 |
+//! | 16 shards, read-only get   | 5.49 | 5.98 | 4.65 |
+//!
+//! With one core the threads never actually run in parallel, so these only
+//! show that sharding costs nothing per operation; the 4- and 8-thread
+//! figures are within run-to-run noise. The contention the redesign removes,
+//! misses and expired reads queueing on one write lock, needs several cores
+//! to show up; rerun there before quoting a speedup.
+
+use std::hint::black_box;
+use std::sync::{Arc, Barrier};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use criterion::{BenchmarkId, Criterion, Throughput, criterion_group, criterion_main};
+use stellar_explain_core::services::transaction_cache::{CacheKey, Network, TransactionCache};
+
+const KEYS: usize = 1024;
+const OPS_PER_THREAD: u64 = 50_000;
+
+fn keys() -> Arc<Vec<CacheKey>> {
+    Arc::new(
+        (0..KEYS)
+            .map(|i| CacheKey::new(format!("{i:064x}"), Network::Public))
+            .collect(),
+    )
+}
+
+/// A populated cache: even keys are live, every fourth odd key has expired,
+/// and the rest are missing.
+fn populated(keys: &[CacheKey]) -> TransactionCache<String> {
+    let cache = TransactionCache::new(Duration::from_secs(600));
+    for (i, key) in keys.iter().enumerate() {
+        if i % 2 == 0 {
+            cache.insert(key.clone(), "explanation".repeat(20));
+        } else if i % 8 == 1 {
+            cache.insert_with_ttl(key.clone(), "stale".to_string(), Duration::ZERO);
+        }
+    }
+    cache
+}
+
+/// Run `ops` operations per thread, one write for every 19 reads.
+fn run_mix(
+    cache: &TransactionCache<String>,
+    keys: &Arc<Vec<CacheKey>>,
+    threads: usize,
+    ops: u64,
+) -> Duration {
+    let barrier = Arc::new(Barrier::new(threads + 1));
+    let handles: Vec<_> = (0..threads)
+        .map(|t| {
+            let cache = cache.clone();
+            let keys = Arc::clone(keys);
+            let barrier = Arc::clone(&barrier);
+            thread::spawn(move || {
+                barrier.wait();
+                // Cheap per-thread LCG so the key order differs between threads.
+                let mut state = 0x9e37_79b9_7f4a_7c15_u64 ^ t as u64;
+                for op in 0..ops {
+                    state = state
+                        .wrapping_mul(6_364_136_223_846_793_005)
+                        .wrapping_add(1_442_695_040_888_963_407);
+                    let key = &keys[(state >> 33) as usize % keys.len()];
+                    if op % 20 == 0 {
+                        cache.insert(key.clone(), "explanation".to_string());
+                    } else {
+                        black_box(cache.get(key));
+                    }
+                }
+            })
+        })
+        .collect();
+
+    barrier.wait();
+    let started = Instant::now();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    started.elapsed()
+}
+
+fn read_heavy_mix(c: &mut Criterion) {
+    let keys = keys();
+    let mut group = c.benchmark_group("transaction_cache_95_5");
+
+    for threads in [1, 4, 8] {
+        group.throughput(Throughput::Elements(OPS_PER_THREAD * threads as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{threads}_threads")),
+            &threads,
+            |b, &threads| {
+                b.iter_custom(|iters| {
+                    (0..iters)
+                        .map(|_| run_mix(&populated(&keys), &keys, threads, OPS_PER_THREAD))
+                        .sum()
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, read_heavy_mix);
+criterion_main!(benches);
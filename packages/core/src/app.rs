@@ -0,0 +1,221 @@
+//! The whole HTTP app: every route, the shared state its handlers read, and
+//! the middleware stack around them.
+//!
+//! The binary and the route tests both build the app here, so a test request
+//! passes through the same layers, in the same order, as a production one.
+//! [`AppConfig`] holds everything that is optional; `main` fills it from the
+//! environment, and a test sets only what it needs:
+//!
+//! ```ignore
+//! let app = build_router(
+//!     AppConfig { warm_cache: Some(cache), ..AppConfig::default() },
+//!     Arc::new(HorizonClient::new(horizon_url)),
+//! );
+//! ```
+
+use axum::{
+    Extension, Router,
+    http::{HeaderValue, Method, header},
+    middleware::{from_fn, from_fn_with_state},
+};
+use std::sync::Arc;
+use tower_http::{
+    compression::CompressionLayer,
+    cors::{AllowOrigin, CorsLayer},
+};
+use utoipa::OpenApi;
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::middleware::audit::{AuditContext, audit_middleware};
+use crate::middleware::envelope::{EnvelopeConfig, envelope_middleware};
+use crate::middleware::fields::fields_middleware;
+use crate::middleware::jsonapi::jsonapi_middleware;
+use crate::middleware::msgpack::msgpack_middleware;
+use crate::middleware::rate_limit::{RateLimit, rate_limit_middleware};
+use crate::middleware::receipt::{ReceiptContext, receipt_middleware};
+use crate::middleware::request_id::request_id_middleware;
+use crate::middleware::slo::slo_middleware;
+use crate::middleware::timeout::{TimeoutConfig, timeout_middleware};
+use crate::routes::{self, ApiDoc, admin::AdminToken};
+use crate::services::blocklist::BlocklistLoader;
+use crate::services::consistency::ConsistencyStatus;
+use crate::services::contract_labels::ContractLabels;
+use crate::services::exports::ExportContext;
+use crate::services::faucet::Faucet;
+use crate::services::horizon_api::HorizonApi;
+use crate::services::ingestion::IngestionStatus;
+use crate::services::market::MarketCache;
+use crate::services::network_lookup::NetworkLookup;
+use crate::services::notice::NoticeBoard;
+use crate::services::operation_cache::OperationCache;
+use crate::services::pending::SubmissionStatusSource;
+use crate::services::provider::LedgerDataProvider;
+use crate::services::slo::SloTracker;
+use crate::services::storage::ExplanationStore;
+use crate::services::submit::SubmitConfig;
+use crate::services::summarizer::SummarizerContext;
+use crate::services::warmup::WarmCache;
+
+/// The origin browsers may call the API from when `CORS_ORIGIN` is unset.
+pub const DEFAULT_CORS_ORIGIN: &str = "http://localhost:3000";
+
+/// What the app is built with beyond its routes. Every `None` leaves the
+/// feature off: its routes answer as they do when it is not configured, and
+/// its middleware is not installed.
+#[derive(Clone)]
+pub struct AppConfig {
+    /// The browser origin CORS allows.
+    pub cors_origin: HeaderValue,
+    pub timeouts: TimeoutConfig,
+    pub rate_limit: Option<RateLimit>,
+    /// `None` serves bare payloads, as `RESPONSE_ENVELOPE=false` does.
+    pub envelope: Option<EnvelopeConfig>,
+    pub provider: Option<Arc<dyn LedgerDataProvider>>,
+    pub submit: Option<SubmitConfig>,
+    pub faucet: Option<Arc<dyn Faucet>>,
+    pub submissions: Option<Arc<dyn SubmissionStatusSource>>,
+    pub network_lookup: Option<NetworkLookup>,
+    pub index: Option<Arc<ExplanationStore>>,
+    pub ingestion: Option<Arc<IngestionStatus>>,
+    pub consistency: Option<Arc<ConsistencyStatus>>,
+    pub warm_cache: Option<Arc<WarmCache>>,
+    pub operation_cache: Option<Arc<OperationCache>>,
+    pub market_cache: Option<Arc<MarketCache>>,
+    pub audit: Option<AuditContext>,
+    pub exports: Option<ExportContext>,
+    pub summarizer: Option<SummarizerContext>,
+    pub blocklist: Option<BlocklistLoader>,
+    pub admin_token: Option<AdminToken>,
+    pub receipts: Option<ReceiptContext>,
+    pub notices: Option<Arc<NoticeBoard>>,
+    pub contract_labels: Option<Arc<ContractLabels>>,
+    pub slo: Option<Arc<SloTracker>>,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            cors_origin: HeaderValue::from_static(DEFAULT_CORS_ORIGIN),
+            timeouts: TimeoutConfig::default(),
+            rate_limit: None,
+            envelope: None,
+            provider: None,
+            submit: None,
+            faucet: None,
+            submissions: None,
+            network_lookup: None,
+            index: None,
+            ingestion: None,
+            consistency: None,
+            warm_cache: None,
+            operation_cache: None,
+            market_cache: None,
+            audit: None,
+            exports: None,
+            summarizer: None,
+            blocklist: None,
+            admin_token: None,
+            receipts: None,
+            notices: None,
+            contract_labels: None,
+            slo: None,
+        }
+    }
+}
+
+/// Layer `value`, when there is one, for handlers to extract.
+fn with_extension<T: Clone + Send + Sync + 'static>(app: Router, value: Option<T>) -> Router {
+    match value {
+        Some(value) => app.layer(Extension(value)),
+        None => app,
+    }
+}
+
+/// Every route, served from `state` (Horizon normally, fixtures in mock
+/// mode), with `config` and the middleware stack layered on.
+pub fn build_router<H: HorizonApi + 'static>(config: AppConfig, state: Arc<H>) -> Router {
+    // SwaggerUi::url() registers /openapi.json internally.
+    // Do NOT add a separate .route("/openapi.json") or Axum will panic
+    // with "Overlapping method route" at startup.
+    let mut app = routes::api_routes()
+        .with_state(state)
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", ApiDoc::openapi()));
+
+    app = with_extension(app, config.provider);
+    app = with_extension(app, config.submit);
+    app = with_extension(app, config.faucet);
+    app = with_extension(app, config.submissions);
+    app = with_extension(app, config.network_lookup);
+    app = with_extension(app, config.consistency);
+    app = with_extension(app, config.index);
+    app = with_extension(app, config.ingestion);
+    app = with_extension(app, config.warm_cache);
+    app = with_extension(app, config.operation_cache);
+    app = with_extension(app, config.market_cache);
+    app = with_extension(
+        app,
+        config.audit.as_ref().map(|audit| Arc::clone(&audit.store)),
+    );
+    app = with_extension(app, config.exports);
+    app = with_extension(app, config.summarizer);
+    app = with_extension(app, config.blocklist);
+    app = with_extension(app, config.admin_token);
+    app = with_extension(
+        app,
+        config
+            .receipts
+            .as_ref()
+            .map(|receipts| Arc::clone(&receipts.signer)),
+    );
+    app = with_extension(app, config.notices);
+    app = with_extension(app, config.contract_labels);
+
+    // Inside the envelope so timeout errors are wrapped like any other.
+    app = app.layer(from_fn_with_state(config.timeouts, timeout_middleware));
+    // Outside the timeout so timed-out requests count against their SLOs.
+    if let Some(tracker) = config.slo {
+        app = app
+            .layer(from_fn_with_state(Arc::clone(&tracker), slo_middleware))
+            .layer(Extension(tracker));
+    }
+    // Outside the timeout so timed-out requests are audited with their 504.
+    if let Some(context) = config.audit {
+        app = app.layer(from_fn_with_state(context, audit_middleware));
+    }
+
+    // Inside the envelope so only the payload is trimmed, and inside
+    // receipts so the signed body is the one the client receives.
+    app = app.layer(from_fn(fields_middleware));
+
+    // Inside the envelope so the signed body is the bare explanation.
+    if let Some(context) = config.receipts {
+        app = app.layer(from_fn_with_state(context, receipt_middleware));
+    }
+
+    // Outside receipts so they sign the bare explanation; JSON:API bodies
+    // are not JSON to the envelope, so they are never wrapped.
+    app = app.layer(from_fn(jsonapi_middleware));
+
+    if let Some(envelope) = config.envelope {
+        app = app.layer(from_fn_with_state(envelope, envelope_middleware));
+    }
+
+    // Outside the envelope so the wrapped body is what gets transcoded,
+    // and inside compression so MessagePack bodies are compressed too.
+    app = app
+        .layer(from_fn(msgpack_middleware))
+        .layer(CompressionLayer::new().gzip(true).br(true));
+
+    // Outside everything that does work for a request, so a throttled client
+    // costs nothing, and inside CORS so browsers can read the 429.
+    if let Some(rate_limit) = config.rate_limit {
+        app = app.layer(from_fn_with_state(rate_limit, rate_limit_middleware));
+    }
+
+    let cors = CorsLayer::new()
+        .allow_origin(AllowOrigin::exact(config.cors_origin))
+        .allow_methods([Method::GET, Method::POST, Method::OPTIONS])
+        .allow_headers([header::CONTENT_TYPE, header::ACCEPT]);
+
+    app.layer(cors).layer(from_fn(request_id_middleware))
+}
@@ -17,12 +17,50 @@ impl StellarNetwork {
         }
     }
 
+    /// Every network `STELLAR_NETWORK` can select.
+    pub fn all() -> [StellarNetwork; 2] {
+        [StellarNetwork::Public, StellarNetwork::Testnet]
+    }
+
+    /// Lowercase name, as accepted by `STELLAR_NETWORK`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            StellarNetwork::Public => "public",
+            StellarNetwork::Testnet => "testnet",
+        }
+    }
+
+    pub fn passphrase(&self) -> &'static str {
+        match self {
+            StellarNetwork::Public => "Public Global Stellar Network ; September 2015",
+            StellarNetwork::Testnet => "Test SDF Network ; September 2015",
+        }
+    }
+
     pub fn horizon_url(&self) -> &'static str {
         match self {
             StellarNetwork::Public => "https://horizon.stellar.org",
             StellarNetwork::Testnet => "https://horizon-testnet.stellar.org",
         }
     }
+
+    /// SDF's friendbot, which funds new accounts with test lumens. The
+    /// public network has none.
+    pub fn friendbot_url(&self) -> Option<&'static str> {
+        match self {
+            StellarNetwork::Public => None,
+            StellarNetwork::Testnet => Some("https://friendbot.stellar.org"),
+        }
+    }
+
+    /// Default stellar-rpc endpoint. SDF runs no public-network RPC, so the
+    /// public network has no default.
+    pub fn rpc_url(&self) -> Option<&'static str> {
+        match self {
+            StellarNetwork::Public => None,
+            StellarNetwork::Testnet => Some("https://soroban-testnet.stellar.org"),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -45,12 +83,48 @@ mod tests {
         assert_eq!(StellarNetwork::from_env(), StellarNetwork::Testnet);
     }
 
+    #[test]
+    fn all_networks_have_distinct_names() {
+        let names = StellarNetwork::all().map(|network| network.name());
+        assert_eq!(names, ["public", "testnet"]);
+    }
+
     #[test]
     fn public_network_url() {
         let net = StellarNetwork::Public;
         assert_eq!(net.horizon_url(), "https://horizon.stellar.org");
     }
 
+    #[test]
+    fn network_passphrases() {
+        assert_eq!(
+            StellarNetwork::Public.passphrase(),
+            "Public Global Stellar Network ; September 2015"
+        );
+        assert_eq!(
+            StellarNetwork::Testnet.passphrase(),
+            "Test SDF Network ; September 2015"
+        );
+    }
+
+    #[test]
+    fn rpc_urls() {
+        assert_eq!(StellarNetwork::Public.rpc_url(), None);
+        assert_eq!(
+            StellarNetwork::Testnet.rpc_url(),
+            Some("https://soroban-testnet.stellar.org")
+        );
+    }
+
+    #[test]
+    fn friendbot_urls() {
+        assert_eq!(StellarNetwork::Public.friendbot_url(), None);
+        assert_eq!(
+            StellarNetwork::Testnet.friendbot_url(),
+            Some("https://friendbot.stellar.org")
+        );
+    }
+
     #[test]
     fn testnet_network_url() {
         let net = StellarNetwork::Testnet;
@@ -1,19 +1,126 @@
-use crate::explain::transaction::ExplainError;
+use crate::explain::transaction::{ExplainError, format_ledger_time};
+use crate::services::circuit::retry_after_seconds;
 use axum::{
     Json,
-    http::StatusCode,
+    http::{HeaderValue, StatusCode, header},
     response::{IntoResponse, Response},
 };
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::time::Duration;
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct ApiError {
     pub error: ErrorBody,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct ErrorBody {
-    pub code: String,
+    pub code: ErrorCode,
+    pub message: String,
+    /// Structured context for the error, e.g. where a closed account was merged into.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<serde_json::Value>,
+}
+
+/// Every value of `error.code`. Clients should branch on the code; messages
+/// are for people and may change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// The request is malformed, e.g. an invalid hash or query parameter.
+    BadRequest,
+    /// The request lacks valid credentials for an operator endpoint.
+    Unauthorized,
+    /// This deployment's policy does not allow explaining the requested
+    /// account, asset, or transaction.
+    PolicyBlocked,
+    /// The transaction, account, or other resource does not exist.
+    NotFound,
+    /// The account was merged into another account; `details` says which.
+    AccountMerged,
+    /// The transaction is in a ledger the data source has not indexed yet;
+    /// `details` says which. Retry shortly.
+    TransactionPending,
+    /// The Stellar network is rate limiting this server. Retry later.
+    RateLimited,
+    /// The request asks about an operation this server cannot explain.
+    UnsupportedOperation,
+    /// The Stellar network failed or returned something unreadable.
+    UpstreamError,
+    /// The Stellar network is temporarily unavailable; `details` says when
+    /// to retry.
+    UpstreamUnavailable,
+    /// The Stellar network did not answer in time; `details` says how long
+    /// the request waited.
+    GatewayTimeout,
+    InternalError,
+}
+
+impl ErrorCode {
+    pub const ALL: [ErrorCode; 12] = [
+        ErrorCode::BadRequest,
+        ErrorCode::Unauthorized,
+        ErrorCode::PolicyBlocked,
+        ErrorCode::NotFound,
+        ErrorCode::AccountMerged,
+        ErrorCode::TransactionPending,
+        ErrorCode::RateLimited,
+        ErrorCode::UnsupportedOperation,
+        ErrorCode::UpstreamError,
+        ErrorCode::UpstreamUnavailable,
+        ErrorCode::GatewayTimeout,
+        ErrorCode::InternalError,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::BadRequest => "BAD_REQUEST",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::PolicyBlocked => "POLICY_BLOCKED",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::AccountMerged => "ACCOUNT_MERGED",
+            ErrorCode::TransactionPending => "TRANSACTION_PENDING",
+            ErrorCode::RateLimited => "RATE_LIMITED",
+            ErrorCode::UnsupportedOperation => "UNSUPPORTED_OPERATION",
+            ErrorCode::UpstreamError => "UPSTREAM_ERROR",
+            ErrorCode::UpstreamUnavailable => "UPSTREAM_UNAVAILABLE",
+            ErrorCode::GatewayTimeout => "GATEWAY_TIMEOUT",
+            ErrorCode::InternalError => "INTERNAL_ERROR",
+        }
+    }
+
+    /// The HTTP status every error with this code is returned with.
+    pub fn status(self) -> StatusCode {
+        match self {
+            ErrorCode::BadRequest => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::PolicyBlocked => StatusCode::FORBIDDEN,
+            ErrorCode::NotFound | ErrorCode::AccountMerged | ErrorCode::TransactionPending => {
+                StatusCode::NOT_FOUND
+            }
+            ErrorCode::RateLimited => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::UnsupportedOperation => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorCode::UpstreamError => StatusCode::BAD_GATEWAY,
+            ErrorCode::UpstreamUnavailable => StatusCode::SERVICE_UNAVAILABLE,
+            ErrorCode::GatewayTimeout => StatusCode::GATEWAY_TIMEOUT,
+            ErrorCode::InternalError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// One query parameter that was rejected, in `details.fields` of a
+/// `BAD_REQUEST` error.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct FieldError {
+    /// The parameter's name, e.g. "limit".
+    pub field: String,
     pub message: String,
 }
 
@@ -22,54 +129,207 @@ pub enum HorizonError {
     NetworkError,
     TransactionNotFound,
     AccountNotFound,
+    LedgerNotFound,
+    ClaimableBalanceNotFound,
+    LiquidityPoolNotFound,
+    OfferNotFound,
     InvalidResponse,
+    /// Horizon answered 429 Too Many Requests.
+    RateLimited,
+    /// Horizon has been failing and the circuit breaker is open.
+    CircuitOpen {
+        retry_after: Duration,
+    },
 }
 
 #[derive(Debug)]
 pub enum AppError {
     NotFound(String),
+    /// The account existed once but was merged into another account.
+    AccountMerged {
+        address: String,
+        merged_into: String,
+        closed_at: Option<String>,
+    },
+    /// The transaction is in a ledger but not yet indexed.
+    TransactionPending {
+        hash: String,
+        ledger: Option<u64>,
+    },
+    /// The transaction is in none of the ledgers that were checked.
+    TransactionNotIncluded {
+        hash: String,
+        latest_ledger: Option<u64>,
+        oldest_ledger: Option<u64>,
+    },
+    /// The transaction is not on `network` but is on each of `found_on`.
+    TransactionOnOtherNetwork {
+        hash: String,
+        network: String,
+        found_on: Vec<String>,
+    },
     BadRequest(String),
+    /// Query parameters that failed to parse or validate, one entry per
+    /// offending parameter.
+    InvalidQuery(Vec<FieldError>),
+    Unauthorized(String),
+    /// Refused by the deployment's allowlist/blocklist policy.
+    PolicyBlocked(String),
+    RateLimited(String),
+    UnsupportedOperation(String),
     UpstreamFailure(String),
+    /// The upstream is temporarily unavailable; clients should retry later.
+    Unavailable {
+        message: String,
+        retry_after_secs: u64,
+    },
+    /// The request ran past its deadline waiting on the upstream.
+    GatewayTimeout {
+        elapsed_ms: u64,
+        timeout_ms: u64,
+    },
     Internal(String),
 }
 
 impl AppError {
-    fn to_api_error(&self) -> ApiError {
+    pub fn code(&self) -> ErrorCode {
         match self {
-            AppError::NotFound(msg) => ApiError {
-                error: ErrorBody {
-                    code: "NOT_FOUND".into(),
-                    message: msg.clone(),
-                },
-            },
-            AppError::BadRequest(msg) => ApiError {
-                error: ErrorBody {
-                    code: "BAD_REQUEST".into(),
-                    message: msg.clone(),
-                },
-            },
-            AppError::UpstreamFailure(msg) => ApiError {
-                error: ErrorBody {
-                    code: "UPSTREAM_ERROR".into(),
-                    message: msg.clone(),
-                },
-            },
-            AppError::Internal(msg) => ApiError {
-                error: ErrorBody {
-                    code: "INTERNAL_ERROR".into(),
-                    message: msg.clone(),
-                },
+            AppError::NotFound(_) => ErrorCode::NotFound,
+            AppError::AccountMerged { .. } => ErrorCode::AccountMerged,
+            AppError::TransactionPending { .. } => ErrorCode::TransactionPending,
+            AppError::TransactionNotIncluded { .. }
+            | AppError::TransactionOnOtherNetwork { .. } => ErrorCode::NotFound,
+            AppError::BadRequest(_) | AppError::InvalidQuery(_) => ErrorCode::BadRequest,
+            AppError::Unauthorized(_) => ErrorCode::Unauthorized,
+            AppError::PolicyBlocked(_) => ErrorCode::PolicyBlocked,
+            AppError::RateLimited(_) => ErrorCode::RateLimited,
+            AppError::UnsupportedOperation(_) => ErrorCode::UnsupportedOperation,
+            AppError::UpstreamFailure(_) => ErrorCode::UpstreamError,
+            AppError::Unavailable { .. } => ErrorCode::UpstreamUnavailable,
+            AppError::GatewayTimeout { .. } => ErrorCode::GatewayTimeout,
+            AppError::Internal(_) => ErrorCode::InternalError,
+        }
+    }
+
+    pub(crate) fn to_api_error(&self) -> ApiError {
+        let (message, details) = match self {
+            AppError::NotFound(msg)
+            | AppError::BadRequest(msg)
+            | AppError::Unauthorized(msg)
+            | AppError::PolicyBlocked(msg)
+            | AppError::RateLimited(msg)
+            | AppError::UnsupportedOperation(msg)
+            | AppError::UpstreamFailure(msg)
+            | AppError::Internal(msg) => (msg.clone(), None),
+            AppError::InvalidQuery(fields) => {
+                let message = fields
+                    .iter()
+                    .map(|field| field.message.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                (message, Some(serde_json::json!({ "fields": fields })))
+            }
+            AppError::AccountMerged {
+                address,
+                merged_into,
+                closed_at,
+            } => {
+                let message = match closed_at {
+                    Some(date) => format!(
+                        "This account was closed on {}: it was merged into {merged_into} and no longer exists.",
+                        format_ledger_time(date)
+                    ),
+                    None => format!(
+                        "This account was closed: it was merged into {merged_into} and no longer exists."
+                    ),
+                };
+                let details = serde_json::json!({
+                    "address": address,
+                    "account_status": "merged",
+                    "merged_into": merged_into,
+                    "closed_at": closed_at,
+                });
+                (message, Some(details))
+            }
+            AppError::TransactionPending { hash, ledger } => {
+                let message = match ledger {
+                    Some(ledger) => format!(
+                        "This transaction was included in ledger {ledger} but has not been indexed yet. Try again in a few seconds."
+                    ),
+                    None => "This transaction was included in a ledger but has not been indexed yet. Try again in a few seconds.".to_string(),
+                };
+                let details = serde_json::json!({
+                    "hash": hash,
+                    "submission_status": "included",
+                    "ledger": ledger,
+                });
+                (message, Some(details))
+            }
+            AppError::TransactionNotIncluded {
+                hash,
+                latest_ledger,
+                oldest_ledger,
+            } => {
+                let message = match latest_ledger {
+                    Some(latest) => format!(
+                        "Transaction not found on the Stellar network: it is not in any ledger up to {latest}. A transaction that was just submitted may still be waiting to be included."
+                    ),
+                    None => "Transaction not found on the Stellar network. A transaction that was just submitted may still be waiting to be included.".to_string(),
+                };
+                let details = serde_json::json!({
+                    "hash": hash,
+                    "submission_status": "not_included",
+                    "latest_ledger": latest_ledger,
+                    "oldest_ledger": oldest_ledger,
+                });
+                (message, Some(details))
+            }
+            AppError::TransactionOnOtherNetwork {
+                hash,
+                network,
+                found_on,
+            } => {
+                let message = format!(
+                    "This transaction is on {}, not {network}, the network this server explains.",
+                    found_on.join(" and ")
+                );
+                let details = serde_json::json!({
+                    "hash": hash,
+                    "network": network,
+                    "found_on": found_on,
+                });
+                (message, Some(details))
+            }
+            AppError::Unavailable {
+                message,
+                retry_after_secs,
+            } => (
+                message.clone(),
+                Some(serde_json::json!({ "retry_after_seconds": retry_after_secs })),
+            ),
+            AppError::GatewayTimeout {
+                elapsed_ms,
+                timeout_ms,
+            } => (
+                "The Stellar network took too long to respond. Please try again.".to_string(),
+                Some(serde_json::json!({
+                    "elapsed_ms": elapsed_ms,
+                    "timeout_ms": timeout_ms,
+                })),
+            ),
+        };
+
+        ApiError {
+            error: ErrorBody {
+                code: self.code(),
+                message,
+                details,
             },
         }
     }
 
     pub fn status_code(&self) -> StatusCode {
-        match self {
-            AppError::NotFound(_) => StatusCode::NOT_FOUND,
-            AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
-            AppError::UpstreamFailure(_) => StatusCode::BAD_GATEWAY,
-            AppError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
-        }
+        self.code().status()
     }
 }
 
@@ -77,7 +337,16 @@ impl IntoResponse for AppError {
     fn into_response(self) -> Response {
         let status = self.status_code();
         let body = Json(self.to_api_error());
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        if let AppError::Unavailable {
+            retry_after_secs, ..
+        } = self
+        {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+        }
+        response
     }
 }
 
@@ -90,12 +359,32 @@ impl From<HorizonError> for AppError {
             HorizonError::AccountNotFound => {
                 AppError::NotFound("Account not found on the Stellar network.".into())
             }
+            HorizonError::LedgerNotFound => {
+                AppError::NotFound("Ledger not found on the Stellar network.".into())
+            }
+            HorizonError::ClaimableBalanceNotFound => AppError::NotFound(
+                "Claimable balance not found. It may have already been claimed.".into(),
+            ),
+            HorizonError::LiquidityPoolNotFound => {
+                AppError::NotFound("Liquidity pool not found on the Stellar network.".into())
+            }
+            HorizonError::OfferNotFound => {
+                AppError::NotFound("Offer not found. It may have been filled or cancelled.".into())
+            }
             HorizonError::NetworkError => AppError::UpstreamFailure(
                 "Unable to reach Stellar network. Please try again later.".into(),
             ),
             HorizonError::InvalidResponse => AppError::UpstreamFailure(
                 "Received an invalid response from the Stellar network.".into(),
             ),
+            HorizonError::RateLimited => AppError::RateLimited(
+                "The Stellar network is rate limiting requests. Please try again shortly.".into(),
+            ),
+            HorizonError::CircuitOpen { retry_after } => AppError::Unavailable {
+                message: "The Stellar network is temporarily unavailable. Please retry shortly."
+                    .into(),
+                retry_after_secs: retry_after_seconds(retry_after),
+            },
         }
     }
 }
@@ -109,3 +398,33 @@ impl From<ExplainError> for AppError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_serialize_as_their_names() {
+        for code in ErrorCode::ALL {
+            assert_eq!(
+                serde_json::to_value(code).unwrap(),
+                serde_json::Value::from(code.as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn readme_catalogs_every_code() {
+        let readme = include_str!("../README.md");
+        for code in ErrorCode::ALL {
+            assert!(readme.contains(&format!("| `{code}` |")), "{code}");
+        }
+    }
+
+    #[test]
+    fn rate_limited_upstream_is_429() {
+        let err = AppError::from(HorizonError::RateLimited);
+        assert_eq!(err.status_code(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(err.to_api_error().error.code, ErrorCode::RateLimited);
+    }
+}
@@ -1,5 +1,18 @@
-use crate::models::account::Account;
-use serde::Serialize;
+use crate::explain::format::{count, format_amount};
+use crate::explain::glossary::{GlossaryTerm, terms_in};
+use crate::explain::issuer_label::LabelVerification;
+use crate::explain::templates;
+use crate::explain::transaction::explain_operation;
+use crate::models::account::{Account, Balance};
+use crate::models::amount::Amount;
+use crate::models::asset::TomlIdentity;
+use crate::models::operation::Operation;
+use crate::services::labels::resolve_label;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Horizon reports this limit for trust lines created without an explicit limit.
+const MAX_TRUST_LIMIT: &str = "922337203685.4775807";
 
 #[derive(Debug, Serialize)]
 pub struct AccountExplanation {
@@ -9,16 +22,47 @@ pub struct AccountExplanation {
     pub signer_count: u32,
     pub home_domain: Option<String>,
     pub org_name: Option<String>,
+    /// Whether the home domain's stellar.toml lists this account, backing
+    /// `org_name`. None when there is no `org_name`.
+    pub org_name_verification: Option<LabelVerification>,
     pub flag_descriptions: Vec<String>,
+    /// One entry per non-native balance (trust line or liquidity pool share).
+    pub assets: Vec<AssetBalanceExplanation>,
+    /// Stellar terms the descriptions above mention, with definitions.
+    pub terms: Vec<GlossaryTerm>,
+}
+
+/// Explanation of a single non-native balance held by an account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct AssetBalanceExplanation {
+    /// Plain-English description of this holding.
+    pub summary: String,
+    /// Asset code, or "Liquidity pool shares" for pool share balances.
+    pub asset_code: String,
+    pub asset_issuer: Option<String>,
+    /// Known label for the issuer account, e.g. "USDC Issuer (Circle)".
+    pub issuer_label: Option<String>,
+    pub balance: String,
+    /// Trust limit as reported by Horizon. None when the limit is the protocol maximum.
+    pub limit: Option<String>,
+    /// Whether the issuer has authorized this trust line.
+    pub authorized: bool,
+    pub buying_liabilities: String,
+    pub selling_liabilities: String,
+    /// Whether the asset is on the deployment's spam blocklist.
+    #[serde(default)]
+    pub blocklisted: bool,
 }
 
 pub fn explain_account(account: &Account) -> AccountExplanation {
-    explain_account_with_org_name(account, None)
+    explain_account_with_toml(account, None)
 }
 
-pub fn explain_account_with_org_name(
+/// Explain an account, naming its operator from `toml`, its home domain's
+/// stellar.toml.
+pub fn explain_account_with_toml(
     account: &Account,
-    org_name: Option<String>,
+    toml: Option<&TomlIdentity>,
 ) -> AccountExplanation {
     let xlm_balance = account
         .balances
@@ -35,57 +79,75 @@ pub fn explain_account_with_org_name(
 
     let asset_count = other_assets.len();
 
-    let base_summary = if asset_count == 0 {
-        format!(
-            "This account holds {} XLM. It has {} signer{}.",
-            xlm_balance,
-            account.num_signers,
-            if account.num_signers == 1 { "" } else { "s" },
-        )
+    let xlm = format_amount(&xlm_balance);
+    let signers = count(account.num_signers as usize, "signer", "signers");
+    let mut summary = if asset_count == 0 {
+        templates::render("account.summary", &[("xlm", &xlm), ("signers", &signers)])
     } else {
-        format!(
-            "This account holds {} XLM and {} other asset{}. It has {} signer{}.",
-            xlm_balance,
-            asset_count,
-            if asset_count == 1 { "" } else { "s" },
-            account.num_signers,
-            if account.num_signers == 1 { "" } else { "s" },
+        templates::render(
+            "account.summary_with_assets",
+            &[
+                ("xlm", &xlm),
+                ("assets", &count(asset_count, "other asset", "other assets")),
+                ("signers", &signers),
+            ],
         )
     };
 
     let home_domain = account.home_domain.clone();
-    let org_name = org_name.filter(|value| !value.trim().is_empty());
+    let org_name = toml
+        .and_then(|toml| toml.org_name.clone())
+        .filter(|value| !value.trim().is_empty());
+    let org_name_verification = toml
+        .filter(|_| org_name.is_some())
+        .map(|toml| LabelVerification::of(toml, &account.account_id));
 
-    let summary = match (&home_domain, &org_name) {
-        (Some(domain), Some(name)) => {
-            format!("{base_summary} Account operated by {name} ({domain}).")
+    let operator = match (&home_domain, &org_name) {
+        (Some(domain), Some(name))
+            if org_name_verification == Some(LabelVerification::Verified) =>
+        {
+            Some(templates::render(
+                "account.operator",
+                &[("name", name), ("domain", domain)],
+            ))
         }
-        (Some(domain), None) => format!("{base_summary} Home domain: {domain}."),
-        (None, _) => base_summary,
+        (Some(domain), Some(name)) => Some(templates::render(
+            "account.operator_unlisted",
+            &[("name", name), ("domain", domain)],
+        )),
+        (Some(domain), None) => Some(templates::render(
+            "account.home_domain",
+            &[("domain", domain)],
+        )),
+        (None, _) => None,
     };
-
-    let mut flag_descriptions = Vec::new();
-    if account.flags.auth_required {
-        flag_descriptions.push(
-            "Auth required: accounts must be authorized before holding this asset.".to_string(),
-        );
-    }
-    if account.flags.auth_revocable {
-        flag_descriptions.push(
-            "Auth revocable: the issuer can freeze this asset in a holder's account.".to_string(),
-        );
-    }
-    if account.flags.auth_immutable {
-        flag_descriptions.push(
-            "Auth immutable: account flags and signers can no longer be changed.".to_string(),
-        );
-    }
-    if account.flags.auth_clawback_enabled {
-        flag_descriptions.push(
-            "Clawback enabled: the issuer can claw back this asset from holders.".to_string(),
-        );
+    if let Some(operator) = operator {
+        summary.push(' ');
+        summary.push_str(&operator);
     }
 
+    let flag_descriptions = [
+        (account.flags.auth_required, "account.flag.auth_required"),
+        (account.flags.auth_revocable, "account.flag.auth_revocable"),
+        (account.flags.auth_immutable, "account.flag.auth_immutable"),
+        (
+            account.flags.auth_clawback_enabled,
+            "account.flag.clawback_enabled",
+        ),
+    ]
+    .into_iter()
+    .filter(|(set, _)| *set)
+    .map(|(_, key)| templates::render(key, &[]))
+    .collect::<Vec<_>>();
+
+    let assets: Vec<AssetBalanceExplanation> =
+        other_assets.into_iter().map(explain_balance).collect();
+    let terms = terms_in(
+        std::iter::once(summary.as_str())
+            .chain(flag_descriptions.iter().map(String::as_str))
+            .chain(assets.iter().map(|asset| asset.summary.as_str())),
+    );
+
     AccountExplanation {
         summary,
         xlm_balance,
@@ -93,10 +155,163 @@ pub fn explain_account_with_org_name(
         signer_count: account.num_signers,
         home_domain,
         org_name,
+        org_name_verification,
         flag_descriptions,
+        assets,
+        terms,
     }
 }
 
+/// Explain a single non-native balance: trust limit, authorization, and liabilities.
+pub fn explain_balance(balance: &Balance) -> AssetBalanceExplanation {
+    let buying_liabilities = balance
+        .buying_liabilities
+        .clone()
+        .unwrap_or_else(|| "0".to_string());
+    let selling_liabilities = balance
+        .selling_liabilities
+        .clone()
+        .unwrap_or_else(|| "0".to_string());
+
+    if balance.asset_type == "liquidity_pool_shares" {
+        let pool = balance.liquidity_pool_id.as_deref().unwrap_or("Unknown");
+        return AssetBalanceExplanation {
+            summary: templates::render(
+                "account.pool_shares",
+                &[("amount", &format_amount(&balance.balance)), ("pool", pool)],
+            ),
+            asset_code: "Liquidity pool shares".to_string(),
+            asset_issuer: None,
+            issuer_label: None,
+            balance: balance.balance.clone(),
+            limit: balance.limit.clone().filter(|l| l != MAX_TRUST_LIMIT),
+            authorized: balance.is_authorized.unwrap_or(true),
+            buying_liabilities,
+            selling_liabilities,
+            blocklisted: false,
+        };
+    }
+
+    let asset_code = balance
+        .asset_code
+        .clone()
+        .unwrap_or_else(|| "Unknown".to_string());
+    let issuer_label = balance
+        .asset_issuer
+        .as_deref()
+        .and_then(resolve_label)
+        .map(|label| label.to_string());
+    let limit = balance.limit.clone().filter(|l| l != MAX_TRUST_LIMIT);
+    let authorized = balance.is_authorized.unwrap_or(true);
+
+    let amount = format_amount(&balance.balance);
+    let mut sentences = vec![match (&balance.asset_issuer, &issuer_label) {
+        (Some(issuer), label) => {
+            let issuer = match label {
+                Some(label) => format!("{label} ({issuer})"),
+                None => issuer.clone(),
+            };
+            templates::render(
+                "account.balance_issued",
+                &[
+                    ("amount", &amount),
+                    ("asset", &asset_code),
+                    ("issuer", &issuer),
+                ],
+            )
+        }
+        (None, _) => templates::render(
+            "account.balance",
+            &[("amount", &amount), ("asset", &asset_code)],
+        ),
+    }];
+
+    sentences.push(match &limit {
+        Some(limit) => templates::render(
+            "account.trust_limit",
+            &[("limit", &format_amount(limit)), ("asset", &asset_code)],
+        ),
+        None => templates::render("account.no_trust_limit", &[]),
+    });
+
+    if !authorized {
+        if balance.is_authorized_to_maintain_liabilities == Some(true) {
+            sentences.push(templates::render("account.trust_line_restricted", &[]));
+        } else {
+            sentences.push(templates::render("account.trust_line_unauthorized", &[]));
+        }
+    }
+
+    for (liabilities, key) in [
+        (&buying_liabilities, "account.buying_liabilities"),
+        (&selling_liabilities, "account.selling_liabilities"),
+    ] {
+        if !is_zero_amount(liabilities) {
+            sentences.push(templates::render(
+                key,
+                &[
+                    ("amount", &format_amount(liabilities)),
+                    ("asset", &asset_code),
+                ],
+            ));
+        }
+    }
+    let summary = sentences.join(" ");
+
+    AssetBalanceExplanation {
+        summary,
+        asset_code,
+        asset_issuer: balance.asset_issuer.clone(),
+        issuer_label,
+        balance: balance.balance.clone(),
+        limit,
+        authorized,
+        buying_liabilities,
+        selling_liabilities,
+        blocklisted: false,
+    }
+}
+
+/// One of an account's latest operations, explained on its own.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct RecentOperationExplanation {
+    pub transaction_hash: String,
+    /// ISO 8601 close time of the ledger that included the operation.
+    pub created_at: Option<String>,
+    /// The Stellar operation type, e.g. "payment", "create_account".
+    #[serde(rename = "type")]
+    pub operation_type: String,
+    /// Stellar docs for this operation type.
+    #[serde(default)]
+    pub learn_more: Option<String>,
+    pub summary: String,
+    pub details: serde_json::Value,
+}
+
+pub fn explain_recent_operation(
+    transaction_hash: String,
+    created_at: Option<String>,
+    operation: &Operation,
+) -> RecentOperationExplanation {
+    // Fee context belongs to whole transactions, not account history.
+    let explained = explain_operation(0, operation, 0, None);
+    RecentOperationExplanation {
+        transaction_hash,
+        created_at,
+        operation_type: explained.operation_type,
+        learn_more: explained.learn_more,
+        summary: explained.summary,
+        details: explained.details,
+    }
+}
+
+fn is_zero_amount(amount: &str) -> bool {
+    amount
+        .parse::<Amount>()
+        .map(Amount::is_zero)
+        .unwrap_or(true)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -110,9 +325,8 @@ mod tests {
     ) -> Account {
         let mut balances = vec![Balance {
             asset_type: "native".to_string(),
-            asset_code: None,
-            asset_issuer: None,
             balance: xlm.to_string(),
+            ..Default::default()
         }];
         for i in 0..extra_assets {
             balances.push(Balance {
@@ -120,6 +334,7 @@ mod tests {
                 asset_code: Some(format!("ASSET{i}")),
                 asset_issuer: Some("GISSUER".to_string()),
                 balance: "10.0000000".to_string(),
+                ..Default::default()
             });
         }
         Account {
@@ -127,6 +342,9 @@ mod tests {
             account_id: "GTEST".to_string(),
             sequence: "1234".to_string(),
             num_signers,
+            signers: Vec::new(),
+            master_key_weight: 1,
+            thresholds: Default::default(),
             balances,
             flags: AccountFlags {
                 auth_required: false,
@@ -135,6 +353,7 @@ mod tests {
                 auth_clawback_enabled: false,
             },
             home_domain: home_domain.map(|s| s.to_string()),
+            memo_required: false,
         }
     }
 
@@ -144,7 +363,7 @@ mod tests {
         let explanation = explain_account(&account);
         assert_eq!(explanation.asset_count, 0);
         assert_eq!(explanation.signer_count, 1);
-        assert!(explanation.summary.contains("100.5000000 XLM"));
+        assert!(explanation.summary.contains("100.5 XLM"));
         assert!(!explanation.summary.contains("other asset"));
         assert!(explanation.home_domain.is_none());
         assert!(explanation.org_name.is_none());
@@ -155,7 +374,7 @@ mod tests {
         let account = mock_account("104.5000000", 2, 1, Some("stellar.org"));
         let explanation = explain_account(&account);
         assert_eq!(explanation.asset_count, 2);
-        assert!(explanation.summary.contains("104.5000000 XLM"));
+        assert!(explanation.summary.contains("104.5 XLM"));
         assert!(explanation.summary.contains("2 other assets"));
         assert!(explanation.summary.contains("Home domain: stellar.org."));
         assert_eq!(explanation.home_domain.as_deref(), Some("stellar.org"));
@@ -164,14 +383,38 @@ mod tests {
     #[test]
     fn test_summary_with_org_name_and_home_domain() {
         let account = mock_account("200.0000000", 0, 2, Some("anchorage.com"));
-        let explanation =
-            explain_account_with_org_name(&account, Some("Anchorage Digital".to_string()));
+        let toml = TomlIdentity {
+            org_name: Some("Anchorage Digital".to_string()),
+            accounts: vec![account.account_id.clone()],
+        };
+        let explanation = explain_account_with_toml(&account, Some(&toml));
         assert!(
             explanation
                 .summary
                 .contains("Account operated by Anchorage Digital (anchorage.com)")
         );
         assert_eq!(explanation.org_name.as_deref(), Some("Anchorage Digital"));
+        assert_eq!(
+            explanation.org_name_verification,
+            Some(LabelVerification::Verified)
+        );
+    }
+
+    #[test]
+    fn test_org_name_unlisted_account_is_only_claimed() {
+        let account = mock_account("200.0000000", 0, 2, Some("anchorage.com"));
+        let toml = TomlIdentity {
+            org_name: Some("Anchorage Digital".to_string()),
+            accounts: vec![],
+        };
+        let explanation = explain_account_with_toml(&account, Some(&toml));
+        assert!(explanation.summary.ends_with(
+            "Home domain anchorage.com names Anchorage Digital as its operator, but its stellar.toml does not list this account."
+        ));
+        assert_eq!(
+            explanation.org_name_verification,
+            Some(LabelVerification::Claimed)
+        );
     }
 
     #[test]
@@ -206,6 +449,9 @@ mod tests {
             account_id: "G1".to_string(),
             sequence: "0".to_string(),
             num_signers: 1,
+            signers: Vec::new(),
+            master_key_weight: 1,
+            thresholds: Default::default(),
             balances: vec![],
             flags: AccountFlags {
                 auth_required: false,
@@ -214,8 +460,110 @@ mod tests {
                 auth_clawback_enabled: false,
             },
             home_domain: None,
+            memo_required: false,
         };
         let explanation = explain_account(&account);
         assert_eq!(explanation.xlm_balance, "0");
     }
+
+    fn trust_line(code: &str, issuer: &str) -> Balance {
+        Balance {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some(code.to_string()),
+            asset_issuer: Some(issuer.to_string()),
+            balance: "250.0000000".to_string(),
+            limit: Some("1000.0000000".to_string()),
+            buying_liabilities: Some("0.0000000".to_string()),
+            selling_liabilities: Some("0.0000000".to_string()),
+            is_authorized: Some(true),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_assets_breakdown_has_one_entry_per_non_native_balance() {
+        let account = mock_account("10.0000000", 3, 1, None);
+        let explanation = explain_account(&account);
+        assert_eq!(explanation.assets.len(), 3);
+        assert_eq!(explanation.assets[0].asset_code, "ASSET0");
+    }
+
+    #[test]
+    fn test_balance_with_limit_and_known_issuer() {
+        let balance = trust_line(
+            "USDC",
+            "GUSDCISSUERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+        );
+        let explanation = explain_balance(&balance);
+        assert_eq!(explanation.limit.as_deref(), Some("1000.0000000"));
+        assert_eq!(
+            explanation.issuer_label.as_deref(),
+            Some("USDC Issuer (Circle)")
+        );
+        assert!(explanation.authorized);
+        assert!(explanation.summary.contains("Holds 250 USDC"));
+        assert!(explanation.summary.contains("USDC Issuer (Circle)"));
+        assert!(explanation.summary.contains("Trust limit: 1,000 USDC."));
+        assert!(!explanation.summary.contains("reserved"));
+    }
+
+    #[test]
+    fn test_balance_with_max_limit_reports_no_limit() {
+        let mut balance = trust_line("BTC", "GISSUER");
+        balance.limit = Some(MAX_TRUST_LIMIT.to_string());
+        let explanation = explain_balance(&balance);
+        assert!(explanation.limit.is_none());
+        assert!(explanation.summary.contains("No trust limit set."));
+    }
+
+    #[test]
+    fn test_unauthorized_balance() {
+        let mut balance = trust_line("EURT", "GISSUER");
+        balance.is_authorized = Some(false);
+        let explanation = explain_balance(&balance);
+        assert!(!explanation.authorized);
+        assert!(explanation.summary.contains("not authorized"));
+    }
+
+    #[test]
+    fn test_authorized_to_maintain_liabilities() {
+        let mut balance = trust_line("EURT", "GISSUER");
+        balance.is_authorized = Some(false);
+        balance.is_authorized_to_maintain_liabilities = Some(true);
+        let explanation = explain_balance(&balance);
+        assert!(explanation.summary.contains("existing offers remain"));
+    }
+
+    #[test]
+    fn test_balance_liabilities() {
+        let mut balance = trust_line("USDC", "GISSUER");
+        balance.buying_liabilities = Some("5.0000000".to_string());
+        balance.selling_liabilities = Some("20.0000000".to_string());
+        let explanation = explain_balance(&balance);
+        assert_eq!(explanation.buying_liabilities, "5.0000000");
+        assert_eq!(explanation.selling_liabilities, "20.0000000");
+        assert!(
+            explanation
+                .summary
+                .contains("5 USDC is reserved for open buy offers")
+        );
+        assert!(
+            explanation
+                .summary
+                .contains("20 USDC is locked in open sell offers")
+        );
+    }
+
+    #[test]
+    fn test_liquidity_pool_share_balance() {
+        let balance = Balance {
+            asset_type: "liquidity_pool_shares".to_string(),
+            balance: "12.5000000".to_string(),
+            liquidity_pool_id: Some("abcd1234".to_string()),
+            ..Default::default()
+        };
+        let explanation = explain_balance(&balance);
+        assert_eq!(explanation.asset_code, "Liquidity pool shares");
+        assert!(explanation.summary.contains("liquidity pool abcd1234"));
+    }
 }
@@ -0,0 +1,249 @@
+//! Anchor deposit/withdrawal pattern recognition.
+//!
+//! SEP-6 and SEP-24 anchors move value on and off Stellar with a small set of
+//! recognisable on-chain shapes: the issuer pays the user on deposit, the user
+//! pays the issuer (with a memo identifying the withdrawal) on withdrawal, and
+//! regulated assets can be clawed back. These heuristics look for those shapes
+//! and return a single high-level interpretation line. They are deliberately
+//! conservative — when nothing matches, no interpretation is given.
+
+use crate::explain::format::format_exact;
+use crate::explain::templates;
+use crate::models::memo::Memo;
+use crate::models::operation::{Operation, PathPaymentOperation, PaymentOperation};
+use crate::models::transaction::Transaction;
+
+/// Return a high-level interpretation when the transaction matches a common
+/// anchor pattern, e.g. "This looks like a USD withdrawal through an anchor".
+pub fn detect_anchor_pattern(transaction: &Transaction) -> Option<String> {
+    let memo = transaction.memo.as_ref().filter(|m| !m.is_none());
+
+    transaction.operations.iter().find_map(|op| match op {
+        Operation::Payment(payment) => payment_pattern(payment, memo),
+        Operation::PathPayment(path) => path_payment_pattern(path, memo),
+        Operation::Clawback(clawback) => Some(templates::render(
+            "anchor.clawback",
+            &[
+                ("asset", &clawback.asset_code),
+                ("amount", &format_exact(clawback.amount)),
+            ],
+        )),
+        _ => None,
+    })
+}
+
+fn payment_pattern(payment: &PaymentOperation, memo: Option<&Memo>) -> Option<String> {
+    let code = payment.asset_code.as_deref()?;
+    let issuer = payment.asset_issuer.as_deref()?;
+
+    if payment.source_account.as_deref() == Some(issuer) {
+        let mut deposit = templates::render(
+            "anchor.deposit",
+            &[
+                ("asset", display_code(code)),
+                ("amount", &format_exact(payment.amount)),
+                ("code", code),
+            ],
+        );
+        let reference = match memo {
+            Some(Memo::Id(id)) => Some(templates::render(
+                "anchor.deposit_memo_id",
+                &[("id", &id.to_string())],
+            )),
+            Some(_) => Some(templates::render("anchor.deposit_memo", &[])),
+            None => None,
+        };
+        if let Some(reference) = reference {
+            deposit.push(' ');
+            deposit.push_str(&reference);
+        }
+        return Some(deposit);
+    }
+
+    // Anchors require a memo on withdrawals so they can match the incoming
+    // payment to the user's request; without one this is just a payment.
+    if payment.destination == issuer && memo.is_some() {
+        return Some(templates::render(
+            "anchor.withdrawal",
+            &[
+                ("asset", display_code(code)),
+                ("amount", &format_exact(payment.amount)),
+                ("code", code),
+            ],
+        ));
+    }
+
+    None
+}
+
+fn path_payment_pattern(path: &PathPaymentOperation, memo: Option<&Memo>) -> Option<String> {
+    let (send_code, _) = parse_asset(&path.send_asset)?;
+    let (dest_code, dest_issuer) = parse_asset(&path.dest_asset)?;
+
+    if dest_issuer.as_deref() == Some(path.destination.as_str()) && memo.is_some() {
+        return Some(templates::render(
+            "anchor.withdrawal_converted",
+            &[
+                ("asset", display_code(&dest_code)),
+                ("amount", &format_exact(path.send_amount)),
+                ("code", &send_code),
+            ],
+        ));
+    }
+
+    if send_code != "XLM" && dest_code != "XLM" && send_code != dest_code {
+        return Some(templates::render(
+            "anchor.conversion",
+            &[("send_code", &send_code), ("dest_code", &dest_code)],
+        ));
+    }
+
+    None
+}
+
+/// Parse the "CODE (ISSUER)" / "XLM (native)" display form used by path
+/// payment operations into a code and optional issuer.
+pub(crate) fn parse_asset(asset: &str) -> Option<(String, Option<String>)> {
+    let asset = asset.trim();
+    if asset.is_empty() || asset == "Unknown" {
+        return None;
+    }
+    match asset.split_once(" (") {
+        Some((code, rest)) => {
+            let issuer = rest.trim_end_matches(')');
+            if issuer == "native" {
+                Some((code.to_string(), None))
+            } else {
+                Some((code.to_string(), Some(issuer.to_string())))
+            }
+        }
+        None => Some((asset.to_string(), None)),
+    }
+}
+
+/// Fiat-backed stablecoins are described by the currency they represent.
+fn display_code(code: &str) -> &str {
+    match code {
+        "USDC" | "USDT" => "USD",
+        "EURC" => "EUR",
+        _ => code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::{ClawbackOperation, PathPaymentType};
+
+    const ISSUER: &str = "GISSUER";
+    const USER: &str = "GUSER";
+
+    fn payment(source: &str, destination: &str) -> Operation {
+        Operation::Payment(PaymentOperation {
+            id: "1".to_string(),
+            source_account: Some(source.to_string()),
+            destination: destination.to_string(),
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: Some(ISSUER.to_string()),
+            amount: "100.0000000".parse().unwrap(),
+        })
+    }
+
+    fn tx(operations: Vec<Operation>, memo: Option<Memo>) -> Transaction {
+        Transaction::new("hash".to_string(), true, 100, operations, memo, None)
+    }
+
+    #[test]
+    fn test_deposit_from_issuer_with_id_memo() {
+        let t = tx(vec![payment(ISSUER, USER)], Some(Memo::id(12345)));
+        let interpretation = detect_anchor_pattern(&t).unwrap();
+        assert!(interpretation.starts_with("This looks like a USD deposit through an anchor"));
+        assert!(interpretation.contains("ID memo 12345"));
+    }
+
+    #[test]
+    fn test_withdrawal_to_issuer_requires_memo() {
+        let with_memo = tx(vec![payment(USER, ISSUER)], Memo::text("withdraw-42"));
+        assert!(
+            detect_anchor_pattern(&with_memo)
+                .unwrap()
+                .contains("USD withdrawal through an anchor")
+        );
+
+        let without_memo = tx(vec![payment(USER, ISSUER)], None);
+        assert_eq!(detect_anchor_pattern(&without_memo), None);
+    }
+
+    #[test]
+    fn test_native_payment_has_no_interpretation() {
+        let op = Operation::Payment(PaymentOperation {
+            id: "1".to_string(),
+            source_account: Some(USER.to_string()),
+            destination: "GOTHER".to_string(),
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+            amount: "5".parse().unwrap(),
+        });
+        assert_eq!(
+            detect_anchor_pattern(&tx(vec![op], Some(Memo::id(1)))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_clawback() {
+        let op = Operation::Clawback(ClawbackOperation {
+            id: "1".to_string(),
+            source_account: Some(ISSUER.to_string()),
+            from: USER.to_string(),
+            asset_code: "EURT".to_string(),
+            asset_issuer: ISSUER.to_string(),
+            amount: "10".parse().unwrap(),
+        });
+        let interpretation = detect_anchor_pattern(&tx(vec![op], None)).unwrap();
+        assert!(interpretation.contains("EURT anchor reclaiming 10 EURT"));
+    }
+
+    #[test]
+    fn test_path_payment_withdrawal_and_conversion() {
+        let path = |destination: &str| {
+            Operation::PathPayment(PathPaymentOperation {
+                id: "1".to_string(),
+                source_account: Some(USER.to_string()),
+                destination: destination.to_string(),
+                send_asset: "EURC (GEURISSUER)".to_string(),
+                send_amount: "90".parse().unwrap(),
+                dest_asset: format!("USDC ({ISSUER})"),
+                dest_amount: "100".parse().unwrap(),
+                path: vec![],
+                payment_type: PathPaymentType::StrictReceive,
+            })
+        };
+
+        let withdrawal = tx(vec![path(ISSUER)], Some(Memo::id(7)));
+        assert!(
+            detect_anchor_pattern(&withdrawal)
+                .unwrap()
+                .contains("USD withdrawal through an anchor: 90 EURC")
+        );
+
+        let conversion = tx(vec![path("GOTHER")], None);
+        assert!(
+            detect_anchor_pattern(&conversion)
+                .unwrap()
+                .contains("EURC was exchanged for USDC")
+        );
+    }
+
+    #[test]
+    fn test_parse_asset() {
+        assert_eq!(parse_asset("XLM (native)"), Some(("XLM".to_string(), None)));
+        assert_eq!(
+            parse_asset("USDC (GISSUER)"),
+            Some(("USDC".to_string(), Some("GISSUER".to_string())))
+        );
+        assert_eq!(parse_asset("Unknown"), None);
+    }
+}
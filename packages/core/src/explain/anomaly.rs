@@ -0,0 +1,331 @@
+//! Unusual activity on an account, judged against its own history.
+//!
+//! A baseline records what an account usually does: the typical (median)
+//! amount it moves of each asset, who it deals with, and the hours of the
+//! day it is active. A recent transaction is flagged when it moves many
+//! times the typical amount, sends to an address the account has never paid,
+//! or lands at an hour the account is never active. Each check needs enough
+//! history to say what "usual" is, so young accounts are never flagged.
+
+use std::collections::{BTreeMap, HashSet};
+
+use chrono::Timelike;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::explain::format::{format_asset, format_exact, shorten};
+use crate::explain::statement::StatementEntry;
+use crate::explain::templates;
+use crate::explain::time::parse_timestamp;
+use crate::models::amount::Amount;
+use crate::models::operation::Operation;
+
+/// Transfers of an asset needed before its typical amount means anything.
+const MIN_AMOUNT_SAMPLES: usize = 5;
+/// How many times the typical amount counts as unusually large.
+const LARGE_AMOUNT_FACTOR: f64 = 10.0;
+/// Transactions needed before a new counterparty is worth mentioning.
+const MIN_COUNTERPARTY_SAMPLES: usize = 10;
+/// Transactions needed before an hour with no activity is telling.
+const MIN_HOUR_SAMPLES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AnomalyKind {
+    LargeAmount,
+    NewCounterparty,
+    UnusualTime,
+}
+
+/// Something a recent transaction did that the account does not usually do.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct AccountAnomaly {
+    pub transaction_hash: String,
+    pub created_at: String,
+    pub kind: AnomalyKind,
+    pub description: String,
+}
+
+/// What an account usually does, from its successful transactions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Baseline {
+    transactions: usize,
+    /// Median amount per asset, for assets with enough transfers.
+    typical_amounts: BTreeMap<String, Amount>,
+    counterparties: HashSet<String>,
+    /// Transactions by UTC hour of day.
+    hours: [usize; 24],
+}
+
+impl Baseline {
+    pub fn from_history(address: &str, entries: &[StatementEntry]) -> Self {
+        let mut baseline = Baseline::default();
+        let mut amounts: BTreeMap<String, Vec<Amount>> = BTreeMap::new();
+        for entry in entries.iter().filter(|e| e.transaction.successful) {
+            baseline.transactions += 1;
+            if let Some(hour) = hour_of(entry) {
+                baseline.hours[hour] += 1;
+            }
+            for op in &entry.transaction.operations {
+                for transfer in transfers(address, op) {
+                    amounts
+                        .entry(transfer.asset)
+                        .or_default()
+                        .push(transfer.amount);
+                    baseline.counterparties.insert(transfer.counterparty);
+                }
+            }
+        }
+        baseline.typical_amounts = amounts
+            .into_iter()
+            .filter(|(_, amounts)| amounts.len() >= MIN_AMOUNT_SAMPLES)
+            .map(|(asset, mut amounts)| {
+                amounts.sort();
+                (asset, amounts[amounts.len() / 2])
+            })
+            .collect();
+        baseline
+    }
+
+    /// What is unusual about `entry`, a successful transaction not in the
+    /// baseline. At most one anomaly of each kind.
+    pub fn anomalies(&self, address: &str, entry: &StatementEntry) -> Vec<AccountAnomaly> {
+        let tx = &entry.transaction;
+        if !tx.successful {
+            return Vec::new();
+        }
+        let flag = |kind, description| AccountAnomaly {
+            transaction_hash: tx.hash.clone(),
+            created_at: entry.created_at.clone(),
+            kind,
+            description,
+        };
+        let transfers: Vec<Transfer> = tx
+            .operations
+            .iter()
+            .flat_map(|op| transfers(address, op))
+            .collect();
+
+        let mut found = Vec::new();
+        let largest = transfers
+            .iter()
+            .filter_map(|t| {
+                let typical = self.typical_amounts.get(&t.asset)?;
+                let ratio = t.amount.ratio(*typical)?;
+                (ratio >= LARGE_AMOUNT_FACTOR).then_some((t, *typical, ratio))
+            })
+            .max_by(|a, b| a.2.total_cmp(&b.2));
+        if let Some((transfer, typical, ratio)) = largest {
+            let code = asset_code(&transfer.asset);
+            found.push(flag(
+                AnomalyKind::LargeAmount,
+                templates::render(
+                    if transfer.outgoing {
+                        "anomaly.large_payment"
+                    } else {
+                        "anomaly.large_deposit"
+                    },
+                    &[
+                        ("amount", &format_exact(transfer.amount)),
+                        ("code", code),
+                        ("ratio", &(ratio.round() as u64).to_string()),
+                        ("typical", &format_exact(typical)),
+                    ],
+                ),
+            ));
+        }
+
+        if self.transactions >= MIN_COUNTERPARTY_SAMPLES
+            && let Some(transfer) = transfers
+                .iter()
+                .find(|t| t.outgoing && !self.counterparties.contains(&t.counterparty))
+        {
+            found.push(flag(
+                AnomalyKind::NewCounterparty,
+                templates::render(
+                    "anomaly.new_counterparty",
+                    &[("counterparty", &shorten(&transfer.counterparty))],
+                ),
+            ));
+        }
+
+        if self.transactions >= MIN_HOUR_SAMPLES
+            && let Some(hour) = hour_of(entry)
+            && [23, 0, 1]
+                .iter()
+                .all(|offset| self.hours[(hour + offset) % 24] == 0)
+        {
+            found.push(flag(
+                AnomalyKind::UnusualTime,
+                templates::render(
+                    "anomaly.unusual_time",
+                    &[("hour", &format!("{hour:02}:00"))],
+                ),
+            ));
+        }
+        found
+    }
+}
+
+/// Value moving between `address` and someone else in one operation.
+struct Transfer {
+    asset: String,
+    amount: Amount,
+    counterparty: String,
+    outgoing: bool,
+}
+
+fn transfers(address: &str, op: &Operation) -> Vec<Transfer> {
+    let mut found = Vec::new();
+    let mut record = |asset: String, amount: Amount, from: Option<&str>, to: &str| {
+        if from == Some(address) && to != address {
+            found.push(Transfer {
+                asset,
+                amount,
+                counterparty: to.to_string(),
+                outgoing: true,
+            });
+        } else if to == address
+            && let Some(from) = from.filter(|from| *from != address)
+        {
+            found.push(Transfer {
+                asset,
+                amount,
+                counterparty: from.to_string(),
+                outgoing: false,
+            });
+        }
+    };
+    match op {
+        Operation::Payment(p) => record(
+            format_asset(
+                Some(p.asset_type.as_str()),
+                p.asset_code.as_deref(),
+                p.asset_issuer.as_deref(),
+            ),
+            p.amount,
+            p.source_account.as_deref(),
+            &p.destination,
+        ),
+        Operation::CreateAccount(c) => record(
+            "XLM (native)".to_string(),
+            c.starting_balance,
+            Some(&c.funder),
+            &c.new_account,
+        ),
+        Operation::PathPayment(p) => {
+            let source = p.source_account.as_deref();
+            if source == Some(address) {
+                record(p.send_asset.clone(), p.send_amount, source, &p.destination);
+            } else {
+                record(p.dest_asset.clone(), p.dest_amount, source, &p.destination);
+            }
+        }
+        _ => {}
+    }
+    found
+}
+
+fn hour_of(entry: &StatementEntry) -> Option<usize> {
+    parse_timestamp(&entry.created_at).map(|at| at.hour() as usize)
+}
+
+/// "USDC" from "USDC (GISSUER)", "XLM" from "XLM (native)".
+fn asset_code(asset: &str) -> &str {
+    asset.split_once(" (").map_or(asset, |(code, _)| code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::PaymentOperation;
+    use crate::models::transaction::Transaction;
+
+    const ACCOUNT: &str = "GACCOUNT";
+
+    fn entry(hash: &str, created_at: &str, from: &str, to: &str, amount: &str) -> StatementEntry {
+        let payment = Operation::Payment(PaymentOperation {
+            id: "1".to_string(),
+            source_account: Some(from.to_string()),
+            destination: to.to_string(),
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+            amount: amount.parse().unwrap(),
+        });
+        StatementEntry {
+            transaction: Transaction::new(hash.to_string(), true, 100, vec![payment], None, None),
+            created_at: created_at.to_string(),
+            operation_count: 1,
+        }
+    }
+
+    /// Twenty 1,000 XLM payments to the same friend during office hours.
+    fn history() -> Vec<StatementEntry> {
+        (0..20)
+            .map(|i| {
+                entry(
+                    &format!("tx{i}"),
+                    &format!("2025-03-{:02}T{:02}:00:00Z", i + 1, 9 + i % 8),
+                    ACCOUNT,
+                    "GFRIEND",
+                    "1000",
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_usual_transaction_is_not_flagged() {
+        let baseline = Baseline::from_history(ACCOUNT, &history());
+        let usual = entry("new", "2025-04-01T10:30:00Z", ACCOUNT, "GFRIEND", "1200");
+        assert!(baseline.anomalies(ACCOUNT, &usual).is_empty());
+    }
+
+    #[test]
+    fn test_large_payment_to_stranger_at_night_is_flagged() {
+        let baseline = Baseline::from_history(ACCOUNT, &history());
+        let odd = entry("odd", "2025-04-01T03:10:00Z", ACCOUNT, "GSTRANGER", "40000");
+
+        let anomalies = baseline.anomalies(ACCOUNT, &odd);
+        let kinds: Vec<AnomalyKind> = anomalies.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                AnomalyKind::LargeAmount,
+                AnomalyKind::NewCounterparty,
+                AnomalyKind::UnusualTime
+            ]
+        );
+        assert_eq!(
+            anomalies[0].description,
+            "This payment of 40,000 XLM is 40x larger than this account's typical transfer of 1,000 XLM."
+        );
+        assert_eq!(anomalies[0].transaction_hash, "odd");
+        assert_eq!(
+            anomalies[2].description,
+            "Made at 03:00 UTC, outside the hours this account is usually active."
+        );
+    }
+
+    #[test]
+    fn test_short_history_flags_nothing() {
+        let baseline = Baseline::from_history(ACCOUNT, &history()[..4]);
+        let odd = entry("odd", "2025-04-01T03:10:00Z", ACCOUNT, "GSTRANGER", "40000");
+        assert!(baseline.anomalies(ACCOUNT, &odd).is_empty());
+    }
+
+    #[test]
+    fn test_incoming_deposit_from_stranger_is_only_large() {
+        let baseline = Baseline::from_history(ACCOUNT, &history());
+        let deposit = entry("in", "2025-04-01T11:00:00Z", "GSTRANGER", ACCOUNT, "15000");
+
+        let anomalies = baseline.anomalies(ACCOUNT, &deposit);
+        assert_eq!(anomalies.len(), 1);
+        assert!(
+            anomalies[0]
+                .description
+                .starts_with("This deposit of 15,000 XLM is 15x")
+        );
+    }
+}
@@ -0,0 +1,449 @@
+//! Issuer configuration audit for issued assets.
+//!
+//! Each check of the issuing account produces a finding with a severity, so
+//! a holder can see at a glance what the issuer is able to do with their
+//! funds and how well the issuer identifies itself.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::{format_account, format_amount, format_date};
+use crate::explain::issuer_label::{IssuerLabel, LabelVerification, label_issuer};
+use crate::explain::templates;
+use crate::models::account::Account;
+use crate::models::asset::{Clawback, StellarTomlStatus, TomlIdentity};
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    fn as_str(self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Low => "low",
+            Severity::Medium => "medium",
+            Severity::High => "high",
+        }
+    }
+}
+
+/// One plain-English observation about the issuer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct AuditFinding {
+    pub severity: Severity,
+    /// Stable identifier for the check, e.g. "clawback_enabled".
+    pub check: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct AssetAuditExplanation {
+    pub asset_code: String,
+    pub asset_issuer: String,
+    pub summary: String,
+    /// Highest severity among the findings.
+    pub risk_level: Severity,
+    /// Findings ordered from most to least severe.
+    pub findings: Vec<AuditFinding>,
+    /// The organization the issuer's home domain names, and whether the
+    /// domain's stellar.toml lists the issuer.
+    #[serde(default)]
+    pub issuer_label: Option<IssuerLabel>,
+}
+
+/// What the audit knows about the issuer's home domain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HomeDomainCheck {
+    pub domain: String,
+    pub status: StellarTomlStatus,
+    /// ORG_NAME and ACCOUNTS from the stellar.toml, when it could be read.
+    pub identity: Option<TomlIdentity>,
+}
+
+pub fn audit_asset(
+    asset_code: &str,
+    issuer: &Account,
+    home_domain: Option<&HomeDomainCheck>,
+    clawbacks: &[Clawback],
+) -> AssetAuditExplanation {
+    let asset = &[("asset", asset_code)];
+    let mut findings = Vec::new();
+    let mut add = |severity, check: &str, message: String| {
+        findings.push(AuditFinding {
+            severity,
+            check: check.to_string(),
+            message,
+        });
+    };
+
+    if issuer.flags.auth_clawback_enabled {
+        add(
+            Severity::High,
+            "clawback_enabled",
+            templates::render("asset_audit.clawback_enabled", asset),
+        );
+    } else {
+        add(
+            Severity::Info,
+            "clawback_disabled",
+            templates::render("asset_audit.clawback_disabled", asset),
+        );
+    }
+
+    if let Some(latest) = clawbacks.first() {
+        let count = match clawbacks.len() {
+            1 => "once".to_string(),
+            n => format!("{n} times"),
+        };
+        let when = latest
+            .created_at
+            .as_deref()
+            .map(|t| format!(" on {}", format_date(t)))
+            .unwrap_or_default();
+        add(
+            Severity::High,
+            "recent_clawbacks",
+            templates::render(
+                "asset_audit.recent_clawbacks",
+                &[
+                    ("asset", asset_code),
+                    ("count", &count),
+                    ("amount", &format_amount(&latest.amount)),
+                    ("from", &format_account(&latest.from)),
+                    ("when", &when),
+                ],
+            ),
+        );
+    }
+
+    if issuer.flags.auth_revocable {
+        add(
+            Severity::Medium,
+            "auth_revocable",
+            templates::render("asset_audit.auth_revocable", asset),
+        );
+    }
+    if issuer.flags.auth_required {
+        add(
+            Severity::Low,
+            "auth_required",
+            templates::render("asset_audit.auth_required", asset),
+        );
+    }
+    if issuer.flags.auth_immutable {
+        add(
+            Severity::Info,
+            "auth_immutable",
+            templates::render("asset_audit.auth_immutable", &[]),
+        );
+    }
+
+    match (issuer.master_key_weight, issuer.num_signers) {
+        (0, 0) => add(
+            Severity::Info,
+            "master_key_locked",
+            templates::render("asset_audit.master_key_locked", asset),
+        ),
+        (0, signers) => add(
+            Severity::Low,
+            "master_key_delegated",
+            templates::render(
+                "asset_audit.master_key_delegated",
+                &[(
+                    "signers",
+                    &format!(
+                        "{signers} other signer{}",
+                        if signers == 1 { "" } else { "s" }
+                    ),
+                )],
+            ),
+        ),
+        (weight, _) => add(
+            Severity::Low,
+            "master_key_active",
+            templates::render(
+                "asset_audit.master_key_active",
+                &[("asset", asset_code), ("weight", &weight.to_string())],
+            ),
+        ),
+    }
+
+    match home_domain {
+        None => add(
+            Severity::Medium,
+            "home_domain_missing",
+            templates::render("asset_audit.home_domain_missing", asset),
+        ),
+        Some(check) => {
+            let (severity, name) = match check.status {
+                StellarTomlStatus::Listed => (Severity::Info, "toml_listed"),
+                StellarTomlStatus::Unlisted => (Severity::Medium, "toml_unlisted"),
+                StellarTomlStatus::Invalid => (Severity::Medium, "toml_invalid"),
+                StellarTomlStatus::Missing => (Severity::Medium, "toml_missing"),
+                StellarTomlStatus::Unreachable => (Severity::Medium, "toml_unreachable"),
+            };
+            add(
+                severity,
+                name,
+                templates::render(
+                    &format!("asset_audit.{name}"),
+                    &[("domain", &check.domain), ("asset", asset_code)],
+                ),
+            );
+        }
+    }
+
+    let issuer_label = home_domain.and_then(|check| {
+        label_issuer(&issuer.account_id, &check.domain, check.identity.as_ref()?)
+    });
+    match &issuer_label {
+        Some(label) if label.verification == LabelVerification::Verified => add(
+            Severity::Info,
+            "issuer_verified",
+            templates::render(
+                "asset_audit.issuer_verified",
+                &[("domain", &label.home_domain), ("name", &label.name)],
+            ),
+        ),
+        Some(label) => add(
+            Severity::Medium,
+            "issuer_unverified",
+            templates::render(
+                "asset_audit.issuer_unverified",
+                &[("domain", &label.home_domain), ("name", &label.name)],
+            ),
+        ),
+        None => {}
+    }
+
+    // Stable sort keeps related findings in check order within a severity.
+    findings.sort_by_key(|finding| std::cmp::Reverse(finding.severity));
+    let risk_level = findings
+        .first()
+        .map_or(Severity::Info, |finding| finding.severity);
+
+    let summary = templates::render(
+        "asset_audit.summary",
+        &[
+            ("asset", asset_code),
+            ("issuer", &format_account(&issuer.account_id)),
+            ("risk", risk_level.as_str()),
+            ("findings", &describe_counts(&findings)),
+        ],
+    );
+
+    AssetAuditExplanation {
+        asset_code: asset_code.to_string(),
+        asset_issuer: issuer.account_id.clone(),
+        summary,
+        risk_level,
+        findings,
+        issuer_label,
+    }
+}
+
+/// "1 high, 2 medium and 1 info finding(s)" — omitting empty severities.
+fn describe_counts(findings: &[AuditFinding]) -> String {
+    let parts: Vec<String> = [
+        Severity::High,
+        Severity::Medium,
+        Severity::Low,
+        Severity::Info,
+    ]
+    .into_iter()
+    .filter_map(|severity| {
+        let count = findings.iter().filter(|f| f.severity == severity).count();
+        (count > 0).then(|| format!("{count} {}", severity.as_str()))
+    })
+    .collect();
+    let total = findings.len();
+    let noun = if total == 1 { "finding" } else { "findings" };
+    match parts.split_last() {
+        Some((last, [])) => format!("{last} {noun}"),
+        Some((last, rest)) => format!("{} and {last} {noun}", rest.join(", ")),
+        None => format!("no {noun}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::account::AccountFlags;
+
+    const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    fn issuer(flags: AccountFlags, master_key_weight: u32, num_signers: u32) -> Account {
+        Account {
+            id: ISSUER.to_string(),
+            account_id: ISSUER.to_string(),
+            sequence: "1".to_string(),
+            num_signers,
+            signers: Vec::new(),
+            master_key_weight,
+            thresholds: Default::default(),
+            balances: vec![],
+            flags,
+            home_domain: Some("example.com".to_string()),
+            memo_required: false,
+        }
+    }
+
+    fn no_flags() -> AccountFlags {
+        AccountFlags {
+            auth_required: false,
+            auth_revocable: false,
+            auth_immutable: false,
+            auth_clawback_enabled: false,
+        }
+    }
+
+    fn checks(audit: &AssetAuditExplanation) -> Vec<&str> {
+        audit.findings.iter().map(|f| f.check.as_str()).collect()
+    }
+
+    #[test]
+    fn test_locked_issuer_with_listed_toml_is_low_risk() {
+        let domain = HomeDomainCheck {
+            domain: "example.com".to_string(),
+            status: StellarTomlStatus::Listed,
+            identity: None,
+        };
+        let audit = audit_asset(
+            "USDX",
+            &issuer(
+                AccountFlags {
+                    auth_immutable: true,
+                    ..no_flags()
+                },
+                0,
+                0,
+            ),
+            Some(&domain),
+            &[],
+        );
+
+        assert_eq!(audit.risk_level, Severity::Info);
+        assert_eq!(
+            checks(&audit),
+            vec![
+                "clawback_disabled",
+                "auth_immutable",
+                "master_key_locked",
+                "toml_listed"
+            ]
+        );
+        assert_eq!(
+            audit.summary,
+            format!("USDX issued by {ISSUER}: overall risk is info, from 4 info findings.")
+        );
+        assert_eq!(
+            audit.findings[3].message,
+            "example.com publishes a stellar.toml that lists USDX."
+        );
+    }
+
+    #[test]
+    fn test_clawback_issuer_is_high_risk() {
+        let clawbacks = vec![
+            Clawback {
+                from: "GHOLDER".to_string(),
+                amount: "12.5000000".to_string(),
+                transaction_hash: "abc".to_string(),
+                created_at: Some("2025-03-03T00:00:00Z".to_string()),
+            },
+            Clawback {
+                from: "GOTHER".to_string(),
+                amount: "1.0000000".to_string(),
+                transaction_hash: "def".to_string(),
+                created_at: None,
+            },
+        ];
+        let audit = audit_asset(
+            "USDX",
+            &issuer(
+                AccountFlags {
+                    auth_revocable: true,
+                    auth_clawback_enabled: true,
+                    ..no_flags()
+                },
+                1,
+                1,
+            ),
+            None,
+            &clawbacks,
+        );
+
+        assert_eq!(audit.risk_level, Severity::High);
+        assert_eq!(
+            checks(&audit),
+            vec![
+                "clawback_enabled",
+                "recent_clawbacks",
+                "auth_revocable",
+                "home_domain_missing",
+                "master_key_active"
+            ]
+        );
+        assert_eq!(
+            audit.findings[1].message,
+            "The issuer recently clawed back USDX 2 times, most recently 12.5 USDX from GHOLDER on March 3, 2025."
+        );
+        assert!(
+            audit
+                .summary
+                .ends_with("from 2 high, 2 medium and 1 low findings.")
+        );
+    }
+
+    #[test]
+    fn test_issuer_label_is_checked_against_toml_accounts() {
+        let check = |accounts: Vec<String>| HomeDomainCheck {
+            domain: "example.com".to_string(),
+            status: StellarTomlStatus::Listed,
+            identity: Some(TomlIdentity {
+                org_name: Some("Example Inc".to_string()),
+                accounts,
+            }),
+        };
+
+        let listed = check(vec![ISSUER.to_string()]);
+        let audit = audit_asset("USDX", &issuer(no_flags(), 0, 0), Some(&listed), &[]);
+        let label = audit.issuer_label.as_ref().unwrap();
+        assert_eq!(label.verification, LabelVerification::Verified);
+        assert_eq!(audit.risk_level, Severity::Info);
+        assert!(checks(&audit).ends_with(&["toml_listed", "issuer_verified"]));
+
+        let unlisted = check(vec![]);
+        let audit = audit_asset("USDX", &issuer(no_flags(), 0, 0), Some(&unlisted), &[]);
+        assert_eq!(
+            audit.issuer_label.unwrap().verification,
+            LabelVerification::Claimed
+        );
+        assert_eq!(audit.risk_level, Severity::Medium);
+        assert_eq!(
+            audit.findings[0].message,
+            "The issuer claims to be Example Inc through example.com, but that domain's stellar.toml does not list the issuing account."
+        );
+    }
+
+    #[test]
+    fn test_master_key_delegated_to_other_signers() {
+        let audit = audit_asset("USDX", &issuer(no_flags(), 0, 3), None, &[]);
+        let finding = audit
+            .findings
+            .iter()
+            .find(|f| f.check == "master_key_delegated")
+            .unwrap();
+        assert_eq!(finding.severity, Severity::Low);
+        assert!(finding.message.ends_with("controlled by 3 other signers."));
+    }
+}
@@ -0,0 +1,418 @@
+//! Several transactions told as one economic event.
+//!
+//! Some flows cannot fit in one transaction: an atomic swap between two
+//! parties who each sign their own leg, or a payout split across batches.
+//! Explained one hash at a time, each leg looks like an unrelated payment.
+//! This module puts the transactions in ledger order, nets what every
+//! account sent and received across all of them, and recognises the common
+//! case of two accounts trading one asset for another. Failed transactions
+//! moved nothing, so they are listed but leave the flow incomplete.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::anchor::parse_asset;
+use crate::explain::format::{format_account, format_exact, shorten};
+use crate::explain::statement::{AssetTotal, movements};
+use crate::explain::templates;
+use crate::explain::transaction::explain_transaction;
+use crate::models::amount::Amount;
+use crate::models::operation::Operation;
+use crate::models::transaction::Transaction;
+
+/// What one account sent and received across the bundle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct BundleAccount {
+    pub address: String,
+    /// Hashes of the transactions that moved funds for this account.
+    pub transactions: Vec<String>,
+    pub totals: Vec<AssetTotal>,
+}
+
+/// One transaction of the bundle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct BundleTransaction {
+    pub hash: String,
+    pub successful: bool,
+    pub ledger: Option<u64>,
+    pub created_at: Option<String>,
+    /// What the transaction did, one clause per operation.
+    pub summary: String,
+}
+
+/// Several transactions explained as a single flow.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct BundleExplanation {
+    /// The transactions' hashes, in ledger order.
+    pub hashes: Vec<String>,
+    /// True when every transaction succeeded.
+    pub complete: bool,
+    pub summary: String,
+    /// The flow step by step, one sentence per transaction.
+    pub narrative: String,
+    /// Accounts that sent or received funds, in order of first appearance.
+    pub accounts: Vec<BundleAccount>,
+    /// Assets moved, in order of first appearance.
+    pub assets: Vec<String>,
+    pub transactions: Vec<BundleTransaction>,
+}
+
+/// Explain `transactions` as one flow. They are put in ledger order; ties
+/// and transactions without a ledger keep the order given.
+pub fn explain_bundle(transactions: &[Transaction]) -> BundleExplanation {
+    let mut ordered: Vec<&Transaction> = transactions.iter().collect();
+    ordered.sort_by_key(|tx| tx.ledger.unwrap_or(u64::MAX));
+
+    let mut addresses: Vec<String> = Vec::new();
+    let mut assets: Vec<String> = Vec::new();
+    let mut flows: BTreeMap<&str, AccountFlows> = BTreeMap::new();
+    for tx in ordered.iter().filter(|tx| tx.successful) {
+        for op in &tx.operations {
+            for address in parties(op) {
+                for (asset, received, sent) in movements(address, op) {
+                    if !addresses.iter().any(|a| a == address) {
+                        addresses.push(address.to_string());
+                    }
+                    if !assets.contains(&asset) {
+                        assets.push(asset.clone());
+                    }
+                    flows
+                        .entry(address)
+                        .or_default()
+                        .add(&tx.hash, asset, received, sent);
+                }
+            }
+        }
+    }
+
+    let accounts: Vec<BundleAccount> = addresses
+        .iter()
+        .map(|address| flows[address.as_str()].to_account(address, &assets))
+        .collect();
+    let steps: Vec<BundleTransaction> = ordered.iter().map(|tx| step(tx)).collect();
+    let failed: Vec<&str> = steps
+        .iter()
+        .filter(|step| !step.successful)
+        .map(|step| step.hash.as_str())
+        .collect();
+
+    let mut summary = swap(&accounts, steps.len())
+        .unwrap_or_else(|| flow_summary(steps.len(), &assets, accounts.len()));
+    if !failed.is_empty() {
+        let failed = list(&failed.iter().map(|hash| shorten(hash)).collect::<Vec<_>>());
+        summary.push(' ');
+        summary.push_str(&templates::render("bundle.failed", &[("failed", &failed)]));
+    }
+    let narrative = steps
+        .iter()
+        .enumerate()
+        .map(|(i, step)| {
+            let key = match (i, step.successful) {
+                (0, true) => "bundle.step.first",
+                (0, false) => "bundle.step.first_failed",
+                (_, true) => "bundle.step.then",
+                (_, false) => "bundle.step.then_failed",
+            };
+            templates::render(
+                key,
+                &[("hash", &shorten(&step.hash)), ("summary", &step.summary)],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    BundleExplanation {
+        hashes: steps.iter().map(|step| step.hash.clone()).collect(),
+        complete: failed.is_empty(),
+        summary,
+        narrative,
+        accounts,
+        assets,
+        transactions: steps,
+    }
+}
+
+#[derive(Default)]
+struct AccountFlows {
+    transactions: Vec<String>,
+    /// asset -> (received, sent)
+    assets: BTreeMap<String, (Amount, Amount)>,
+}
+
+impl AccountFlows {
+    fn add(&mut self, hash: &str, asset: String, received: Amount, sent: Amount) {
+        if !self.transactions.iter().any(|h| h == hash) {
+            self.transactions.push(hash.to_string());
+        }
+        let (total_received, total_sent) = self.assets.entry(asset).or_default();
+        *total_received = total_received.saturating_add(received);
+        *total_sent = total_sent.saturating_add(sent);
+    }
+
+    /// Totals in the bundle's asset order.
+    fn to_account(&self, address: &str, assets: &[String]) -> BundleAccount {
+        BundleAccount {
+            address: address.to_string(),
+            transactions: self.transactions.clone(),
+            totals: assets
+                .iter()
+                .filter_map(|asset| {
+                    let (received, sent) = self.assets.get(asset)?;
+                    Some(AssetTotal {
+                        asset: asset.clone(),
+                        received: received.to_string(),
+                        sent: sent.to_string(),
+                        net: (*received - *sent).to_string(),
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Accounts an operation can move funds between, each once.
+fn parties(op: &Operation) -> Vec<&str> {
+    let (source, destination) = match op {
+        Operation::Payment(p) => (p.source_account.as_deref(), p.destination.as_str()),
+        Operation::CreateAccount(c) => (Some(c.funder.as_str()), c.new_account.as_str()),
+        Operation::PathPayment(p) => (p.source_account.as_deref(), p.destination.as_str()),
+        _ => return Vec::new(),
+    };
+    let mut parties: Vec<&str> = source.into_iter().chain([destination]).collect();
+    parties.dedup();
+    parties
+}
+
+fn step(tx: &Transaction) -> BundleTransaction {
+    let summary = match explain_transaction(tx, None) {
+        Ok(explanation) => explanation.narrative.unwrap_or_else(|| {
+            let clauses: Vec<String> = explanation
+                .operations
+                .iter()
+                .map(|op| op.summary.trim_end_matches('.').to_string())
+                .collect();
+            if clauses.is_empty() {
+                explanation.summary.trim_end_matches('.').to_string()
+            } else {
+                clauses.join("; ")
+            }
+        }),
+        Err(_) => templates::render("bundle.step.unexplained", &[]),
+    };
+    BundleTransaction {
+        hash: tx.hash.clone(),
+        successful: tx.successful,
+        ledger: tx.ledger,
+        created_at: tx.created_at.clone(),
+        summary: summary.trim_end_matches('.').to_string(),
+    }
+}
+
+/// Exactly two accounts, each giving up one asset and receiving exactly
+/// what the other gave up.
+fn swap(accounts: &[BundleAccount], transactions: usize) -> Option<String> {
+    let moved: Vec<&BundleAccount> = accounts
+        .iter()
+        .filter(|account| account.totals.iter().any(|total| !is_zero(&total.net)))
+        .collect();
+    let [a, b] = moved.as_slice() else {
+        return None;
+    };
+    let (a_gave, a_got) = trade(a)?;
+    let (b_gave, b_got) = trade(b)?;
+    if a_gave != b_got || b_gave != a_got {
+        return None;
+    }
+    let (a_code, _) = parse_asset(&a_gave.0)?;
+    let (b_code, _) = parse_asset(&b_gave.0)?;
+    Some(templates::render(
+        "bundle.swap",
+        &[
+            ("a", &format_account(&a.address)),
+            ("b", &format_account(&b.address)),
+            ("amount_a", &format_exact(a_gave.1)),
+            ("asset_a", &a_code),
+            ("amount_b", &format_exact(b_gave.1)),
+            ("asset_b", &b_code),
+            ("transactions", &transactions.to_string()),
+        ],
+    ))
+}
+
+/// The one asset an account gave up and the one it received, by net.
+fn trade(account: &BundleAccount) -> Option<((String, Amount), (String, Amount))> {
+    let mut gave = None;
+    let mut got = None;
+    for total in &account.totals {
+        let net: Amount = total.net.parse().ok()?;
+        if net.is_zero() {
+            continue;
+        }
+        let (slot, amount) = if net.is_negative() {
+            (&mut gave, -net)
+        } else {
+            (&mut got, net)
+        };
+        if slot.replace((total.asset.clone(), amount)).is_some() {
+            return None;
+        }
+    }
+    Some((gave?, got?))
+}
+
+fn flow_summary(transactions: usize, assets: &[String], accounts: usize) -> String {
+    if assets.is_empty() {
+        return templates::render(
+            "bundle.no_funds",
+            &[("transactions", &transactions.to_string())],
+        );
+    }
+    let codes: Vec<String> = assets
+        .iter()
+        .map(|asset| parse_asset(asset).map_or_else(|| asset.clone(), |(code, _)| code))
+        .collect();
+    templates::render(
+        "bundle.flow",
+        &[
+            ("transactions", &transactions.to_string()),
+            ("assets", &list(&codes)),
+            ("accounts", &accounts.to_string()),
+        ],
+    )
+}
+
+fn is_zero(amount: &str) -> bool {
+    amount.parse::<Amount>().is_ok_and(Amount::is_zero)
+}
+
+/// "a", "a and b", "a, b, and c".
+fn list(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [first, second] => format!("{first} and {second}"),
+        [rest @ .., last] => format!("{}, and {last}", rest.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::PaymentOperation;
+
+    const ALICE: &str = "GALICE";
+    const BOB: &str = "GBOB";
+    const CAROL: &str = "GCAROL";
+
+    fn payment(from: &str, to: &str, amount: &str, code: Option<&str>) -> Operation {
+        Operation::Payment(PaymentOperation {
+            id: "1".to_string(),
+            source_account: Some(from.to_string()),
+            destination: to.to_string(),
+            asset_type: if code.is_some() {
+                "credit_alphanum4"
+            } else {
+                "native"
+            }
+            .to_string(),
+            asset_code: code.map(str::to_string),
+            asset_issuer: code.map(|_| "GISSUER".to_string()),
+            amount: amount.parse().unwrap(),
+        })
+    }
+
+    fn tx(hash: &str, ledger: u64, successful: bool, operations: Vec<Operation>) -> Transaction {
+        let mut tx = Transaction::new(hash.to_string(), successful, 100, operations, None, None);
+        tx.ledger = Some(ledger);
+        tx
+    }
+
+    #[test]
+    fn test_two_legs_are_told_as_a_swap_in_ledger_order() {
+        let bundle = explain_bundle(&[
+            tx(
+                "b".repeat(64).as_str(),
+                9,
+                true,
+                vec![payment(BOB, ALICE, "50", Some("USDC"))],
+            ),
+            tx(
+                "a".repeat(64).as_str(),
+                8,
+                true,
+                vec![payment(ALICE, BOB, "400", None)],
+            ),
+        ]);
+
+        assert_eq!(bundle.hashes, vec!["a".repeat(64), "b".repeat(64)]);
+        assert!(bundle.complete);
+        assert_eq!(
+            bundle.summary,
+            "GALICE and GBOB swapped 400 XLM for 50 USDC across 2 transactions."
+        );
+        assert!(
+            bundle
+                .narrative
+                .starts_with("First, in aaaaaaaa...aaaaaaaa: ")
+        );
+        assert!(bundle.narrative.contains("Then, in bbbbbbbb...bbbbbbbb: "));
+        assert_eq!(bundle.assets, vec!["XLM (native)", "USDC (GISSUER)"]);
+        assert_eq!(bundle.accounts[0].address, ALICE);
+        assert_eq!(bundle.accounts[0].transactions.len(), 2);
+        assert_eq!(bundle.accounts[0].totals[0].net, "-400.0000000");
+        assert_eq!(bundle.accounts[0].totals[1].net, "50.0000000");
+    }
+
+    #[test]
+    fn test_other_flows_are_summarised_by_assets_and_accounts() {
+        let bundle = explain_bundle(&[
+            tx(
+                "a".repeat(64).as_str(),
+                1,
+                true,
+                vec![payment(ALICE, BOB, "5", None)],
+            ),
+            tx(
+                "b".repeat(64).as_str(),
+                2,
+                true,
+                vec![payment(BOB, CAROL, "5", None)],
+            ),
+        ]);
+
+        assert_eq!(
+            bundle.summary,
+            "These 2 transactions moved XLM between 3 accounts."
+        );
+        // Bob passed the funds through and nets to zero.
+        assert_eq!(bundle.accounts[1].totals[0].net, "0.0000000");
+    }
+
+    #[test]
+    fn test_failed_leg_leaves_the_flow_incomplete() {
+        let bundle = explain_bundle(&[
+            tx(
+                "a".repeat(64).as_str(),
+                1,
+                true,
+                vec![payment(ALICE, BOB, "400", None)],
+            ),
+            tx(
+                "b".repeat(64).as_str(),
+                2,
+                false,
+                vec![payment(BOB, ALICE, "50", Some("USDC"))],
+            ),
+        ]);
+
+        assert!(!bundle.complete);
+        assert_eq!(
+            bundle.summary,
+            "These 2 transactions moved XLM between 2 accounts. bbbbbbbb...bbbbbbbb failed, so the flow did not complete; only the successful transactions are counted."
+        );
+        assert!(bundle.narrative.contains("bbbbbbbb...bbbbbbbb (failed)"));
+        assert_eq!(bundle.assets, vec!["XLM (native)"]);
+    }
+}
@@ -0,0 +1,54 @@
+//! What changed on an account since a client last synced.
+//!
+//! A client keeps the cursor from its last sync and sends it back; the
+//! response holds only the transactions after it, explained, and how each
+//! asset's balance moved across them. Balance deltas count what statement
+//! totals count: payments, path payments, and account creation in successful
+//! transactions. Fees and other operations are not included.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::statement::{
+    AssetTotal, StatementEntry, StatementTransaction, asset_totals, statement_transaction,
+};
+
+/// Transactions on an account after a sync point, oldest first.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct AccountChanges {
+    pub address: String,
+    /// Transactions since the sync point, oldest first.
+    pub transactions: Vec<StatementTransaction>,
+    /// Amounts received and sent per asset across `transactions`, by asset.
+    pub balance_deltas: Vec<AssetTotal>,
+    /// Send as `since` on the next sync. Absent only when the account has no
+    /// transactions yet; sync again with the same `since`.
+    pub cursor: Option<String>,
+    /// More transactions follow `cursor`; sync again straight away to get
+    /// them.
+    pub has_more: bool,
+}
+
+/// Changes from `entries`, each with the cursor that resumes after it.
+/// `start` is the cursor the sync began after, kept when nothing is new.
+pub fn build_changes(
+    address: &str,
+    entries: &[(Option<String>, StatementEntry)],
+    start: Option<String>,
+    has_more: bool,
+) -> AccountChanges {
+    let cursor = entries
+        .iter()
+        .rev()
+        .find_map(|(cursor, _)| cursor.clone())
+        .or(start);
+    let entries: Vec<StatementEntry> = entries.iter().map(|(_, entry)| entry.clone()).collect();
+    AccountChanges {
+        address: address.to_string(),
+        transactions: entries.iter().map(statement_transaction).collect(),
+        balance_deltas: asset_totals(address, &entries),
+        cursor,
+        has_more,
+    }
+}
@@ -0,0 +1,216 @@
+//! Explanations for claimable balances.
+//!
+//! Each claimant's predicate is turned into a condition phrase, e.g.
+//! `not(abs_before(2025-03-03))` becomes "any time after March 3, 2025".
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::{format_account, format_amount, format_date, shorten, trim_amount};
+use crate::explain::templates;
+use crate::models::claimable_balance::{ClaimPredicate, ClaimableBalance};
+
+/// Plain-English explanation of a claimable balance.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct ClaimableBalanceExplanation {
+    pub id: String,
+    pub summary: String,
+    /// Asset code, "XLM" for the native asset.
+    pub asset_code: String,
+    pub asset_issuer: Option<String>,
+    pub amount: String,
+    pub sponsor: Option<String>,
+    /// Whether the issuer can still claw the balance back.
+    pub clawback_enabled: bool,
+    pub claimants: Vec<ClaimantExplanation>,
+}
+
+/// Who can claim the balance and when.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct ClaimantExplanation {
+    pub destination: String,
+    /// When the claimant may claim, e.g. "any time after March 3, 2025".
+    pub condition: String,
+    pub summary: String,
+    #[schema(value_type = Object)]
+    pub predicate: ClaimPredicate,
+}
+
+pub fn explain_claimable_balance(balance: &ClaimableBalance) -> ClaimableBalanceExplanation {
+    let amount = trim_amount(&balance.amount);
+    let asset = balance.asset_code();
+    let shown_amount = format_amount(&amount);
+
+    let mut summary = templates::render(
+        "claimable_balance.summary",
+        &[
+            ("balance_id", &shorten(&balance.id)),
+            ("amount", &shown_amount),
+            ("asset", asset),
+        ],
+    );
+    if let Some(sponsor) = &balance.sponsor {
+        summary.push(' ');
+        summary.push_str(&templates::render(
+            "claimable_balance.sponsor",
+            &[("sponsor", &format_account(sponsor))],
+        ));
+    }
+
+    let claimants = balance
+        .claimants
+        .iter()
+        .map(|claimant| {
+            let condition = describe_predicate(&claimant.predicate);
+            let summary = templates::render(
+                "claimable_balance.claimant",
+                &[
+                    ("claimant", &format_account(&claimant.destination)),
+                    ("amount", &shown_amount),
+                    ("asset", asset),
+                    ("condition", &condition),
+                ],
+            );
+            ClaimantExplanation {
+                destination: claimant.destination.clone(),
+                condition,
+                summary,
+                predicate: claimant.predicate.clone(),
+            }
+        })
+        .collect();
+
+    ClaimableBalanceExplanation {
+        id: balance.id.clone(),
+        summary,
+        asset_code: asset.to_string(),
+        asset_issuer: balance.asset_issuer().map(str::to_string),
+        amount,
+        sponsor: balance.sponsor.clone(),
+        clawback_enabled: balance.clawback_enabled,
+        claimants,
+    }
+}
+
+/// Describe when a predicate allows claiming, as a phrase that follows
+/// "can claim ...".
+pub fn describe_predicate(predicate: &ClaimPredicate) -> String {
+    match predicate {
+        ClaimPredicate::Unconditional => "at any time".to_string(),
+        ClaimPredicate::AbsBefore(time) => format!("before {}", format_date(time)),
+        ClaimPredicate::RelBefore(seconds) => format!(
+            "within {} of the balance being created",
+            format_duration(*seconds)
+        ),
+        ClaimPredicate::Not(inner) => match inner.as_ref() {
+            ClaimPredicate::Unconditional => "never".to_string(),
+            ClaimPredicate::AbsBefore(time) => format!("any time after {}", format_date(time)),
+            ClaimPredicate::RelBefore(seconds) => format!(
+                "any time from {} after the balance was created",
+                format_duration(*seconds)
+            ),
+            other => format!("except {}", describe_predicate(other)),
+        },
+        ClaimPredicate::And(all) => join(all, " and "),
+        ClaimPredicate::Or(any) => join(any, ", or "),
+    }
+}
+
+fn join(predicates: &[ClaimPredicate], separator: &str) -> String {
+    predicates
+        .iter()
+        .map(describe_predicate)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// "1 day", "6 hours", "90 seconds" — the largest unit that divides evenly.
+fn format_duration(seconds: u64) -> String {
+    let (value, unit) = match seconds {
+        s if s > 0 && s % 86_400 == 0 => (s / 86_400, "day"),
+        s if s > 0 && s % 3_600 == 0 => (s / 3_600, "hour"),
+        s if s > 0 && s % 60 == 0 => (s / 60, "minute"),
+        s => (s, "second"),
+    };
+    format!("{value} {unit}{}", if value == 1 { "" } else { "s" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::claimable_balance::Claimant;
+
+    const ID: &str = "00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be";
+    const CLAIMANT: &str = "GCKFBEIYV2U22IO2BJ4KVJOIP7XPWQGQFKKWXR6DOSJBV7STMAQSMTGG";
+    const SPONSOR: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    fn balance(predicate: ClaimPredicate) -> ClaimableBalance {
+        ClaimableBalance {
+            id: ID.to_string(),
+            asset: format!("USDC:{SPONSOR}"),
+            amount: "100.0000000".to_string(),
+            sponsor: Some(SPONSOR.to_string()),
+            claimants: vec![Claimant {
+                destination: CLAIMANT.to_string(),
+                predicate,
+            }],
+            last_modified_ledger: Some(1000),
+            clawback_enabled: false,
+        }
+    }
+
+    #[test]
+    fn test_explain_time_locked_balance() {
+        let explanation = explain_claimable_balance(&balance(ClaimPredicate::Not(Box::new(
+            ClaimPredicate::AbsBefore("2025-03-03T00:00:00Z".to_string()),
+        ))));
+
+        assert_eq!(
+            explanation.summary,
+            format!(
+                "Claimable balance 00000000...9b91f5be holds 100 USDC. Its reserve is sponsored by {SPONSOR}."
+            )
+        );
+        assert_eq!(explanation.amount, "100");
+        assert_eq!(explanation.asset_issuer.as_deref(), Some(SPONSOR));
+        assert_eq!(
+            explanation.claimants[0].summary,
+            format!("{CLAIMANT} can claim 100 USDC any time after March 3, 2025.")
+        );
+    }
+
+    #[test]
+    fn test_describe_predicates() {
+        assert_eq!(
+            describe_predicate(&ClaimPredicate::Unconditional),
+            "at any time"
+        );
+        assert_eq!(
+            describe_predicate(&ClaimPredicate::RelBefore(86_400)),
+            "within 1 day of the balance being created"
+        );
+        assert_eq!(
+            describe_predicate(&ClaimPredicate::And(vec![
+                ClaimPredicate::Not(Box::new(ClaimPredicate::AbsBefore(
+                    "2025-03-03T00:00:00Z".to_string()
+                ))),
+                ClaimPredicate::AbsBefore("2025-04-01T12:30:00Z".to_string()),
+            ])),
+            "any time after March 3, 2025 and before April 1, 2025 at 12:30 UTC"
+        );
+        assert_eq!(
+            describe_predicate(&ClaimPredicate::Not(Box::new(ClaimPredicate::RelBefore(
+                7_200
+            )))),
+            "any time from 2 hours after the balance was created"
+        );
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(90), "90 seconds");
+        assert_eq!(format_duration(60), "1 minute");
+        assert_eq!(format_duration(172_800), "2 days");
+    }
+}
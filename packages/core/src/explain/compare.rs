@@ -0,0 +1,564 @@
+//! Side-by-side comparison of two transactions.
+//!
+//! Support teams often need to answer "why did this payment work but that
+//! one fail?". This module lists every meaningful difference between two
+//! transactions — outcome, operations, amounts, fees, and memos — in plain
+//! English.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::failure::explain_failure;
+use crate::explain::format::{count, format_amount, format_exact};
+use crate::explain::memo::explain_memo;
+use crate::explain::templates;
+use crate::models::amount::Amount;
+use crate::models::fee::FeeStats;
+use crate::models::operation::Operation;
+use crate::models::transaction::Transaction;
+
+/// A single difference between the two compared transactions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TransactionDifference {
+    /// What differs, e.g. "outcome", "fee", "operations[0].amount".
+    pub field: String,
+    /// Value on transaction A, rendered for display.
+    pub a: Option<String>,
+    /// Value on transaction B, rendered for display.
+    pub b: Option<String>,
+    /// Plain-English description of the difference.
+    pub explanation: String,
+}
+
+/// Result of comparing two transactions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TransactionComparison {
+    pub a: String,
+    pub b: String,
+    /// True when no differences were found beyond the hashes.
+    pub identical: bool,
+    pub summary: String,
+    pub differences: Vec<TransactionDifference>,
+}
+
+/// Compare two transactions and explain how they differ.
+pub fn compare_transactions(a: &Transaction, b: &Transaction) -> TransactionComparison {
+    let mut differences = Vec::new();
+
+    if a.successful != b.successful {
+        let (succeeded_label, failed_label, failed) = if a.successful {
+            ("A", "B", b)
+        } else {
+            ("B", "A", a)
+        };
+        let mut explanation = templates::render(
+            "compare.outcome",
+            &[("succeeded", succeeded_label), ("failed", failed_label)],
+        );
+        if let Some(reason) = failure_reason(failed) {
+            explanation.push(' ');
+            explanation.push_str(&reason);
+        }
+        differences.push(TransactionDifference {
+            field: "outcome".to_string(),
+            a: Some(outcome(a).to_string()),
+            b: Some(outcome(b).to_string()),
+            explanation,
+        });
+    }
+
+    if a.operations.len() != b.operations.len() {
+        differences.push(TransactionDifference {
+            field: "operation_count".to_string(),
+            a: Some(a.operations.len().to_string()),
+            b: Some(b.operations.len().to_string()),
+            explanation: templates::render(
+                "compare.operation_count",
+                &[
+                    (
+                        "operations_a",
+                        &count(a.operations.len(), "operation", "operations"),
+                    ),
+                    ("operations_b", &b.operations.len().to_string()),
+                ],
+            ),
+        });
+    }
+
+    for (index, (op_a, op_b)) in a.operations.iter().zip(b.operations.iter()).enumerate() {
+        compare_operations(index, op_a, op_b, &mut differences);
+    }
+
+    if a.fee_charged != b.fee_charged {
+        differences.push(TransactionDifference {
+            field: "fee".to_string(),
+            a: Some(format!("{} XLM", FeeStats::stroops_to_xlm(a.fee_charged))),
+            b: Some(format!("{} XLM", FeeStats::stroops_to_xlm(b.fee_charged))),
+            explanation: templates::render(
+                "compare.fee",
+                &[
+                    (
+                        "fee_a",
+                        &format_amount(&FeeStats::stroops_to_xlm(a.fee_charged)),
+                    ),
+                    (
+                        "fee_b",
+                        &format_amount(&FeeStats::stroops_to_xlm(b.fee_charged)),
+                    ),
+                ],
+            ),
+        });
+    }
+
+    if a.memo != b.memo {
+        let memo_a = a.memo.as_ref().and_then(explain_memo);
+        let memo_b = b.memo.as_ref().and_then(explain_memo);
+        let explanation = templates::render(
+            match (&memo_a, &memo_b) {
+                (Some(_), None) => "compare.memo_only_a",
+                (None, Some(_)) => "compare.memo_only_b",
+                _ => "compare.memo_different",
+            },
+            &[],
+        );
+        differences.push(TransactionDifference {
+            field: "memo".to_string(),
+            a: memo_a,
+            b: memo_b,
+            explanation,
+        });
+    }
+
+    let identical = differences.is_empty();
+    let summary = if identical {
+        templates::render("compare.identical", &[])
+    } else {
+        templates::render(
+            "compare.summary",
+            &[
+                (
+                    "differences",
+                    &count(differences.len(), "difference", "differences"),
+                ),
+                ("first", &differences[0].explanation),
+            ],
+        )
+    };
+
+    TransactionComparison {
+        a: a.hash.clone(),
+        b: b.hash.clone(),
+        identical,
+        summary,
+        differences,
+    }
+}
+
+fn compare_operations(
+    index: usize,
+    op_a: &Operation,
+    op_b: &Operation,
+    differences: &mut Vec<TransactionDifference>,
+) {
+    let type_a = operation_type(op_a);
+    let type_b = operation_type(op_b);
+    if type_a != type_b {
+        differences.push(TransactionDifference {
+            field: format!("operations[{index}].type"),
+            a: Some(type_a.to_string()),
+            b: Some(type_b.to_string()),
+            explanation: templates::render(
+                "compare.operation_type",
+                &[
+                    ("index", &(index + 1).to_string()),
+                    ("type_a", type_a),
+                    ("type_b", type_b),
+                ],
+            ),
+        });
+        return;
+    }
+
+    let label = operation_label(op_a);
+    let fields_a = operation_fields(op_a);
+    let fields_b = operation_fields(op_b);
+    for (field_a, field_b) in fields_a.iter().zip(fields_b.iter()) {
+        if field_a.value != field_b.value {
+            differences.push(TransactionDifference {
+                field: format!("operations[{index}].{}", field_a.name),
+                a: Some(field_a.value.clone()),
+                b: Some(field_b.value.clone()),
+                explanation: templates::render(
+                    "compare.operation_field",
+                    &[
+                        ("operation", label),
+                        ("index", &(index + 1).to_string()),
+                        ("field", field_a.noun),
+                        ("a", &field_a.display),
+                        ("b", &field_b.display),
+                    ],
+                ),
+            });
+        }
+    }
+}
+
+/// One comparable field of an operation.
+struct OperationField {
+    name: &'static str,
+    /// How the field is named in explanations.
+    noun: &'static str,
+    value: String,
+    /// The value as explanations show it.
+    display: String,
+}
+
+impl OperationField {
+    fn text(name: &'static str, noun: &'static str, value: &str) -> Self {
+        Self {
+            name,
+            noun,
+            value: value.to_string(),
+            display: value.to_string(),
+        }
+    }
+
+    fn amount(name: &'static str, noun: &'static str, amount: Amount) -> Self {
+        Self {
+            name,
+            noun,
+            value: amount.to_string(),
+            display: format_exact(amount),
+        }
+    }
+
+    fn optional<T: ToString>(name: &'static str, noun: &'static str, value: &Option<T>) -> Self {
+        let value = value
+            .as_ref()
+            .map_or_else(|| "none".to_string(), ToString::to_string);
+        Self::text(name, noun, &value)
+    }
+}
+
+/// The amounts, counterparties and assets of an operation, in the same
+/// order for every operation of one type.
+fn operation_fields(op: &Operation) -> Vec<OperationField> {
+    match op {
+        Operation::Payment(p) => vec![
+            OperationField::amount("amount", "amount", p.amount),
+            OperationField::text("asset", "asset", p.asset_code.as_deref().unwrap_or("XLM")),
+            OperationField::text("destination", "destination", &p.destination),
+        ],
+        Operation::PathPayment(p) => vec![
+            OperationField::amount("send_amount", "send amount", p.send_amount),
+            OperationField::text("send_asset", "send asset", &p.send_asset),
+            OperationField::amount("dest_amount", "destination amount", p.dest_amount),
+            OperationField::text("dest_asset", "destination asset", &p.dest_asset),
+            OperationField::text("destination", "destination", &p.destination),
+            OperationField::text("path", "path", &p.path.join(" → ")),
+        ],
+        Operation::CreateAccount(c) => vec![
+            OperationField::amount("starting_balance", "starting balance", c.starting_balance),
+            OperationField::text("new_account", "new account", &c.new_account),
+            OperationField::text("funder", "funder", &c.funder),
+        ],
+        Operation::ChangeTrust(c) => vec![
+            OperationField::amount("limit", "limit", c.limit),
+            OperationField::text("asset_code", "asset", &c.asset_code),
+            OperationField::text("asset_issuer", "issuer", &c.asset_issuer),
+            OperationField::text("trustor", "trustor", &c.trustor),
+        ],
+        Operation::ManageOffer(o) => vec![
+            OperationField::amount("amount", "amount", o.amount),
+            OperationField::text("price", "price", &o.price),
+            OperationField::text("selling_asset", "selling asset", &o.selling_asset),
+            OperationField::text("buying_asset", "buying asset", &o.buying_asset),
+            OperationField::text("offer_id", "offer ID", &o.offer_id.to_string()),
+            OperationField::text("seller", "seller", &o.seller),
+        ],
+        Operation::Clawback(c) => vec![
+            OperationField::amount("amount", "amount", c.amount),
+            OperationField::text("asset_code", "asset", &c.asset_code),
+            OperationField::text("asset_issuer", "issuer", &c.asset_issuer),
+            OperationField::text("from", "holder", &c.from),
+        ],
+        Operation::ClawbackClaimableBalance(c) => vec![OperationField::text(
+            "balance_id",
+            "claimable balance",
+            &c.balance_id,
+        )],
+        Operation::AccountMerge(m) => vec![
+            OperationField::text("destination", "destination", &m.destination),
+            OperationField::text("source", "source", &m.source),
+        ],
+        Operation::SetOptions(o) => vec![
+            OperationField::optional("home_domain", "home domain", &o.home_domain),
+            OperationField::optional("signer_key", "signer", &o.signer_key),
+            OperationField::optional("signer_weight", "signer weight", &o.signer_weight),
+            OperationField::optional("master_weight", "master key weight", &o.master_weight),
+            OperationField::optional("low_threshold", "low threshold", &o.low_threshold),
+            OperationField::optional("med_threshold", "medium threshold", &o.med_threshold),
+            OperationField::optional("high_threshold", "high threshold", &o.high_threshold),
+            OperationField::optional("set_flags", "flags set", &o.set_flags),
+            OperationField::optional("clear_flags", "flags cleared", &o.clear_flags),
+            OperationField::optional("inflation_dest", "inflation destination", &o.inflation_dest),
+        ],
+        Operation::Other(_) => vec![],
+    }
+}
+
+/// How explanations refer to an operation of this type.
+fn operation_label(op: &Operation) -> &'static str {
+    match op {
+        Operation::Payment(_) => "Payment",
+        Operation::PathPayment(_) => "Path payment",
+        Operation::CreateAccount(_) => "Account creation",
+        Operation::ChangeTrust(_) => "Trust line change",
+        Operation::ManageOffer(_) => "Offer",
+        Operation::Clawback(_) => "Clawback",
+        Operation::ClawbackClaimableBalance(_) => "Claimable balance clawback",
+        Operation::AccountMerge(_) => "Account merge",
+        Operation::SetOptions(_) => "Options change",
+        Operation::Other(_) => "Operation",
+    }
+}
+
+fn operation_type(op: &Operation) -> &str {
+    match op {
+        Operation::Payment(_) => "payment",
+        Operation::SetOptions(_) => "set_options",
+        Operation::CreateAccount(_) => "create_account",
+        Operation::ChangeTrust(_) => "change_trust",
+        Operation::ManageOffer(_) => "manage_offer",
+        Operation::PathPayment(_) => "path_payment",
+        Operation::Clawback(_) => "clawback",
+        Operation::ClawbackClaimableBalance(_) => "clawback_claimable_balance",
+        Operation::AccountMerge(_) => "account_merge",
+        Operation::Other(o) => &o.operation_type,
+    }
+}
+
+fn outcome(tx: &Transaction) -> &'static str {
+    if tx.successful {
+        "successful"
+    } else {
+        "failed"
+    }
+}
+
+/// The most specific failure explanation available: the first failing
+/// operation, otherwise the transaction-level reason.
+fn failure_reason(tx: &Transaction) -> Option<String> {
+    let codes = tx.result_codes.as_ref()?;
+    let (tx_reason, op_failures) = explain_failure(codes.transaction.as_deref(), &codes.operations);
+    match op_failures.first() {
+        Some(failure) => Some(templates::render(
+            "compare.operation_failed",
+            &[
+                ("index", &(failure.index + 1).to_string()),
+                ("reason", &failure.explanation),
+            ],
+        )),
+        None => tx_reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::memo::Memo;
+    use crate::models::operation::{
+        CreateAccountOperation, ManageOfferOperation, OfferType, OtherOperation,
+        PathPaymentOperation, PathPaymentType, PaymentOperation,
+    };
+    use crate::models::transaction::ResultCodes;
+
+    fn payment(amount: &str, destination: &str) -> Operation {
+        Operation::Payment(PaymentOperation {
+            id: "1".to_string(),
+            source_account: Some("GSENDER".to_string()),
+            destination: destination.to_string(),
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+            amount: amount.parse().unwrap(),
+        })
+    }
+
+    fn tx(hash: &str, operations: Vec<Operation>) -> Transaction {
+        Transaction {
+            hash: hash.to_string(),
+            successful: true,
+            fee_charged: 100,
+            operations,
+            memo: None,
+            result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
+        }
+    }
+
+    #[test]
+    fn test_identical_transactions() {
+        let a = tx("a", vec![payment("10", "GDEST")]);
+        let b = tx("b", vec![payment("10", "GDEST")]);
+        let comparison = compare_transactions(&a, &b);
+        assert!(comparison.identical);
+        assert!(comparison.differences.is_empty());
+        assert!(comparison.summary.contains("equivalent"));
+    }
+
+    #[test]
+    fn test_outcome_difference_includes_failure_reason() {
+        let a = tx("a", vec![payment("10", "GDEST")]);
+        let mut b = tx("b", vec![payment("10", "GDEST")]);
+        b.successful = false;
+        b.result_codes = Some(ResultCodes {
+            transaction: Some("tx_failed".to_string()),
+            operations: vec!["op_no_trust".to_string()],
+        });
+
+        let comparison = compare_transactions(&a, &b);
+        let outcome = &comparison.differences[0];
+        assert_eq!(outcome.field, "outcome");
+        assert!(outcome.explanation.contains("transaction B failed"));
+        assert!(outcome.explanation.contains("not opted in"));
+        assert!(comparison.summary.contains("transaction B failed"));
+    }
+
+    #[test]
+    fn test_payment_field_differences() {
+        let a = tx("a", vec![payment("10", "GDEST")]);
+        let b = tx("b", vec![payment("25", "GOTHER")]);
+        let comparison = compare_transactions(&a, &b);
+        let fields: Vec<&str> = comparison
+            .differences
+            .iter()
+            .map(|d| d.field.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["operations[0].amount", "operations[0].destination"]
+        );
+    }
+
+    fn path_payment(dest_amount: &str, dest_asset: &str) -> Operation {
+        Operation::PathPayment(PathPaymentOperation {
+            id: "1".to_string(),
+            source_account: Some("GSENDER".to_string()),
+            destination: "GDEST".to_string(),
+            send_asset: "XLM".to_string(),
+            send_amount: "100".parse().unwrap(),
+            dest_asset: dest_asset.to_string(),
+            dest_amount: dest_amount.parse().unwrap(),
+            path: vec![],
+            payment_type: PathPaymentType::StrictSend,
+        })
+    }
+
+    #[test]
+    fn test_path_payment_field_differences() {
+        let a = tx("a", vec![path_payment("10", "USDC")]);
+        let b = tx("b", vec![path_payment("12.5", "EURC")]);
+        let comparison = compare_transactions(&a, &b);
+        assert!(!comparison.identical);
+        let fields: Vec<&str> = comparison
+            .differences
+            .iter()
+            .map(|d| d.field.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["operations[0].dest_amount", "operations[0].dest_asset"]
+        );
+        assert!(
+            comparison.differences[0]
+                .explanation
+                .starts_with("Path payment 1 has a different destination amount")
+        );
+    }
+
+    #[test]
+    fn test_create_account_and_offer_differences() {
+        let create = |balance: &str, new_account: &str| {
+            Operation::CreateAccount(CreateAccountOperation {
+                id: "1".to_string(),
+                funder: "GFUNDER".to_string(),
+                new_account: new_account.to_string(),
+                starting_balance: balance.parse().unwrap(),
+            })
+        };
+        let offer = |price: &str| {
+            Operation::ManageOffer(ManageOfferOperation {
+                id: "2".to_string(),
+                seller: "GSELLER".to_string(),
+                selling_asset: "XLM".to_string(),
+                buying_asset: "USDC".to_string(),
+                amount: "50".parse().unwrap(),
+                price: price.to_string(),
+                offer_id: 0,
+                offer_type: OfferType::Sell,
+            })
+        };
+        let a = tx("a", vec![create("5", "GNEW"), offer("0.1")]);
+        let b = tx("b", vec![create("5", "GOTHER"), offer("0.2")]);
+        let comparison = compare_transactions(&a, &b);
+        let fields: Vec<&str> = comparison
+            .differences
+            .iter()
+            .map(|d| d.field.as_str())
+            .collect();
+        assert_eq!(
+            fields,
+            vec!["operations[0].new_account", "operations[1].price"]
+        );
+        assert_eq!(
+            comparison.differences[1].explanation,
+            "Offer 2 has a different price: 0.1 in transaction A versus 0.2 in transaction B."
+        );
+    }
+
+    #[test]
+    fn test_operation_type_and_count_differences() {
+        let a = tx("a", vec![payment("10", "GDEST")]);
+        let b = tx(
+            "b",
+            vec![
+                Operation::Other(OtherOperation {
+                    id: "2".to_string(),
+                    operation_type: "bump_sequence".to_string(),
+                }),
+                payment("10", "GDEST"),
+            ],
+        );
+        let comparison = compare_transactions(&a, &b);
+        assert_eq!(comparison.differences[0].field, "operation_count");
+        assert_eq!(comparison.differences[1].field, "operations[0].type");
+        assert!(
+            comparison.differences[1]
+                .explanation
+                .contains("bump_sequence")
+        );
+    }
+
+    #[test]
+    fn test_fee_and_memo_differences() {
+        let a = tx("a", vec![payment("10", "GDEST")]);
+        let mut b = tx("b", vec![payment("10", "GDEST")]);
+        b.fee_charged = 200;
+        b.memo = Some(Memo::id(42));
+
+        let comparison = compare_transactions(&a, &b);
+        assert_eq!(comparison.differences.len(), 2);
+        assert_eq!(comparison.differences[0].field, "fee");
+        assert_eq!(comparison.differences[1].field, "memo");
+        assert!(
+            comparison.differences[1]
+                .explanation
+                .contains("Transaction B has a memo")
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! "Learn more" links for operation types.
+//!
+//! Every operation explanation carries a `learn_more` URL pointing at the
+//! Stellar developer docs for its operation type. The built-in links point
+//! at each operation's section of the list of operations. Operators can
+//! replace or remove links from a TOML file loaded at startup, so a moved
+//! docs page does not need a new build:
+//!
+//! ```toml
+//! payment = "https://developers.stellar.org/docs/build/guides/transactions/send-and-receive-payments"
+//! inflation = ""   # no link
+//! ```
+//!
+//! Keys must be known operation types and links must be http(s) URLs, so a
+//! typo is caught when the file is loaded.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::{env, fmt, fs};
+
+const LIST_OF_OPERATIONS: &str =
+    "https://developers.stellar.org/docs/learn/fundamentals/transactions/list-of-operations";
+
+/// Operation types with a built-in link, and the section each links to.
+const SECTIONS: &[(&str, &str)] = &[
+    ("create_account", "create-account"),
+    ("payment", "payment"),
+    ("path_payment_strict_send", "path-payment-strict-send"),
+    ("path_payment_strict_receive", "path-payment-strict-receive"),
+    ("manage_sell_offer", "manage-sell-offer"),
+    ("manage_buy_offer", "manage-buy-offer"),
+    ("create_passive_sell_offer", "create-passive-sell-offer"),
+    ("set_options", "set-options"),
+    ("change_trust", "change-trust"),
+    ("allow_trust", "allow-trust"),
+    ("account_merge", "account-merge"),
+    ("inflation", "inflation"),
+    ("manage_data", "manage-data"),
+    ("bump_sequence", "bump-sequence"),
+    ("create_claimable_balance", "create-claimable-balance"),
+    ("claim_claimable_balance", "claim-claimable-balance"),
+    (
+        "begin_sponsoring_future_reserves",
+        "begin-sponsoring-future-reserves",
+    ),
+    (
+        "end_sponsoring_future_reserves",
+        "end-sponsoring-future-reserves",
+    ),
+    ("revoke_sponsorship", "revoke-sponsorship"),
+    ("clawback", "clawback"),
+    ("clawback_claimable_balance", "clawback-claimable-balance"),
+    ("set_trust_line_flags", "set-trustline-flags"),
+    ("liquidity_pool_deposit", "liquidity-pool-deposit"),
+    ("liquidity_pool_withdraw", "liquidity-pool-withdraw"),
+    ("invoke_host_function", "invoke-host-function"),
+    ("extend_footprint_ttl", "extend-footprint-ttl"),
+    ("restore_footprint", "restore-footprint"),
+];
+
+/// Errors raised while loading a doc links file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DocLinksError {
+    Io(String),
+    Parse(String),
+    UnknownOperation(String),
+    NotAString(String),
+    InvalidUrl { operation: String, url: String },
+}
+
+impl fmt::Display for DocLinksError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DocLinksError::Io(msg) => write!(f, "could not read doc links file: {msg}"),
+            DocLinksError::Parse(msg) => write!(f, "invalid doc links file: {msg}"),
+            DocLinksError::UnknownOperation(key) => write!(f, "unknown operation type `{key}`"),
+            DocLinksError::NotAString(key) => write!(f, "link for `{key}` must be a string"),
+            DocLinksError::InvalidUrl { operation, url } => {
+                write!(f, "link for `{operation}` is not an http(s) URL: {url}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DocLinksError {}
+
+/// The active links: built-in defaults plus any validated operator
+/// overrides. An override of `None` removes the link.
+#[derive(Debug, Clone, Default)]
+pub struct DocLinks {
+    overrides: HashMap<String, Option<String>>,
+}
+
+impl DocLinks {
+    /// Parse and validate overrides from a flat TOML table of operation
+    /// type to URL. An empty string removes that type's link.
+    pub fn from_toml_str(source: &str) -> Result<Self, DocLinksError> {
+        let table: toml::Table = source
+            .parse()
+            .map_err(|e: toml::de::Error| DocLinksError::Parse(e.to_string()))?;
+
+        let mut overrides = HashMap::new();
+        for (operation, value) in table {
+            if section_for(&operation).is_none() {
+                return Err(DocLinksError::UnknownOperation(operation));
+            }
+            let url = match value {
+                toml::Value::String(url) => url.trim().to_string(),
+                _ => return Err(DocLinksError::NotAString(operation)),
+            };
+            if url.is_empty() {
+                overrides.insert(operation, None);
+                continue;
+            }
+            if !(url.starts_with("https://") || url.starts_with("http://")) {
+                return Err(DocLinksError::InvalidUrl { operation, url });
+            }
+            overrides.insert(operation, Some(url));
+        }
+        Ok(Self { overrides })
+    }
+
+    /// Load overrides from the file named by `DOC_LINKS_PATH`. Returns the
+    /// defaults when the variable is unset.
+    pub fn from_env() -> Result<Self, DocLinksError> {
+        match env::var("DOC_LINKS_PATH") {
+            Ok(path) => {
+                let source = fs::read_to_string(&path)
+                    .map_err(|e| DocLinksError::Io(format!("{path}: {e}")))?;
+                Self::from_toml_str(&source)
+            }
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Number of links replaced or removed by the operator.
+    pub fn override_count(&self) -> usize {
+        self.overrides.len()
+    }
+
+    /// The docs link for `operation_type`, if it has one.
+    pub fn learn_more(&self, operation_type: &str) -> Option<String> {
+        match self.overrides.get(operation_type) {
+            Some(link) => link.clone(),
+            None => default_link(operation_type),
+        }
+    }
+}
+
+static DOC_LINKS: OnceLock<DocLinks> = OnceLock::new();
+
+/// Install the links used by all explainers. Call once at startup; later
+/// calls are ignored.
+pub fn install(links: DocLinks) {
+    let _ = DOC_LINKS.set(links);
+}
+
+/// The docs link for `operation_type` from the installed links, falling
+/// back to the built-in defaults when none were installed.
+pub fn learn_more(operation_type: &str) -> Option<String> {
+    match DOC_LINKS.get() {
+        Some(links) => links.learn_more(operation_type),
+        None => default_link(operation_type),
+    }
+}
+
+fn section_for(operation_type: &str) -> Option<&'static str> {
+    SECTIONS
+        .iter()
+        .find(|(name, _)| *name == operation_type)
+        .map(|(_, section)| *section)
+}
+
+fn default_link(operation_type: &str) -> Option<String> {
+    section_for(operation_type).map(|section| format!("{LIST_OF_OPERATIONS}#{section}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_links() {
+        let links = DocLinks::default();
+        assert_eq!(
+            links.learn_more("payment").as_deref(),
+            Some(
+                "https://developers.stellar.org/docs/learn/fundamentals/transactions/list-of-operations#payment"
+            )
+        );
+        assert!(
+            links
+                .learn_more("set_trust_line_flags")
+                .unwrap()
+                .ends_with("#set-trustline-flags")
+        );
+        assert_eq!(links.learn_more("not_an_operation"), None);
+    }
+
+    #[test]
+    fn test_overrides_replace_and_remove_links() {
+        let links = DocLinks::from_toml_str(
+            "payment = \"https://docs.example/payments\"\ninflation = \"\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(links.override_count(), 2);
+        assert_eq!(
+            links.learn_more("payment").as_deref(),
+            Some("https://docs.example/payments")
+        );
+        assert_eq!(links.learn_more("inflation"), None);
+        assert!(links.learn_more("clawback").is_some());
+    }
+
+    #[test]
+    fn test_invalid_files_are_rejected() {
+        assert_eq!(
+            DocLinks::from_toml_str("paymnet = \"https://docs.example\"").unwrap_err(),
+            DocLinksError::UnknownOperation("paymnet".to_string())
+        );
+        assert_eq!(
+            DocLinks::from_toml_str("payment = 3").unwrap_err(),
+            DocLinksError::NotAString("payment".to_string())
+        );
+        assert!(matches!(
+            DocLinks::from_toml_str("payment = \"docs.example/payments\""),
+            Err(DocLinksError::InvalidUrl { .. })
+        ));
+        assert!(matches!(
+            DocLinks::from_toml_str("payment = "),
+            Err(DocLinksError::Parse(_))
+        ));
+    }
+}
@@ -1,6 +1,9 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+use crate::explain::templates;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct OperationFailure {
     pub index: usize,
     pub code: String,
@@ -8,40 +11,36 @@ pub struct OperationFailure {
 }
 
 pub fn translate_tx_code(code: &str) -> String {
-    let explanation = match code {
-        "tx_bad_seq" => {
-            "Sequence number is out of date — another transaction from this account may have been submitted first. Try again."
-        }
-        "tx_bad_auth" => "The transaction was not properly signed by the required keys.",
-        "tx_insufficient_balance" => {
-            "The account does not have enough XLM to cover this transaction and the minimum balance."
-        }
-        "tx_no_account" => "The source account does not exist on the Stellar network.",
-        "tx_insufficient_fee" => "The fee offered was too low.",
-        "tx_too_early" => "The transaction was submitted before its minimum time boundary.",
-        "tx_too_late" => "The transaction expired before it was processed.",
-        "tx_missing_operation" => "The transaction contains no operations.",
-        "tx_bad_auth_extra" => "The transaction has more signatures than required.",
-        _ => "An unexpected transaction error occurred.",
+    let key = match code {
+        "tx_bad_seq" => "failure.tx_bad_seq",
+        "tx_bad_auth" => "failure.tx_bad_auth",
+        "tx_insufficient_balance" => "failure.tx_insufficient_balance",
+        "tx_no_account" => "failure.tx_no_account",
+        "tx_insufficient_fee" => "failure.tx_insufficient_fee",
+        "tx_too_early" => "failure.tx_too_early",
+        "tx_too_late" => "failure.tx_too_late",
+        "tx_missing_operation" => "failure.tx_missing_operation",
+        "tx_bad_auth_extra" => "failure.tx_bad_auth_extra",
+        _ => "failure.tx_unknown",
     };
-    format!("Transaction failed: {explanation}")
+    templates::render(
+        "failure.transaction",
+        &[("explanation", &templates::render(key, &[]))],
+    )
 }
 
-pub fn translate_op_code(code: &str) -> &str {
-    match code {
-        "op_no_trust" => "The destination account has not opted in to hold this asset.",
-        "op_underfunded" => "The source account does not have enough of this asset to send.",
-        "op_no_destination" => "The destination account does not exist on the Stellar network.",
-        "op_not_authorized" => {
-            "The asset issuer has not authorised this account to hold the asset."
-        }
-        "op_line_full" => "The destination account's trust line is full and cannot receive more.",
-        "op_no_issuer" => "The asset issuer account does not exist.",
-        "op_low_reserve" => {
-            "The account would fall below the minimum XLM reserve after this operation."
-        }
-        _ => "An unexpected operation error occurred.",
-    }
+pub fn translate_op_code(code: &str) -> String {
+    let key = match code {
+        "op_no_trust" => "failure.op_no_trust",
+        "op_underfunded" => "failure.op_underfunded",
+        "op_no_destination" => "failure.op_no_destination",
+        "op_not_authorized" => "failure.op_not_authorized",
+        "op_line_full" => "failure.op_line_full",
+        "op_no_issuer" => "failure.op_no_issuer",
+        "op_low_reserve" => "failure.op_low_reserve",
+        _ => "failure.op_unknown",
+    };
+    templates::render(key, &[])
 }
 
 /// Translate raw Horizon result codes into human-readable failure explanations.
@@ -61,7 +60,7 @@ pub fn explain_failure(
         .map(|(index, code)| OperationFailure {
             index,
             code: code.clone(),
-            explanation: translate_op_code(code).to_string(),
+            explanation: translate_op_code(code),
         })
         .collect();
 
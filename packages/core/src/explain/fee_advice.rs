@@ -0,0 +1,264 @@
+//! What max fee to bid for a transaction to land within a few ledgers.
+//!
+//! A transaction's fee is a bid: it pays only what its ledger charges, the
+//! base fee unless more transactions want in than the ledger holds. Then the
+//! ledger is surge priced, and the lowest bids wait. The advice reads how
+//! full the latest ledger was (Horizon has no view of the queue itself, so
+//! this stands in for its depth), how many recent ledgers were full, and
+//! the fees recent transactions paid, and picks a bid from a higher
+//! percentile of those fees the sooner the transaction must land.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::format_exact;
+use crate::explain::templates;
+use crate::models::amount::Amount;
+use crate::models::fee::{FeeMarket, FeeStats, LedgerLoad};
+
+/// A ledger using this share of its capacity counts as full. Transaction
+/// sets rarely fill to the last operation, even when surge priced.
+const FULL_USAGE: f64 = 0.95;
+
+/// A ledger using this share of its capacity is busy.
+const BUSY_USAGE: f64 = 0.5;
+
+/// How crowded the network is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Congestion {
+    /// Ledgers have room to spare; the base fee is enough.
+    Quiet,
+    /// Ledgers are filling, or some recent ones were full.
+    Busy,
+    /// The latest ledger, or most recent ones, were full and surge priced.
+    Surging,
+}
+
+impl Congestion {
+    fn describe(self) -> &'static str {
+        match self {
+            Congestion::Quiet => "quiet",
+            Congestion::Busy => "busy",
+            Congestion::Surging => "surge pricing",
+        }
+    }
+}
+
+/// A suggested max fee, and why.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct FeeAdvice {
+    /// Ledgers the transaction should land within.
+    pub target_ledgers: u32,
+    /// Suggested max fee per operation, in stroops.
+    pub max_fee_stroops: u64,
+    /// `max_fee_stroops` in XLM.
+    pub max_fee_xlm: String,
+    pub base_fee_stroops: u64,
+    pub congestion: Congestion,
+    /// Share of the latest ledger's capacity used, in percent.
+    pub capacity_usage_percent: u32,
+    /// Recent ledgers that were full.
+    pub surge_ledgers: u32,
+    pub ledgers_scanned: u32,
+    /// Percentile of recently charged fees the suggestion is taken from;
+    /// absent when the base fee is enough.
+    pub percentile: Option<u8>,
+    pub summary: String,
+    /// One sentence per fact the suggestion rests on.
+    pub rationale: Vec<String>,
+}
+
+/// The percentile of charged fees to bid at to land within `target_ledgers`.
+fn percentile_for(target_ledgers: u32) -> u8 {
+    match target_ledgers {
+        0 | 1 => 99,
+        2 => 95,
+        3..=5 => 90,
+        6..=10 => 70,
+        _ => 50,
+    }
+}
+
+/// Advise a max fee to land within `target_ledgers`, from `market` and the
+/// `loads` of recent ledgers.
+pub fn advise_fee(target_ledgers: u32, market: &FeeMarket, loads: &[LedgerLoad]) -> FeeAdvice {
+    let ledgers_scanned = loads.len() as u32;
+    let surge_ledgers = loads
+        .iter()
+        .filter(|load| load.usage() >= FULL_USAGE)
+        .count() as u32;
+
+    let congestion = if market.capacity_usage >= FULL_USAGE
+        || (ledgers_scanned > 0 && surge_ledgers * 2 >= ledgers_scanned)
+    {
+        Congestion::Surging
+    } else if market.capacity_usage >= BUSY_USAGE || surge_ledgers > 0 {
+        Congestion::Busy
+    } else {
+        Congestion::Quiet
+    };
+
+    let percentile = (congestion != Congestion::Quiet).then(|| percentile_for(target_ledgers));
+    let max_fee = percentile
+        .map(|p| market.charged.percentile(p))
+        .unwrap_or(0)
+        .max(market.base_fee);
+
+    let target = match target_ledgers {
+        0 | 1 => "the next ledger".to_string(),
+        n => format!("{n} ledgers"),
+    };
+    let base_fee = market.base_fee.to_string();
+    let capacity_usage_percent = (market.capacity_usage * 100.0).round() as u32;
+
+    let mut rationale = vec![templates::render(
+        "fee_advice.capacity",
+        &[("usage", &capacity_usage_percent.to_string())],
+    )];
+    if ledgers_scanned > 0 {
+        let scanned = ledgers_scanned.to_string();
+        rationale.push(match surge_ledgers {
+            0 => templates::render("fee_advice.no_surge", &[("scanned", &scanned)]),
+            full => templates::render(
+                "fee_advice.surge_history",
+                &[("full", &full.to_string()), ("scanned", &scanned)],
+            ),
+        });
+    }
+    rationale.push(match percentile {
+        Some(p) => templates::render(
+            "fee_advice.percentile",
+            &[
+                ("target", &target),
+                ("percentile", &ordinal(p)),
+                ("fee", &max_fee.to_string()),
+            ],
+        ),
+        None => templates::render("fee_advice.base_fee", &[("base_fee", &base_fee)]),
+    });
+    rationale.push(templates::render(
+        "fee_advice.pay_charged",
+        &[("base_fee", &base_fee)],
+    ));
+
+    let summary = templates::render(
+        "fee_advice.summary",
+        &[
+            ("fee", &max_fee.to_string()),
+            ("xlm", &format_exact(Amount::from(max_fee))),
+            ("target", &target),
+            ("congestion", congestion.describe()),
+        ],
+    );
+
+    FeeAdvice {
+        target_ledgers,
+        max_fee_stroops: max_fee,
+        max_fee_xlm: FeeStats::stroops_to_xlm(max_fee),
+        base_fee_stroops: market.base_fee,
+        congestion,
+        capacity_usage_percent,
+        surge_ledgers,
+        ledgers_scanned,
+        percentile,
+        summary,
+        rationale,
+    }
+}
+
+fn ordinal(percentile: u8) -> String {
+    format!("{percentile}th")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fee::FeeDistribution;
+
+    fn market(capacity_usage: f64) -> FeeMarket {
+        FeeMarket {
+            last_ledger: 50_000_000,
+            base_fee: 100,
+            capacity_usage,
+            charged: FeeDistribution {
+                mode: 100,
+                p50: 100,
+                p70: 150,
+                p90: 1_000,
+                p95: 2_500,
+                p99: 10_000,
+                max: 50_000,
+            },
+            offered: FeeDistribution::default(),
+        }
+    }
+
+    fn loads(full: usize, total: usize) -> Vec<LedgerLoad> {
+        (0..total)
+            .map(|i| LedgerLoad {
+                sequence: 50_000_000 - i as u64,
+                operations: if i < full { 1_000 } else { 300 },
+                capacity: 1_000,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_quiet_network_needs_only_the_base_fee() {
+        let advice = advise_fee(1, &market(0.3), &loads(0, 20));
+
+        assert_eq!(advice.congestion, Congestion::Quiet);
+        assert_eq!(advice.max_fee_stroops, 100);
+        assert_eq!(advice.max_fee_xlm, "0.0000100");
+        assert_eq!(advice.percentile, None);
+        assert_eq!(
+            advice.summary,
+            "Set a max fee of 100 stroops (0.00001 XLM) per operation to be included within the next ledger; the network is quiet."
+        );
+        assert_eq!(
+            advice.rationale,
+            [
+                "The latest ledger used 30% of its capacity.",
+                "None of the last 20 ledgers were full, so none were surge priced.",
+                "With room to spare, the base fee of 100 stroops per operation is enough to be included in the next ledger.",
+                "The max fee is a ceiling: a transaction pays only what its ledger charges, 100 stroops per operation unless the ledger is surge priced.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_surging_network_bids_higher_the_sooner_it_must_land() {
+        let next = advise_fee(1, &market(1.0), &loads(12, 20));
+        assert_eq!(next.congestion, Congestion::Surging);
+        assert_eq!(next.surge_ledgers, 12);
+        assert_eq!(next.percentile, Some(99));
+        assert_eq!(next.max_fee_stroops, 10_000);
+        assert_eq!(
+            next.rationale[1],
+            "12 of the last 20 ledgers were full, and full ledgers are surge priced: the transactions that bid least wait for a later one."
+        );
+        assert_eq!(
+            next.rationale[2],
+            "To be included within the next ledger, bid at least the 99th percentile of fees charged recently, 10000 stroops per operation."
+        );
+
+        let later = advise_fee(20, &market(1.0), &loads(12, 20));
+        assert_eq!(later.percentile, Some(50));
+        assert_eq!(later.max_fee_stroops, 100);
+    }
+
+    #[test]
+    fn test_a_few_full_ledgers_make_the_network_busy() {
+        let advice = advise_fee(5, &market(0.4), &loads(2, 20));
+        assert_eq!(advice.congestion, Congestion::Busy);
+        assert_eq!(advice.percentile, Some(90));
+        assert_eq!(advice.max_fee_stroops, 1_000);
+        assert!(
+            advice
+                .summary
+                .ends_with("within 5 ledgers; the network is busy.")
+        );
+    }
+}
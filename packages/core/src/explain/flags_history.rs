@@ -0,0 +1,263 @@
+//! When an account's security settings changed.
+//!
+//! Scans an account's set_options operations for changes to its flags,
+//! thresholds, master key weight, and signers, and dates each one: "On
+//! 2024-03-01 the master key was disabled." Operations that only touched
+//! the home domain or inflation destination are left out, as are
+//! set_options operations another account submitted, since those changed
+//! that other account.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::count;
+use crate::explain::operation::set_options::{flag_names, join_changes};
+use crate::explain::signer::describe_signer_key;
+use crate::explain::templates;
+use crate::explain::time::parse_timestamp;
+use crate::models::operation::Operation;
+use crate::services::horizon::HorizonOperation;
+
+/// One set_options operation that changed the account's security settings.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct AccountSettingsChange {
+    /// UTC day of the change, "YYYY-MM-DD", when Horizon reported a time.
+    pub date: Option<String>,
+    pub created_at: Option<String>,
+    pub transaction_hash: String,
+    pub operation_id: String,
+    /// One entry per setting, e.g. "the master key was disabled".
+    pub changes: Vec<String>,
+    /// e.g. "On 2024-03-01 the master key was disabled."
+    pub summary: String,
+}
+
+/// The settings changes found in one scan of an account's operations.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct AccountFlagsHistory {
+    pub address: String,
+    pub summary: String,
+    /// In the order the operations were scanned.
+    pub changes: Vec<AccountSettingsChange>,
+    /// Operations read to find these changes.
+    pub scanned_operations: usize,
+    /// Continue the scan from here; None once the history is exhausted.
+    pub next_cursor: Option<String>,
+}
+
+/// The settings `op` changed on `address`, or None when it is not a
+/// set_options operation of that account or changed nothing tracked here.
+pub fn explain_settings_change(
+    address: &str,
+    op: &HorizonOperation,
+) -> Option<AccountSettingsChange> {
+    if op
+        .source_account
+        .as_deref()
+        .is_some_and(|source| source != address)
+    {
+        return None;
+    }
+    let Operation::SetOptions(set) = Operation::from(op.clone()) else {
+        return None;
+    };
+
+    let mut changes = Vec::new();
+    if let Some(weight) = set.master_weight {
+        if weight == 0 {
+            changes.push(templates::render("flags_history.master_key_disabled", &[]));
+        } else {
+            changes.push(templates::render(
+                "flags_history.master_weight",
+                &[("weight", &weight.to_string())],
+            ));
+        }
+    }
+    for (name, value) in [
+        ("low", set.low_threshold),
+        ("medium", set.med_threshold),
+        ("high", set.high_threshold),
+    ] {
+        if let Some(value) = value {
+            changes.push(templates::render(
+                "flags_history.threshold",
+                &[("threshold", name), ("value", &value.to_string())],
+            ));
+        }
+    }
+    for (flags, one, many) in [
+        (
+            set.set_flags,
+            "flags_history.flag_enabled",
+            "flags_history.flags_enabled",
+        ),
+        (
+            set.clear_flags,
+            "flags_history.flag_disabled",
+            "flags_history.flags_disabled",
+        ),
+    ] {
+        let names = flag_names(flags.unwrap_or(0));
+        match names.len() {
+            0 => {}
+            1 => changes.push(templates::render(one, &[("flags", names[0])])),
+            _ => {
+                let names: Vec<String> = names.iter().map(|n| n.to_string()).collect();
+                changes.push(templates::render(many, &[("flags", &join_changes(&names))]));
+            }
+        }
+    }
+    if let Some(key) = &set.signer_key {
+        let signer = describe_signer_key(key);
+        changes.push(match set.signer_weight {
+            Some(0) => templates::render("flags_history.signer_removed", &[("signer", &signer)]),
+            Some(weight) => templates::render(
+                "flags_history.signer_added",
+                &[("signer", &signer), ("weight", &weight.to_string())],
+            ),
+            None => templates::render("flags_history.signer_changed", &[("signer", &signer)]),
+        });
+    }
+    if changes.is_empty() {
+        return None;
+    }
+
+    let date = op
+        .created_at
+        .as_deref()
+        .and_then(parse_timestamp)
+        .map(|at| at.format("%Y-%m-%d").to_string());
+    let joined = join_changes(&changes);
+    let summary = match &date {
+        Some(date) => templates::render(
+            "flags_history.change",
+            &[("date", date), ("changes", &joined)],
+        ),
+        None => templates::render(
+            "flags_history.change_undated",
+            &[(
+                "changes",
+                &format!("{}{}", joined[..1].to_uppercase(), &joined[1..]),
+            )],
+        ),
+    };
+    Some(AccountSettingsChange {
+        date,
+        created_at: op.created_at.clone(),
+        transaction_hash: op.transaction_hash.clone(),
+        operation_id: op.id.clone(),
+        changes,
+        summary,
+    })
+}
+
+pub fn explain_flags_history(
+    address: &str,
+    changes: Vec<AccountSettingsChange>,
+    scanned_operations: usize,
+    next_cursor: Option<String>,
+) -> AccountFlagsHistory {
+    let summary = match changes.len() {
+        0 => templates::render(
+            "flags_history.no_changes",
+            &[("scanned", &scanned_operations.to_string())],
+        ),
+        n => templates::render(
+            "flags_history.summary",
+            &[("changes", &count(n, "change", "changes"))],
+        ),
+    };
+    AccountFlagsHistory {
+        address: address.to_string(),
+        summary,
+        changes,
+        scanned_operations,
+        next_cursor,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "GACCOUNT";
+
+    fn set_options(source: &str) -> HorizonOperation {
+        HorizonOperation {
+            id: "100".to_string(),
+            transaction_hash: "abc".to_string(),
+            operation_type: "set_options".to_string(),
+            source_account: Some(source.to_string()),
+            created_at: Some("2024-03-01T12:30:00Z".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_disabling_the_master_key_is_dated() {
+        let op = HorizonOperation {
+            master_key_weight: Some(0),
+            ..set_options(ACCOUNT)
+        };
+        let change = explain_settings_change(ACCOUNT, &op).unwrap();
+        assert_eq!(change.date.as_deref(), Some("2024-03-01"));
+        assert_eq!(change.summary, "On 2024-03-01 the master key was disabled.");
+        assert_eq!(change.operation_id, "100");
+    }
+
+    #[test]
+    fn test_flags_thresholds_and_signers_are_listed() {
+        let op = HorizonOperation {
+            med_threshold: Some(2),
+            set_flags: Some(vec![1, 2]),
+            clear_flags: Some(vec![8]),
+            signer_key: Some(
+                "GBSIGNERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA".to_string(),
+            ),
+            signer_weight: Some(1),
+            ..set_options(ACCOUNT)
+        };
+        let change = explain_settings_change(ACCOUNT, &op).unwrap();
+        assert_eq!(change.changes.len(), 4);
+        assert_eq!(change.changes[0], "the medium threshold was set to 2");
+        assert_eq!(
+            change.changes[1],
+            "AUTH_REQUIRED and AUTH_REVOCABLE were enabled"
+        );
+        assert_eq!(change.changes[2], "CLAWBACK_ENABLED was disabled");
+        assert!(change.changes[3].ends_with("was added with weight 1"));
+    }
+
+    #[test]
+    fn test_other_operations_are_skipped() {
+        let home_domain_only = HorizonOperation {
+            home_domain: Some("example.com".to_string()),
+            ..set_options(ACCOUNT)
+        };
+        assert!(explain_settings_change(ACCOUNT, &home_domain_only).is_none());
+
+        let other_account = HorizonOperation {
+            master_key_weight: Some(0),
+            ..set_options("GOTHER")
+        };
+        assert!(explain_settings_change(ACCOUNT, &other_account).is_none());
+
+        let payment = HorizonOperation {
+            operation_type: "payment".to_string(),
+            ..set_options(ACCOUNT)
+        };
+        assert!(explain_settings_change(ACCOUNT, &payment).is_none());
+    }
+
+    #[test]
+    fn test_undated_change_is_capitalised() {
+        let op = HorizonOperation {
+            low_threshold: Some(1),
+            created_at: None,
+            ..set_options(ACCOUNT)
+        };
+        let change = explain_settings_change(ACCOUNT, &op).unwrap();
+        assert_eq!(change.summary, "The low threshold was set to 1.");
+    }
+}
@@ -0,0 +1,404 @@
+//! Shared display formatting for explainers.
+//!
+//! Every explainer that shows an account, key, ID, hash, or asset goes
+//! through these helpers so the same value always renders the same way.
+//! The number of characters kept on each side of a shortened value is set
+//! by `DISPLAY_TRUNCATE_CHARS` (default 8), and how amounts are written by
+//! `DISPLAY_LOCALE` (default `en`).
+
+use std::env;
+use std::sync::OnceLock;
+
+use crate::models::amount::Amount;
+use crate::services::assets::resolve_sac;
+use crate::services::contract_labels::resolve_contract_label;
+use crate::services::labels::resolve_label;
+
+/// Characters kept at each end of a shortened value when not configured.
+pub const DEFAULT_TRUNCATE_CHARS: usize = 8;
+
+/// How a locale writes numbers: "1,234.5" in English, "1.234,5" in German.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Placed between groups of three whole digits; empty for none.
+    pub group_separator: &'static str,
+    pub decimal_separator: char,
+}
+
+impl NumberFormat {
+    pub const ENGLISH: Self = Self {
+        group_separator: ",",
+        decimal_separator: '.',
+    };
+
+    /// The number format for a language tag such as `en`, `de-CH`, or
+    /// `pt_BR`, or None for a language without one. `plain` writes numbers
+    /// without grouping, as Horizon does.
+    pub fn for_locale(tag: &str) -> Option<Self> {
+        let tag = tag.trim().to_ascii_lowercase();
+        let language = tag.split(['-', '_']).next().unwrap_or_default();
+        let (group_separator, decimal_separator) = match language {
+            "en" | "ja" | "ko" | "zh" | "th" | "he" => (",", '.'),
+            "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" => (".", ','),
+            // A no-break space, so the number never wraps across lines.
+            "fr" | "ru" | "uk" | "pl" | "cs" | "sv" | "fi" | "nb" => ("\u{a0}", ','),
+            "plain" => ("", '.'),
+            _ => return None,
+        };
+        Some(Self {
+            group_separator,
+            decimal_separator,
+        })
+    }
+}
+
+impl Default for NumberFormat {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+/// Display settings shared by all explainers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayConfig {
+    /// Characters kept at the start and end of a shortened value.
+    pub truncate_chars: usize,
+    /// How amounts in explanation text are written.
+    pub number_format: NumberFormat,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self {
+            truncate_chars: DEFAULT_TRUNCATE_CHARS,
+            number_format: NumberFormat::default(),
+        }
+    }
+}
+
+impl DisplayConfig {
+    /// Read `DISPLAY_TRUNCATE_CHARS` and `DISPLAY_LOCALE`, falling back to
+    /// the defaults when unset or invalid.
+    pub fn from_env() -> Self {
+        let truncate_chars = env::var("DISPLAY_TRUNCATE_CHARS")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(DEFAULT_TRUNCATE_CHARS);
+        let number_format = env::var("DISPLAY_LOCALE")
+            .ok()
+            .and_then(|v| NumberFormat::for_locale(&v))
+            .unwrap_or_default();
+        Self {
+            truncate_chars,
+            number_format,
+        }
+    }
+}
+
+static DISPLAY_CONFIG: OnceLock<DisplayConfig> = OnceLock::new();
+
+/// Install the display settings. Call once at startup; later calls are ignored.
+pub fn install(config: DisplayConfig) {
+    let _ = DISPLAY_CONFIG.set(config);
+}
+
+fn config() -> DisplayConfig {
+    DISPLAY_CONFIG.get().copied().unwrap_or_default()
+}
+
+/// Keep `head` leading and `tail` trailing characters of `value`, joined by
+/// `separator`. Values too short to benefit are returned unchanged.
+pub fn truncate_middle(value: &str, head: usize, tail: usize, separator: &str) -> String {
+    if !value.is_ascii() || value.len() <= head + tail + separator.len() {
+        return value.to_string();
+    }
+    format!(
+        "{}{separator}{}",
+        &value[..head],
+        &value[value.len() - tail..]
+    )
+}
+
+/// `n` and its noun, for template arguments: "1 signer", "3 signers".
+pub fn count(n: usize, singular: &str, plural: &str) -> String {
+    format!("{n} {}", if n == 1 { singular } else { plural })
+}
+
+/// Shorten a long key, ID, or hash for display: "GABCDEFG...STUVWXYZ".
+pub fn shorten(value: &str) -> String {
+    let n = config().truncate_chars;
+    truncate_middle(value, n, n, "...")
+}
+
+/// Render an account or contract for a summary sentence, prefixing its known
+/// label: "Coinbase (GCOINBASE...)", "Soroswap Router (CAG5...)",
+/// "USDC Stellar Asset Contract (CCW6...)".
+/// Unknown addresses are shown as-is.
+pub fn format_account(address: &str) -> String {
+    if address == "Unknown" {
+        return address.to_string();
+    }
+    if let Some(label) = resolve_label(address) {
+        return format!("{label} ({address})");
+    }
+    if address.starts_with('C')
+        && let Some(label) = resolve_contract_label(address)
+    {
+        return format!("{label} ({address})");
+    }
+    match resolve_sac(address) {
+        Some(asset) => format!("{} Stellar Asset Contract ({address})", asset.code),
+        None => address.to_string(),
+    }
+}
+
+/// Render an asset from Horizon's separate type/code/issuer fields:
+/// "XLM (native)" or "USDC (GISSUER...)".
+pub fn format_asset(
+    asset_type: Option<&str>,
+    asset_code: Option<&str>,
+    asset_issuer: Option<&str>,
+) -> String {
+    match asset_type {
+        Some("native") | None => "XLM (native)".to_string(),
+        _ => match (asset_code, asset_issuer) {
+            (Some(code), Some(issuer)) => format!("{code} ({issuer})"),
+            (Some(code), None) => code.to_string(),
+            _ => "Unknown".to_string(),
+        },
+    }
+}
+
+/// Split Horizon's canonical asset string into code and issuer:
+/// "native" → ("XLM", None), "USDC:GISSUER" → ("USDC", Some("GISSUER")).
+pub fn split_canonical_asset(asset: &str) -> (&str, Option<&str>) {
+    match asset.split_once(':') {
+        Some((code, issuer)) => (code, Some(issuer)),
+        None => ("XLM", None),
+    }
+}
+
+/// Drop insignificant zeros from a Horizon decimal amount:
+/// "100.0000000" → "100", "10.5000000" → "10.5".
+pub fn trim_amount(amount: &str) -> String {
+    match amount.split_once('.') {
+        Some((whole, fraction)) => {
+            let fraction = fraction.trim_end_matches('0');
+            if fraction.is_empty() {
+                whole.to_string()
+            } else {
+                format!("{whole}.{fraction}")
+            }
+        }
+        None => amount.to_string(),
+    }
+}
+
+/// Write a Horizon decimal amount for a sentence: trailing zeros dropped,
+/// whole digits grouped, and the configured separators, so "12500.5000000"
+/// reads "12,500.5". Anything that is not a plain decimal is returned
+/// unchanged.
+pub fn format_amount(amount: &str) -> String {
+    format_amount_with(amount, config().number_format)
+}
+
+/// `format_amount` for an exact `Amount`.
+pub fn format_exact(amount: Amount) -> String {
+    format_amount(&amount.trimmed())
+}
+
+pub fn format_amount_with(amount: &str, format: NumberFormat) -> String {
+    let trimmed = trim_amount(amount.trim());
+    let (sign, unsigned) = match trimmed.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", trimmed.as_str()),
+    };
+    let (whole, fraction) = match unsigned.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (unsigned, None),
+    };
+    let is_digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(whole) || !fraction.is_none_or(is_digits) {
+        return amount.to_string();
+    }
+
+    let mut out = sign.to_string();
+    for (i, digit) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            out.push_str(format.group_separator);
+        }
+        out.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        out.push(format.decimal_separator);
+        out.push_str(fraction);
+    }
+    out
+}
+
+/// Render an ISO 8601 timestamp as a calendar date: "March 3, 2025", with
+/// the time appended when it is not midnight UTC. Unparseable input is
+/// returned unchanged.
+pub fn format_date(iso: &str) -> String {
+    let Ok(time) = chrono::DateTime::parse_from_rfc3339(iso.trim()) else {
+        return iso.to_string();
+    };
+    let time = time.with_timezone(&chrono::Utc);
+    let date = time.format("%B %-d, %Y").to_string();
+    if time.format("%H:%M:%S").to_string() == "00:00:00" {
+        date
+    } else {
+        format!("{date} at {} UTC", time.format("%H:%M"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_shorten_long_value() {
+        let hash = "abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890";
+        assert_eq!(shorten(hash), "abcdef12...34567890");
+    }
+
+    #[test]
+    fn test_shorten_short_value_unchanged() {
+        assert_eq!(shorten("short"), "short");
+        assert_eq!(shorten("GAAAA...ZZZZ"), "GAAAA...ZZZZ");
+    }
+
+    #[test]
+    fn test_truncate_middle() {
+        let address = "GCKFBEIYV2U22IO2BJ4KVJOIP7XPWQGQFKKWXR6DOSJBV7STMAQSMTGG";
+        assert_eq!(truncate_middle(address, 5, 4, "…"), "GCKFB…MTGG");
+        assert_eq!(truncate_middle("abc", 5, 4, "…"), "abc");
+    }
+
+    #[test]
+    fn test_format_account_with_label() {
+        let address = "GCOINBASEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+        assert_eq!(format_account(address), format!("Coinbase ({address})"));
+        assert_eq!(format_account("GUNLABELLED"), "GUNLABELLED");
+        assert_eq!(format_account("Unknown"), "Unknown");
+    }
+
+    #[test]
+    fn test_format_account_stellar_asset_contract() {
+        // The public-network native asset contract is always known.
+        let xlm = "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA";
+        assert_eq!(
+            format_account(xlm),
+            format!("XLM Stellar Asset Contract ({xlm})")
+        );
+    }
+
+    #[test]
+    fn test_format_account_labeled_contract() {
+        let router = "CAG5LRYQ5JVEUI5TEID72EYOVX44TTUJT5BQR2J6J77FH65PCCFAJDDH";
+        assert_eq!(
+            format_account(router),
+            format!("Soroswap Router ({router})")
+        );
+    }
+
+    #[test]
+    fn test_format_asset() {
+        assert_eq!(format_asset(Some("native"), None, None), "XLM (native)");
+        assert_eq!(
+            format_asset(Some("credit_alphanum4"), Some("USDC"), Some("GISSUER")),
+            "USDC (GISSUER)"
+        );
+        assert_eq!(
+            format_asset(Some("credit_alphanum4"), Some("USDC"), None),
+            "USDC"
+        );
+        assert_eq!(
+            format_asset(Some("credit_alphanum4"), None, None),
+            "Unknown"
+        );
+    }
+
+    #[test]
+    fn test_split_canonical_asset() {
+        assert_eq!(split_canonical_asset("native"), ("XLM", None));
+        assert_eq!(
+            split_canonical_asset("USDC:GISSUER"),
+            ("USDC", Some("GISSUER"))
+        );
+    }
+
+    #[test]
+    fn test_trim_amount() {
+        assert_eq!(trim_amount("100.0000000"), "100");
+        assert_eq!(trim_amount("10.5000000"), "10.5");
+        assert_eq!(trim_amount("0.0000001"), "0.0000001");
+        assert_eq!(trim_amount("42"), "42");
+    }
+
+    #[test]
+    fn test_format_amount() {
+        assert_eq!(format_amount("500.0000000"), "500");
+        assert_eq!(format_amount("1234567.8900000"), "1,234,567.89");
+        assert_eq!(format_amount("-2500.5"), "-2,500.5");
+        assert_eq!(format_amount("0.0000001"), "0.0000001");
+        assert_eq!(format_amount("Unknown"), "Unknown");
+        assert_eq!(format_amount("1e5"), "1e5");
+    }
+
+    #[test]
+    fn test_format_amount_for_locales() {
+        let german = NumberFormat::for_locale("de-CH").unwrap();
+        assert_eq!(format_amount_with("12500.25", german), "12.500,25");
+        let french = NumberFormat::for_locale("fr").unwrap();
+        assert_eq!(format_amount_with("12500.25", french), "12\u{a0}500,25");
+        let plain = NumberFormat::for_locale("plain").unwrap();
+        assert_eq!(format_amount_with("12500.2500000", plain), "12500.25");
+        assert_eq!(NumberFormat::for_locale("xx"), None);
+    }
+
+    #[test]
+    fn test_format_date() {
+        assert_eq!(format_date("2025-03-03T00:00:00Z"), "March 3, 2025");
+        assert_eq!(
+            format_date("2025-03-03T14:05:00Z"),
+            "March 3, 2025 at 14:05 UTC"
+        );
+        assert_eq!(format_date("not a date"), "not a date");
+    }
+
+    #[test]
+    fn test_display_config_default() {
+        assert_eq!(DisplayConfig::default().truncate_chars, 8);
+    }
+
+    proptest! {
+        #[test]
+        fn truncate_middle_never_panics(
+            value in any::<String>(),
+            head in 0usize..16,
+            tail in 0usize..16,
+            separator in ".{0,3}",
+        ) {
+            let short = truncate_middle(&value, head, tail, &separator);
+            if short != value {
+                prop_assert!(value.starts_with(&short[..head]));
+                prop_assert!(value.ends_with(&short[short.len() - tail..]));
+            }
+        }
+
+        #[test]
+        fn shorten_never_panics(value in any::<String>()) {
+            shorten(&value);
+        }
+
+        #[test]
+        fn format_amount_keeps_the_digits(whole in 0u64..u64::MAX, fraction in 0u32..10_000_000) {
+            let amount = format!("{whole}.{fraction:07}");
+            let formatted = format_amount_with(&amount, NumberFormat::ENGLISH);
+            prop_assert_eq!(formatted.replace(',', ""), trim_amount(&amount));
+        }
+    }
+}
@@ -0,0 +1,289 @@
+//! Definitions of the Stellar terms explanations use.
+//!
+//! Explanations list the glossary terms their text mentions in `terms`, each
+//! with a one-sentence definition and a link to the Stellar docs, so a
+//! frontend can show tooltips without keeping its own copy of the
+//! definitions. Terms are found by whole-word, case-insensitive matches on
+//! the phrases below, and listed in the order they first appear.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+const DOCS: &str = "https://developers.stellar.org/docs";
+
+/// A glossary term an explanation mentions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct GlossaryTerm {
+    /// Stable identifier, e.g. "trustline".
+    pub id: String,
+    /// The term as it would be shown, e.g. "Trustline".
+    pub term: String,
+    pub definition: String,
+    pub doc_url: String,
+}
+
+struct Entry {
+    id: &'static str,
+    term: &'static str,
+    definition: &'static str,
+    /// Path under the Stellar docs.
+    doc_path: &'static str,
+    /// Lowercase phrases that count as a mention.
+    phrases: &'static [&'static str],
+}
+
+const GLOSSARY: &[Entry] = &[
+    Entry {
+        id: "trustline",
+        term: "Trustline",
+        definition: "An account's opt-in to hold an asset other than XLM, up to a limit it chooses.",
+        doc_path: "/learn/fundamentals/stellar-data-structures/accounts#trustlines",
+        phrases: &[
+            "trustline",
+            "trustlines",
+            "trust line",
+            "trust lines",
+            "trust limit",
+        ],
+    },
+    Entry {
+        id: "base_reserve",
+        term: "Base reserve",
+        definition: "XLM an account must keep for itself and for each trustline, offer, signer, or data entry it holds.",
+        doc_path: "/learn/fundamentals/lumens#base-reserves",
+        phrases: &["base reserve", "reserve", "reserves", "minimum balance"],
+    },
+    Entry {
+        id: "sequence_number",
+        term: "Sequence number",
+        definition: "A counter on each account that every transaction must increase by one, so a transaction cannot be replayed.",
+        doc_path: "/learn/fundamentals/stellar-data-structures/accounts#sequence-number",
+        phrases: &["sequence number", "sequence numbers"],
+    },
+    Entry {
+        id: "stroop",
+        term: "Stroop",
+        definition: "The smallest unit of XLM: one ten-millionth (0.0000001) of a lumen. Fees are counted in stroops.",
+        doc_path: "/learn/fundamentals/lumens",
+        phrases: &["stroop", "stroops"],
+    },
+    Entry {
+        id: "base_fee",
+        term: "Base fee",
+        definition: "The minimum fee per operation. When the network is busy, transactions that offer more than the base fee are included first.",
+        doc_path: "/learn/fundamentals/fees-resource-limits-metering",
+        phrases: &["base fee", "surge pricing", "fee bump", "fee-bump"],
+    },
+    Entry {
+        id: "memo",
+        term: "Memo",
+        definition: "A short note attached to a transaction, often used by exchanges to tell which customer a deposit is for.",
+        doc_path: "/learn/fundamentals/transactions/operations-and-transactions#memo",
+        phrases: &["memo", "memos"],
+    },
+    Entry {
+        id: "ledger",
+        term: "Ledger",
+        definition: "A snapshot of the network's state, closed every few seconds with the transactions applied since the last one.",
+        doc_path: "/learn/fundamentals/stellar-data-structures/ledgers",
+        phrases: &["ledger", "ledgers"],
+    },
+    Entry {
+        id: "signer",
+        term: "Signer",
+        definition: "A key allowed to sign for an account. Each has a weight, and an operation needs enough combined weight to meet its threshold.",
+        doc_path: "/learn/encyclopedia/security/signatures-multisig",
+        phrases: &["signer", "signers", "master key", "signing key"],
+    },
+    Entry {
+        id: "threshold",
+        term: "Threshold",
+        definition: "The total signer weight an account requires for low, medium, or high security operations.",
+        doc_path: "/learn/encyclopedia/security/signatures-multisig#thresholds",
+        phrases: &["threshold", "thresholds"],
+    },
+    Entry {
+        id: "home_domain",
+        term: "Home domain",
+        definition: "A website an account points to, whose stellar.toml file describes who runs the account and what it issues.",
+        doc_path: "/learn/fundamentals/stellar-data-structures/accounts#home-domain",
+        phrases: &["home domain"],
+    },
+    Entry {
+        id: "authorization",
+        term: "Authorization",
+        definition: "An issuer's approval for an account to hold its asset. Issuers that require it can also revoke it, freezing the asset.",
+        doc_path: "/learn/fundamentals/stellar-data-structures/accounts#flags",
+        phrases: &[
+            "authorized",
+            "authorize",
+            "authorization",
+            "deauthorized",
+            "auth required",
+            "auth revocable",
+            "auth immutable",
+        ],
+    },
+    Entry {
+        id: "clawback",
+        term: "Clawback",
+        definition: "An issuer taking its asset back from a holder, possible only for assets issued with clawback enabled.",
+        doc_path: "/learn/encyclopedia/transactions-specialized/clawbacks",
+        phrases: &["clawback", "clawbacks", "clawed back", "claw back"],
+    },
+    Entry {
+        id: "claimable_balance",
+        term: "Claimable balance",
+        definition: "Funds set aside on the ledger for one or more accounts to claim later, under conditions such as a time window.",
+        doc_path: "/learn/encyclopedia/transactions-specialized/claimable-balances",
+        phrases: &["claimable balance", "claimable balances"],
+    },
+    Entry {
+        id: "offer",
+        term: "Offer",
+        definition: "A standing order on Stellar's built-in exchange to trade one asset for another at a set price.",
+        doc_path: "/learn/encyclopedia/sdex/liquidity-on-stellar-sdex-liquidity-pools",
+        phrases: &["offer", "offers", "order book"],
+    },
+    Entry {
+        id: "path_payment",
+        term: "Path payment",
+        definition: "A payment that converts the sent asset into a different received asset through the exchange in a single step.",
+        doc_path: "/learn/encyclopedia/transactions-specialized/path-payments",
+        phrases: &["path payment", "path payments", "conversion path"],
+    },
+    Entry {
+        id: "liquidity_pool",
+        term: "Liquidity pool",
+        definition: "A shared reserve of two assets that anyone can trade against; depositors receive pool shares and earn trading fees.",
+        doc_path: "/learn/encyclopedia/sdex/liquidity-on-stellar-sdex-liquidity-pools",
+        phrases: &["liquidity pool", "liquidity pools", "pool shares"],
+    },
+    Entry {
+        id: "sponsorship",
+        term: "Sponsorship",
+        definition: "One account paying the base reserve for another account's ledger entries.",
+        doc_path: "/learn/encyclopedia/transactions-specialized/sponsored-reserves",
+        phrases: &[
+            "sponsor",
+            "sponsors",
+            "sponsored",
+            "sponsoring",
+            "sponsorship",
+        ],
+    },
+    Entry {
+        id: "channel_account",
+        term: "Channel account",
+        definition: "An account used only to submit transactions and pay their fees, so a service can send many transactions at once.",
+        doc_path: "/learn/encyclopedia/transactions-specialized/channel-accounts",
+        phrases: &["channel account", "channel accounts"],
+    },
+    Entry {
+        id: "anchor",
+        term: "Anchor",
+        definition: "A business that moves money between Stellar and the outside world, such as bank deposits and withdrawals.",
+        doc_path: "/learn/fundamentals/anchors",
+        phrases: &["anchor", "anchors"],
+    },
+    Entry {
+        id: "smart_contract",
+        term: "Smart contract",
+        definition: "A program deployed on Stellar (Soroban) that transactions can call.",
+        doc_path: "/learn/fundamentals/contract-development",
+        phrases: &["smart contract", "smart contracts", "contract", "contracts"],
+    },
+    Entry {
+        id: "ttl",
+        term: "Time to live",
+        definition: "How many more ledgers a contract's stored data stays live before it is archived and has to be restored.",
+        doc_path: "/learn/encyclopedia/storage/state-archival",
+        phrases: &["ttl", "time to live", "archived", "restored"],
+    },
+];
+
+/// The glossary terms mentioned anywhere in `texts`, in order of first
+/// mention.
+pub fn terms_in<'a>(texts: impl IntoIterator<Item = &'a str>) -> Vec<GlossaryTerm> {
+    let text = texts
+        .into_iter()
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
+    let mut found: Vec<(usize, &Entry)> = GLOSSARY
+        .iter()
+        .filter_map(|entry| {
+            entry
+                .phrases
+                .iter()
+                .filter_map(|phrase| first_mention(&text, phrase))
+                .min()
+                .map(|at| (at, entry))
+        })
+        .collect();
+    found.sort_by_key(|(at, _)| *at);
+    found
+        .into_iter()
+        .map(|(_, entry)| GlossaryTerm {
+            id: entry.id.to_string(),
+            term: entry.term.to_string(),
+            definition: entry.definition.to_string(),
+            doc_url: format!("{DOCS}{}", entry.doc_path),
+        })
+        .collect()
+}
+
+/// Where `phrase` first appears in `text` as whole words.
+fn first_mention(text: &str, phrase: &str) -> Option<usize> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    text.match_indices(phrase).map(|(at, _)| at).find(|&at| {
+        let before = text[..at].chars().next_back();
+        let after = text[at + phrase.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(terms: &[GlossaryTerm]) -> Vec<&str> {
+        terms.iter().map(|t| t.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_terms_listed_in_order_of_first_mention() {
+        let terms = terms_in([
+            "GA opened a trust line for USDC.",
+            "The fee was 100 stroops, and the memo was \"hello\". Another trust line.",
+        ]);
+        assert_eq!(ids(&terms), vec!["trustline", "stroop", "memo"]);
+        assert_eq!(
+            terms[0].doc_url,
+            "https://developers.stellar.org/docs/learn/fundamentals/stellar-data-structures/accounts#trustlines"
+        );
+        assert!(!terms[0].definition.is_empty());
+    }
+
+    #[test]
+    fn test_only_whole_words_match() {
+        assert!(terms_in(["Offered nothing; contractual terms; memorable."]).is_empty());
+        assert_eq!(
+            ids(&terms_in(["Claimable Balance created."])),
+            vec!["claimable_balance"]
+        );
+    }
+
+    #[test]
+    fn test_glossary_entries_are_well_formed() {
+        let mut seen = std::collections::HashSet::new();
+        for entry in GLOSSARY {
+            assert!(seen.insert(entry.id), "duplicate id {}", entry.id);
+            assert!(entry.doc_path.starts_with('/'), "{}", entry.id);
+            assert!(entry.definition.ends_with('.'), "{}", entry.id);
+            for phrase in entry.phrases {
+                assert_eq!(*phrase, phrase.to_lowercase(), "{}", entry.id);
+            }
+        }
+    }
+}
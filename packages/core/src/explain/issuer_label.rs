@@ -0,0 +1,184 @@
+//! Issuer names taken from home domains, and whether the domain backs them.
+//!
+//! Anyone can set an account's home domain to a well-known site, so the
+//! ORG_NAME in that site's stellar.toml only names the issuer when the file
+//! also lists the issuing account under `ACCOUNTS` (SEP-1). A name that
+//! passes this check is "verified"; one that does not is only "claimed".
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::anchor::parse_asset;
+use crate::explain::format::shorten;
+use crate::models::asset::TomlIdentity;
+use crate::models::operation::Operation;
+use crate::models::transaction::Transaction;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LabelVerification {
+    /// The home domain's stellar.toml lists the account under ACCOUNTS.
+    Verified,
+    /// The account names the domain, but the domain does not list the account.
+    Claimed,
+}
+
+impl LabelVerification {
+    pub fn of(identity: &TomlIdentity, account: &str) -> Self {
+        if identity.lists_account(account) {
+            LabelVerification::Verified
+        } else {
+            LabelVerification::Claimed
+        }
+    }
+}
+
+/// The organization an asset issuer's home domain names.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct IssuerLabel {
+    pub issuer: String,
+    /// ORG_NAME from the home domain's stellar.toml, e.g. "Circle".
+    pub name: String,
+    pub home_domain: String,
+    pub verification: LabelVerification,
+    /// e.g. "centre.io names GA5ZSEJY...34K4KZVN as Circle, and its stellar.toml
+    /// lists the issuing account."
+    pub description: String,
+}
+
+/// Label `issuer` with the ORG_NAME of its home domain. None when the
+/// stellar.toml has no ORG_NAME.
+pub fn label_issuer(
+    issuer: &str,
+    home_domain: &str,
+    identity: &TomlIdentity,
+) -> Option<IssuerLabel> {
+    let name = identity
+        .org_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|name| !name.is_empty())?;
+    let verification = LabelVerification::of(identity, issuer);
+    let description = match verification {
+        LabelVerification::Verified => format!(
+            "{home_domain} names {} as {name}, and its stellar.toml lists the issuing account.",
+            shorten(issuer)
+        ),
+        LabelVerification::Claimed => format!(
+            "{} claims to be {name} through {home_domain}, but that domain's stellar.toml does not list the issuing account.",
+            shorten(issuer)
+        ),
+    };
+    Some(IssuerLabel {
+        issuer: issuer.to_string(),
+        name: name.to_string(),
+        home_domain: home_domain.to_string(),
+        verification,
+        description,
+    })
+}
+
+/// Issuers of the assets `transaction` pays with, in first-seen order.
+pub fn payment_issuers(transaction: &Transaction) -> Vec<String> {
+    let mut issuers: Vec<String> = Vec::new();
+    let mut add = |issuer: Option<String>| {
+        if let Some(issuer) = issuer
+            && !issuers.contains(&issuer)
+        {
+            issuers.push(issuer);
+        }
+    };
+    for op in &transaction.operations {
+        match op {
+            Operation::Payment(p) => add(p.asset_issuer.clone()),
+            Operation::PathPayment(p) => {
+                for asset in [&p.send_asset, &p.dest_asset] {
+                    add(parse_asset(asset).and_then(|(_, issuer)| issuer));
+                }
+            }
+            _ => {}
+        }
+    }
+    issuers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    fn identity(accounts: &[&str]) -> TomlIdentity {
+        TomlIdentity {
+            org_name: Some("Circle".to_string()),
+            accounts: accounts.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_listed_issuer_is_verified() {
+        let label = label_issuer(ISSUER, "centre.io", &identity(&[ISSUER])).unwrap();
+        assert_eq!(label.verification, LabelVerification::Verified);
+        assert_eq!(label.name, "Circle");
+        assert_eq!(
+            label.description,
+            "centre.io names GA5ZSEJY...34K4KZVN as Circle, and its stellar.toml lists the issuing account."
+        );
+    }
+
+    #[test]
+    fn test_unlisted_issuer_is_only_claimed() {
+        let label = label_issuer(ISSUER, "centre.io", &identity(&["GOTHER"])).unwrap();
+        assert_eq!(label.verification, LabelVerification::Claimed);
+        assert!(
+            label
+                .description
+                .contains("does not list the issuing account")
+        );
+    }
+
+    #[test]
+    fn test_no_label_without_org_name() {
+        let identity = TomlIdentity {
+            org_name: Some("  ".to_string()),
+            accounts: vec![ISSUER.to_string()],
+        };
+        assert!(label_issuer(ISSUER, "centre.io", &identity).is_none());
+    }
+
+    #[test]
+    fn test_payment_issuers_are_deduplicated() {
+        use crate::models::operation::{PathPaymentOperation, PathPaymentType, PaymentOperation};
+
+        let payment = Operation::Payment(PaymentOperation {
+            id: "1".to_string(),
+            source_account: Some("GFROM".to_string()),
+            destination: "GTO".to_string(),
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: Some(ISSUER.to_string()),
+            amount: "5".parse().unwrap(),
+        });
+        let path_payment = Operation::PathPayment(PathPaymentOperation {
+            id: "2".to_string(),
+            source_account: Some("GFROM".to_string()),
+            destination: "GTO".to_string(),
+            send_asset: "XLM (native)".to_string(),
+            send_amount: "10".parse().unwrap(),
+            dest_asset: format!("USDC ({ISSUER})"),
+            dest_amount: "1".parse().unwrap(),
+            path: vec![],
+            payment_type: PathPaymentType::StrictSend,
+        });
+        let tx = Transaction::new(
+            "tx".to_string(),
+            true,
+            100,
+            vec![payment, path_payment],
+            None,
+            None,
+        );
+        assert_eq!(payment_issuers(&tx), vec![ISSUER.to_string()]);
+    }
+}
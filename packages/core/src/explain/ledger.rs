@@ -0,0 +1,136 @@
+//! Explanations for closed ledgers, and the ledger context attached to
+//! transaction explanations.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::format_amount;
+use crate::explain::templates;
+use crate::explain::transaction::format_ledger_time;
+use crate::models::fee::FeeStats;
+use crate::models::ledger::Ledger;
+
+/// Plain-English explanation of a closed ledger.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct LedgerExplanation {
+    pub sequence: u64,
+    /// ISO 8601 close time.
+    pub closed_at: String,
+    pub protocol_version: u32,
+    pub summary: String,
+    pub successful_transaction_count: u32,
+    pub failed_transaction_count: u32,
+    pub operation_count: u32,
+    /// Minimum fee per operation, in XLM.
+    pub base_fee: String,
+    /// Minimum balance per ledger entry, in XLM.
+    pub base_reserve: String,
+}
+
+/// The ledger that included a transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct LedgerContext {
+    pub sequence: u64,
+    /// ISO 8601 close time.
+    pub closed_at: Option<String>,
+    /// Protocol version the network ran, when the ledger could be fetched.
+    pub protocol_version: Option<u32>,
+    /// Path of the ledger's own explanation, e.g. "/ledger/1204520".
+    pub explanation_url: String,
+    /// Seconds from submission to ledger close, for transactions submitted
+    /// through this service.
+    pub inclusion_latency_seconds: Option<i64>,
+}
+
+impl LedgerContext {
+    pub fn new(sequence: u64, closed_at: Option<&str>) -> Self {
+        Self {
+            sequence,
+            closed_at: closed_at.map(str::to_string),
+            protocol_version: None,
+            explanation_url: format!("/ledger/{sequence}"),
+            inclusion_latency_seconds: None,
+        }
+    }
+}
+
+pub fn explain_ledger(ledger: &Ledger) -> LedgerExplanation {
+    let base_fee = FeeStats::stroops_to_xlm(ledger.base_fee_in_stroops);
+    let base_reserve = FeeStats::stroops_to_xlm(ledger.base_reserve_in_stroops);
+
+    let summary = [
+        templates::render(
+            "ledger.summary",
+            &[
+                ("sequence", &ledger.sequence.to_string()),
+                ("closed_at", &format_ledger_time(&ledger.closed_at)),
+                ("protocol_version", &ledger.protocol_version.to_string()),
+                (
+                    "successful",
+                    &ledger.successful_transaction_count.to_string(),
+                ),
+                ("failed", &ledger.failed_transaction_count.to_string()),
+                ("operations", &ledger.operation_count.to_string()),
+            ],
+        ),
+        templates::render(
+            "ledger.fees",
+            &[
+                ("base_fee", &format_amount(&base_fee)),
+                ("base_reserve", &format_amount(&base_reserve)),
+            ],
+        ),
+    ]
+    .join(" ");
+
+    LedgerExplanation {
+        sequence: ledger.sequence,
+        closed_at: ledger.closed_at.clone(),
+        protocol_version: ledger.protocol_version,
+        summary,
+        successful_transaction_count: ledger.successful_transaction_count,
+        failed_transaction_count: ledger.failed_transaction_count,
+        operation_count: ledger.operation_count,
+        base_fee,
+        base_reserve,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ledger() -> Ledger {
+        Ledger {
+            sequence: 1204520,
+            closed_at: "2025-03-04T10:15:22Z".to_string(),
+            protocol_version: 22,
+            successful_transaction_count: 14,
+            failed_transaction_count: 2,
+            operation_count: 37,
+            base_fee_in_stroops: 100,
+            base_reserve_in_stroops: 5_000_000,
+        }
+    }
+
+    #[test]
+    fn test_explain_ledger() {
+        let explanation = explain_ledger(&ledger());
+        assert_eq!(
+            explanation.summary,
+            "Ledger #1204520 closed on 2025-03-04 at 10:15 UTC under protocol version 22. \
+             It included 14 successful and 2 failed transactions, with 37 operations. \
+             The base fee was 0.00001 XLM per operation and the base reserve 0.5 XLM."
+        );
+        assert_eq!(explanation.base_fee, "0.0000100");
+        assert_eq!(explanation.base_reserve, "0.5000000");
+    }
+
+    #[test]
+    fn test_ledger_context_links_to_ledger_explanation() {
+        let context = LedgerContext::new(1204520, Some("2025-03-04T10:15:22Z"));
+        assert_eq!(context.explanation_url, "/ledger/1204520");
+        assert_eq!(context.protocol_version, None);
+    }
+}
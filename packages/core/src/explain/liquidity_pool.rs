@@ -0,0 +1,231 @@
+//! Explanations for liquidity pools and their recent activity.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::{
+    format_account, format_amount, shorten, split_canonical_asset, trim_amount,
+};
+use crate::explain::templates;
+use crate::models::amount::Amount;
+use crate::models::liquidity_pool::{LiquidityPool, PoolActivity, PoolActivityKind, PoolReserve};
+use crate::services::labels::resolve_label;
+
+/// Plain-English explanation of a liquidity pool.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct LiquidityPoolExplanation {
+    pub id: String,
+    pub summary: String,
+    /// Swap fee in basis points, e.g. 30.
+    pub fee_bps: u32,
+    /// Swap fee as a percentage, e.g. "0.3%".
+    pub fee_percent: String,
+    pub total_shares: String,
+    pub total_trustlines: u64,
+    pub reserves: Vec<PoolReserveExplanation>,
+    /// Spot price implied by the reserves, e.g. "1 XLM = 0.1124 USDC".
+    pub price: Option<String>,
+    /// Latest deposits and withdrawals, newest first.
+    pub recent_activity: Vec<PoolActivityExplanation>,
+}
+
+/// One of the pool's assets and how much of it the pool holds.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct PoolReserveExplanation {
+    /// Asset code, "XLM" for the native asset.
+    pub asset_code: String,
+    pub asset_issuer: Option<String>,
+    /// Known label for the issuer account, e.g. "USDC Issuer (Circle)".
+    pub issuer_label: Option<String>,
+    pub amount: String,
+}
+
+/// A deposit into or withdrawal from the pool.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct PoolActivityExplanation {
+    /// "deposit" or "withdraw".
+    pub kind: String,
+    pub account: String,
+    pub summary: String,
+    pub shares: String,
+    pub transaction_hash: String,
+    pub created_at: Option<String>,
+}
+
+pub fn explain_liquidity_pool(
+    pool: &LiquidityPool,
+    activity: &[PoolActivity],
+) -> LiquidityPoolExplanation {
+    let reserves: Vec<PoolReserveExplanation> = pool.reserves.iter().map(explain_reserve).collect();
+    let fee_percent = format!(
+        "{}%",
+        format_amount(&format!("{:.2}", f64::from(pool.fee_bp) / 100.0))
+    );
+    let total_shares = trim_amount(&pool.total_shares);
+
+    let summary = templates::render(
+        "liquidity_pool.summary",
+        &[
+            ("pool_id", &shorten(&pool.id)),
+            ("reserves", &describe_reserves(&pool.reserves)),
+            ("shares", &format_amount(&total_shares)),
+            ("trustlines", &pool.total_trustlines.to_string()),
+            ("fee", &fee_percent),
+        ],
+    );
+
+    LiquidityPoolExplanation {
+        id: pool.id.clone(),
+        summary,
+        fee_bps: pool.fee_bp,
+        fee_percent,
+        total_shares,
+        total_trustlines: pool.total_trustlines,
+        price: spot_price(&reserves),
+        reserves,
+        recent_activity: activity.iter().map(explain_activity).collect(),
+    }
+}
+
+fn explain_reserve(reserve: &PoolReserve) -> PoolReserveExplanation {
+    let (code, issuer) = split_canonical_asset(&reserve.asset);
+    PoolReserveExplanation {
+        asset_code: code.to_string(),
+        asset_issuer: issuer.map(str::to_string),
+        issuer_label: issuer.and_then(resolve_label).map(str::to_string),
+        amount: trim_amount(&reserve.amount),
+    }
+}
+
+fn explain_activity(activity: &PoolActivity) -> PoolActivityExplanation {
+    let (kind, key) = match activity.kind {
+        PoolActivityKind::Deposit => ("deposit", "liquidity_pool.deposit"),
+        PoolActivityKind::Withdraw => ("withdraw", "liquidity_pool.withdraw"),
+    };
+    let shares = trim_amount(&activity.shares);
+    let summary = templates::render(
+        key,
+        &[
+            ("account", &format_account(&activity.account)),
+            ("amounts", &describe_reserves(&activity.reserves)),
+            ("shares", &format_amount(&shares)),
+        ],
+    );
+    PoolActivityExplanation {
+        kind: kind.to_string(),
+        account: activity.account.clone(),
+        summary,
+        shares,
+        transaction_hash: activity.transaction_hash.clone(),
+        created_at: activity.created_at.clone(),
+    }
+}
+
+/// "1000 XLM and 112.4 USDC".
+fn describe_reserves(reserves: &[PoolReserve]) -> String {
+    reserves
+        .iter()
+        .map(|r| {
+            let (code, _) = split_canonical_asset(&r.asset);
+            format!("{} {code}", format_amount(&r.amount))
+        })
+        .collect::<Vec<_>>()
+        .join(" and ")
+}
+
+/// Price of the first reserve asset in terms of the second.
+fn spot_price(reserves: &[PoolReserveExplanation]) -> Option<String> {
+    let [a, b] = reserves else {
+        return None;
+    };
+    let amount_a: Amount = a.amount.parse().ok()?;
+    let amount_b: Amount = b.amount.parse().ok()?;
+    if amount_a <= Amount::ZERO {
+        return None;
+    }
+    Some(format!(
+        "1 {} = {} {}",
+        a.asset_code,
+        format_amount(&format!("{:.7}", amount_b.ratio(amount_a)?)),
+        b.asset_code
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POOL_ID: &str = "dd7b1ab831c273310ddbec6f97870aa83c2fbd78ce22aded37ecbf4f3380fac7";
+    const PROVIDER: &str = "GCKFBEIYV2U22IO2BJ4KVJOIP7XPWQGQFKKWXR6DOSJBV7STMAQSMTGG";
+    const USDC: &str = "USDC:GUSDCISSUERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+    fn reserve(asset: &str, amount: &str) -> PoolReserve {
+        PoolReserve {
+            asset: asset.to_string(),
+            amount: amount.to_string(),
+        }
+    }
+
+    fn pool() -> LiquidityPool {
+        LiquidityPool {
+            id: POOL_ID.to_string(),
+            fee_bp: 30,
+            pool_type: "constant_product".to_string(),
+            total_trustlines: 42,
+            total_shares: "5000.0000000".to_string(),
+            reserves: vec![
+                reserve("native", "1000.0000000"),
+                reserve(USDC, "112.4000000"),
+            ],
+            last_modified_ledger: Some(1000),
+        }
+    }
+
+    #[test]
+    fn test_explain_pool_composition() {
+        let explanation = explain_liquidity_pool(&pool(), &[]);
+
+        assert_eq!(
+            explanation.summary,
+            "Liquidity pool dd7b1ab8...3380fac7 holds 1,000 XLM and 112.4 USDC. 5,000 pool shares are held across 42 accounts, and swaps pay a 0.3% fee."
+        );
+        assert_eq!(explanation.fee_percent, "0.3%");
+        assert_eq!(explanation.price.as_deref(), Some("1 XLM = 0.1124 USDC"));
+        assert_eq!(
+            explanation.reserves[1].issuer_label.as_deref(),
+            Some("USDC Issuer (Circle)")
+        );
+        assert!(explanation.recent_activity.is_empty());
+    }
+
+    #[test]
+    fn test_explain_deposit_and_withdraw() {
+        let deposit = PoolActivity {
+            kind: PoolActivityKind::Deposit,
+            account: PROVIDER.to_string(),
+            reserves: vec![reserve("native", "10.0000000"), reserve(USDC, "1.1240000")],
+            shares: "50.0000000".to_string(),
+            transaction_hash: "abc".to_string(),
+            created_at: None,
+        };
+        let withdraw = PoolActivity {
+            kind: PoolActivityKind::Withdraw,
+            shares: "25.0000000".to_string(),
+            ..deposit.clone()
+        };
+
+        let explanation = explain_liquidity_pool(&pool(), &[deposit, withdraw]);
+        let activity = &explanation.recent_activity;
+
+        assert_eq!(activity[0].kind, "deposit");
+        assert_eq!(
+            activity[0].summary,
+            format!("{PROVIDER} deposited 10 XLM and 1.124 USDC and received 50 pool shares.")
+        );
+        assert_eq!(
+            activity[1].summary,
+            format!("{PROVIDER} withdrew 10 XLM and 1.124 USDC by redeeming 25 pool shares.")
+        );
+    }
+}
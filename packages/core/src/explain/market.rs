@@ -0,0 +1,346 @@
+//! What an asset pair's market did recently.
+//!
+//! Horizon's trade aggregations add up a pair's trades per bucket of time.
+//! A summary covers a window (the last hour, day, week, or month), read in
+//! buckets of one of the resolutions Horizon supports, and tells the volume
+//! and how the price moved: "USDC/XLM traded 1,200,000 USDC for 9,500,000
+//! XLM in 3,412 trades over the last 24 hours; the price moved +2.3%, from
+//! 7.75 to 7.928 XLM per USDC."
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::{format_amount, format_exact, split_canonical_asset};
+use crate::explain::slippage::format_rate;
+use crate::explain::templates;
+use crate::models::amount::Amount;
+use crate::models::trade::TradeBucket;
+
+/// Buckets Horizon returns in one page of trade aggregations.
+pub const MAX_BUCKETS: i64 = 200;
+
+/// A price change within this many percent either way counts as flat.
+const FLAT_PERCENT: f64 = 0.05;
+
+const MINUTE_MS: i64 = 60_000;
+const HOUR_MS: i64 = 60 * MINUTE_MS;
+const DAY_MS: i64 = 24 * HOUR_MS;
+
+/// How far back a summary looks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarketWindow {
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+impl MarketWindow {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "1h" => Some(MarketWindow::Hour),
+            "24h" => Some(MarketWindow::Day),
+            "7d" => Some(MarketWindow::Week),
+            "30d" => Some(MarketWindow::Month),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MarketWindow::Hour => "1h",
+            MarketWindow::Day => "24h",
+            MarketWindow::Week => "7d",
+            MarketWindow::Month => "30d",
+        }
+    }
+
+    pub fn millis(self) -> i64 {
+        match self {
+            MarketWindow::Hour => HOUR_MS,
+            MarketWindow::Day => DAY_MS,
+            MarketWindow::Week => 7 * DAY_MS,
+            MarketWindow::Month => 30 * DAY_MS,
+        }
+    }
+
+    fn phrase(self) -> &'static str {
+        match self {
+            MarketWindow::Hour => "the last hour",
+            MarketWindow::Day => "the last 24 hours",
+            MarketWindow::Week => "the last 7 days",
+            MarketWindow::Month => "the last 30 days",
+        }
+    }
+
+    /// Whether buckets of `resolution` cover the window in one page, and
+    /// are no longer than the window itself.
+    pub fn fits(self, resolution: Resolution) -> bool {
+        resolution.millis() <= self.millis()
+            && (self.millis() + resolution.millis() - 1) / resolution.millis() <= MAX_BUCKETS
+    }
+
+    /// The finest resolution that [fits](Self::fits): one-minute buckets
+    /// for the last hour, 15 minutes for a day, an hour for a week, and a
+    /// day for a month.
+    pub fn default_resolution(self) -> Resolution {
+        Resolution::ALL
+            .into_iter()
+            .find(|resolution| self.fits(*resolution))
+            .unwrap_or(Resolution::Week)
+    }
+}
+
+/// A bucket size Horizon aggregates trades in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    Minute,
+    FiveMinutes,
+    FifteenMinutes,
+    Hour,
+    Day,
+    Week,
+}
+
+impl Resolution {
+    /// Finest first.
+    pub const ALL: [Resolution; 6] = [
+        Resolution::Minute,
+        Resolution::FiveMinutes,
+        Resolution::FifteenMinutes,
+        Resolution::Hour,
+        Resolution::Day,
+        Resolution::Week,
+    ];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|resolution| resolution.as_str() == s)
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Resolution::Minute => "1m",
+            Resolution::FiveMinutes => "5m",
+            Resolution::FifteenMinutes => "15m",
+            Resolution::Hour => "1h",
+            Resolution::Day => "1d",
+            Resolution::Week => "1w",
+        }
+    }
+
+    pub fn millis(self) -> i64 {
+        match self {
+            Resolution::Minute => MINUTE_MS,
+            Resolution::FiveMinutes => 5 * MINUTE_MS,
+            Resolution::FifteenMinutes => 15 * MINUTE_MS,
+            Resolution::Hour => HOUR_MS,
+            Resolution::Day => DAY_MS,
+            Resolution::Week => 7 * DAY_MS,
+        }
+    }
+}
+
+/// An asset pair's trading over a recent window.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct MarketSummary {
+    /// "native" or "CODE:ISSUER".
+    pub base: String,
+    /// "native" or "CODE:ISSUER"; prices are in this asset per unit of base.
+    pub counter: String,
+    /// e.g. "24h".
+    pub window: String,
+    /// The bucket size trades were read in, e.g. "15m".
+    pub resolution: String,
+    pub trade_count: u64,
+    /// Base asset that changed hands, as a 7-decimal amount.
+    pub base_volume: String,
+    /// Counter asset that changed hands, as a 7-decimal amount.
+    pub counter_volume: String,
+    /// Price at the start of the first bucket with trades. The prices are
+    /// `null` when the pair did not trade.
+    pub open: Option<f64>,
+    /// Price at the end of the last bucket with trades.
+    pub close: Option<f64>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    /// Change from `open` to `close`, in percent.
+    pub price_change_percent: Option<f64>,
+    pub summary: String,
+}
+
+/// Summarize `buckets`, the pair's trades over `window` oldest first.
+pub fn explain_market(
+    base: &str,
+    counter: &str,
+    window: MarketWindow,
+    resolution: Resolution,
+    buckets: &[TradeBucket],
+) -> MarketSummary {
+    let base_code = split_canonical_asset(base).0;
+    let counter_code = split_canonical_asset(counter).0;
+    let pair = format!("{base_code}/{counter_code}");
+
+    let trade_count: u64 = buckets.iter().map(|b| b.trade_count).sum();
+    let base_volume = buckets
+        .iter()
+        .map(|b| b.base_volume)
+        .fold(Amount::ZERO, Amount::saturating_add);
+    let counter_volume = buckets
+        .iter()
+        .map(|b| b.counter_volume)
+        .fold(Amount::ZERO, Amount::saturating_add);
+
+    let open = buckets.first().map(|b| b.open);
+    let close = buckets.last().map(|b| b.close);
+    let high = buckets.iter().map(|b| b.high).reduce(f64::max);
+    let low = buckets.iter().map(|b| b.low).reduce(f64::min);
+    let price_change_percent = open
+        .zip(close)
+        .filter(|(open, _)| *open > 0.0)
+        .map(|(open, close)| (close - open) / open * 100.0);
+
+    let summary = match (open, close) {
+        (Some(open), Some(close)) if trade_count > 0 => {
+            let trades = match trade_count {
+                1 => "1 trade".to_string(),
+                n => format!("{} trades", format_amount(&n.to_string())),
+            };
+            let traded = templates::render(
+                "market.traded",
+                &[
+                    ("pair", &pair),
+                    ("base_volume", &format_exact(base_volume)),
+                    ("base", base_code),
+                    ("counter_volume", &format_exact(counter_volume)),
+                    ("counter", counter_code),
+                    ("trades", &trades),
+                    ("window", window.phrase()),
+                ],
+            );
+            let rate =
+                |price: f64| format!("{} {counter_code} per {base_code}", format_rate(price));
+            let price = match price_change_percent {
+                Some(percent) if percent.abs() >= FLAT_PERCENT => templates::render(
+                    "market.price_moved",
+                    &[
+                        ("percent", &format!("{percent:+.1}")),
+                        ("open", &format_rate(open)),
+                        ("close", &rate(close)),
+                    ],
+                ),
+                _ => templates::render("market.price_flat", &[("close", &rate(close))]),
+            };
+            format!("{traded}; {price}.")
+        }
+        _ => templates::render(
+            "market.no_trades",
+            &[("pair", &pair), ("window", window.phrase())],
+        ),
+    };
+
+    MarketSummary {
+        base: base.to_string(),
+        counter: counter.to_string(),
+        window: window.as_str().to_string(),
+        resolution: resolution.as_str().to_string(),
+        trade_count,
+        base_volume: base_volume.to_string(),
+        counter_volume: counter_volume.to_string(),
+        open,
+        close,
+        high,
+        low,
+        price_change_percent,
+        summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    fn bucket(trades: u64, base: &str, counter: &str, open: f64, close: f64) -> TradeBucket {
+        TradeBucket {
+            timestamp: 0,
+            trade_count: trades,
+            base_volume: base.parse().unwrap(),
+            counter_volume: counter.parse().unwrap(),
+            open,
+            high: open.max(close) + 0.1,
+            low: open.min(close) - 0.1,
+            close,
+        }
+    }
+
+    #[test]
+    fn test_default_resolution_fits_the_window_in_one_page() {
+        assert_eq!(MarketWindow::Hour.default_resolution(), Resolution::Minute);
+        assert_eq!(
+            MarketWindow::Day.default_resolution(),
+            Resolution::FifteenMinutes
+        );
+        assert_eq!(MarketWindow::Week.default_resolution(), Resolution::Hour);
+        assert_eq!(MarketWindow::Month.default_resolution(), Resolution::Day);
+
+        assert!(!MarketWindow::Day.fits(Resolution::FiveMinutes));
+        assert!(MarketWindow::Month.fits(Resolution::Week));
+        assert!(!MarketWindow::Hour.fits(Resolution::Day));
+    }
+
+    #[test]
+    fn test_explain_market_totals_the_buckets() {
+        let buckets = [
+            bucket(3000, "1000000", "7750000", 7.75, 7.8),
+            bucket(412, "200000", "1750000", 7.8, 7.928),
+        ];
+        let summary = explain_market(
+            USDC,
+            "native",
+            MarketWindow::Day,
+            Resolution::FifteenMinutes,
+            &buckets,
+        );
+
+        assert_eq!(summary.trade_count, 3412);
+        assert_eq!(summary.base_volume, "1200000.0000000");
+        assert_eq!(summary.counter_volume, "9500000.0000000");
+        assert_eq!(summary.open, Some(7.75));
+        assert_eq!(summary.close, Some(7.928));
+        assert_eq!(summary.high, Some(8.028));
+        assert_eq!(summary.low, Some(7.65));
+        assert!((summary.price_change_percent.unwrap() - 2.296).abs() < 0.001);
+        assert_eq!(
+            summary.summary,
+            "USDC/XLM traded 1,200,000 USDC for 9,500,000 XLM in 3,412 trades over the last 24 hours; the price moved +2.3%, from 7.75 to 7.928 XLM per USDC."
+        );
+    }
+
+    #[test]
+    fn test_explain_market_flat_and_quiet() {
+        let flat = explain_market(
+            "native",
+            USDC,
+            MarketWindow::Hour,
+            Resolution::Minute,
+            &[bucket(1, "10", "1.29", 0.129, 0.129)],
+        );
+        assert_eq!(
+            flat.summary,
+            "XLM/USDC traded 10 XLM for 1.29 USDC in 1 trade over the last hour; the price held at 0.129 USDC per XLM."
+        );
+
+        let quiet = explain_market("native", USDC, MarketWindow::Week, Resolution::Hour, &[]);
+        assert_eq!(quiet.trade_count, 0);
+        assert_eq!(quiet.open, None);
+        assert_eq!(quiet.price_change_percent, None);
+        assert_eq!(
+            quiet.summary,
+            "XLM/USDC did not trade over the last 7 days."
+        );
+    }
+}
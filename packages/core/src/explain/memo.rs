@@ -2,6 +2,8 @@
 //!
 //! Provides human-readable explanations for transaction memos.
 
+use crate::explain::format::shorten;
+use crate::explain::templates;
 use crate::models::memo::Memo;
 
 /// Explains a memo in human-readable terms.
@@ -31,30 +33,21 @@ pub fn explain_memo(memo: &Memo) -> Option<String> {
     match memo {
         Memo::None => None,
 
-        Memo::Text(text) => Some(format!("This transaction includes a text memo: \"{text}\"")),
+        Memo::Text(text) => Some(templates::render("memo.text", &[("memo", text)])),
 
-        Memo::Id(id) => Some(format!(
-            "This transaction includes an ID memo: {id}. This is typically used as a reference number, customer ID, or invoice number."
-        )),
+        Memo::Id(id) => Some(templates::render("memo.id", &[("memo", &id.to_string())])),
 
-        Memo::Hash(hash) => Some(format!(
-            "This transaction includes a hash memo: {}. This is typically used to reference a document, contract, or other data.",
-            format_hash(hash)
-        )),
+        Memo::Hash(hash) => Some(templates::render("memo.hash", &[("memo", &shorten(hash))])),
 
-        Memo::Return(hash) => Some(format!(
-            "This transaction includes a return memo: {}. This indicates a refund or return transaction.",
-            format_hash(hash)
+        Memo::Return(hash) => Some(templates::render(
+            "memo.return",
+            &[("memo", &shorten(hash))],
         )),
-    }
-}
 
-/// Formats a hash for display (shows first 8 and last 8 characters).
-fn format_hash(hash: &str) -> String {
-    if hash.len() > 20 {
-        format!("{}...{}", &hash[..8], &hash[hash.len() - 8..])
-    } else {
-        hash.to_string()
+        Memo::Binary(hex) => Some(templates::render(
+            "memo.binary",
+            &[("memo", hex), ("bytes", &(hex.len() / 2).to_string())],
+        )),
     }
 }
 
@@ -76,6 +69,7 @@ pub fn memo_type_description(memo: &Memo) -> &'static str {
         Memo::Id(_) => "ID memo",
         Memo::Hash(_) => "Hash memo",
         Memo::Return(_) => "Return memo",
+        Memo::Binary(_) => "Binary text memo",
     }
 }
 
@@ -108,6 +102,10 @@ pub fn memo_usage_context(memo: &Memo) -> String {
         Memo::Return(_) => String::from(
             "Return memos indicate refund or return transactions, referencing the original transaction",
         ),
+
+        Memo::Binary(_) => String::from(
+            "Text memos holding binary data usually carry a reference packed as raw bytes, which the recipient decodes",
+        ),
     }
 }
 
@@ -161,10 +159,21 @@ mod tests {
         assert!(explanation.contains("refund") || explanation.contains("return"));
     }
 
+    #[test]
+    fn test_explain_binary_memo() {
+        let memo = Memo::from_text_bytes(&[0x00, 0x9f, 0x92, 0x96]).unwrap();
+        let explanation = explain_memo(&memo).unwrap();
+
+        assert!(explanation.contains("binary data"));
+        assert!(explanation.contains("4 bytes"));
+        assert!(explanation.contains("0x009f9296"));
+        assert_eq!(memo_type_description(&memo), "Binary text memo");
+    }
+
     #[test]
     fn test_format_hash_long() {
         let hash = "abcdef1234567890abcdef1234567890abcdef1234567890abcdef1234567890";
-        let formatted = format_hash(hash);
+        let formatted = shorten(hash);
 
         assert!(formatted.contains("abcdef12"));
         assert!(formatted.contains("34567890"));
@@ -175,7 +184,7 @@ mod tests {
     #[test]
     fn test_format_hash_short() {
         let hash = "short";
-        let formatted = format_hash(hash);
+        let formatted = shorten(hash);
 
         assert_eq!(formatted, hash);
     }
@@ -220,6 +229,7 @@ mod tests {
             (Memo::id(123), true),
             (Memo::hash("abc123"), true),
             (Memo::return_hash("def456"), true),
+            (Memo::Binary("ff".to_string()), true),
         ];
 
         for (memo, should_have_explanation) in memos {
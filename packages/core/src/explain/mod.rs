@@ -3,7 +3,37 @@
 //! Protocol explanation and transformation logic.
 
 pub mod account;
+pub mod anchor;
+pub mod anomaly;
+pub mod asset_audit;
+pub mod bundle;
+pub mod changes;
+pub mod claimable_balance;
+pub mod compare;
+pub mod doc_links;
 pub mod failure;
+pub mod fee_advice;
+pub mod flags_history;
+pub mod format;
+pub mod glossary;
+pub mod issuer_label;
+pub mod ledger;
+pub mod liquidity_pool;
+pub mod market;
 pub mod memo;
+pub mod narrative;
+pub mod offer;
 pub mod operation;
+pub mod payment_graph;
+pub mod related_accounts;
+pub mod send_check;
+pub mod signatures;
+pub mod signer;
+pub mod slippage;
+pub mod soroban;
+pub mod source;
+pub mod statement;
+pub mod templates;
+pub mod time;
+pub mod timeline;
 pub mod transaction;
@@ -0,0 +1,298 @@
+//! Narratives for common multi-operation transactions.
+//!
+//! Wallets and services bundle several operations into one transaction to do
+//! a single thing: a swap opts in to the asset it buys, converts through a
+//! path payment, and drops the trust line it no longer needs; onboarding
+//! creates an account, opts it in to an asset, and sends it some. Listed one
+//! by one, the operations hide that intent. Each pattern below recognises one
+//! such shape and tells it as a single paragraph. Like the anchor heuristics,
+//! they are conservative: any operation that does not fit the shape means
+//! no narrative.
+
+use crate::explain::anchor::parse_asset;
+use crate::explain::format::{format_account, format_exact};
+use crate::explain::templates;
+use crate::models::operation::{ChangeTrustOperation, Operation};
+use crate::models::transaction::Transaction;
+
+/// A recognisable multi-operation shape.
+pub struct Pattern {
+    pub name: &'static str,
+    /// The narrative for `operations` if they have this shape.
+    pub detect: fn(&[Operation]) -> Option<String>,
+}
+
+/// Patterns in the order they are tried.
+pub const PATTERNS: &[Pattern] = &[
+    Pattern {
+        name: "swap",
+        detect: swap,
+    },
+    Pattern {
+        name: "account_funding",
+        detect: account_funding,
+    },
+];
+
+/// Tell a successful multi-operation transaction as one paragraph when it
+/// matches a known pattern. Failed transactions changed nothing, so they get
+/// no story.
+pub fn compose_narrative(transaction: &Transaction) -> Option<String> {
+    if !transaction.successful || transaction.operations.len() < 2 {
+        return None;
+    }
+    PATTERNS
+        .iter()
+        .find_map(|pattern| (pattern.detect)(&transaction.operations))
+}
+
+/// Trust lines added, one path payment from an account to itself, then
+/// trust lines removed, all by the same account.
+fn swap(operations: &[Operation]) -> Option<String> {
+    let position = operations
+        .iter()
+        .position(|op| matches!(op, Operation::PathPayment(_)))?;
+    let Operation::PathPayment(path) = &operations[position] else {
+        return None;
+    };
+    let account = path.source_account.as_deref().unwrap_or(&path.destination);
+    if path.destination != account {
+        return None;
+    }
+
+    let mut added = Vec::new();
+    for op in &operations[..position] {
+        match op {
+            Operation::ChangeTrust(trust) if trust.trustor == account && !is_removal(trust) => {
+                added.push(trust.asset_code.clone());
+            }
+            _ => return None,
+        }
+    }
+    let mut removed = Vec::new();
+    for op in &operations[position + 1..] {
+        match op {
+            Operation::ChangeTrust(trust) if trust.trustor == account && is_removal(trust) => {
+                removed.push(trust.asset_code.clone());
+            }
+            _ => return None,
+        }
+    }
+
+    let (send_code, _) = parse_asset(&path.send_asset)?;
+    let (dest_code, _) = parse_asset(&path.dest_asset)?;
+    let mut sentences = vec![templates::render(
+        "narrative.swap",
+        &[
+            ("account", &format_account(account)),
+            ("send_amount", &format_exact(path.send_amount)),
+            ("send_asset", &send_code),
+            ("dest_amount", &format_exact(path.dest_amount)),
+            ("dest_asset", &dest_code),
+        ],
+    )];
+    if !added.is_empty() {
+        sentences.push(templates::render(
+            "narrative.swap.trust_added",
+            &[("assets", &join(&added))],
+        ));
+    }
+    if !removed.is_empty() {
+        sentences.push(templates::render(
+            "narrative.swap.trust_removed",
+            &[("assets", &join(&removed))],
+        ));
+    }
+    Some(sentences.join(" "))
+}
+
+/// An account created, then opted in to assets and paid by its funder.
+fn account_funding(operations: &[Operation]) -> Option<String> {
+    let (Operation::CreateAccount(create), rest) = operations.split_first()? else {
+        return None;
+    };
+    let funder = create.funder.as_str();
+    let account = create.new_account.as_str();
+
+    let mut trusted = Vec::new();
+    let mut payments = Vec::new();
+    for op in rest {
+        match op {
+            Operation::ChangeTrust(trust) if trust.trustor == account && !is_removal(trust) => {
+                trusted.push(trust.asset_code.clone());
+            }
+            Operation::Payment(payment)
+                if payment.destination == account
+                    && payment.source_account.as_deref().unwrap_or(funder) == funder =>
+            {
+                let code = payment.asset_code.as_deref().unwrap_or("XLM");
+                payments.push(format!("{} {code}", format_exact(payment.amount)));
+            }
+            _ => return None,
+        }
+    }
+
+    let funder = format_account(funder);
+    let mut sentences = vec![templates::render(
+        "narrative.funding",
+        &[
+            ("funder", &funder),
+            ("account", &format_account(account)),
+            ("starting_balance", &format_exact(create.starting_balance)),
+        ],
+    )];
+    if !trusted.is_empty() {
+        sentences.push(templates::render(
+            "narrative.funding.trust_added",
+            &[("assets", &join(&trusted))],
+        ));
+    }
+    if !payments.is_empty() {
+        sentences.push(templates::render(
+            "narrative.funding.payments",
+            &[("funder", &funder), ("payments", &join(&payments))],
+        ));
+    }
+    Some(sentences.join(" "))
+}
+
+fn is_removal(trust: &ChangeTrustOperation) -> bool {
+    trust.limit.is_zero()
+}
+
+/// "a", "a and b", "a, b, and c".
+fn join(items: &[String]) -> String {
+    match items {
+        [] => String::new(),
+        [only] => only.clone(),
+        [first, second] => format!("{first} and {second}"),
+        [rest @ .., last] => format!("{}, and {last}", rest.join(", ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::{
+        CreateAccountOperation, PathPaymentOperation, PathPaymentType, PaymentOperation,
+    };
+
+    const TRADER: &str = "GTRADER";
+    const FUNDER: &str = "GFUNDER";
+    const NEW: &str = "GNEW";
+
+    fn trust(trustor: &str, code: &str, limit: &str) -> Operation {
+        Operation::ChangeTrust(ChangeTrustOperation {
+            id: "1".to_string(),
+            trustor: trustor.to_string(),
+            asset_code: code.to_string(),
+            asset_issuer: "GISSUER".to_string(),
+            limit: limit.parse().unwrap(),
+        })
+    }
+
+    fn path_payment(from: &str, to: &str) -> Operation {
+        Operation::PathPayment(PathPaymentOperation {
+            id: "2".to_string(),
+            source_account: Some(from.to_string()),
+            destination: to.to_string(),
+            send_asset: "AQUA (GISSUER)".to_string(),
+            send_amount: "500.0000000".parse().unwrap(),
+            dest_asset: "USDC (GISSUER)".to_string(),
+            dest_amount: "12.5000000".parse().unwrap(),
+            path: vec![],
+            payment_type: PathPaymentType::StrictSend,
+        })
+    }
+
+    fn create_account() -> Operation {
+        Operation::CreateAccount(CreateAccountOperation {
+            id: "3".to_string(),
+            funder: FUNDER.to_string(),
+            new_account: NEW.to_string(),
+            starting_balance: "5.0000000".parse().unwrap(),
+        })
+    }
+
+    fn payment(from: &str, to: &str, code: Option<&str>) -> Operation {
+        Operation::Payment(PaymentOperation {
+            id: "4".to_string(),
+            source_account: Some(from.to_string()),
+            destination: to.to_string(),
+            asset_type: if code.is_some() {
+                "credit_alphanum4"
+            } else {
+                "native"
+            }
+            .to_string(),
+            asset_code: code.map(str::to_string),
+            asset_issuer: code.map(|_| "GISSUER".to_string()),
+            amount: "20.0000000".parse().unwrap(),
+        })
+    }
+
+    fn tx(successful: bool, operations: Vec<Operation>) -> Transaction {
+        Transaction::new("hash".to_string(), successful, 100, operations, None, None)
+    }
+
+    #[test]
+    fn test_swap_with_trust_lines() {
+        let operations = vec![
+            trust(TRADER, "USDC", "922337203685.4775807"),
+            path_payment(TRADER, TRADER),
+            trust(TRADER, "AQUA", "0.0000000"),
+        ];
+        assert_eq!(
+            swap(&operations).unwrap(),
+            "GTRADER swapped 500 AQUA for 12.5 USDC on the decentralized exchange. \
+             It first opted in to hold USDC so it could receive the proceeds. \
+             Afterwards it removed its AQUA trust line, which it no longer needed."
+        );
+    }
+
+    #[test]
+    fn test_swap_requires_one_account_end_to_end() {
+        assert!(swap(&[trust(TRADER, "USDC", "1000"), path_payment(TRADER, NEW)]).is_none());
+        assert!(swap(&[trust(NEW, "USDC", "1000"), path_payment(TRADER, TRADER)]).is_none());
+        assert!(swap(&[path_payment(TRADER, TRADER), trust(TRADER, "USDC", "1000")]).is_none());
+    }
+
+    #[test]
+    fn test_account_funding() {
+        let operations = vec![
+            create_account(),
+            trust(NEW, "USDC", "1000.0000000"),
+            payment(FUNDER, NEW, Some("USDC")),
+            payment(FUNDER, NEW, None),
+        ];
+        assert_eq!(
+            account_funding(&operations).unwrap(),
+            "GFUNDER funded a new account GNEW with 5 XLM. \
+             The new account opted in to hold USDC. \
+             GFUNDER then sent it 20 USDC and 20 XLM."
+        );
+    }
+
+    #[test]
+    fn test_account_funding_rejects_unrelated_operations() {
+        assert!(account_funding(&[create_account(), payment(FUNDER, TRADER, None)]).is_none());
+        assert!(account_funding(&[payment(FUNDER, NEW, None), create_account()]).is_none());
+        assert!(account_funding(&[create_account(), path_payment(TRADER, TRADER)]).is_none());
+    }
+
+    #[test]
+    fn test_compose_narrative_skips_single_and_failed_transactions() {
+        let funding = vec![create_account(), payment(FUNDER, NEW, None)];
+        assert!(compose_narrative(&tx(true, funding.clone())).is_some());
+        assert!(compose_narrative(&tx(false, funding)).is_none());
+        assert!(compose_narrative(&tx(true, vec![path_payment(TRADER, TRADER)])).is_none());
+    }
+
+    #[test]
+    fn test_join() {
+        let items = ["a", "b", "c"].map(String::from);
+        assert_eq!(join(&items[..1]), "a");
+        assert_eq!(join(&items[..2]), "a and b");
+        assert_eq!(join(&items), "a, b, and c");
+    }
+}
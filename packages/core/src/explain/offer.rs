@@ -0,0 +1,189 @@
+//! Explanations for open DEX offers.
+//!
+//! Uses the same vocabulary as the manage_offer explainer: every open offer
+//! is described as selling one asset for another at a price.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::{format_account, format_amount, split_canonical_asset, trim_amount};
+use crate::explain::templates;
+use crate::models::amount::Amount;
+use crate::models::offer::Offer;
+
+/// Plain-English summary of an account's open offers.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct AccountOffersExplanation {
+    pub address: String,
+    pub summary: String,
+    pub offer_count: usize,
+    pub offers: Vec<OfferExplanation>,
+}
+
+/// One open offer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct OfferExplanation {
+    pub offer_id: u64,
+    pub seller: String,
+    /// e.g. "GABC... is selling 500 XLM for USDC at 0.112, 40% filled."
+    pub summary: String,
+    /// Asset code, "XLM" for the native asset.
+    pub selling_asset_code: String,
+    pub selling_asset_issuer: Option<String>,
+    pub buying_asset_code: String,
+    pub buying_asset_issuer: Option<String>,
+    /// Amount of the selling asset still on offer.
+    pub amount_remaining: String,
+    /// Amount of the selling asset already sold, when trade history was available.
+    pub amount_sold: Option<String>,
+    /// Share of the offer already sold, 0–100.
+    pub filled_percent: Option<u32>,
+    /// Units of the buying asset asked per unit of the selling asset.
+    pub price: String,
+    pub sponsor: Option<String>,
+    pub last_modified_time: Option<String>,
+}
+
+/// Explain an open offer. `amount_sold` is the total the offer has sold so
+/// far; without it the offer is described by its remaining amount only.
+pub fn explain_offer(offer: &Offer, amount_sold: Option<&str>) -> OfferExplanation {
+    let (selling_code, selling_issuer) = split_canonical_asset(&offer.selling);
+    let (buying_code, buying_issuer) = split_canonical_asset(&offer.buying);
+    let remaining = trim_amount(&offer.amount);
+    let price = trim_amount(&offer.price);
+    let seller = format_account(&offer.seller);
+
+    let fill = amount_sold.and_then(|sold| fill_progress(&offer.amount, sold));
+    let summary = match &fill {
+        Some((original, percent)) => templates::render(
+            "offer.partially_filled",
+            &[
+                ("seller", &seller),
+                ("amount", &format_amount(original)),
+                ("remaining", &format_amount(&remaining)),
+                ("selling_asset", selling_code),
+                ("buying_asset", buying_code),
+                ("price", &format_amount(&price)),
+                ("filled_percent", &percent.to_string()),
+            ],
+        ),
+        None => templates::render(
+            "offer.open",
+            &[
+                ("seller", &seller),
+                ("amount", &format_amount(&remaining)),
+                ("selling_asset", selling_code),
+                ("buying_asset", buying_code),
+                ("price", &format_amount(&price)),
+            ],
+        ),
+    };
+
+    OfferExplanation {
+        offer_id: offer.id,
+        seller: offer.seller.clone(),
+        summary,
+        selling_asset_code: selling_code.to_string(),
+        selling_asset_issuer: selling_issuer.map(str::to_string),
+        buying_asset_code: buying_code.to_string(),
+        buying_asset_issuer: buying_issuer.map(str::to_string),
+        amount_remaining: remaining,
+        amount_sold: amount_sold.map(trim_amount),
+        filled_percent: fill.map(|(_, percent)| percent),
+        price,
+        sponsor: offer.sponsor.clone(),
+        last_modified_time: offer.last_modified_time.clone(),
+    }
+}
+
+pub fn explain_account_offers(
+    address: &str,
+    offers: Vec<OfferExplanation>,
+) -> AccountOffersExplanation {
+    let count = match offers.len() {
+        0 => "no open offers".to_string(),
+        1 => "1 open offer".to_string(),
+        n => format!("{n} open offers"),
+    };
+    AccountOffersExplanation {
+        address: address.to_string(),
+        summary: templates::render(
+            "offer.account_summary",
+            &[("account", &format_account(address)), ("offers", &count)],
+        ),
+        offer_count: offers.len(),
+        offers,
+    }
+}
+
+/// Original offer size and the whole percentage already sold.
+fn fill_progress(remaining: &str, sold: &str) -> Option<(String, u32)> {
+    let remaining: Amount = remaining.parse().ok()?;
+    let sold: Amount = sold.parse().ok()?;
+    let original = remaining.checked_add(sold)?;
+    if original <= Amount::ZERO {
+        return None;
+    }
+    let percent = (sold.ratio(original)? * 100.0).round() as u32;
+    Some((original.trimmed(), percent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SELLER: &str = "GCKFBEIYV2U22IO2BJ4KVJOIP7XPWQGQFKKWXR6DOSJBV7STMAQSMTGG";
+
+    fn offer() -> Offer {
+        Offer {
+            id: 165561423,
+            seller: SELLER.to_string(),
+            selling: "native".to_string(),
+            buying: "USDC:GISSUER".to_string(),
+            amount: "300.0000000".to_string(),
+            price: "0.1120000".to_string(),
+            sponsor: None,
+            last_modified_ledger: Some(1000),
+            last_modified_time: None,
+        }
+    }
+
+    #[test]
+    fn test_partially_filled_offer() {
+        let explanation = explain_offer(&offer(), Some("200.0000000"));
+
+        assert_eq!(
+            explanation.summary,
+            format!("{SELLER} is selling 500 XLM for USDC at 0.112, 40% filled.")
+        );
+        assert_eq!(explanation.filled_percent, Some(40));
+        assert_eq!(explanation.amount_remaining, "300");
+        assert_eq!(explanation.amount_sold.as_deref(), Some("200"));
+        assert_eq!(explanation.buying_asset_issuer.as_deref(), Some("GISSUER"));
+    }
+
+    #[test]
+    fn test_offer_without_trade_history() {
+        let explanation = explain_offer(&offer(), None);
+
+        assert_eq!(
+            explanation.summary,
+            format!("{SELLER} is selling 300 XLM for USDC at 0.112.")
+        );
+        assert_eq!(explanation.filled_percent, None);
+    }
+
+    #[test]
+    fn test_account_offers_summary() {
+        let none = explain_account_offers(SELLER, vec![]);
+        assert_eq!(
+            none.summary,
+            format!("{SELLER} has no open offers on the Stellar DEX.")
+        );
+
+        let one = explain_account_offers(SELLER, vec![explain_offer(&offer(), None)]);
+        assert_eq!(one.offer_count, 1);
+        assert!(one.summary.contains("has 1 open offer on"));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::explain::templates;
 use crate::models::operation::AccountMergeOperation;
 use serde::{Deserialize, Serialize};
 
@@ -19,9 +20,9 @@ pub struct AccountMergeExplanation {
 /// The source account is removed from the ledger and any remaining XLM
 /// it held is transferred in full to the destination account.
 pub fn explain_account_merge(op: &AccountMergeOperation) -> AccountMergeExplanation {
-    let summary = format!(
-        "{} merged their account into {}, transferring all remaining XLM",
-        op.source, op.destination
+    let summary = templates::render(
+        "account_merge.summary",
+        &[("source", &op.source), ("destination", &op.destination)],
     );
 
     AccountMergeExplanation {
@@ -1,3 +1,5 @@
+use crate::explain::format::format_exact;
+use crate::explain::templates;
 use crate::models::operation::ChangeTrustOperation;
 use serde::{Deserialize, Serialize};
 
@@ -27,14 +29,26 @@ pub struct ChangeTrustExplanation {
 /// A limit of "0" means the account is removing an existing trust line.
 /// Any other limit means the account is adding or updating a trust line.
 pub fn explain_change_trust(op: &ChangeTrustOperation) -> ChangeTrustExplanation {
-    let is_removal = op.limit == "0";
+    let is_removal = op.limit.is_zero();
 
     let summary = if is_removal {
-        format!("{} removed trust for {}.", op.trustor, op.asset_code)
+        templates::render(
+            "change_trust.removed",
+            &[
+                ("trustor", &op.trustor),
+                ("asset_code", &op.asset_code),
+                ("asset_issuer", &op.asset_issuer),
+            ],
+        )
     } else {
-        format!(
-            "{} opted in to hold up to {} {} issued by {}.",
-            op.trustor, op.limit, op.asset_code, op.asset_issuer
+        templates::render(
+            "change_trust.added",
+            &[
+                ("trustor", &op.trustor),
+                ("limit", &format_exact(op.limit)),
+                ("asset_code", &op.asset_code),
+                ("asset_issuer", &op.asset_issuer),
+            ],
         )
     };
 
@@ -43,7 +57,7 @@ pub fn explain_change_trust(op: &ChangeTrustOperation) -> ChangeTrustExplanation
         trustor: op.trustor.clone(),
         asset_code: op.asset_code.clone(),
         asset_issuer: op.asset_issuer.clone(),
-        limit: op.limit.clone(),
+        limit: op.limit.to_string(),
         is_removal,
     }
 }
@@ -63,7 +77,7 @@ mod tests {
             trustor: trustor.to_string(),
             asset_code: asset_code.to_string(),
             asset_issuer: asset_issuer.to_string(),
-            limit: limit.to_string(),
+            limit: limit.parse().unwrap(),
         }
     }
 
@@ -76,10 +90,10 @@ mod tests {
         assert_eq!(explanation.trustor, "GAAAA");
         assert_eq!(explanation.asset_code, "USDC");
         assert_eq!(explanation.asset_issuer, "GBBB");
-        assert_eq!(explanation.limit, "10000");
+        assert_eq!(explanation.limit, "10000.0000000");
         assert!(explanation.summary.contains("GAAAA"));
         assert!(explanation.summary.contains("opted in"));
-        assert!(explanation.summary.contains("10000"));
+        assert!(explanation.summary.contains("10,000"));
         assert!(explanation.summary.contains("USDC"));
         assert!(explanation.summary.contains("GBBB"));
     }
@@ -92,7 +106,7 @@ mod tests {
         assert!(explanation.is_removal);
         assert_eq!(explanation.trustor, "GAAAA");
         assert_eq!(explanation.asset_code, "USDC");
-        assert_eq!(explanation.limit, "0");
+        assert_eq!(explanation.limit, "0.0000000");
         assert!(explanation.summary.contains("GAAAA"));
         assert!(explanation.summary.contains("removed trust"));
         assert!(explanation.summary.contains("USDC"));
@@ -111,7 +125,7 @@ mod tests {
         let explanation = explain_change_trust(&op);
         assert_eq!(
             explanation.summary,
-            "GAAAA opted in to hold up to 10000 USDC issued by GBBB."
+            "GAAAA opted in to hold up to 10,000 USDC issued by GBBB."
         );
     }
 
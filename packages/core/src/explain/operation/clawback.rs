@@ -4,6 +4,8 @@
 //! funds from a holder's account. It is often unexpected by the recipient
 //! so explanations include contextual information about what clawback means.
 
+use crate::explain::format::{format_exact, shorten};
+use crate::explain::templates;
 use crate::models::operation::{ClawbackClaimableBalanceOperation, ClawbackOperation};
 use serde::{Deserialize, Serialize};
 
@@ -56,9 +58,15 @@ pub fn explain_clawback(op: &ClawbackOperation) -> ClawbackExplanation {
         .clone()
         .unwrap_or_else(|| "Unknown issuer".to_string());
 
-    let summary = format!(
-        "The asset issuer reclaimed {} {} from {}. {}",
-        op.amount, op.asset_code, op.from, CLAWBACK_CONTEXT
+    let summary = templates::render(
+        "clawback.summary",
+        &[
+            ("amount", &format_exact(op.amount)),
+            ("asset_code", &op.asset_code),
+            ("from", &op.from),
+            ("issuer", &issuer),
+            ("context", CLAWBACK_CONTEXT),
+        ],
     );
 
     ClawbackExplanation {
@@ -67,7 +75,7 @@ pub fn explain_clawback(op: &ClawbackOperation) -> ClawbackExplanation {
         from: op.from.clone(),
         asset_code: op.asset_code.clone(),
         asset_issuer: op.asset_issuer.clone(),
-        amount: op.amount.clone(),
+        amount: op.amount.to_string(),
     }
 }
 
@@ -83,10 +91,16 @@ pub fn explain_clawback_claimable_balance(
         .clone()
         .unwrap_or_else(|| "Unknown issuer".to_string());
 
-    let short_id = shorten_id(&op.balance_id);
+    let short_id = shorten(&op.balance_id);
 
-    let summary =
-        format!("The asset issuer clawed back claimable balance {short_id}. {CLAWBACK_CONTEXT}");
+    let summary = templates::render(
+        "clawback_claimable_balance.summary",
+        &[
+            ("balance_id", &short_id),
+            ("issuer", &issuer),
+            ("context", CLAWBACK_CONTEXT),
+        ],
+    );
 
     ClawbackClaimableBalanceExplanation {
         summary,
@@ -95,15 +109,6 @@ pub fn explain_clawback_claimable_balance(
     }
 }
 
-/// Shorten a long balance ID for display: "00000000abcd...ef12"
-fn shorten_id(id: &str) -> String {
-    if id.len() > 16 {
-        format!("{}...{}", &id[..8], &id[id.len() - 4..])
-    } else {
-        id.to_string()
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -116,7 +121,7 @@ mod tests {
             from: "GHOLDER456".to_string(),
             asset_code: "USDC".to_string(),
             asset_issuer: "GISSUER123".to_string(),
-            amount: "100".to_string(),
+            amount: "100".parse().unwrap(),
         }
     }
 
@@ -167,7 +172,7 @@ mod tests {
         assert_eq!(result.from, "GHOLDER456");
         assert_eq!(result.asset_code, "USDC");
         assert_eq!(result.asset_issuer, "GISSUER123");
-        assert_eq!(result.amount, "100");
+        assert_eq!(result.amount, "100.0000000");
         assert_eq!(result.issuer, "GISSUER123");
     }
 
@@ -184,7 +189,7 @@ mod tests {
     #[test]
     fn test_clawback_fractional_amount() {
         let op = ClawbackOperation {
-            amount: "0.0000001".to_string(),
+            amount: "0.0000001".parse().unwrap(),
             ..base_clawback()
         };
         let result = explain_clawback(&op);
@@ -1,3 +1,5 @@
+use crate::explain::format::format_exact;
+use crate::explain::templates;
 use crate::models::operation::CreateAccountOperation;
 use serde::{Deserialize, Serialize};
 
@@ -22,16 +24,20 @@ pub struct CreateAccountExplanation {
 /// The funder sends a starting balance (in XLM) which covers the base reserve
 /// and makes the account usable on the network.
 pub fn explain_create_account(op: &CreateAccountOperation) -> CreateAccountExplanation {
-    let summary = format!(
-        "{} created account {} with a starting balance of {} XLM.",
-        op.funder, op.new_account, op.starting_balance
+    let summary = templates::render(
+        "create_account.summary",
+        &[
+            ("funder", &op.funder),
+            ("account", &op.new_account),
+            ("starting_balance", &format_exact(op.starting_balance)),
+        ],
     );
 
     CreateAccountExplanation {
         summary,
         funder: op.funder.clone(),
         new_account: op.new_account.clone(),
-        starting_balance: op.starting_balance.clone(),
+        starting_balance: op.starting_balance.to_string(),
     }
 }
 
@@ -48,7 +54,7 @@ mod tests {
             id: "test_op_id".to_string(),
             funder: funder.to_string(),
             new_account: new_account.to_string(),
-            starting_balance: starting_balance.to_string(),
+            starting_balance: starting_balance.parse().unwrap(),
         }
     }
 
@@ -59,7 +65,7 @@ mod tests {
 
         assert_eq!(explanation.funder, "GAAAA");
         assert_eq!(explanation.new_account, "GBBBB");
-        assert_eq!(explanation.starting_balance, "100");
+        assert_eq!(explanation.starting_balance, "100.0000000");
         assert!(explanation.summary.contains("GAAAA"));
         assert!(explanation.summary.contains("GBBBB"));
         assert!(explanation.summary.contains("100"));
@@ -82,7 +88,7 @@ mod tests {
         let explanation = explain_create_account(&op);
         assert_eq!(explanation.funder, "GSOURCE");
         assert_eq!(explanation.new_account, "GDESTINATION");
-        assert_eq!(explanation.starting_balance, "200.5");
+        assert_eq!(explanation.starting_balance, "200.5000000");
     }
 
     #[test]
@@ -96,10 +102,10 @@ mod tests {
 
     #[test]
     fn test_explain_create_account_default_balance_fallback() {
-        // When starting_balance is missing from Horizon, From mapping defaults to "0"
+        // When starting_balance is missing from Horizon, From mapping defaults to zero
         let op = make_create_account("GAAAA", "GBBBB", "0");
         let explanation = explain_create_account(&op);
-        assert_eq!(explanation.starting_balance, "0");
+        assert_eq!(explanation.starting_balance, "0.0000000");
         assert!(explanation.summary.contains("0"));
     }
 }
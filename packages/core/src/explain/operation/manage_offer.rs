@@ -1,4 +1,8 @@
+use crate::explain::format::{format_amount, format_exact};
+use crate::explain::templates;
+use crate::models::amount::Amount;
 use crate::models::operation::{ManageOfferOperation, OfferType};
+use crate::models::trade::TradeFill;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -14,16 +18,19 @@ pub struct ManageOfferExplanation {
 }
 
 pub fn explain_manage_offer(op: &ManageOfferOperation) -> ManageOfferExplanation {
-    if op.amount == "0" && op.offer_id > 0 {
+    if op.amount.is_zero() && op.offer_id > 0 {
         return ManageOfferExplanation {
-            summary: format!(
-                "{} cancelled their existing offer #{}",
-                op.seller, op.offer_id
+            summary: templates::render(
+                "manage_offer.cancelled",
+                &[
+                    ("seller", &op.seller),
+                    ("offer_id", &op.offer_id.to_string()),
+                ],
             ),
             seller: op.seller.clone(),
             selling_asset: op.selling_asset.clone(),
             buying_asset: op.buying_asset.clone(),
-            amount: op.amount.clone(),
+            amount: op.amount.to_string(),
             price: op.price.clone(),
             offer_id: op.offer_id,
             action: "cancel".to_string(),
@@ -35,9 +42,16 @@ pub fn explain_manage_offer(op: &ManageOfferOperation) -> ManageOfferExplanation
         OfferType::Buy => ("buy", &op.buying_asset, &op.selling_asset),
     };
 
-    let summary = format!(
-        "{} placed an order to {} {} {} for {} at a price of {} {} per {}",
-        op.seller, action, op.amount, base_asset, quote_asset, op.price, quote_asset, base_asset,
+    let summary = templates::render(
+        "manage_offer.placed",
+        &[
+            ("seller", &op.seller),
+            ("action", action),
+            ("amount", &format_exact(op.amount)),
+            ("base_asset", base_asset),
+            ("quote_asset", quote_asset),
+            ("price", &format_amount(&op.price)),
+        ],
     );
 
     let op_action = if op.offer_id == 0 { "new" } else { "update" };
@@ -47,13 +61,56 @@ pub fn explain_manage_offer(op: &ManageOfferOperation) -> ManageOfferExplanation
         seller: op.seller.clone(),
         selling_asset: op.selling_asset.clone(),
         buying_asset: op.buying_asset.clone(),
-        amount: op.amount.clone(),
+        amount: op.amount.to_string(),
         price: op.price.clone(),
         offer_id: op.offer_id,
         action: op_action.to_string(),
     }
 }
 
+/// What an order traded as soon as it was placed, against offers already on
+/// the book.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OfferFills {
+    /// Number of existing offers it traded with.
+    pub trades: usize,
+    /// Total of the buying asset received.
+    pub bought_amount: String,
+    /// Total of the selling asset given up.
+    pub sold_amount: String,
+    /// e.g. "it immediately traded with 3 existing offers, buying 45 USDC
+    /// (GISSUER) for 400 XLM (native)"
+    pub summary: String,
+}
+
+/// Describe the trades `op` made when it was placed, from its seller's
+/// side. None when it traded nothing and only rested on the book.
+pub fn explain_offer_fills(op: &ManageOfferOperation, fills: &[TradeFill]) -> Option<OfferFills> {
+    if fills.is_empty() {
+        return None;
+    }
+    let bought: Amount = fills.iter().map(|fill| fill.bought_amount).sum();
+    let sold: Amount = fills.iter().map(|fill| fill.sold_amount).sum();
+    let count = fills.len();
+    let summary = templates::render(
+        "manage_offer.filled",
+        &[
+            ("count", &count.to_string()),
+            ("offers", if count == 1 { "offer" } else { "offers" }),
+            ("bought_amount", &format_exact(bought)),
+            ("buying_asset", &op.buying_asset),
+            ("sold_amount", &format_exact(sold)),
+            ("selling_asset", &op.selling_asset),
+        ],
+    );
+    Some(OfferFills {
+        trades: count,
+        bought_amount: bought.to_string(),
+        sold_amount: sold.to_string(),
+        summary,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -65,7 +122,7 @@ mod tests {
             seller: "GAAAA".to_string(),
             selling_asset: "XLM (native)".to_string(),
             buying_asset: "USDC (GISSUER)".to_string(),
-            amount: "100".to_string(),
+            amount: "100".parse().unwrap(),
             price: "0.10".to_string(),
             offer_id: 0,
             offer_type: OfferType::Sell,
@@ -80,7 +137,7 @@ mod tests {
         assert!(result.summary.contains("100"));
         assert!(result.summary.contains("XLM (native)"));
         assert!(result.summary.contains("USDC (GISSUER)"));
-        assert!(result.summary.contains("0.10"));
+        assert!(result.summary.contains("price of 0.1 "));
     }
 
     #[test]
@@ -97,7 +154,7 @@ mod tests {
     #[test]
     fn test_cancel_offer() {
         let op = ManageOfferOperation {
-            amount: "0".to_string(),
+            amount: "0".parse().unwrap(),
             offer_id: 12345,
             ..base_op()
         };
@@ -121,4 +178,26 @@ mod tests {
         assert!(result.summary.contains("placed an order to buy"));
         assert!(result.summary.contains("USDC (GISSUER)"));
     }
+
+    #[test]
+    fn test_offer_fills() {
+        let fill = |sold: &str, bought: &str| TradeFill {
+            sold_asset: "native".to_string(),
+            sold_amount: sold.parse().unwrap(),
+            bought_asset: "USDC:GISSUER".to_string(),
+            bought_amount: bought.parse().unwrap(),
+        };
+        let fills = [fill("200", "22.5"), fill("150", "15"), fill("50", "7.5")];
+        let result = explain_offer_fills(&base_op(), &fills).unwrap();
+        assert_eq!(result.trades, 3);
+        assert_eq!(result.bought_amount, "45.0000000");
+        assert_eq!(
+            result.summary,
+            "it immediately traded with 3 existing offers, buying 45 USDC (GISSUER) for 400 XLM (native)"
+        );
+
+        let one = explain_offer_fills(&base_op(), &fills[..1]).unwrap();
+        assert!(one.summary.contains("with 1 existing offer,"));
+        assert_eq!(explain_offer_fills(&base_op(), &[]), None);
+    }
 }
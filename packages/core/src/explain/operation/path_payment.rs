@@ -1,3 +1,5 @@
+use crate::explain::format::format_exact;
+use crate::explain::templates;
 use crate::models::operation::{PathPaymentOperation, PathPaymentType};
 use serde::{Deserialize, Serialize};
 
@@ -32,14 +34,26 @@ pub fn explain_path_payment(op: &PathPaymentOperation) -> PathPaymentExplanation
     };
 
     let conversion = if op.send_asset == op.dest_asset {
-        format!(
-            "{} sent {} {} to {}",
-            sender, op.send_amount, op.send_asset, op.destination
+        templates::render(
+            "path_payment.direct",
+            &[
+                ("from", &sender),
+                ("send_amount", &format_exact(op.send_amount)),
+                ("send_asset", &op.send_asset),
+                ("to", &op.destination),
+            ],
         )
     } else {
-        format!(
-            "{} sent {} {} which was converted to {} {} received by {}",
-            sender, op.send_amount, op.send_asset, op.dest_amount, op.dest_asset, op.destination
+        templates::render(
+            "path_payment.converted",
+            &[
+                ("from", &sender),
+                ("send_amount", &format_exact(op.send_amount)),
+                ("send_asset", &op.send_asset),
+                ("dest_amount", &format_exact(op.dest_amount)),
+                ("dest_asset", &op.dest_asset),
+                ("to", &op.destination),
+            ],
         )
     };
 
@@ -59,9 +73,9 @@ pub fn explain_path_payment(op: &PathPaymentOperation) -> PathPaymentExplanation
         sender,
         destination: op.destination.clone(),
         send_asset: op.send_asset.clone(),
-        send_amount: op.send_amount.clone(),
+        send_amount: op.send_amount.to_string(),
         dest_asset: op.dest_asset.clone(),
-        dest_amount: op.dest_amount.clone(),
+        dest_amount: op.dest_amount.to_string(),
         path_description,
         payment_type,
     }
@@ -78,9 +92,9 @@ mod tests {
             source_account: Some("GAAAA".to_string()),
             destination: "GBBB".to_string(),
             send_asset: "XLM (native)".to_string(),
-            send_amount: "50".to_string(),
+            send_amount: "50".parse().unwrap(),
             dest_asset: "USDC (GISSUER)".to_string(),
-            dest_amount: "45".to_string(),
+            dest_amount: "45".parse().unwrap(),
             path: vec![],
             payment_type: PathPaymentType::StrictSend,
         }
@@ -1,9 +1,11 @@
+use crate::explain::format::{format_account, format_amount, format_asset, format_exact};
+use crate::explain::templates;
 use crate::models::fee::FeeStats;
 use crate::models::operation::PaymentOperation;
-use crate::services::labels::resolve_label;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct PaymentExplanation {
     /// Short, human-readable payment summary
     pub summary: String,
@@ -30,18 +32,27 @@ pub struct PaymentExplanation {
 ///
 /// Use this when fee stats are unavailable. fee_note will be None.
 pub fn explain_payment(op: &PaymentOperation) -> PaymentExplanation {
-    let asset = format_asset(op);
+    let asset = format_asset(
+        Some(&op.asset_type),
+        op.asset_code.as_deref(),
+        op.asset_issuer.as_deref(),
+    );
     let from = op
         .source_account
         .clone()
         .unwrap_or_else(|| "Unknown".to_string());
     let to = op.destination.clone();
-    let from_display = format_account_for_summary(&from);
-    let to_display = format_account_for_summary(&to);
-
-    let summary = format!(
-        "{} sent {} {} to {}",
-        from_display, op.amount, asset, to_display
+    let from_display = format_account(&from);
+    let to_display = format_account(&to);
+
+    let summary = templates::render(
+        "payment.summary",
+        &[
+            ("from", &from_display),
+            ("amount", &format_exact(op.amount)),
+            ("asset", &asset),
+            ("to", &to_display),
+        ],
     );
 
     PaymentExplanation {
@@ -49,7 +60,7 @@ pub fn explain_payment(op: &PaymentOperation) -> PaymentExplanation {
         from,
         to,
         asset,
-        amount: op.amount.clone(),
+        amount: op.amount.to_string(),
         fee_note: None,
     }
 }
@@ -63,29 +74,42 @@ pub fn explain_payment_with_fee(
     fee_charged: u64,
     network_fees: &FeeStats,
 ) -> PaymentExplanation {
-    let asset = format_asset(op);
+    let asset = format_asset(
+        Some(&op.asset_type),
+        op.asset_code.as_deref(),
+        op.asset_issuer.as_deref(),
+    );
     let from = op
         .source_account
         .clone()
         .unwrap_or_else(|| "Unknown".to_string());
     let to = op.destination.clone();
-    let from_display = format_account_for_summary(&from);
-    let to_display = format_account_for_summary(&to);
-
-    let summary = format!(
-        "{} sent {} {} to {}",
-        from_display, op.amount, asset, to_display
+    let from_display = format_account(&from);
+    let to_display = format_account(&to);
+
+    let summary = templates::render(
+        "payment.summary",
+        &[
+            ("from", &from_display),
+            ("amount", &format_exact(op.amount)),
+            ("asset", &asset),
+            ("to", &to_display),
+        ],
     );
 
-    let xlm = FeeStats::stroops_to_xlm(fee_charged);
+    let xlm = format_amount(&FeeStats::stroops_to_xlm(fee_charged));
 
     let fee_note = if network_fees.is_high_fee(fee_charged) {
         let multiplier = fee_charged / network_fees.base_fee.max(1);
-        Some(format!(
-            "Fee paid: {xlm} XLM (above average — {multiplier}x base fee)."
+        Some(templates::render(
+            "payment.fee_note.elevated",
+            &[("fee", &xlm), ("multiplier", &multiplier.to_string())],
         ))
     } else {
-        Some(format!("Fee paid: {xlm} XLM (standard)."))
+        Some(templates::render(
+            "payment.fee_note.standard",
+            &[("fee", &xlm)],
+        ))
     };
 
     PaymentExplanation {
@@ -93,39 +117,11 @@ pub fn explain_payment_with_fee(
         from,
         to,
         asset,
-        amount: op.amount.clone(),
+        amount: op.amount.to_string(),
         fee_note,
     }
 }
 
-fn format_asset(op: &PaymentOperation) -> String {
-    match op.asset_type.as_str() {
-        "native" => "XLM (native)".to_string(),
-        _ => {
-            if let Some(code) = &op.asset_code {
-                if let Some(issuer) = &op.asset_issuer {
-                    format!("{code} ({issuer})")
-                } else {
-                    code.clone()
-                }
-            } else {
-                "Unknown".to_string()
-            }
-        }
-    }
-}
-
-fn format_account_for_summary(address: &str) -> String {
-    if address == "Unknown" {
-        return "Unknown".to_string();
-    }
-
-    match resolve_label(address) {
-        Some(label) => format!("{label} ({address})"),
-        None => address.to_string(),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -146,7 +142,7 @@ mod tests {
             asset_type,
             asset_code,
             asset_issuer,
-            amount,
+            amount: amount.parse().unwrap(),
         }
     }
 
@@ -184,7 +180,7 @@ mod tests {
         assert!(explanation.fee_note.is_some());
         let note = explanation.fee_note.unwrap();
         assert!(note.contains("standard"));
-        assert!(note.contains("0.0000100"));
+        assert!(note.contains("0.00001 XLM"));
     }
 
     #[test]
@@ -245,7 +241,7 @@ mod tests {
         let explanation = explain_payment(&op);
         assert_eq!(explanation.from, "GSENDER");
         assert_eq!(explanation.to, "GRECIPIENT");
-        assert_eq!(explanation.amount, "100.5");
+        assert_eq!(explanation.amount, "100.5000000");
         assert_eq!(explanation.asset, "XLM (native)");
         assert_eq!(
             explanation.summary,
@@ -4,6 +4,8 @@
 //! This module enumerates every field that was set and assembles
 //! them into a single readable summary.
 
+use crate::explain::signer::describe_signer_key;
+use crate::explain::templates;
 use crate::models::operation::SetOptionsOperation;
 use serde::{Deserialize, Serialize};
 
@@ -12,14 +14,14 @@ use serde::{Deserialize, Serialize};
 pub struct SetOptionsExplanation {
     /// Full natural-language summary of what changed.
     /// e.g. "GAAAA updated their account: set home domain to example.com,
-    ///        and added signer GBBB...YYYY with weight 1"
+    ///        and added signer GBBBBBBB...BBBBYYYY with weight 1"
     pub summary: String,
 
     /// The account that submitted the operation. "Unknown" if not present.
     pub account: String,
 
     /// One entry per modified field.
-    /// e.g. ["set home domain to example.com", "added signer GBBB...YYYY with weight 1"]
+    /// e.g. ["set home domain to example.com", "added signer GBBBBBBB...BBBBYYYY with weight 1"]
     pub changes: Vec<String>,
 }
 
@@ -86,16 +88,16 @@ pub fn explain_set_options(op: &SetOptionsOperation) -> SetOptionsExplanation {
 
     // Signer — weight 0 means remove, anything else means add/modify
     if let Some(ref key) = op.signer_key {
-        let short_key = shorten_key(key);
+        let signer = describe_signer_key(key);
         match op.signer_weight {
             Some(0) => {
-                changes.push(format!("removed signer {short_key}"));
+                changes.push(format!("removed {signer}"));
             }
             Some(weight) => {
-                changes.push(format!("added signer {short_key} with weight {weight}"));
+                changes.push(format!("added {signer} with weight {weight}"));
             }
             None => {
-                changes.push(format!("modified signer {short_key}"));
+                changes.push(format!("modified {signer}"));
             }
         }
     }
@@ -112,12 +114,11 @@ pub fn explain_set_options(op: &SetOptionsOperation) -> SetOptionsExplanation {
 /// Build the final summary string.
 fn build_summary(account: &str, changes: &[String]) -> String {
     if changes.is_empty() {
-        return format!("{account} submitted a set_options operation with no recognised changes.");
+        return templates::render("set_options.no_changes", &[("account", account)]);
     }
-    format!(
-        "{} updated their account: {}",
-        account,
-        join_changes(changes)
+    templates::render(
+        "set_options.summary",
+        &[("account", account), ("changes", &join_changes(changes))],
     )
 }
 
@@ -125,7 +126,7 @@ fn build_summary(account: &str, changes: &[String]) -> String {
 ///   1 item  → "a"
 ///   2 items → "a and b"
 ///   3+      → "a, b, and c"
-fn join_changes(changes: &[String]) -> String {
+pub(crate) fn join_changes(changes: &[String]) -> String {
     match changes.len() {
         0 => String::new(),
         1 => changes[0].clone(),
@@ -138,9 +139,19 @@ fn join_changes(changes: &[String]) -> String {
 }
 
 /// Translate Stellar account flag bitmasks into readable names.
-/// AUTH_REQUIRED=1, AUTH_REVOCABLE=2, AUTH_IMMUTABLE=4, CLAWBACK_ENABLED=8
 fn describe_flags(flags: u32) -> String {
-    let mut names: Vec<&str> = Vec::new();
+    let names = flag_names(flags);
+    if names.is_empty() {
+        flags.to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+/// The names of the account flags set in a bitmask.
+/// AUTH_REQUIRED=1, AUTH_REVOCABLE=2, AUTH_IMMUTABLE=4, CLAWBACK_ENABLED=8
+pub(crate) fn flag_names(flags: u32) -> Vec<&'static str> {
+    let mut names: Vec<&'static str> = Vec::new();
     if flags & 1 != 0 {
         names.push("AUTH_REQUIRED");
     }
@@ -153,20 +164,7 @@ fn describe_flags(flags: u32) -> String {
     if flags & 8 != 0 {
         names.push("CLAWBACK_ENABLED");
     }
-    if names.is_empty() {
-        flags.to_string()
-    } else {
-        names.join(", ")
-    }
-}
-
-/// Shorten a long Stellar key for display: "GABC...WXYZ"
-fn shorten_key(key: &str) -> String {
-    if key.len() > 12 {
-        format!("{}...{}", &key[..4], &key[key.len() - 4..])
-    } else {
-        key.to_string()
-    }
+    names
 }
 
 #[cfg(test)]
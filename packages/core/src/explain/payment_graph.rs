@@ -0,0 +1,293 @@
+//! Payment flows around an account, as a directed graph.
+//!
+//! Nodes are accounts, labelled when they are well known; edges are the
+//! payments from one account to another, added up per asset. The graph is
+//! returned as JSON or rendered as Graphviz DOT for visualization tools.
+
+use std::collections::{BTreeMap, HashMap};
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::{count, format_asset, format_exact, shorten};
+use crate::explain::templates;
+use crate::models::amount::Amount;
+use crate::models::operation::Operation;
+use crate::services::labels::resolve_label;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct GraphNode {
+    pub id: String,
+    /// Known name for the account, e.g. "Coinbase".
+    pub label: Option<String>,
+    /// Hops from the account the graph was built around.
+    pub depth: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct GraphEdge {
+    pub from: String,
+    pub to: String,
+    /// "XLM (native)" or "CODE (ISSUER)".
+    pub asset: String,
+    /// Sum of the payments, e.g. "1200.0000000".
+    pub total: String,
+    pub payment_count: u32,
+    /// e.g. "GABC...WXYZ sent GDEF...1234 1,200 XLM in 3 payments."
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct PaymentGraph {
+    pub address: String,
+    pub depth: u32,
+    pub summary: String,
+    /// Nearest first.
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+    /// True when accounts or payments were left out to keep the graph small.
+    pub truncated: bool,
+}
+
+/// Value one operation moved from one account to another.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PaymentFlow {
+    pub from: String,
+    pub to: String,
+    pub asset: String,
+    pub amount: Amount,
+}
+
+/// The payment `op` made, if it moved value between two accounts. Path
+/// payments count what arrived.
+pub fn payment_flow(op: &Operation) -> Option<PaymentFlow> {
+    let (from, to, asset, amount) = match op {
+        Operation::Payment(p) => (
+            p.source_account.clone()?,
+            p.destination.clone(),
+            format_asset(
+                Some(p.asset_type.as_str()),
+                p.asset_code.as_deref(),
+                p.asset_issuer.as_deref(),
+            ),
+            p.amount,
+        ),
+        Operation::CreateAccount(c) => (
+            c.funder.clone(),
+            c.new_account.clone(),
+            "XLM (native)".to_string(),
+            c.starting_balance,
+        ),
+        Operation::PathPayment(p) => (
+            p.source_account.clone()?,
+            p.destination.clone(),
+            p.dest_asset.clone(),
+            p.dest_amount,
+        ),
+        _ => return None,
+    };
+    (from != to && !to.is_empty()).then_some(PaymentFlow {
+        from,
+        to,
+        asset,
+        amount,
+    })
+}
+
+/// Build the graph from the accounts it reached, with their depths, and the
+/// payments found between them.
+pub fn build_payment_graph(
+    address: &str,
+    depth: u32,
+    nodes: &HashMap<String, u32>,
+    flows: &[PaymentFlow],
+    truncated: bool,
+) -> PaymentGraph {
+    let mut totals: BTreeMap<(&str, &str, &str), (Amount, u32)> = BTreeMap::new();
+    for flow in flows {
+        let total = totals
+            .entry((&flow.from, &flow.to, &flow.asset))
+            .or_insert((Amount::ZERO, 0));
+        total.0 = total.0.saturating_add(flow.amount);
+        total.1 += 1;
+    }
+    let edges: Vec<GraphEdge> = totals
+        .into_iter()
+        .map(|((from, to, asset), (total, payments))| GraphEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            asset: asset.to_string(),
+            total: total.to_string(),
+            payment_count: payments,
+            summary: templates::render(
+                "payment_graph.edge",
+                &[
+                    ("from", &shorten(from)),
+                    ("to", &shorten(to)),
+                    ("amount", &format_exact(total)),
+                    ("asset", asset_code(asset)),
+                    ("payments", &count(payments as usize, "payment", "payments")),
+                ],
+            ),
+        })
+        .collect();
+
+    let mut nodes: Vec<GraphNode> = nodes
+        .iter()
+        .map(|(id, depth)| GraphNode {
+            id: id.clone(),
+            label: resolve_label(id).map(str::to_string),
+            depth: *depth,
+        })
+        .collect();
+    nodes.sort_by(|a, b| a.depth.cmp(&b.depth).then_with(|| a.id.cmp(&b.id)));
+
+    let summary = templates::render(
+        "payment_graph.summary",
+        &[
+            (
+                "flows",
+                &count(edges.len(), "payment flow", "payment flows"),
+            ),
+            ("accounts", &count(nodes.len(), "account", "accounts")),
+            ("hops", &count(depth as usize, "hop", "hops")),
+            ("account", &shorten(address)),
+        ],
+    );
+
+    PaymentGraph {
+        address: address.to_string(),
+        depth,
+        summary,
+        nodes,
+        edges,
+        truncated,
+    }
+}
+
+/// The graph in Graphviz DOT, e.g. for `dot -Tsvg`.
+pub fn to_dot(graph: &PaymentGraph) -> String {
+    let mut dot = String::from("digraph payments {\n    rankdir=LR;\n    node [shape=box];\n");
+    for node in &graph.nodes {
+        let label = match &node.label {
+            Some(label) => format!("{label}\\n{}", shorten(&node.id)),
+            None => shorten(&node.id),
+        };
+        let style = if node.id == graph.address {
+            ", style=bold"
+        } else {
+            ""
+        };
+        dot.push_str(&format!(
+            "    \"{}\" [label=\"{}\"{style}];\n",
+            escape(&node.id),
+            escape(&label)
+        ));
+    }
+    for edge in &graph.edges {
+        dot.push_str(&format!(
+            "    \"{}\" -> \"{}\" [label=\"{} {} ({})\"];\n",
+            escape(&edge.from),
+            escape(&edge.to),
+            escape(
+                &edge
+                    .total
+                    .parse::<Amount>()
+                    .map_or(edge.total.clone(), format_exact)
+            ),
+            escape(asset_code(&edge.asset)),
+            edge.payment_count
+        ));
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+/// Escape a DOT string, keeping `\n` line breaks in labels.
+fn escape(value: &str) -> String {
+    value.replace('"', "\\\"")
+}
+
+/// "USDC" from "USDC (GISSUER)", "XLM" from "XLM (native)".
+fn asset_code(asset: &str) -> &str {
+    asset.split_once(" (").map_or(asset, |(code, _)| code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::PaymentOperation;
+
+    fn flow(from: &str, to: &str, amount: &str) -> PaymentFlow {
+        PaymentFlow {
+            from: from.to_string(),
+            to: to.to_string(),
+            asset: "XLM (native)".to_string(),
+            amount: amount.parse().unwrap(),
+        }
+    }
+
+    fn graph() -> PaymentGraph {
+        let nodes = HashMap::from([
+            ("GME".to_string(), 0),
+            ("GYOU".to_string(), 1),
+            ("GTHEM".to_string(), 2),
+        ]);
+        build_payment_graph(
+            "GME",
+            2,
+            &nodes,
+            &[
+                flow("GME", "GYOU", "1000"),
+                flow("GME", "GYOU", "200"),
+                flow("GYOU", "GTHEM", "5"),
+            ],
+            false,
+        )
+    }
+
+    #[test]
+    fn test_payments_are_added_up_per_edge() {
+        let graph = graph();
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.edges[0].total, "1200.0000000");
+        assert_eq!(graph.edges[0].payment_count, 2);
+        assert_eq!(
+            graph.edges[0].summary,
+            "GME sent GYOU 1,200 XLM in 2 payments."
+        );
+        let ids: Vec<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(ids, vec!["GME", "GYOU", "GTHEM"]);
+        assert_eq!(
+            graph.summary,
+            "2 payment flows between 3 accounts within 2 hops of GME."
+        );
+    }
+
+    #[test]
+    fn test_dot_output() {
+        let dot = to_dot(&graph());
+        assert!(dot.starts_with("digraph payments {\n"));
+        assert!(dot.contains("    \"GME\" [label=\"GME\", style=bold];\n"));
+        assert!(dot.contains("    \"GME\" -> \"GYOU\" [label=\"1,200 XLM (2)\"];\n"));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_payment_flow_skips_self_payments() {
+        let payment = |to: &str| {
+            Operation::Payment(PaymentOperation {
+                id: "1".to_string(),
+                source_account: Some("GME".to_string()),
+                destination: to.to_string(),
+                asset_type: "native".to_string(),
+                asset_code: None,
+                asset_issuer: None,
+                amount: "1".parse().unwrap(),
+            })
+        };
+        assert_eq!(payment_flow(&payment("GYOU")).unwrap().to, "GYOU");
+        assert!(payment_flow(&payment("GME")).is_none());
+    }
+}
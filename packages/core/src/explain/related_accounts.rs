@@ -0,0 +1,271 @@
+//! Accounts connected to an account through signers or sponsorship.
+//!
+//! Two accounts are connected when one can sign for the other, when the same
+//! key can sign for both, or when one pays the other's reserves. Such links
+//! usually mean the accounts are run by the same person or service, which is
+//! what an analyst mapping a cluster of accounts wants to know.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::{count, shorten};
+use crate::explain::templates;
+use crate::models::account::AccountLinks;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConnectionKind {
+    /// The same key can sign for both accounts.
+    SharedSigner,
+    /// The account is a signer on the related account.
+    SignsFor,
+    /// The related account is a signer on the account.
+    SignedBy,
+    /// The account pays reserves for the related account.
+    Sponsors,
+    /// The related account pays reserves for the account.
+    SponsoredBy,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct AccountConnection {
+    pub kind: ConnectionKind,
+    /// e.g. "GBSIG...WXYZ can sign for both accounts."
+    pub description: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct RelatedAccount {
+    pub address: String,
+    pub connections: Vec<AccountConnection>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema, ToSchema)]
+pub struct RelatedAccountsExplanation {
+    pub address: String,
+    pub summary: String,
+    /// Most connected first.
+    pub related: Vec<RelatedAccount>,
+    /// True when a search hit its limit or failed, so some connections may
+    /// be missing.
+    pub truncated: bool,
+}
+
+/// Connect `account` to the accounts found by searching each of its signer
+/// keys (`by_signer`) and its address as a sponsor (`sponsored`).
+pub fn explain_related_accounts(
+    account: &AccountLinks,
+    by_signer: &[(String, Vec<AccountLinks>)],
+    sponsored: &[AccountLinks],
+    truncated: bool,
+) -> RelatedAccountsExplanation {
+    let address = account.account_id.as_str();
+    let mut related: BTreeMap<String, Vec<AccountConnection>> = BTreeMap::new();
+    let mut connect = |other: &str, kind, description: String| {
+        if other == address {
+            return;
+        }
+        let connections = related.entry(other.to_string()).or_default();
+        if !connections.iter().any(|c| c.description == description) {
+            connections.push(AccountConnection { kind, description });
+        }
+    };
+
+    for (key, accounts) in by_signer {
+        for other in accounts {
+            if key == address {
+                connect(
+                    &other.account_id,
+                    ConnectionKind::SignsFor,
+                    templates::render(
+                        "related_accounts.signs_for",
+                        &[("account", &shorten(&other.account_id))],
+                    ),
+                );
+            } else {
+                connect(
+                    &other.account_id,
+                    ConnectionKind::SharedSigner,
+                    templates::render(
+                        "related_accounts.shared_signer",
+                        &[("signer", &shorten(key))],
+                    ),
+                );
+            }
+        }
+    }
+
+    for signer in &account.signers {
+        if signer.weight > 0 && signer.key.starts_with('G') {
+            connect(
+                &signer.key,
+                ConnectionKind::SignedBy,
+                templates::render(
+                    "related_accounts.signed_by",
+                    &[
+                        ("signer", &shorten(&signer.key)),
+                        ("weight", &signer.weight.to_string()),
+                    ],
+                ),
+            );
+        }
+        if let Some(sponsor) = &signer.sponsor {
+            connect(
+                sponsor,
+                ConnectionKind::SponsoredBy,
+                templates::render(
+                    "related_accounts.sponsors_signer",
+                    &[
+                        ("sponsor", &shorten(sponsor)),
+                        ("signer", &shorten(&signer.key)),
+                    ],
+                ),
+            );
+        }
+    }
+    if let Some(sponsor) = &account.sponsor {
+        connect(
+            sponsor,
+            ConnectionKind::SponsoredBy,
+            templates::render(
+                "related_accounts.sponsored_by",
+                &[("sponsor", &shorten(sponsor))],
+            ),
+        );
+    }
+
+    for other in sponsored {
+        let key = if other.sponsor.as_deref() == Some(address) {
+            "related_accounts.sponsors"
+        } else {
+            "related_accounts.sponsors_entries"
+        };
+        let description = templates::render(key, &[("account", &shorten(&other.account_id))]);
+        connect(&other.account_id, ConnectionKind::Sponsors, description);
+    }
+
+    let mut related: Vec<RelatedAccount> = related
+        .into_iter()
+        .map(|(address, connections)| RelatedAccount {
+            address,
+            connections,
+        })
+        .collect();
+    // Stable, so equally connected accounts stay in address order.
+    related.sort_by_key(|r| std::cmp::Reverse(r.connections.len()));
+
+    let summary = match related.len() {
+        0 => templates::render("related_accounts.none", &[("account", &shorten(address))]),
+        n => templates::render(
+            "related_accounts.summary",
+            &[
+                ("account", &shorten(address)),
+                ("related", &count(n, "account", "accounts")),
+            ],
+        ),
+    };
+
+    RelatedAccountsExplanation {
+        address: address.to_string(),
+        summary,
+        related,
+        truncated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::account::Signer;
+
+    const ME: &str = "GMEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+    const KEY: &str = "GKEYAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+    fn signer(key: &str, weight: u32) -> Signer {
+        Signer {
+            key: key.to_string(),
+            weight,
+            sponsor: None,
+        }
+    }
+
+    fn links(account_id: &str, signers: Vec<Signer>, sponsor: Option<&str>) -> AccountLinks {
+        AccountLinks {
+            account_id: account_id.to_string(),
+            signers,
+            sponsor: sponsor.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_connections_are_grouped_by_account() {
+        let me = links(ME, vec![signer(ME, 1), signer(KEY, 1)], Some("GFUNDER"));
+        let other = links("GOTHER", vec![signer("GOTHER", 1), signer(KEY, 1)], None);
+        let controlled = links("GCHILD", vec![signer(ME, 1)], Some(ME));
+
+        let explanation = explain_related_accounts(
+            &me,
+            &[
+                (ME.to_string(), vec![me.clone(), controlled.clone()]),
+                (KEY.to_string(), vec![me.clone(), other]),
+            ],
+            &[controlled],
+            false,
+        );
+
+        let addresses: Vec<&str> = explanation
+            .related
+            .iter()
+            .map(|r| r.address.as_str())
+            .collect();
+        assert_eq!(addresses, vec!["GCHILD", "GFUNDER", KEY, "GOTHER"]);
+        let child = &explanation.related[0];
+        assert_eq!(
+            child.connections.iter().map(|c| c.kind).collect::<Vec<_>>(),
+            vec![ConnectionKind::SignsFor, ConnectionKind::Sponsors]
+        );
+        assert_eq!(
+            child.connections[1].description,
+            "This account pays GCHILD's base reserve."
+        );
+        assert_eq!(
+            explanation.related[3].connections[0].kind,
+            ConnectionKind::SharedSigner
+        );
+        assert!(
+            explanation
+                .summary
+                .ends_with("connected to 4 accounts through signers or sponsorship.")
+        );
+    }
+
+    #[test]
+    fn test_unconnected_account() {
+        let me = links(ME, vec![signer(ME, 1)], None);
+        let explanation =
+            explain_related_accounts(&me, &[(ME.to_string(), vec![me.clone()])], &[], false);
+        assert!(explanation.related.is_empty());
+        assert!(explanation.summary.starts_with("No accounts share signers"));
+    }
+
+    #[test]
+    fn test_removed_signers_and_non_account_keys_are_ignored() {
+        let me = links(
+            ME,
+            vec![
+                signer(ME, 1),
+                signer(KEY, 0),
+                signer(
+                    "TPREAUTHTXAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+                    1,
+                ),
+            ],
+            None,
+        );
+        let explanation = explain_related_accounts(&me, &[], &[], false);
+        assert!(explanation.related.is_empty());
+    }
+}
@@ -0,0 +1,448 @@
+//! What could go wrong with a payment, before it is sent.
+//!
+//! A wallet about to send an asset asks whether the destination can receive
+//! it: the account may not exist yet, may expect a memo, may not trust the
+//! asset, or may be a known scam. The asset itself may be one its issuer can
+//! claw back from the recipient.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::count;
+use crate::explain::templates;
+use crate::models::account::Account;
+use crate::models::amount::Amount;
+
+/// Smallest balance a new account can start with, in XLM: two base
+/// reserves of 0.5 XLM.
+pub const MIN_STARTING_BALANCE: Amount = Amount::from_stroops(10_000_000);
+
+/// A payment a wallet is about to send.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedPayment {
+    pub destination: String,
+    /// Code and issuer; `None` for XLM.
+    pub asset: Option<(String, String)>,
+    pub amount: Amount,
+    pub memo: Option<String>,
+}
+
+impl PlannedPayment {
+    /// E.g. "100 XLM" or "25 USDC".
+    fn describe(&self) -> String {
+        let code = self.asset.as_ref().map_or("XLM", |(code, _)| code);
+        format!("{} {code}", self.amount.trimmed())
+    }
+}
+
+/// What is known about the destination and asset.
+#[derive(Debug, Clone, Default)]
+pub struct SendContext {
+    /// `None` when the destination account does not exist.
+    pub destination: Option<Account>,
+    /// The asset's issuer; `None` for XLM or when it could not be read.
+    pub issuer: Option<Account>,
+    /// Whether the destination is on the spam blocklist, with the reason
+    /// if given.
+    pub destination_listing: Option<Option<String>>,
+    /// Whether the asset is on the spam blocklist, with the reason if given.
+    pub asset_listing: Option<Option<String>>,
+    /// Who runs the destination, when the memo registry lists it.
+    pub memo_required_by: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct SendCheck {
+    pub destination: String,
+    /// "native" or "CODE:ISSUER".
+    pub asset: String,
+    pub amount: String,
+    /// e.g. "Found 2 problems sending 25 USDC to G...."
+    pub summary: String,
+    /// Empty when nothing stands in the way.
+    pub warnings: Vec<SendWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct SendWarning {
+    pub code: SendWarningCode,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SendWarningCode {
+    /// The destination does not exist; only a `create_account` operation
+    /// with enough XLM can send to it.
+    DestinationUnfunded,
+    /// The destination asks for a memo (SEP-29) or is in the memo registry,
+    /// and none was given.
+    MemoRequired,
+    /// The destination has no trustline to the asset.
+    NoTrustline,
+    /// The destination's trustline is not authorized by the issuer.
+    TrustlineNotAuthorized,
+    /// The amount would take the destination over its trustline's limit.
+    TrustlineLimitExceeded,
+    /// The issuer can claw the asset back from the recipient.
+    ClawbackEnabled,
+    /// The destination is on the deployment's spam blocklist.
+    FlaggedDestination,
+    /// The asset is on the deployment's spam blocklist.
+    FlaggedAsset,
+}
+
+/// Everything that could make `payment` fail or go somewhere unintended.
+pub fn check_send(payment: &PlannedPayment, context: &SendContext) -> SendCheck {
+    let mut warnings = Vec::new();
+    let mut warn =
+        |code: SendWarningCode, message: String| warnings.push(SendWarning { code, message });
+    let destination = &payment.destination;
+    let sending = payment.describe();
+
+    if let Some(reason) = &context.destination_listing {
+        warn(
+            SendWarningCode::FlaggedDestination,
+            match reason {
+                Some(reason) => templates::render(
+                    "send_check.flagged_destination_reason",
+                    &[("destination", destination), ("reason", reason)],
+                ),
+                None => templates::render(
+                    "send_check.flagged_destination",
+                    &[("destination", destination)],
+                ),
+            },
+        );
+    }
+
+    match (&context.destination, &payment.asset) {
+        (None, None) if payment.amount < MIN_STARTING_BALANCE => warn(
+            SendWarningCode::DestinationUnfunded,
+            templates::render(
+                "send_check.unfunded_below_minimum",
+                &[
+                    ("destination", destination),
+                    ("minimum", &MIN_STARTING_BALANCE.trimmed()),
+                    ("sending", &sending),
+                ],
+            ),
+        ),
+        (None, None) => warn(
+            SendWarningCode::DestinationUnfunded,
+            templates::render(
+                "send_check.unfunded",
+                &[("destination", destination), ("sending", &sending)],
+            ),
+        ),
+        (None, Some((code, _))) => warn(
+            SendWarningCode::DestinationUnfunded,
+            templates::render(
+                "send_check.unfunded_asset",
+                &[
+                    ("destination", destination),
+                    ("code", code),
+                    ("minimum", &MIN_STARTING_BALANCE.trimmed()),
+                ],
+            ),
+        ),
+        (Some(account), asset) => {
+            let memo_missing = payment
+                .memo
+                .as_deref()
+                .is_none_or(|memo| memo.trim().is_empty());
+            if memo_missing {
+                match (&context.memo_required_by, account.memo_required) {
+                    (Some(name), _) => warn(
+                        SendWarningCode::MemoRequired,
+                        templates::render(
+                            "send_check.memo_required_by",
+                            &[("destination", destination), ("name", name)],
+                        ),
+                    ),
+                    (None, true) => warn(
+                        SendWarningCode::MemoRequired,
+                        templates::render(
+                            "send_check.memo_required",
+                            &[("destination", destination)],
+                        ),
+                    ),
+                    (None, false) => {}
+                }
+            }
+            if let Some((code, issuer)) = asset {
+                check_trustline(account, code, issuer, payment.amount, &mut warn);
+            }
+        }
+    }
+
+    if let Some((code, issuer)) = &payment.asset {
+        if context
+            .issuer
+            .as_ref()
+            .is_some_and(|issuer| issuer.flags.auth_clawback_enabled)
+        {
+            warn(
+                SendWarningCode::ClawbackEnabled,
+                templates::render(
+                    "send_check.clawback_enabled",
+                    &[("code", code), ("issuer", issuer)],
+                ),
+            );
+        }
+        if let Some(reason) = &context.asset_listing {
+            warn(
+                SendWarningCode::FlaggedAsset,
+                match reason {
+                    Some(reason) => templates::render(
+                        "send_check.flagged_asset_reason",
+                        &[("code", code), ("issuer", issuer), ("reason", reason)],
+                    ),
+                    None => templates::render(
+                        "send_check.flagged_asset",
+                        &[("code", code), ("issuer", issuer)],
+                    ),
+                },
+            );
+        }
+    }
+
+    let summary = match warnings.len() {
+        0 => templates::render(
+            "send_check.no_problems",
+            &[("sending", &sending), ("destination", destination)],
+        ),
+        n => templates::render(
+            "send_check.summary",
+            &[
+                ("problems", &count(n, "problem", "problems")),
+                ("sending", &sending),
+                ("destination", destination),
+            ],
+        ),
+    };
+    SendCheck {
+        destination: destination.clone(),
+        asset: payment.asset.as_ref().map_or_else(
+            || "native".to_string(),
+            |(code, issuer)| format!("{code}:{issuer}"),
+        ),
+        amount: payment.amount.trimmed(),
+        summary,
+        warnings,
+    }
+}
+
+/// Whether `account` can receive `amount` of the asset. Issuers hold their
+/// own assets without a trustline.
+fn check_trustline(
+    account: &Account,
+    code: &str,
+    issuer: &str,
+    amount: Amount,
+    warn: &mut impl FnMut(SendWarningCode, String),
+) {
+    if account.account_id == issuer {
+        return;
+    }
+    let destination = &account.account_id;
+    let Some(line) = account.balances.iter().find(|balance| {
+        balance.asset_code.as_deref() == Some(code)
+            && balance.asset_issuer.as_deref() == Some(issuer)
+    }) else {
+        warn(
+            SendWarningCode::NoTrustline,
+            templates::render(
+                "send_check.no_trustline",
+                &[
+                    ("destination", destination),
+                    ("code", code),
+                    ("issuer", issuer),
+                ],
+            ),
+        );
+        return;
+    };
+    if line.is_authorized == Some(false) {
+        warn(
+            SendWarningCode::TrustlineNotAuthorized,
+            templates::render(
+                "send_check.not_authorized",
+                &[("destination", destination), ("code", code)],
+            ),
+        );
+    }
+    let parse = |value: Option<&str>| value.and_then(|v| v.parse::<Amount>().ok());
+    let headroom = parse(line.limit.as_deref()).and_then(|limit| {
+        let held = parse(Some(&line.balance))?
+            .saturating_add(parse(line.buying_liabilities.as_deref()).unwrap_or_default());
+        limit.checked_sub(held)
+    });
+    if let Some(headroom) = headroom
+        && amount > headroom
+    {
+        warn(
+            SendWarningCode::TrustlineLimitExceeded,
+            templates::render(
+                "send_check.limit_exceeded",
+                &[
+                    ("destination", destination),
+                    ("headroom", &headroom.trimmed()),
+                    ("code", code),
+                    ("amount", &amount.trimmed()),
+                ],
+            ),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::account::{AccountFlags, Balance};
+
+    const DEST: &str = "GDEST";
+    const ISSUER: &str = "GISSUER";
+
+    fn account(id: &str, balances: Vec<Balance>) -> Account {
+        Account {
+            id: id.to_string(),
+            account_id: id.to_string(),
+            sequence: "1".to_string(),
+            num_signers: 1,
+            signers: Vec::new(),
+            master_key_weight: 1,
+            thresholds: Default::default(),
+            balances,
+            flags: AccountFlags {
+                auth_required: false,
+                auth_revocable: false,
+                auth_immutable: false,
+                auth_clawback_enabled: false,
+            },
+            home_domain: None,
+            memo_required: false,
+        }
+    }
+
+    fn usdc(balance: &str, limit: &str, authorized: bool) -> Balance {
+        Balance {
+            asset_type: "credit_alphanum4".to_string(),
+            asset_code: Some("USDC".to_string()),
+            asset_issuer: Some(ISSUER.to_string()),
+            balance: balance.to_string(),
+            limit: Some(limit.to_string()),
+            is_authorized: Some(authorized),
+            ..Default::default()
+        }
+    }
+
+    fn payment(asset: Option<&str>, amount: &str) -> PlannedPayment {
+        PlannedPayment {
+            destination: DEST.to_string(),
+            asset: asset.map(|code| (code.to_string(), ISSUER.to_string())),
+            amount: amount.parse().unwrap(),
+            memo: None,
+        }
+    }
+
+    fn codes(check: &SendCheck) -> Vec<SendWarningCode> {
+        check.warnings.iter().map(|w| w.code).collect()
+    }
+
+    #[test]
+    fn test_clear_payment_has_no_warnings() {
+        let context = SendContext {
+            destination: Some(account(DEST, vec![usdc("10", "1000", true)])),
+            issuer: Some(account(ISSUER, vec![])),
+            ..Default::default()
+        };
+        let check = check_send(&payment(Some("USDC"), "25"), &context);
+        assert!(check.warnings.is_empty());
+        assert_eq!(check.asset, "USDC:GISSUER");
+        assert_eq!(check.summary, "No problems found sending 25 USDC to GDEST.");
+    }
+
+    #[test]
+    fn test_unfunded_destination() {
+        let context = SendContext::default();
+        let small = check_send(&payment(None, "0.5"), &context);
+        assert_eq!(codes(&small), vec![SendWarningCode::DestinationUnfunded]);
+        assert!(small.warnings[0].message.contains("at least 1 XLM"));
+        let enough = check_send(&payment(None, "5"), &context);
+        assert!(enough.warnings[0].message.contains("create_account"));
+        let credit = check_send(&payment(Some("USDC"), "5"), &context);
+        assert!(credit.warnings[0].message.contains("cannot hold USDC"));
+    }
+
+    #[test]
+    fn test_memo_trustline_and_clawback() {
+        let mut destination = account(DEST, vec![]);
+        destination.memo_required = true;
+        let mut issuer = account(ISSUER, vec![]);
+        issuer.flags.auth_clawback_enabled = true;
+        let context = SendContext {
+            destination: Some(destination.clone()),
+            issuer: Some(issuer),
+            destination_listing: Some(Some("fake exchange".to_string())),
+            ..Default::default()
+        };
+        let check = check_send(&payment(Some("USDC"), "25"), &context);
+        assert!(
+            check.warnings[1]
+                .message
+                .starts_with("GDEST requires a memo")
+        );
+        assert_eq!(
+            codes(&check),
+            vec![
+                SendWarningCode::FlaggedDestination,
+                SendWarningCode::MemoRequired,
+                SendWarningCode::NoTrustline,
+                SendWarningCode::ClawbackEnabled,
+            ]
+        );
+        assert_eq!(check.summary, "Found 4 problems sending 25 USDC to GDEST.");
+
+        // A memo satisfies the destination; a full, unauthorized trustline
+        // does not.
+        destination.balances = vec![usdc("990", "1000", false)];
+        let context = SendContext {
+            destination: Some(destination),
+            ..Default::default()
+        };
+        let mut with_memo = payment(Some("USDC"), "25");
+        with_memo.memo = Some("12345".to_string());
+        assert_eq!(
+            codes(&check_send(&with_memo, &context)),
+            vec![
+                SendWarningCode::TrustlineNotAuthorized,
+                SendWarningCode::TrustlineLimitExceeded,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_registered_exchange_needs_memo() {
+        let context = SendContext {
+            destination: Some(account(DEST, vec![])),
+            memo_required_by: Some("Binance".to_string()),
+            ..Default::default()
+        };
+        let check = check_send(&payment(None, "25"), &context);
+        assert_eq!(codes(&check), vec![SendWarningCode::MemoRequired]);
+        assert!(check.warnings[0].message.contains("belongs to Binance"));
+    }
+
+    #[test]
+    fn test_issuer_needs_no_trustline() {
+        let context = SendContext {
+            destination: Some(account(ISSUER, vec![])),
+            ..Default::default()
+        };
+        let mut to_issuer = payment(Some("USDC"), "25");
+        to_issuer.destination = ISSUER.to_string();
+        assert!(check_send(&to_issuer, &context).warnings.is_empty());
+    }
+}
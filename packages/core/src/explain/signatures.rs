@@ -0,0 +1,663 @@
+//! Who signed a transaction, and whether that was enough.
+//!
+//! A transaction's signatures do not name their signers. Each carries a
+//! 4-byte hint, the last bytes of the key that made it, so signers are
+//! found by matching hints against the keys of the accounts that had to
+//! sign: the transaction source, the source of every operation, and the
+//! fee payer of a fee bump. Their weights are added up and compared with the
+//! threshold the operations need:
+//!
+//! - low: `allow_trust`, `set_trust_line_flags`, `bump_sequence`,
+//!   `claim_claimable_balance`, `extend_footprint_ttl`,
+//!   `restore_footprint`, and the transaction itself (fee and sequence).
+//! - high: `account_merge`, and `set_options` that changes the master
+//!   weight, thresholds or signers.
+//! - medium: everything else.
+//!
+//! Matching uses the accounts' signers as they are now. Signers or
+//! thresholds that changed since the transaction was signed can make a
+//! threshold look unmet.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use stellar_xdr::curr::{
+    DecoratedSignature, FeeBumpTransactionInnerTx, ReadXdr, TransactionEnvelope,
+};
+use utoipa::ToSchema;
+
+use crate::explain::format::{count, shorten};
+use crate::explain::signer::describe_signer_key;
+use crate::explain::templates;
+use crate::models::account::{Account, Signer};
+use crate::models::contract_event::upstream_limits;
+use crate::models::strkey::StrKey;
+use crate::services::horizon::{HorizonOperation, HorizonTransaction};
+
+/// Accounts checked per transaction; each is a Horizon request.
+pub const MAX_SIGNING_ACCOUNTS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl ThresholdLevel {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ThresholdOutcome {
+    MetExactly,
+    MetWithSurplus,
+    /// The account's current signers do not add up to the threshold.
+    NotMet,
+    /// The account could not be loaded.
+    Unknown,
+}
+
+/// One of an account's signers whose signature is on the transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct MatchedSigner {
+    pub key: String,
+    pub weight: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AccountSignatures {
+    pub account: String,
+    /// Whether these are the fee bump's own signatures, which only the fee
+    /// payer needs.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fee_bump: bool,
+    pub threshold: ThresholdLevel,
+    /// Weight the threshold needs; never less than 1.
+    pub required_weight: u32,
+    pub signed_weight: u32,
+    pub signers: Vec<MatchedSigner>,
+    pub outcome: ThresholdOutcome,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct SignatureExplanation {
+    pub transaction_hash: String,
+    pub summary: String,
+    pub signature_count: usize,
+    pub accounts: Vec<AccountSignatures>,
+    /// Hex hints of signatures that matched no signer of any account above.
+    pub unmatched_hints: Vec<String>,
+}
+
+/// An account whose signatures the transaction needs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureRequirement {
+    pub account: String,
+    pub threshold: ThresholdLevel,
+    pub fee_bump: bool,
+}
+
+/// The threshold an operation needs from its source account.
+pub fn operation_threshold(op: &HorizonOperation) -> ThresholdLevel {
+    match op.operation_type.as_str() {
+        "allow_trust"
+        | "set_trust_line_flags"
+        | "bump_sequence"
+        | "claim_claimable_balance"
+        | "extend_footprint_ttl"
+        | "restore_footprint"
+        | "inflation" => ThresholdLevel::Low,
+        "account_merge" => ThresholdLevel::High,
+        "set_options"
+            if op.master_key_weight.is_some()
+                || op.low_threshold.is_some()
+                || op.med_threshold.is_some()
+                || op.high_threshold.is_some()
+                || op.signer_key.is_some() =>
+        {
+            ThresholdLevel::High
+        }
+        _ => ThresholdLevel::Medium,
+    }
+}
+
+/// The accounts that had to sign, in the order they are first needed, with
+/// the highest threshold each needed. At most [`MAX_SIGNING_ACCOUNTS`].
+pub fn signature_requirements(
+    tx: &HorizonTransaction,
+    operations: &[HorizonOperation],
+) -> Vec<SignatureRequirement> {
+    let mut requirements: Vec<SignatureRequirement> = Vec::new();
+    let mut require = |account: &str, threshold: ThresholdLevel, fee_bump: bool| match requirements
+        .iter_mut()
+        .find(|r| r.account == account && r.fee_bump == fee_bump)
+    {
+        Some(existing) => existing.threshold = existing.threshold.max(threshold),
+        None => requirements.push(SignatureRequirement {
+            account: account.to_string(),
+            threshold,
+            fee_bump,
+        }),
+    };
+
+    let source = tx.source_account.as_deref();
+    if let Some(source) = source {
+        require(source, ThresholdLevel::Low, false);
+    }
+    for op in operations {
+        if let Some(account) = op.source_account.as_deref().or(source) {
+            require(account, operation_threshold(op), false);
+        }
+    }
+    if is_fee_bump(tx)
+        && let Some(fee_account) = tx.fee_account.as_deref()
+    {
+        require(fee_account, ThresholdLevel::Low, true);
+    }
+    requirements.truncate(MAX_SIGNING_ACCOUNTS);
+    requirements
+}
+
+/// The signatures of a decoded envelope: those on the transaction, and for a
+/// fee bump those on the fee bump itself.
+struct Signatures {
+    inner: Vec<[u8; 4]>,
+    outer: Option<Vec<[u8; 4]>>,
+}
+
+fn decode_signatures(envelope_xdr: &str) -> Option<Signatures> {
+    let hints = |signatures: &[DecoratedSignature]| -> Vec<[u8; 4]> {
+        signatures.iter().map(|s| s.hint.0).collect()
+    };
+    let envelope =
+        TransactionEnvelope::from_xdr_base64(envelope_xdr, upstream_limits(envelope_xdr)).ok()?;
+    Some(match envelope {
+        TransactionEnvelope::TxV0(env) => Signatures {
+            inner: hints(&env.signatures),
+            outer: None,
+        },
+        TransactionEnvelope::Tx(env) => Signatures {
+            inner: hints(&env.signatures),
+            outer: None,
+        },
+        TransactionEnvelope::TxFeeBump(env) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = &env.tx.inner_tx;
+            Signatures {
+                inner: hints(&inner.signatures),
+                outer: Some(hints(&env.signatures)),
+            }
+        }
+    })
+}
+
+fn is_fee_bump(tx: &HorizonTransaction) -> bool {
+    tx.envelope_xdr.as_deref().is_some_and(|xdr| {
+        TransactionEnvelope::from_xdr_base64(xdr, upstream_limits(xdr))
+            .is_ok_and(|env| matches!(env, TransactionEnvelope::TxFeeBump(_)))
+    })
+}
+
+/// The hint a signature by this signer would carry. Pre-authorized
+/// transaction and signed payload signers have none here.
+fn signer_hint(key: &str) -> Option<[u8; 4]> {
+    let bytes = match StrKey::decode(key).ok()? {
+        StrKey::Account(key) | StrKey::HashX(key) => key,
+        _ => return None,
+    };
+    bytes[28..].try_into().ok()
+}
+
+/// Whether `key` pre-authorizes the transaction with `hash`, which then
+/// counts without a signature.
+fn preauthorizes(key: &str, hash: &str) -> bool {
+    match StrKey::decode(key) {
+        Ok(StrKey::PreAuthTx(tx)) => hex(&tx).eq_ignore_ascii_case(hash),
+        _ => false,
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Match the envelope's signatures against the signers of `accounts`, the
+/// accounts named by [`signature_requirements`] that could be loaded.
+/// `None` when the transaction has no envelope that decodes.
+pub fn explain_signatures(
+    tx: &HorizonTransaction,
+    operations: &[HorizonOperation],
+    accounts: &HashMap<String, Account>,
+) -> Option<SignatureExplanation> {
+    let signatures = decode_signatures(tx.envelope_xdr.as_deref()?)?;
+    let mut used = vec![false; signatures.inner.len()];
+    let mut used_outer = vec![false; signatures.outer.as_ref().map_or(0, Vec::len)];
+
+    let mut results = Vec::new();
+    for requirement in signature_requirements(tx, operations) {
+        let (hints, used) = match (&signatures.outer, requirement.fee_bump) {
+            (Some(outer), true) => (outer, &mut used_outer),
+            _ => (&signatures.inner, &mut used),
+        };
+        let account = accounts.get(&requirement.account);
+        results.push(account_signatures(
+            &requirement,
+            account,
+            &tx.hash,
+            hints,
+            used,
+        ));
+    }
+
+    let unmatched_hints: Vec<String> = signatures
+        .inner
+        .iter()
+        .zip(&used)
+        .chain(signatures.outer.iter().flatten().zip(&used_outer))
+        .filter(|(_, used)| !**used)
+        .map(|(hint, _)| hex(hint))
+        .collect();
+    let signature_count = signatures.inner.len() + signatures.outer.as_ref().map_or(0, Vec::len);
+
+    Some(SignatureExplanation {
+        transaction_hash: tx.hash.clone(),
+        summary: summary(signature_count, &results, unmatched_hints.len()),
+        signature_count,
+        accounts: results,
+        unmatched_hints,
+    })
+}
+
+fn account_signatures(
+    requirement: &SignatureRequirement,
+    account: Option<&Account>,
+    hash: &str,
+    hints: &[[u8; 4]],
+    used: &mut [bool],
+) -> AccountSignatures {
+    let level = requirement.threshold;
+    let who = shorten(&requirement.account);
+    let Some(account) = account else {
+        return AccountSignatures {
+            account: requirement.account.clone(),
+            fee_bump: requirement.fee_bump,
+            threshold: level,
+            required_weight: 1,
+            signed_weight: 0,
+            signers: Vec::new(),
+            outcome: ThresholdOutcome::Unknown,
+            summary: templates::render("signatures.account_missing", &[("account", &who)]),
+        };
+    };
+
+    let threshold = match level {
+        ThresholdLevel::Low => account.thresholds.low,
+        ThresholdLevel::Medium => account.thresholds.medium,
+        ThresholdLevel::High => account.thresholds.high,
+    };
+    let required_weight = threshold.max(1);
+    let signers: Vec<MatchedSigner> = account
+        .signers
+        .iter()
+        .filter(|signer| signed(signer, hash, hints, used))
+        .map(|signer| MatchedSigner {
+            key: signer.key.clone(),
+            weight: signer.weight,
+        })
+        .collect();
+    let signed_weight = signers.iter().map(|s| s.weight).sum::<u32>();
+
+    let outcome = match signed_weight.cmp(&required_weight) {
+        std::cmp::Ordering::Less => ThresholdOutcome::NotMet,
+        std::cmp::Ordering::Equal => ThresholdOutcome::MetExactly,
+        std::cmp::Ordering::Greater => ThresholdOutcome::MetWithSurplus,
+    };
+    let required = required_weight.to_string();
+    let needed = if requirement.fee_bump {
+        templates::render(
+            "signatures.needed_fee_bump",
+            &[("account", &who), ("required", &required)],
+        )
+    } else {
+        templates::render(
+            "signatures.needed",
+            &[
+                ("account", &who),
+                ("required", &required),
+                ("threshold", level.label()),
+            ],
+        )
+    };
+    let by = signers
+        .iter()
+        .map(|s| format!("{} ({})", describe_signer_key(&s.key), s.weight))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let got = signed_weight.to_string();
+    let summary = match outcome {
+        ThresholdOutcome::MetExactly => templates::render(
+            "signatures.met_exactly",
+            &[("needed", &needed), ("signers", &by)],
+        ),
+        ThresholdOutcome::MetWithSurplus => templates::render(
+            "signatures.met_with_surplus",
+            &[
+                ("needed", &needed),
+                ("signed", &got),
+                ("signers", &by),
+                ("surplus", &(signed_weight - required_weight).to_string()),
+            ],
+        ),
+        _ if signers.is_empty() => {
+            templates::render("signatures.not_signed", &[("needed", &needed)])
+        }
+        _ => templates::render(
+            "signatures.not_met",
+            &[("needed", &needed), ("signed", &got), ("signers", &by)],
+        ),
+    };
+
+    AccountSignatures {
+        account: requirement.account.clone(),
+        fee_bump: requirement.fee_bump,
+        threshold: level,
+        required_weight,
+        signed_weight,
+        signers,
+        outcome,
+        summary,
+    }
+}
+
+/// Whether `signer` signed: a signature carries its hint, or it
+/// pre-authorized this very transaction. Matched signatures are marked used.
+fn signed(signer: &Signer, hash: &str, hints: &[[u8; 4]], used: &mut [bool]) -> bool {
+    if preauthorizes(&signer.key, hash) {
+        return true;
+    }
+    let Some(hint) = signer_hint(&signer.key) else {
+        return false;
+    };
+    let mut found = false;
+    for (candidate, used) in hints.iter().zip(used.iter_mut()) {
+        if *candidate == hint {
+            *used = true;
+            found = true;
+        }
+    }
+    found
+}
+
+fn summary(signature_count: usize, accounts: &[AccountSignatures], unmatched: usize) -> String {
+    let mut parts = Vec::new();
+    for (outcome, key) in [
+        (ThresholdOutcome::MetExactly, "signatures.count.met_exactly"),
+        (
+            ThresholdOutcome::MetWithSurplus,
+            "signatures.count.met_with_surplus",
+        ),
+        (ThresholdOutcome::NotMet, "signatures.count.not_met"),
+        (ThresholdOutcome::Unknown, "signatures.count.unknown"),
+    ] {
+        let n = accounts.iter().filter(|a| a.outcome == outcome).count();
+        if n > 0 {
+            parts.push(templates::render(key, &[("count", &n.to_string())]));
+        }
+    }
+    let mut summary = templates::render(
+        "signatures.summary",
+        &[
+            (
+                "signatures",
+                &count(signature_count, "signature", "signatures"),
+            ),
+            (
+                "accounts",
+                &count(accounts.len(), "signing account", "signing accounts"),
+            ),
+            ("outcomes", &parts.join(", ")),
+        ],
+    );
+    if unmatched > 0 {
+        summary.push(' ');
+        summary.push_str(&templates::render(
+            "signatures.unmatched",
+            &[("signatures", &count(unmatched, "signature", "signatures"))],
+        ));
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::account::{AccountFlags, Thresholds};
+    use stellar_strkey::{PreAuthTx, ed25519};
+    use stellar_xdr::curr::{
+        FeeBumpTransaction, FeeBumpTransactionEnvelope, FeeBumpTransactionExt, Limits, Memo,
+        MuxedAccount, Preconditions, SequenceNumber, Signature, SignatureHint, Transaction,
+        TransactionExt, TransactionV1Envelope, Uint256, WriteXdr,
+    };
+
+    fn key(byte: u8) -> String {
+        ed25519::PublicKey([byte; 32]).to_string()
+    }
+
+    fn signature(byte: u8) -> DecoratedSignature {
+        DecoratedSignature {
+            hint: SignatureHint([byte; 4]),
+            signature: Signature(vec![0; 64].try_into().unwrap()),
+        }
+    }
+
+    fn transaction(source: u8) -> Transaction {
+        Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([source; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: Vec::new().try_into().unwrap(),
+            ext: TransactionExt::V0,
+        }
+    }
+
+    fn envelope(source: u8, signers: &[u8]) -> TransactionV1Envelope {
+        TransactionV1Envelope {
+            tx: transaction(source),
+            signatures: signers
+                .iter()
+                .map(|&b| signature(b))
+                .collect::<Vec<_>>()
+                .try_into()
+                .unwrap(),
+        }
+    }
+
+    fn record(envelope: TransactionEnvelope, source: u8, fee_account: u8) -> HorizonTransaction {
+        HorizonTransaction {
+            hash: "ab".repeat(32),
+            successful: true,
+            source_account: Some(key(source)),
+            fee_account: Some(key(fee_account)),
+            envelope_xdr: Some(envelope.to_xdr_base64(Limits::none()).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    fn op(kind: &str, source: Option<u8>) -> HorizonOperation {
+        HorizonOperation {
+            operation_type: kind.to_string(),
+            source_account: source.map(key),
+            ..Default::default()
+        }
+    }
+
+    fn account(id: u8, signers: &[(String, u32)], thresholds: Thresholds) -> (String, Account) {
+        let account = Account {
+            id: key(id),
+            account_id: key(id),
+            sequence: "1".to_string(),
+            num_signers: signers.len() as u32,
+            signers: signers
+                .iter()
+                .map(|(key, weight)| Signer {
+                    key: key.clone(),
+                    weight: *weight,
+                    sponsor: None,
+                })
+                .collect(),
+            master_key_weight: 1,
+            thresholds,
+            balances: Vec::new(),
+            flags: AccountFlags {
+                auth_required: false,
+                auth_revocable: false,
+                auth_immutable: false,
+                auth_clawback_enabled: false,
+            },
+            home_domain: None,
+            memo_required: false,
+        };
+        (key(id), account)
+    }
+
+    const MULTISIG: Thresholds = Thresholds {
+        low: 1,
+        medium: 2,
+        high: 3,
+    };
+
+    #[test]
+    fn test_operation_thresholds() {
+        assert_eq!(
+            operation_threshold(&op("payment", None)),
+            ThresholdLevel::Medium
+        );
+        assert_eq!(
+            operation_threshold(&op("bump_sequence", None)),
+            ThresholdLevel::Low
+        );
+        assert_eq!(
+            operation_threshold(&op("account_merge", None)),
+            ThresholdLevel::High
+        );
+        assert_eq!(
+            operation_threshold(&op("set_options", None)),
+            ThresholdLevel::Medium
+        );
+        let mut add_signer = op("set_options", None);
+        add_signer.signer_key = Some(key(9));
+        assert_eq!(operation_threshold(&add_signer), ThresholdLevel::High);
+    }
+
+    #[test]
+    fn test_multisig_threshold_met_exactly_and_with_surplus() {
+        let signers = [(key(1), 1), (key(2), 1), (key(3), 2)];
+        let accounts = HashMap::from([account(1, &signers, MULTISIG)]);
+
+        // Two weight-1 signers for a medium payment: exactly the threshold.
+        let tx = record(TransactionEnvelope::Tx(envelope(1, &[1, 2])), 1, 1);
+        let explained = explain_signatures(&tx, &[op("payment", None)], &accounts).unwrap();
+        let source = &explained.accounts[0];
+        assert_eq!(explained.signature_count, 2);
+        assert_eq!(source.threshold, ThresholdLevel::Medium);
+        assert_eq!(source.required_weight, 2);
+        assert_eq!(source.outcome, ThresholdOutcome::MetExactly);
+        assert_eq!(source.signers.len(), 2);
+        assert!(source.summary.ends_with(&format!(
+            "and got exactly that from signer {} (1), signer {} (1).",
+            shorten(&key(1)),
+            shorten(&key(2))
+        )));
+
+        // All three signers and a stranger: surplus, and one unmatched hint.
+        let tx = record(TransactionEnvelope::Tx(envelope(1, &[1, 2, 3, 7])), 1, 1);
+        let explained = explain_signatures(&tx, &[op("payment", None)], &accounts).unwrap();
+        let source = &explained.accounts[0];
+        assert_eq!(source.outcome, ThresholdOutcome::MetWithSurplus);
+        assert_eq!(source.signed_weight, 4);
+        assert!(source.summary.ends_with("2 more than needed."));
+        assert_eq!(explained.unmatched_hints, vec!["07070707".to_string()]);
+        assert_eq!(
+            explained.summary,
+            "4 signatures for 1 signing account: 1 met it with surplus weight. 1 signature matched none of their current signers."
+        );
+    }
+
+    #[test]
+    fn test_operation_sources_and_missing_accounts() {
+        let accounts = HashMap::from([
+            account(1, &[(key(1), 1)], Thresholds::default()),
+            account(2, &[(key(2), 1), (key(4), 1)], MULTISIG),
+        ]);
+        let tx = record(TransactionEnvelope::Tx(envelope(1, &[1, 2])), 1, 1);
+        let ops = [
+            op("payment", None),
+            op("account_merge", Some(2)),
+            op("payment", Some(5)),
+        ];
+        let explained = explain_signatures(&tx, &ops, &accounts).unwrap();
+
+        let outcomes: Vec<_> = explained.accounts.iter().map(|a| a.outcome).collect();
+        assert_eq!(
+            outcomes,
+            [
+                ThresholdOutcome::MetExactly,
+                ThresholdOutcome::NotMet,
+                ThresholdOutcome::Unknown
+            ]
+        );
+        let merged = &explained.accounts[1];
+        assert_eq!(merged.threshold, ThresholdLevel::High);
+        assert_eq!((merged.required_weight, merged.signed_weight), (3, 1));
+        assert!(merged.summary.contains("add up to only 1"));
+    }
+
+    #[test]
+    fn test_fee_bump_and_preauthorized_signers() {
+        let hash = "ab".repeat(32);
+        let preauth = PreAuthTx([0xab; 32]).to_string();
+        let accounts = HashMap::from([
+            account(1, &[(key(1), 1), (preauth, 1)], MULTISIG),
+            account(8, &[(key(8), 1)], Thresholds::default()),
+        ]);
+        let inner = envelope(1, &[1]);
+        let bump = FeeBumpTransactionEnvelope {
+            tx: FeeBumpTransaction {
+                fee_source: MuxedAccount::Ed25519(Uint256([8; 32])),
+                fee: 200,
+                inner_tx: FeeBumpTransactionInnerTx::Tx(inner),
+                ext: FeeBumpTransactionExt::V0,
+            },
+            signatures: vec![signature(8)].try_into().unwrap(),
+        };
+        let tx = record(TransactionEnvelope::TxFeeBump(bump), 1, 8);
+        assert_eq!(tx.hash, hash);
+
+        let explained = explain_signatures(&tx, &[op("payment", None)], &accounts).unwrap();
+        assert_eq!(explained.signature_count, 2);
+        assert!(explained.unmatched_hints.is_empty());
+        let source = &explained.accounts[0];
+        assert_eq!(source.outcome, ThresholdOutcome::MetExactly);
+        assert_eq!(source.signers[1].key, PreAuthTx([0xab; 32]).to_string());
+        let payer = &explained.accounts[1];
+        assert!(payer.fee_bump);
+        assert_eq!(payer.outcome, ThresholdOutcome::MetExactly);
+        assert!(payer.summary.contains("paid the fee bump"));
+    }
+
+    #[test]
+    fn test_missing_envelope_is_not_explained() {
+        let tx = HorizonTransaction::default();
+        assert_eq!(explain_signatures(&tx, &[], &HashMap::new()), None);
+    }
+}
@@ -0,0 +1,219 @@
+//! Explanations of an account's signers.
+//!
+//! Most signers are other accounts' keys, but a signer can also be:
+//!
+//! - a pre-authorized transaction (`T...`): the hash of one transaction,
+//!   which can then be submitted with no other signature. The signer is
+//!   removed when that transaction is applied.
+//! - a hash(x) signer (`X...`): the SHA-256 hash of a secret value. Whoever
+//!   reveals the value can sign, and revealing it makes it public.
+//! - a signed payload signer (`P...`): a key that signs a specific payload
+//!   rather than the transaction itself.
+//!
+//! Pre-authorized transactions are looked up by hash, so the explanation can
+//! say whether the transaction is still waiting to be submitted.
+
+use crate::explain::format::shorten;
+use crate::models::account::Signer;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use stellar_strkey::Strkey;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerKind {
+    Ed25519,
+    PreAuthTx,
+    HashX,
+    SignedPayload,
+    Unknown,
+}
+
+/// What the network knows about the transaction a pre-authorized
+/// transaction signer names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum PreAuthStatus {
+    /// No transaction with the hash is in a ledger yet.
+    Pending,
+    /// The transaction is in `ledger`.
+    Applied { ledger: Option<u64> },
+    /// The lookup failed.
+    Unknown,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SignerExplanation {
+    pub key: String,
+    pub kind: SignerKind,
+    pub weight: u32,
+    pub summary: String,
+    /// Hex hash of the transaction a pre-authorized transaction signer
+    /// allows, or of the secret a hash(x) signer needs.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    /// Whether a pre-authorized transaction is still to be submitted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preauth_status: Option<PreAuthStatus>,
+}
+
+pub fn signer_kind(key: &str) -> SignerKind {
+    match Strkey::from_string(key) {
+        Ok(Strkey::PublicKeyEd25519(_)) => SignerKind::Ed25519,
+        Ok(Strkey::PreAuthTx(_)) => SignerKind::PreAuthTx,
+        Ok(Strkey::HashX(_)) => SignerKind::HashX,
+        Ok(Strkey::SignedPayloadEd25519(_)) => SignerKind::SignedPayload,
+        _ => SignerKind::Unknown,
+    }
+}
+
+/// The hex hash inside a pre-authorized transaction or hash(x) signer key.
+pub fn signer_hash(key: &str) -> Option<String> {
+    let bytes = match Strkey::from_string(key).ok()? {
+        Strkey::PreAuthTx(tx) => tx.0,
+        Strkey::HashX(hash) => hash.0,
+        _ => return None,
+    };
+    Some(bytes.iter().map(|byte| format!("{byte:02x}")).collect())
+}
+
+/// A signer key as it reads in a sentence: "signer GABC...XYZ",
+/// "pre-authorized transaction 3f9a...c0b7".
+pub fn describe_signer_key(key: &str) -> String {
+    match (signer_kind(key), signer_hash(key)) {
+        (SignerKind::PreAuthTx, Some(hash)) => {
+            format!("pre-authorized transaction {}", shorten(&hash))
+        }
+        (SignerKind::HashX, Some(hash)) => format!("hash(x) signer {}", shorten(&hash)),
+        (SignerKind::SignedPayload, _) => format!("signed payload signer {}", shorten(key)),
+        _ => format!("signer {}", shorten(key)),
+    }
+}
+
+/// Explain one of `account_id`'s signers. `preauth_status` is what a lookup
+/// of a pre-authorized transaction found, if one was made.
+pub fn explain_signer(
+    account_id: &str,
+    signer: &Signer,
+    preauth_status: Option<PreAuthStatus>,
+) -> SignerExplanation {
+    let kind = signer_kind(&signer.key);
+    let hash = signer_hash(&signer.key);
+    let weight = signer.weight;
+    let summary = match (kind, &hash) {
+        (SignerKind::Ed25519, _) if signer.key == account_id => {
+            format!("The account's own key (the master key) signs with weight {weight}.")
+        }
+        (SignerKind::PreAuthTx, Some(hash)) => {
+            let mut summary = format!(
+                "Pre-authorizes transaction {} with weight {weight}: that exact transaction can be submitted without any other signature, and this signer is removed once it is applied.",
+                shorten(hash)
+            );
+            match preauth_status {
+                Some(PreAuthStatus::Pending) => summary.push_str(
+                    " It is not in any ledger yet, so it is still waiting to be submitted.",
+                ),
+                Some(PreAuthStatus::Applied {
+                    ledger: Some(ledger),
+                }) => summary.push_str(&format!(" It was applied in ledger {ledger}.")),
+                Some(PreAuthStatus::Applied { ledger: None }) => {
+                    summary.push_str(" It has already been applied.")
+                }
+                Some(PreAuthStatus::Unknown) | None => {}
+            }
+            summary
+        }
+        (SignerKind::HashX, Some(hash)) => format!(
+            "Anyone who reveals the secret value whose SHA-256 hash is {} can sign with weight {weight}. Using it publishes the value on the ledger, so it usually signs once, as in atomic swaps and payment channels.",
+            shorten(hash)
+        ),
+        (SignerKind::SignedPayload, _) => format!(
+            "{} signs with weight {weight} by signing a specific payload named in the key, rather than the transaction itself.",
+            shorten(&signer.key)
+        ),
+        _ => format!("{} signs with weight {weight}.", shorten(&signer.key)),
+    };
+    SignerExplanation {
+        key: signer.key.clone(),
+        kind,
+        weight,
+        summary,
+        hash,
+        preauth_status: (kind == SignerKind::PreAuthTx)
+            .then_some(preauth_status)
+            .flatten(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stellar_strkey::{HashX, PreAuthTx, ed25519};
+
+    const ME: &str = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+
+    fn signer(key: String, weight: u32) -> Signer {
+        Signer {
+            key,
+            weight,
+            sponsor: None,
+        }
+    }
+
+    #[test]
+    fn test_signer_kinds_and_hashes() {
+        let preauth = PreAuthTx([0xab; 32]).to_string();
+        let hash_x = HashX([0x01; 32]).to_string();
+
+        assert_eq!(signer_kind(ME), SignerKind::Ed25519);
+        assert_eq!(signer_kind(&preauth), SignerKind::PreAuthTx);
+        assert_eq!(signer_kind(&hash_x), SignerKind::HashX);
+        assert_eq!(signer_kind("nonsense"), SignerKind::Unknown);
+        assert_eq!(signer_hash(&preauth), Some("ab".repeat(32)));
+        assert_eq!(signer_hash(ME), None);
+        assert!(describe_signer_key(&preauth).starts_with("pre-authorized transaction abab"));
+        assert!(describe_signer_key(&hash_x).starts_with("hash(x) signer 0101"));
+        assert!(describe_signer_key(ME).starts_with("signer GAAZ"));
+    }
+
+    #[test]
+    fn test_preauth_signer_reports_whether_the_transaction_is_waiting() {
+        let preauth = signer(PreAuthTx([0xab; 32]).to_string(), 1);
+
+        let waiting = explain_signer(ME, &preauth, Some(PreAuthStatus::Pending));
+        assert_eq!(waiting.kind, SignerKind::PreAuthTx);
+        assert_eq!(waiting.hash, Some("ab".repeat(32)));
+        assert!(
+            waiting
+                .summary
+                .starts_with("Pre-authorizes transaction abab")
+        );
+        assert!(waiting.summary.ends_with("still waiting to be submitted."));
+
+        let applied = explain_signer(
+            ME,
+            &preauth,
+            Some(PreAuthStatus::Applied { ledger: Some(42) }),
+        );
+        assert!(applied.summary.ends_with("It was applied in ledger 42."));
+
+        let unchecked = explain_signer(ME, &preauth, None);
+        assert_eq!(unchecked.preauth_status, None);
+        assert!(unchecked.summary.ends_with("once it is applied."));
+    }
+
+    #[test]
+    fn test_other_signers() {
+        let master = explain_signer(ME, &signer(ME.to_string(), 1), None);
+        assert!(master.summary.starts_with("The account's own key"));
+
+        let hash_x = explain_signer(ME, &signer(HashX([0x01; 32]).to_string(), 2), None);
+        assert_eq!(hash_x.kind, SignerKind::HashX);
+        assert!(hash_x.summary.contains("can sign with weight 2"));
+
+        let other = ed25519::PublicKey([9; 32]).to_string();
+        let other = explain_signer(ME, &signer(other, 1), None);
+        assert_eq!(other.kind, SignerKind::Ed25519);
+        assert!(other.summary.ends_with("signs with weight 1."));
+    }
+}
@@ -0,0 +1,250 @@
+//! How a path payment's conversion rate compared with the market.
+//!
+//! The effective rate is what the payer received per unit sent, taken from
+//! the trades the payment made. The market rate for each hop is the last
+//! price the pair traded at before the including ledger closed; Horizon
+//! keeps no order book history, so this stands in for the mid-price at the
+//! time. A multi-hop payment's market rate is the product of its hops'.
+
+use serde::{Deserialize, Serialize};
+
+use crate::explain::anchor::parse_asset;
+use crate::explain::format::format_amount;
+use crate::explain::templates;
+use crate::models::amount::Amount;
+use crate::models::operation::PathPaymentOperation;
+use crate::models::trade::TradeFill;
+
+/// Slippage within this many percent either way counts as the market rate.
+const IN_LINE_PERCENT: f64 = 0.05;
+
+/// A path payment's conversion rate against the market rate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PathPaymentSlippage {
+    /// Position of the path payment within the transaction (0-based).
+    pub operation_index: usize,
+    /// Destination asset received per unit of source asset.
+    pub effective_rate: f64,
+    /// Destination asset per unit of source asset at the last traded
+    /// prices before the ledger closed.
+    pub market_rate: f64,
+    /// How much worse the effective rate was than the market rate, in
+    /// percent; negative when it was better.
+    pub slippage_percent: f64,
+    /// e.g. "converted at 0.112 USDC per XLM, about 0.8% worse than the
+    /// market rate"
+    pub summary: String,
+}
+
+/// One step of a conversion, from one asset to the next, with the amounts
+/// of every trade in that step added up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hop {
+    pub sold_asset: String,
+    pub sold_amount: Amount,
+    pub bought_asset: String,
+    pub bought_amount: Amount,
+}
+
+/// Group a path payment's trades into hops, in the order they were made.
+/// `None` unless the hops lead from `send_asset` to `dest_asset`, both
+/// "native" or "CODE:ISSUER".
+pub fn path_hops(fills: &[TradeFill], send_asset: &str, dest_asset: &str) -> Option<Vec<Hop>> {
+    let mut hops: Vec<Hop> = Vec::new();
+    for fill in fills {
+        match hops.last_mut() {
+            Some(hop)
+                if hop.sold_asset == fill.sold_asset && hop.bought_asset == fill.bought_asset =>
+            {
+                hop.sold_amount = hop.sold_amount.saturating_add(fill.sold_amount);
+                hop.bought_amount = hop.bought_amount.saturating_add(fill.bought_amount);
+            }
+            _ => hops.push(Hop {
+                sold_asset: fill.sold_asset.clone(),
+                sold_amount: fill.sold_amount,
+                bought_asset: fill.bought_asset.clone(),
+                bought_amount: fill.bought_amount,
+            }),
+        }
+    }
+    let chained = hops
+        .windows(2)
+        .all(|pair| pair[0].bought_asset == pair[1].sold_asset);
+    let first = hops.first()?;
+    let last = hops.last()?;
+    (chained && first.sold_asset == send_asset && last.bought_asset == dest_asset).then_some(hops)
+}
+
+/// Compare the rate the hops achieved with `market_rates`, one per hop as
+/// bought asset per sold asset.
+pub fn explain_slippage(
+    operation_index: usize,
+    op: &PathPaymentOperation,
+    hops: &[Hop],
+    market_rates: &[f64],
+) -> Option<PathPaymentSlippage> {
+    if hops.is_empty() || hops.len() != market_rates.len() {
+        return None;
+    }
+    let sent = hops.first()?.sold_amount;
+    let received = hops.last()?.bought_amount;
+    let effective_rate = received.ratio(sent)?;
+    let market_rate: f64 = market_rates.iter().product();
+    if !market_rate.is_finite() || market_rate <= 0.0 {
+        return None;
+    }
+    let slippage_percent = (1.0 - effective_rate / market_rate) * 100.0;
+
+    let rate = format!(
+        "{} {} per {}",
+        format_rate(effective_rate),
+        asset_code(&op.dest_asset),
+        asset_code(&op.send_asset)
+    );
+    let percent = format!("{:.1}", slippage_percent.abs());
+    let summary = if slippage_percent.abs() < IN_LINE_PERCENT {
+        templates::render("slippage.in_line", &[("rate", &rate)])
+    } else if slippage_percent > 0.0 {
+        templates::render("slippage.worse", &[("rate", &rate), ("percent", &percent)])
+    } else {
+        templates::render("slippage.better", &[("rate", &rate), ("percent", &percent)])
+    };
+
+    Some(PathPaymentSlippage {
+        operation_index,
+        effective_rate,
+        market_rate,
+        slippage_percent,
+        summary,
+    })
+}
+
+/// A displayed asset ("USDC (GISSUER)", "XLM (native)") as "native" or
+/// "CODE:ISSUER", the form trades and market prices use.
+pub fn canonical_asset(asset: &str) -> Option<String> {
+    match parse_asset(asset)? {
+        (code, None) => (code == "XLM").then(|| "native".to_string()),
+        (code, Some(issuer)) => Some(format!("{code}:{issuer}")),
+    }
+}
+
+fn asset_code(asset: &str) -> String {
+    parse_asset(asset).map_or_else(|| asset.to_string(), |(code, _)| code)
+}
+
+/// Four significant digits, without trailing zeros: 0.112, 8.929, 1235.
+pub(crate) fn format_rate(rate: f64) -> String {
+    let decimals = (3 - rate.log10().floor() as i32).clamp(0, 7) as usize;
+    let fixed = format!("{rate:.decimals$}");
+    let trimmed = if fixed.contains('.') {
+        fixed.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &fixed
+    };
+    format_amount(trimmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::PathPaymentType;
+
+    const USDC: &str = "USDC:GISSUER";
+    const BTC: &str = "BTC:GBTCISSUER";
+
+    fn fill(sold: &str, sold_amount: &str, bought: &str, bought_amount: &str) -> TradeFill {
+        TradeFill {
+            sold_asset: sold.to_string(),
+            sold_amount: sold_amount.parse().unwrap(),
+            bought_asset: bought.to_string(),
+            bought_amount: bought_amount.parse().unwrap(),
+        }
+    }
+
+    fn op() -> PathPaymentOperation {
+        PathPaymentOperation {
+            id: "1".to_string(),
+            source_account: Some("GAAAA".to_string()),
+            destination: "GBBB".to_string(),
+            send_asset: "XLM (native)".to_string(),
+            send_amount: "100".parse().unwrap(),
+            dest_asset: "USDC (GISSUER)".to_string(),
+            dest_amount: "11.2".parse().unwrap(),
+            path: vec![],
+            payment_type: PathPaymentType::StrictSend,
+        }
+    }
+
+    #[test]
+    fn test_fills_are_grouped_into_hops() {
+        let fills = [
+            fill("native", "60", BTC, "0.0006"),
+            fill("native", "40", BTC, "0.0004"),
+            fill(BTC, "0.001", USDC, "11.2"),
+        ];
+        let hops = path_hops(&fills, "native", USDC).unwrap();
+        assert_eq!(hops.len(), 2);
+        assert_eq!(hops[0].sold_amount, "100".parse().unwrap());
+        assert_eq!(hops[0].bought_amount, "0.001".parse().unwrap());
+
+        // Trades that do not lead to the destination are not a path.
+        assert_eq!(path_hops(&fills[..2], "native", USDC), None);
+        assert_eq!(path_hops(&[], "native", USDC), None);
+    }
+
+    #[test]
+    fn test_slippage_against_market_rate() {
+        let hops = path_hops(&[fill("native", "100", USDC, "11.2")], "native", USDC).unwrap();
+
+        let worse = explain_slippage(2, &op(), &hops, &[0.1129]).unwrap();
+        assert_eq!(worse.operation_index, 2);
+        assert!((worse.slippage_percent - 0.797).abs() < 0.01);
+        assert_eq!(
+            worse.summary,
+            "converted at 0.112 USDC per XLM, about 0.8% worse than the market rate"
+        );
+
+        let better = explain_slippage(0, &op(), &hops, &[0.11]).unwrap();
+        assert!(
+            better
+                .summary
+                .ends_with("about 1.8% better than the market rate")
+        );
+        let in_line = explain_slippage(0, &op(), &hops, &[0.112]).unwrap();
+        assert_eq!(
+            in_line.summary,
+            "converted at 0.112 USDC per XLM, in line with the market rate"
+        );
+
+        assert_eq!(explain_slippage(0, &op(), &hops, &[]), None);
+        assert_eq!(explain_slippage(0, &op(), &hops, &[0.0]), None);
+    }
+
+    #[test]
+    fn test_multi_hop_market_rate_is_the_product() {
+        let hops = vec![
+            Hop {
+                sold_asset: "native".to_string(),
+                sold_amount: "100".parse().unwrap(),
+                bought_asset: BTC.to_string(),
+                bought_amount: "0.001".parse().unwrap(),
+            },
+            Hop {
+                sold_asset: BTC.to_string(),
+                sold_amount: "0.001".parse().unwrap(),
+                bought_asset: USDC.to_string(),
+                bought_amount: "11.2".parse().unwrap(),
+            },
+        ];
+        let slippage = explain_slippage(0, &op(), &hops, &[0.00001, 11200.0]).unwrap();
+        assert!((slippage.market_rate - 0.112).abs() < 1e-9);
+        assert!(slippage.slippage_percent.abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_canonical_asset() {
+        assert_eq!(canonical_asset("XLM (native)").as_deref(), Some("native"));
+        assert_eq!(canonical_asset("USDC (GISSUER)").as_deref(), Some(USDC));
+        assert_eq!(canonical_asset("Unknown"), None);
+    }
+}
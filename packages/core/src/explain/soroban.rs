@@ -0,0 +1,383 @@
+//! Explanations for Soroban contract calls and events.
+//!
+//! Stellar Asset Contract (SAC) events are recognised by their topics — an
+//! event name, the addresses involved, and the asset as "CODE:ISSUER" or
+//! "native" — and described like classic payments. Events from other
+//! contracts are named but not interpreted. Contract addresses of known SACs
+//! are shown by asset name (see [`crate::services::assets`]), and other
+//! well-known contracts and contract code by their label (see
+//! [`crate::services::contract_labels`]).
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::explain::format::{format_account, format_amount, shorten};
+use crate::explain::templates;
+use crate::models::amount::Amount;
+use crate::models::contract_call::{ContractCall, ContractCallKind};
+use crate::models::contract_event::{ContractEvent, EventValue};
+use crate::services::assets::{SacAsset, resolve_sac};
+use crate::services::contract_labels::resolve_contract_label;
+
+/// Plain-English explanation of a single contract event.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ContractEventExplanation {
+    /// Strkey (C...) of the contract that emitted the event.
+    pub contract_id: Option<String>,
+    /// Event name from the first topic, e.g. "transfer".
+    pub event_type: String,
+    pub summary: String,
+    /// Structured fields recognised in the event, e.g. from/to/amount/asset.
+    pub details: serde_json::Value,
+    /// True when decoded from diagnostic events, e.g. for a failed invocation.
+    pub diagnostic: bool,
+}
+
+/// Contract WASM a transaction runs, deploys, or uploads.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ContractCode {
+    /// Hex SHA-256 hash of the WASM.
+    pub wasm_hash: String,
+    /// Name of well-known code, e.g. "Soroswap Pair".
+    pub label: Option<String>,
+}
+
+/// Plain-English explanation of a Soroban transaction's contract call.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ContractCallExplanation {
+    pub kind: ContractCallKind,
+    /// Strkey (C...) of the invoked contract.
+    pub contract_id: Option<String>,
+    /// Name of the invoked function.
+    pub function: Option<String>,
+    /// Name of the invoked contract, when it is well known or a Stellar
+    /// Asset Contract.
+    pub label: Option<String>,
+    /// The WASM involved, with labels for well-known code.
+    pub code: Vec<ContractCode>,
+    pub summary: String,
+}
+
+/// Explain a contract call, naming well-known contracts and code.
+pub fn explain_contract_call(call: &ContractCall) -> ContractCallExplanation {
+    let code: Vec<ContractCode> = call
+        .wasm_hashes
+        .iter()
+        .map(|hash| ContractCode {
+            wasm_hash: hash.clone(),
+            label: resolve_contract_label(hash),
+        })
+        .collect();
+    let label = call.contract_id.as_deref().and_then(|contract| {
+        resolve_contract_label(contract).or_else(|| {
+            resolve_sac(contract).map(|asset| format!("{} Stellar Asset Contract", asset.code))
+        })
+    });
+
+    let summary = match (call.kind, code.first()) {
+        (ContractCallKind::Invoke, _) => {
+            let contract = call
+                .contract_id
+                .as_deref()
+                .map_or_else(|| "Unknown".to_string(), format_account);
+            let function = call.function.as_deref().unwrap_or("unknown");
+            let mut sentences = vec![templates::render(
+                "soroban.invoke",
+                &[("contract", &contract), ("function", function)],
+            )];
+            // The footprint holds the code of every contract the call
+            // reaches, so the code is the call's rather than the contract's.
+            sentences.extend(code.iter().filter_map(|code| {
+                let label = code.label.as_deref()?;
+                Some(templates::render("soroban.runs_code", &[("code", label)]))
+            }));
+            sentences.join(" ")
+        }
+        (ContractCallKind::Deploy, Some(wasm)) => {
+            templates::render("soroban.deploy", &[("code", &describe_code(wasm))])
+        }
+        (ContractCallKind::Deploy, None) => templates::render("soroban.deploy_asset", &[]),
+        (ContractCallKind::UploadWasm, wasm) => {
+            let shown = wasm.map_or_else(|| "Unknown".to_string(), describe_code);
+            templates::render("soroban.upload", &[("code", &shown)])
+        }
+    };
+
+    ContractCallExplanation {
+        kind: call.kind,
+        contract_id: call.contract_id.clone(),
+        function: call.function.clone(),
+        label,
+        code,
+        summary,
+    }
+}
+
+/// "Soroswap Pair (WASM ab12...ef90)", or "WASM ab12...ef90" for unknown code.
+fn describe_code(code: &ContractCode) -> String {
+    let hash = shorten(&code.wasm_hash);
+    match &code.label {
+        Some(label) => format!("{label} (WASM {hash})"),
+        None => format!("WASM {hash}"),
+    }
+}
+
+/// Explain a contract event, interpreting Stellar Asset Contract events.
+pub fn explain_contract_event(event: &ContractEvent) -> ContractEventExplanation {
+    let event_type = event
+        .topics
+        .first()
+        .and_then(EventValue::as_symbol)
+        .unwrap_or("unknown")
+        .to_string();
+    let contract = event.contract_id.as_deref().unwrap_or("Unknown");
+
+    let (summary, details) = match explain_sac_event(&event_type, event, contract) {
+        Some(explained) => explained,
+        None => (
+            templates::render(
+                "soroban.event",
+                &[
+                    ("contract", &format_account(contract)),
+                    ("name", &event_type),
+                ],
+            ),
+            serde_json::json!({}),
+        ),
+    };
+
+    ContractEventExplanation {
+        contract_id: event.contract_id.clone(),
+        event_type,
+        summary,
+        details,
+        diagnostic: event.diagnostic,
+    }
+}
+
+fn explain_sac_event(
+    event_type: &str,
+    event: &ContractEvent,
+    contract: &str,
+) -> Option<(String, serde_json::Value)> {
+    // SAC events name the asset in their last topic; fall back to the
+    // resolver for events that omit it.
+    let asset = match event.topics.last()? {
+        EventValue::String(asset) => SacAsset::from_sep11(asset)?,
+        _ => resolve_sac(contract)?,
+    };
+    let asset = asset.code.as_str();
+    let amount = match &event.data {
+        EventValue::Amount(amount) => token_amount(*amount),
+        _ => return None,
+    };
+    let addresses: Vec<&str> = event.topics[1..]
+        .iter()
+        .filter_map(EventValue::as_address)
+        .collect();
+
+    // Older protocol versions put the admin before the affected account in
+    // mint and clawback topics, so the affected account is always the last.
+    let (from, to) = match (event_type, addresses.as_slice()) {
+        ("transfer", [from, to]) => (Some(*from), Some(*to)),
+        ("mint", [.., to]) => (None, Some(*to)),
+        ("burn", [from]) | ("clawback", [.., from]) => (Some(*from), None),
+        _ => return None,
+    };
+
+    let from_display = from.map(format_account);
+    let to_display = to.map(format_account);
+    let shown_amount = format_amount(&amount);
+    let mut args = vec![
+        ("amount", shown_amount.as_str()),
+        ("asset", asset),
+        ("contract", contract),
+    ];
+    if let Some(from) = &from_display {
+        args.push(("from", from));
+    }
+    if let Some(to) = &to_display {
+        args.push(("to", to));
+    }
+
+    let summary = templates::render(&format!("soroban.{event_type}"), &args);
+    let details = serde_json::json!({
+        "from": from,
+        "to": to,
+        "amount": amount,
+        "asset": asset,
+    });
+    Some((summary, details))
+}
+
+/// Render a 7-decimal token amount without trailing zeros: 105000000 → "10.5".
+fn token_amount(amount: i128) -> String {
+    Amount::from_stroops(amount).trimmed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: &str = "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC";
+    const FROM: &str = "CBQHNAXSI55GX2GN6D67GK7BHVPSLJUGZQEU7WJ5LKR5PNUCGLIMAO4K";
+    const PUBLIC_XLM_SAC: &str = "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA";
+    const TO: &str = "GCKFBEIYV2U22IO2BJ4KVJOIP7XPWQGQFKKWXR6DOSJBV7STMAQSMTGG";
+
+    fn event(topics: Vec<EventValue>, data: EventValue) -> ContractEvent {
+        ContractEvent {
+            contract_id: Some(CONTRACT.to_string()),
+            topics,
+            data,
+            diagnostic: false,
+        }
+    }
+
+    fn sym(s: &str) -> EventValue {
+        EventValue::Symbol(s.to_string())
+    }
+
+    fn addr(s: &str) -> EventValue {
+        EventValue::Address(s.to_string())
+    }
+
+    fn asset(s: &str) -> EventValue {
+        EventValue::String(s.to_string())
+    }
+
+    #[test]
+    fn test_explain_sac_transfer() {
+        let explanation = explain_contract_event(&event(
+            vec![sym("transfer"), addr(FROM), addr(TO), asset("USDC:GISSUER")],
+            EventValue::Amount(100_000_000),
+        ));
+        assert_eq!(explanation.event_type, "transfer");
+        assert_eq!(
+            explanation.summary,
+            format!("The USDC Stellar Asset Contract transferred 10 USDC from {FROM} to {TO}.")
+        );
+        assert_eq!(explanation.details["amount"], "10");
+        assert_eq!(explanation.details["to"], TO);
+    }
+
+    #[test]
+    fn test_explain_sac_mint_with_admin_topic() {
+        let explanation = explain_contract_event(&event(
+            vec![sym("mint"), addr(FROM), addr(TO), asset("native")],
+            EventValue::Amount(5_000_000),
+        ));
+        assert_eq!(
+            explanation.summary,
+            format!("The XLM Stellar Asset Contract minted 0.5 XLM to {TO}.")
+        );
+    }
+
+    #[test]
+    fn test_explain_sac_burn_and_clawback() {
+        let burn = explain_contract_event(&event(
+            vec![sym("burn"), addr(TO), asset("USDC:GISSUER")],
+            EventValue::Amount(10_000_000),
+        ));
+        assert!(burn.summary.contains("burned 1 USDC from"));
+
+        let clawback = explain_contract_event(&event(
+            vec![sym("clawback"), addr(FROM), addr(TO), asset("USDC:GISSUER")],
+            EventValue::Amount(10_000_000),
+        ));
+        assert!(
+            clawback
+                .summary
+                .ends_with(&format!("clawed back 1 USDC from {TO}."))
+        );
+    }
+
+    #[test]
+    fn test_known_sac_addresses_shown_by_asset_name() {
+        let explanation = explain_contract_event(&event(
+            vec![
+                sym("transfer"),
+                addr(TO),
+                addr(PUBLIC_XLM_SAC),
+                asset("native"),
+            ],
+            EventValue::Amount(10_000_000),
+        ));
+        assert!(explanation.summary.ends_with(&format!(
+            "to XLM Stellar Asset Contract ({PUBLIC_XLM_SAC})."
+        )));
+
+        // Without an asset topic the emitting contract identifies the asset.
+        let explanation = explain_contract_event(&ContractEvent {
+            contract_id: Some(PUBLIC_XLM_SAC.to_string()),
+            ..event(
+                vec![sym("transfer"), addr(FROM), addr(TO)],
+                EventValue::Amount(10_000_000),
+            )
+        });
+        assert!(
+            explanation
+                .summary
+                .starts_with("The XLM Stellar Asset Contract transferred 1 XLM")
+        );
+    }
+
+    #[test]
+    fn test_explain_custom_contract_event() {
+        let explanation = explain_contract_event(&event(
+            vec![sym("swap"), addr(FROM)],
+            EventValue::Other("Void".to_string()),
+        ));
+        assert_eq!(
+            explanation.summary,
+            format!("Contract {CONTRACT} emitted a \"swap\" event.")
+        );
+        assert_eq!(explanation.details, serde_json::json!({}));
+    }
+
+    fn call(kind: ContractCallKind, contract_id: Option<&str>, wasm: &[String]) -> ContractCall {
+        ContractCall {
+            kind,
+            contract_id: contract_id.map(str::to_string),
+            function: (kind == ContractCallKind::Invoke).then(|| "swap".to_string()),
+            wasm_hashes: wasm.to_vec(),
+        }
+    }
+
+    #[test]
+    fn test_explain_contract_calls_with_labels() {
+        let router = "CAG5LRYQ5JVEUI5TEID72EYOVX44TTUJT5BQR2J6J77FH65PCCFAJDDH";
+        let unknown_wasm = "ab".repeat(32);
+
+        let explained = explain_contract_call(&call(
+            ContractCallKind::Invoke,
+            Some(router),
+            std::slice::from_ref(&unknown_wasm),
+        ));
+        assert_eq!(explained.label.as_deref(), Some("Soroswap Router"));
+        assert_eq!(explained.code[0].label, None);
+        assert_eq!(
+            explained.summary,
+            format!("Called \"swap\" on Soroswap Router ({router}).")
+        );
+
+        let sac = explain_contract_call(&call(ContractCallKind::Invoke, Some(PUBLIC_XLM_SAC), &[]));
+        assert_eq!(sac.label.as_deref(), Some("XLM Stellar Asset Contract"));
+
+        let deploy = explain_contract_call(&call(ContractCallKind::Deploy, None, &[unknown_wasm]));
+        assert!(
+            deploy
+                .summary
+                .starts_with("Deployed a contract running WASM abab")
+        );
+        let asset = explain_contract_call(&call(ContractCallKind::Deploy, None, &[]));
+        assert_eq!(asset.summary, "Deployed a Stellar Asset Contract.");
+    }
+
+    #[test]
+    fn test_token_amount() {
+        assert_eq!(token_amount(100_000_000), "10");
+        assert_eq!(token_amount(105_000_000), "10.5");
+        assert_eq!(token_amount(1), "0.0000001");
+        assert_eq!(token_amount(-25_000_000), "-2.5");
+    }
+}
@@ -0,0 +1,114 @@
+//! Who submitted a transaction, and for whom.
+//!
+//! Every transaction has a source account that supplies its sequence number
+//! and normally pays its fee; each operation acts for its own source, which
+//! defaults to the transaction's. When none of the operations act for the
+//! transaction's source, that account is a channel account: a separate
+//! account whose only job is to carry sequence numbers, so a busy sender can
+//! submit many transactions in parallel without them competing for one
+//! sequence.
+
+use crate::explain::format::format_account;
+use crate::explain::templates;
+use crate::models::transaction::TransactionSource;
+
+/// What the source of a transaction says about how it was submitted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceExplanation {
+    pub summary: String,
+    /// The transaction's source, when it acted only as a channel account.
+    pub channel_account: Option<String>,
+}
+
+pub fn explain_source(source: &TransactionSource) -> Option<SourceExplanation> {
+    let is_channel = !source.operation_accounts.is_empty()
+        && !source.operation_accounts.contains(&source.account);
+
+    let mut summary = if is_channel {
+        let accounts = source
+            .operation_accounts
+            .iter()
+            .map(|account| format_account(account))
+            .collect::<Vec<_>>()
+            .join(", ");
+        templates::render(
+            "source.channel",
+            &[
+                ("channel", &format_account(&source.account)),
+                ("accounts", &accounts),
+            ],
+        )
+    } else {
+        templates::render(
+            "source.sequence",
+            &[
+                ("account", &format_account(&source.account)),
+                ("sequence", source.sequence.as_deref()?),
+            ],
+        )
+    };
+
+    if let Some(fee_account) = source
+        .fee_account
+        .as_ref()
+        .filter(|fee_account| **fee_account != source.account)
+    {
+        summary.push(' ');
+        summary.push_str(&templates::render(
+            "source.fee_bump",
+            &[("fee_account", &format_account(fee_account))],
+        ));
+    }
+
+    Some(SourceExplanation {
+        summary,
+        channel_account: is_channel.then(|| source.account.clone()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(account: &str, operation_accounts: &[&str]) -> TransactionSource {
+        TransactionSource {
+            account: account.to_string(),
+            sequence: Some("5173180072476675".to_string()),
+            fee_account: Some(account.to_string()),
+            operation_accounts: operation_accounts.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_ordinary_source_explains_sequence() {
+        let explanation = explain_source(&source("GSENDER", &["GSENDER", "GOTHER"])).unwrap();
+
+        assert_eq!(explanation.channel_account, None);
+        assert_eq!(
+            explanation.summary,
+            "GSENDER submitted this transaction using sequence number 5173180072476675, so its next transaction must use the number after it."
+        );
+    }
+
+    #[test]
+    fn test_channel_account_detected() {
+        let explanation = explain_source(&source("GCHANNEL", &["GEXCHANGE"])).unwrap();
+
+        assert_eq!(explanation.channel_account.as_deref(), Some("GCHANNEL"));
+        assert!(explanation.summary.contains("channel account GCHANNEL"));
+        assert!(explanation.summary.contains("on behalf of GEXCHANGE"));
+    }
+
+    #[test]
+    fn test_fee_bump_payer_mentioned() {
+        let mut bumped = source("GSENDER", &["GSENDER"]);
+        bumped.fee_account = Some("GSPONSOR".to_string());
+
+        let explanation = explain_source(&bumped).unwrap();
+        assert!(
+            explanation
+                .summary
+                .ends_with("GSPONSOR paid the fee through a fee bump.")
+        );
+    }
+}
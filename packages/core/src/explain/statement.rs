@@ -0,0 +1,357 @@
+//! Account statements.
+//!
+//! A statement lists an account's balances, every transaction in a date
+//! range with its plain-English summary, and how much of each asset came in
+//! and went out. Totals count payments, path payments, and account creation
+//! in successful transactions; other operations move no amount the operation
+//! itself records.
+
+use std::collections::BTreeMap;
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::{count, format_asset};
+use crate::explain::templates;
+use crate::explain::transaction::explain_transaction;
+use crate::models::account::Account;
+use crate::models::amount::Amount;
+use crate::models::operation::Operation;
+use crate::models::transaction::Transaction;
+
+/// An account's balances and activity over a date range.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct AccountStatement {
+    pub address: String,
+    /// First day covered, "YYYY-MM-DD" in UTC.
+    pub from: String,
+    /// Last day covered, inclusive.
+    pub to: String,
+    /// Balances when the statement was generated, not at the end of the range.
+    pub balances: Vec<StatementBalance>,
+    /// Transactions in the range, oldest first.
+    pub transactions: Vec<StatementTransaction>,
+    /// Amounts received and sent per asset, by asset.
+    pub totals: Vec<AssetTotal>,
+    /// Not every transaction in the range is listed: there were more than a
+    /// statement holds. The newest are listed and the totals cover only those.
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct StatementBalance {
+    /// E.g. "XLM (native)" or "USDC (GISSUER)".
+    pub asset: String,
+    pub balance: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct StatementTransaction {
+    pub hash: String,
+    pub created_at: String,
+    pub successful: bool,
+    /// What the transaction's operations did, one sentence each.
+    pub summary: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema, ToSchema)]
+pub struct AssetTotal {
+    pub asset: String,
+    pub received: String,
+    pub sent: String,
+    /// Received minus sent; negative when more went out.
+    pub net: String,
+}
+
+/// A transaction on the account, with its operations when they could be
+/// fetched.
+#[derive(Debug, Clone)]
+pub struct StatementEntry {
+    pub transaction: Transaction,
+    pub created_at: String,
+    pub operation_count: u32,
+}
+
+/// Build a statement from the account and its transactions in the range,
+/// oldest first.
+pub fn build_statement(
+    address: &str,
+    from: &str,
+    to: &str,
+    account: &Account,
+    entries: &[StatementEntry],
+    truncated: bool,
+) -> AccountStatement {
+    let balances = account
+        .balances
+        .iter()
+        .map(|balance| StatementBalance {
+            asset: format_asset(
+                Some(balance.asset_type.as_str()),
+                balance.asset_code.as_deref(),
+                balance.asset_issuer.as_deref(),
+            ),
+            balance: balance.balance.clone(),
+        })
+        .collect();
+
+    AccountStatement {
+        address: address.to_string(),
+        from: from.to_string(),
+        to: to.to_string(),
+        balances,
+        transactions: entries.iter().map(statement_transaction).collect(),
+        totals: asset_totals(address, entries),
+        truncated,
+    }
+}
+
+/// Amounts `address` received and sent per asset across the successful
+/// transactions in `entries`, by asset.
+pub fn asset_totals(address: &str, entries: &[StatementEntry]) -> Vec<AssetTotal> {
+    let mut flows: BTreeMap<String, (Amount, Amount)> = BTreeMap::new();
+    for entry in entries.iter().filter(|e| e.transaction.successful) {
+        for op in &entry.transaction.operations {
+            for (asset, received, sent) in movements(address, op) {
+                let (total_received, total_sent) = flows.entry(asset).or_default();
+                *total_received = total_received.saturating_add(received);
+                *total_sent = total_sent.saturating_add(sent);
+            }
+        }
+    }
+    flows
+        .into_iter()
+        .map(|(asset, (received, sent))| AssetTotal {
+            asset,
+            received: received.to_string(),
+            sent: sent.to_string(),
+            net: (received - sent).to_string(),
+        })
+        .collect()
+}
+
+/// One transaction as a statement lists it, summarized from its operations.
+pub fn statement_transaction(entry: &StatementEntry) -> StatementTransaction {
+    let tx = &entry.transaction;
+    let summary = match explain_transaction(tx, None) {
+        Ok(explanation) => explanation
+            .operations
+            .iter()
+            .map(|op| op.summary.trim_end_matches('.').to_string() + ".")
+            .collect::<Vec<_>>()
+            .join(" "),
+        // Operations could not be fetched: say what the listing knows.
+        Err(_) => templates::render(
+            if tx.successful {
+                "statement.unexplained_successful"
+            } else {
+                "statement.unexplained_failed"
+            },
+            &[(
+                "operations",
+                &count(entry.operation_count as usize, "operation", "operations"),
+            )],
+        ),
+    };
+    StatementTransaction {
+        hash: tx.hash.clone(),
+        created_at: entry.created_at.clone(),
+        successful: tx.successful,
+        summary,
+    }
+}
+
+/// What `op` moved into and out of `address`: (asset, received, sent).
+pub(crate) fn movements(address: &str, op: &Operation) -> Vec<(String, Amount, Amount)> {
+    let mut moved = Vec::new();
+    let mut record = |asset: &str, from: Option<&str>, to: &str, sent: Amount, received: Amount| {
+        if to == address {
+            moved.push((asset.to_string(), received, Amount::ZERO));
+        }
+        if from == Some(address) {
+            moved.push((asset.to_string(), Amount::ZERO, sent));
+        }
+    };
+    match op {
+        Operation::Payment(p) => {
+            let asset = format_asset(
+                Some(p.asset_type.as_str()),
+                p.asset_code.as_deref(),
+                p.asset_issuer.as_deref(),
+            );
+            record(
+                &asset,
+                p.source_account.as_deref(),
+                &p.destination,
+                p.amount,
+                p.amount,
+            );
+        }
+        Operation::CreateAccount(c) => record(
+            "XLM (native)",
+            Some(&c.funder),
+            &c.new_account,
+            c.starting_balance,
+            c.starting_balance,
+        ),
+        Operation::PathPayment(p) => {
+            if p.source_account.as_deref() == Some(address) {
+                moved.push((p.send_asset.clone(), Amount::ZERO, p.send_amount));
+            }
+            if p.destination == address {
+                moved.push((p.dest_asset.clone(), p.dest_amount, Amount::ZERO));
+            }
+        }
+        _ => {}
+    }
+    moved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::account::{AccountFlags, Balance};
+    use crate::models::operation::{
+        CreateAccountOperation, PathPaymentOperation, PathPaymentType, PaymentOperation,
+    };
+
+    const ME: &str = "GME";
+    const THEM: &str = "GTHEM";
+
+    fn account() -> Account {
+        Account {
+            id: ME.to_string(),
+            account_id: ME.to_string(),
+            sequence: "1".to_string(),
+            num_signers: 1,
+            signers: Vec::new(),
+            master_key_weight: 1,
+            thresholds: Default::default(),
+            balances: vec![Balance {
+                asset_type: "native".to_string(),
+                asset_code: None,
+                asset_issuer: None,
+                balance: "120.0000000".to_string(),
+                ..Default::default()
+            }],
+            flags: AccountFlags {
+                auth_required: false,
+                auth_revocable: false,
+                auth_immutable: false,
+                auth_clawback_enabled: false,
+            },
+            home_domain: None,
+            memo_required: false,
+        }
+    }
+
+    fn payment(from: &str, to: &str, amount: &str) -> Operation {
+        Operation::Payment(PaymentOperation {
+            id: "1".to_string(),
+            source_account: Some(from.to_string()),
+            destination: to.to_string(),
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+            amount: amount.parse().unwrap(),
+        })
+    }
+
+    fn entry(hash: &str, successful: bool, operations: Vec<Operation>) -> StatementEntry {
+        StatementEntry {
+            operation_count: operations.len() as u32,
+            transaction: Transaction::new(
+                hash.to_string(),
+                successful,
+                100,
+                operations,
+                None,
+                None,
+            ),
+            created_at: "2025-03-04T10:15:22Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_totals_per_asset() {
+        let entries = vec![
+            entry(
+                "a",
+                true,
+                vec![
+                    Operation::CreateAccount(CreateAccountOperation {
+                        id: "1".to_string(),
+                        funder: THEM.to_string(),
+                        new_account: ME.to_string(),
+                        starting_balance: "100".parse().unwrap(),
+                    }),
+                    payment(ME, THEM, "30.5"),
+                ],
+            ),
+            entry(
+                "b",
+                true,
+                vec![Operation::PathPayment(PathPaymentOperation {
+                    id: "2".to_string(),
+                    source_account: Some(ME.to_string()),
+                    destination: ME.to_string(),
+                    send_asset: "XLM (native)".to_string(),
+                    send_amount: "10".parse().unwrap(),
+                    dest_asset: "USDC (GISSUER)".to_string(),
+                    dest_amount: "1.1".parse().unwrap(),
+                    path: vec![],
+                    payment_type: PathPaymentType::StrictSend,
+                })],
+            ),
+            // Failed transactions moved nothing.
+            entry("c", false, vec![payment(THEM, ME, "999")]),
+        ];
+
+        let statement =
+            build_statement(ME, "2025-03-01", "2025-03-31", &account(), &entries, false);
+
+        assert_eq!(
+            statement.totals,
+            vec![
+                AssetTotal {
+                    asset: "USDC (GISSUER)".to_string(),
+                    received: "1.1000000".to_string(),
+                    sent: "0.0000000".to_string(),
+                    net: "1.1000000".to_string(),
+                },
+                AssetTotal {
+                    asset: "XLM (native)".to_string(),
+                    received: "100.0000000".to_string(),
+                    sent: "40.5000000".to_string(),
+                    net: "59.5000000".to_string(),
+                },
+            ]
+        );
+        assert_eq!(statement.balances[0].asset, "XLM (native)");
+        assert_eq!(statement.transactions.len(), 3);
+    }
+
+    #[test]
+    fn test_transaction_summaries() {
+        let entries = vec![
+            entry("a", true, vec![payment(ME, THEM, "5")]),
+            StatementEntry {
+                operation_count: 2,
+                ..entry("b", false, vec![])
+            },
+        ];
+
+        let statement = build_statement(ME, "2025-03-01", "2025-03-31", &account(), &entries, true);
+
+        assert_eq!(
+            statement.transactions[0].summary,
+            "GME sent 5 XLM (native) to GTHEM."
+        );
+        assert_eq!(
+            statement.transactions[1].summary,
+            "Failed transaction with 2 operations."
+        );
+        assert!(statement.truncated);
+    }
+}
@@ -0,0 +1,1698 @@
+//! Configurable explanation templates.
+//!
+//! Every sentence an explainer produces is rendered from a named template
+//! such as `payment.summary` = `"{from} sent {amount} {asset} to {to}"`.
+//! Operators can override any template from a TOML file loaded at startup:
+//!
+//! ```toml
+//! [payment]
+//! summary = "{amount} {asset} moved from {from} to {to}"
+//! ```
+//!
+//! Overrides are validated when the file is loaded: unknown template names,
+//! unknown placeholders, and templates missing a required placeholder are
+//! rejected so a typo cannot silently drop information from explanations.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::{env, fmt, fs};
+
+/// A built-in template and the placeholders it accepts.
+struct TemplateSpec {
+    key: &'static str,
+    default: &'static str,
+    /// Placeholders an override must keep.
+    required: &'static [&'static str],
+    /// Placeholders an override may use in addition to the required ones.
+    optional: &'static [&'static str],
+}
+
+const SPECS: &[TemplateSpec] = &[
+    TemplateSpec {
+        key: "payment.summary",
+        default: "{from} sent {amount} {asset} to {to}",
+        required: &["amount", "asset", "to"],
+        optional: &["from"],
+    },
+    TemplateSpec {
+        key: "payment.fee_note.standard",
+        default: "Fee paid: {fee} XLM (standard).",
+        required: &["fee"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "payment.fee_note.elevated",
+        default: "Fee paid: {fee} XLM (above average — {multiplier}x base fee).",
+        required: &["fee"],
+        optional: &["multiplier"],
+    },
+    TemplateSpec {
+        key: "path_payment.direct",
+        default: "{from} sent {send_amount} {send_asset} to {to}",
+        required: &["send_amount", "send_asset", "to"],
+        optional: &["from"],
+    },
+    TemplateSpec {
+        key: "path_payment.converted",
+        default: "{from} sent {send_amount} {send_asset} which was converted to {dest_amount} {dest_asset} received by {to}",
+        required: &[
+            "send_amount",
+            "send_asset",
+            "dest_amount",
+            "dest_asset",
+            "to",
+        ],
+        optional: &["from"],
+    },
+    TemplateSpec {
+        key: "create_account.summary",
+        default: "{funder} created account {account} with a starting balance of {starting_balance} XLM.",
+        required: &["account", "starting_balance"],
+        optional: &["funder"],
+    },
+    TemplateSpec {
+        key: "change_trust.added",
+        default: "{trustor} opted in to hold up to {limit} {asset_code} issued by {asset_issuer}.",
+        required: &["trustor", "asset_code"],
+        optional: &["limit", "asset_issuer"],
+    },
+    TemplateSpec {
+        key: "change_trust.removed",
+        default: "{trustor} removed trust for {asset_code}.",
+        required: &["trustor", "asset_code"],
+        optional: &["asset_issuer"],
+    },
+    TemplateSpec {
+        key: "manage_offer.placed",
+        default: "{seller} placed an order to {action} {amount} {base_asset} for {quote_asset} at a price of {price} {quote_asset} per {base_asset}",
+        required: &["action", "amount", "base_asset", "quote_asset", "price"],
+        optional: &["seller"],
+    },
+    TemplateSpec {
+        key: "manage_offer.filled",
+        default: "it immediately traded with {count} existing {offers}, buying {bought_amount} {buying_asset} for {sold_amount} {selling_asset}",
+        required: &[
+            "count",
+            "offers",
+            "bought_amount",
+            "buying_asset",
+            "sold_amount",
+            "selling_asset",
+        ],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "manage_offer.cancelled",
+        default: "{seller} cancelled their existing offer #{offer_id}",
+        required: &["offer_id"],
+        optional: &["seller"],
+    },
+    TemplateSpec {
+        key: "clawback.summary",
+        default: "The asset issuer reclaimed {amount} {asset_code} from {from}. {context}",
+        required: &["amount", "asset_code", "from"],
+        optional: &["context", "issuer"],
+    },
+    TemplateSpec {
+        key: "clawback_claimable_balance.summary",
+        default: "The asset issuer clawed back claimable balance {balance_id}. {context}",
+        required: &["balance_id"],
+        optional: &["context", "issuer"],
+    },
+    TemplateSpec {
+        key: "account_merge.summary",
+        default: "{source} merged their account into {destination}, transferring all remaining XLM",
+        required: &["source", "destination"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "set_options.summary",
+        default: "{account} updated their account: {changes}",
+        required: &["changes"],
+        optional: &["account"],
+    },
+    TemplateSpec {
+        key: "set_options.no_changes",
+        default: "{account} submitted a set_options operation with no recognised changes.",
+        required: &[],
+        optional: &["account"],
+    },
+    TemplateSpec {
+        key: "claimable_balance.summary",
+        default: "Claimable balance {balance_id} holds {amount} {asset}.",
+        required: &["amount", "asset"],
+        optional: &["balance_id"],
+    },
+    TemplateSpec {
+        key: "claimable_balance.sponsor",
+        default: "Its reserve is sponsored by {sponsor}.",
+        required: &["sponsor"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "claimable_balance.claimant",
+        default: "{claimant} can claim {amount} {asset} {condition}.",
+        required: &["claimant", "condition"],
+        optional: &["amount", "asset"],
+    },
+    TemplateSpec {
+        key: "ledger.summary",
+        default: "Ledger #{sequence} closed on {closed_at} under protocol version {protocol_version}. It included {successful} successful and {failed} failed transactions, with {operations} operations.",
+        required: &["sequence"],
+        optional: &[
+            "closed_at",
+            "protocol_version",
+            "successful",
+            "failed",
+            "operations",
+        ],
+    },
+    TemplateSpec {
+        key: "ledger.fees",
+        default: "The base fee was {base_fee} XLM per operation and the base reserve {base_reserve} XLM.",
+        required: &["base_fee", "base_reserve"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "liquidity_pool.summary",
+        default: "Liquidity pool {pool_id} holds {reserves}. {shares} pool shares are held across {trustlines} accounts, and swaps pay a {fee} fee.",
+        required: &["reserves"],
+        optional: &["pool_id", "shares", "trustlines", "fee"],
+    },
+    TemplateSpec {
+        key: "liquidity_pool.deposit",
+        default: "{account} deposited {amounts} and received {shares} pool shares.",
+        required: &["amounts"],
+        optional: &["account", "shares"],
+    },
+    TemplateSpec {
+        key: "liquidity_pool.withdraw",
+        default: "{account} withdrew {amounts} by redeeming {shares} pool shares.",
+        required: &["amounts"],
+        optional: &["account", "shares"],
+    },
+    TemplateSpec {
+        key: "offer.open",
+        default: "{seller} is selling {amount} {selling_asset} for {buying_asset} at {price}.",
+        required: &["amount", "selling_asset", "buying_asset", "price"],
+        optional: &["seller"],
+    },
+    TemplateSpec {
+        key: "offer.partially_filled",
+        default: "{seller} is selling {amount} {selling_asset} for {buying_asset} at {price}, {filled_percent}% filled.",
+        required: &[
+            "amount",
+            "selling_asset",
+            "buying_asset",
+            "price",
+            "filled_percent",
+        ],
+        optional: &["seller", "remaining"],
+    },
+    TemplateSpec {
+        key: "offer.account_summary",
+        default: "{account} has {offers} on the Stellar DEX.",
+        required: &["offers"],
+        optional: &["account"],
+    },
+    TemplateSpec {
+        key: "asset_audit.summary",
+        default: "{asset} issued by {issuer}: overall risk is {risk}, from {findings}.",
+        required: &["asset", "risk", "findings"],
+        optional: &["issuer"],
+    },
+    TemplateSpec {
+        key: "asset_audit.clawback_enabled",
+        default: "The issuer can claw back {asset} from any holder's account.",
+        required: &["asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.clawback_disabled",
+        default: "Clawback is disabled, so the issuer cannot take back {asset} once it is sent.",
+        required: &["asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.auth_revocable",
+        default: "The issuer can freeze {asset} held by any account by revoking its authorization.",
+        required: &["asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.auth_required",
+        default: "Accounts must be approved by the issuer before they can hold {asset}.",
+        required: &["asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.auth_immutable",
+        default: "The issuer's authorization flags are locked and can never be changed.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.master_key_active",
+        default: "The issuer's master key is active (weight {weight}), so more {asset} can be issued at any time.",
+        required: &["asset"],
+        optional: &["weight"],
+    },
+    TemplateSpec {
+        key: "asset_audit.master_key_locked",
+        default: "The issuer's master key is disabled and no other signers remain, so no more {asset} can ever be issued.",
+        required: &["asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.master_key_delegated",
+        default: "The issuer's master key is disabled; the account is controlled by {signers}.",
+        required: &["signers"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.home_domain_missing",
+        default: "The issuer has no home domain, so {asset} cannot be linked to an organization.",
+        required: &["asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.toml_listed",
+        default: "{domain} publishes a stellar.toml that lists {asset}.",
+        required: &["domain", "asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.toml_unlisted",
+        default: "{domain} publishes a stellar.toml, but it does not list {asset} from this issuer.",
+        required: &["domain", "asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.toml_invalid",
+        default: "{domain} serves a stellar.toml that is not valid TOML.",
+        required: &["domain"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.toml_missing",
+        default: "{domain} does not publish a stellar.toml.",
+        required: &["domain"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.toml_unreachable",
+        default: "{domain} could not be reached over HTTPS, so its stellar.toml could not be checked.",
+        required: &["domain"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.issuer_verified",
+        default: "{domain} names the issuer as {name} and lists the issuing account in its stellar.toml.",
+        required: &["domain", "name"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.issuer_unverified",
+        default: "The issuer claims to be {name} through {domain}, but that domain's stellar.toml does not list the issuing account.",
+        required: &["domain", "name"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "asset_audit.recent_clawbacks",
+        default: "The issuer recently clawed back {asset} {count}, most recently {amount} {asset} from {from}{when}.",
+        required: &["asset", "count", "amount", "from"],
+        optional: &["when"],
+    },
+    TemplateSpec {
+        key: "memo.text",
+        default: "This transaction includes a text memo: \"{memo}\"",
+        required: &["memo"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "memo.id",
+        default: "This transaction includes an ID memo: {memo}. This is typically used as a reference number, customer ID, or invoice number.",
+        required: &["memo"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "memo.hash",
+        default: "This transaction includes a hash memo: {memo}. This is typically used to reference a document, contract, or other data.",
+        required: &["memo"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "memo.return",
+        default: "This transaction includes a return memo: {memo}. This indicates a refund or return transaction.",
+        required: &["memo"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "memo.binary",
+        default: "This transaction includes a text memo that holds binary data rather than readable text ({bytes} bytes): 0x{memo}. The recipient's software decodes it; it is not meant to be read.",
+        required: &["memo", "bytes"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "source.sequence",
+        default: "{account} submitted this transaction using sequence number {sequence}, so its next transaction must use the number after it.",
+        required: &["account", "sequence"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "source.channel",
+        default: "This transaction was submitted through channel account {channel}, which supplied the sequence number and paid the fee on behalf of {accounts}. Exchanges and payment services commonly use channel accounts to submit many transactions at once.",
+        required: &["channel", "accounts"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "source.fee_bump",
+        default: "{fee_account} paid the fee through a fee bump.",
+        required: &["fee_account"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "narrative.swap",
+        default: "{account} swapped {send_amount} {send_asset} for {dest_amount} {dest_asset} on the decentralized exchange.",
+        required: &[
+            "account",
+            "send_amount",
+            "send_asset",
+            "dest_amount",
+            "dest_asset",
+        ],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "narrative.swap.trust_added",
+        default: "It first opted in to hold {assets} so it could receive the proceeds.",
+        required: &["assets"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "narrative.swap.trust_removed",
+        default: "Afterwards it removed its {assets} trust line, which it no longer needed.",
+        required: &["assets"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "narrative.funding",
+        default: "{funder} funded a new account {account} with {starting_balance} XLM.",
+        required: &["funder", "account", "starting_balance"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "narrative.funding.trust_added",
+        default: "The new account opted in to hold {assets}.",
+        required: &["assets"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "narrative.funding.payments",
+        default: "{funder} then sent it {payments}.",
+        required: &["payments"],
+        optional: &["funder"],
+    },
+    TemplateSpec {
+        key: "fee.plain",
+        default: "A fee of {fee} XLM was charged.",
+        required: &["fee"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "fee.standard",
+        default: "A fee of {fee} XLM was charged. This is a standard network fee.",
+        required: &["fee"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "fee.elevated",
+        default: "A fee of {fee} XLM was charged. This is above average — {multiplier}x the base fee.",
+        required: &["fee"],
+        optional: &["multiplier"],
+    },
+    TemplateSpec {
+        key: "soroban.transfer",
+        default: "The {asset} Stellar Asset Contract transferred {amount} {asset} from {from} to {to}.",
+        required: &["amount", "asset", "from", "to"],
+        optional: &["contract"],
+    },
+    TemplateSpec {
+        key: "soroban.mint",
+        default: "The {asset} Stellar Asset Contract minted {amount} {asset} to {to}.",
+        required: &["amount", "asset", "to"],
+        optional: &["contract"],
+    },
+    TemplateSpec {
+        key: "soroban.burn",
+        default: "The {asset} Stellar Asset Contract burned {amount} {asset} from {from}.",
+        required: &["amount", "asset", "from"],
+        optional: &["contract"],
+    },
+    TemplateSpec {
+        key: "soroban.clawback",
+        default: "The {asset} Stellar Asset Contract clawed back {amount} {asset} from {from}.",
+        required: &["amount", "asset", "from"],
+        optional: &["contract"],
+    },
+    TemplateSpec {
+        key: "soroban.event",
+        default: "Contract {contract} emitted a \"{name}\" event.",
+        required: &["contract", "name"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "soroban.invoke",
+        default: "Called \"{function}\" on {contract}.",
+        required: &["contract", "function"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "soroban.runs_code",
+        default: "The call runs {code} code.",
+        required: &["code"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "soroban.deploy",
+        default: "Deployed a contract running {code}.",
+        required: &["code"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "soroban.deploy_asset",
+        default: "Deployed a Stellar Asset Contract.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "soroban.upload",
+        default: "Uploaded contract code {code}.",
+        required: &["code"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "slippage.worse",
+        default: "converted at {rate}, about {percent}% worse than the market rate",
+        required: &["rate", "percent"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "slippage.better",
+        default: "converted at {rate}, about {percent}% better than the market rate",
+        required: &["rate", "percent"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "slippage.in_line",
+        default: "converted at {rate}, in line with the market rate",
+        required: &["rate"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "fee_advice.summary",
+        default: "Set a max fee of {fee} stroops ({xlm} XLM) per operation to be included within {target}; the network is {congestion}.",
+        required: &["fee", "target"],
+        optional: &["xlm", "congestion"],
+    },
+    TemplateSpec {
+        key: "fee_advice.capacity",
+        default: "The latest ledger used {usage}% of its capacity.",
+        required: &["usage"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "fee_advice.no_surge",
+        default: "None of the last {scanned} ledgers were full, so none were surge priced.",
+        required: &["scanned"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "fee_advice.surge_history",
+        default: "{full} of the last {scanned} ledgers were full, and full ledgers are surge priced: the transactions that bid least wait for a later one.",
+        required: &["full", "scanned"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "fee_advice.base_fee",
+        default: "With room to spare, the base fee of {base_fee} stroops per operation is enough to be included in the next ledger.",
+        required: &["base_fee"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "fee_advice.percentile",
+        default: "To be included within {target}, bid at least the {percentile} percentile of fees charged recently, {fee} stroops per operation.",
+        required: &["target", "fee"],
+        optional: &["percentile"],
+    },
+    TemplateSpec {
+        key: "fee_advice.pay_charged",
+        default: "The max fee is a ceiling: a transaction pays only what its ledger charges, {base_fee} stroops per operation unless the ledger is surge priced.",
+        required: &["base_fee"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "market.traded",
+        default: "{pair} traded {base_volume} {base} for {counter_volume} {counter} in {trades} over {window}",
+        required: &["pair", "base_volume", "counter_volume", "window"],
+        optional: &["base", "counter", "trades"],
+    },
+    TemplateSpec {
+        key: "market.price_moved",
+        default: "the price moved {percent}%, from {open} to {close}",
+        required: &["percent", "close"],
+        optional: &["open"],
+    },
+    TemplateSpec {
+        key: "market.price_flat",
+        default: "the price held at {close}",
+        required: &["close"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "market.no_trades",
+        default: "{pair} did not trade over {window}.",
+        required: &["pair", "window"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.summary",
+        default: "This account holds {xlm} XLM. It has {signers}.",
+        required: &["xlm"],
+        optional: &["signers"],
+    },
+    TemplateSpec {
+        key: "account.summary_with_assets",
+        default: "This account holds {xlm} XLM and {assets}. It has {signers}.",
+        required: &["xlm", "assets"],
+        optional: &["signers"],
+    },
+    TemplateSpec {
+        key: "account.operator",
+        default: "Account operated by {name} ({domain}).",
+        required: &["name"],
+        optional: &["domain"],
+    },
+    TemplateSpec {
+        key: "account.operator_unlisted",
+        default: "Home domain {domain} names {name} as its operator, but its stellar.toml does not list this account.",
+        required: &["domain", "name"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.home_domain",
+        default: "Home domain: {domain}.",
+        required: &["domain"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.flag.auth_required",
+        default: "Auth required: accounts must be authorized before holding this asset.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.flag.auth_revocable",
+        default: "Auth revocable: the issuer can freeze this asset in a holder's account.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.flag.auth_immutable",
+        default: "Auth immutable: account flags and signers can no longer be changed.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.flag.clawback_enabled",
+        default: "Clawback enabled: the issuer can claw back this asset from holders.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.pool_shares",
+        default: "Holds {amount} shares of liquidity pool {pool}.",
+        required: &["amount", "pool"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.balance",
+        default: "Holds {amount} {asset}.",
+        required: &["amount", "asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.balance_issued",
+        default: "Holds {amount} {asset} issued by {issuer}.",
+        required: &["amount", "asset"],
+        optional: &["issuer"],
+    },
+    TemplateSpec {
+        key: "account.trust_limit",
+        default: "Trust limit: {limit} {asset}.",
+        required: &["limit"],
+        optional: &["asset"],
+    },
+    TemplateSpec {
+        key: "account.no_trust_limit",
+        default: "No trust limit set.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.trust_line_restricted",
+        default: "The issuer has restricted this trust line: existing offers remain, but no new funds can be received.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.trust_line_unauthorized",
+        default: "The issuer has not authorized this account to hold the asset.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.buying_liabilities",
+        default: "{amount} {asset} is reserved for open buy offers.",
+        required: &["amount", "asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "account.selling_liabilities",
+        default: "{amount} {asset} is locked in open sell offers.",
+        required: &["amount", "asset"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.transaction",
+        default: "Transaction failed: {explanation}",
+        required: &["explanation"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.tx_bad_seq",
+        default: "Sequence number is out of date — another transaction from this account may have been submitted first. Try again.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.tx_bad_auth",
+        default: "The transaction was not properly signed by the required keys.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.tx_insufficient_balance",
+        default: "The account does not have enough XLM to cover this transaction and the minimum balance.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.tx_no_account",
+        default: "The source account does not exist on the Stellar network.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.tx_insufficient_fee",
+        default: "The fee offered was too low.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.tx_too_early",
+        default: "The transaction was submitted before its minimum time boundary.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.tx_too_late",
+        default: "The transaction expired before it was processed.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.tx_missing_operation",
+        default: "The transaction contains no operations.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.tx_bad_auth_extra",
+        default: "The transaction has more signatures than required.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.tx_unknown",
+        default: "An unexpected transaction error occurred.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.op_no_trust",
+        default: "The destination account has not opted in to hold this asset.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.op_underfunded",
+        default: "The source account does not have enough of this asset to send.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.op_no_destination",
+        default: "The destination account does not exist on the Stellar network.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.op_not_authorized",
+        default: "The asset issuer has not authorised this account to hold the asset.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.op_line_full",
+        default: "The destination account's trust line is full and cannot receive more.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.op_no_issuer",
+        default: "The asset issuer account does not exist.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.op_low_reserve",
+        default: "The account would fall below the minimum XLM reserve after this operation.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "failure.op_unknown",
+        default: "An unexpected operation error occurred.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "timeline.valid_from",
+        default: "The transaction became valid for inclusion on {time}.",
+        required: &["time"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "timeline.outcome.success",
+        default: "and executed successfully",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "timeline.outcome.failed",
+        default: "but failed during execution",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "timeline.included",
+        default: "Included in ledger #{ledger}, which closed on {time}, {outcome}.",
+        required: &["outcome"],
+        optional: &["ledger", "time"],
+    },
+    TemplateSpec {
+        key: "timeline.included_ledger",
+        default: "Included in ledger #{ledger} {outcome}.",
+        required: &["outcome"],
+        optional: &["ledger"],
+    },
+    TemplateSpec {
+        key: "timeline.included_time",
+        default: "Included in a ledger that closed on {time}, {outcome}.",
+        required: &["outcome"],
+        optional: &["time"],
+    },
+    TemplateSpec {
+        key: "timeline.included_unknown",
+        default: "Included in a ledger {outcome}.",
+        required: &["outcome"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "timeline.inclusion_delay",
+        default: "This was {seconds} after it became valid.",
+        required: &["seconds"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "timeline.inclusion_delay_ledgers",
+        default: "This was {seconds} after it became valid, roughly {ledgers} at the current average close time of {average}s.",
+        required: &["seconds"],
+        optional: &["ledgers", "average"],
+    },
+    TemplateSpec {
+        key: "timeline.close_rate",
+        default: "Ledgers are currently closing every {average}s on average.",
+        required: &["average"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "timeline.valid_until",
+        default: "The transaction had to be included before {time}.",
+        required: &["time"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "timeline.valid_until_met",
+        default: "The transaction had to be included before {time}; it made it with {margin} to spare.",
+        required: &["time"],
+        optional: &["margin"],
+    },
+    TemplateSpec {
+        key: "timeline.fee_offered",
+        default: "The submitter offered up to {max_fee} XLM in fees and was charged {charged} XLM.",
+        required: &["charged"],
+        optional: &["max_fee"],
+    },
+    TemplateSpec {
+        key: "timeline.fee_charged",
+        default: "The submitter was charged {charged} XLM in fees.",
+        required: &["charged"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "timeline.fee_elevated",
+        default: "That is well above the base fee, which suggests the network was congested.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "timeline.fee_standard",
+        default: "That is a standard fee for current network conditions.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "compare.outcome",
+        default: "Transaction {succeeded} succeeded but transaction {failed} failed.",
+        required: &["succeeded", "failed"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "compare.operation_failed",
+        default: "Operation {index} failed: {reason}",
+        required: &["reason"],
+        optional: &["index"],
+    },
+    TemplateSpec {
+        key: "compare.operation_count",
+        default: "Transaction A has {operations_a} while transaction B has {operations_b}.",
+        required: &["operations_a", "operations_b"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "compare.operation_type",
+        default: "Operation {index} is a {type_a} in transaction A but a {type_b} in transaction B.",
+        required: &["type_a", "type_b"],
+        optional: &["index"],
+    },
+    TemplateSpec {
+        key: "compare.operation_field",
+        default: "{operation} {index} has a different {field}: {a} in transaction A versus {b} in transaction B.",
+        required: &["field", "a", "b"],
+        optional: &["operation", "index"],
+    },
+    TemplateSpec {
+        key: "compare.fee",
+        default: "Transaction A paid {fee_a} XLM in fees and transaction B paid {fee_b} XLM.",
+        required: &["fee_a", "fee_b"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "compare.memo_only_a",
+        default: "Transaction A has a memo but transaction B has none.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "compare.memo_only_b",
+        default: "Transaction B has a memo but transaction A has none.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "compare.memo_different",
+        default: "The two transactions carry different memos.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "compare.identical",
+        default: "The two transactions are equivalent: same outcome, operations, fees, and memo.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "compare.summary",
+        default: "Found {differences} between the two transactions. {first}",
+        required: &["differences"],
+        optional: &["first"],
+    },
+    TemplateSpec {
+        key: "anchor.deposit",
+        default: "This looks like a {asset} deposit through an anchor: the issuer sent {amount} {code} into the account.",
+        required: &["amount", "code"],
+        optional: &["asset"],
+    },
+    TemplateSpec {
+        key: "anchor.deposit_memo_id",
+        default: "The ID memo {id} likely references the deposit.",
+        required: &["id"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "anchor.deposit_memo",
+        default: "The memo likely references the deposit.",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "anchor.withdrawal",
+        default: "This looks like a {asset} withdrawal through an anchor: {amount} {code} was returned to the issuer with a memo identifying the request.",
+        required: &["amount", "code"],
+        optional: &["asset"],
+    },
+    TemplateSpec {
+        key: "anchor.withdrawal_converted",
+        default: "This looks like a {asset} withdrawal through an anchor: {amount} {code} was converted and sent to the issuer with a memo identifying the request.",
+        required: &["amount", "code"],
+        optional: &["asset"],
+    },
+    TemplateSpec {
+        key: "anchor.conversion",
+        default: "This looks like a conversion between anchor-issued assets: {send_code} was exchanged for {dest_code}.",
+        required: &["send_code", "dest_code"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "anchor.clawback",
+        default: "This looks like the {asset} anchor reclaiming {amount} {asset} from a holder under regulated asset rules.",
+        required: &["asset", "amount"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "signatures.summary",
+        default: "{signatures} for {accounts}: {outcomes}.",
+        required: &["outcomes"],
+        optional: &["signatures", "accounts"],
+    },
+    TemplateSpec {
+        key: "signatures.count.met_exactly",
+        default: "{count} met the threshold exactly",
+        required: &["count"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "signatures.count.met_with_surplus",
+        default: "{count} met it with surplus weight",
+        required: &["count"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "signatures.count.not_met",
+        default: "{count} did not meet it with current signers",
+        required: &["count"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "signatures.count.unknown",
+        default: "{count} could not be loaded",
+        required: &["count"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "signatures.unmatched",
+        default: "{signatures} matched none of their current signers.",
+        required: &["signatures"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "signatures.account_missing",
+        default: "{account} could not be loaded (it may have been merged), so its signatures are not matched.",
+        required: &["account"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "signatures.needed",
+        default: "{account} needed weight {required} (its {threshold} threshold)",
+        required: &["account", "required"],
+        optional: &["threshold"],
+    },
+    TemplateSpec {
+        key: "signatures.needed_fee_bump",
+        default: "{account} paid the fee bump and needed weight {required} (its low threshold)",
+        required: &["account", "required"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "signatures.met_exactly",
+        default: "{needed} and got exactly that from {signers}.",
+        required: &["needed"],
+        optional: &["signers"],
+    },
+    TemplateSpec {
+        key: "signatures.met_with_surplus",
+        default: "{needed} and got {signed} from {signers}, {surplus} more than needed.",
+        required: &["needed", "signed"],
+        optional: &["signers", "surplus"],
+    },
+    TemplateSpec {
+        key: "signatures.not_signed",
+        default: "{needed}, but none of its current signers signed. Its signers may have changed since.",
+        required: &["needed"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "signatures.not_met",
+        default: "{needed}, but its current signers that signed add up to only {signed}: {signers}. Its signers or thresholds may have changed since.",
+        required: &["needed", "signed"],
+        optional: &["signers"],
+    },
+    TemplateSpec {
+        key: "flags_history.master_key_disabled",
+        default: "the master key was disabled",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "flags_history.master_weight",
+        default: "the master key weight was set to {weight}",
+        required: &["weight"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "flags_history.threshold",
+        default: "the {threshold} threshold was set to {value}",
+        required: &["threshold", "value"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "flags_history.flag_enabled",
+        default: "{flags} was enabled",
+        required: &["flags"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "flags_history.flags_enabled",
+        default: "{flags} were enabled",
+        required: &["flags"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "flags_history.flag_disabled",
+        default: "{flags} was disabled",
+        required: &["flags"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "flags_history.flags_disabled",
+        default: "{flags} were disabled",
+        required: &["flags"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "flags_history.signer_removed",
+        default: "{signer} was removed",
+        required: &["signer"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "flags_history.signer_added",
+        default: "{signer} was added with weight {weight}",
+        required: &["signer"],
+        optional: &["weight"],
+    },
+    TemplateSpec {
+        key: "flags_history.signer_changed",
+        default: "{signer} was changed",
+        required: &["signer"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "flags_history.change",
+        default: "On {date} {changes}.",
+        required: &["changes"],
+        optional: &["date"],
+    },
+    TemplateSpec {
+        key: "flags_history.change_undated",
+        default: "{changes}.",
+        required: &["changes"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "flags_history.no_changes",
+        default: "No changes to flags, thresholds, or signers in the {scanned} operations scanned.",
+        required: &[],
+        optional: &["scanned"],
+    },
+    TemplateSpec {
+        key: "flags_history.summary",
+        default: "Found {changes} to flags, thresholds, or signers.",
+        required: &["changes"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "related_accounts.signs_for",
+        default: "This account is a signer on {account}.",
+        required: &["account"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "related_accounts.shared_signer",
+        default: "{signer} can sign for both accounts.",
+        required: &["signer"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "related_accounts.signed_by",
+        default: "{signer} is a signer on this account with weight {weight}.",
+        required: &["signer"],
+        optional: &["weight"],
+    },
+    TemplateSpec {
+        key: "related_accounts.sponsors_signer",
+        default: "{sponsor} pays the reserve for signer {signer} on this account.",
+        required: &["sponsor"],
+        optional: &["signer"],
+    },
+    TemplateSpec {
+        key: "related_accounts.sponsored_by",
+        default: "{sponsor} pays this account's base reserve.",
+        required: &["sponsor"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "related_accounts.sponsors",
+        default: "This account pays {account}'s base reserve.",
+        required: &["account"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "related_accounts.sponsors_entries",
+        default: "This account pays reserves for some of {account}'s signers, trustlines, offers, or data entries.",
+        required: &["account"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "related_accounts.none",
+        default: "No accounts share signers or sponsorship with {account}.",
+        required: &["account"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "related_accounts.summary",
+        default: "{account} is connected to {related} through signers or sponsorship.",
+        required: &["related"],
+        optional: &["account"],
+    },
+    TemplateSpec {
+        key: "send_check.no_problems",
+        default: "No problems found sending {sending} to {destination}.",
+        required: &["sending", "destination"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "send_check.summary",
+        default: "Found {problems} sending {sending} to {destination}.",
+        required: &["problems", "sending", "destination"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "send_check.flagged_destination",
+        default: "{destination} is on the spam blocklist.",
+        required: &["destination"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "send_check.flagged_destination_reason",
+        default: "{destination} is on the spam blocklist: {reason}.",
+        required: &["destination"],
+        optional: &["reason"],
+    },
+    TemplateSpec {
+        key: "send_check.flagged_asset",
+        default: "{code}:{issuer} is on the spam blocklist.",
+        required: &["code", "issuer"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "send_check.flagged_asset_reason",
+        default: "{code}:{issuer} is on the spam blocklist: {reason}.",
+        required: &["code", "issuer"],
+        optional: &["reason"],
+    },
+    TemplateSpec {
+        key: "send_check.unfunded_below_minimum",
+        default: "{destination} does not exist yet. Sending XLM to it creates it with a create_account operation, which needs at least {minimum} XLM; {sending} is not enough.",
+        required: &["destination", "minimum"],
+        optional: &["sending"],
+    },
+    TemplateSpec {
+        key: "send_check.unfunded",
+        default: "{destination} does not exist yet. A payment to it fails; send the {sending} with a create_account operation instead.",
+        required: &["destination"],
+        optional: &["sending"],
+    },
+    TemplateSpec {
+        key: "send_check.unfunded_asset",
+        default: "{destination} does not exist yet, so it cannot hold {code}. It needs to be created with at least {minimum} XLM and trust {code} first.",
+        required: &["destination", "code"],
+        optional: &["minimum"],
+    },
+    TemplateSpec {
+        key: "send_check.memo_required_by",
+        default: "{destination} belongs to {name}, which needs a memo to tell whose payment it is. Without one the funds may not be credited.",
+        required: &["destination"],
+        optional: &["name"],
+    },
+    TemplateSpec {
+        key: "send_check.memo_required",
+        default: "{destination} requires a memo on incoming payments, usually to tell which customer of an exchange they are for. Without one the funds may not be credited.",
+        required: &["destination"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "send_check.no_trustline",
+        default: "{destination} does not trust {code} from {issuer}, so a payment of it fails. The recipient needs to add a trustline first.",
+        required: &["destination", "code"],
+        optional: &["issuer"],
+    },
+    TemplateSpec {
+        key: "send_check.not_authorized",
+        default: "The issuer has not authorized {destination} to hold {code}, so a payment of it fails.",
+        required: &["destination", "code"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "send_check.limit_exceeded",
+        default: "{destination} can receive at most {headroom} more {code} before reaching its trustline limit, so a payment of {amount} fails.",
+        required: &["destination", "headroom", "code"],
+        optional: &["amount"],
+    },
+    TemplateSpec {
+        key: "send_check.clawback_enabled",
+        default: "The issuer of {code}, {issuer}, can claw it back: take it out of the recipient's account at any time.",
+        required: &["code"],
+        optional: &["issuer"],
+    },
+    TemplateSpec {
+        key: "bundle.swap",
+        default: "{a} and {b} swapped {amount_a} {asset_a} for {amount_b} {asset_b} across {transactions} transactions.",
+        required: &["a", "b", "amount_a", "asset_a", "amount_b", "asset_b"],
+        optional: &["transactions"],
+    },
+    TemplateSpec {
+        key: "bundle.flow",
+        default: "These {transactions} transactions moved {assets} between {accounts} accounts.",
+        required: &["assets"],
+        optional: &["transactions", "accounts"],
+    },
+    TemplateSpec {
+        key: "bundle.no_funds",
+        default: "These {transactions} transactions moved no funds.",
+        required: &[],
+        optional: &["transactions"],
+    },
+    TemplateSpec {
+        key: "bundle.failed",
+        default: "{failed} failed, so the flow did not complete; only the successful transactions are counted.",
+        required: &["failed"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "bundle.step.first",
+        default: "First, in {hash}: {summary}.",
+        required: &["summary"],
+        optional: &["hash"],
+    },
+    TemplateSpec {
+        key: "bundle.step.first_failed",
+        default: "First, in {hash} (failed): {summary}.",
+        required: &["summary"],
+        optional: &["hash"],
+    },
+    TemplateSpec {
+        key: "bundle.step.then",
+        default: "Then, in {hash}: {summary}.",
+        required: &["summary"],
+        optional: &["hash"],
+    },
+    TemplateSpec {
+        key: "bundle.step.then_failed",
+        default: "Then, in {hash} (failed): {summary}.",
+        required: &["summary"],
+        optional: &["hash"],
+    },
+    TemplateSpec {
+        key: "bundle.step.unexplained",
+        default: "its operations could not be explained",
+        required: &[],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "statement.unexplained_successful",
+        default: "Successful transaction with {operations}.",
+        required: &["operations"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "statement.unexplained_failed",
+        default: "Failed transaction with {operations}.",
+        required: &["operations"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "anomaly.large_payment",
+        default: "This payment of {amount} {code} is {ratio}x larger than this account's typical transfer of {typical} {code}.",
+        required: &["amount", "code"],
+        optional: &["ratio", "typical"],
+    },
+    TemplateSpec {
+        key: "anomaly.large_deposit",
+        default: "This deposit of {amount} {code} is {ratio}x larger than this account's typical transfer of {typical} {code}.",
+        required: &["amount", "code"],
+        optional: &["ratio", "typical"],
+    },
+    TemplateSpec {
+        key: "anomaly.new_counterparty",
+        default: "First payment to {counterparty} in this account's recent history.",
+        required: &["counterparty"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "anomaly.unusual_time",
+        default: "Made at {hour} UTC, outside the hours this account is usually active.",
+        required: &["hour"],
+        optional: &[],
+    },
+    TemplateSpec {
+        key: "payment_graph.edge",
+        default: "{from} sent {to} {amount} {asset} in {payments}.",
+        required: &["from", "to", "amount", "asset"],
+        optional: &["payments"],
+    },
+    TemplateSpec {
+        key: "payment_graph.summary",
+        default: "{flows} between {accounts} within {hops} of {account}.",
+        required: &["flows", "accounts"],
+        optional: &["hops", "account"],
+    },
+];
+
+/// Errors raised while loading a templates file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemplateError {
+    Io(String),
+    Parse(String),
+    UnknownTemplate(String),
+    NotAString(String),
+    UnknownPlaceholder { key: String, placeholder: String },
+    MissingPlaceholder { key: String, placeholder: String },
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::Io(msg) => write!(f, "could not read templates file: {msg}"),
+            TemplateError::Parse(msg) => write!(f, "invalid templates file: {msg}"),
+            TemplateError::UnknownTemplate(key) => write!(f, "unknown template `{key}`"),
+            TemplateError::NotAString(key) => write!(f, "template `{key}` must be a string"),
+            TemplateError::UnknownPlaceholder { key, placeholder } => {
+                write!(
+                    f,
+                    "template `{key}` uses unknown placeholder {{{placeholder}}}"
+                )
+            }
+            TemplateError::MissingPlaceholder { key, placeholder } => {
+                write!(
+                    f,
+                    "template `{key}` is missing required placeholder {{{placeholder}}}"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+/// The active set of explanation templates: built-in defaults plus any
+/// validated operator overrides.
+#[derive(Debug, Clone, Default)]
+pub struct Templates {
+    overrides: HashMap<String, String>,
+}
+
+impl Templates {
+    /// Parse and validate overrides from TOML. Nested tables are flattened
+    /// into dotted template names.
+    pub fn from_toml_str(source: &str) -> Result<Self, TemplateError> {
+        let table: toml::Table = source
+            .parse()
+            .map_err(|e: toml::de::Error| TemplateError::Parse(e.to_string()))?;
+
+        let mut flat = Vec::new();
+        flatten("", &toml::Value::Table(table), &mut flat)?;
+
+        let mut overrides = HashMap::new();
+        for (key, template) in flat {
+            let spec = spec_for(&key).ok_or_else(|| TemplateError::UnknownTemplate(key.clone()))?;
+            validate(spec, &template)?;
+            overrides.insert(key, template);
+        }
+        Ok(Self { overrides })
+    }
+
+    /// Load overrides from the file named by `EXPLAIN_TEMPLATES_PATH`.
+    /// Returns the defaults when the variable is unset.
+    pub fn from_env() -> Result<Self, TemplateError> {
+        match env::var("EXPLAIN_TEMPLATES_PATH") {
+            Ok(path) => {
+                let source = fs::read_to_string(&path)
+                    .map_err(|e| TemplateError::Io(format!("{path}: {e}")))?;
+                Self::from_toml_str(&source)
+            }
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    /// Number of templates overridden by the operator.
+    pub fn override_count(&self) -> usize {
+        self.overrides.len()
+    }
+
+    /// Render a template, substituting `{name}` placeholders from `args`.
+    pub fn render(&self, key: &str, args: &[(&str, &str)]) -> String {
+        let template = self
+            .overrides
+            .get(key)
+            .map(String::as_str)
+            .or_else(|| spec_for(key).map(|s| s.default))
+            .unwrap_or(key);
+        substitute(template, args)
+    }
+}
+
+static TEMPLATES: OnceLock<Templates> = OnceLock::new();
+
+/// Install the templates used by all explainers. Call once at startup;
+/// later calls are ignored.
+pub fn install(templates: Templates) {
+    let _ = TEMPLATES.set(templates);
+}
+
+/// Render a named template with the installed templates, falling back to
+/// the built-in defaults when none were installed.
+pub fn render(key: &str, args: &[(&str, &str)]) -> String {
+    match TEMPLATES.get() {
+        Some(templates) => templates.render(key, args),
+        None => substitute(spec_for(key).map(|s| s.default).unwrap_or(key), args),
+    }
+}
+
+fn spec_for(key: &str) -> Option<&'static TemplateSpec> {
+    SPECS.iter().find(|s| s.key == key)
+}
+
+fn flatten(
+    prefix: &str,
+    value: &toml::Value,
+    out: &mut Vec<(String, String)>,
+) -> Result<(), TemplateError> {
+    match value {
+        toml::Value::Table(table) => {
+            for (name, child) in table {
+                let key = if prefix.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{prefix}.{name}")
+                };
+                flatten(&key, child, out)?;
+            }
+            Ok(())
+        }
+        toml::Value::String(s) => {
+            out.push((prefix.to_string(), s.clone()));
+            Ok(())
+        }
+        _ => Err(TemplateError::NotAString(prefix.to_string())),
+    }
+}
+
+fn validate(spec: &TemplateSpec, template: &str) -> Result<(), TemplateError> {
+    let used = placeholders(template);
+    for placeholder in &used {
+        if !spec.required.contains(placeholder) && !spec.optional.contains(placeholder) {
+            return Err(TemplateError::UnknownPlaceholder {
+                key: spec.key.to_string(),
+                placeholder: placeholder.to_string(),
+            });
+        }
+    }
+    for required in spec.required {
+        if !used.contains(required) {
+            return Err(TemplateError::MissingPlaceholder {
+                key: spec.key.to_string(),
+                placeholder: required.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Names of the `{placeholder}`s used in a template.
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                names.push(&after[..end]);
+                rest = &after[end + 1..];
+            }
+            None => break,
+        }
+    }
+    names
+}
+
+fn substitute(template: &str, args: &[(&str, &str)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                let name = &after[..end];
+                match args.iter().find(|(k, _)| *k == name) {
+                    Some((_, value)) => out.push_str(value),
+                    None => {
+                        out.push('{');
+                        out.push_str(name);
+                        out.push('}');
+                    }
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_rendering() {
+        let templates = Templates::default();
+        let rendered = templates.render(
+            "payment.summary",
+            &[
+                ("from", "GA"),
+                ("amount", "5"),
+                ("asset", "XLM"),
+                ("to", "GB"),
+            ],
+        );
+        assert_eq!(rendered, "GA sent 5 XLM to GB");
+    }
+
+    #[test]
+    fn test_override_from_nested_table() {
+        let templates = Templates::from_toml_str(
+            "[payment]\nsummary = \"{amount} {asset} moved from {from} to {to}\"\n",
+        )
+        .unwrap();
+        assert_eq!(templates.override_count(), 1);
+        let rendered = templates.render(
+            "payment.summary",
+            &[
+                ("from", "GA"),
+                ("amount", "5"),
+                ("asset", "XLM"),
+                ("to", "GB"),
+            ],
+        );
+        assert_eq!(rendered, "5 XLM moved from GA to GB");
+    }
+
+    #[test]
+    fn test_missing_required_placeholder_rejected() {
+        let err =
+            Templates::from_toml_str("[payment]\nsummary = \"{from} paid {to}\"\n").unwrap_err();
+        assert_eq!(
+            err,
+            TemplateError::MissingPlaceholder {
+                key: "payment.summary".to_string(),
+                placeholder: "amount".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_placeholder_and_template_rejected() {
+        let err = Templates::from_toml_str(
+            "[payment]\nsummary = \"{amount} {asset} to {to} via {bridge}\"\n",
+        )
+        .unwrap_err();
+        assert!(matches!(err, TemplateError::UnknownPlaceholder { .. }));
+
+        let err = Templates::from_toml_str("[payment]\nheadline = \"x\"\n").unwrap_err();
+        assert_eq!(
+            err,
+            TemplateError::UnknownTemplate("payment.headline".to_string())
+        );
+    }
+
+    #[test]
+    fn test_every_default_satisfies_its_spec() {
+        for spec in SPECS {
+            assert!(validate(spec, spec.default).is_ok(), "{}", spec.key);
+        }
+    }
+
+    #[test]
+    fn test_unmatched_placeholder_left_intact() {
+        assert_eq!(substitute("a {x} b {y", &[("x", "1")]), "a 1 b {y");
+        assert_eq!(substitute("{z}", &[]), "{z}");
+    }
+}
@@ -0,0 +1,131 @@
+//! Absolute and relative timestamps.
+//!
+//! Explanations carry when a transaction's ledger closed both as ISO 8601 and
+//! as "3 hours ago". The relative form depends on when it is read, so it is
+//! filled in as a response is served rather than when the explanation is
+//! built or stored, from a [`Clock`] that tests can pin.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// Source of the current time.
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// The system's wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A clock stopped at one instant, for tests.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+/// Parse a Horizon timestamp, e.g. "2024-01-15T14:32:00Z".
+pub fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value.trim())
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// A timestamp as ISO 8601 in UTC, whatever offset it was written with.
+/// Returns None when `value` is not a timestamp.
+pub fn to_iso8601(value: &str) -> Option<String> {
+    parse_timestamp(value).map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, true))
+}
+
+/// How long before `now` the instant `then` was: "just now", "5 minutes ago",
+/// "1 day ago". A `then` more than a minute ahead of `now`, from clock skew
+/// between us and the network, reads "in 2 minutes".
+pub fn time_ago(then: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - then).num_seconds();
+    if seconds.abs() < 60 {
+        return "just now".to_string();
+    }
+    let span = describe_span(seconds.unsigned_abs());
+    if seconds < 0 {
+        format!("in {span}")
+    } else {
+        format!("{span} ago")
+    }
+}
+
+fn describe_span(seconds: u64) -> String {
+    const MINUTE: u64 = 60;
+    const HOUR: u64 = 60 * MINUTE;
+    const DAY: u64 = 24 * HOUR;
+    const MONTH: u64 = 30 * DAY;
+    const YEAR: u64 = 365 * DAY;
+
+    let (count, unit) = match seconds {
+        s if s < HOUR => (s / MINUTE, "minute"),
+        s if s < DAY => (s / HOUR, "hour"),
+        s if s < MONTH => (s / DAY, "day"),
+        s if s < YEAR => (s / MONTH, "month"),
+        s => (s / YEAR, "year"),
+    };
+    format!("{count} {unit}{}", if count == 1 { "" } else { "s" })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        parse_timestamp(value).unwrap()
+    }
+
+    #[test]
+    fn test_time_ago_units() {
+        let now = at("2024-06-15T12:00:00Z");
+        let cases = [
+            ("2024-06-15T11:59:30Z", "just now"),
+            ("2024-06-15T11:59:00Z", "1 minute ago"),
+            ("2024-06-15T11:15:00Z", "45 minutes ago"),
+            ("2024-06-15T09:00:00Z", "3 hours ago"),
+            ("2024-06-14T12:00:00Z", "1 day ago"),
+            ("2024-04-15T12:00:00Z", "2 months ago"),
+            ("2022-06-15T12:00:00Z", "2 years ago"),
+        ];
+        for (then, expected) in cases {
+            assert_eq!(time_ago(at(then), now), expected, "{then}");
+        }
+    }
+
+    #[test]
+    fn test_time_ago_future_from_clock_skew() {
+        let now = at("2024-06-15T12:00:00Z");
+        assert_eq!(time_ago(at("2024-06-15T12:00:20Z"), now), "just now");
+        assert_eq!(time_ago(at("2024-06-15T12:02:00Z"), now), "in 2 minutes");
+    }
+
+    #[test]
+    fn test_to_iso8601_normalizes_offsets() {
+        assert_eq!(
+            to_iso8601("2024-01-15T14:32:00Z").as_deref(),
+            Some("2024-01-15T14:32:00Z")
+        );
+        assert_eq!(
+            to_iso8601("2024-01-15T16:32:00+02:00").as_deref(),
+            Some("2024-01-15T14:32:00Z")
+        );
+        assert_eq!(to_iso8601("yesterday"), None);
+    }
+
+    #[test]
+    fn test_fixed_clock() {
+        let instant = at("2024-06-15T12:00:00Z");
+        assert_eq!(FixedClock(instant).now(), instant);
+    }
+}
@@ -0,0 +1,310 @@
+//! Transaction lifecycle timeline.
+//!
+//! Turns the timing fields Horizon records for a transaction into ordered,
+//! narrated steps: when it became valid, which ledger included it, how its
+//! fee compared to the network, and how close it came to expiring.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::explain::format::{count, format_amount};
+use crate::explain::templates;
+use crate::explain::time::parse_timestamp;
+use crate::explain::transaction::format_ledger_time;
+use crate::models::fee::FeeStats;
+
+/// Timing and fee data gathered from Horizon for a single transaction.
+#[derive(Debug, Clone, Default)]
+pub struct TimelineInput {
+    pub transaction_hash: String,
+    pub successful: bool,
+    pub ledger: Option<u64>,
+    /// ISO 8601 close time of the including ledger.
+    pub ledger_closed_at: Option<String>,
+    /// Lower time bound. Horizon reports the Unix epoch when unset.
+    pub valid_after: Option<String>,
+    /// Upper time bound, if the transaction had one.
+    pub valid_before: Option<String>,
+    pub fee_charged: u64,
+    /// The maximum fee the submitter was willing to pay, in stroops.
+    pub max_fee: Option<u64>,
+    /// Average seconds between recent ledger closes.
+    pub average_ledger_close_seconds: Option<f64>,
+}
+
+/// One step in a transaction's lifecycle.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TimelineStep {
+    /// Machine-readable step name: "valid_from", "included", "fee", "valid_until".
+    pub step: String,
+    /// ISO 8601 timestamp for this step, when known.
+    pub timestamp: Option<String>,
+    /// Plain-English description of what happened at this step.
+    pub narrative: String,
+}
+
+/// Ordered lifecycle of a transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema)]
+pub struct TransactionTimeline {
+    pub transaction_hash: String,
+    pub ledger: Option<u64>,
+    /// Steps in chronological order.
+    pub steps: Vec<TimelineStep>,
+    /// Seconds between the start of the validity window and ledger close,
+    /// when the transaction set a lower time bound.
+    pub inclusion_seconds: Option<i64>,
+    /// Recent average ledger close interval on the network.
+    pub average_ledger_close_seconds: Option<f64>,
+    /// "standard" or "elevated", when network fee stats were available.
+    pub fee_priority: Option<String>,
+}
+
+/// Build the lifecycle timeline for a transaction.
+pub fn build_timeline(input: &TimelineInput, fee_stats: Option<&FeeStats>) -> TransactionTimeline {
+    let closed_at = input.ledger_closed_at.as_deref().and_then(parse_timestamp);
+    let valid_after = input
+        .valid_after
+        .as_deref()
+        .and_then(parse_timestamp)
+        .filter(|t| t.timestamp() > 0);
+    let valid_before = input
+        .valid_before
+        .as_deref()
+        .and_then(parse_timestamp)
+        .filter(|t| t.timestamp() > 0);
+
+    let mut steps = Vec::new();
+
+    if let Some(start) = valid_after {
+        steps.push(TimelineStep {
+            step: "valid_from".to_string(),
+            timestamp: input.valid_after.clone(),
+            narrative: templates::render(
+                "timeline.valid_from",
+                &[("time", &format_ledger_time(&to_iso(start)))],
+            ),
+        });
+    }
+
+    let inclusion_seconds = match (valid_after, closed_at) {
+        (Some(start), Some(closed)) => Some((closed - start).num_seconds()),
+        _ => None,
+    };
+
+    let outcome = templates::render(
+        if input.successful {
+            "timeline.outcome.success"
+        } else {
+            "timeline.outcome.failed"
+        },
+        &[],
+    );
+    let ledger = input.ledger.map(|seq| seq.to_string());
+    let closed = input.ledger_closed_at.as_deref().map(format_ledger_time);
+    let mut included = match (&ledger, &closed) {
+        (Some(ledger), Some(closed)) => templates::render(
+            "timeline.included",
+            &[("ledger", ledger), ("time", closed), ("outcome", &outcome)],
+        ),
+        (Some(ledger), None) => templates::render(
+            "timeline.included_ledger",
+            &[("ledger", ledger), ("outcome", &outcome)],
+        ),
+        (None, Some(closed)) => templates::render(
+            "timeline.included_time",
+            &[("time", closed), ("outcome", &outcome)],
+        ),
+        (None, None) => templates::render("timeline.included_unknown", &[("outcome", &outcome)]),
+    };
+    let delay = match (inclusion_seconds, input.average_ledger_close_seconds) {
+        (Some(secs), Some(avg)) if avg > 0.0 => {
+            let ledgers = (secs as f64 / avg).ceil().max(1.0) as usize;
+            Some(templates::render(
+                "timeline.inclusion_delay_ledgers",
+                &[
+                    ("seconds", &count(secs.max(0) as usize, "second", "seconds")),
+                    ("ledgers", &count(ledgers, "ledger", "ledgers")),
+                    ("average", &format!("{avg:.1}")),
+                ],
+            ))
+        }
+        (Some(secs), _) => Some(templates::render(
+            "timeline.inclusion_delay",
+            &[("seconds", &count(secs.max(0) as usize, "second", "seconds"))],
+        )),
+        (None, Some(avg)) => Some(templates::render(
+            "timeline.close_rate",
+            &[("average", &format!("{avg:.1}"))],
+        )),
+        (None, None) => None,
+    };
+    if let Some(delay) = delay {
+        included.push(' ');
+        included.push_str(&delay);
+    }
+    steps.push(TimelineStep {
+        step: "included".to_string(),
+        timestamp: input.ledger_closed_at.clone(),
+        narrative: included,
+    });
+
+    let fee_priority = fee_stats.map(|stats| {
+        if stats.is_high_fee(input.fee_charged) {
+            "elevated".to_string()
+        } else {
+            "standard".to_string()
+        }
+    });
+    steps.push(TimelineStep {
+        step: "fee".to_string(),
+        timestamp: input.ledger_closed_at.clone(),
+        narrative: fee_narrative(input.fee_charged, input.max_fee, fee_stats),
+    });
+
+    if let Some(deadline) = valid_before {
+        let time = format_ledger_time(&to_iso(deadline));
+        let narrative = match closed_at {
+            Some(closed) if closed <= deadline => {
+                let margin = (deadline - closed).num_seconds() as usize;
+                templates::render(
+                    "timeline.valid_until_met",
+                    &[
+                        ("time", &time),
+                        ("margin", &count(margin, "second", "seconds")),
+                    ],
+                )
+            }
+            _ => templates::render("timeline.valid_until", &[("time", &time)]),
+        };
+        steps.push(TimelineStep {
+            step: "valid_until".to_string(),
+            timestamp: input.valid_before.clone(),
+            narrative,
+        });
+    }
+
+    TransactionTimeline {
+        transaction_hash: input.transaction_hash.clone(),
+        ledger: input.ledger,
+        steps,
+        inclusion_seconds,
+        average_ledger_close_seconds: input.average_ledger_close_seconds,
+        fee_priority,
+    }
+}
+
+fn fee_narrative(fee_charged: u64, max_fee: Option<u64>, fee_stats: Option<&FeeStats>) -> String {
+    let charged = format_amount(&FeeStats::stroops_to_xlm(fee_charged));
+    let mut narrative = match max_fee {
+        Some(max) if max > fee_charged => templates::render(
+            "timeline.fee_offered",
+            &[
+                ("max_fee", &format_amount(&FeeStats::stroops_to_xlm(max))),
+                ("charged", &charged),
+            ],
+        ),
+        _ => templates::render("timeline.fee_charged", &[("charged", &charged)]),
+    };
+    if let Some(stats) = fee_stats {
+        let key = if stats.is_high_fee(fee_charged) {
+            "timeline.fee_elevated"
+        } else {
+            "timeline.fee_standard"
+        };
+        narrative.push(' ');
+        narrative.push_str(&templates::render(key, &[]));
+    }
+    narrative
+}
+
+fn to_iso(t: DateTime<Utc>) -> String {
+    t.format("%Y-%m-%dT%H:%M:%SZ").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_input() -> TimelineInput {
+        TimelineInput {
+            transaction_hash: "abc".to_string(),
+            successful: true,
+            ledger: Some(500),
+            ledger_closed_at: Some("2024-01-15T14:32:10Z".to_string()),
+            valid_after: Some("1970-01-01T00:00:00Z".to_string()),
+            valid_before: None,
+            fee_charged: 100,
+            max_fee: Some(100),
+            average_ledger_close_seconds: Some(5.0),
+        }
+    }
+
+    #[test]
+    fn test_epoch_valid_after_is_treated_as_unset() {
+        let timeline = build_timeline(&base_input(), None);
+        assert!(timeline.steps.iter().all(|s| s.step != "valid_from"));
+        assert_eq!(timeline.inclusion_seconds, None);
+        assert_eq!(timeline.steps[0].step, "included");
+        assert!(timeline.steps[0].narrative.contains("ledger #500"));
+        assert!(timeline.steps[0].narrative.contains("every 5.0s"));
+    }
+
+    #[test]
+    fn test_inclusion_latency_from_lower_time_bound() {
+        let input = TimelineInput {
+            valid_after: Some("2024-01-15T14:32:00Z".to_string()),
+            ..base_input()
+        };
+        let timeline = build_timeline(&input, None);
+        assert_eq!(timeline.steps[0].step, "valid_from");
+        assert_eq!(timeline.inclusion_seconds, Some(10));
+        assert!(timeline.steps[1].narrative.contains("10 seconds after"));
+        assert!(timeline.steps[1].narrative.contains("roughly 2 ledgers"));
+    }
+
+    #[test]
+    fn test_deadline_margin() {
+        let input = TimelineInput {
+            valid_before: Some("2024-01-15T14:33:00Z".to_string()),
+            ..base_input()
+        };
+        let timeline = build_timeline(&input, None);
+        let last = timeline.steps.last().unwrap();
+        assert_eq!(last.step, "valid_until");
+        assert!(last.narrative.contains("50 seconds to spare"));
+    }
+
+    #[test]
+    fn test_fee_priority_with_stats() {
+        let stats = FeeStats::new(100, 100, 5000, 100, 250);
+        let standard = build_timeline(&base_input(), Some(&stats));
+        assert_eq!(standard.fee_priority.as_deref(), Some("standard"));
+
+        let input = TimelineInput {
+            fee_charged: 10_000,
+            max_fee: Some(20_000),
+            ..base_input()
+        };
+        let elevated = build_timeline(&input, Some(&stats));
+        assert_eq!(elevated.fee_priority.as_deref(), Some("elevated"));
+        let fee_step = elevated.steps.iter().find(|s| s.step == "fee").unwrap();
+        assert!(fee_step.narrative.contains("offered up to 0.002 XLM"));
+        assert!(fee_step.narrative.contains("congested"));
+    }
+
+    #[test]
+    fn test_failed_transaction_narrative() {
+        let input = TimelineInput {
+            successful: false,
+            ..base_input()
+        };
+        let timeline = build_timeline(&input, None);
+        assert!(
+            timeline.steps[0]
+                .narrative
+                .contains("failed during execution")
+        );
+        assert_eq!(timeline.fee_priority, None);
+    }
+}
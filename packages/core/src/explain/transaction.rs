@@ -1,10 +1,27 @@
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::explain::anchor::detect_anchor_pattern;
+use crate::explain::doc_links::learn_more;
 use crate::explain::failure::{OperationFailure, explain_failure};
+use crate::explain::format::format_amount;
+use crate::explain::glossary::{GlossaryTerm, terms_in};
+use crate::explain::issuer_label::IssuerLabel;
+use crate::explain::ledger::LedgerContext;
 use crate::explain::memo::explain_memo;
+use crate::explain::narrative::compose_narrative;
+use crate::explain::soroban::{
+    ContractCallExplanation, ContractEventExplanation, explain_contract_call,
+    explain_contract_event,
+};
+use crate::explain::source::explain_source;
+use crate::explain::templates;
+use crate::explain::time::{parse_timestamp, time_ago, to_iso8601};
 use crate::models::fee::FeeStats;
 use crate::models::operation::{OfferType, Operation, PathPaymentType};
 use crate::models::transaction::Transaction;
+use crate::services::memo_registry::memo_required_by;
 
 use super::operation::account_merge::explain_account_merge;
 use super::operation::change_trust::explain_change_trust;
@@ -16,13 +33,16 @@ use super::operation::payment::{PaymentExplanation, explain_payment, explain_pay
 use super::operation::set_options::explain_set_options;
 
 /// A single explained operation within a transaction, in original order.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct OperationExplanation {
     /// Position of this operation within the transaction (0-based).
     pub index: usize,
     /// The Stellar operation type, e.g. "payment", "create_account".
     #[serde(rename = "type")]
     pub operation_type: String,
+    /// Stellar docs for this operation type; null for types without a link.
+    #[serde(default)]
+    pub learn_more: Option<String>,
     /// Plain-English summary of what this operation did.
     pub summary: String,
     /// Structured, type-specific details for this operation.
@@ -30,7 +50,7 @@ pub struct OperationExplanation {
 }
 
 /// Complete explanation of a transaction.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 pub struct TransactionExplanation {
     pub transaction_hash: String,
     pub successful: bool,
@@ -42,18 +62,164 @@ pub struct TransactionExplanation {
     pub payment_explanations: Vec<PaymentExplanation>,
     /// Count of operations whose type is genuinely unsupported (i.e. mapped to "Other").
     pub skipped_operations: usize,
+    /// Explained contract events, for Soroban transactions.
+    pub contract_events: Vec<ContractEventExplanation>,
+    /// The contract a Soroban transaction called, deployed, or uploaded,
+    /// with well-known contracts and code named.
+    #[serde(default)]
+    pub contract_call: Option<ContractCallExplanation>,
+    /// High-level interpretation when the transaction matches a common anchor
+    /// deposit/withdrawal pattern, e.g. "This looks like a USD withdrawal through an anchor".
+    pub anchor_interpretation: Option<String>,
+    /// One-paragraph story of a multi-operation transaction that matches a
+    /// common pattern such as a swap or account funding.
+    #[serde(default)]
+    pub narrative: Option<String>,
     /// Human-readable explanation of the transaction memo.
     pub memo_explanation: Option<String>,
+    /// Who submitted the transaction, with its sequence number, and whether
+    /// it went through a channel account.
+    #[serde(default)]
+    pub source_explanation: Option<String>,
+    /// The transaction's source account when it only served as a channel
+    /// account for other accounts' operations.
+    #[serde(default)]
+    pub channel_account: Option<String>,
+    /// Accounts that acted in the transaction without being named in an
+    /// operation's details: its source account, a fee bump payer, and the
+    /// operations' source accounts, in that order without repeats.
+    #[serde(default)]
+    pub source_accounts: Vec<String>,
     /// Human-readable explanation of transaction fee context.
     pub fee_explanation: Option<String>,
     /// ISO 8601 timestamp of when the ledger closed (from Horizon).
     pub ledger_closed_at: Option<String>,
     /// Ledger sequence number this transaction was included in.
     pub ledger: Option<u64>,
+    /// When the including ledger closed, as ISO 8601 in UTC.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// How long ago the including ledger closed, e.g. "3 hours ago", as of
+    /// when this response was served.
+    #[serde(default)]
+    pub created_at_relative: Option<String>,
+    /// The including ledger: its protocol version, a link to its
+    /// explanation, and how long inclusion took when known.
+    #[serde(default)]
+    pub ledger_context: Option<LedgerContext>,
     /// Plain-English reason the transaction failed, or null for successful transactions.
     pub failure_reason: Option<String>,
     /// Per-operation failure details when individual operations carry error codes.
     pub operation_failures: Vec<OperationFailure>,
+    /// What this explanation is missing because part of it could not be
+    /// fetched, and problems with the transaction itself, such as a missing
+    /// memo. Empty when there are none.
+    #[serde(default)]
+    pub warnings: Vec<ExplanationWarning>,
+    /// Stellar terms the summaries above mention, with definitions, in the
+    /// order they first appear.
+    #[serde(default)]
+    pub terms: Vec<GlossaryTerm>,
+    /// Who issued the assets the payments moved, as named by each issuer's
+    /// home domain, marked "verified" when the domain's stellar.toml lists
+    /// the issuer and "claimed" when it does not.
+    #[serde(default)]
+    pub issuers: Vec<IssuerLabel>,
+    /// The explanation rewritten as one paragraph by the configured
+    /// summarizer. Null when none is configured or it did not answer in
+    /// time; the fields above are always present either way.
+    #[serde(default)]
+    pub generated_summary: Option<String>,
+}
+
+/// Something a reader should know before trusting the explanation: a part
+/// that is missing because an upstream fetch failed, or an asset the
+/// deployment lists as a scam.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+pub struct ExplanationWarning {
+    pub code: WarningCode,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WarningCode {
+    /// The operations could not be fetched, so `operations` is empty.
+    OperationsUnavailable,
+    /// Network fee stats could not be fetched, so the fee is not compared
+    /// with what other transactions paid.
+    FeeStatsUnavailable,
+    /// The transaction moves an asset on the deployment's spam blocklist.
+    BlocklistedAsset,
+    /// The transaction pays an account known to need a memo, without one.
+    MemoMissing,
+}
+
+impl ExplanationWarning {
+    pub fn operations_unavailable() -> Self {
+        Self {
+            code: WarningCode::OperationsUnavailable,
+            message:
+                "The operations in this transaction could not be loaded, so they are not explained."
+                    .to_string(),
+        }
+    }
+
+    pub fn fee_stats_unavailable() -> Self {
+        Self {
+            code: WarningCode::FeeStatsUnavailable,
+            message: "Network fee stats could not be loaded, so the fee is not compared with what other transactions paid.".to_string(),
+        }
+    }
+
+    /// `asset` is "CODE:ISSUER".
+    pub fn blocklisted_asset(asset: &str, reason: Option<&str>) -> Self {
+        let message = match reason {
+            Some(reason) => format!("{asset} is on the spam blocklist: {reason}."),
+            None => format!("{asset} is on the spam blocklist."),
+        };
+        Self {
+            code: WarningCode::BlocklistedAsset,
+            message,
+        }
+    }
+
+    /// `destination` is run by `name` and credits payments by memo.
+    pub fn memo_missing(destination: &str, name: &str) -> Self {
+        Self {
+            code: WarningCode::MemoMissing,
+            message: format!(
+                "{destination} belongs to {name}, which needs a memo to tell whose payment it is. This transaction has none, so the funds may not have been credited to anyone."
+            ),
+        }
+    }
+}
+
+impl TransactionExplanation {
+    /// Fill in `created_at_relative` as of `now`. Done when a response is
+    /// served, so stored explanations do not carry a stale "ago".
+    pub fn with_relative_time(mut self, now: DateTime<Utc>) -> Self {
+        self.created_at_relative = self
+            .created_at
+            .as_deref()
+            .and_then(parse_timestamp)
+            .map(|then| time_ago(then, now));
+        self
+    }
+
+    /// Fill in the inclusion latency for a transaction submitted at
+    /// `submitted_at`. Ledger close times are whole seconds, so a
+    /// transaction included in the same second reads as zero.
+    pub fn with_submission_time(mut self, submitted_at: DateTime<Utc>) -> Self {
+        if let Some(context) = self.ledger_context.as_mut() {
+            context.inclusion_latency_seconds = context
+                .closed_at
+                .as_deref()
+                .and_then(parse_timestamp)
+                .map(|closed| (closed - submitted_at).num_seconds().max(0));
+        }
+        self
+    }
 }
 
 pub type ExplainResult = Result<TransactionExplanation, ExplainError>;
@@ -99,7 +265,11 @@ pub fn format_ledger_time(iso_string: &str) -> String {
         &time_part[..z_pos]
     } else if let Some(plus_pos) = time_part.find('+') {
         &time_part[..plus_pos]
-    } else if let Some(minus_pos) = time_part[1..].find('-').map(|p| p + 1) {
+    } else if let Some(minus_pos) = time_part
+        .get(1..)
+        .and_then(|rest| rest.find('-'))
+        .map(|p| p + 1)
+    {
         // Only strip trailing timezone offset (not the date hyphens)
         // time_part looks like "14:32:00-05:00"
         &time_part[..minus_pos]
@@ -108,24 +278,25 @@ pub fn format_ledger_time(iso_string: &str) -> String {
     };
 
     // Take only HH:MM (drop seconds)
-    let hhmm = if time.len() >= 5 { &time[..5] } else { time };
+    let hhmm = time.get(..5).unwrap_or(time);
 
     format!("{date} at {hhmm} UTC")
 }
 
 /// Produce a plain-English fee explanation.
 pub fn explain_fee(fee_charged: u64, fee_stats: Option<&FeeStats>) -> String {
-    let xlm = FeeStats::stroops_to_xlm(fee_charged);
+    let xlm = format_amount(&FeeStats::stroops_to_xlm(fee_charged));
     match fee_stats {
-        None => format!("A fee of {xlm} XLM was charged."),
+        None => templates::render("fee.plain", &[("fee", &xlm)]),
         Some(stats) => {
             if stats.is_high_fee(fee_charged) {
                 let multiplier = fee_charged / stats.base_fee.max(1);
-                format!(
-                    "A fee of {xlm} XLM was charged. This is above average — {multiplier}x the base fee."
+                templates::render(
+                    "fee.elevated",
+                    &[("fee", &xlm), ("multiplier", &multiplier.to_string())],
                 )
             } else {
-                format!("A fee of {xlm} XLM was charged. This is a standard network fee.")
+                templates::render("fee.standard", &[("fee", &xlm)])
             }
         }
     }
@@ -145,13 +316,70 @@ pub fn explain_transaction_with_ledger(
     created_at: Option<&str>,
     ledger: Option<u64>,
 ) -> ExplainResult {
-    let total_operations = transaction.operations.len();
-
-    if total_operations == 0 {
+    if transaction.operations.is_empty() {
         return Err(ExplainError::EmptyTransaction);
     }
 
-    let payment_count = transaction.payment_count();
+    let skipped_operations = transaction
+        .operations
+        .iter()
+        .filter(|op| matches!(op, Operation::Other(_)))
+        .count();
+    let base_summary = build_transaction_summary(
+        transaction.successful,
+        transaction.payment_count(),
+        skipped_operations,
+    );
+
+    Ok(build_explanation(
+        transaction,
+        fee_stats,
+        created_at,
+        ledger,
+        base_summary,
+    ))
+}
+
+/// Explain a transaction whose operations could not be fetched, from what
+/// the transaction itself records: status, fee, memo, and ledger.
+pub fn explain_transaction_without_operations(
+    transaction: &Transaction,
+    fee_stats: Option<&FeeStats>,
+    created_at: Option<&str>,
+    ledger: Option<u64>,
+) -> TransactionExplanation {
+    let status = if transaction.successful {
+        "successful"
+    } else {
+        "failed"
+    };
+    let base_summary = format!("This {status} transaction's operations could not be loaded.");
+
+    let mut explanation = build_explanation(
+        &Transaction {
+            operations: Vec::new(),
+            ..transaction.clone()
+        },
+        fee_stats,
+        created_at,
+        ledger,
+        base_summary,
+    );
+    explanation
+        .warnings
+        .push(ExplanationWarning::operations_unavailable());
+    explanation
+}
+
+fn build_explanation(
+    transaction: &Transaction,
+    fee_stats: Option<&FeeStats>,
+    created_at: Option<&str>,
+    ledger: Option<u64>,
+    base_summary: String,
+) -> TransactionExplanation {
+    let created_at = created_at.or(transaction.created_at.as_deref());
+    let ledger = ledger.or(transaction.ledger);
 
     let payment_explanations = transaction
         .payment_operations()
@@ -175,9 +403,6 @@ pub fn explain_transaction_with_ledger(
         .filter(|op| matches!(op, Operation::Other(_)))
         .count();
 
-    let base_summary =
-        build_transaction_summary(transaction.successful, payment_count, skipped_operations);
-
     // Enrich summary with ledger time if available
     let summary = match (created_at, ledger) {
         (Some(ts), Some(seq)) => {
@@ -196,7 +421,15 @@ pub fn explain_transaction_with_ledger(
         (None, None) => base_summary,
     };
 
+    let contract_events = transaction
+        .contract_events
+        .iter()
+        .map(explain_contract_event)
+        .collect();
+    let anchor_interpretation = detect_anchor_pattern(transaction);
+    let narrative = compose_narrative(transaction);
     let memo_explanation = transaction.memo.as_ref().and_then(explain_memo);
+    let source = transaction.source.as_ref().and_then(explain_source);
     let fee_explanation = Some(explain_fee(transaction.fee_charged, fee_stats));
 
     let (failure_reason, operation_failures) = if transaction.is_failed() {
@@ -208,25 +441,100 @@ pub fn explain_transaction_with_ledger(
         (None, vec![])
     };
 
-    Ok(TransactionExplanation {
+    let mut explanation = TransactionExplanation {
         transaction_hash: transaction.hash.clone(),
         successful: transaction.successful,
         summary,
         operations,
         payment_explanations,
         skipped_operations,
+        contract_events,
+        contract_call: transaction
+            .contract_call
+            .as_ref()
+            .map(explain_contract_call),
+        anchor_interpretation,
+        narrative,
         memo_explanation,
+        source_explanation: source.as_ref().map(|s| s.summary.clone()),
+        channel_account: source.and_then(|s| s.channel_account),
+        source_accounts: source_accounts(transaction),
         fee_explanation,
         ledger_closed_at: created_at.map(|s| s.to_string()),
         ledger,
+        created_at: created_at.and_then(to_iso8601),
+        created_at_relative: None,
+        ledger_context: ledger.map(|seq| LedgerContext::new(seq, created_at)),
         failure_reason,
         operation_failures,
-    })
+        warnings: missing_memo_warnings(transaction),
+        terms: Vec::new(),
+        issuers: Vec::new(),
+        generated_summary: None,
+    };
+    explanation.terms = terms_in(
+        std::iter::once(explanation.summary.as_str())
+            .chain(explanation.operations.iter().map(|op| op.summary.as_str()))
+            .chain(
+                [
+                    &explanation.narrative,
+                    &explanation.anchor_interpretation,
+                    &explanation.memo_explanation,
+                    &explanation.source_explanation,
+                    &explanation.fee_explanation,
+                    &explanation.failure_reason,
+                ]
+                .into_iter()
+                .filter_map(|text| text.as_deref()),
+            ),
+    );
+    explanation
+}
+
+/// A warning for each account in the memo registry that `transaction` paid
+/// without a memo.
+fn missing_memo_warnings(transaction: &Transaction) -> Vec<ExplanationWarning> {
+    if !transaction.successful || transaction.memo.as_ref().is_some_and(|m| !m.is_none()) {
+        return Vec::new();
+    }
+    let mut paid: Vec<&str> = Vec::new();
+    for op in &transaction.operations {
+        let destination = match op {
+            Operation::Payment(p) => p.destination.as_str(),
+            Operation::PathPayment(p) => p.destination.as_str(),
+            _ => continue,
+        };
+        if !paid.contains(&destination) {
+            paid.push(destination);
+        }
+    }
+    paid.into_iter()
+        .filter_map(|destination| {
+            memo_required_by(destination)
+                .map(|name| ExplanationWarning::memo_missing(destination, name))
+        })
+        .collect()
+}
+
+/// The transaction's source, fee, and operation source accounts, deduplicated.
+fn source_accounts(transaction: &Transaction) -> Vec<String> {
+    let mut accounts: Vec<String> = Vec::new();
+    if let Some(source) = &transaction.source {
+        let all = std::iter::once(&source.account)
+            .chain(&source.fee_account)
+            .chain(&source.operation_accounts);
+        for account in all {
+            if !accounts.contains(account) {
+                accounts.push(account.clone());
+            }
+        }
+    }
+    accounts
 }
 
 /// Build the structured explanation for a single operation, preserving its
 /// position within the transaction.
-fn explain_operation(
+pub(crate) fn explain_operation(
     index: usize,
     op: &Operation,
     fee_charged: u64,
@@ -241,6 +549,7 @@ fn explain_operation(
             OperationExplanation {
                 index,
                 operation_type: "payment".to_string(),
+                learn_more: learn_more("payment"),
                 summary: explanation.summary.clone(),
                 details: serde_json::json!({
                     "from": explanation.from,
@@ -255,6 +564,7 @@ fn explain_operation(
             OperationExplanation {
                 index,
                 operation_type: "create_account".to_string(),
+                learn_more: learn_more("create_account"),
                 summary: explanation.summary.clone(),
                 details: serde_json::json!({
                     "funder": explanation.funder,
@@ -268,6 +578,7 @@ fn explain_operation(
             OperationExplanation {
                 index,
                 operation_type: "change_trust".to_string(),
+                learn_more: learn_more("change_trust"),
                 summary: explanation.summary.clone(),
                 details: serde_json::json!({
                     "account": explanation.trustor,
@@ -283,6 +594,7 @@ fn explain_operation(
             OperationExplanation {
                 index,
                 operation_type: "set_options".to_string(),
+                learn_more: learn_more("set_options"),
                 summary: explanation.summary.clone(),
                 details: serde_json::json!({
                     "account": explanation.account,
@@ -295,6 +607,7 @@ fn explain_operation(
             OperationExplanation {
                 index,
                 operation_type: "account_merge".to_string(),
+                learn_more: learn_more("account_merge"),
                 summary: explanation.summary.clone(),
                 details: serde_json::json!({
                     "source": explanation.source,
@@ -311,6 +624,7 @@ fn explain_operation(
             OperationExplanation {
                 index,
                 operation_type: operation_type.to_string(),
+                learn_more: learn_more(operation_type),
                 summary: explanation.summary.clone(),
                 details: serde_json::json!({
                     "account": explanation.seller,
@@ -332,6 +646,7 @@ fn explain_operation(
             OperationExplanation {
                 index,
                 operation_type: operation_type.to_string(),
+                learn_more: learn_more(operation_type),
                 summary: explanation.summary.clone(),
                 details: serde_json::json!({
                     "from": explanation.sender,
@@ -349,6 +664,7 @@ fn explain_operation(
             OperationExplanation {
                 index,
                 operation_type: "clawback".to_string(),
+                learn_more: learn_more("clawback"),
                 summary: explanation.summary.clone(),
                 details: serde_json::json!({
                     "issuer": explanation.issuer,
@@ -364,6 +680,7 @@ fn explain_operation(
             OperationExplanation {
                 index,
                 operation_type: "clawback_claimable_balance".to_string(),
+                learn_more: learn_more("clawback_claimable_balance"),
                 summary: explanation.summary.clone(),
                 details: serde_json::json!({
                     "issuer": explanation.issuer,
@@ -374,6 +691,7 @@ fn explain_operation(
         Operation::Other(other) => OperationExplanation {
             index,
             operation_type: other.operation_type.clone(),
+            learn_more: learn_more(&other.operation_type),
             summary: format!(
                 "{} operation — full support coming soon",
                 other.operation_type
@@ -425,6 +743,7 @@ mod tests {
     use super::*;
     use crate::models::memo::Memo;
     use crate::models::operation::{Operation, OtherOperation, PaymentOperation};
+    use proptest::prelude::*;
 
     fn create_payment_operation(id: &str, amount: &str) -> Operation {
         Operation::Payment(PaymentOperation {
@@ -434,7 +753,7 @@ mod tests {
             asset_type: "native".to_string(),
             asset_code: None,
             asset_issuer: None,
-            amount: amount.to_string(),
+            amount: amount.parse().unwrap(),
         })
     }
 
@@ -453,6 +772,11 @@ mod tests {
             operations: vec![create_payment_operation("1", "50.0")],
             memo: None,
             result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         }
     }
 
@@ -504,6 +828,30 @@ mod tests {
         assert_eq!(result, "");
     }
 
+    #[test]
+    fn test_format_ledger_time_truncated_or_non_ascii() {
+        assert_eq!(format_ledger_time("2024-01-15T"), "2024-01-15 at  UTC");
+        assert_eq!(
+            format_ledger_time("2024-01-15T1234é"),
+            "2024-01-15 at 1234é UTC"
+        );
+        assert_eq!(format_ledger_time("2024-01-15Té"), "2024-01-15 at é UTC");
+    }
+
+    proptest! {
+        #[test]
+        fn format_ledger_time_never_panics(input in any::<String>()) {
+            format_ledger_time(&input);
+        }
+
+        #[test]
+        fn format_ledger_time_never_panics_on_near_timestamps(
+            input in "[0-9]{4}-[0-9]{2}-[0-9]{2}T.{0,12}"
+        ) {
+            format_ledger_time(&input);
+        }
+    }
+
     #[test]
     fn test_format_ledger_time_date_only() {
         // No T separator — should return original
@@ -572,7 +920,7 @@ mod tests {
         let stats = FeeStats::new(100, 100, 5000, 100, 250);
         let result = explain_fee(100, Some(&stats));
         assert!(result.contains("standard network fee"));
-        assert!(result.contains("0.0000100"));
+        assert!(result.contains("0.00001 XLM"));
     }
 
     #[test]
@@ -599,6 +947,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_payment_to_memo_exchange_without_memo_is_warned_about() {
+        let binance = "GBINANCEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+        let mut payment = create_payment_operation("1", "50.0");
+        if let Operation::Payment(p) = &mut payment {
+            p.destination = binance.to_string();
+        }
+        let tx = Transaction {
+            operations: vec![payment.clone(), payment],
+            ..base_tx()
+        };
+        let explanation = explain_transaction(&tx, None).unwrap();
+        assert_eq!(explanation.warnings.len(), 1);
+        assert_eq!(explanation.warnings[0].code, WarningCode::MemoMissing);
+        assert!(
+            explanation.warnings[0]
+                .message
+                .contains("belongs to Binance")
+        );
+
+        let with_memo = Transaction {
+            memo: Some(Memo::id(42)),
+            ..tx
+        };
+        let explanation = explain_transaction(&with_memo, None).unwrap();
+        assert!(explanation.warnings.is_empty());
+        // Other destinations need no memo.
+        assert!(
+            explain_transaction(&base_tx(), None)
+                .unwrap()
+                .warnings
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_explain_no_payments_returns_ok() {
         let tx = Transaction {
@@ -0,0 +1,106 @@
+//! Entry points for the cargo-fuzz targets in `fuzz/`.
+//!
+//! Each function feeds arbitrary bytes through a path that handles upstream
+//! data, the same way the service does. Malformed input must end in an error
+//! or a degraded explanation; a panic or crash is a bug. Built only with the
+//! `fuzzing` feature.
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use serde::Deserialize;
+
+use crate::explain::transaction::explain_transaction_with_ledger;
+use crate::models::contract_event::{decode_contract_events, decode_value_base64};
+use crate::services::explain::map_transaction_to_domain;
+use crate::services::horizon::{self, HorizonOperation, HorizonTransaction};
+use crate::services::rpc::{RpcTransaction, to_horizon_records};
+
+/// Decode `data` as XDR. The first byte picks the decoder; the rest is the
+/// raw XDR, base64-encoded as upstreams send it.
+///
+/// - `0`: `TransactionMeta`, for contract events.
+/// - `1`: a single `ScVal`, as in stellar-rpc `getEvents`.
+/// - otherwise: a stellar-rpc transaction, split into envelope and result
+///   at the position given by the second byte.
+pub fn xdr(data: &[u8]) {
+    let Some((&selector, rest)) = data.split_first() else {
+        return;
+    };
+    match selector {
+        0 => {
+            let _ = decode_contract_events(&STANDARD.encode(rest));
+        }
+        1 => {
+            let _ = decode_value_base64(&STANDARD.encode(rest));
+        }
+        _ => {
+            let Some((&split, rest)) = rest.split_first() else {
+                return;
+            };
+            let (envelope, result) = rest.split_at(usize::from(split).min(rest.len()));
+            let tx = RpcTransaction {
+                status: "SUCCESS".to_string(),
+                ledger: Some(1),
+                created_at: None,
+                application_order: Some(1),
+                envelope_xdr: Some(STANDARD.encode(envelope)),
+                result_xdr: Some(STANDARD.encode(result)),
+                result_meta_xdr: None,
+            };
+            if let Ok((tx, operations)) = to_horizon_records("fuzz", tx) {
+                explain(tx, operations);
+            }
+        }
+    }
+}
+
+/// Parse `data` as a Horizon response body: every response model the client
+/// reads, and a transaction with its operations (the snapshot fixture shape),
+/// which is then explained.
+pub fn horizon_json(data: &[u8]) {
+    horizon::parse_responses(data);
+
+    #[derive(Deserialize)]
+    struct TransactionWithOperations {
+        transaction: HorizonTransaction,
+        operations: Vec<HorizonOperation>,
+    }
+    if let Ok(fixture) = serde_json::from_slice::<TransactionWithOperations>(data) {
+        explain(fixture.transaction, fixture.operations);
+    }
+}
+
+fn explain(tx: HorizonTransaction, operations: Vec<HorizonOperation>) {
+    let created_at = tx.created_at.clone();
+    let ledger = tx.ledger;
+    let domain = map_transaction_to_domain(tx, operations);
+    let _ = explain_transaction_with_ledger(&domain, None, created_at.as_deref(), ledger);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn horizon_json_accepts_fixtures_and_garbage() {
+        let fixtures = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/horizon");
+        for entry in std::fs::read_dir(fixtures).unwrap() {
+            horizon_json(&std::fs::read(entry.unwrap().path()).unwrap());
+        }
+        horizon_json(b"");
+        horizon_json(b"{\"_embedded\": {\"records\": [{\"base_amount\": \"9223372036854775807.0\", \"counter_amount\": \"1\", \"base_offer_id\": \"1\"}]}, \"_links\": {}}");
+    }
+
+    #[test]
+    fn xdr_accepts_short_and_deep_input() {
+        xdr(b"");
+        xdr(&[0]);
+        xdr(&[2, 200, 1, 2, 3]);
+        // A `ScVal::Vec` nested far beyond the depth limit.
+        let mut nested = vec![1];
+        for _ in 0..100_000 {
+            nested.extend_from_slice(&[0, 0, 0, 16, 0, 0, 0, 1, 0, 0, 0, 1]);
+        }
+        xdr(&nested);
+    }
+}
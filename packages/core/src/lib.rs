@@ -4,8 +4,11 @@
 
 #![allow(dead_code)]
 
+pub mod app;
 pub mod errors;
 pub mod explain;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz;
 pub mod middleware;
 pub mod models;
 pub mod routes;
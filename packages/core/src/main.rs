@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+mod app;
 mod config;
 mod errors;
 mod explain;
@@ -8,25 +9,53 @@ mod routes;
 mod services;
 mod state;
 
-use axum::{
-    Router,
-    http::{HeaderValue, Method, header},
-    middleware as axum_middleware,
-    routing::get,
-};
-use std::{env, sync::Arc};
+use axum::http::HeaderValue;
+use std::{env, net::SocketAddr, sync::Arc};
 use tokio::net::TcpListener;
-use tower::ServiceBuilder;
-use tower_http::cors::{AllowOrigin, CorsLayer};
-use tracing::info;
+use tracing::{info, warn};
 use tracing_subscriber::EnvFilter;
-use utoipa::OpenApi;
-use utoipa_swagger_ui::SwaggerUi;
 
+use crate::app::{AppConfig, DEFAULT_CORS_ORIGIN, build_router};
 use crate::config::network::StellarNetwork;
-use crate::middleware::request_id::request_id_middleware;
-use crate::routes::{ApiDoc, health::health};
+use crate::explain::doc_links::DocLinks;
+use crate::explain::format::DisplayConfig;
+use crate::explain::templates::Templates;
+use crate::middleware::audit::AuditContext;
+use crate::middleware::envelope::EnvelopeConfig;
+use crate::middleware::rate_limit::{RateLimit, RateLimitConfig, run_cleanup};
+use crate::middleware::receipt::ReceiptContext;
+use crate::middleware::timeout::TimeoutConfig;
+use crate::routes::admin::AdminToken;
+use crate::services::assets::SacResolver;
+use crate::services::audit::{AuditConfig, AuditStore};
+use crate::services::blocklist::{BlocklistConfig, BlocklistLoader};
+use crate::services::circuit::CircuitConfig;
+use crate::services::consistency::{ConsistencyChecker, ConsistencyConfig};
+use crate::services::contract_labels::ContractLabels;
+use crate::services::exports::{ExportConfig, ExportContext, ExportStore};
+use crate::services::faucet::{Faucet, Friendbot, friendbot_url};
 use crate::services::horizon::HorizonClient;
+use crate::services::horizon_pool::{HorizonPool, PoolConfig, run_health_checks};
+use crate::services::http::HttpConfig;
+use crate::services::ingestion::{IngestionConfig, IngestionWorker};
+use crate::services::market::{MarketCache, market_cache_ttl_from_env};
+use crate::services::memo_registry::MemoRegistry;
+use crate::services::mock::{mock_data_provider, mock_mode_enabled};
+use crate::services::network_lookup::{NetworkLookup, network_lookup_enabled};
+use crate::services::notice::NoticeBoard;
+use crate::services::operation_cache::{OperationCache, operation_cache_ttl_from_env};
+use crate::services::pending::{SubmissionStatusSource, pending_check_enabled};
+use crate::services::policy::ExplanationPolicy;
+use crate::services::provider::{DataSource, LedgerDataProvider};
+use crate::services::receipt::ReceiptSigner;
+use crate::services::recording::RecordingConfig;
+use crate::services::rpc::RpcClient;
+use crate::services::slo::{SloConfig, SloTracker};
+use crate::services::spam::SpamFilter;
+use crate::services::storage::ExplanationStore;
+use crate::services::submit::SubmitConfig;
+use crate::services::transaction_cache::{Network, run_eviction};
+use crate::services::warmup::{SnapshotConfig, WarmCache, WarmupConfig, run_snapshots, run_warmup};
 
 fn init_tracing() {
     let log_format = env::var("LOG_FORMAT").unwrap_or_else(|_| "pretty".to_string());
@@ -60,45 +89,388 @@ async fn main() {
     info!(network = ?network, "network_selected");
     info!(horizon_url = %horizon_url, "horizon_url_selected");
 
-    let cors_origin =
-        env::var("CORS_ORIGIN").unwrap_or_else(|_| "http://localhost:3000".to_string());
+    let cors_origin = env::var("CORS_ORIGIN").unwrap_or_else(|_| DEFAULT_CORS_ORIGIN.to_string());
 
     info!(cors_origin = %cors_origin, "cors_origin_selected");
 
-    let allowed_origin: HeaderValue = cors_origin.parse().expect("CORS_ORIGIN is not valid");
-
-    let cors = CorsLayer::new()
-        .allow_origin(AllowOrigin::exact(allowed_origin))
-        .allow_methods([Method::GET, Method::OPTIONS])
-        .allow_headers([header::CONTENT_TYPE, header::ACCEPT]);
-
-    let horizon_client = Arc::new(HorizonClient::new(horizon_url));
-
-    // SwaggerUi::url() registers /openapi.json internally.
-    // Do NOT add a separate .route("/openapi.json") or Axum will panic
-    // with "Overlapping method route" at startup.
-    let openapi = ApiDoc::openapi();
-
-    let app = Router::new()
-        .route("/health", get(health))
-        .route("/tx/:hash", get(routes::tx::get_tx_explanation))
-        .route(
-            "/account/:address",
-            get(routes::account::get_account_explanation),
-        )
-        .merge(SwaggerUi::new("/docs").url("/openapi.json", openapi))
-        .with_state(horizon_client)
-        .layer(cors)
-        .layer(ServiceBuilder::new().layer(axum_middleware::from_fn(request_id_middleware)));
-
-    // Rate limiting (tower_governor) removed for local dev.
-    // PeerIpKeyExtractor cannot extract IPs from loopback connections
-    // and returns "Unable To Extract Key!". Re-add behind a reverse
-    // proxy in production where X-Forwarded-For is available.
+    let mut config = AppConfig {
+        cors_origin: cors_origin
+            .parse::<HeaderValue>()
+            .expect("CORS_ORIGIN is not valid"),
+        ..AppConfig::default()
+    };
+
+    let templates = Templates::from_env().expect("EXPLAIN_TEMPLATES_PATH is not valid");
+    info!(
+        overrides = templates.override_count(),
+        "explanation_templates_loaded"
+    );
+    explain::templates::install(templates);
+    let doc_links = DocLinks::from_env().expect("DOC_LINKS_PATH is not valid");
+    info!(overrides = doc_links.override_count(), "doc_links_loaded");
+    explain::doc_links::install(doc_links);
+    explain::format::install(DisplayConfig::from_env());
+    services::assets::install(SacResolver::new(network.passphrase()));
+    let contract_labels = Arc::new(
+        ContractLabels::from_env(network.passphrase()).expect("CONTRACT_LABELS_PATH is not valid"),
+    );
+    info!(
+        labels = contract_labels.list().len(),
+        "contract_labels_loaded"
+    );
+    services::contract_labels::install(Arc::clone(&contract_labels));
+    services::spam::install(SpamFilter::from_env());
+    let memo_registry = MemoRegistry::from_env();
+    info!(accounts = memo_registry.len(), "memo_registry_loaded");
+    services::memo_registry::install(memo_registry);
+    let policy = ExplanationPolicy::from_env().expect("POLICY_* is not valid");
+    info!(
+        mode = policy.mode().as_str(),
+        entries = policy.len(),
+        "explanation_policy_configured"
+    );
+    services::policy::install(policy);
+
+    let circuit = CircuitConfig::from_env();
+    info!(
+        failure_threshold = circuit.failure_threshold,
+        cooldown_seconds = circuit.cooldown.as_secs(),
+        "horizon_circuit_configured"
+    );
+    let http = HttpConfig::from_env();
+    info!(
+        pool_max_idle_per_host = http.pool_max_idle_per_host,
+        connect_timeout_seconds = http.connect_timeout.as_secs(),
+        request_timeout_seconds = http.request_timeout.as_secs(),
+        "http_client_configured"
+    );
+    let http_client = http.build_client();
+
+    let mut horizon_client = HorizonClient::new(horizon_url)
+        .with_http_client(http_client.clone())
+        .with_circuit(circuit);
+    if let Some(config) = PoolConfig::from_env(network.name()) {
+        info!(
+            upstreams = ?config.urls,
+            eject_after = config.eject_after,
+            ejection_seconds = config.ejection.as_secs(),
+            "horizon_pool_configured"
+        );
+        let pool = Arc::new(HorizonPool::new(config));
+        if !mock_mode_enabled() {
+            tokio::spawn(run_health_checks(Arc::clone(&pool), http_client.clone()));
+        }
+        horizon_client = horizon_client.with_pool(pool);
+    }
+    if let Some(config) = RecordingConfig::from_env() {
+        info!(
+            mode = ?config.mode,
+            dir = %config.dir.display(),
+            "horizon_recording_enabled"
+        );
+        horizon_client = horizon_client.with_recording(config);
+    }
+    let horizon_client = Arc::new(horizon_client);
+
+    // Mock mode answers every route from fixtures instead of the network.
+    let mock = mock_mode_enabled().then(|| Arc::new(mock_data_provider()));
+    if mock.is_some() {
+        info!("mock_mode_enabled");
+    }
+
+    let provider: Arc<dyn LedgerDataProvider> = match (&mock, DataSource::from_env()) {
+        (Some(mock), _) => Arc::clone(mock) as Arc<dyn LedgerDataProvider>,
+        (None, DataSource::Horizon) => Arc::clone(&horizon_client) as Arc<dyn LedgerDataProvider>,
+        (None, DataSource::Rpc) => {
+            let rpc_url = env::var("RPC_URL")
+                .ok()
+                .or_else(|| network.rpc_url().map(str::to_string))
+                .expect("RPC_URL must be set when DATA_SOURCE=rpc on this network");
+            info!(rpc_url = %rpc_url, "rpc_url_selected");
+            Arc::new(RpcClient::new(rpc_url).with_http_client(http_client.clone()))
+        }
+    };
+    info!(data_source = provider.name(), "data_source_selected");
+
+    let submissions: Option<Arc<dyn SubmissionStatusSource>> = pending_check_enabled().then(|| {
+        let rpc_url = env::var("RPC_URL")
+            .ok()
+            .or_else(|| network.rpc_url().map(str::to_string))
+            .expect("RPC_URL must be set when PENDING_CHECK_ENABLED on this network");
+        info!(rpc_url = %rpc_url, "pending_check_enabled");
+        Arc::new(RpcClient::new(rpc_url).with_http_client(http_client.clone()))
+            as Arc<dyn SubmissionStatusSource>
+    });
+
+    config.provider = Some(Arc::clone(&provider));
+
+    if let Some(submit) = SubmitConfig::from_env(network.passphrase()) {
+        info!(
+            timeout_seconds = submit.timeout.as_secs(),
+            "transaction_submission_enabled"
+        );
+        config.submit = Some(submit);
+    }
+
+    // Friendbot would fund real accounts that mock mode cannot show.
+    if let Some(url) = friendbot_url(network.friendbot_url()).filter(|_| mock.is_none()) {
+        info!(friendbot_url = %url, "testnet_funding_enabled");
+        let faucet: Arc<dyn Faucet> =
+            Arc::new(Friendbot::new(url).with_http_client(http_client.clone()));
+        config.faucet = Some(faucet);
+    }
+
+    config.submissions = submissions;
+
+    // Mock mode has no other networks to look on.
+    if network_lookup_enabled() && mock.is_none() {
+        let others: Vec<StellarNetwork> = StellarNetwork::all()
+            .into_iter()
+            .filter(|other| *other != network)
+            .collect();
+        info!(
+            networks = ?others.iter().map(StellarNetwork::name).collect::<Vec<_>>(),
+            "network_lookup_enabled"
+        );
+        let lookup =
+            others
+                .into_iter()
+                .fold(NetworkLookup::new(network.name()), |lookup, other| {
+                    let horizon: Arc<dyn LedgerDataProvider> = Arc::new(
+                        HorizonClient::new(other.horizon_url())
+                            .with_http_client(http_client.clone()),
+                    );
+                    lookup.with_network(other.name(), horizon)
+                });
+        config.network_lookup = Some(lookup);
+    }
+
+    if let Some(ingestion) = IngestionConfig::from_env().filter(|_| mock.is_none()) {
+        let db_path =
+            env::var("INDEX_DB_PATH").unwrap_or_else(|_| "stellar-explain.db".to_string());
+        let store = Arc::new(ExplanationStore::open(&db_path).expect("INDEX_DB_PATH is not valid"));
+        info!(db_path = %db_path, "explanation_index_opened");
+
+        if let Some(consistency) = ConsistencyConfig::from_env() {
+            let checker = ConsistencyChecker::new(
+                Arc::clone(&horizon_client),
+                Arc::clone(&store),
+                consistency,
+            );
+            config.consistency = Some(checker.status());
+            tokio::spawn(checker.run());
+        }
+
+        let worker =
+            IngestionWorker::new(Arc::clone(&horizon_client), Arc::clone(&store), ingestion);
+        config.ingestion = Some(worker.status());
+        tokio::spawn(worker.run());
+        tokio::spawn(services::stats::run_refresh(
+            Arc::clone(&store),
+            services::stats::refresh_interval_from_env(),
+        ));
+
+        config.index = Some(store);
+    }
+
+    let cache_network = match network {
+        StellarNetwork::Public => Network::Public,
+        StellarNetwork::Testnet => Network::Testnet,
+    };
+    if let Some(warmup) = WarmupConfig::from_env() {
+        info!(
+            top_n = warmup.top_n,
+            interval_seconds = warmup.interval.as_secs(),
+            ttl_seconds = warmup.ttl.as_secs(),
+            "warmup_enabled"
+        );
+        let cache = Arc::new(WarmCache::new(cache_network, warmup.ttl));
+        if let Some(snapshot) = SnapshotConfig::from_env() {
+            match cache.load_snapshot(&snapshot.path) {
+                Ok(entries) => info!(entries, "cache_snapshot_loaded"),
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    warn!(path = %snapshot.path.display(), error = %err, "cache_snapshot_not_loaded")
+                }
+            }
+            tokio::spawn(run_snapshots(Arc::clone(&cache), snapshot));
+        }
+        let warmup_cache = Arc::clone(&cache);
+        let warmup_provider = Arc::clone(&provider);
+        let index = config.index.clone();
+        match &mock {
+            Some(mock) => tokio::spawn(run_warmup(
+                warmup_cache,
+                warmup_provider,
+                Arc::clone(mock),
+                index,
+                warmup,
+            )),
+            None => tokio::spawn(run_warmup(
+                warmup_cache,
+                warmup_provider,
+                Arc::clone(&horizon_client),
+                index,
+                warmup,
+            )),
+        };
+        config.warm_cache = Some(cache);
+    }
+    if let Some(ttl) = operation_cache_ttl_from_env() {
+        info!(ttl_seconds = ttl.as_secs(), "operation_cache_enabled");
+        let cache = Arc::new(OperationCache::new(cache_network, ttl));
+        let evicting = Arc::clone(&cache);
+        tokio::spawn(run_eviction("operations", ttl, move || {
+            evicting.evict_expired()
+        }));
+        config.operation_cache = Some(cache);
+    }
+    if let Some(ttl) = market_cache_ttl_from_env() {
+        let cache = Arc::new(MarketCache::new(cache_network, ttl));
+        let evicting = Arc::clone(&cache);
+        tokio::spawn(run_eviction("market", ttl, move || {
+            evicting.evict_expired()
+        }));
+        config.market_cache = Some(cache);
+    }
+
+    if let Some(audit) = AuditConfig::from_env() {
+        let store = Arc::new(AuditStore::open(&audit.db_path).expect("AUDIT_DB_PATH is not valid"));
+        info!(
+            db_path = %audit.db_path,
+            retention_days = audit.retention.as_secs() / 86_400,
+            "audit_log_opened"
+        );
+        tokio::spawn(services::audit::run_retention(
+            Arc::clone(&store),
+            audit.retention,
+        ));
+        config.audit = Some(AuditContext {
+            store,
+            network: network.name().to_string(),
+        });
+    }
+
+    if let Some(exports) = ExportConfig::from_env() {
+        let store =
+            Arc::new(ExportStore::open(&exports.db_path).expect("EXPORTS_DB_PATH is not valid"));
+        info!(
+            db_path = %exports.db_path,
+            per_hour = exports.max_per_hour,
+            retention_hours = exports.retention.as_secs() / 3_600,
+            "exports_enabled"
+        );
+        tokio::spawn(services::exports::run_retention(
+            Arc::clone(&store),
+            exports.retention,
+        ));
+        config.exports = Some(ExportContext {
+            store,
+            max_per_hour: exports.max_per_hour,
+        });
+    }
+
+    #[cfg(feature = "llm-summary")]
+    if let Some(summarizer) = services::summarizer::SummarizerConfig::from_env() {
+        info!(
+            url = %summarizer.url,
+            model = %summarizer.model,
+            budget_ms = summarizer.budget.as_millis() as u64,
+            "generated_summaries_enabled"
+        );
+        let budget = summarizer.budget;
+        config.summarizer = Some(services::summarizer::SummarizerContext {
+            summarizer: Arc::new(services::summarizer::HttpSummarizer::new(
+                http_client.clone(),
+                summarizer,
+            )),
+            budget,
+        });
+    }
+
+    if let Some(blocklist) = BlocklistConfig::from_env() {
+        let loader = BlocklistLoader::new(blocklist, http_client.clone());
+        // A list that cannot be read yet is picked up by the next reload.
+        let _ = loader.reload().await;
+        #[cfg(unix)]
+        tokio::spawn(services::blocklist::run_reload_on_sighup(loader.clone()));
+        config.blocklist = Some(loader);
+    }
+
+    if let Some(token) = AdminToken::from_env() {
+        info!("admin_endpoints_enabled");
+        config.admin_token = Some(token);
+    }
+
+    if let Ok(seed) = env::var("RECEIPT_SIGNING_SEED") {
+        let signer = Arc::new(
+            ReceiptSigner::from_seed(&seed)
+                .expect("RECEIPT_SIGNING_SEED is not a valid Stellar secret seed"),
+        );
+        info!(key_id = signer.key_id(), "receipt_signing_enabled");
+        config.receipts = Some(ReceiptContext {
+            signer,
+            network: network.name().to_string(),
+        });
+    }
+
+    config.timeouts = TimeoutConfig::from_env();
+    info!(
+        tx_seconds = config.timeouts.transaction.as_secs(),
+        account_seconds = config.timeouts.account.as_secs(),
+        lookup_seconds = config.timeouts.lookup.as_secs(),
+        "route_timeouts_configured"
+    );
+
+    let notices = Arc::new(NoticeBoard::from_env().expect("SERVICE_NOTICE_PATH is not readable"));
+    if let Some(notice) = notices.get() {
+        info!(message = %notice.message, "service_notice_restored");
+    }
+    config.notices = Some(Arc::clone(&notices));
+    config.contract_labels = Some(contract_labels);
+    let envelope = EnvelopeConfig::from_env(network.name()).with_notices(notices);
+    info!(enabled = envelope.enabled, "response_envelope_configured");
+    config.envelope = Some(envelope);
+
+    if let Some(slo) = SloConfig::from_env() {
+        info!(
+            availability_target = slo.availability_target,
+            latency_threshold_ms = slo.latency_threshold.as_millis() as u64,
+            latency_target = slo.latency_target,
+            window_hours = slo.window.as_secs() / 3600,
+            "slo_tracking_enabled"
+        );
+        let tracker = Arc::new(SloTracker::new(slo));
+        tokio::spawn(services::slo::run_alerts(Arc::clone(&tracker)));
+        config.slo = Some(tracker);
+    }
+
+    if let Some(limits) = RateLimitConfig::from_env() {
+        info!(
+            per_second = limits.per_second.get(),
+            burst = limits.burst.get(),
+            trust_proxy = limits.trust_proxy,
+            "rate_limiting_enabled"
+        );
+        let rate_limit = RateLimit::new(&limits);
+        tokio::spawn(run_cleanup(rate_limit.clone()));
+        config.rate_limit = Some(rate_limit);
+    }
+
+    let app = match &mock {
+        Some(mock) => build_router(config, Arc::clone(mock)),
+        None => build_router(config, Arc::clone(&horizon_client)),
+    };
 
     let addr = "0.0.0.0:4000";
     info!(bind_addr = %addr, "server_starting");
 
     let listener = TcpListener::bind(addr).await.unwrap();
-    axum::serve(listener, app).await.unwrap();
+    // Connection info gives the audit log the client address.
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await
+    .unwrap();
 }
@@ -0,0 +1,199 @@
+//! Records explanation requests in the audit log.
+//!
+//! Runs only when auditing is enabled (see [`crate::services::audit`]).
+//! The record is written after the response is produced, off the request
+//! path, so a slow or failing audit database never delays or fails the
+//! request itself.
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::HeaderMap,
+    middleware::Next,
+    response::Response,
+};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tracing::error;
+
+use crate::services::audit::{
+    AuditRecord, AuditStore, AuditSubject, audit_timestamp, key_requester,
+};
+
+/// Request header carrying a client's API key.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+#[derive(Clone)]
+pub struct AuditContext {
+    pub store: Arc<AuditStore>,
+    /// Network name recorded with each request, e.g. "public".
+    pub network: String,
+}
+
+/// The subject of an explanation request, or None for paths that are not
+/// audited.
+fn subject(path: &str) -> Option<(AuditSubject, String)> {
+    let mut segments = path.trim_start_matches('/').split('/');
+    let kind = match segments.next()? {
+        "tx" => AuditSubject::Transaction,
+        "account" => AuditSubject::Account,
+        _ => return None,
+    };
+    let id = segments.next().filter(|id| !id.is_empty())?;
+    // /tx/compare names its transactions in the query string.
+    if kind == AuditSubject::Transaction && id == "compare" {
+        return None;
+    }
+    Some((kind, id.to_string()))
+}
+
+/// The API key the client sent, if any.
+pub fn api_key(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+/// Who made the request: an API key fingerprint if one was sent, otherwise
+/// the client IP, preferring the first `X-Forwarded-For` hop set by a
+/// reverse proxy.
+fn requester(headers: &HeaderMap, peer: Option<SocketAddr>) -> String {
+    if let Some(key) = api_key(headers) {
+        return key_requester(key);
+    }
+    let forwarded = headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(',').next())
+        .map(str::trim)
+        .filter(|v| !v.is_empty());
+    match (forwarded, peer) {
+        (Some(ip), _) => format!("ip:{ip}"),
+        (None, Some(peer)) => format!("ip:{}", peer.ip()),
+        (None, None) => "ip:unknown".to_string(),
+    }
+}
+
+pub async fn audit_middleware(
+    State(context): State<AuditContext>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some((subject_type, subject)) = subject(request.uri().path()) else {
+        return next.run(request).await;
+    };
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let requester = requester(request.headers(), peer);
+    let requested_at = audit_timestamp(chrono::Utc::now());
+
+    let response = next.run(request).await;
+
+    let record = AuditRecord {
+        subject_type,
+        subject,
+        network: context.network,
+        requester,
+        requested_at,
+        status: response.status().as_u16(),
+    };
+    tokio::task::spawn_blocking(move || {
+        if let Err(err) = context.store.record(&record) {
+            error!(error = %err, subject = %record.subject, "audit_record_failed");
+        }
+    });
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::audit::AuditFilter;
+    use axum::{Router, http::HeaderValue, middleware::from_fn_with_state, routing::get};
+    use std::time::Duration;
+
+    #[test]
+    fn audits_transaction_and_account_paths() {
+        assert_eq!(
+            subject("/tx/abc"),
+            Some((AuditSubject::Transaction, "abc".to_string()))
+        );
+        assert_eq!(
+            subject("/tx/abc/timeline"),
+            Some((AuditSubject::Transaction, "abc".to_string()))
+        );
+        assert_eq!(
+            subject("/account/GABC/offers"),
+            Some((AuditSubject::Account, "GABC".to_string()))
+        );
+        assert_eq!(subject("/tx/compare"), None);
+        assert_eq!(subject("/search"), None);
+        assert_eq!(subject("/admin/audit"), None);
+    }
+
+    #[test]
+    fn identifies_requester_by_key_then_ip() {
+        let peer: SocketAddr = "192.0.2.1:5000".parse().unwrap();
+        let mut headers = HeaderMap::new();
+        assert_eq!(requester(&headers, Some(peer)), "ip:192.0.2.1");
+
+        headers.insert(
+            "x-forwarded-for",
+            HeaderValue::from_static("203.0.113.9, 10.0.0.1"),
+        );
+        assert_eq!(requester(&headers, Some(peer)), "ip:203.0.113.9");
+
+        headers.insert(API_KEY_HEADER, HeaderValue::from_static("k1"));
+        assert_eq!(requester(&headers, Some(peer)), key_requester("k1"));
+    }
+
+    #[tokio::test]
+    async fn records_explanation_requests() {
+        let store = Arc::new(AuditStore::open_in_memory().unwrap());
+        let context = AuditContext {
+            store: Arc::clone(&store),
+            network: "testnet".to_string(),
+        };
+        let app = Router::new()
+            .route("/tx/:hash", get(|| async { "ok" }))
+            .route("/search", get(|| async { "ok" }))
+            .layer(from_fn_with_state(context, audit_middleware));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .unwrap()
+        });
+
+        reqwest::get(format!("http://{addr}/tx/abc")).await.unwrap();
+        reqwest::get(format!("http://{addr}/search")).await.unwrap();
+        reqwest::get(format!("http://{addr}/tx/missing/extra/segments"))
+            .await
+            .unwrap();
+
+        // Records are written in the background.
+        let mut records = Vec::new();
+        for _ in 0..50 {
+            records = store.query(&AuditFilter::default(), 10, 0).unwrap().0;
+            if records.len() == 2 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(records.len(), 2);
+        let ok = records.iter().find(|r| r.subject == "abc").unwrap();
+        assert_eq!(ok.subject_type, AuditSubject::Transaction);
+        assert_eq!(ok.network, "testnet");
+        assert_eq!(ok.requester, "ip:127.0.0.1");
+        assert_eq!(ok.status, 200);
+        let missing = records.iter().find(|r| r.subject == "missing").unwrap();
+        assert_eq!(missing.status, 404);
+    }
+}
@@ -0,0 +1,296 @@
+//! Standard envelope around successful JSON responses.
+//!
+//! Handlers return their payload as usual; this middleware rewrites 2xx JSON
+//! bodies to `{ "data": ..., "meta": { ... } }` so every endpoint reports the
+//! same request metadata. The payload is passed through as it streams
+//! rather than parsed, so wrapping costs no memory however large it is.
+//! Error bodies keep their own `{ "error": ... }` shape, and the API docs
+//! and JSON Schemas are served unwrapped.
+//!
+//! While an operator has set a service notice (see
+//! [`crate::services::notice`]), it is added to every `meta` as `notice`.
+//!
+//! Consumers that predate the envelope can opt out per request with
+//! `X-Response-Envelope: false`, or operators can turn it off for everyone
+//! with `RESPONSE_ENVELOPE=false`.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+use futures_util::{StreamExt, stream};
+use serde::Serialize;
+use std::env;
+use std::sync::Arc;
+
+use crate::middleware::request_id::RequestId;
+use crate::services::notice::{NoticeBoard, ServiceNotice};
+
+/// Version of the envelope and the payloads inside it.
+pub const API_VERSION: &str = "v1";
+
+/// Request header that selects the bare payload when set to "false".
+pub const ENVELOPE_HEADER: &str = "x-response-envelope";
+
+/// Paths whose bodies are documents in their own right.
+const BARE_PATH_PREFIXES: &[&str] = &["/docs", "/openapi.json", "/schema/"];
+
+/// Whether a payload was served from a cache or index instead of upstream.
+///
+/// Handlers insert this as a response extension; responses without it are
+/// reported as misses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheStatus {
+    Hit,
+    Miss,
+}
+
+#[derive(Debug, Clone)]
+pub struct EnvelopeConfig {
+    /// Network name reported in `meta.network`, e.g. "public".
+    pub network: String,
+    pub enabled: bool,
+    /// Where the service notice shown in `meta.notice` is kept.
+    pub notices: Option<Arc<NoticeBoard>>,
+}
+
+impl EnvelopeConfig {
+    /// Read `RESPONSE_ENVELOPE`; the envelope is on unless it is "false".
+    pub fn from_env(network: &str) -> Self {
+        let enabled = env::var("RESPONSE_ENVELOPE")
+            .map(|v| !v.trim().eq_ignore_ascii_case("false"))
+            .unwrap_or(true);
+        Self {
+            network: network.to_string(),
+            enabled,
+            notices: None,
+        }
+    }
+
+    pub fn with_notices(mut self, notices: Arc<NoticeBoard>) -> Self {
+        self.notices = Some(notices);
+        self
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct Meta {
+    request_id: Option<String>,
+    network: String,
+    generated_at: String,
+    cache: CacheStatus,
+    api_version: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notice: Option<ServiceNotice>,
+}
+
+pub async fn envelope_middleware(
+    State(config): State<EnvelopeConfig>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let wanted = config.enabled
+        && !opted_out(request.headers())
+        && !BARE_PATH_PREFIXES
+            .iter()
+            .any(|prefix| request.uri().path().starts_with(prefix));
+    let request_id = request.extensions().get::<RequestId>().cloned();
+
+    let response = next.run(request).await;
+    if !wanted || !response.status().is_success() || !is_json(response.headers()) {
+        return response;
+    }
+    // Already wrapped further in; wrapping again would nest envelopes.
+    if response.headers().contains_key(ENVELOPE_HEADER) {
+        return response;
+    }
+
+    let cache = response
+        .extensions()
+        .get::<CacheStatus>()
+        .copied()
+        .unwrap_or(CacheStatus::Miss);
+    let meta = Meta {
+        request_id: request_id.map(|id| id.to_string()),
+        network: config.network.clone(),
+        generated_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+        cache,
+        api_version: API_VERSION,
+        notice: config.notices.as_ref().and_then(|notices| notices.get()),
+    };
+    let Ok(meta) = serde_json::to_vec(&meta) else {
+        return response;
+    };
+    let mut closing = b",\"meta\":".to_vec();
+    closing.extend_from_slice(&meta);
+    closing.push(b'}');
+
+    let (mut parts, body) = response.into_parts();
+    let wrapped = stream::iter([Ok(Bytes::from_static(b"{\"data\":"))])
+        .chain(body.into_data_stream())
+        .chain(stream::iter([Ok(Bytes::from(closing))]));
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts
+        .headers
+        .insert(ENVELOPE_HEADER, HeaderValue::from_static(API_VERSION));
+    Response::from_parts(parts, Body::from_stream(wrapped))
+}
+
+fn opted_out(headers: &HeaderMap) -> bool {
+    headers
+        .get(ENVELOPE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim().eq_ignore_ascii_case("false"))
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Extension, Json, Router,
+        http::StatusCode,
+        middleware::{from_fn, from_fn_with_state},
+        response::IntoResponse,
+        routing::get,
+    };
+    use serde_json::{Value, json};
+
+    use crate::middleware::request_id::request_id_middleware;
+    use crate::services::notice::NoticeSeverity;
+
+    async fn spawn(enabled: bool) -> String {
+        spawn_with(EnvelopeConfig {
+            network: "testnet".to_string(),
+            enabled,
+            notices: None,
+        })
+        .await
+    }
+
+    async fn spawn_with(config: EnvelopeConfig) -> String {
+        let app = Router::new()
+            .route(
+                "/payload",
+                get(|| async { Json(json!({ "summary": "ok" })) }),
+            )
+            .route(
+                "/indexed",
+                get(|| async { (Extension(CacheStatus::Hit), Json(json!([1, 2]))) }),
+            )
+            .route(
+                "/missing",
+                get(|| async {
+                    (StatusCode::NOT_FOUND, Json(json!({ "error": "nope" }))).into_response()
+                }),
+            )
+            .route(
+                "/schema/thing",
+                get(|| async { Json(json!({ "type": "object" })) }),
+            )
+            .layer(from_fn_with_state(config, envelope_middleware))
+            .layer(from_fn(request_id_middleware));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}")
+    }
+
+    async fn get_json(url: &str, bare: bool) -> (StatusCode, Value) {
+        let mut request = reqwest::Client::new().get(url);
+        if bare {
+            request = request.header(ENVELOPE_HEADER, "false");
+        }
+        let response = request.send().await.unwrap();
+        let status = StatusCode::from_u16(response.status().as_u16()).unwrap();
+        (status, response.json().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn wraps_successful_json_with_metadata() {
+        let base = spawn(true).await;
+
+        let (status, body) = get_json(&format!("{base}/payload"), false).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"]["summary"], "ok");
+        assert_eq!(body["meta"]["network"], "testnet");
+        assert_eq!(body["meta"]["cache"], "miss");
+        assert_eq!(body["meta"]["api_version"], "v1");
+        assert_eq!(body["meta"]["request_id"].as_str().map(str::len), Some(36));
+        assert!(
+            body["meta"]["generated_at"]
+                .as_str()
+                .unwrap()
+                .ends_with('Z')
+        );
+
+        let (_, body) = get_json(&format!("{base}/indexed"), false).await;
+        assert_eq!(body["data"], json!([1, 2]));
+        assert_eq!(body["meta"]["cache"], "hit");
+        assert!(body["meta"].get("notice").is_none());
+    }
+
+    #[tokio::test]
+    async fn service_notice_is_added_to_meta() {
+        let path = env::temp_dir().join(format!("notice-{}.json", uuid::Uuid::new_v4()));
+        let notices = Arc::new(NoticeBoard::open(&path).unwrap());
+        let base =
+            spawn_with(EnvelopeConfig::from_env("testnet").with_notices(Arc::clone(&notices)))
+                .await;
+        let notice = ServiceNotice {
+            message: "Horizon mainnet is degraded; data may lag".to_string(),
+            severity: NoticeSeverity::Warning,
+            set_at: "2026-01-02T10:15:00Z".to_string(),
+        };
+        notices.set(notice).unwrap();
+
+        let (_, body) = get_json(&format!("{base}/payload"), false).await;
+        assert_eq!(
+            body["meta"]["notice"],
+            json!({
+                "message": "Horizon mainnet is degraded; data may lag",
+                "severity": "warning",
+                "set_at": "2026-01-02T10:15:00Z"
+            })
+        );
+
+        notices.clear().unwrap();
+        let (_, body) = get_json(&format!("{base}/payload"), false).await;
+        assert!(body["meta"].get("notice").is_none());
+    }
+
+    #[tokio::test]
+    async fn leaves_errors_schemas_and_opt_outs_bare() {
+        let base = spawn(true).await;
+
+        let (status, body) = get_json(&format!("{base}/missing"), false).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body, json!({ "error": "nope" }));
+
+        let (_, body) = get_json(&format!("{base}/schema/thing"), false).await;
+        assert_eq!(body, json!({ "type": "object" }));
+
+        let (_, body) = get_json(&format!("{base}/payload"), true).await;
+        assert_eq!(body, json!({ "summary": "ok" }));
+    }
+
+    #[tokio::test]
+    async fn disabled_envelope_returns_bare_payloads() {
+        let base = spawn(false).await;
+
+        let (_, body) = get_json(&format!("{base}/payload"), false).await;
+        assert_eq!(body, json!({ "summary": "ok" }));
+    }
+}
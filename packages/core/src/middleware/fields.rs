@@ -0,0 +1,255 @@
+//! Sparse fieldsets: `?fields=` trims a JSON response to the fields named.
+//!
+//! `?fields=summary,payment_explanations` keeps only those top-level fields
+//! of an explanation. Dots reach into nested objects, so
+//! `?fields=summary,operations.summary` keeps each operation's summary and
+//! nothing else of it. Arrays are projected element by element, and fields
+//! a response does not have are ignored. Works on any successful JSON
+//! response, so every endpoint supports it without knowing about it.
+//!
+//! The parameter is taken off the request before it reaches the handler,
+//! so endpoints that reject unknown query parameters accept it too.
+//!
+//! This layer sits inside the response envelope, which projects the
+//! payload and leaves `meta` whole, and inside signed receipts, which sign
+//! the trimmed body the client actually receives.
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{Query, Request},
+    http::{HeaderMap, Uri, header, uri::PathAndQuery},
+    middleware::Next,
+    response::Response,
+};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Deserialize)]
+struct FieldsQuery {
+    fields: Option<String>,
+}
+
+/// The fields to keep: each name maps to the subfields to keep under it,
+/// or to an empty set to keep the field whole.
+#[derive(Debug, Default, PartialEq)]
+pub struct FieldSet(BTreeMap<String, FieldSet>);
+
+impl FieldSet {
+    /// Parse a comma-separated list of dotted paths; `None` if it names
+    /// no fields.
+    pub fn parse(fields: &str) -> Option<Self> {
+        let mut set = FieldSet::default();
+        for path in fields.split(',') {
+            let path: Vec<&str> = path.split('.').map(str::trim).collect();
+            if !path.iter().any(|name| name.is_empty()) {
+                set.insert(&path);
+            }
+        }
+        (!set.0.is_empty()).then_some(set)
+    }
+
+    fn insert(&mut self, path: &[&str]) {
+        let Some((name, rest)) = path.split_first() else {
+            return;
+        };
+        match self.0.get_mut(*name) {
+            // Already kept whole; subfields add nothing.
+            Some(sub) if sub.0.is_empty() => {}
+            Some(sub) if rest.is_empty() => sub.0.clear(),
+            Some(sub) => sub.insert(rest),
+            None => {
+                let mut sub = FieldSet::default();
+                sub.insert(rest);
+                self.0.insert(name.to_string(), sub);
+            }
+        }
+    }
+
+    /// `value` with only the fields in this set.
+    pub fn project(&self, value: Value) -> Value {
+        match value {
+            Value::Object(object) => Value::Object(
+                object
+                    .into_iter()
+                    .filter_map(|(name, field)| {
+                        let sub = self.0.get(&name)?;
+                        let field = if sub.0.is_empty() {
+                            field
+                        } else {
+                            sub.project(field)
+                        };
+                        Some((name, field))
+                    })
+                    .collect::<Map<_, _>>(),
+            ),
+            Value::Array(items) => {
+                Value::Array(items.into_iter().map(|item| self.project(item)).collect())
+            }
+            scalar => scalar,
+        }
+    }
+}
+
+pub async fn fields_middleware(mut request: Request, next: Next) -> Response {
+    let fields = Query::<FieldsQuery>::try_from_uri(request.uri())
+        .ok()
+        .and_then(|Query(query)| query.fields)
+        .and_then(|fields| FieldSet::parse(&fields));
+    if let Some(uri) = without_fields(request.uri()) {
+        *request.uri_mut() = uri;
+    }
+
+    let response = next.run(request).await;
+    let Some(fields) = fields else {
+        return response;
+    };
+    if !response.status().is_success() || !is_json(response.headers()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let Ok(value) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let Ok(projected) = serde_json::to_vec(&fields.project(value)) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Body::from(projected))
+}
+
+/// `uri` with any `fields` parameters removed, or `None` if it has none.
+fn without_fields(uri: &Uri) -> Option<Uri> {
+    let query = uri.query()?;
+    let is_fields = |pair: &&str| pair.split('=').next() == Some("fields");
+    if !query.split('&').any(|pair| is_fields(&pair)) {
+        return None;
+    }
+    let kept: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty() && !is_fields(pair))
+        .collect();
+    let path_and_query = if kept.is_empty() {
+        uri.path().to_string()
+    } else {
+        format!("{}?{}", uri.path(), kept.join("&"))
+    };
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::try_from(path_and_query).ok()?);
+    Uri::from_parts(parts).ok()
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Json, Router, http::StatusCode, middleware::from_fn, routing::get};
+    use serde_json::json;
+
+    fn explanation() -> Value {
+        json!({
+            "transaction_hash": "abc",
+            "summary": "Sent 5 XLM",
+            "operations": [
+                { "index": 0, "summary": "Paid", "details": { "amount": "5" } },
+                { "index": 1, "summary": "Paid again", "details": { "amount": "1" } }
+            ],
+            "payment_explanations": []
+        })
+    }
+
+    #[test]
+    fn test_projects_top_level_fields() {
+        let fields = FieldSet::parse("summary, payment_explanations,missing").unwrap();
+
+        assert_eq!(
+            fields.project(explanation()),
+            json!({ "summary": "Sent 5 XLM", "payment_explanations": [] })
+        );
+    }
+
+    #[test]
+    fn test_dotted_paths_reach_into_arrays() {
+        let fields = FieldSet::parse("operations.summary,operations.details.amount").unwrap();
+
+        assert_eq!(
+            fields.project(explanation()),
+            json!({
+                "operations": [
+                    { "summary": "Paid", "details": { "amount": "5" } },
+                    { "summary": "Paid again", "details": { "amount": "1" } }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_whole_field_wins_over_subfields() {
+        let whole = FieldSet::parse("operations");
+        assert_eq!(FieldSet::parse("operations,operations.summary"), whole);
+        assert_eq!(FieldSet::parse("operations.summary,operations"), whole);
+        assert_eq!(FieldSet::parse(" , "), None);
+    }
+
+    #[derive(Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct StrictQuery {
+        #[allow(dead_code)]
+        privacy: Option<bool>,
+    }
+
+    #[tokio::test]
+    async fn test_middleware_trims_successful_json_only() {
+        let app = Router::new()
+            .route(
+                "/tx",
+                get(|_: Query<StrictQuery>| async { Json(explanation()) }),
+            )
+            .route(
+                "/missing",
+                get(|| async {
+                    (
+                        StatusCode::NOT_FOUND,
+                        Json(json!({ "error": { "code": "NOT_FOUND" } })),
+                    )
+                }),
+            )
+            .layer(from_fn(fields_middleware));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let get_json = |path: &'static str| async move {
+            reqwest::get(format!("http://{addr}{path}"))
+                .await
+                .unwrap()
+                .json::<Value>()
+                .await
+                .unwrap()
+        };
+
+        assert_eq!(
+            get_json("/tx?fields=summary").await,
+            json!({ "summary": "Sent 5 XLM" })
+        );
+        assert_eq!(
+            get_json("/tx?privacy=false&fields=summary").await,
+            json!({ "summary": "Sent 5 XLM" })
+        );
+        assert_eq!(get_json("/tx").await, explanation());
+        assert_eq!(
+            get_json("/missing?fields=summary").await,
+            json!({ "error": { "code": "NOT_FOUND" } })
+        );
+    }
+}
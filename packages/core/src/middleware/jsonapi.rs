@@ -0,0 +1,357 @@
+//! JSON:API output for clients that ask for it.
+//!
+//! When a request's `Accept` header lists `application/vnd.api+json`, the
+//! JSON body is rewritten as a JSON:API document instead of being wrapped
+//! in the response envelope:
+//!
+//! - Routes with path parameters answer with a resource: `type` comes from
+//!   the route (`transactions` for `/tx/:hash`), `id` from the parameters,
+//!   and the payload becomes `attributes`. Sub-resources such as
+//!   `/tx/:hash/timeline` relate to their parent, and an explanation's
+//!   `ledger` relates to the ledger resource.
+//! - Routes without path parameters, and payloads that are not objects,
+//!   answer with a meta-only document whose `meta` is the payload.
+//! - Errors become `{"errors": [{"status", "code", "detail", "meta"}]}`.
+//!
+//! Every document links to itself. This layer sits outside signed receipts,
+//! so a receipt still signs the bare explanation.
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{MatchedPath, Request},
+    http::{HeaderMap, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+use serde_json::{Map, Value, json};
+
+pub const JSONAPI_CONTENT_TYPE: &str = "application/vnd.api+json";
+
+/// Route prefixes that name a resource, with its JSON:API type.
+const RESOURCE_TYPES: &[(&str, &str)] = &[
+    ("/tx/:hash", "transactions"),
+    ("/ledger/:sequence", "ledgers"),
+    ("/account/:address", "accounts"),
+    ("/claimable-balance/:id", "claimable-balances"),
+    ("/liquidity-pool/:id", "liquidity-pools"),
+];
+
+/// Attributes holding the ID of another resource: (attribute, type, route
+/// prefix the ID completes).
+const RELATED_ATTRIBUTES: &[(&str, &str, &str)] = &[("ledger", "ledgers", "/ledger/")];
+
+pub async fn jsonapi_middleware(request: Request, next: Next) -> Response {
+    if !accepts_jsonapi(request.headers()) {
+        return next.run(request).await;
+    }
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string());
+    let path = request.uri().path().to_string();
+    let self_link = request
+        .uri()
+        .path_and_query()
+        .map(|target| target.as_str().to_string())
+        .unwrap_or_else(|| path.clone());
+
+    let response = next.run(request).await;
+    if !is_json(response.headers()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let Ok(payload) = serde_json::from_slice::<Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let document = if parts.status.is_success() {
+        let resource = route
+            .as_deref()
+            .and_then(|route| Resource::identify(route, &path));
+        match (resource, payload) {
+            (Some(resource), Value::Object(attributes)) => {
+                resource.document(attributes, &self_link)
+            }
+            (_, payload) => json!({
+                "meta": payload,
+                "links": { "self": self_link },
+            }),
+        }
+    } else {
+        error_document(parts.status.as_u16(), payload)
+    };
+    let Ok(encoded) = serde_json::to_vec(&document) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(JSONAPI_CONTENT_TYPE),
+    );
+    Response::from_parts(parts, Body::from(encoded))
+}
+
+/// The resource a request addressed, worked out from its route pattern and
+/// the concrete path.
+#[derive(Debug, PartialEq)]
+struct Resource {
+    kind: String,
+    id: String,
+    /// The resource this one belongs to, with its link.
+    parent: Option<(String, String, String)>,
+}
+
+impl Resource {
+    /// `None` for routes without path parameters.
+    fn identify(route: &str, path: &str) -> Option<Self> {
+        let route_segments: Vec<&str> = route.split('/').collect();
+        let path_segments: Vec<&str> = path.split('/').collect();
+        if route_segments.len() != path_segments.len() {
+            return None;
+        }
+        let ids: Vec<&str> = route_segments
+            .iter()
+            .zip(&path_segments)
+            .filter(|(pattern, _)| pattern.starts_with(':'))
+            .map(|(_, value)| *value)
+            .collect();
+        if ids.is_empty() {
+            return None;
+        }
+
+        if let Some((_, kind)) = RESOURCE_TYPES.iter().find(|(prefix, _)| *prefix == route) {
+            return Some(Self {
+                kind: kind.to_string(),
+                id: ids.join(":"),
+                parent: None,
+            });
+        }
+
+        // A sub-resource, typed after its route and related to its parent.
+        let kind = route_segments
+            .iter()
+            .filter(|segment| !segment.is_empty() && !segment.starts_with(':'))
+            .copied()
+            .collect::<Vec<_>>()
+            .join("-");
+        let parent = RESOURCE_TYPES.iter().find_map(|(prefix, kind)| {
+            let depth = prefix.split('/').count();
+            (route.starts_with(&format!("{prefix}/")) && path_segments.len() > depth).then(|| {
+                (
+                    kind.to_string(),
+                    path_segments[depth - 1].to_string(),
+                    path_segments[..depth].join("/"),
+                )
+            })
+        });
+        Some(Self {
+            kind,
+            id: ids.join(":"),
+            parent,
+        })
+    }
+
+    fn document(self, mut attributes: Map<String, Value>, self_link: &str) -> Value {
+        // `id` and `type` are reserved members of a resource object.
+        let mut meta = Map::new();
+        for reserved in ["id", "type"] {
+            if let Some(value) = attributes.remove(reserved) {
+                meta.insert(reserved.to_string(), value);
+            }
+        }
+
+        let mut relationships = Map::new();
+        if let Some((kind, id, link)) = self.parent {
+            let name = kind.trim_end_matches('s').to_string();
+            relationships.insert(name, relationship(&kind, &id, &link));
+        }
+        for (attribute, kind, prefix) in RELATED_ATTRIBUTES {
+            let id = match attributes.get(*attribute) {
+                Some(Value::Number(id)) => id.to_string(),
+                Some(Value::String(id)) => id.clone(),
+                _ => continue,
+            };
+            relationships.insert(
+                attribute.to_string(),
+                relationship(kind, &id, &format!("{prefix}{id}")),
+            );
+        }
+
+        let mut resource = json!({
+            "type": self.kind,
+            "id": self.id,
+            "attributes": attributes,
+        });
+        if !relationships.is_empty() {
+            resource["relationships"] = Value::Object(relationships);
+        }
+        if !meta.is_empty() {
+            resource["meta"] = Value::Object(meta);
+        }
+        json!({
+            "data": resource,
+            "links": { "self": self_link },
+        })
+    }
+}
+
+fn relationship(kind: &str, id: &str, link: &str) -> Value {
+    json!({
+        "data": { "type": kind, "id": id },
+        "links": { "related": link },
+    })
+}
+
+/// `{"error": {"code", "message", "details"}}` as a JSON:API error document.
+fn error_document(status: u16, payload: Value) -> Value {
+    let error = payload.get("error").cloned().unwrap_or(Value::Null);
+    let mut object = json!({ "status": status.to_string() });
+    if let Some(code) = error.get("code") {
+        object["code"] = code.clone();
+    }
+    if let Some(message) = error.get("message") {
+        object["detail"] = message.clone();
+    }
+    if let Some(details) = error.get("details") {
+        object["meta"] = details.clone();
+    }
+    json!({ "errors": [object] })
+}
+
+fn accepts_jsonapi(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|accept| {
+            accept.split(',').any(|media_type| {
+                media_type
+                    .split(';')
+                    .next()
+                    .unwrap_or("")
+                    .trim()
+                    .eq_ignore_ascii_case(JSONAPI_CONTENT_TYPE)
+            })
+        })
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Json, Router, http::StatusCode, middleware::from_fn, routing::get};
+
+    #[test]
+    fn test_sub_resources_relate_to_their_parent() {
+        assert_eq!(
+            Resource::identify("/tx/:hash/timeline", "/tx/abc/timeline"),
+            Some(Resource {
+                kind: "tx-timeline".to_string(),
+                id: "abc".to_string(),
+                parent: Some((
+                    "transactions".to_string(),
+                    "abc".to_string(),
+                    "/tx/abc".to_string()
+                )),
+            })
+        );
+        assert_eq!(
+            Resource::identify("/asset/:code/:issuer/audit", "/asset/USDC/GA/audit")
+                .map(|resource| resource.id),
+            Some("USDC:GA".to_string())
+        );
+        assert_eq!(Resource::identify("/search", "/search"), None);
+    }
+
+    async fn spawn() -> String {
+        let app = Router::new()
+            .route(
+                "/tx/:hash",
+                get(|| async { Json(json!({ "transaction_hash": "abc", "ledger": 7 })) }),
+            )
+            .route("/search", get(|| async { Json(json!([1, 2])) }))
+            .route(
+                "/ledger/:sequence",
+                get(|| async {
+                    (
+                        StatusCode::NOT_FOUND,
+                        Json(json!({ "error": { "code": "NOT_FOUND", "message": "gone" } })),
+                    )
+                }),
+            )
+            .layer(from_fn(jsonapi_middleware));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}")
+    }
+
+    async fn get_jsonapi(url: String) -> (String, Value) {
+        let response = reqwest::Client::new()
+            .get(url)
+            .header(header::ACCEPT, JSONAPI_CONTENT_TYPE)
+            .send()
+            .await
+            .unwrap();
+        let content_type = response.headers()[header::CONTENT_TYPE]
+            .to_str()
+            .unwrap()
+            .to_string();
+        (content_type, response.json().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_resources_errors_and_meta_documents() {
+        let base = spawn().await;
+
+        let (content_type, document) = get_jsonapi(format!("{base}/tx/abc")).await;
+        assert_eq!(content_type, JSONAPI_CONTENT_TYPE);
+        assert_eq!(
+            document,
+            json!({
+                "data": {
+                    "type": "transactions",
+                    "id": "abc",
+                    "attributes": { "transaction_hash": "abc", "ledger": 7 },
+                    "relationships": {
+                        "ledger": {
+                            "data": { "type": "ledgers", "id": "7" },
+                            "links": { "related": "/ledger/7" }
+                        }
+                    }
+                },
+                "links": { "self": "/tx/abc" }
+            })
+        );
+
+        let (_, document) = get_jsonapi(format!("{base}/search?q=x")).await;
+        assert_eq!(
+            document,
+            json!({ "meta": [1, 2], "links": { "self": "/search?q=x" } })
+        );
+
+        let (_, document) = get_jsonapi(format!("{base}/ledger/9")).await;
+        assert_eq!(
+            document,
+            json!({ "errors": [{ "status": "404", "code": "NOT_FOUND", "detail": "gone" }] })
+        );
+
+        let plain: Value = reqwest::get(format!("{base}/tx/abc"))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(plain, json!({ "transaction_hash": "abc", "ledger": 7 }));
+    }
+}
@@ -1 +1,10 @@
+pub mod audit;
+pub mod envelope;
+pub mod fields;
+pub mod jsonapi;
+pub mod msgpack;
+pub mod rate_limit;
+pub mod receipt;
 pub mod request_id;
+pub mod slo;
+pub mod timeout;
@@ -0,0 +1,153 @@
+//! MessagePack output for clients that ask for it.
+//!
+//! Handlers always produce JSON. When a request's `Accept` header lists
+//! `application/msgpack` ahead of JSON, this middleware transcodes the JSON
+//! body (envelope included) to MessagePack with field names kept, so the
+//! payload decodes to the same shape. Everything else is left untouched.
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::Request,
+    http::{HeaderMap, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+
+pub const MSGPACK_CONTENT_TYPE: &str = "application/msgpack";
+
+pub async fn msgpack_middleware(request: Request, next: Next) -> Response {
+    let wanted = prefers_msgpack(request.headers());
+
+    let mut response = next.run(request).await;
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_static("accept"));
+    if !wanted || !is_json(response.headers()) {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let Ok(value) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    let Ok(packed) = rmp_serde::to_vec_named(&value) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    parts.headers.remove(header::CONTENT_LENGTH);
+    parts.headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(MSGPACK_CONTENT_TYPE),
+    );
+    Response::from_parts(parts, Body::from(packed))
+}
+
+/// True when `Accept` lists a MessagePack type before any JSON type.
+/// Quality values are not weighed; clients list their preference first.
+fn prefers_msgpack(headers: &HeaderMap) -> bool {
+    let Some(accept) = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    for media_type in accept.split(',') {
+        let media_type = media_type.split(';').next().unwrap_or("").trim();
+        match media_type.to_ascii_lowercase().as_str() {
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                return true;
+            }
+            "application/json" | "application/*" | "*/*" => return false,
+            _ => {}
+        }
+    }
+    false
+}
+
+fn is_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Json, Router, middleware::from_fn, routing::get};
+    use serde_json::{Value, json};
+
+    async fn spawn() -> String {
+        let app = Router::new()
+            .route(
+                "/payload",
+                get(|| async { Json(json!({ "summary": "ok", "fee": 100 })) }),
+            )
+            .route("/text", get(|| async { "plain" }))
+            .layer(from_fn(msgpack_middleware));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}")
+    }
+
+    async fn get_with_accept(url: &str, accept: &str) -> reqwest::Response {
+        reqwest::Client::new()
+            .get(url)
+            .header("accept", accept)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    #[test]
+    fn test_prefers_msgpack_respects_order() {
+        let accept = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::ACCEPT, HeaderValue::from_static(value));
+            prefers_msgpack(&headers)
+        };
+
+        assert!(accept("application/msgpack"));
+        assert!(accept("application/x-msgpack;q=1.0, application/json"));
+        assert!(!accept("application/json, application/msgpack"));
+        assert!(!accept("*/*"));
+        assert!(!prefers_msgpack(&HeaderMap::new()));
+    }
+
+    #[tokio::test]
+    async fn transcodes_json_when_msgpack_is_accepted() {
+        let base = spawn().await;
+
+        let response = get_with_accept(&format!("{base}/payload"), MSGPACK_CONTENT_TYPE).await;
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE.as_str()],
+            MSGPACK_CONTENT_TYPE
+        );
+        let body = response.bytes().await.unwrap();
+        let decoded: Value = rmp_serde::from_slice(&body).unwrap();
+        assert_eq!(decoded, json!({ "summary": "ok", "fee": 100 }));
+
+        let response = get_with_accept(&format!("{base}/payload"), "application/json").await;
+        let decoded: Value = response.json().await.unwrap();
+        assert_eq!(decoded["summary"], "ok");
+    }
+
+    #[tokio::test]
+    async fn leaves_non_json_responses_alone() {
+        let base = spawn().await;
+
+        let response = get_with_accept(&format!("{base}/text"), MSGPACK_CONTENT_TYPE).await;
+        assert!(
+            response.headers()[header::CONTENT_TYPE.as_str()]
+                .to_str()
+                .unwrap()
+                .starts_with("text/plain")
+        );
+        assert_eq!(response.text().await.unwrap(), "plain");
+    }
+}
@@ -0,0 +1,312 @@
+//! Per-client request rate limits.
+//!
+//! Each client IP gets a token bucket: it may send `RATE_LIMIT_BURST`
+//! requests at once, refilled at `RATE_LIMIT_PER_SECOND`. A client over its
+//! limit gets a 429 `RATE_LIMITED` error with a `Retry-After` header, before
+//! the request reaches any handler or Horizon. Health checks are never
+//! limited, so probes keep working while a client is throttled.
+//!
+//! The client is the connection's peer address. Behind a reverse proxy every
+//! request comes from the proxy, so `RATE_LIMIT_TRUST_PROXY` keys on the
+//! last `X-Forwarded-For` hop instead. That is the address the proxy itself
+//! appended; earlier hops come from the client and are ignored, since a
+//! client could send a new one with every request. Only set it when a proxy
+//! you control appends to that header.
+//!
+//! Configuration (environment):
+//! - `RATE_LIMIT_PER_SECOND`: requests each client may make per second
+//!   (default off).
+//! - `RATE_LIMIT_BURST`: requests a client may make at once (default the
+//!   per-second rate).
+//! - `RATE_LIMIT_TRUST_PROXY`: `true` or `1` to key on the last
+//!   `X-Forwarded-For` hop (default off).
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, HeaderValue, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use governor::{
+    DefaultKeyedRateLimiter, Quota, RateLimiter,
+    clock::{Clock, DefaultClock},
+};
+use std::env;
+use std::net::{IpAddr, SocketAddr};
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::errors::AppError;
+use crate::middleware::request_id::RequestId;
+
+/// How often buckets of clients that have gone quiet are dropped.
+const CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitConfig {
+    pub per_second: NonZeroU32,
+    pub burst: NonZeroU32,
+    pub trust_proxy: bool,
+}
+
+impl RateLimitConfig {
+    /// Read the limits, or `None` when no per-second rate is set.
+    pub fn from_env() -> Option<Self> {
+        let positive = |name: &str| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .and_then(NonZeroU32::new)
+        };
+        let per_second = positive("RATE_LIMIT_PER_SECOND")?;
+        Some(Self {
+            per_second,
+            burst: positive("RATE_LIMIT_BURST").unwrap_or(per_second),
+            trust_proxy: env::var("RATE_LIMIT_TRUST_PROXY")
+                .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+                .unwrap_or(false),
+        })
+    }
+}
+
+/// The buckets of every client seen recently. Cheap to clone.
+#[derive(Clone)]
+pub struct RateLimit {
+    limiter: Arc<DefaultKeyedRateLimiter<IpAddr>>,
+    trust_proxy: bool,
+}
+
+impl RateLimit {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        let quota = Quota::per_second(config.per_second).allow_burst(config.burst);
+        Self {
+            limiter: Arc::new(RateLimiter::keyed(quota)),
+            trust_proxy: config.trust_proxy,
+        }
+    }
+
+    /// The client a request is counted against, or `None` when it cannot be
+    /// told apart from any other (no connection info and no trusted header).
+    /// Only the last forwarded hop is trusted: the proxy appends the address
+    /// it saw, so anything before it is whatever the client sent.
+    fn client(&self, headers: &HeaderMap, peer: Option<SocketAddr>) -> Option<IpAddr> {
+        let forwarded = self
+            .trust_proxy
+            .then(|| {
+                headers
+                    .get("x-forwarded-for")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.rsplit(',').next())
+                    .and_then(|v| v.trim().parse::<IpAddr>().ok())
+            })
+            .flatten();
+        forwarded.or(peer.map(|peer| peer.ip()))
+    }
+
+    /// Take one request from `client`'s bucket, or say how long until it
+    /// has one again.
+    fn check(&self, client: IpAddr) -> Result<(), Duration> {
+        self.limiter
+            .check_key(&client)
+            .map_err(|not_until| not_until.wait_time_from(DefaultClock::default().now()))
+    }
+}
+
+/// Drop the buckets of clients that are back to a full burst, so the limiter
+/// does not grow with every address that has ever connected.
+pub async fn run_cleanup(rate_limit: RateLimit) {
+    let mut interval = tokio::time::interval(CLEANUP_INTERVAL);
+    loop {
+        interval.tick().await;
+        rate_limit.limiter.retain_recent();
+        rate_limit.limiter.shrink_to_fit();
+    }
+}
+
+pub async fn rate_limit_middleware(
+    State(rate_limit): State<RateLimit>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.uri().path().starts_with("/health") {
+        return next.run(request).await;
+    }
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| *addr);
+    let Some(client) = rate_limit.client(request.headers(), peer) else {
+        return next.run(request).await;
+    };
+
+    match rate_limit.check(client) {
+        Ok(()) => next.run(request).await,
+        Err(wait) => {
+            // Whole seconds, rounded up so a client that waits is let in.
+            let retry_after_secs = wait.as_secs() + u64::from(wait.subsec_nanos() > 0);
+            let request_id = request.extensions().get::<RequestId>().cloned();
+            warn!(
+                request_id = request_id.map(|id| id.to_string()),
+                path = %request.uri().path(),
+                client = %client,
+                retry_after_secs,
+                "rate_limited"
+            );
+            let mut response = AppError::RateLimited(format!(
+                "Too many requests. Retry in {retry_after_secs} seconds."
+            ))
+            .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Router, body::Body, http::StatusCode, middleware::from_fn_with_state, routing::get,
+    };
+    use tower::Service;
+
+    fn app(trust_proxy: bool) -> Router {
+        let config = RateLimitConfig {
+            per_second: NonZeroU32::new(1).unwrap(),
+            burst: NonZeroU32::new(2).unwrap(),
+            trust_proxy,
+        };
+        Router::new()
+            .route("/tx/:hash", get(|| async { "ok" }))
+            .route("/health", get(|| async { "ok" }))
+            .layer(from_fn_with_state(
+                RateLimit::new(&config),
+                rate_limit_middleware,
+            ))
+    }
+
+    fn request(path: &str, peer: [u8; 4], forwarded_for: Option<&str>) -> Request {
+        let mut builder = Request::builder().uri(path);
+        if let Some(ip) = forwarded_for {
+            builder = builder.header("x-forwarded-for", ip);
+        }
+        let mut request = builder.body(Body::empty()).unwrap();
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from((peer, 40_000))));
+        request
+    }
+
+    /// A router is always ready, so it can be called without `poll_ready`.
+    async fn send(app: &Router, request: Request) -> Response {
+        app.clone().call(request).await.unwrap()
+    }
+
+    async fn statuses(app: &Router, requests: Vec<Request>) -> Vec<StatusCode> {
+        let mut statuses = Vec::new();
+        for request in requests {
+            statuses.push(send(app, request).await.status());
+        }
+        statuses
+    }
+
+    #[tokio::test]
+    async fn test_client_over_its_burst_is_rate_limited() {
+        let app = app(false);
+        let statuses = statuses(
+            &app,
+            (0..3)
+                .map(|_| request("/tx/abc", [10, 0, 0, 1], None))
+                .collect(),
+        )
+        .await;
+        assert_eq!(
+            statuses,
+            [
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::TOO_MANY_REQUESTS
+            ]
+        );
+
+        let response = send(&app, request("/tx/abc", [10, 0, 0, 1], None)).await;
+        assert_eq!(response.headers()[header::RETRY_AFTER], "1");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error"]["code"], "RATE_LIMITED");
+    }
+
+    #[tokio::test]
+    async fn test_clients_and_health_checks_are_counted_separately() {
+        let app = app(false);
+        let mut requests: Vec<Request> = (0..3)
+            .map(|_| request("/tx/abc", [10, 0, 0, 1], None))
+            .collect();
+        requests.push(request("/tx/abc", [10, 0, 0, 2], None));
+        requests.push(request("/health", [10, 0, 0, 1], None));
+        assert_eq!(
+            statuses(&app, requests).await,
+            [
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::TOO_MANY_REQUESTS,
+                StatusCode::OK,
+                StatusCode::OK
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_forwarded_for_is_only_used_when_trusted() {
+        // Behind a proxy every peer is the proxy; the header tells clients apart.
+        let proxied = |ip| request("/tx/abc", [10, 0, 0, 9], Some(ip));
+
+        let trusted = app(true);
+        let requests = vec![
+            proxied("203.0.113.1"),
+            proxied("203.0.113.1"),
+            proxied("203.0.113.2"),
+        ];
+        assert_eq!(
+            statuses(&trusted, requests).await,
+            [StatusCode::OK, StatusCode::OK, StatusCode::OK]
+        );
+
+        // A client-supplied hop before the proxy's is not a new client.
+        let trusted = app(true);
+        let requests = vec![
+            proxied("198.51.100.1, 203.0.113.1"),
+            proxied("198.51.100.2, 203.0.113.1"),
+            proxied("198.51.100.3, 203.0.113.1"),
+        ];
+        assert_eq!(
+            statuses(&trusted, requests).await,
+            [
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::TOO_MANY_REQUESTS
+            ]
+        );
+
+        let untrusted = app(false);
+        let requests = vec![
+            proxied("203.0.113.1"),
+            proxied("203.0.113.2"),
+            proxied("203.0.113.3"),
+        ];
+        assert_eq!(
+            statuses(&untrusted, requests).await,
+            [
+                StatusCode::OK,
+                StatusCode::OK,
+                StatusCode::TOO_MANY_REQUESTS
+            ]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Attaches signed receipts to transaction explanations.
+//!
+//! Clients ask for a receipt with `X-Explanation-Receipt: true` on
+//! `GET /tx/:hash`. When a signing key is configured, the successful JSON
+//! explanation is signed (see [`crate::services::receipt`]) and the receipt
+//! returned in response headers, leaving the body untouched:
+//!
+//! - `X-Receipt-Signature`: hex Ed25519 signature.
+//! - `X-Receipt-Key-Id`: the signing key's `G...` ID, published at `/keys`.
+//! - `X-Receipt-Signed-At`: the RFC 3339 time that was signed.
+//! - `X-Receipt-Network`: the network that was signed.
+//!
+//! This layer sits inside the response envelope, so the signed explanation
+//! is the envelope's `data`.
+
+use axum::{
+    body::{Body, to_bytes},
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, header},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+
+use crate::services::receipt::ReceiptSigner;
+
+/// Request header that asks for a receipt when set to "true".
+pub const RECEIPT_HEADER: &str = "x-explanation-receipt";
+
+pub const SIGNATURE_HEADER: &str = "x-receipt-signature";
+pub const KEY_ID_HEADER: &str = "x-receipt-key-id";
+pub const SIGNED_AT_HEADER: &str = "x-receipt-signed-at";
+pub const NETWORK_HEADER: &str = "x-receipt-network";
+
+#[derive(Clone)]
+pub struct ReceiptContext {
+    pub signer: Arc<ReceiptSigner>,
+    /// Network name included in every signature, e.g. "public".
+    pub network: String,
+}
+
+/// Whether `path` is a single transaction explanation.
+fn is_explanation_path(path: &str) -> bool {
+    path.strip_prefix("/tx/")
+        .is_some_and(|hash| !hash.is_empty() && !hash.contains('/') && hash != "compare")
+}
+
+fn requested(headers: &HeaderMap) -> bool {
+    headers
+        .get(RECEIPT_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.trim().eq_ignore_ascii_case("true"))
+}
+
+pub async fn receipt_middleware(
+    State(context): State<ReceiptContext>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let wanted = requested(request.headers()) && is_explanation_path(request.uri().path());
+
+    let response = next.run(request).await;
+    let is_json = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/json"));
+    if !wanted || !response.status().is_success() || !is_json {
+        return response;
+    }
+
+    let (mut parts, body) = response.into_parts();
+    let Ok(bytes) = to_bytes(body, usize::MAX).await else {
+        return Response::from_parts(parts, Body::empty());
+    };
+    let Ok(explanation) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+
+    let signed_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let signature = context
+        .signer
+        .sign(&explanation, &context.network, &signed_at);
+    for (name, value) in [
+        (SIGNATURE_HEADER, signature.as_str()),
+        (KEY_ID_HEADER, context.signer.key_id()),
+        (SIGNED_AT_HEADER, signed_at.as_str()),
+        (NETWORK_HEADER, context.network.as_str()),
+    ] {
+        if let Ok(value) = HeaderValue::from_str(value) {
+            parts.headers.insert(HeaderName::from_static(name), value);
+        }
+    }
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::receipt::verify_receipt;
+    use axum::{Json, Router, middleware::from_fn_with_state, routing::get};
+    use serde_json::{Value, json};
+
+    async fn spawn() -> (String, String) {
+        let seed = stellar_strkey::ed25519::PrivateKey([7; 32]).to_string();
+        let signer = Arc::new(ReceiptSigner::from_seed(&seed).unwrap());
+        let key_id = signer.key_id().to_string();
+        let context = ReceiptContext {
+            signer,
+            network: "testnet".to_string(),
+        };
+        let explanation = || async { Json(json!({ "transaction_hash": "abc", "summary": "ok" })) };
+        let app = Router::new()
+            .route("/tx/:hash", get(explanation))
+            .route("/tx/:hash/timeline", get(explanation))
+            .layer(from_fn_with_state(context, receipt_middleware));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{addr}"), key_id)
+    }
+
+    fn header<'a>(response: &'a reqwest::Response, name: &str) -> Option<&'a str> {
+        response
+            .headers()
+            .get(name)
+            .map(|value| value.to_str().unwrap())
+    }
+
+    #[test]
+    fn signs_only_single_explanations() {
+        assert!(is_explanation_path("/tx/abc"));
+        assert!(!is_explanation_path("/tx/abc/timeline"));
+        assert!(!is_explanation_path("/tx/compare"));
+        assert!(!is_explanation_path("/account/GABC"));
+    }
+
+    #[tokio::test]
+    async fn requested_receipts_verify_against_the_body() {
+        let (base, key_id) = spawn().await;
+
+        let response = reqwest::Client::new()
+            .get(format!("{base}/tx/abc"))
+            .header(RECEIPT_HEADER, "true")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(header(&response, KEY_ID_HEADER), Some(key_id.as_str()));
+        assert_eq!(header(&response, NETWORK_HEADER), Some("testnet"));
+        let signature = header(&response, SIGNATURE_HEADER).unwrap().to_string();
+        let signed_at = header(&response, SIGNED_AT_HEADER).unwrap().to_string();
+        let body: Value = response.json().await.unwrap();
+
+        assert!(verify_receipt(
+            &key_id, &body, "testnet", &signed_at, &signature
+        ));
+    }
+
+    #[tokio::test]
+    async fn receipts_are_opt_in() {
+        let (base, _) = spawn().await;
+
+        let plain = reqwest::get(format!("{base}/tx/abc")).await.unwrap();
+        assert_eq!(header(&plain, SIGNATURE_HEADER), None);
+
+        let timeline = reqwest::Client::new()
+            .get(format!("{base}/tx/abc/timeline"))
+            .header(RECEIPT_HEADER, "true")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(header(&timeline, SIGNATURE_HEADER), None);
+    }
+}
@@ -0,0 +1,67 @@
+//! Count every routed request toward its route's SLOs.
+//!
+//! Requests are grouped by route pattern, so `/tx/abc` and `/tx/def` both
+//! count toward `/tx/:hash`. Requests that match no route are not counted.
+//! Sits outside the route timeouts so timed-out requests count with their
+//! 504.
+
+use axum::{
+    extract::{MatchedPath, Request, State},
+    middleware::Next,
+    response::Response,
+};
+use std::sync::Arc;
+use std::time::Instant;
+
+use crate::services::slo::SloTracker;
+
+pub async fn slo_middleware(
+    State(tracker): State<Arc<SloTracker>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(route) = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|path| path.as_str().to_string())
+    else {
+        return next.run(request).await;
+    };
+    let started_at = Instant::now();
+    let response = next.run(request).await;
+    tracker.record(&route, response.status().as_u16(), started_at.elapsed());
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::slo::SloConfig;
+    use axum::{Router, http::StatusCode, middleware::from_fn_with_state, routing::get};
+
+    #[tokio::test]
+    async fn test_requests_count_by_route_pattern() {
+        let tracker = Arc::new(SloTracker::new(SloConfig::default()));
+        let app = Router::new()
+            .route("/tx/:hash", get(|| async { "ok" }))
+            .route(
+                "/ledger/:sequence",
+                get(|| async { StatusCode::BAD_GATEWAY }),
+            )
+            .layer(from_fn_with_state(Arc::clone(&tracker), slo_middleware));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        for path in ["/tx/a", "/tx/b", "/ledger/1", "/nowhere"] {
+            reqwest::get(format!("http://{addr}{path}")).await.unwrap();
+        }
+
+        let report = tracker.report();
+        assert_eq!(report.routes.len(), 2);
+        assert_eq!(report.routes[0].route, "/ledger/:sequence");
+        assert_eq!(report.routes[0].errors, 1);
+        assert_eq!(report.routes[1].route, "/tx/:hash");
+        assert_eq!(report.routes[1].requests, 2);
+    }
+}
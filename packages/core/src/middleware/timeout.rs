@@ -0,0 +1,255 @@
+//! Deadlines for requests that wait on Horizon.
+//!
+//! A Horizon that accepts connections but answers slowly would otherwise
+//! hold requests open for as long as the HTTP client's own timeout, once per
+//! upstream call. This middleware gives each class of Horizon-bound route a
+//! deadline for the whole request; when it passes, the handler is dropped,
+//! cancelling its upstream calls, and the client gets a 504
+//! `GATEWAY_TIMEOUT` error with the elapsed time in its details.
+//!
+//! Configuration (environment), in seconds:
+//! - `ROUTE_TIMEOUT_TX_SECONDS` for `/tx/...` and `/explain/...` (default 10).
+//! - `ROUTE_TIMEOUT_ACCOUNT_SECONDS` for `/account/...` and `/accounts/related`
+//!   (default 15).
+//! - `ROUTE_TIMEOUT_BATCH_SECONDS` for `/accounts/batch`, which looks up as
+//!   many as 25 accounts in one request (default 30).
+//! - `ROUTE_TIMEOUT_LOOKUP_SECONDS` for `/asset/...`, `/check`,
+//!   `/claimable-balance/...`, `/fees/...`, `/ledger/...`,
+//!   `/liquidity-pool/...` and `/market/...` (default 10).
+//!
+//! Other routes (health, docs, schemas, search, stats) do not call Horizon
+//! on the request path, or bound their own calls, and have no deadline.
+
+use axum::{
+    extract::{Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::env;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+use crate::errors::AppError;
+use crate::middleware::request_id::RequestId;
+
+/// Groups of routes that share a deadline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouteClass {
+    Transaction,
+    Account,
+    Batch,
+    Lookup,
+}
+
+impl RouteClass {
+    /// The class of a request path, or None for routes without a deadline.
+    pub fn of(path: &str) -> Option<Self> {
+        if path.starts_with("/tx/") || path.starts_with("/explain/") {
+            Some(RouteClass::Transaction)
+        } else if path.starts_with("/account/") || path.starts_with("/accounts/related") {
+            Some(RouteClass::Account)
+        } else if path.starts_with("/accounts/batch") {
+            Some(RouteClass::Batch)
+        } else if path == "/check"
+            || [
+                "/asset/",
+                "/claimable-balance/",
+                "/fees/",
+                "/ledger/",
+                "/liquidity-pool/",
+                "/market/",
+            ]
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+        {
+            Some(RouteClass::Lookup)
+        } else {
+            None
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            RouteClass::Transaction => "transaction",
+            RouteClass::Account => "account",
+            RouteClass::Batch => "batch",
+            RouteClass::Lookup => "lookup",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeoutConfig {
+    pub transaction: Duration,
+    pub account: Duration,
+    pub batch: Duration,
+    pub lookup: Duration,
+}
+
+impl Default for TimeoutConfig {
+    fn default() -> Self {
+        Self {
+            transaction: Duration::from_secs(10),
+            account: Duration::from_secs(15),
+            batch: Duration::from_secs(30),
+            lookup: Duration::from_secs(10),
+        }
+    }
+}
+
+impl TimeoutConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let seconds = |name: &str, default: Duration| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(default)
+        };
+        Self {
+            transaction: seconds("ROUTE_TIMEOUT_TX_SECONDS", defaults.transaction),
+            account: seconds("ROUTE_TIMEOUT_ACCOUNT_SECONDS", defaults.account),
+            batch: seconds("ROUTE_TIMEOUT_BATCH_SECONDS", defaults.batch),
+            lookup: seconds("ROUTE_TIMEOUT_LOOKUP_SECONDS", defaults.lookup),
+        }
+    }
+
+    pub fn deadline(&self, class: RouteClass) -> Duration {
+        match class {
+            RouteClass::Transaction => self.transaction,
+            RouteClass::Account => self.account,
+            RouteClass::Batch => self.batch,
+            RouteClass::Lookup => self.lookup,
+        }
+    }
+}
+
+pub async fn timeout_middleware(
+    State(config): State<TimeoutConfig>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(class) = RouteClass::of(request.uri().path()) else {
+        return next.run(request).await;
+    };
+    let deadline = config.deadline(class);
+    let path = request.uri().path().to_string();
+    let request_id = request.extensions().get::<RequestId>().cloned();
+    let started_at = Instant::now();
+
+    match tokio::time::timeout(deadline, next.run(request)).await {
+        Ok(response) => response,
+        Err(_) => {
+            let app_error = AppError::GatewayTimeout {
+                elapsed_ms: started_at.elapsed().as_millis() as u64,
+                timeout_ms: deadline.as_millis() as u64,
+            };
+            warn!(
+                request_id = request_id.map(|id| id.to_string()),
+                path = %path,
+                route_class = class.name(),
+                status = app_error.status_code().as_u16(),
+                total_duration_ms = started_at.elapsed().as_millis() as u64,
+                "request_timed_out"
+            );
+            app_error.into_response()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, http::StatusCode, middleware::from_fn_with_state, routing::get};
+    use serde_json::Value;
+
+    async fn spawn(config: TimeoutConfig) -> String {
+        let slow = || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            "done"
+        };
+        let app = Router::new()
+            .route("/tx/:hash", get(slow))
+            .route("/account/:address", get(|| async { "fast" }))
+            .route("/search", get(slow))
+            .layer(from_fn_with_state(config, timeout_middleware));
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{addr}")
+    }
+
+    fn short() -> TimeoutConfig {
+        TimeoutConfig {
+            transaction: Duration::from_millis(100),
+            account: Duration::from_millis(100),
+            batch: Duration::from_millis(100),
+            lookup: Duration::from_millis(100),
+        }
+    }
+
+    #[test]
+    fn classifies_horizon_bound_routes() {
+        assert_eq!(RouteClass::of("/tx/abc"), Some(RouteClass::Transaction));
+        assert_eq!(RouteClass::of("/tx/compare"), Some(RouteClass::Transaction));
+        assert_eq!(
+            RouteClass::of("/explain/bundle"),
+            Some(RouteClass::Transaction)
+        );
+        assert_eq!(
+            RouteClass::of("/account/GABC/offers"),
+            Some(RouteClass::Account)
+        );
+        assert_eq!(RouteClass::of("/accounts/batch"), Some(RouteClass::Batch));
+        assert_eq!(
+            RouteClass::of("/accounts/related"),
+            Some(RouteClass::Account)
+        );
+        assert_eq!(
+            RouteClass::of("/liquidity-pool/abc"),
+            Some(RouteClass::Lookup)
+        );
+        assert_eq!(RouteClass::of("/ledger/500"), Some(RouteClass::Lookup));
+        assert_eq!(RouteClass::of("/fees/advise"), Some(RouteClass::Lookup));
+        assert_eq!(RouteClass::of("/check"), Some(RouteClass::Lookup));
+        assert_eq!(
+            RouteClass::of("/market/XLM/USDC:GISSUER/summary"),
+            Some(RouteClass::Lookup)
+        );
+        assert_eq!(RouteClass::of("/health/ready"), None);
+        assert_eq!(RouteClass::of("/search"), None);
+    }
+
+    #[tokio::test]
+    async fn slow_requests_time_out_with_504() {
+        let base = spawn(short()).await;
+
+        let started = Instant::now();
+        let response = reqwest::get(format!("{base}/tx/abc")).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert!(started.elapsed() < Duration::from_secs(2));
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["error"]["code"], "GATEWAY_TIMEOUT");
+        assert_eq!(body["error"]["details"]["timeout_ms"], 100);
+        assert!(body["error"]["details"]["elapsed_ms"].as_u64().unwrap() >= 100);
+    }
+
+    #[tokio::test]
+    async fn fast_and_unclassified_requests_pass_through() {
+        let base = spawn(short()).await;
+
+        let fast = reqwest::get(format!("{base}/account/GABC")).await.unwrap();
+        assert_eq!(fast.status(), StatusCode::OK);
+
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(300))
+            .build()
+            .unwrap();
+        // No deadline: still running when the client gives up.
+        assert!(client.get(format!("{base}/search")).send().await.is_err());
+    }
+}
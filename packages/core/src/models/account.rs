@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 pub struct Balance {
     pub asset_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -8,6 +8,24 @@ pub struct Balance {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub asset_issuer: Option<String>,
     pub balance: String,
+    /// Trust limit for this asset. Absent for the native XLM balance.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<String>,
+    /// Amount reserved by open buy offers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buying_liabilities: Option<String>,
+    /// Amount reserved by open sell offers.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selling_liabilities: Option<String>,
+    /// Whether the issuer has authorized this trust line. Absent for XLM.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_authorized: Option<bool>,
+    /// Whether the trust line may keep existing offers but not receive new funds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_authorized_to_maintain_liabilities: Option<bool>,
+    /// Liquidity pool ID for `liquidity_pool_shares` balances.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub liquidity_pool_id: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -24,8 +42,51 @@ pub struct Account {
     pub account_id: String,
     pub sequence: String,
     pub num_signers: u32,
+    /// Every signer with a weight, including the account's own key.
+    #[serde(default)]
+    pub signers: Vec<Signer>,
+    /// Weight of the account's own key; 0 means the master key is disabled.
+    #[serde(default)]
+    pub master_key_weight: u32,
+    /// Signing weight each threshold level needs.
+    #[serde(default)]
+    pub thresholds: Thresholds,
     pub balances: Vec<Balance>,
     pub flags: AccountFlags,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub home_domain: Option<String>,
+    /// The account asks senders to include a memo (SEP-29), as exchanges
+    /// that pool deposits do.
+    #[serde(default)]
+    pub memo_required: bool,
+}
+
+/// The signing weight operations of each level need. Most operations are
+/// medium; 0 means any single signer will do.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Thresholds {
+    pub low: u32,
+    pub medium: u32,
+    pub high: u32,
+}
+
+/// A key allowed to sign for an account.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Signer {
+    pub key: String,
+    pub weight: u32,
+    /// Account paying the reserve for this signer, if sponsored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor: Option<String>,
+}
+
+/// Who can sign for an account and who pays its reserves.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AccountLinks {
+    pub account_id: String,
+    /// Every signer, including the account's own key.
+    pub signers: Vec<Signer>,
+    /// Account paying this account's base reserve, if sponsored.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sponsor: Option<String>,
 }
@@ -0,0 +1,297 @@
+//! Exact Stellar amounts.
+//!
+//! Every amount on the network is a whole number of stroops, one ten-millionth
+//! of a unit, and Horizon writes them as decimals with up to seven places.
+//! `Amount` keeps the stroops as an integer so amounts can be added, compared,
+//! and converted without the rounding an `f64` brings above 2^53 stroops.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::iter::Sum;
+use std::ops::{Add, Neg, Sub};
+use std::str::FromStr;
+
+/// Stroops in one unit of an asset (XLM or any issued asset).
+pub const STROOPS_PER_UNIT: i128 = 10_000_000;
+
+/// Decimal places in a Stellar amount.
+pub const DECIMALS: usize = 7;
+
+/// An amount as a whole number of stroops.
+///
+/// Parses from and displays as Horizon's decimal form: `"12.5"` parses to
+/// 125000000 stroops and displays as `"12.5000000"`. Serialized as that
+/// string, so stored and returned JSON is unchanged.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Amount(i128);
+
+impl Amount {
+    pub const ZERO: Self = Self(0);
+
+    pub const fn from_stroops(stroops: i128) -> Self {
+        Self(stroops)
+    }
+
+    pub const fn stroops(self) -> i128 {
+        self.0
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.0.checked_add(other.0).map(Self)
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.0.checked_sub(other.0).map(Self)
+    }
+
+    pub fn saturating_add(self, other: Self) -> Self {
+        Self(self.0.saturating_add(other.0))
+    }
+
+    /// This amount as a fraction of `whole`, or None when `whole` is zero.
+    /// Only for display: the ratio of two amounts is not itself an amount.
+    pub fn ratio(self, whole: Self) -> Option<f64> {
+        (whole.0 != 0).then(|| self.0 as f64 / whole.0 as f64)
+    }
+
+    /// The decimal form without trailing zeros: `"12.5"`, `"100"`.
+    pub fn trimmed(self) -> String {
+        let full = self.to_string();
+        let trimmed = full.trim_end_matches('0').trim_end_matches('.');
+        trimmed.to_string()
+    }
+}
+
+/// Why a string is not an amount.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AmountError {
+    /// Not a plain decimal number, e.g. empty, `"1e5"`, or `"Unknown"`.
+    Invalid(String),
+    /// More than seven decimal places, which no Stellar amount has.
+    TooPrecise(String),
+    /// Too large to hold.
+    Overflow(String),
+}
+
+impl fmt::Display for AmountError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AmountError::Invalid(s) => write!(f, "{s:?} is not a decimal amount"),
+            AmountError::TooPrecise(s) => {
+                write!(f, "{s:?} has more than {DECIMALS} decimal places")
+            }
+            AmountError::Overflow(s) => write!(f, "{s:?} is too large"),
+        }
+    }
+}
+
+impl std::error::Error for AmountError {}
+
+impl FromStr for Amount {
+    type Err = AmountError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AmountError::Invalid(s.to_string());
+        let trimmed = s.trim();
+        let (negative, unsigned) = match trimmed.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        };
+        let (whole, fraction) = unsigned.split_once('.').unwrap_or((unsigned, ""));
+        let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+        if whole.is_empty() || !digits(whole) || !digits(fraction) {
+            return Err(invalid());
+        }
+        if fraction.len() > DECIMALS {
+            return Err(AmountError::TooPrecise(s.to_string()));
+        }
+
+        let overflow = || AmountError::Overflow(s.to_string());
+        let whole: i128 = whole.parse().map_err(|_| overflow())?;
+        let fraction: i128 = if fraction.is_empty() {
+            0
+        } else {
+            format!("{fraction:0<DECIMALS$}")
+                .parse()
+                .map_err(|_| invalid())?
+        };
+        let stroops = whole
+            .checked_mul(STROOPS_PER_UNIT)
+            .and_then(|w| w.checked_add(fraction))
+            .ok_or_else(overflow)?;
+        Ok(Self(if negative { -stroops } else { stroops }))
+    }
+}
+
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sign = if self.0 < 0 { "-" } else { "" };
+        let stroops = self.0.unsigned_abs();
+        let scale = STROOPS_PER_UNIT as u128;
+        write!(
+            f,
+            "{sign}{}.{:0DECIMALS$}",
+            stroops / scale,
+            stroops % scale
+        )
+    }
+}
+
+impl From<u64> for Amount {
+    /// An amount from a count of stroops, such as a fee.
+    fn from(stroops: u64) -> Self {
+        Self(i128::from(stroops))
+    }
+}
+
+impl From<i64> for Amount {
+    fn from(stroops: i64) -> Self {
+        Self(i128::from(stroops))
+    }
+}
+
+impl Add for Amount {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self(self.0 + other.0)
+    }
+}
+
+impl Sub for Amount {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self(self.0 - other.0)
+    }
+}
+
+impl Neg for Amount {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Sum for Amount {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn amount(s: &str) -> Amount {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_horizon_amounts() {
+        assert_eq!(amount("100.0000000").stroops(), 1_000_000_000);
+        assert_eq!(amount("12.5").stroops(), 125_000_000);
+        assert_eq!(amount("0.0000001").stroops(), 1);
+        assert_eq!(amount("42").stroops(), 420_000_000);
+        assert_eq!(amount("-2.5").stroops(), -25_000_000);
+        assert_eq!(amount(" 7 ").stroops(), 70_000_000);
+    }
+
+    #[test]
+    fn test_parse_rejects_non_amounts() {
+        for s in ["", ".5", "1e5", "Unknown", "1.2.3", "--1", "+1"] {
+            assert_eq!(
+                s.parse::<Amount>(),
+                Err(AmountError::Invalid(s.to_string()))
+            );
+        }
+        assert!(matches!(
+            "0.00000001".parse::<Amount>(),
+            Err(AmountError::TooPrecise(_))
+        ));
+        assert!(matches!(
+            "9".repeat(40).parse::<Amount>(),
+            Err(AmountError::Overflow(_))
+        ));
+    }
+
+    #[test]
+    fn test_display_and_trimmed() {
+        assert_eq!(amount("12.5").to_string(), "12.5000000");
+        assert_eq!(amount("12.5").trimmed(), "12.5");
+        assert_eq!(amount("100").trimmed(), "100");
+        assert_eq!(Amount::ZERO.trimmed(), "0");
+        assert_eq!(amount("-0.25").to_string(), "-0.2500000");
+        assert_eq!(Amount::from(100u64).to_string(), "0.0000100");
+    }
+
+    #[test]
+    fn test_arithmetic_is_exact() {
+        // 0.1 + 0.2 is not 0.3 in f64.
+        assert_eq!(amount("0.1") + amount("0.2"), amount("0.3"));
+        assert_eq!(amount("5") - amount("7.5"), amount("-2.5"));
+        assert_eq!(
+            [amount("1"), amount("2.25")].into_iter().sum::<Amount>(),
+            amount("3.25")
+        );
+        assert_eq!(
+            Amount::from_stroops(i128::MAX).checked_add(amount("0.0000001")),
+            None
+        );
+        // Above 2^53 stroops an f64 can no longer count single stroops.
+        let large = amount("922337203685.4775807");
+        assert_eq!(large.stroops(), i64::MAX as i128);
+        assert_eq!(
+            (large - Amount::from_stroops(1)).to_string(),
+            "922337203685.4775806"
+        );
+    }
+
+    #[test]
+    fn test_serde_uses_horizon_strings() {
+        let json = serde_json::to_string(&amount("1.5")).unwrap();
+        assert_eq!(json, "\"1.5000000\"");
+        assert_eq!(
+            serde_json::from_str::<Amount>("\"1.5\"").unwrap(),
+            amount("1.5")
+        );
+        assert!(serde_json::from_str::<Amount>("\"abc\"").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn display_round_trips(stroops in any::<i64>()) {
+            let amount = Amount::from(stroops);
+            prop_assert_eq!(amount.to_string().parse::<Amount>().unwrap(), amount);
+            prop_assert_eq!(amount.trimmed().parse::<Amount>().unwrap(), amount);
+        }
+
+        #[test]
+        fn parse_never_panics(s in any::<String>()) {
+            let _ = s.parse::<Amount>();
+        }
+    }
+}
@@ -0,0 +1,45 @@
+//! Issued assets and evidence about how their issuers behave.
+
+use serde::{Deserialize, Serialize};
+
+/// A clawback performed by an asset's issuer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Clawback {
+    /// The account the funds were taken from.
+    pub from: String,
+    pub amount: String,
+    pub transaction_hash: String,
+    pub created_at: Option<String>,
+}
+
+/// What the issuer's home domain says about an asset in its stellar.toml.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StellarTomlStatus {
+    /// The file lists the asset under `[[CURRENCIES]]`.
+    Listed,
+    /// The file is valid but does not list the asset.
+    Unlisted,
+    /// The file exists but is not valid TOML.
+    Invalid,
+    /// The domain answered but has no stellar.toml.
+    Missing,
+    /// The domain could not be reached over HTTPS, e.g. a TLS or DNS failure.
+    Unreachable,
+}
+
+/// Who a home domain's stellar.toml says runs it, and which accounts it
+/// claims.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TomlIdentity {
+    pub org_name: Option<String>,
+    /// The top-level `ACCOUNTS` list: accounts the organization controls.
+    pub accounts: Vec<String>,
+}
+
+impl TomlIdentity {
+    /// Whether the file lists `account` under `ACCOUNTS`.
+    pub fn lists_account(&self, account: &str) -> bool {
+        self.accounts.iter().any(|listed| listed == account)
+    }
+}
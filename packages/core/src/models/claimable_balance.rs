@@ -0,0 +1,78 @@
+//! Claimable balances and the predicates that control who may claim them.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A condition under which a claimant may claim a balance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ClaimPredicate {
+    Unconditional,
+    And(Vec<ClaimPredicate>),
+    Or(Vec<ClaimPredicate>),
+    Not(Box<ClaimPredicate>),
+    /// Claimable only before this ISO 8601 time.
+    AbsBefore(String),
+    /// Claimable only within this many seconds of the balance being created.
+    RelBefore(u64),
+}
+
+/// An account allowed to claim a balance, and when.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Claimant {
+    pub destination: String,
+    pub predicate: ClaimPredicate,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClaimableBalance {
+    /// Hex balance ID, e.g. "00000000da0d57da...".
+    pub id: String,
+    /// "native" or "CODE:ISSUER".
+    pub asset: String,
+    pub amount: String,
+    /// Account paying the balance's reserve.
+    pub sponsor: Option<String>,
+    pub claimants: Vec<Claimant>,
+    pub last_modified_ledger: Option<u64>,
+    /// Whether the issuer can still claw the balance back.
+    pub clawback_enabled: bool,
+}
+
+impl ClaimableBalance {
+    /// Asset code, "XLM" for the native asset.
+    pub fn asset_code(&self) -> &str {
+        match self.asset.split_once(':') {
+            Some((code, _)) => code,
+            None => "XLM",
+        }
+    }
+
+    pub fn asset_issuer(&self) -> Option<&str> {
+        self.asset.split_once(':').map(|(_, issuer)| issuer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_asset_parts() {
+        let mut balance = ClaimableBalance {
+            id: "00".to_string(),
+            asset: "USDC:GISSUER".to_string(),
+            amount: "1.0000000".to_string(),
+            sponsor: None,
+            claimants: vec![],
+            last_modified_ledger: None,
+            clawback_enabled: false,
+        };
+        assert_eq!(balance.asset_code(), "USDC");
+        assert_eq!(balance.asset_issuer(), Some("GISSUER"));
+
+        balance.asset = "native".to_string();
+        assert_eq!(balance.asset_code(), "XLM");
+        assert_eq!(balance.asset_issuer(), None);
+    }
+}
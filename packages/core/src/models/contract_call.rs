@@ -0,0 +1,186 @@
+//! The contract call a Soroban transaction makes, decoded from its envelope.
+//!
+//! A Soroban transaction holds one `invoke_host_function` operation. It
+//! invokes a contract function, deploys a contract, or uploads contract
+//! WASM. Its footprint, the ledger entries it may touch, names the WASM of
+//! every contract it runs by hash.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use stellar_xdr::curr::{
+    ContractExecutable, FeeBumpTransactionInnerTx, HostFunction, LedgerKey, OperationBody, ReadXdr,
+    TransactionEnvelope, TransactionExt,
+};
+
+use crate::models::contract_event::upstream_limits;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractCallKind {
+    Invoke,
+    Deploy,
+    UploadWasm,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractCall {
+    pub kind: ContractCallKind,
+    /// Strkey (C...) of the invoked contract.
+    pub contract_id: Option<String>,
+    /// Name of the invoked function.
+    pub function: Option<String>,
+    /// Hex hashes of the WASM the transaction runs, deploys, or uploads.
+    pub wasm_hashes: Vec<String>,
+}
+
+/// Decode the contract call from a base64 `TransactionEnvelope`; `None` when
+/// it does not decode or holds no `invoke_host_function` operation.
+pub fn decode_contract_call(envelope_xdr: &str) -> Option<ContractCall> {
+    let envelope =
+        TransactionEnvelope::from_xdr_base64(envelope_xdr, upstream_limits(envelope_xdr)).ok()?;
+    let tx = match envelope {
+        TransactionEnvelope::Tx(env) => env.tx,
+        TransactionEnvelope::TxFeeBump(env) => {
+            let FeeBumpTransactionInnerTx::Tx(inner) = env.tx.inner_tx;
+            inner.tx
+        }
+        // Version 0 envelopes predate Soroban.
+        TransactionEnvelope::TxV0(_) => return None,
+    };
+    let host_function = tx.operations.iter().find_map(|op| match &op.body {
+        OperationBody::InvokeHostFunction(invoke) => Some(invoke.host_function.clone()),
+        _ => None,
+    })?;
+
+    let mut wasm_hashes = Vec::new();
+    let (kind, contract_id, function) = match host_function {
+        HostFunction::InvokeContract(args) => (
+            ContractCallKind::Invoke,
+            Some(args.contract_address.to_string()),
+            Some(args.function_name.to_utf8_string_lossy()),
+        ),
+        HostFunction::CreateContract(args) => {
+            wasm_hashes.extend(executable_hash(&args.executable));
+            (ContractCallKind::Deploy, None, None)
+        }
+        HostFunction::CreateContractV2(args) => {
+            wasm_hashes.extend(executable_hash(&args.executable));
+            (ContractCallKind::Deploy, None, None)
+        }
+        HostFunction::UploadContractWasm(wasm) => {
+            wasm_hashes.push(hex(&Sha256::digest(wasm.as_slice())));
+            (ContractCallKind::UploadWasm, None, None)
+        }
+    };
+
+    if let TransactionExt::V1(data) = &tx.ext {
+        let footprint = &data.resources.footprint;
+        for key in footprint
+            .read_only
+            .iter()
+            .chain(footprint.read_write.iter())
+        {
+            if let LedgerKey::ContractCode(code) = key {
+                let hash = hex(&code.hash.0);
+                if !wasm_hashes.contains(&hash) {
+                    wasm_hashes.push(hash);
+                }
+            }
+        }
+    }
+
+    Some(ContractCall {
+        kind,
+        contract_id,
+        function,
+        wasm_hashes,
+    })
+}
+
+fn executable_hash(executable: &ContractExecutable) -> Option<String> {
+    match executable {
+        ContractExecutable::Wasm(hash) => Some(hex(&hash.0)),
+        ContractExecutable::StellarAsset => None,
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::models::contract_event::tests::contract_address;
+    use stellar_xdr::curr::{
+        Hash, InvokeContractArgs, InvokeHostFunctionOp, LedgerFootprint, LedgerKeyContractCode,
+        Limits, Memo, MuxedAccount, Operation, Preconditions, SequenceNumber, SorobanResources,
+        SorobanTransactionData, SorobanTransactionDataExt, Transaction, TransactionV1Envelope,
+        Uint256, WriteXdr,
+    };
+
+    /// A base64 envelope invoking `function` on the contract
+    /// `[contract; 32]`, whose WASM has the hash `[wasm; 32]`.
+    pub(crate) fn invoke_envelope(contract: u8, function: &str, wasm: u8) -> String {
+        let op = Operation {
+            source_account: None,
+            body: OperationBody::InvokeHostFunction(InvokeHostFunctionOp {
+                host_function: HostFunction::InvokeContract(InvokeContractArgs {
+                    contract_address: contract_address(contract),
+                    function_name: function.try_into().unwrap(),
+                    args: Default::default(),
+                }),
+                auth: Default::default(),
+            }),
+        };
+        let data = SorobanTransactionData {
+            ext: SorobanTransactionDataExt::V0,
+            resources: SorobanResources {
+                footprint: LedgerFootprint {
+                    read_only: vec![LedgerKey::ContractCode(LedgerKeyContractCode {
+                        hash: Hash([wasm; 32]),
+                    })]
+                    .try_into()
+                    .unwrap(),
+                    read_write: Default::default(),
+                },
+                instructions: 0,
+                disk_read_bytes: 0,
+                write_bytes: 0,
+            },
+            resource_fee: 0,
+        };
+        let tx = Transaction {
+            source_account: MuxedAccount::Ed25519(Uint256([1; 32])),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: Preconditions::None,
+            memo: Memo::None,
+            operations: vec![op].try_into().unwrap(),
+            ext: TransactionExt::V1(data),
+        };
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: Default::default(),
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decode_invocation() {
+        let call = decode_contract_call(&invoke_envelope(7, "swap", 0xab)).unwrap();
+        assert_eq!(call.kind, ContractCallKind::Invoke);
+        assert_eq!(call.contract_id, Some(contract_address(7).to_string()));
+        assert_eq!(call.function.as_deref(), Some("swap"));
+        assert_eq!(call.wasm_hashes, vec!["ab".repeat(32)]);
+    }
+
+    #[test]
+    fn test_classic_envelope_has_no_call() {
+        let classic = crate::services::rpc::tests::payment_envelope(1);
+        assert_eq!(decode_contract_call(&classic), None);
+        assert_eq!(decode_contract_call("not xdr"), None);
+    }
+}
@@ -0,0 +1,271 @@
+//! Soroban contract events decoded from transaction metadata.
+//!
+//! Horizon returns the transaction's `result_meta_xdr`; the events emitted by
+//! contracts live inside it. Decoding turns the XDR values we explain into
+//! plain Rust values and keeps a debug rendering of everything else.
+
+use serde::{Deserialize, Serialize};
+use stellar_xdr::curr::{
+    self as xdr, ContractEventBody, ContractEventType, Limits, ReadXdr, ScVal, TransactionMeta,
+};
+
+/// A value from an event's topics or data.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum EventValue {
+    Symbol(String),
+    /// A G..., C..., or M... strkey.
+    Address(String),
+    String(String),
+    /// A 128-bit integer, as used for token amounts.
+    Amount(i128),
+    /// Any other value, rendered for display only.
+    Other(String),
+}
+
+impl EventValue {
+    pub fn as_symbol(&self) -> Option<&str> {
+        match self {
+            EventValue::Symbol(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_address(&self) -> Option<&str> {
+        match self {
+            EventValue::Address(s) => Some(s),
+            _ => None,
+        }
+    }
+}
+
+/// An event emitted by a contract during a Soroban transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContractEvent {
+    /// Strkey (C...) of the emitting contract.
+    pub contract_id: Option<String>,
+    pub topics: Vec<EventValue>,
+    pub data: EventValue,
+    /// True when the event came from the diagnostic stream rather than the
+    /// transaction's own events, e.g. for a failed invocation.
+    pub diagnostic: bool,
+}
+
+/// Why transaction metadata could not be decoded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventDecodeError(pub String);
+
+impl std::fmt::Display for EventDecodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "could not decode transaction metadata: {}", self.0)
+    }
+}
+
+impl std::error::Error for EventDecodeError {}
+
+/// Deepest XDR nesting accepted from upstream, matching stellar-core.
+const XDR_DEPTH_LIMIT: u32 = 500;
+
+/// Limits for decoding base64 XDR received from upstream. The depth bound
+/// keeps crafted nesting from overflowing the stack, and the length bound
+/// stops a forged length prefix from reading past the payload.
+pub(crate) fn upstream_limits(encoded: &str) -> Limits {
+    Limits {
+        depth: XDR_DEPTH_LIMIT,
+        len: encoded.len(),
+    }
+}
+
+/// Decode the contract events from a base64 `TransactionMeta`.
+///
+/// Contract events are returned when present. Otherwise, contract-type
+/// diagnostic events are returned, flagged as such, since they are the only
+/// record of what a failed invocation did.
+pub fn decode_contract_events(
+    result_meta_xdr: &str,
+) -> Result<Vec<ContractEvent>, EventDecodeError> {
+    let meta = TransactionMeta::from_xdr_base64(result_meta_xdr, upstream_limits(result_meta_xdr))
+        .map_err(|e| EventDecodeError(e.to_string()))?;
+
+    let (events, diagnostics): (Vec<&xdr::ContractEvent>, Vec<&xdr::ContractEvent>) = match &meta {
+        TransactionMeta::V3(v3) => match &v3.soroban_meta {
+            Some(soroban) => (
+                soroban.events.iter().collect(),
+                soroban.diagnostic_events.iter().map(|d| &d.event).collect(),
+            ),
+            None => (vec![], vec![]),
+        },
+        TransactionMeta::V4(v4) => (
+            v4.operations
+                .iter()
+                .flat_map(|op| op.events.iter())
+                .collect(),
+            v4.diagnostic_events.iter().map(|d| &d.event).collect(),
+        ),
+        // Metadata before V3 predates Soroban.
+        _ => (vec![], vec![]),
+    };
+
+    let decoded: Vec<ContractEvent> = events
+        .into_iter()
+        .filter(|e| e.type_ == ContractEventType::Contract)
+        .map(|e| decode_event(e, false))
+        .collect();
+    if !decoded.is_empty() {
+        return Ok(decoded);
+    }
+
+    Ok(diagnostics
+        .into_iter()
+        .filter(|e| e.type_ == ContractEventType::Contract)
+        .map(|e| decode_event(e, true))
+        .collect())
+}
+
+/// Decode a single base64 `ScVal`, as returned for event topics and values by
+/// stellar-rpc's `getEvents`.
+pub fn decode_value_base64(value: &str) -> Result<EventValue, EventDecodeError> {
+    ScVal::from_xdr_base64(value, upstream_limits(value))
+        .map(|v| decode_value(&v))
+        .map_err(|e| EventDecodeError(e.to_string()))
+}
+
+fn decode_event(event: &xdr::ContractEvent, diagnostic: bool) -> ContractEvent {
+    let ContractEventBody::V0(body) = &event.body;
+    ContractEvent {
+        contract_id: event.contract_id.as_ref().map(|id| id.to_string()),
+        topics: body.topics.iter().map(decode_value).collect(),
+        data: decode_value(&body.data),
+        diagnostic,
+    }
+}
+
+fn decode_value(value: &ScVal) -> EventValue {
+    match value {
+        ScVal::Symbol(s) => EventValue::Symbol(s.to_utf8_string_lossy()),
+        ScVal::String(s) => EventValue::String(s.to_utf8_string_lossy()),
+        ScVal::Address(address) => EventValue::Address(address.to_string()),
+        ScVal::I128(parts) => EventValue::Amount(i128::from(parts)),
+        ScVal::U32(n) => EventValue::Other(n.to_string()),
+        ScVal::I32(n) => EventValue::Other(n.to_string()),
+        ScVal::U64(n) => EventValue::Other(n.to_string()),
+        ScVal::I64(n) => EventValue::Other(n.to_string()),
+        // Newer token transfers carry the amount in a map alongside a muxed id.
+        ScVal::Map(Some(map)) => map
+            .iter()
+            .find(|entry| matches!(&entry.key, ScVal::Symbol(s) if s.as_slice() == b"amount"))
+            .map(|entry| decode_value(&entry.val))
+            .unwrap_or_else(|| EventValue::Other(format!("{value:?}"))),
+        other => EventValue::Other(format!("{other:?}")),
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use stellar_xdr::curr::{
+        ContractEventV0, ContractId, ExtensionPoint, Hash, Int128Parts, LedgerEntryChanges,
+        ScAddress, ScString, ScSymbol, SorobanTransactionMeta, SorobanTransactionMetaExt,
+        TransactionMetaV3, WriteXdr,
+    };
+
+    pub(crate) const CONTRACT: [u8; 32] = [7; 32];
+
+    fn symbol(s: &str) -> ScVal {
+        ScVal::Symbol(ScSymbol(s.try_into().unwrap()))
+    }
+
+    /// Base64 metadata containing one SAC-style transfer event.
+    pub(crate) fn transfer_meta(
+        from: ScAddress,
+        to: ScAddress,
+        asset: &str,
+        amount: i128,
+    ) -> String {
+        let event = xdr::ContractEvent {
+            ext: ExtensionPoint::V0,
+            contract_id: Some(ContractId(Hash(CONTRACT))),
+            type_: ContractEventType::Contract,
+            body: ContractEventBody::V0(ContractEventV0 {
+                topics: vec![
+                    symbol("transfer"),
+                    ScVal::Address(from),
+                    ScVal::Address(to),
+                    ScVal::String(ScString(asset.try_into().unwrap())),
+                ]
+                .try_into()
+                .unwrap(),
+                data: ScVal::I128(Int128Parts {
+                    hi: (amount >> 64) as i64,
+                    lo: amount as u64,
+                }),
+            }),
+        };
+        TransactionMeta::V3(TransactionMetaV3 {
+            ext: ExtensionPoint::V0,
+            tx_changes_before: LedgerEntryChanges::default(),
+            operations: Default::default(),
+            tx_changes_after: LedgerEntryChanges::default(),
+            soroban_meta: Some(SorobanTransactionMeta {
+                ext: SorobanTransactionMetaExt::V0,
+                events: vec![event].try_into().unwrap(),
+                return_value: ScVal::Void,
+                diagnostic_events: Default::default(),
+            }),
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
+
+    pub(crate) fn contract_address(byte: u8) -> ScAddress {
+        ScAddress::Contract(ContractId(Hash([byte; 32])))
+    }
+
+    pub(crate) fn account_address(byte: u8) -> ScAddress {
+        ScAddress::Account(xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(
+            xdr::Uint256([byte; 32]),
+        )))
+    }
+
+    #[test]
+    fn test_decode_transfer_event() {
+        let meta = transfer_meta(
+            contract_address(1),
+            account_address(2),
+            "native",
+            100_000_000,
+        );
+        let events = decode_contract_events(&meta).unwrap();
+
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert!(event.contract_id.as_deref().unwrap().starts_with('C'));
+        assert_eq!(event.topics[0], EventValue::Symbol("transfer".to_string()));
+        assert!(event.topics[1].as_address().unwrap().starts_with('C'));
+        assert!(event.topics[2].as_address().unwrap().starts_with('G'));
+        assert_eq!(event.topics[3], EventValue::String("native".to_string()));
+        assert_eq!(event.data, EventValue::Amount(100_000_000));
+        assert!(!event.diagnostic);
+    }
+
+    #[test]
+    fn test_decode_invalid_meta() {
+        assert!(decode_contract_events("not-base64!").is_err());
+    }
+
+    #[test]
+    fn test_deeply_nested_value_is_rejected() {
+        // Each 12-byte unit (16 base64 chars) is an `ScVal::Vec` holding one
+        // more level; without a depth limit this overflows the stack.
+        let nested = "AAAAEAAAAAEAAAAB".repeat(100_000) + "AAAAAQ==";
+        assert!(decode_value_base64(&nested).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_base64_never_panics(encoded in "[A-Za-z0-9+/]{0,512}={0,2}") {
+            let _ = decode_contract_events(&encoded);
+            let _ = decode_value_base64(&encoded);
+        }
+    }
+}
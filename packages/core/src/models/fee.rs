@@ -5,6 +5,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::amount::Amount;
+
 /// Statistics about network fees at a given point in time.
 ///
 /// These statistics are typically retrieved from Horizon's `/fee_stats` endpoint
@@ -140,8 +142,7 @@ impl FeeStats {
     /// assert_eq!(xlm, "1.0000000");
     /// ```
     pub fn stroops_to_xlm(stroops: u64) -> String {
-        let xlm = stroops as f64 / 10_000_000.0;
-        format!("{xlm:.7}")
+        Amount::from(stroops).to_string()
     }
 }
 
@@ -151,9 +152,73 @@ impl Default for FeeStats {
     }
 }
 
+/// Fees per operation across the transactions of recent ledgers, in stroops.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FeeDistribution {
+    pub mode: u64,
+    pub p50: u64,
+    pub p70: u64,
+    pub p90: u64,
+    pub p95: u64,
+    pub p99: u64,
+    pub max: u64,
+}
+
+impl FeeDistribution {
+    /// The `percentile`th fee, for one of 50, 70, 90, 95, or 99; anything
+    /// else reads as the maximum.
+    pub fn percentile(&self, percentile: u8) -> u64 {
+        match percentile {
+            50 => self.p50,
+            70 => self.p70,
+            90 => self.p90,
+            95 => self.p95,
+            99 => self.p99,
+            _ => self.max,
+        }
+    }
+}
+
+/// Horizon's `/fee_stats` in full: how busy the latest ledger was, and what
+/// transactions paid and offered to pay in recent ones.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct FeeMarket {
+    pub last_ledger: u64,
+    /// The network base fee, in stroops per operation.
+    pub base_fee: u64,
+    /// Share of the latest ledger's operation capacity that was used, from
+    /// 0.0 to 1.0.
+    pub capacity_usage: f64,
+    /// Fees charged.
+    pub charged: FeeDistribution,
+    /// Max fees bid.
+    pub offered: FeeDistribution,
+}
+
+/// How full one ledger's transaction set was.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LedgerLoad {
+    pub sequence: u64,
+    /// Operations in the ledger's transaction set, successful or not.
+    pub operations: u32,
+    /// Most operations a transaction set may hold.
+    pub capacity: u32,
+}
+
+impl LedgerLoad {
+    /// Share of the ledger's capacity used, from 0.0 to 1.0.
+    pub fn usage(&self) -> f64 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        f64::from(self.operations) / f64::from(self.capacity)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_new_fee_stats() {
@@ -201,6 +266,18 @@ mod tests {
         assert_eq!(FeeStats::stroops_to_xlm(0), "0.0000000");
     }
 
+    proptest! {
+        #[test]
+        fn stroops_to_xlm_is_exact(stroops in any::<u64>()) {
+            let xlm = FeeStats::stroops_to_xlm(stroops);
+            let (whole, fraction) = xlm.split_once('.').unwrap();
+            prop_assert_eq!(fraction.len(), 7);
+            let back = whole.parse::<u64>().unwrap() as u128 * 10_000_000
+                + fraction.parse::<u64>().unwrap() as u128;
+            prop_assert_eq!(back, stroops as u128);
+        }
+    }
+
     #[test]
     fn test_default_trait() {
         let fees = FeeStats::default();
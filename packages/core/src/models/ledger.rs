@@ -0,0 +1,21 @@
+//! Closed ledgers.
+
+use serde::{Deserialize, Serialize};
+
+/// A closed ledger's header and what it contained.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ledger {
+    pub sequence: u64,
+    /// ISO 8601 close time.
+    pub closed_at: String,
+    /// Protocol version the network ran when the ledger closed.
+    pub protocol_version: u32,
+    pub successful_transaction_count: u32,
+    pub failed_transaction_count: u32,
+    /// Operations in successful transactions.
+    pub operation_count: u32,
+    /// Minimum fee per operation, in stroops.
+    pub base_fee_in_stroops: u64,
+    /// Minimum balance per ledger entry, in stroops.
+    pub base_reserve_in_stroops: u64,
+}
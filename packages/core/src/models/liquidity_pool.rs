@@ -0,0 +1,45 @@
+//! Liquidity pools and deposits/withdrawals against them.
+
+use serde::{Deserialize, Serialize};
+
+/// One asset held by a pool, or moved in or out of it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolReserve {
+    /// "native" or "CODE:ISSUER".
+    pub asset: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidityPool {
+    pub id: String,
+    /// Fee charged on swaps, in basis points.
+    pub fee_bp: u32,
+    /// Pool type, e.g. "constant_product".
+    pub pool_type: String,
+    /// Number of accounts holding a trust line to the pool's shares.
+    pub total_trustlines: u64,
+    pub total_shares: String,
+    pub reserves: Vec<PoolReserve>,
+    pub last_modified_ledger: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PoolActivityKind {
+    Deposit,
+    Withdraw,
+}
+
+/// A deposit into or withdrawal from a pool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PoolActivity {
+    pub kind: PoolActivityKind,
+    pub account: String,
+    /// Reserves deposited or received.
+    pub reserves: Vec<PoolReserve>,
+    /// Pool shares received for a deposit, or redeemed for a withdrawal.
+    pub shares: String,
+    pub transaction_hash: String,
+    pub created_at: Option<String>,
+}
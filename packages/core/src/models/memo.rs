@@ -14,33 +14,32 @@ use serde::{Deserialize, Serialize};
 /// - Hash: 32-byte hash
 /// - Return: 32-byte hash for returns/refunds
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(tag = "type", content = "value")]
+#[serde(tag = "type", content = "value", rename_all = "lowercase")]
 #[derive(Default)]
 pub enum Memo {
     /// No memo attached to the transaction
-    #[serde(rename = "none")]
     #[default]
     None,
 
     /// Text memo: UTF-8 string up to 28 bytes
     /// Common uses: order numbers, payment references, notes
-    #[serde(rename = "text")]
     Text(String),
 
     /// ID memo: Unsigned 64-bit integer
     /// Common uses: customer IDs, invoice numbers
-    #[serde(rename = "id")]
     Id(u64),
 
     /// Hash memo: 32-byte hash
     /// Common uses: document hashes, preimage for HTLCs
-    #[serde(rename = "hash")]
     Hash(String),
 
     /// Return memo: 32-byte hash for returns/refunds
     /// Common uses: indicating a refund/return transaction
-    #[serde(rename = "return")]
     Return(String),
+
+    /// Text memo whose bytes are not UTF-8, as hex
+    /// Common uses: binary references packed into a text memo
+    Binary(String),
 }
 
 impl Memo {
@@ -71,6 +70,34 @@ impl Memo {
         }
     }
 
+    /// Creates a text memo from its raw bytes: `Memo::Text` when they are
+    /// UTF-8, `Memo::Binary` when they are not.
+    ///
+    /// # Returns
+    /// `None` if there are more than 28 bytes
+    ///
+    /// # Examples
+    /// ```
+    /// use stellar_explain_core::models::memo::Memo;
+    ///
+    /// assert_eq!(Memo::from_text_bytes(b"hello"), Memo::text("hello"));
+    /// assert_eq!(
+    ///     Memo::from_text_bytes(&[0xde, 0xad, 0xbe, 0xef]),
+    ///     Some(Memo::Binary("deadbeef".to_string()))
+    /// );
+    /// ```
+    pub fn from_text_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() > 28 {
+            return None;
+        }
+        match std::str::from_utf8(bytes) {
+            Ok(text) => Some(Memo::Text(text.to_string())),
+            Err(_) => Some(Memo::Binary(
+                bytes.iter().map(|b| format!("{b:02x}")).collect(),
+            )),
+        }
+    }
+
     /// Creates an ID memo.
     ///
     /// # Examples
@@ -115,7 +142,8 @@ impl Memo {
         Memo::Return(hash.into())
     }
 
-    /// Returns the memo type as a string.
+    /// Returns the memo type as a string. A binary memo is a text memo
+    /// on the network.
     ///
     /// # Examples
     /// ```
@@ -128,7 +156,7 @@ impl Memo {
     pub fn memo_type(&self) -> &str {
         match self {
             Memo::None => "none",
-            Memo::Text(_) => "text",
+            Memo::Text(_) | Memo::Binary(_) => "text",
             Memo::Id(_) => "id",
             Memo::Hash(_) => "hash",
             Memo::Return(_) => "return",
@@ -165,6 +193,7 @@ impl Memo {
             Memo::Id(id) => id.to_string(),
             Memo::Hash(hash) => hash.clone(),
             Memo::Return(hash) => hash.clone(),
+            Memo::Binary(hex) => hex.clone(),
         }
     }
 }
@@ -177,6 +206,7 @@ impl std::fmt::Display for Memo {
             Memo::Id(id) => write!(f, "ID: {id}"),
             Memo::Hash(hash) => write!(f, "Hash: {hash}"),
             Memo::Return(hash) => write!(f, "Return: {hash}"),
+            Memo::Binary(hex) => write!(f, "Binary: {hex}"),
         }
     }
 }
@@ -184,6 +214,7 @@ impl std::fmt::Display for Memo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
 
     #[test]
     fn test_text_memo_valid() {
@@ -246,6 +277,24 @@ mod tests {
         assert_eq!(Memo::return_hash("def").to_string(), "Return: def");
     }
 
+    #[test]
+    fn test_text_memo_from_bytes() {
+        assert_eq!(
+            Memo::from_text_bytes("café".as_bytes()),
+            Some(Memo::Text("café".to_string()))
+        );
+
+        // Latin-1 "café": one byte short of valid UTF-8.
+        let memo = Memo::from_text_bytes(&[0x63, 0x61, 0x66, 0xe9]).unwrap();
+        assert_eq!(memo, Memo::Binary("636166e9".to_string()));
+        assert_eq!(memo.memo_type(), "text");
+        assert_eq!(memo.value_string(), "636166e9");
+        assert_eq!(memo.to_string(), "Binary: 636166e9");
+
+        assert!(Memo::from_text_bytes(&[0xff; 28]).is_some());
+        assert!(Memo::from_text_bytes(&[0xff; 29]).is_none());
+    }
+
     #[test]
     fn test_default_memo() {
         let memo = Memo::default();
@@ -260,6 +309,7 @@ mod tests {
             Memo::id(42),
             Memo::hash("test_hash".to_string()),
             Memo::return_hash("return_hash".to_string()),
+            Memo::Binary("ff00".to_string()),
         ];
 
         for memo in memos {
@@ -268,4 +318,16 @@ mod tests {
             assert_eq!(memo, deserialized);
         }
     }
+
+    proptest! {
+        #[test]
+        fn text_memo_accepts_at_most_28_bytes(text in any::<String>()) {
+            prop_assert_eq!(Memo::text(text.clone()).is_some(), text.len() <= 28);
+        }
+
+        #[test]
+        fn memo_from_utf8_bytes_is_the_text(text in any::<String>()) {
+            prop_assert_eq!(Memo::from_text_bytes(text.as_bytes()), Memo::text(text));
+        }
+    }
 }
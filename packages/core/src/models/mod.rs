@@ -1,5 +1,15 @@
 pub mod account;
+pub mod amount;
+pub mod asset;
+pub mod claimable_balance;
+pub mod contract_call;
+pub mod contract_event;
 pub mod fee;
+pub mod ledger;
+pub mod liquidity_pool;
 pub mod memo;
+pub mod offer;
 pub mod operation;
+pub mod strkey;
+pub mod trade;
 pub mod transaction;
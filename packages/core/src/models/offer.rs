@@ -0,0 +1,23 @@
+//! Open offers on the Stellar DEX.
+
+use serde::{Deserialize, Serialize};
+
+/// A standing sell offer. Buy offers are stored by the network as the
+/// equivalent sell offer, so every open offer sells `selling` for `buying`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Offer {
+    pub id: u64,
+    pub seller: String,
+    /// "native" or "CODE:ISSUER".
+    pub selling: String,
+    /// "native" or "CODE:ISSUER".
+    pub buying: String,
+    /// Amount of `selling` still on offer.
+    pub amount: String,
+    /// Units of `buying` asked per unit of `selling`.
+    pub price: String,
+    /// Account paying the offer's reserve.
+    pub sponsor: Option<String>,
+    pub last_modified_ledger: Option<u64>,
+    pub last_modified_time: Option<String>,
+}
@@ -1,3 +1,4 @@
+use crate::models::amount::Amount;
 use crate::models::memo::Memo;
 use serde::{Deserialize, Serialize};
 
@@ -35,7 +36,7 @@ pub struct PaymentOperation {
     pub asset_type: String,
     pub asset_code: Option<String>,
     pub asset_issuer: Option<String>,
-    pub amount: String,
+    pub amount: Amount,
 }
 
 /// A set_options operation that configures account settings.
@@ -64,7 +65,7 @@ pub struct CreateAccountOperation {
     /// The newly created account address.
     pub new_account: String,
     /// Starting XLM balance sent to the new account.
-    pub starting_balance: String,
+    pub starting_balance: Amount,
 }
 
 /// A change_trust operation that adds, updates, or removes a trust line.
@@ -75,8 +76,8 @@ pub struct ChangeTrustOperation {
     pub trustor: String,
     pub asset_code: String,
     pub asset_issuer: String,
-    /// Trust limit. "0" means remove the trust line.
-    pub limit: String,
+    /// Trust limit. Zero means remove the trust line.
+    pub limit: Amount,
 }
 
 /// Whether the offer intends to sell or buy the named asset.
@@ -94,7 +95,7 @@ pub struct ManageOfferOperation {
     pub seller: String,
     pub selling_asset: String,
     pub buying_asset: String,
-    pub amount: String,
+    pub amount: Amount,
     pub price: String,
     /// 0 = new offer, non-zero = update or cancel.
     pub offer_id: u64,
@@ -116,9 +117,9 @@ pub struct PathPaymentOperation {
     pub source_account: Option<String>,
     pub destination: String,
     pub send_asset: String,
-    pub send_amount: String,
+    pub send_amount: Amount,
     pub dest_asset: String,
-    pub dest_amount: String,
+    pub dest_amount: Amount,
     /// Intermediate assets in the conversion path.
     pub path: Vec<String>,
     pub payment_type: PathPaymentType,
@@ -133,7 +134,7 @@ pub struct ClawbackOperation {
     pub from: String,
     pub asset_code: String,
     pub asset_issuer: String,
-    pub amount: String,
+    pub amount: Amount,
 }
 
 /// A clawback_claimable_balance operation.
@@ -187,25 +188,9 @@ impl Operation {
     }
 }
 
+use crate::explain::format::format_asset;
 use crate::services::horizon::HorizonOperation;
 
-/// Format an asset from Horizon's separate code/issuer/type fields into
-/// a single display string: "XLM (native)" or "USDC (GISSUER...)".
-fn format_asset(
-    asset_type: Option<&str>,
-    asset_code: Option<&str>,
-    asset_issuer: Option<&str>,
-) -> String {
-    match asset_type {
-        Some("native") | None => "XLM (native)".to_string(),
-        _ => match (asset_code, asset_issuer) {
-            (Some(code), Some(issuer)) => format!("{code} ({issuer})"),
-            (Some(code), None) => code.to_string(),
-            _ => "Unknown".to_string(),
-        },
-    }
-}
-
 impl From<HorizonOperation> for Operation {
     fn from(op: HorizonOperation) -> Self {
         match op.operation_type.as_str() {
@@ -216,7 +201,7 @@ impl From<HorizonOperation> for Operation {
                 asset_type: op.asset_type.unwrap_or_else(|| "native".to_string()),
                 asset_code: op.asset_code,
                 asset_issuer: op.asset_issuer,
-                amount: op.amount.unwrap_or_else(|| "0".to_string()),
+                amount: parse_amount(op.amount),
             }),
             "set_options" => Operation::SetOptions(SetOptionsOperation {
                 id: op.id,
@@ -241,14 +226,14 @@ impl From<HorizonOperation> for Operation {
                 id: op.id,
                 funder: op.funder.unwrap_or_else(|| "Unknown".to_string()),
                 new_account: op.account.unwrap_or_default(),
-                starting_balance: op.starting_balance.unwrap_or_else(|| "0".to_string()),
+                starting_balance: parse_amount(op.starting_balance),
             }),
             "change_trust" => Operation::ChangeTrust(ChangeTrustOperation {
                 id: op.id,
                 trustor: op.source_account.unwrap_or_else(|| "Unknown".to_string()),
                 asset_code: op.asset_code.unwrap_or_default(),
                 asset_issuer: op.asset_issuer.unwrap_or_default(),
-                limit: op.limit.unwrap_or_else(|| "0".to_string()),
+                limit: parse_amount(op.limit),
             }),
             "manage_offer" | "manage_sell_offer" | "create_passive_sell_offer" => {
                 let selling = format_asset(
@@ -266,7 +251,7 @@ impl From<HorizonOperation> for Operation {
                     seller: op.source_account.unwrap_or_else(|| "Unknown".to_string()),
                     selling_asset: selling,
                     buying_asset: buying,
-                    amount: op.amount.unwrap_or_else(|| "0".to_string()),
+                    amount: parse_amount(op.amount),
                     price: op.price.unwrap_or_default(),
                     offer_id: op.offer_id.and_then(|s| s.parse::<u64>().ok()).unwrap_or(0),
                     offer_type: OfferType::Sell,
@@ -288,7 +273,7 @@ impl From<HorizonOperation> for Operation {
                     seller: op.source_account.unwrap_or_else(|| "Unknown".to_string()),
                     selling_asset: selling,
                     buying_asset: buying,
-                    amount: op.amount.unwrap_or_else(|| "0".to_string()),
+                    amount: parse_amount(op.amount),
                     price: op.price.unwrap_or_default(),
                     offer_id: op.offer_id.and_then(|s| s.parse::<u64>().ok()).unwrap_or(0),
                     offer_type: OfferType::Buy,
@@ -310,9 +295,9 @@ impl From<HorizonOperation> for Operation {
                     source_account: op.from.clone().or(op.source_account.clone()),
                     destination: op.to.unwrap_or_default(),
                     send_asset,
-                    send_amount: op.source_amount.unwrap_or_else(|| "0".to_string()),
+                    send_amount: parse_amount(op.source_amount),
                     dest_asset,
-                    dest_amount: op.amount.unwrap_or_else(|| "0".to_string()),
+                    dest_amount: parse_amount(op.amount),
                     path: vec![],
                     payment_type: PathPaymentType::StrictSend,
                 })
@@ -333,9 +318,9 @@ impl From<HorizonOperation> for Operation {
                     source_account: op.from.clone().or(op.source_account.clone()),
                     destination: op.to.unwrap_or_default(),
                     send_asset,
-                    send_amount: op.source_amount.unwrap_or_else(|| "0".to_string()),
+                    send_amount: parse_amount(op.source_amount),
                     dest_asset,
-                    dest_amount: op.amount.unwrap_or_else(|| "0".to_string()),
+                    dest_amount: parse_amount(op.amount),
                     path: vec![],
                     payment_type: PathPaymentType::StrictReceive,
                 })
@@ -346,7 +331,7 @@ impl From<HorizonOperation> for Operation {
                 from: op.from.unwrap_or_default(),
                 asset_code: op.asset_code.unwrap_or_default(),
                 asset_issuer: op.asset_issuer.unwrap_or_default(),
-                amount: op.amount.unwrap_or_else(|| "0".to_string()),
+                amount: parse_amount(op.amount),
             }),
             "clawback_claimable_balance" => {
                 Operation::ClawbackClaimableBalance(ClawbackClaimableBalanceOperation {
@@ -372,6 +357,11 @@ impl From<HorizonOperation> for Operation {
     }
 }
 
+/// A Horizon amount, or zero when it is missing or malformed.
+fn parse_amount(amount: Option<String>) -> Amount {
+    amount.and_then(|a| a.parse().ok()).unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -385,7 +375,7 @@ mod tests {
             asset_type: "native".to_string(),
             asset_code: None,
             asset_issuer: None,
-            amount: "100.0".to_string(),
+            amount: "100.0".parse().unwrap(),
         });
         let other = Operation::Other(OtherOperation {
             id: "67890".to_string(),
@@ -415,7 +405,7 @@ mod tests {
             asset_type: "native".to_string(),
             asset_code: None,
             asset_issuer: None,
-            amount: "100.0".to_string(),
+            amount: "100.0".parse().unwrap(),
         });
         assert_eq!(payment.id(), "12345");
     }
@@ -435,7 +425,7 @@ mod tests {
             id: "ca-1".to_string(),
             funder: "GFUNDER".to_string(),
             new_account: "GNEW".to_string(),
-            starting_balance: "100".to_string(),
+            starting_balance: "100".parse().unwrap(),
         });
         assert_eq!(op.id(), "ca-1");
     }
@@ -447,7 +437,7 @@ mod tests {
             trustor: "GTRUSTEE".to_string(),
             asset_code: "USDC".to_string(),
             asset_issuer: "GISSUER".to_string(),
-            limit: "1000".to_string(),
+            limit: "1000".parse().unwrap(),
         });
         assert_eq!(op.id(), "ct-1");
     }
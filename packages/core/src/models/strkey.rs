@@ -0,0 +1,406 @@
+//! StrKey addresses.
+//!
+//! Stellar writes keys, accounts, and contracts as StrKeys: a version byte,
+//! the raw payload, and a CRC16-XModem checksum of both (little-endian),
+//! base32-encoded without padding. The version byte fixes the first
+//! character, so the kind can be read from the address:
+//!
+//! | Prefix | Kind | Payload |
+//! |--------|------|---------|
+//! | `G` | account (ed25519 public key) | 32-byte key |
+//! | `M` | muxed account | 32-byte key, then a 64-bit big-endian ID |
+//! | `T` | pre-authorized transaction | 32-byte transaction hash |
+//! | `X` | hash(x) signer | 32-byte SHA-256 hash |
+//! | `C` | contract | 32-byte contract ID |
+//!
+//! Encoding and decoding are `stellar_strkey`'s. This module adds the kinds
+//! an address can be and [`StrKeyError`], which says which part of a bad
+//! address was wrong, so a mistyped character is caught by the checksum and
+//! explained rather than sent to Horizon.
+
+use std::fmt;
+use std::str::FromStr;
+
+use stellar_strkey::{Contract, HashX, PreAuthTx, Strkey, ed25519};
+
+/// What a StrKey names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrKeyKind {
+    Account,
+    MuxedAccount,
+    PreAuthTx,
+    HashX,
+    Contract,
+}
+
+impl StrKeyKind {
+    pub const ALL: [StrKeyKind; 5] = [
+        StrKeyKind::Account,
+        StrKeyKind::MuxedAccount,
+        StrKeyKind::PreAuthTx,
+        StrKeyKind::HashX,
+        StrKeyKind::Contract,
+    ];
+
+    /// The first character of every StrKey of this kind.
+    pub fn prefix(self) -> char {
+        match self {
+            StrKeyKind::Account => 'G',
+            StrKeyKind::MuxedAccount => 'M',
+            StrKeyKind::PreAuthTx => 'T',
+            StrKeyKind::HashX => 'X',
+            StrKeyKind::Contract => 'C',
+        }
+    }
+
+    /// How the kind is named in error messages, e.g. "an account address (G...)".
+    pub fn description(self) -> &'static str {
+        match self {
+            StrKeyKind::Account => "an account address (G...)",
+            StrKeyKind::MuxedAccount => "a muxed account address (M...)",
+            StrKeyKind::PreAuthTx => "a pre-authorized transaction signer (T...)",
+            StrKeyKind::HashX => "a hash(x) signer (X...)",
+            StrKeyKind::Contract => "a contract address (C...)",
+        }
+    }
+
+    /// The number of characters in a StrKey of this kind: the version byte,
+    /// payload, and checksum in base32.
+    pub fn encoded_len(self) -> usize {
+        ((1 + self.payload_len() + 2) * 8).div_ceil(5)
+    }
+
+    fn payload_len(self) -> usize {
+        match self {
+            StrKeyKind::MuxedAccount => 40,
+            _ => 32,
+        }
+    }
+
+    fn from_prefix(prefix: char) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.prefix() == prefix)
+    }
+}
+
+/// A decoded StrKey.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StrKey {
+    Account([u8; 32]),
+    /// An account with a 64-bit ID, for many users sharing one account.
+    MuxedAccount {
+        account: [u8; 32],
+        id: u64,
+    },
+    PreAuthTx([u8; 32]),
+    HashX([u8; 32]),
+    Contract([u8; 32]),
+}
+
+impl StrKey {
+    pub fn kind(&self) -> StrKeyKind {
+        match self {
+            StrKey::Account(_) => StrKeyKind::Account,
+            StrKey::MuxedAccount { .. } => StrKeyKind::MuxedAccount,
+            StrKey::PreAuthTx(_) => StrKeyKind::PreAuthTx,
+            StrKey::HashX(_) => StrKeyKind::HashX,
+            StrKey::Contract(_) => StrKeyKind::Contract,
+        }
+    }
+
+    /// The account a muxed account belongs to; an account is its own.
+    pub fn account(&self) -> Option<StrKey> {
+        match *self {
+            StrKey::Account(key) | StrKey::MuxedAccount { account: key, .. } => {
+                Some(StrKey::Account(key))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        Strkey::from(*self).to_string()
+    }
+
+    pub fn decode(strkey: &str) -> Result<Self, StrKeyError> {
+        let kind = strkey
+            .chars()
+            .next()
+            .and_then(StrKeyKind::from_prefix)
+            .ok_or(StrKeyError::UnknownPrefix)?;
+        if strkey.len() != kind.encoded_len() {
+            return Err(StrKeyError::Length {
+                kind,
+                actual: strkey.chars().count(),
+            });
+        }
+        if !strkey.bytes().all(is_base32) {
+            return Err(StrKeyError::Alphabet);
+        }
+        // The prefix and length are right and every character is in the
+        // alphabet, so what is left to fail is the checksum.
+        let decoded = match Strkey::from_string(strkey).map_err(|_| StrKeyError::Checksum)? {
+            Strkey::PublicKeyEd25519(key) => StrKey::Account(key.0),
+            Strkey::MuxedAccountEd25519(muxed) => StrKey::MuxedAccount {
+                account: muxed.ed25519,
+                id: muxed.id,
+            },
+            Strkey::PreAuthTx(tx) => StrKey::PreAuthTx(tx.0),
+            Strkey::HashX(hash) => StrKey::HashX(hash.0),
+            Strkey::Contract(contract) => StrKey::Contract(contract.0),
+            _ => return Err(StrKeyError::UnknownPrefix),
+        };
+        if decoded.kind() != kind {
+            return Err(StrKeyError::UnknownPrefix);
+        }
+        Ok(decoded)
+    }
+}
+
+impl From<StrKey> for Strkey {
+    fn from(strkey: StrKey) -> Self {
+        match strkey {
+            StrKey::Account(key) => Strkey::PublicKeyEd25519(ed25519::PublicKey(key)),
+            StrKey::MuxedAccount { account, id } => {
+                Strkey::MuxedAccountEd25519(ed25519::MuxedAccount {
+                    ed25519: account,
+                    id,
+                })
+            }
+            StrKey::PreAuthTx(hash) => Strkey::PreAuthTx(PreAuthTx(hash)),
+            StrKey::HashX(hash) => Strkey::HashX(HashX(hash)),
+            StrKey::Contract(id) => Strkey::Contract(Contract(id)),
+        }
+    }
+}
+
+impl fmt::Display for StrKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.encode())
+    }
+}
+
+impl FromStr for StrKey {
+    type Err = StrKeyError;
+
+    fn from_str(strkey: &str) -> Result<Self, Self::Err> {
+        Self::decode(strkey)
+    }
+}
+
+/// Why a string is not a StrKey of the kind wanted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StrKeyError {
+    /// The first character is not one of G, M, T, X, or C.
+    UnknownPrefix,
+    Length {
+        kind: StrKeyKind,
+        actual: usize,
+    },
+    /// A character outside A-Z and 2-7.
+    Alphabet,
+    /// Includes a last character with stray bits set, which no encoder
+    /// writes.
+    Checksum,
+    WrongKind {
+        expected: StrKeyKind,
+        actual: StrKeyKind,
+    },
+}
+
+impl fmt::Display for StrKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrKeyError::UnknownPrefix => {
+                f.write_str("not a Stellar address: it must start with G, M, T, X, or C")
+            }
+            StrKeyError::Length { kind, actual } => write!(
+                f,
+                "{} is {} characters long, not {actual}",
+                kind.description(),
+                kind.encoded_len()
+            ),
+            StrKeyError::Alphabet => {
+                f.write_str("a Stellar address only uses the letters A-Z and the digits 2-7")
+            }
+            StrKeyError::Checksum => f.write_str(
+                "the address's checksum does not match, so a character was probably mistyped",
+            ),
+            StrKeyError::WrongKind { expected, actual } => write!(
+                f,
+                "expected {}, but this is {}",
+                expected.description(),
+                actual.description()
+            ),
+        }
+    }
+}
+
+impl std::error::Error for StrKeyError {}
+
+/// Decode `strkey`, which must be one of `kinds`. With several kinds, a
+/// mismatch is reported against the first.
+pub fn validate(strkey: &str, kinds: &[StrKeyKind]) -> Result<StrKey, StrKeyError> {
+    let decoded = StrKey::decode(strkey)?;
+    match kinds.first() {
+        Some(&expected) if !kinds.contains(&decoded.kind()) => Err(StrKeyError::WrongKind {
+            expected,
+            actual: decoded.kind(),
+        }),
+        _ => Ok(decoded),
+    }
+}
+
+/// Whether `address` is a valid account address (G...), checksum included.
+pub fn is_account_address(address: &str) -> bool {
+    validate(address, &[StrKeyKind::Account]).is_ok()
+}
+
+/// The secret key a Stellar secret seed (`S...`) spells. Seeds are not a
+/// [`StrKey`], so they are never accepted where an address is expected.
+pub fn decode_seed(seed: &str) -> Result<[u8; 32], StrKeyError> {
+    if !seed.starts_with('S') {
+        return Err(StrKeyError::UnknownPrefix);
+    }
+    if !seed.bytes().all(is_base32) {
+        return Err(StrKeyError::Alphabet);
+    }
+    ed25519::PrivateKey::from_string(seed)
+        .map(|key| key.0)
+        .map_err(|_| StrKeyError::Checksum)
+}
+
+fn is_base32(byte: u8) -> bool {
+    byte.is_ascii_uppercase() || (b'2'..=b'7').contains(&byte)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The public network's native XLM contract.
+    const XLM_CONTRACT: &str = "CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA";
+
+    fn account() -> String {
+        StrKey::Account([3; 32]).encode()
+    }
+
+    #[test]
+    fn test_round_trips_match_the_reference_encoding() {
+        let key = [7u8; 32];
+        let cases = [
+            (
+                StrKey::Account(key),
+                stellar_strkey::ed25519::PublicKey(key).to_string(),
+            ),
+            (
+                StrKey::MuxedAccount {
+                    account: key,
+                    id: 1_234_567_890_123,
+                },
+                stellar_strkey::ed25519::MuxedAccount {
+                    ed25519: key,
+                    id: 1_234_567_890_123,
+                }
+                .to_string(),
+            ),
+            (
+                StrKey::PreAuthTx(key),
+                stellar_strkey::PreAuthTx(key).to_string(),
+            ),
+            (StrKey::HashX(key), stellar_strkey::HashX(key).to_string()),
+            (
+                StrKey::Contract(key),
+                stellar_strkey::Contract(key).to_string(),
+            ),
+        ];
+        for (strkey, reference) in cases {
+            assert_eq!(strkey.encode(), reference);
+            assert_eq!(reference.len(), strkey.kind().encoded_len());
+            assert!(reference.starts_with(strkey.kind().prefix()));
+            assert_eq!(reference.parse::<StrKey>(), Ok(strkey));
+        }
+    }
+
+    #[test]
+    fn test_decodes_a_real_contract() {
+        let decoded = StrKey::decode(XLM_CONTRACT).unwrap();
+        assert_eq!(decoded.kind(), StrKeyKind::Contract);
+        assert_eq!(decoded.to_string(), XLM_CONTRACT);
+        assert!(!is_account_address(XLM_CONTRACT));
+        assert!(is_account_address(&account()));
+    }
+
+    #[test]
+    fn test_muxed_account_belongs_to_its_account() {
+        let muxed = StrKey::MuxedAccount {
+            account: [9; 32],
+            id: 42,
+        };
+        assert_eq!(muxed.account(), Some(StrKey::Account([9; 32])));
+        assert_eq!(StrKey::Contract([9; 32]).account(), None);
+    }
+
+    #[test]
+    fn test_mistakes_are_told_apart() {
+        let account = account();
+        // One character changed.
+        let changed = if &account[10..11] == "A" { "B" } else { "A" };
+        let typo = format!("{}{changed}{}", &account[..10], &account[11..]);
+        assert_eq!(StrKey::decode(&typo), Err(StrKeyError::Checksum));
+        assert_eq!(
+            StrKey::decode(&account[..55]),
+            Err(StrKeyError::Length {
+                kind: StrKeyKind::Account,
+                actual: 55
+            })
+        );
+        assert_eq!(
+            StrKey::decode(&account.to_lowercase()),
+            Err(StrKeyError::UnknownPrefix)
+        );
+        let lowercase = format!("G{}", &account[1..].to_lowercase());
+        assert_eq!(StrKey::decode(&lowercase), Err(StrKeyError::Alphabet));
+        assert_eq!(StrKey::decode(""), Err(StrKeyError::UnknownPrefix));
+        // Secret seeds are StrKeys too, but never accepted as an address.
+        let seed = stellar_strkey::ed25519::PrivateKey([7; 32]).to_string();
+        assert_eq!(StrKey::decode(&seed), Err(StrKeyError::UnknownPrefix));
+    }
+
+    #[test]
+    fn test_muxed_account_last_character_must_be_canonical() {
+        let muxed = StrKey::MuxedAccount {
+            account: [7; 32],
+            id: 1,
+        }
+        .encode();
+        // 43 bytes leave one spare bit in the 69th character.
+        const ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+        let last = muxed.as_bytes()[68];
+        let position = ALPHABET.iter().position(|&a| a == last).unwrap();
+        let non_canonical = format!("{}{}", &muxed[..68], ALPHABET[position ^ 1] as char);
+        assert_eq!(StrKey::decode(&non_canonical), Err(StrKeyError::Checksum));
+    }
+
+    #[test]
+    fn test_decodes_seeds_apart_from_addresses() {
+        let seed = stellar_strkey::ed25519::PrivateKey([7; 32]).to_string();
+        assert_eq!(decode_seed(&seed), Ok([7; 32]));
+        assert_eq!(decode_seed(&account()), Err(StrKeyError::UnknownPrefix));
+        assert_eq!(
+            decode_seed(&seed.to_lowercase()),
+            Err(StrKeyError::UnknownPrefix)
+        );
+    }
+
+    #[test]
+    fn test_validate_checks_the_kind() {
+        let contract = StrKey::Contract([1; 32]).encode();
+        let err = validate(&contract, &[StrKeyKind::Account]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "expected an account address (G...), but this is a contract address (C...)"
+        );
+        assert!(validate(&contract, &[StrKeyKind::Account, StrKeyKind::Contract]).is_ok());
+        assert!(!is_account_address(&contract));
+    }
+}
@@ -0,0 +1,30 @@
+//! Trades an account made while a path payment converted its assets.
+
+use serde::{Deserialize, Serialize};
+
+use crate::models::amount::Amount;
+
+/// One trade against an offer or a liquidity pool, from the trading
+/// account's side. Assets are "native" or "CODE:ISSUER".
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeFill {
+    pub sold_asset: String,
+    pub sold_amount: Amount,
+    pub bought_asset: String,
+    pub bought_amount: Amount,
+}
+
+/// An asset pair's trades over one bucket of time, from Horizon's trade
+/// aggregations. Prices are in counter asset per unit of base asset.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TradeBucket {
+    /// Start of the bucket, in Unix milliseconds.
+    pub timestamp: i64,
+    pub trade_count: u64,
+    pub base_volume: Amount,
+    pub counter_volume: Amount,
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+}
@@ -1,3 +1,5 @@
+use crate::models::contract_call::ContractCall;
+use crate::models::contract_event::ContractEvent;
 use crate::models::memo::Memo;
 use crate::models::operation::{Operation, PaymentOperation};
 use serde::{Deserialize, Serialize};
@@ -9,6 +11,18 @@ pub struct ResultCodes {
     pub operations: Vec<String>,
 }
 
+/// The account that submitted a transaction and the accounts it acted for.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TransactionSource {
+    /// Supplied the sequence number.
+    pub account: String,
+    pub sequence: Option<String>,
+    /// Paid the fee: `account`, or the payer of a fee bump.
+    pub fee_account: Option<String>,
+    /// Distinct operation source accounts, in order of first appearance.
+    pub operation_accounts: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Transaction {
     pub hash: String,
@@ -18,6 +32,20 @@ pub struct Transaction {
     pub memo: Option<Memo>,
     /// Raw Horizon result codes — only present for failed transactions.
     pub result_codes: Option<ResultCodes>,
+    /// Events emitted by contracts, for Soroban transactions.
+    pub contract_events: Vec<ContractEvent>,
+    /// The contract call, for Soroban transactions.
+    #[serde(default)]
+    pub contract_call: Option<ContractCall>,
+    /// Who submitted the transaction, when the data source says.
+    #[serde(default)]
+    pub source: Option<TransactionSource>,
+    /// ISO 8601 close time of the ledger that included the transaction.
+    #[serde(default)]
+    pub created_at: Option<String>,
+    /// Sequence number of the ledger that included the transaction.
+    #[serde(default)]
+    pub ledger: Option<u64>,
 }
 
 impl Transaction {
@@ -36,9 +64,39 @@ impl Transaction {
             operations,
             memo,
             result_codes,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         }
     }
 
+    /// Attach decoded contract events.
+    pub fn with_contract_events(mut self, contract_events: Vec<ContractEvent>) -> Self {
+        self.contract_events = contract_events;
+        self
+    }
+
+    /// Attach the decoded contract call.
+    pub fn with_contract_call(mut self, contract_call: Option<ContractCall>) -> Self {
+        self.contract_call = contract_call;
+        self
+    }
+
+    /// Attach the submitting account.
+    pub fn with_source(mut self, source: TransactionSource) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// Attach when and in which ledger the transaction was included.
+    pub fn with_ledger(mut self, created_at: Option<String>, ledger: Option<u64>) -> Self {
+        self.created_at = created_at;
+        self.ledger = ledger;
+        self
+    }
+
     pub fn payment_operations(&self) -> Vec<&PaymentOperation> {
         self.operations
             .iter()
@@ -95,7 +153,7 @@ mod tests {
             asset_type: "native".to_string(),
             asset_code: None,
             asset_issuer: None,
-            amount: amount.to_string(),
+            amount: amount.parse().unwrap(),
         })
     }
 
@@ -119,12 +177,17 @@ mod tests {
             ],
             memo: None,
             result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         };
 
         let payments = tx.payment_operations();
         assert_eq!(payments.len(), 2);
-        assert_eq!(payments[0].amount, "50");
-        assert_eq!(payments[1].amount, "25");
+        assert_eq!(payments[0].amount.trimmed(), "50");
+        assert_eq!(payments[1].amount.trimmed(), "25");
     }
 
     #[test]
@@ -136,6 +199,11 @@ mod tests {
             operations: vec![create_unsupported(), create_payment("10")],
             memo: None,
             result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         };
 
         let tx_without_payment = Transaction {
@@ -145,6 +213,11 @@ mod tests {
             operations: vec![create_unsupported()],
             memo: None,
             result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         };
 
         assert!(tx_with_payment.has_payments());
@@ -165,6 +238,11 @@ mod tests {
             ],
             memo: None,
             result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         };
 
         assert_eq!(tx.payment_count(), 3);
@@ -179,6 +257,11 @@ mod tests {
             operations: vec![create_payment("100")],
             memo: None,
             result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         };
 
         assert!(tx.is_failed());
@@ -194,6 +277,11 @@ mod tests {
             operations: vec![create_payment("10")],
             memo: Some(Memo::text("test").unwrap()),
             result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         };
 
         let tx_without_memo = Transaction {
@@ -203,6 +291,11 @@ mod tests {
             operations: vec![create_payment("10")],
             memo: None,
             result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         };
 
         assert!(tx_with_memo.has_memo());
@@ -219,6 +312,11 @@ mod tests {
             operations: vec![create_payment("10")],
             memo: Some(memo.clone()),
             result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         };
 
         assert_eq!(tx.get_memo(), Some(&memo));
@@ -237,6 +335,11 @@ mod tests {
                 transaction: Some("tx_bad_seq".to_string()),
                 operations: vec!["op_no_trust".to_string()],
             }),
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         };
 
         assert!(tx.is_failed());
@@ -254,6 +357,11 @@ mod tests {
             operations: vec![create_payment("10")],
             memo: None,
             result_codes: None,
+            contract_events: vec![],
+            contract_call: None,
+            source: None,
+            created_at: None,
+            ledger: None,
         };
 
         assert!(!tx.is_failed());
@@ -1,18 +1,54 @@
 use axum::{
     Json,
-    extract::{Extension, Path, Query, State},
+    extract::{Extension, OriginalUri, Path, Query, State},
+    http::{
+        HeaderMap,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
+    response::{IntoResponse, Response},
 };
+use chrono::NaiveDate;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Instant;
+use tokio::task::JoinSet;
 use tracing::{error, info, info_span};
+use utoipa::ToSchema;
 
 use crate::{
-    errors::AppError, explain::account::explain_account_with_org_name,
-    middleware::request_id::RequestId, services::horizon::HorizonClient,
+    errors::AppError,
+    errors::ErrorBody,
+    errors::HorizonError,
+    explain::account::{AssetBalanceExplanation, RecentOperationExplanation},
+    explain::anomaly::AccountAnomaly,
+    explain::changes::AccountChanges,
+    explain::flags_history::AccountFlagsHistory,
+    explain::glossary::GlossaryTerm,
+    explain::issuer_label::LabelVerification,
+    explain::offer::AccountOffersExplanation,
+    explain::payment_graph::to_dot,
+    explain::related_accounts::RelatedAccountsExplanation,
+    explain::signer::SignerExplanation,
+    explain::time::{Clock, parse_timestamp},
+    middleware::request_id::RequestId,
+    models::strkey::{self, StrKeyKind},
+    routes::pagination::{PageLinks, is_valid_cursor, page_links},
+    routes::query::{FieldErrors, SortOrder, ValidateQuery, ValidatedQuery},
+    routes::tx::now,
+    services::account::{AccountOptions, AccountService, SyncPoint},
+    services::blocklist,
+    services::full_history::{HistoryWalk, NDJSON_CONTENT_TYPE},
+    services::horizon::{AccountHistory, HorizonAccountTransaction},
+    services::horizon_api::HorizonApi,
+    services::operation_cache::OperationCache,
+    services::pdf::render_statement,
+    services::policy::{Parties, policy},
+    services::privacy::{apply_privacy, privacy_enabled},
+    services::spam::{self, SpamKind},
 };
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AccountExplanationResponse {
     pub address: String,
     pub summary: String,
@@ -21,17 +57,233 @@ pub struct AccountExplanationResponse {
     pub signer_count: u32,
     pub home_domain: Option<String>,
     pub org_name: Option<String>,
+    /// "verified" when the home domain's stellar.toml lists this account,
+    /// "claimed" when it does not. Null without an `org_name`.
+    #[serde(default)]
+    pub org_name_verification: Option<LabelVerification>,
     pub flag_descriptions: Vec<String>,
+    pub assets: Vec<AssetBalanceExplanation>,
+    /// Stellar terms the summaries mention, with definitions.
+    #[serde(default)]
+    pub terms: Vec<GlossaryTerm>,
+    /// The account's latest operations, newest first.
+    #[serde(default)]
+    pub recent_operations: Vec<RecentOperationExplanation>,
+    /// Open offers, present when `include_offers=true` and they could be fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub offers: Option<AccountOffersExplanation>,
+    /// Unusual recent transactions, present when `include_anomalies=true`
+    /// and the history could be read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anomalies: Option<Vec<AccountAnomaly>>,
+    /// Who can sign for the account. Pre-authorized transaction signers say
+    /// whether their transaction is still waiting to be submitted.
+    #[serde(default)]
+    pub signers: Vec<SignerExplanation>,
+}
+
+/// Most recent operations `/account/:address` will explain.
+pub const MAX_RECENT_OPERATIONS: u32 = 20;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountQuery {
+    pub privacy: Option<bool>,
+    /// How many of the latest operations to explain, 0-20 (default 5).
+    pub operations: Option<u32>,
+    /// Also list open DEX offers.
+    pub include_offers: Option<bool>,
+    /// Flag recent transactions unlike the account's usual activity.
+    pub include_anomalies: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct AccountTransactionsQuery {
+    /// Most transactions to return, 1-50 (default 10).
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+    /// "asc" (default, oldest first) or "desc".
+    pub order: Option<SortOrder>,
+    pub privacy: Option<bool>,
+    /// Drop incoming dust payments and known spam-asset airdrops.
+    pub hide_spam: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountOffersQuery {
+    /// Most offers to return, 1-50 (default 20).
+    pub limit: Option<u32>,
+    pub privacy: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountFlagsHistoryQuery {
+    /// Most changes to return, 1-50 (default 20).
+    pub limit: Option<u32>,
+    pub cursor: Option<String>,
+    /// "desc" (default, newest first) or "asc".
+    pub order: Option<SortOrder>,
+    pub privacy: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountChangesQuery {
+    /// The `cursor` of the last sync, or an RFC 3339 timestamp.
+    pub since: Since,
+    /// Most transactions to return, 1-50 (default 20).
     pub limit: Option<u32>,
+    pub privacy: Option<bool>,
+}
+
+/// A sync's `since`: the cursor the last sync returned, or a timestamp.
+#[derive(Debug, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Since(pub SyncPoint);
+
+impl TryFrom<String> for Since {
+    type Error = &'static str;
+
+    fn try_from(since: String) -> Result<Self, Self::Error> {
+        let since = since.trim();
+        // "now" would skip whatever happens before the next sync.
+        if since != "now" && is_valid_cursor(since) {
+            Ok(Self(SyncPoint::Cursor(since.to_string())))
+        } else if let Some(at) = parse_timestamp(since) {
+            Ok(Self(SyncPoint::Time(at)))
+        } else {
+            Err("expected the cursor of a previous sync or an RFC 3339 timestamp")
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountGraphQuery {
+    /// Hops from the account, 1-3 (default 2).
+    pub depth: Option<u32>,
+    pub format: Option<GraphFormat>,
+    pub privacy: Option<bool>,
+}
+
+/// How `/account/:address/graph` is returned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GraphFormat {
+    #[default]
+    Json,
+    /// Graphviz DOT.
+    Dot,
+}
+
+/// Most hops `/account/:address/graph` follows.
+pub const MAX_GRAPH_DEPTH: u32 = 3;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RelatedAccountsQuery {
+    pub address: String,
+    pub privacy: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AccountStatementQuery {
+    /// First day, "YYYY-MM-DD" in UTC.
+    pub from: String,
+    /// Last day, inclusive.
+    pub to: String,
+    /// "json" (default) or "pdf".
+    pub format: Option<String>,
+    pub privacy: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FullHistoryQuery {
+    /// Resume after the transaction whose line carried this cursor.
     pub cursor: Option<String>,
-    pub order: Option<String>,
+    /// "asc" (default, oldest first) or "desc".
+    pub order: Option<SortOrder>,
+    pub privacy: Option<bool>,
+}
+
+/// Most items one page of a listing holds.
+const MAX_PAGE_SIZE: u32 = 50;
+
+impl ValidateQuery for AccountQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.check(
+            "operations",
+            self.operations
+                .is_none_or(|operations| operations <= MAX_RECENT_OPERATIONS),
+            format!("operations must be between 0 and {MAX_RECENT_OPERATIONS}"),
+        );
+    }
+}
+
+impl ValidateQuery for AccountGraphQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.check(
+            "depth",
+            self.depth
+                .is_none_or(|depth| (1..=MAX_GRAPH_DEPTH).contains(&depth)),
+            format!("depth must be between 1 and {MAX_GRAPH_DEPTH}"),
+        );
+    }
+}
+
+impl ValidateQuery for AccountTransactionsQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.limit(self.limit, MAX_PAGE_SIZE);
+        errors.check(
+            "cursor",
+            self.cursor.as_deref().is_none_or(is_valid_cursor),
+            "cursor must be a paging token from a previous page's next_cursor or prev_cursor",
+        );
+    }
+}
+
+impl ValidateQuery for AccountOffersQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.limit(self.limit, MAX_PAGE_SIZE);
+    }
+}
+
+impl ValidateQuery for AccountFlagsHistoryQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.limit(self.limit, MAX_PAGE_SIZE);
+        errors.check(
+            "cursor",
+            self.cursor.as_deref().is_none_or(is_valid_cursor),
+            "cursor must be the next_cursor of a previous response",
+        );
+    }
+}
+
+impl ValidateQuery for AccountChangesQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.limit(self.limit, MAX_PAGE_SIZE);
+    }
+}
+
+impl ValidateQuery for FullHistoryQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.check(
+            "cursor",
+            self.cursor.as_deref().is_none_or(is_valid_cursor),
+            "cursor must be a cursor from a previous line of the stream",
+        );
+    }
 }
 
-#[derive(Debug, Serialize)]
+/// Longest range one statement or export covers, in days.
+pub const MAX_STATEMENT_DAYS: i64 = 366;
+
+#[derive(Debug, Serialize, Deserialize)]
 pub struct TransactionSummary {
     pub hash: String,
     pub created_at: String,
@@ -41,18 +293,35 @@ pub struct TransactionSummary {
     pub summary: String,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct PaginatedResponse<T> {
     pub items: Vec<T>,
+    /// The `limit` this page was requested with.
+    pub page_size: u32,
     pub next_cursor: Option<String>,
     pub prev_cursor: Option<String>,
+    /// Fully-qualified URLs for this page and its neighbours.
+    pub links: PageLinks,
+    /// What `hide_spam` removed from this page; absent when it was not set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<HiddenCounts>,
+}
+
+/// Items removed from a page by `hide_spam`, so totals stay transparent.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HiddenCounts {
+    pub dust: usize,
+    pub spam_assets: usize,
+    pub total: usize,
 }
 
-pub async fn get_account_transactions(
+pub async fn get_account_transactions<H: HorizonApi>(
     Path(address): Path<String>,
-    Query(params): Query<AccountTransactionsQuery>,
-    State(client): State<Arc<HorizonClient>>,
+    ValidatedQuery(params): ValidatedQuery<AccountTransactionsQuery>,
+    State(client): State<Arc<H>>,
     Extension(request_id): Extension<RequestId>,
+    OriginalUri(uri): OriginalUri,
+    headers: HeaderMap,
 ) -> Result<Json<PaginatedResponse<TransactionSummary>>, AppError> {
     let span = info_span!(
         "account_transactions_request",
@@ -68,34 +337,10 @@ pub async fn get_account_transactions(
         "incoming_request"
     );
 
-    let limit = params.limit.unwrap_or(10);
-    let order = params.order.as_deref().unwrap_or("asc");
-
-    if limit == 0 || limit > 50 {
-        let app_error = AppError::BadRequest("limit must be between 1 and 50".to_string());
-        info!(
-            request_id = %request_id,
-            address = %address,
-            status = app_error.status_code().as_u16(),
-            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
-            error = ?app_error,
-            "request_completed"
-        );
-        return Err(app_error);
-    }
+    policy().check(&Parties::account(&address), "this account")?;
 
-    if order != "asc" && order != "desc" {
-        let app_error = AppError::BadRequest("order must be 'asc' or 'desc'".to_string());
-        info!(
-            request_id = %request_id,
-            address = %address,
-            status = app_error.status_code().as_u16(),
-            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
-            error = ?app_error,
-            "request_completed"
-        );
-        return Err(app_error);
-    }
+    let limit = params.limit.unwrap_or(10);
+    let order = params.order.unwrap_or(SortOrder::Asc).as_str();
 
     let horizon_started_at = Instant::now();
     let fetch_result = client
@@ -127,6 +372,19 @@ pub async fn get_account_transactions(
         "horizon_fetch_completed"
     );
 
+    let (records, hidden) = if params.hide_spam.unwrap_or(false) {
+        let (kept, hidden) = remove_spam(&client, &address, records).await;
+        info!(
+            request_id = %request_id,
+            address = %address,
+            hidden = hidden.total,
+            "spam_filtered"
+        );
+        (kept, Some(hidden))
+    } else {
+        (records, None)
+    };
+
     let items = records
         .into_iter()
         .map(|tx| {
@@ -163,19 +421,84 @@ pub async fn get_account_transactions(
         "request_completed"
     );
 
-    Ok(Json(PaginatedResponse {
+    let links = page_links(
+        &headers,
+        &uri,
+        order,
+        next_cursor.as_deref(),
+        prev_cursor.as_deref(),
+    );
+    let page = PaginatedResponse {
         items,
+        page_size: limit,
         next_cursor,
         prev_cursor,
-    }))
+        links,
+        hidden,
+    };
+
+    Ok(Json(apply_privacy(page, privacy_enabled(params.privacy))?))
+}
+
+/// Split a page into the transactions to show and counts of the spam removed.
+///
+/// Only transactions submitted by someone else can be spam, so only their
+/// operations are fetched. A transaction whose operations cannot be fetched
+/// is kept.
+async fn remove_spam<H: HorizonApi>(
+    client: &Arc<H>,
+    address: &str,
+    records: Vec<HorizonAccountTransaction>,
+) -> (Vec<HorizonAccountTransaction>, HiddenCounts) {
+    let mut lookups = JoinSet::new();
+    for (index, tx) in records.iter().enumerate() {
+        if tx.source_account.as_deref() == Some(address) {
+            continue;
+        }
+        let client = Arc::clone(client);
+        let hash = tx.hash.clone();
+        lookups.spawn(async move { (index, client.fetch_operations(&hash).await) });
+    }
+
+    let mut verdicts: Vec<Option<SpamKind>> = vec![None; records.len()];
+    while let Some(joined) = lookups.join_next().await {
+        if let Ok((index, Ok(operations))) = joined {
+            verdicts[index] = spam::filter().classify(
+                address,
+                records[index].source_account.as_deref(),
+                &operations,
+            );
+        }
+    }
+
+    let mut hidden = HiddenCounts::default();
+    let kept = records
+        .into_iter()
+        .zip(verdicts)
+        .filter_map(|(tx, verdict)| match verdict {
+            None => Some(tx),
+            Some(kind) => {
+                match kind {
+                    SpamKind::Dust => hidden.dust += 1,
+                    SpamKind::SpamAsset => hidden.spam_assets += 1,
+                }
+                hidden.total += 1;
+                None
+            }
+        })
+        .collect();
+    (kept, hidden)
 }
 
 /// GET /account/:address
 /// Returns a plain-English explanation of a Stellar account.
-pub async fn get_account_explanation(
+pub async fn get_account_explanation<H: HorizonApi>(
     Path(address): Path<String>,
-    State(horizon_client): State<Arc<HorizonClient>>,
+    ValidatedQuery(params): ValidatedQuery<AccountQuery>,
+    State(horizon_client): State<Arc<H>>,
     Extension(request_id): Extension<RequestId>,
+    clock: Option<Extension<Arc<dyn Clock>>>,
+    operation_cache: Option<Extension<Arc<OperationCache>>>,
 ) -> Result<Json<AccountExplanationResponse>, AppError> {
     let span = info_span!(
         "account_explanation_request",
@@ -187,10 +510,29 @@ pub async fn get_account_explanation(
 
     info!(request_id = %request_id, address = %address, "incoming_request");
 
-    let account = match horizon_client.fetch_account(&address).await {
-        Ok(a) => a,
+    policy().check(&Parties::account(&address), "this account")?;
+
+    let recent_operations = params.operations.unwrap_or(5);
+    let options = AccountOptions {
+        recent_operations,
+        include_offers: params.include_offers.unwrap_or(false),
+        anomalies_as_of: params
+            .include_anomalies
+            .unwrap_or(false)
+            .then(|| now(clock).date_naive()),
+    };
+
+    let service = AccountService::new(Arc::clone(&horizon_client))
+        .with_operation_cache(operation_cache.map(|Extension(cache)| cache));
+    let report = match service.explain(&address, &options).await {
+        Ok(report) => report,
         Err(err) => {
-            let app_error: AppError = err.into();
+            let app_error = match err {
+                HorizonError::AccountNotFound => {
+                    missing_account_error(horizon_client.as_ref(), &address).await
+                }
+                other => other.into(),
+            };
             error!(
                 request_id = %request_id,
                 address = %address,
@@ -202,110 +544,1738 @@ pub async fn get_account_explanation(
         }
     };
 
-    // Attempt stellar.toml org name lookup if the account has a home domain
-    let org_name = if let Some(ref domain) = account.home_domain {
-        let domain_url = if domain.starts_with("http") {
-            domain.clone()
-        } else {
-            format!("https://{domain}")
-        };
-        horizon_client
-            .fetch_stellar_toml_org_name(&domain_url)
-            .await
-    } else {
-        None
-    };
-
-    let explanation = explain_account_with_org_name(&account, org_name);
-
     info!(
         request_id = %request_id,
         address = %address,
+        recent_operations = report.recent_operations.len(),
         total_duration_ms = request_started_at.elapsed().as_millis() as u64,
         status = 200u16,
         "request_completed"
     );
 
-    Ok(Json(AccountExplanationResponse {
-        address: account.account_id,
+    let explanation = report.explanation;
+    let response = AccountExplanationResponse {
+        address: report.account.account_id,
         summary: explanation.summary,
         xlm_balance: explanation.xlm_balance,
         asset_count: explanation.asset_count,
         signer_count: explanation.signer_count,
         home_domain: explanation.home_domain,
         org_name: explanation.org_name,
+        org_name_verification: explanation.org_name_verification,
         flag_descriptions: explanation.flag_descriptions,
-    }))
+        assets: blocklist::flag_balances(explanation.assets),
+        terms: explanation.terms,
+        recent_operations: report.recent_operations,
+        offers: report.offers,
+        anomalies: report.anomalies,
+        signers: report.signers,
+    };
+
+    Ok(Json(apply_privacy(
+        response,
+        privacy_enabled(params.privacy),
+    )?))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Addresses one batch may hold.
+const MAX_BATCH_ACCOUNTS: usize = 25;
 
-    fn validate(limit: Option<u32>, order: Option<&str>) -> Result<(u32, &'static str), AppError> {
-        let limit = limit.unwrap_or(10);
-        let order = order.unwrap_or("asc");
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AccountBatchRequest {
+    /// Account addresses (G...), 1 to 25.
+    pub addresses: Vec<String>,
+    /// Mask addresses.
+    #[serde(default)]
+    pub privacy: Option<bool>,
+}
 
-        if limit == 0 || limit > 50 {
-            return Err(AppError::BadRequest(
-                "limit must be between 1 and 50".to_string(),
-            ));
-        }
-        if order != "asc" && order != "desc" {
-            return Err(AppError::BadRequest(
-                "order must be 'asc' or 'desc'".to_string(),
-            ));
-        }
-        Ok((limit, if order == "asc" { "asc" } else { "desc" }))
-    }
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountBatchResponse {
+    /// One entry per requested address, in the order requested.
+    pub accounts: Vec<AccountBatchItem>,
+}
 
-    #[test]
-    fn test_default_pagination() {
-        let (limit, order) = validate(None, None).unwrap();
-        assert_eq!(limit, 10);
-        assert_eq!(order, "asc");
-    }
+/// One account of a batch: its summary, or why it could not be explained.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountBatchItem {
+    pub address: String,
+    /// Null when the account could not be explained.
+    pub account: Option<AccountSummary>,
+    /// Why the account could not be explained, shaped like the `error` of
+    /// a failed `GET /account/:address`. Null on success.
+    #[schema(value_type = Option<Object>)]
+    pub error: Option<ErrorBody>,
+}
 
-    #[test]
-    fn test_custom_limit_and_order() {
-        let (limit, order) = validate(Some(25), Some("desc")).unwrap();
-        assert_eq!(limit, 25);
-        assert_eq!(order, "desc");
-    }
+/// The headline of an account explanation, without its balances, recent
+/// operations, or signers.
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AccountSummary {
+    pub summary: String,
+    pub xlm_balance: String,
+    pub asset_count: usize,
+    pub signer_count: u32,
+    pub home_domain: Option<String>,
+    pub org_name: Option<String>,
+    pub org_name_verification: Option<LabelVerification>,
+}
 
-    #[test]
-    fn test_max_limit_accepted() {
-        let (limit, _) = validate(Some(50), None).unwrap();
-        assert_eq!(limit, 50);
-    }
+/// POST /accounts/batch
+/// Summaries of up to 25 accounts, fetched concurrently. An account that
+/// cannot be explained gets an error entry without failing the others.
+#[utoipa::path(
+    post,
+    path = "/accounts/batch",
+    request_body = AccountBatchRequest,
+    responses(
+        (status = 200, description = "One summary or error per address", body = AccountBatchResponse),
+        (status = 400, description = "No addresses, more than 25, or a repeated address")
+    )
+)]
+pub async fn post_accounts_batch<H: HorizonApi>(
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+    Json(request): Json<AccountBatchRequest>,
+) -> Result<Json<AccountBatchResponse>, AppError> {
+    let addresses: Vec<String> = request
+        .addresses
+        .iter()
+        .map(|address| address.trim().to_string())
+        .collect();
+    let count = addresses.len();
+    let span = info_span!("accounts_batch_request", request_id = %request_id, addresses = count);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
 
-    #[test]
-    fn test_limit_zero_rejected() {
-        assert!(matches!(
-            validate(Some(0), None),
-            Err(AppError::BadRequest(_))
-        ));
+    info!(request_id = %request_id, addresses = count, "incoming_request");
+
+    if let Err(app_error) = validate_batch_addresses(&addresses) {
+        info!(
+            request_id = %request_id,
+            addresses = count,
+            status = app_error.status_code().as_u16(),
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            error = ?app_error,
+            "request_completed"
+        );
+        return Err(app_error);
     }
 
-    #[test]
-    fn test_limit_over_max_rejected() {
-        assert!(matches!(
-            validate(Some(51), None),
-            Err(AppError::BadRequest(_))
-        ));
+    let fetch_started_at = Instant::now();
+    let results = futures_util::future::join_all(
+        addresses
+            .iter()
+            .map(|address| batch_account_summary(&horizon_client, address)),
+    )
+    .await;
+    let fetch_duration_ms = fetch_started_at.elapsed().as_millis() as u64;
+
+    let mut failed = 0usize;
+    let accounts = addresses
+        .into_iter()
+        .zip(results)
+        .map(|(address, result)| match result {
+            Ok(account) => AccountBatchItem {
+                address,
+                account: Some(account),
+                error: None,
+            },
+            Err(app_error) => {
+                failed += 1;
+                error!(
+                    request_id = %request_id,
+                    address = %address,
+                    status = app_error.status_code().as_u16(),
+                    error = ?app_error,
+                    "batch_account_failed"
+                );
+                AccountBatchItem {
+                    address,
+                    account: None,
+                    error: Some(app_error.to_api_error().error),
+                }
+            }
+        })
+        .collect();
+
+    info!(
+        request_id = %request_id,
+        addresses = count,
+        failed,
+        fetch_duration_ms,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(apply_privacy(
+        AccountBatchResponse { accounts },
+        privacy_enabled(request.privacy),
+    )?))
+}
+
+async fn batch_account_summary<H: HorizonApi>(
+    horizon_client: &Arc<H>,
+    address: &str,
+) -> Result<AccountSummary, AppError> {
+    if let Err(err) = strkey::validate(address, &[StrKeyKind::Account]) {
+        return Err(AppError::BadRequest(format!(
+            "Invalid account address: {err}."
+        )));
     }
+    policy().check(&Parties::account(address), "this account")?;
+    let options = AccountOptions {
+        recent_operations: 0,
+        include_offers: false,
+        anomalies_as_of: None,
+    };
+    let report = match AccountService::new(Arc::clone(horizon_client))
+        .explain(address, &options)
+        .await
+    {
+        Ok(report) => report,
+        Err(HorizonError::AccountNotFound) => {
+            return Err(missing_account_error(horizon_client.as_ref(), address).await);
+        }
+        Err(other) => return Err(other.into()),
+    };
+    let explanation = report.explanation;
+    Ok(AccountSummary {
+        summary: explanation.summary,
+        xlm_balance: explanation.xlm_balance,
+        asset_count: explanation.asset_count,
+        signer_count: explanation.signer_count,
+        home_domain: explanation.home_domain,
+        org_name: explanation.org_name,
+        org_name_verification: explanation.org_name_verification,
+    })
+}
 
-    #[test]
-    fn test_invalid_order_rejected() {
-        assert!(matches!(
-            validate(Some(10), Some("latest")),
-            Err(AppError::BadRequest(_))
-        ));
+fn validate_batch_addresses(addresses: &[String]) -> Result<(), AppError> {
+    if addresses.is_empty() || addresses.len() > MAX_BATCH_ACCOUNTS {
+        return Err(AppError::BadRequest(format!(
+            "A batch takes between 1 and {MAX_BATCH_ACCOUNTS} addresses."
+        )));
+    }
+    for (i, address) in addresses.iter().enumerate() {
+        if addresses[..i].contains(address) {
+            return Err(AppError::BadRequest(format!(
+                "`addresses[{i}]` repeats an earlier address."
+            )));
+        }
     }
+    Ok(())
+}
 
-    #[test]
-    fn test_cursor_navigation_values_are_passed_through() {
-        let cursor: Option<String> = Some("157639564177408001".to_string());
-        assert_eq!(cursor.as_deref(), Some("157639564177408001"));
+/// GET /account/:address/offers
+/// Lists an account's open DEX offers with how much of each has been filled.
+#[utoipa::path(
+    get,
+    path = "/account/{address}/offers",
+    params(
+        ("address" = String, Path, description = "Stellar account address (G...)"),
+        ("limit" = Option<u32>, Query, description = "Maximum offers to return, 1-50 (default 20)"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses")
+    ),
+    responses(
+        (status = 200, description = "Open offers explanation", body = AccountOffersExplanation),
+        (status = 400, description = "Invalid limit"),
+        (status = 404, description = "Account not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_account_offers<H: HorizonApi>(
+    Path(address): Path<String>,
+    ValidatedQuery(params): ValidatedQuery<AccountOffersQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Json<AccountOffersExplanation>, AppError> {
+    let span = info_span!(
+        "account_offers_request",
+        request_id = %request_id,
+        address = %address
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, address = %address, "incoming_request");
+
+    policy().check(&Parties::account(&address), "this account")?;
+
+    let limit = params.limit.unwrap_or(20);
+
+    let horizon_started_at = Instant::now();
+    let service = AccountService::new(horizon_client);
+    let explanation = match service.offers(&address, limit).await {
+        Ok(explanation) => explanation,
+        Err(err) => {
+            let app_error: AppError = err.into();
+            error!(
+                request_id = %request_id,
+                address = %address,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_offers_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+    let horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64;
+
+    info!(
+        request_id = %request_id,
+        address = %address,
+        offers = explanation.offer_count,
+        horizon_fetch_duration_ms,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(apply_privacy(
+        explanation,
+        privacy_enabled(params.privacy),
+    )?))
+}
+
+/// GET /account/:address/flags-history
+/// When the account's flags, thresholds, and signers changed, from its
+/// set_options operations.
+#[utoipa::path(
+    get,
+    path = "/account/{address}/flags-history",
+    params(
+        ("address" = String, Path, description = "Stellar account address (G...)"),
+        ("limit" = Option<u32>, Query, description = "Maximum changes to return, 1-50 (default 20)"),
+        ("cursor" = Option<String>, Query, description = "next_cursor from a previous response"),
+        ("order" = Option<String>, Query, description = "\"desc\" (default, newest first) or \"asc\""),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses")
+    ),
+    responses(
+        (status = 200, description = "Settings changes", body = AccountFlagsHistory),
+        (status = 400, description = "Invalid limit, cursor, or order"),
+        (status = 404, description = "Account not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_account_flags_history<H: HorizonApi>(
+    Path(address): Path<String>,
+    ValidatedQuery(params): ValidatedQuery<AccountFlagsHistoryQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+    operation_cache: Option<Extension<Arc<OperationCache>>>,
+) -> Result<Json<AccountFlagsHistory>, AppError> {
+    let span = info_span!(
+        "account_flags_history_request",
+        request_id = %request_id,
+        address = %address
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, address = %address, "incoming_request");
+
+    policy().check(&Parties::account(&address), "this account")?;
+
+    let limit = params.limit.unwrap_or(20) as usize;
+    let order = params.order.unwrap_or(SortOrder::Desc).as_str();
+
+    let horizon_started_at = Instant::now();
+    let service = AccountService::new(Arc::clone(&horizon_client))
+        .with_operation_cache(operation_cache.map(|Extension(cache)| cache));
+    let history = match service
+        .flags_history(&address, limit, params.cursor.as_deref(), order)
+        .await
+    {
+        Ok(history) => history,
+        Err(err) => {
+            let app_error = match err {
+                HorizonError::AccountNotFound => {
+                    missing_account_error(horizon_client.as_ref(), &address).await
+                }
+                other => other.into(),
+            };
+            error!(
+                request_id = %request_id,
+                address = %address,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_flags_history_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+
+    info!(
+        request_id = %request_id,
+        address = %address,
+        changes = history.changes.len(),
+        scanned_operations = history.scanned_operations,
+        horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(apply_privacy(
+        history,
+        privacy_enabled(params.privacy),
+    )?))
+}
+
+/// GET /account/:address/graph
+/// Payment flows around an account as a directed graph, as JSON or as
+/// Graphviz DOT.
+#[utoipa::path(
+    get,
+    path = "/account/{address}/graph",
+    params(
+        ("address" = String, Path, description = "Stellar account address (G...)"),
+        ("depth" = Option<u32>, Query, description = "Hops from the account, 1-3 (default 2)"),
+        ("format" = Option<String>, Query, description = "\"json\" (default) or \"dot\""),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses")
+    ),
+    responses(
+        (status = 200, description = "The payment graph", content(
+            ("application/json" = crate::explain::payment_graph::PaymentGraph),
+            ("text/vnd.graphviz" = String)
+        )),
+        (status = 400, description = "Invalid depth or format"),
+        (status = 404, description = "Account not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_account_graph<H: HorizonApi>(
+    Path(address): Path<String>,
+    ValidatedQuery(params): ValidatedQuery<AccountGraphQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Response, AppError> {
+    let span = info_span!(
+        "account_graph_request",
+        request_id = %request_id,
+        address = %address
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, address = %address, "incoming_request");
+
+    policy().check(&Parties::account(&address), "this account")?;
+
+    let depth = params.depth.unwrap_or(2);
+    let dot = params.format.unwrap_or_default() == GraphFormat::Dot;
+
+    let horizon_started_at = Instant::now();
+    let service = AccountService::new(Arc::clone(&horizon_client));
+    let graph = match service.payment_graph(&address, depth).await {
+        Ok(graph) => graph,
+        Err(err) => {
+            let app_error = match err {
+                HorizonError::AccountNotFound => {
+                    missing_account_error(horizon_client.as_ref(), &address).await
+                }
+                other => other.into(),
+            };
+            error!(
+                request_id = %request_id,
+                address = %address,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_graph_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+    let graph = apply_privacy(graph, privacy_enabled(params.privacy))?;
+
+    info!(
+        request_id = %request_id,
+        address = %address,
+        nodes = graph.nodes.len(),
+        edges = graph.edges.len(),
+        truncated = graph.truncated,
+        format = if dot { "dot" } else { "json" },
+        horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    if !dot {
+        return Ok(Json(graph).into_response());
+    }
+    Ok((
+        [(CONTENT_TYPE, "text/vnd.graphviz; charset=utf-8")],
+        to_dot(&graph),
+    )
+        .into_response())
+}
+
+/// GET /accounts/related?address=...
+/// Accounts connected to an account through shared signers or sponsorship.
+#[utoipa::path(
+    get,
+    path = "/accounts/related",
+    params(
+        ("address" = String, Query, description = "Stellar account address (G...)"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses")
+    ),
+    responses(
+        (status = 200, description = "Related accounts", body = RelatedAccountsExplanation),
+        (status = 400, description = "Missing address"),
+        (status = 404, description = "Account not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_related_accounts<H: HorizonApi>(
+    Query(params): Query<RelatedAccountsQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Json<RelatedAccountsExplanation>, AppError> {
+    let address = params.address.trim().to_string();
+    let span = info_span!(
+        "related_accounts_request",
+        request_id = %request_id,
+        address = %address
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, address = %address, "incoming_request");
+
+    if address.is_empty() {
+        let app_error = AppError::BadRequest("address must not be empty".to_string());
+        info!(
+            request_id = %request_id,
+            status = app_error.status_code().as_u16(),
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            error = ?app_error,
+            "request_completed"
+        );
+        return Err(app_error);
+    }
+
+    policy().check(&Parties::account(&address), "this account")?;
+
+    let horizon_started_at = Instant::now();
+    let service = AccountService::new(Arc::clone(&horizon_client));
+    let explanation = match service.related(&address).await {
+        Ok(explanation) => explanation,
+        Err(err) => {
+            let app_error = match err {
+                HorizonError::AccountNotFound => {
+                    missing_account_error(horizon_client.as_ref(), &address).await
+                }
+                other => other.into(),
+            };
+            error!(
+                request_id = %request_id,
+                address = %address,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_related_accounts_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+
+    info!(
+        request_id = %request_id,
+        address = %address,
+        related = explanation.related.len(),
+        truncated = explanation.truncated,
+        horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(apply_privacy(
+        explanation,
+        privacy_enabled(params.privacy),
+    )?))
+}
+
+/// GET /account/:address/changes
+/// The account's transactions since a client's last sync, explained, with
+/// per-asset balance deltas and the cursor to sync from next time.
+#[utoipa::path(
+    get,
+    path = "/account/{address}/changes",
+    params(
+        ("address" = String, Path, description = "Stellar account address (G...)"),
+        ("since" = String, Query, description = "cursor from the previous sync, or an RFC 3339 timestamp"),
+        ("limit" = Option<u32>, Query, description = "Maximum transactions to return, 1-50 (default 20)"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses and redact memo contents")
+    ),
+    responses(
+        (status = 200, description = "Changes since the sync point", body = AccountChanges),
+        (status = 400, description = "Invalid since or limit, or a timestamp too far back"),
+        (status = 404, description = "Account not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_account_changes<H: HorizonApi>(
+    Path(address): Path<String>,
+    ValidatedQuery(params): ValidatedQuery<AccountChangesQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Json<AccountChanges>, AppError> {
+    let span = info_span!(
+        "account_changes_request",
+        request_id = %request_id,
+        address = %address
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, address = %address, "incoming_request");
+
+    policy().check(&Parties::account(&address), "this account")?;
+
+    let limit = params.limit.unwrap_or(20);
+
+    let horizon_started_at = Instant::now();
+    let service = AccountService::new(Arc::clone(&horizon_client));
+    let changes = match service.changes(&address, &params.since.0, limit).await {
+        Ok(Some(changes)) => changes,
+        Ok(None) => {
+            let app_error = AppError::BadRequest(
+                "since is further back than a sync reaches; use a cursor or a later time"
+                    .to_string(),
+            );
+            info!(
+                request_id = %request_id,
+                address = %address,
+                status = app_error.status_code().as_u16(),
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                error = ?app_error,
+                "request_completed"
+            );
+            return Err(app_error);
+        }
+        Err(err) => {
+            let app_error = match err {
+                HorizonError::AccountNotFound => {
+                    missing_account_error(horizon_client.as_ref(), &address).await
+                }
+                other => other.into(),
+            };
+            error!(
+                request_id = %request_id,
+                address = %address,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_changes_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+
+    info!(
+        request_id = %request_id,
+        address = %address,
+        transactions = changes.transactions.len(),
+        has_more = changes.has_more,
+        horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(apply_privacy(
+        changes,
+        privacy_enabled(params.privacy),
+    )?))
+}
+
+/// GET /account/:address/statement
+/// An account's balances, transactions, and per-asset totals over a date
+/// range, as JSON or as a PDF to download.
+#[utoipa::path(
+    get,
+    path = "/account/{address}/statement",
+    params(
+        ("address" = String, Path, description = "Stellar account address (G...)"),
+        ("from" = String, Query, description = "First day, YYYY-MM-DD (UTC)"),
+        ("to" = String, Query, description = "Last day, YYYY-MM-DD (UTC), inclusive"),
+        ("format" = Option<String>, Query, description = "\"json\" (default) or \"pdf\""),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses and redact memo contents")
+    ),
+    responses(
+        (status = 200, description = "The statement", content(
+            ("application/json" = crate::explain::statement::AccountStatement),
+            ("application/pdf" = String)
+        )),
+        (status = 400, description = "Invalid date range or format"),
+        (status = 404, description = "Account not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_account_statement<H: HorizonApi>(
+    Path(address): Path<String>,
+    Query(params): Query<AccountStatementQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Response, AppError> {
+    let span = info_span!(
+        "account_statement_request",
+        request_id = %request_id,
+        address = %address
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, address = %address, "incoming_request");
+
+    policy().check(&Parties::account(&address), "this account")?;
+
+    let (from, to, pdf) = match validate_statement_query(&params) {
+        Ok(validated) => validated,
+        Err(app_error) => {
+            info!(
+                request_id = %request_id,
+                address = %address,
+                status = app_error.status_code().as_u16(),
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                error = ?app_error,
+                "request_completed"
+            );
+            return Err(app_error);
+        }
+    };
+
+    let horizon_started_at = Instant::now();
+    let service = AccountService::new(horizon_client);
+    let statement = match service.statement(&address, from, to).await {
+        Ok(statement) => statement,
+        Err(err) => {
+            let app_error: AppError = err.into();
+            error!(
+                request_id = %request_id,
+                address = %address,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_statement_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+    let statement = apply_privacy(statement, privacy_enabled(params.privacy))?;
+
+    info!(
+        request_id = %request_id,
+        address = %address,
+        transactions = statement.transactions.len(),
+        truncated = statement.truncated,
+        format = if pdf { "pdf" } else { "json" },
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    if !pdf {
+        return Ok(Json(statement).into_response());
+    }
+    let bytes = render_statement(&statement).map_err(|err| {
+        error!(request_id = %request_id, error = %err, "statement_render_failed");
+        AppError::Internal("The statement could not be rendered as PDF.".to_string())
+    })?;
+    let filename = format!("statement-{}-{from}-{to}.pdf", statement.address);
+    Ok((
+        [
+            (CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        bytes,
+    )
+        .into_response())
+}
+
+/// GET /account/:address/history/full
+/// Every transaction on the account, explained and streamed as NDJSON.
+#[utoipa::path(
+    get,
+    path = "/account/{address}/history/full",
+    params(
+        ("address" = String, Path, description = "Stellar account address (G...)"),
+        ("cursor" = Option<String>, Query, description = "Resume after the line that carried this cursor"),
+        ("order" = Option<String>, Query, description = "\"asc\" (default, oldest first) or \"desc\""),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses and redact memo contents")
+    ),
+    responses(
+        (status = 200, description = "One explained transaction per line, each with the cursor that resumes after it", content(
+            ("application/x-ndjson" = String)
+        )),
+        (status = 400, description = "Invalid cursor or order"),
+        (status = 404, description = "Account not found"),
+        (status = 429, description = "Horizon is rate limiting the first page"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_account_full_history<H: HorizonApi + 'static>(
+    Path(address): Path<String>,
+    ValidatedQuery(params): ValidatedQuery<FullHistoryQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Response, AppError> {
+    let span = info_span!(
+        "account_full_history_request",
+        request_id = %request_id,
+        address = %address
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, address = %address, "incoming_request");
+
+    policy().check(&Parties::account(&address), "this account")?;
+
+    let order = params.order.unwrap_or(SortOrder::Asc).as_str().to_string();
+
+    // The first page is read before streaming starts, so a missing account
+    // or an unavailable Horizon still answers with an error status.
+    let horizon_started_at = Instant::now();
+    let walk = HistoryWalk::new(horizon_client, address.clone(), order)
+        .with_privacy(privacy_enabled(params.privacy));
+    let first = match walk.page(params.cursor.as_deref()).await {
+        Ok(page) => page,
+        Err(err) => {
+            let app_error: AppError = err.into();
+            error!(
+                request_id = %request_id,
+                address = %address,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_account_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+
+    info!(
+        request_id = %request_id,
+        address = %address,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(([(CONTENT_TYPE, NDJSON_CONTENT_TYPE)], walk.stream(first)).into_response())
+}
+
+/// The statement's first and last days, and whether it was asked for as PDF.
+fn validate_statement_query(
+    params: &AccountStatementQuery,
+) -> Result<(NaiveDate, NaiveDate, bool), AppError> {
+    let (from, to) = parse_date_range(&params.from, &params.to)?;
+    let pdf = match params.format.as_deref() {
+        None | Some("json") => false,
+        Some("pdf") => true,
+        Some(_) => {
+            return Err(AppError::BadRequest(
+                "format must be 'json' or 'pdf'".to_string(),
+            ));
+        }
+    };
+    Ok((from, to, pdf))
+}
+
+/// First and last days of a statement or export, "YYYY-MM-DD" each, at most
+/// [`MAX_STATEMENT_DAYS`] apart.
+pub(crate) fn parse_date_range(from: &str, to: &str) -> Result<(NaiveDate, NaiveDate), AppError> {
+    let day = |name: &str, value: &str| {
+        NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+            .map_err(|_| AppError::BadRequest(format!("{name} must be a date, YYYY-MM-DD")))
+    };
+    let from = day("from", from)?;
+    let to = day("to", to)?;
+    if from > to {
+        return Err(AppError::BadRequest(
+            "from must not be after to".to_string(),
+        ));
+    }
+    if (to - from).num_days() >= MAX_STATEMENT_DAYS {
+        return Err(AppError::BadRequest(format!(
+            "a date range covers at most {MAX_STATEMENT_DAYS} days"
+        )));
+    }
+    Ok((from, to))
+}
+
+/// Build the 404 for an address with no live account entry, distinguishing
+/// accounts that were merged away from addresses that were never funded.
+async fn missing_account_error(horizon_client: &impl HorizonApi, address: &str) -> AppError {
+    match horizon_client.fetch_account_history(address).await {
+        Ok(history) => account_history_error(address, history),
+        // History is best-effort; fall back to the plain not-found message.
+        Err(_) => HorizonError::AccountNotFound.into(),
+    }
+}
+
+fn account_history_error(address: &str, history: AccountHistory) -> AppError {
+    match history {
+        AccountHistory::Merged { into, closed_at } => AppError::AccountMerged {
+            address: address.to_string(),
+            merged_into: into,
+            closed_at,
+        },
+        AccountHistory::Inactive => AppError::NotFound(
+            "This account does not currently exist on the Stellar network, although it has past activity.".into(),
+        ),
+        AccountHistory::NeverCreated => AppError::NotFound(
+            "This account has never been created on the Stellar network. An account only exists once it has been funded with a create_account operation.".into(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::account::{Account, AccountFlags, Balance};
+    use crate::services::horizon::{HorizonOperation, HorizonTransaction};
+    use crate::services::horizon_api::FakeHorizon;
+
+    fn validate(query: &str) -> Result<AccountTransactionsQuery, AppError> {
+        ValidatedQuery::parse(query).map(|ValidatedQuery(query)| query)
+    }
+
+    fn rejected_field(query: &str) -> String {
+        match validate(query) {
+            Err(AppError::InvalidQuery(fields)) => fields[0].field.clone(),
+            other => panic!("expected InvalidQuery, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_default_pagination() {
+        let query = validate("").unwrap();
+        assert_eq!(query.limit, None);
+        assert_eq!(query.order, None);
+    }
+
+    #[test]
+    fn test_custom_limit_and_order() {
+        let query = validate("limit=25&order=desc").unwrap();
+        assert_eq!(query.limit, Some(25));
+        assert_eq!(query.order, Some(SortOrder::Desc));
+    }
+
+    #[test]
+    fn test_max_limit_accepted() {
+        assert_eq!(validate("limit=50").unwrap().limit, Some(50));
+    }
+
+    #[test]
+    fn test_limit_zero_rejected() {
+        assert_eq!(rejected_field("limit=0"), "limit");
+    }
+
+    #[test]
+    fn test_limit_over_max_rejected() {
+        assert_eq!(rejected_field("limit=51"), "limit");
+    }
+
+    #[test]
+    fn test_invalid_order_rejected() {
+        assert_eq!(rejected_field("limit=10&order=latest"), "order");
+        assert_eq!(rejected_field("cursor=12%26limit=200"), "cursor");
+    }
+
+    #[test]
+    fn test_merged_account_error_carries_details() {
+        let err = account_history_error(
+            "GOLD",
+            AccountHistory::Merged {
+                into: "GNEW".to_string(),
+                closed_at: Some("2024-03-01T10:15:00Z".to_string()),
+            },
+        );
+        assert_eq!(err.status_code(), axum::http::StatusCode::NOT_FOUND);
+
+        let body = serde_json::to_value(err.to_api_error()).unwrap();
+        assert_eq!(body["error"]["code"], "ACCOUNT_MERGED");
+        assert_eq!(body["error"]["details"]["merged_into"], "GNEW");
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("closed on 2024-03-01 at 10:15 UTC")
+        );
+    }
+
+    #[test]
+    fn test_never_created_account_error() {
+        let err = account_history_error("GNONE", AccountHistory::NeverCreated);
+        assert!(matches!(err, AppError::NotFound(ref msg) if msg.contains("never been created")));
+    }
+
+    #[tokio::test]
+    async fn test_merged_account_explanation_is_not_found() {
+        let horizon = FakeHorizon::new().with_account_history(
+            "GOLD",
+            AccountHistory::Merged {
+                into: "GNEW".to_string(),
+                closed_at: None,
+            },
+        );
+        let query = AccountQuery {
+            privacy: None,
+            operations: None,
+            include_offers: Some(true),
+            include_anomalies: None,
+        };
+
+        let err = get_account_explanation(
+            Path("GOLD".to_string()),
+            ValidatedQuery(query),
+            State(Arc::new(horizon)),
+            Extension(RequestId::new()),
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(
+            matches!(err, AppError::AccountMerged { merged_into, .. } if merged_into == "GNEW")
+        );
+    }
+
+    #[test]
+    fn test_cursor_navigation_values_are_passed_through() {
+        let cursor: Option<String> = Some("157639564177408001".to_string());
+        assert_eq!(cursor.as_deref(), Some("157639564177408001"));
+    }
+
+    fn statement_horizon() -> FakeHorizon {
+        let account = Account {
+            id: "GME".to_string(),
+            account_id: "GME".to_string(),
+            sequence: "1".to_string(),
+            num_signers: 1,
+            signers: Vec::new(),
+            master_key_weight: 1,
+            thresholds: Default::default(),
+            balances: vec![Balance {
+                asset_type: "native".to_string(),
+                balance: "95.0000000".to_string(),
+                ..Default::default()
+            }],
+            flags: AccountFlags {
+                auth_required: false,
+                auth_revocable: false,
+                auth_immutable: false,
+                auth_clawback_enabled: false,
+            },
+            home_domain: None,
+            memo_required: false,
+        };
+        let listed = |hash: &str, created_at: &str| HorizonAccountTransaction {
+            hash: hash.to_string(),
+            successful: true,
+            created_at: created_at.to_string(),
+            source_account: Some("GME".to_string()),
+            operation_count: 1,
+            memo_type: None,
+            memo: None,
+            memo_bytes: None,
+            paging_token: None,
+        };
+        let payment = |hash: &str| HorizonOperation {
+            id: "1".to_string(),
+            transaction_hash: hash.to_string(),
+            operation_type: "payment".to_string(),
+            from: Some("GME".to_string()),
+            to: Some("GTHEM".to_string()),
+            amount: Some("5.0".to_string()),
+            asset_type: Some("native".to_string()),
+            ..Default::default()
+        };
+        FakeHorizon::new()
+            .with_account(account)
+            .with_account_transactions(
+                "GME",
+                vec![
+                    listed("before", "2025-02-28T23:59:59Z"),
+                    listed("first", "2025-03-01T00:00:00Z"),
+                    listed("second", "2025-03-31T23:59:59Z"),
+                    listed("after", "2025-04-01T00:00:00Z"),
+                ],
+            )
+            .with_transaction(
+                HorizonTransaction {
+                    hash: "first".to_string(),
+                    successful: true,
+                    ..Default::default()
+                },
+                vec![payment("first")],
+            )
+    }
+
+    #[tokio::test]
+    async fn test_explanation_flags_anomalies_when_asked() {
+        let mut horizon = statement_horizon();
+        let mut listed = Vec::new();
+        for day in 1..=16u32 {
+            let (hash, to, amount) = if day == 16 {
+                ("big".to_string(), "GNEW", "500.0")
+            } else {
+                (format!("usual{day}"), "GTHEM", "5.0")
+            };
+            listed.push(HorizonAccountTransaction {
+                hash: hash.clone(),
+                successful: true,
+                created_at: format!("2025-03-{day:02}T12:00:00Z"),
+                source_account: Some("GME".to_string()),
+                operation_count: 1,
+                memo_type: None,
+                memo: None,
+                memo_bytes: None,
+                paging_token: None,
+            });
+            horizon = horizon.with_transaction(
+                HorizonTransaction {
+                    hash: hash.clone(),
+                    successful: true,
+                    ..Default::default()
+                },
+                vec![HorizonOperation {
+                    id: "1".to_string(),
+                    transaction_hash: hash,
+                    operation_type: "payment".to_string(),
+                    from: Some("GME".to_string()),
+                    to: Some(to.to_string()),
+                    amount: Some(amount.to_string()),
+                    asset_type: Some("native".to_string()),
+                    ..Default::default()
+                }],
+            );
+        }
+        let horizon = horizon.with_account_transactions("GME", listed);
+        let clock: Arc<dyn Clock> = Arc::new(crate::explain::time::FixedClock(
+            "2025-03-20T00:00:00Z".parse().unwrap(),
+        ));
+
+        let Json(response) = get_account_explanation(
+            Path("GME".to_string()),
+            ValidatedQuery(AccountQuery {
+                privacy: Some(false),
+                operations: Some(0),
+                include_offers: None,
+                include_anomalies: Some(true),
+            }),
+            State(Arc::new(horizon)),
+            Extension(RequestId::new()),
+            Some(Extension(clock)),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let anomalies = response.anomalies.unwrap();
+        let kinds: Vec<_> = anomalies.iter().map(|a| a.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                crate::explain::anomaly::AnomalyKind::LargeAmount,
+                crate::explain::anomaly::AnomalyKind::NewCounterparty
+            ]
+        );
+        assert!(anomalies.iter().all(|a| a.transaction_hash == "big"));
+        assert!(anomalies[0].description.contains("100x larger"));
+    }
+
+    fn statement_query(from: &str, to: &str, format: Option<&str>) -> AccountStatementQuery {
+        AccountStatementQuery {
+            from: from.to_string(),
+            to: to.to_string(),
+            format: format.map(str::to_string),
+            privacy: Some(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_statement_lists_the_range_oldest_first() {
+        let response = get_account_statement(
+            Path("GME".to_string()),
+            Query(statement_query("2025-03-01", "2025-03-31", None)),
+            State(Arc::new(statement_horizon())),
+            Extension(RequestId::new()),
+        )
+        .await
+        .unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let statement: crate::explain::statement::AccountStatement =
+            serde_json::from_slice(&body).unwrap();
+
+        let hashes: Vec<&str> = statement
+            .transactions
+            .iter()
+            .map(|tx| tx.hash.as_str())
+            .collect();
+        assert_eq!(hashes, vec!["first", "second"]);
+        assert_eq!(
+            statement.transactions[0].summary,
+            "GME sent 5 XLM (native) to GTHEM."
+        );
+        // "second"'s operations are unknown to Horizon; it is still listed.
+        assert_eq!(
+            statement.transactions[1].summary,
+            "Successful transaction with 1 operation."
+        );
+        assert_eq!(statement.totals[0].sent, "5.0000000");
+        assert_eq!(statement.balances[0].balance, "95.0000000");
+        assert!(!statement.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_changes_since_cursor_or_timestamp() {
+        let listed = |token: &str, hash: &str, created_at: &str| HorizonAccountTransaction {
+            hash: hash.to_string(),
+            successful: true,
+            created_at: created_at.to_string(),
+            source_account: Some("GME".to_string()),
+            operation_count: 1,
+            memo_type: None,
+            memo: None,
+            memo_bytes: None,
+            paging_token: Some(token.to_string()),
+        };
+        let horizon = Arc::new(statement_horizon().with_account_transactions(
+            "GME",
+            vec![
+                listed("1", "before", "2025-02-28T23:59:59Z"),
+                listed("2", "first", "2025-03-01T00:00:00Z"),
+                listed("3", "second", "2025-03-31T23:59:59Z"),
+                listed("4", "after", "2025-04-01T00:00:00Z"),
+            ],
+        ));
+        let changes = |query: &str| {
+            let query = ValidatedQuery::parse(query);
+            let horizon = Arc::clone(&horizon);
+            async move {
+                get_account_changes(
+                    Path("GME".to_string()),
+                    query?,
+                    State(horizon),
+                    Extension(RequestId::new()),
+                )
+                .await
+            }
+        };
+
+        let Json(from_cursor) = changes("since=1&limit=2").await.unwrap();
+        let hashes: Vec<&str> = from_cursor
+            .transactions
+            .iter()
+            .map(|tx| tx.hash.as_str())
+            .collect();
+        assert_eq!(hashes, vec!["first", "second"]);
+        assert_eq!(from_cursor.cursor.as_deref(), Some("3"));
+        assert!(from_cursor.has_more);
+        assert_eq!(from_cursor.balance_deltas[0].net, "-5.0000000");
+
+        // A timestamp starts at the first transaction at or after it.
+        let Json(from_time) = changes("since=2025-03-01T00:00:00Z&limit=2").await.unwrap();
+        assert_eq!(from_time, from_cursor);
+
+        let Json(from_start) = changes("since=2025-01-01T00:00:00%2B01:00").await.unwrap();
+        assert_eq!(from_start.transactions.len(), 4);
+        assert_eq!(from_start.cursor.as_deref(), Some("4"));
+        assert!(!from_start.has_more);
+
+        // Nothing new keeps the cursor the client sent.
+        let Json(caught_up) = changes("since=4").await.unwrap();
+        assert!(caught_up.transactions.is_empty());
+        assert!(caught_up.balance_deltas.is_empty());
+        assert_eq!(caught_up.cursor.as_deref(), Some("4"));
+
+        for query in ["since=yesterday", "since=now", "limit=5"] {
+            assert!(matches!(
+                changes(query).await,
+                Err(AppError::InvalidQuery(fields)) if fields[0].field == "since"
+            ));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_full_history_streams_ndjson() {
+        let full_history = |query: &str| {
+            let query = ValidatedQuery::parse(query);
+            async move {
+                get_account_full_history(
+                    Path("GME".to_string()),
+                    query?,
+                    State(Arc::new(statement_horizon())),
+                    Extension(RequestId::new()),
+                )
+                .await
+            }
+        };
+
+        let response = full_history("").await.unwrap();
+        assert_eq!(response.headers()[CONTENT_TYPE], NDJSON_CONTENT_TYPE);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let hashes: Vec<String> = String::from_utf8(body.to_vec())
+            .unwrap()
+            .lines()
+            .map(|line| {
+                serde_json::from_str::<serde_json::Value>(line).unwrap()["hash"].to_string()
+            })
+            .collect();
+        assert_eq!(
+            hashes,
+            vec!["\"before\"", "\"first\"", "\"second\"", "\"after\""]
+        );
+
+        assert!(matches!(
+            full_history("order=newest").await,
+            Err(AppError::InvalidQuery(fields)) if fields[0].field == "order"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_statement_as_pdf() {
+        let response = get_account_statement(
+            Path("GME".to_string()),
+            Query(statement_query("2025-03-01", "2025-03-31", Some("pdf"))),
+            State(Arc::new(statement_horizon())),
+            Extension(RequestId::new()),
+        )
+        .await
+        .unwrap();
+        let headers = response.headers().clone();
+        assert_eq!(headers[CONTENT_TYPE], "application/pdf");
+        assert_eq!(
+            headers[CONTENT_DISPOSITION],
+            "attachment; filename=\"statement-GME-2025-03-01-2025-03-31.pdf\""
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert!(body.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn test_statement_query_validation() {
+        let (from, to, pdf) =
+            validate_statement_query(&statement_query("2025-03-01", "2025-03-01", Some("pdf")))
+                .unwrap();
+        assert_eq!(from, to);
+        assert!(pdf);
+
+        for (from, to, format) in [
+            ("2025-03-02", "2025-03-01", None),
+            ("2025-3-1x", "2025-03-01", None),
+            ("2024-01-01", "2025-01-01", None),
+            ("2025-03-01", "2025-03-31", Some("csv")),
+        ] {
+            assert!(
+                matches!(
+                    validate_statement_query(&statement_query(from, to, format)),
+                    Err(AppError::BadRequest(_))
+                ),
+                "{from}..{to} {format:?}"
+            );
+        }
+    }
+
+    fn flags_history_horizon() -> FakeHorizon {
+        let set_options = |id: &str, created_at: &str| HorizonOperation {
+            id: id.to_string(),
+            transaction_hash: format!("tx{id}"),
+            operation_type: "set_options".to_string(),
+            source_account: Some("GME".to_string()),
+            created_at: Some(created_at.to_string()),
+            ..Default::default()
+        };
+        statement_horizon().with_account_operations(
+            "GME",
+            vec![
+                HorizonOperation {
+                    master_key_weight: Some(0),
+                    ..set_options("30", "2024-03-01T09:00:00Z")
+                },
+                HorizonOperation {
+                    operation_type: "payment".to_string(),
+                    ..set_options("20", "2024-02-15T09:00:00Z")
+                },
+                HorizonOperation {
+                    high_threshold: Some(3),
+                    ..set_options("10", "2024-01-10T09:00:00Z")
+                },
+            ],
+        )
+    }
+
+    fn flags_history_query(limit: Option<u32>, cursor: Option<&str>) -> AccountFlagsHistoryQuery {
+        AccountFlagsHistoryQuery {
+            limit,
+            cursor: cursor.map(str::to_string),
+            order: None,
+            privacy: Some(false),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flags_history_lists_changes_newest_first() {
+        let Json(history) = get_account_flags_history(
+            Path("GME".to_string()),
+            ValidatedQuery(flags_history_query(None, None)),
+            State(Arc::new(flags_history_horizon())),
+            Extension(RequestId::new()),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let summaries: Vec<&str> = history.changes.iter().map(|c| c.summary.as_str()).collect();
+        assert_eq!(
+            summaries,
+            vec![
+                "On 2024-03-01 the master key was disabled.",
+                "On 2024-01-10 the high threshold was set to 3."
+            ]
+        );
+        assert_eq!(history.scanned_operations, 3);
+        assert_eq!(history.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_flags_history_continues_from_cursor() {
+        let horizon = Arc::new(flags_history_horizon());
+        let Json(first) = get_account_flags_history(
+            Path("GME".to_string()),
+            ValidatedQuery(flags_history_query(Some(1), None)),
+            State(Arc::clone(&horizon)),
+            Extension(RequestId::new()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.changes.len(), 1);
+        assert_eq!(first.next_cursor.as_deref(), Some("30"));
+
+        let Json(second) = get_account_flags_history(
+            Path("GME".to_string()),
+            ValidatedQuery(flags_history_query(Some(1), first.next_cursor.as_deref())),
+            State(horizon),
+            Extension(RequestId::new()),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(second.changes[0].operation_id, "10");
+    }
+
+    #[tokio::test]
+    async fn test_flags_history_rejects_bad_queries_and_unknown_accounts() {
+        for (query, field) in [
+            ("limit=0", "limit"),
+            ("cursor=abc", "cursor"),
+            ("order=sideways", "order"),
+        ] {
+            assert!(matches!(
+                ValidatedQuery::<AccountFlagsHistoryQuery>::parse(query),
+                Err(AppError::InvalidQuery(fields)) if fields[0].field == field
+            ));
+        }
+
+        let err = get_account_flags_history(
+            Path("GNOBODY".to_string()),
+            ValidatedQuery(flags_history_query(None, None)),
+            State(Arc::new(flags_history_horizon())),
+            Extension(RequestId::new()),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_related_accounts_by_signer_and_sponsor() {
+        use crate::models::account::{AccountLinks, Signer};
+        let signer = |key: &str| Signer {
+            key: key.to_string(),
+            weight: 1,
+            sponsor: None,
+        };
+        let horizon = FakeHorizon::default()
+            .with_account_links(AccountLinks {
+                account_id: "GME".to_string(),
+                signers: vec![signer("GME"), signer("GSHARED")],
+                sponsor: None,
+            })
+            .with_account_links(AccountLinks {
+                account_id: "GTWIN".to_string(),
+                signers: vec![signer("GTWIN"), signer("GSHARED")],
+                sponsor: Some("GME".to_string()),
+            })
+            .with_account_links(AccountLinks {
+                account_id: "GSTRANGER".to_string(),
+                signers: vec![signer("GSTRANGER")],
+                sponsor: None,
+            });
+
+        let Json(explanation) = get_related_accounts(
+            Query(RelatedAccountsQuery {
+                address: "GME".to_string(),
+                privacy: Some(false),
+            }),
+            State(Arc::new(horizon)),
+            Extension(RequestId::new()),
+        )
+        .await
+        .unwrap();
+
+        let addresses: Vec<&str> = explanation
+            .related
+            .iter()
+            .map(|r| r.address.as_str())
+            .collect();
+        assert_eq!(addresses, vec!["GTWIN", "GSHARED"]);
+        assert_eq!(explanation.related[0].connections.len(), 2);
+        assert!(!explanation.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_related_accounts_requires_a_known_address() {
+        let query = |address: &str| {
+            Query(RelatedAccountsQuery {
+                address: address.to_string(),
+                privacy: None,
+            })
+        };
+        let err = get_related_accounts(
+            query(" "),
+            State(Arc::new(FakeHorizon::default())),
+            Extension(RequestId::new()),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(_)));
+
+        let err = get_related_accounts(
+            query("GNOBODY"),
+            State(Arc::new(FakeHorizon::default())),
+            Extension(RequestId::new()),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_batch_isolates_failing_addresses() {
+        use crate::errors::ErrorCode;
+        let funded = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        let unfunded = "GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7";
+        let horizon = FakeHorizon::new().with_account(Account {
+            id: funded.to_string(),
+            account_id: funded.to_string(),
+            sequence: "1".to_string(),
+            num_signers: 1,
+            signers: Vec::new(),
+            master_key_weight: 1,
+            thresholds: Default::default(),
+            balances: vec![Balance {
+                asset_type: "native".to_string(),
+                balance: "95.0000000".to_string(),
+                ..Default::default()
+            }],
+            flags: AccountFlags {
+                auth_required: false,
+                auth_revocable: false,
+                auth_immutable: false,
+                auth_clawback_enabled: false,
+            },
+            home_domain: None,
+            memo_required: false,
+        });
+        let call = |addresses: &[&str]| {
+            post_accounts_batch(
+                State(Arc::new(horizon.clone())),
+                Extension(RequestId::new()),
+                Json(AccountBatchRequest {
+                    addresses: addresses.iter().map(|a| a.to_string()).collect(),
+                    privacy: None,
+                }),
+            )
+        };
+
+        let Json(response) = call(&[funded, unfunded, "not-an-address"]).await.unwrap();
+        let accounts = &response.accounts;
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts[0].address, funded);
+        assert_eq!(
+            accounts[0].account.as_ref().unwrap().xlm_balance,
+            "95.0000000"
+        );
+        assert!(accounts[0].error.is_none());
+        assert_eq!(
+            accounts[1].error.as_ref().unwrap().code,
+            ErrorCode::NotFound
+        );
+        assert_eq!(
+            accounts[2].error.as_ref().unwrap().code,
+            ErrorCode::BadRequest
+        );
+
+        for bad in [&[][..], &[funded, funded][..], &[funded; 26][..]] {
+            assert!(matches!(call(bad).await, Err(AppError::BadRequest(_))));
+        }
+    }
+
+    fn graph_horizon() -> FakeHorizon {
+        let payment = |id: &str, from: &str, to: &str, amount: &str| HorizonOperation {
+            id: id.to_string(),
+            transaction_hash: format!("tx{id}"),
+            operation_type: "payment".to_string(),
+            from: Some(from.to_string()),
+            to: Some(to.to_string()),
+            amount: Some(amount.to_string()),
+            asset_type: Some("native".to_string()),
+            ..Default::default()
+        };
+        let account = |id: &str| Account {
+            id: id.to_string(),
+            account_id: id.to_string(),
+            sequence: "1".to_string(),
+            num_signers: 1,
+            signers: Vec::new(),
+            master_key_weight: 1,
+            thresholds: Default::default(),
+            balances: Vec::new(),
+            flags: AccountFlags {
+                auth_required: false,
+                auth_revocable: false,
+                auth_immutable: false,
+                auth_clawback_enabled: false,
+            },
+            home_domain: None,
+            memo_required: false,
+        };
+        statement_horizon()
+            .with_account(account("GYOU"))
+            .with_account(account("GTHEM"))
+            .with_account_operations(
+                "GME",
+                vec![
+                    payment("2", "GME", "GYOU", "20.0"),
+                    payment("1", "GME", "GYOU", "10.0"),
+                ],
+            )
+            .with_account_operations(
+                "GYOU",
+                vec![
+                    payment("3", "GYOU", "GTHEM", "5.0"),
+                    payment("2", "GME", "GYOU", "20.0"),
+                    payment("1", "GME", "GYOU", "10.0"),
+                ],
+            )
+            .with_account_operations("GTHEM", vec![payment("4", "GTHEM", "GFAR", "1.0")])
+    }
+
+    async fn graph_response(depth: Option<u32>, format: Option<GraphFormat>) -> Response {
+        get_account_graph(
+            Path("GME".to_string()),
+            ValidatedQuery(AccountGraphQuery {
+                depth,
+                format,
+                privacy: Some(false),
+            }),
+            State(Arc::new(graph_horizon())),
+            Extension(RequestId::new()),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_graph_follows_payments_to_the_requested_depth() {
+        let body = axum::body::to_bytes(graph_response(None, None).await.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let graph: crate::explain::payment_graph::PaymentGraph =
+            serde_json::from_slice(&body).unwrap();
+
+        let nodes: Vec<(&str, u32)> = graph
+            .nodes
+            .iter()
+            .map(|n| (n.id.as_str(), n.depth))
+            .collect();
+        assert_eq!(nodes, vec![("GME", 0), ("GYOU", 1), ("GTHEM", 2)]);
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(
+            graph.edges[0].summary,
+            "GME sent GYOU 30 XLM in 2 payments."
+        );
+        assert!(!graph.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_graph_as_dot() {
+        let response = graph_response(Some(1), Some(GraphFormat::Dot)).await;
+        assert_eq!(
+            response.headers()[CONTENT_TYPE],
+            "text/vnd.graphviz; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let dot = String::from_utf8(body.to_vec()).unwrap();
+        assert!(dot.contains("\"GME\" -> \"GYOU\" [label=\"30 XLM (2)\"];"));
+        assert!(!dot.contains("GTHEM"));
+    }
+
+    #[test]
+    fn test_graph_query_validation() {
+        let rejected = |query: &str| match ValidatedQuery::<AccountGraphQuery>::parse(query) {
+            Err(AppError::InvalidQuery(fields)) => fields[0].field.clone(),
+            other => panic!("expected InvalidQuery for {query:?}, got {other:?}"),
+        };
+        let ValidatedQuery(query) = ValidatedQuery::<AccountGraphQuery>::parse("").unwrap();
+        assert_eq!((query.depth, query.format), (None, None));
+        assert_eq!(rejected("depth=0"), "depth");
+        assert_eq!(rejected("depth=4"), "depth");
+        assert_eq!(rejected("format=svg"), "format");
+    }
+
+    #[test]
+    fn test_account_query_validation() {
+        assert!(ValidatedQuery::<AccountQuery>::parse("operations=20").is_ok());
+        match ValidatedQuery::<AccountQuery>::parse("operations=21") {
+            Err(AppError::InvalidQuery(fields)) => {
+                assert_eq!(fields[0].field, "operations");
+                assert_eq!(fields[0].message, "operations must be between 0 and 20");
+            }
+            other => panic!("expected InvalidQuery, got {other:?}"),
+        }
     }
 }
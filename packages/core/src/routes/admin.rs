@@ -0,0 +1,886 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+    http::{HeaderMap, header},
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    errors::AppError,
+    middleware::request_id::RequestId,
+    routes::query::{FieldErrors, Timestamp, ValidateQuery, ValidatedQuery},
+    services::audit::{AuditFilter, AuditRecord, AuditStore, audit_timestamp},
+    services::blocklist::{BlocklistLoader, BlocklistStatus},
+    services::consistency::{ConsistencySnapshot, ConsistencyStatus},
+    services::contract_labels::{ContractLabel, ContractLabels, MAX_LABEL_LENGTH, label_target},
+    services::notice::{NoticeBoard, NoticeSeverity, ServiceNotice},
+    services::slo::{SloReport, SloTracker},
+};
+
+const DEFAULT_LIMIT: u32 = 50;
+const MAX_LIMIT: u32 = 500;
+
+/// Longest service notice accepted, in characters.
+const MAX_NOTICE_LENGTH: usize = 500;
+
+/// Bearer token that grants access to the `/admin` endpoints.
+#[derive(Clone)]
+pub struct AdminToken(pub String);
+
+impl AdminToken {
+    /// Read `ADMIN_TOKEN`, or `None` when it is unset or blank, which leaves
+    /// every admin endpoint disabled.
+    pub fn from_env() -> Option<Self> {
+        env::var("ADMIN_TOKEN")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .map(Self)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AuditQuery {
+    /// Transaction hash or account address.
+    pub subject: Option<String>,
+    pub requester: Option<String>,
+    pub status: Option<u16>,
+    /// RFC 3339 timestamp; records at or after it.
+    pub since: Option<Timestamp>,
+    /// RFC 3339 timestamp; records before it.
+    pub until: Option<Timestamp>,
+    pub limit: Option<u32>,
+    /// Opaque cursor from a previous page's `next_cursor`.
+    pub cursor: Option<u32>,
+}
+
+impl ValidateQuery for AuditQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.limit(self.limit, MAX_LIMIT);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuditResponse {
+    /// Total number of matching records across all pages.
+    pub total: u64,
+    /// Newest first.
+    pub items: Vec<AuditRecord>,
+    pub next_cursor: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/audit",
+    params(
+        ("subject" = Option<String>, Query, description = "Transaction hash or account address"),
+        ("requester" = Option<String>, Query, description = "Requester, e.g. ip:203.0.113.9 or key:<fingerprint>"),
+        ("status" = Option<u16>, Query, description = "HTTP status of the audited response"),
+        ("since" = Option<String>, Query, description = "RFC 3339 timestamp; records at or after it"),
+        ("until" = Option<String>, Query, description = "RFC 3339 timestamp; records before it"),
+        ("limit" = Option<u32>, Query, description = "Records per page (1-500, default 50)"),
+        ("cursor" = Option<String>, Query, description = "Cursor from a previous page")
+    ),
+    responses(
+        (status = 200, description = "Audited explanation requests, newest first", body = AuditResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "The audit log is not enabled")
+    )
+)]
+pub async fn get_audit_log(
+    ValidatedQuery(params): ValidatedQuery<AuditQuery>,
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    store: Option<Extension<Arc<AuditStore>>>,
+    admin_token: Option<Extension<AdminToken>>,
+) -> Result<Json<AuditResponse>, AppError> {
+    let span = info_span!("audit_request", request_id = %request_id);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let (Some(Extension(store)), Some(Extension(admin_token))) = (store, admin_token) else {
+        return Err(AppError::NotFound(
+            "The audit log is not enabled; set AUDIT_ENABLED and ADMIN_TOKEN.".to_string(),
+        ));
+    };
+
+    if !authorized(&headers, &admin_token) {
+        warn!(request_id = %request_id, "audit_access_denied");
+        return Err(AppError::Unauthorized(
+            "A valid admin token is required.".to_string(),
+        ));
+    }
+
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT);
+    let offset = params.cursor.unwrap_or(0);
+
+    let filter = AuditFilter {
+        subject: params.subject,
+        requester: params.requester,
+        status: params.status,
+        since: params.since.map(|Timestamp(at)| audit_timestamp(at)),
+        until: params.until.map(|Timestamp(at)| audit_timestamp(at)),
+    };
+
+    let (items, total) = store.query(&filter, limit, offset).map_err(|err| {
+        error!(request_id = %request_id, error = %err, "audit_query_failed");
+        AppError::Internal("The audit log is temporarily unavailable.".to_string())
+    })?;
+
+    let next_offset = offset as u64 + items.len() as u64;
+    info!(
+        request_id = %request_id,
+        total,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+    Ok(Json(AuditResponse {
+        total,
+        next_cursor: (next_offset < total).then(|| next_offset.to_string()),
+        items,
+    }))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/consistency",
+    responses(
+        (status = 200, description = "Results of re-deriving indexed explanations", body = ConsistencySnapshot),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "The consistency checker is not enabled")
+    )
+)]
+pub async fn get_consistency(
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    status: Option<Extension<Arc<ConsistencyStatus>>>,
+    admin_token: Option<Extension<AdminToken>>,
+) -> Result<Json<ConsistencySnapshot>, AppError> {
+    let span = info_span!("consistency_request", request_id = %request_id);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let (Some(Extension(status)), Some(Extension(admin_token))) = (status, admin_token) else {
+        return Err(AppError::NotFound(
+            "The consistency checker is not enabled; set CONSISTENCY_CHECK_ENABLED and ADMIN_TOKEN."
+                .to_string(),
+        ));
+    };
+
+    if !authorized(&headers, &admin_token) {
+        warn!(request_id = %request_id, "consistency_access_denied");
+        return Err(AppError::Unauthorized(
+            "A valid admin token is required.".to_string(),
+        ));
+    }
+
+    let snapshot = status.snapshot();
+    info!(
+        request_id = %request_id,
+        mismatched = snapshot.mismatched,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+    Ok(Json(snapshot))
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/slo",
+    responses(
+        (status = 200, description = "Availability and latency objectives per route, with error budgets and burn rates", body = SloReport),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "SLO tracking is not enabled")
+    )
+)]
+pub async fn get_slo(
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    tracker: Option<Extension<Arc<SloTracker>>>,
+    admin_token: Option<Extension<AdminToken>>,
+) -> Result<Json<SloReport>, AppError> {
+    let span = info_span!("slo_request", request_id = %request_id);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let (Some(Extension(tracker)), Some(Extension(admin_token))) = (tracker, admin_token) else {
+        return Err(AppError::NotFound(
+            "SLO tracking is not enabled; set SLO_ENABLED and ADMIN_TOKEN.".to_string(),
+        ));
+    };
+
+    if !authorized(&headers, &admin_token) {
+        warn!(request_id = %request_id, "slo_access_denied");
+        return Err(AppError::Unauthorized(
+            "A valid admin token is required.".to_string(),
+        ));
+    }
+
+    let report = tracker.report();
+    info!(
+        request_id = %request_id,
+        routes = report.routes.len(),
+        alerting = report.routes.iter().filter(|route| route.alert.is_some()).count(),
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+    Ok(Json(report))
+}
+
+#[utoipa::path(
+    post,
+    path = "/admin/blocklist/reload",
+    responses(
+        (status = 200, description = "The spam blocklist now in use", body = BlocklistStatus),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "No spam blocklist is configured"),
+        (status = 502, description = "The list could not be read; the previous version stays in use")
+    )
+)]
+pub async fn post_blocklist_reload(
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    loader: Option<Extension<BlocklistLoader>>,
+    admin_token: Option<Extension<AdminToken>>,
+) -> Result<Json<BlocklistStatus>, AppError> {
+    let span = info_span!("blocklist_reload_request", request_id = %request_id);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let (Some(Extension(loader)), Some(Extension(admin_token))) = (loader, admin_token) else {
+        return Err(AppError::NotFound(
+            "No spam blocklist is configured; set SPAM_BLOCKLIST and ADMIN_TOKEN.".to_string(),
+        ));
+    };
+
+    if !authorized(&headers, &admin_token) {
+        warn!(request_id = %request_id, "blocklist_reload_denied");
+        return Err(AppError::Unauthorized(
+            "A valid admin token is required.".to_string(),
+        ));
+    }
+
+    let status = loader.reload().await.map_err(|err| {
+        AppError::UpstreamFailure(format!(
+            "The blocklist could not be reloaded, so the previous version stays in use: {err}"
+        ))
+    })?;
+    info!(
+        request_id = %request_id,
+        version = %status.version,
+        entries = status.entries,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+    Ok(Json(status))
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct NoticeRequest {
+    pub message: String,
+    /// Defaults to `info`.
+    pub severity: Option<NoticeSeverity>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct NoticeResponse {
+    /// The notice shown with every response; null when none is set.
+    pub notice: Option<ServiceNotice>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/notice",
+    responses(
+        (status = 200, description = "The service notice currently shown", body = NoticeResponse),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "Admin endpoints are not enabled")
+    )
+)]
+pub async fn get_notice(
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    notices: Option<Extension<Arc<NoticeBoard>>>,
+    admin_token: Option<Extension<AdminToken>>,
+) -> Result<Json<NoticeResponse>, AppError> {
+    let span = info_span!("notice_request", request_id = %request_id);
+    let _span_guard = span.enter();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let notices = notice_board(&headers, &request_id, notices, admin_token)?;
+    let notice = notices.get();
+    info!(request_id = %request_id, status = 200u16, "request_completed");
+    Ok(Json(NoticeResponse { notice }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/notice",
+    request_body = NoticeRequest,
+    responses(
+        (status = 200, description = "The notice now shown with every response", body = NoticeResponse),
+        (status = 400, description = "Empty or overlong message"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "Admin endpoints are not enabled")
+    )
+)]
+pub async fn put_notice(
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    notices: Option<Extension<Arc<NoticeBoard>>>,
+    admin_token: Option<Extension<AdminToken>>,
+    Json(request): Json<NoticeRequest>,
+) -> Result<Json<NoticeResponse>, AppError> {
+    let span = info_span!("notice_request", request_id = %request_id);
+    let _span_guard = span.enter();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let notices = notice_board(&headers, &request_id, notices, admin_token)?;
+    let message = request.message.trim();
+    if message.is_empty() || message.chars().count() > MAX_NOTICE_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "message must be between 1 and {MAX_NOTICE_LENGTH} characters"
+        )));
+    }
+    let notice = ServiceNotice {
+        message: message.to_string(),
+        severity: request.severity.unwrap_or_default(),
+        set_at: chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+    };
+    notices.set(notice.clone()).map_err(|err| {
+        error!(request_id = %request_id, error = %err, "notice_not_saved");
+        AppError::Internal("The notice could not be saved.".to_string())
+    })?;
+    info!(
+        request_id = %request_id,
+        severity = ?notice.severity,
+        status = 200u16,
+        "notice_set"
+    );
+    Ok(Json(NoticeResponse {
+        notice: Some(notice),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/notice",
+    responses(
+        (status = 200, description = "The notice was cleared", body = NoticeResponse),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "Admin endpoints are not enabled")
+    )
+)]
+pub async fn delete_notice(
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    notices: Option<Extension<Arc<NoticeBoard>>>,
+    admin_token: Option<Extension<AdminToken>>,
+) -> Result<Json<NoticeResponse>, AppError> {
+    let span = info_span!("notice_request", request_id = %request_id);
+    let _span_guard = span.enter();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let notices = notice_board(&headers, &request_id, notices, admin_token)?;
+    let cleared = notices.clear().map_err(|err| {
+        error!(request_id = %request_id, error = %err, "notice_not_cleared");
+        AppError::Internal("The notice could not be cleared.".to_string())
+    })?;
+    info!(request_id = %request_id, cleared, status = 200u16, "notice_cleared");
+    Ok(Json(NoticeResponse { notice: None }))
+}
+
+/// The notice board, once the request has shown the admin token.
+fn notice_board(
+    headers: &HeaderMap,
+    request_id: &RequestId,
+    notices: Option<Extension<Arc<NoticeBoard>>>,
+    admin_token: Option<Extension<AdminToken>>,
+) -> Result<Arc<NoticeBoard>, AppError> {
+    let (Some(Extension(notices)), Some(Extension(admin_token))) = (notices, admin_token) else {
+        return Err(AppError::NotFound(
+            "Service notices are not enabled; set ADMIN_TOKEN.".to_string(),
+        ));
+    };
+    if !authorized(headers, &admin_token) {
+        warn!(request_id = %request_id, "notice_access_denied");
+        return Err(AppError::Unauthorized(
+            "A valid admin token is required.".to_string(),
+        ));
+    }
+    Ok(notices)
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ContractLabelRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ContractLabelsResponse {
+    /// Contracts first, then WASM hashes.
+    pub labels: Vec<ContractLabel>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/admin/labels/contracts",
+    responses(
+        (status = 200, description = "Every contract and WASM label in effect", body = ContractLabelsResponse),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "Admin endpoints are not enabled")
+    )
+)]
+pub async fn get_contract_labels(
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    labels: Option<Extension<Arc<ContractLabels>>>,
+    admin_token: Option<Extension<AdminToken>>,
+) -> Result<Json<ContractLabelsResponse>, AppError> {
+    let span = info_span!("contract_labels_request", request_id = %request_id);
+    let _span_guard = span.enter();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let labels = contract_labels(&headers, &request_id, labels, admin_token)?;
+    let labels = labels.list();
+    info!(
+        request_id = %request_id,
+        labels = labels.len(),
+        status = 200u16,
+        "request_completed"
+    );
+    Ok(Json(ContractLabelsResponse { labels }))
+}
+
+#[utoipa::path(
+    put,
+    path = "/admin/labels/contracts/{id}",
+    params(("id" = String, Path, description = "Contract address (C...) or hex WASM hash")),
+    request_body = ContractLabelRequest,
+    responses(
+        (status = 200, description = "Every label in effect, including the new one", body = ContractLabelsResponse),
+        (status = 400, description = "Invalid id, or empty or overlong name"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "Admin endpoints are not enabled")
+    )
+)]
+pub async fn put_contract_label(
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    labels: Option<Extension<Arc<ContractLabels>>>,
+    admin_token: Option<Extension<AdminToken>>,
+    Json(request): Json<ContractLabelRequest>,
+) -> Result<Json<ContractLabelsResponse>, AppError> {
+    let span = info_span!("contract_labels_request", request_id = %request_id);
+    let _span_guard = span.enter();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let labels = contract_labels(&headers, &request_id, labels, admin_token)?;
+    let (id, kind) = contract_label_target(&id)?;
+    let name = request.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_LABEL_LENGTH {
+        return Err(AppError::BadRequest(format!(
+            "name must be between 1 and {MAX_LABEL_LENGTH} characters"
+        )));
+    }
+    labels.set(&id, name).map_err(|err| {
+        error!(request_id = %request_id, error = %err, "contract_label_not_saved");
+        AppError::Internal("The label could not be saved.".to_string())
+    })?;
+    info!(
+        request_id = %request_id,
+        id = %id,
+        kind = ?kind,
+        name = %name,
+        status = 200u16,
+        "contract_label_set"
+    );
+    Ok(Json(ContractLabelsResponse {
+        labels: labels.list(),
+    }))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/admin/labels/contracts/{id}",
+    params(("id" = String, Path, description = "Contract address (C...) or hex WASM hash")),
+    responses(
+        (status = 200, description = "Every label still in effect", body = ContractLabelsResponse),
+        (status = 400, description = "Invalid id"),
+        (status = 401, description = "Missing or wrong admin token"),
+        (status = 404, description = "Admin endpoints are not enabled, or the id has no operator label")
+    )
+)]
+pub async fn delete_contract_label(
+    Path(id): Path<String>,
+    headers: HeaderMap,
+    Extension(request_id): Extension<RequestId>,
+    labels: Option<Extension<Arc<ContractLabels>>>,
+    admin_token: Option<Extension<AdminToken>>,
+) -> Result<Json<ContractLabelsResponse>, AppError> {
+    let span = info_span!("contract_labels_request", request_id = %request_id);
+    let _span_guard = span.enter();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let labels = contract_labels(&headers, &request_id, labels, admin_token)?;
+    let (id, _) = contract_label_target(&id)?;
+    let removed = labels.remove(&id).map_err(|err| {
+        error!(request_id = %request_id, error = %err, "contract_label_not_removed");
+        AppError::Internal("The label could not be removed.".to_string())
+    })?;
+    if !removed {
+        return Err(AppError::NotFound(format!(
+            "{id} has no operator label; built-in labels can only be renamed."
+        )));
+    }
+    info!(request_id = %request_id, id = %id, status = 200u16, "contract_label_removed");
+    Ok(Json(ContractLabelsResponse {
+        labels: labels.list(),
+    }))
+}
+
+/// The contract label registry, once the request has shown the admin token.
+fn contract_labels(
+    headers: &HeaderMap,
+    request_id: &RequestId,
+    labels: Option<Extension<Arc<ContractLabels>>>,
+    admin_token: Option<Extension<AdminToken>>,
+) -> Result<Arc<ContractLabels>, AppError> {
+    let (Some(Extension(labels)), Some(Extension(admin_token))) = (labels, admin_token) else {
+        return Err(AppError::NotFound(
+            "Contract labels cannot be edited; set ADMIN_TOKEN.".to_string(),
+        ));
+    };
+    if !authorized(headers, &admin_token) {
+        warn!(request_id = %request_id, "contract_labels_access_denied");
+        return Err(AppError::Unauthorized(
+            "A valid admin token is required.".to_string(),
+        ));
+    }
+    Ok(labels)
+}
+
+fn contract_label_target(
+    id: &str,
+) -> Result<(String, crate::services::contract_labels::ContractLabelKind), AppError> {
+    label_target(id).ok_or_else(|| {
+        AppError::BadRequest(
+            "id must be a contract address (C...) or a 64-character hex WASM hash".to_string(),
+        )
+    })
+}
+
+/// Whether the request carries `Authorization: Bearer <admin token>`.
+/// Digests are compared so the check takes the same time however much of
+/// the token matches.
+fn authorized(headers: &HeaderMap, admin_token: &AdminToken) -> bool {
+    let Some(presented) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+    else {
+        return false;
+    };
+    Sha256::digest(presented.trim().as_bytes()) == Sha256::digest(admin_token.0.as_bytes())
+}
+
+/// Parse an RFC 3339 query parameter into the form records are stored in.
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::audit::AuditSubject;
+    use axum::http::HeaderValue;
+
+    fn store() -> Arc<AuditStore> {
+        let store = AuditStore::open_in_memory().unwrap();
+        for (i, subject) in ["tx1", "tx2", "tx1"].iter().enumerate() {
+            store
+                .record(&AuditRecord {
+                    subject_type: AuditSubject::Transaction,
+                    subject: subject.to_string(),
+                    network: "testnet".to_string(),
+                    requester: "ip:10.0.0.1".to_string(),
+                    requested_at: format!("2026-01-0{}T00:00:00.000Z", i + 1),
+                    status: 200,
+                })
+                .unwrap();
+        }
+        Arc::new(store)
+    }
+
+    fn bearer(token: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_str(&format!("Bearer {token}")).unwrap(),
+        );
+        headers
+    }
+
+    async fn call(query: &str, headers: HeaderMap) -> Result<AuditResponse, AppError> {
+        get_audit_log(
+            ValidatedQuery::parse(query)?,
+            headers,
+            Extension(RequestId::new()),
+            Some(Extension(store())),
+            Some(Extension(AdminToken("s3cret".to_string()))),
+        )
+        .await
+        .map(|Json(response)| response)
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_filters_and_pages() {
+        let response = call("subject=tx1&limit=1", bearer("s3cret")).await.unwrap();
+        assert_eq!(response.total, 2);
+        assert_eq!(response.items[0].requested_at, "2026-01-03T00:00:00.000Z");
+        assert_eq!(response.next_cursor.as_deref(), Some("1"));
+
+        let response = call("since=2026-01-02T01:00:00%2B01:00", bearer("s3cret"))
+            .await
+            .unwrap();
+        assert_eq!(response.total, 2);
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_requires_token() {
+        for headers in [HeaderMap::new(), bearer("wrong")] {
+            let err = call("", headers).await.unwrap_err();
+            assert!(matches!(err, AppError::Unauthorized(_)));
+        }
+
+        let err = call("since=yesterday&cursor=x", bearer("s3cret"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::InvalidQuery(fields) if fields[0].field == "since"));
+    }
+
+    #[tokio::test]
+    async fn test_audit_log_disabled_without_token() {
+        let err = get_audit_log(
+            ValidatedQuery(AuditQuery::default()),
+            bearer("anything"),
+            Extension(RequestId::new()),
+            Some(Extension(store())),
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_consistency_requires_token() {
+        let status = Arc::new(ConsistencyStatus::default());
+        let call = |headers, status| {
+            get_consistency(
+                headers,
+                Extension(RequestId::new()),
+                status,
+                Some(Extension(AdminToken("s3cret".to_string()))),
+            )
+        };
+
+        let Json(snapshot) = call(bearer("s3cret"), Some(Extension(Arc::clone(&status))))
+            .await
+            .unwrap();
+        assert_eq!(snapshot, ConsistencySnapshot::default());
+
+        let err = call(bearer("wrong"), Some(Extension(Arc::clone(&status))))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Unauthorized(_)));
+
+        let err = call(bearer("s3cret"), None).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_slo_report_requires_token() {
+        use crate::services::slo::SloConfig;
+        use std::time::Duration;
+
+        let tracker = Arc::new(SloTracker::new(SloConfig::default()));
+        tracker.record("/tx/:hash", 200, Duration::from_millis(20));
+        let call = |headers, tracker| {
+            get_slo(
+                headers,
+                Extension(RequestId::new()),
+                tracker,
+                Some(Extension(AdminToken("s3cret".to_string()))),
+            )
+        };
+
+        let Json(report) = call(bearer("s3cret"), Some(Extension(Arc::clone(&tracker))))
+            .await
+            .unwrap();
+        assert_eq!(report.routes[0].route, "/tx/:hash");
+        assert_eq!(report.routes[0].availability, 1.0);
+
+        let err = call(bearer("wrong"), Some(Extension(Arc::clone(&tracker))))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Unauthorized(_)));
+
+        let err = call(bearer("s3cret"), None).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_notice_is_set_read_and_cleared() {
+        let path = env::temp_dir().join(format!("notice-{}.json", uuid::Uuid::new_v4()));
+        let notices = Arc::new(NoticeBoard::open(&path).unwrap());
+        let board = || Some(Extension(Arc::clone(&notices)));
+        let token = || Some(Extension(AdminToken("s3cret".to_string())));
+        let request = |message: &str| {
+            Json(NoticeRequest {
+                message: message.to_string(),
+                severity: Some(NoticeSeverity::Warning),
+            })
+        };
+
+        let denied = put_notice(
+            bearer("wrong"),
+            Extension(RequestId::new()),
+            board(),
+            token(),
+            request("Horizon mainnet is degraded"),
+        )
+        .await;
+        assert!(matches!(denied, Err(AppError::Unauthorized(_))));
+        let empty = put_notice(
+            bearer("s3cret"),
+            Extension(RequestId::new()),
+            board(),
+            token(),
+            request("  "),
+        )
+        .await;
+        assert!(matches!(empty, Err(AppError::BadRequest(_))));
+
+        let Json(set) = put_notice(
+            bearer("s3cret"),
+            Extension(RequestId::new()),
+            board(),
+            token(),
+            request(" Horizon mainnet is degraded; data may lag "),
+        )
+        .await
+        .unwrap();
+        let Json(current) = get_notice(
+            bearer("s3cret"),
+            Extension(RequestId::new()),
+            board(),
+            token(),
+        )
+        .await
+        .unwrap();
+        let notice = current.notice.unwrap();
+        assert_eq!(notice.message, "Horizon mainnet is degraded; data may lag");
+        assert_eq!(notice.severity, NoticeSeverity::Warning);
+        assert_eq!(set.notice, Some(notice));
+
+        let Json(cleared) = delete_notice(
+            bearer("s3cret"),
+            Extension(RequestId::new()),
+            board(),
+            token(),
+        )
+        .await
+        .unwrap();
+        assert!(cleared.notice.is_none());
+        assert_eq!(notices.get(), None);
+    }
+
+    #[tokio::test]
+    async fn test_contract_labels_are_set_and_removed() {
+        let path = env::temp_dir().join(format!("contract-labels-{}.json", uuid::Uuid::new_v4()));
+        let labels = Arc::new(
+            ContractLabels::open(crate::services::assets::PUBLIC_NETWORK_PASSPHRASE, &path)
+                .unwrap(),
+        );
+        let registry = || Some(Extension(Arc::clone(&labels)));
+        let token = || Some(Extension(AdminToken("s3cret".to_string())));
+        let wasm = "AB".repeat(32);
+        let put = |id: &str, name: &str, headers| {
+            put_contract_label(
+                Path(id.to_string()),
+                headers,
+                Extension(RequestId::new()),
+                registry(),
+                token(),
+                Json(ContractLabelRequest {
+                    name: name.to_string(),
+                }),
+            )
+        };
+
+        let denied = put(&wasm, "Soroswap Pair", bearer("wrong")).await;
+        assert!(matches!(denied, Err(AppError::Unauthorized(_))));
+        let bad_id = put("GABC", "Soroswap Pair", bearer("s3cret")).await;
+        assert!(matches!(bad_id, Err(AppError::BadRequest(_))));
+        let bad_name = put(&wasm, " ", bearer("s3cret")).await;
+        assert!(matches!(bad_name, Err(AppError::BadRequest(_))));
+
+        let Json(set) = put(&wasm, " Soroswap Pair ", bearer("s3cret"))
+            .await
+            .unwrap();
+        let pair = set.labels.iter().find(|l| !l.builtin).unwrap();
+        assert_eq!(pair.id, "ab".repeat(32));
+        assert_eq!(pair.name, "Soroswap Pair");
+        assert_eq!(labels.get(&wasm).as_deref(), Some("Soroswap Pair"));
+
+        let Json(listed) = get_contract_labels(
+            bearer("s3cret"),
+            Extension(RequestId::new()),
+            registry(),
+            token(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(listed.labels, set.labels);
+
+        let delete = |id: &str| {
+            delete_contract_label(
+                Path(id.to_string()),
+                bearer("s3cret"),
+                Extension(RequestId::new()),
+                registry(),
+                token(),
+            )
+        };
+        let Json(removed) = delete(&wasm).await.unwrap();
+        assert!(removed.labels.iter().all(|l| l.builtin));
+        assert!(matches!(delete(&wasm).await, Err(AppError::NotFound(_))));
+        std::fs::remove_file(path).unwrap();
+    }
+}
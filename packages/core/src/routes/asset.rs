@@ -0,0 +1,167 @@
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span, warn};
+
+use crate::{
+    errors::AppError,
+    explain::asset_audit::{AssetAuditExplanation, HomeDomainCheck, audit_asset},
+    middleware::request_id::RequestId,
+    models::strkey::{self, StrKeyKind},
+    services::{
+        horizon_api::HorizonApi,
+        issuer_labels::domain_url,
+        policy::{Parties, policy},
+        privacy::{PrivacyQuery, apply_privacy, privacy_enabled},
+    },
+};
+
+/// Issuer operations scanned for recent clawbacks.
+const CLAWBACK_SCAN_LIMIT: u32 = 200;
+
+#[utoipa::path(
+    get,
+    path = "/asset/{code}/{issuer}/audit",
+    params(
+        ("code" = String, Path, description = "Asset code, 1-12 alphanumeric characters"),
+        ("issuer" = String, Path, description = "Issuer account address (G...)"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses")
+    ),
+    responses(
+        (status = 200, description = "Issuer configuration audit", body = AssetAuditExplanation),
+        (status = 400, description = "Invalid asset code or issuer"),
+        (status = 404, description = "Issuer account not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_asset_audit<H: HorizonApi>(
+    Path((code, issuer)): Path<(String, String)>,
+    Query(options): Query<PrivacyQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Json<AssetAuditExplanation>, AppError> {
+    let span = info_span!(
+        "asset_audit_request",
+        request_id = %request_id,
+        asset_code = %code,
+        issuer = %issuer
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, asset_code = %code, issuer = %issuer, "incoming_request");
+
+    if let Err(app_error) = validate_asset(&code, &issuer) {
+        info!(
+            request_id = %request_id,
+            asset_code = %code,
+            status = app_error.status_code().as_u16(),
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            error = ?app_error,
+            "request_completed"
+        );
+        return Err(app_error);
+    }
+    policy().check(&Parties::asset(&code, &issuer), "this asset")?;
+
+    let horizon_started_at = Instant::now();
+    let account = match horizon_client.fetch_account(&issuer).await {
+        Ok(account) => account,
+        Err(err) => {
+            let app_error: AppError = err.into();
+            error!(
+                request_id = %request_id,
+                issuer = %issuer,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_account_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+
+    let toml_check = async {
+        let domain = account.home_domain.as_deref()?;
+        let url = domain_url(domain);
+        let (status, identity) = tokio::join!(
+            horizon_client.check_stellar_toml(&url, &code, &issuer),
+            horizon_client.fetch_stellar_toml_identity(&url),
+        );
+        Some(HomeDomainCheck {
+            domain: domain.to_string(),
+            status,
+            identity,
+        })
+    };
+    let (home_domain, clawbacks_res) = tokio::join!(
+        toml_check,
+        horizon_client.fetch_issuer_clawbacks(&issuer, &code, CLAWBACK_SCAN_LIMIT),
+    );
+    let horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64;
+
+    let clawbacks = clawbacks_res.unwrap_or_else(|err| {
+        warn!(
+            request_id = %request_id,
+            issuer = %issuer,
+            error = ?err,
+            "issuer_clawbacks_fetch_failed"
+        );
+        vec![]
+    });
+
+    let audit = audit_asset(&code, &account, home_domain.as_ref(), &clawbacks);
+
+    info!(
+        request_id = %request_id,
+        asset_code = %code,
+        issuer = %issuer,
+        risk_level = ?audit.risk_level,
+        horizon_fetch_duration_ms,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(apply_privacy(
+        audit,
+        privacy_enabled(options.privacy),
+    )?))
+}
+
+pub(crate) fn validate_asset(code: &str, issuer: &str) -> Result<(), AppError> {
+    if code.is_empty() || code.len() > 12 || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(AppError::BadRequest(
+            "Invalid asset code. Expected 1-12 letters or digits.".to_string(),
+        ));
+    }
+    if let Err(err) = strkey::validate(issuer, &[StrKeyKind::Account]) {
+        return Err(AppError::BadRequest(format!("Invalid issuer: {err}.")));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ISSUER: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    #[test]
+    fn test_asset_validation() {
+        assert!(validate_asset("USDC", ISSUER).is_ok());
+        assert!(validate_asset("LONGASSET123", ISSUER).is_ok());
+        assert!(validate_asset("", ISSUER).is_err());
+        assert!(validate_asset("TOOLONGASSET1", ISSUER).is_err());
+        assert!(validate_asset("US-D", ISSUER).is_err());
+        assert!(validate_asset("USDC", "GSHORT").is_err());
+        // One character off the real issuer fails its checksum.
+        let typo = ISSUER.replacen("KZVN", "KZVM", 1);
+        let err = validate_asset("USDC", &typo).unwrap_err();
+        assert!(err.to_api_error().error.message.contains("checksum"));
+    }
+}
@@ -0,0 +1,240 @@
+use axum::{
+    Json,
+    extract::{Extension, State},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span};
+use utoipa::ToSchema;
+
+use crate::{
+    errors::AppError,
+    explain::send_check::{PlannedPayment, SendCheck, check_send},
+    middleware::request_id::RequestId,
+    models::{amount::Amount, strkey},
+    routes::asset::validate_asset,
+    services::{
+        horizon_api::HorizonApi,
+        policy::{Parties, policy},
+        send_check::send_context,
+    },
+};
+
+/// Longest memo a transaction carries, in bytes (a text memo).
+const MAX_MEMO_BYTES: usize = 28;
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CheckRequest {
+    /// G... address the payment goes to.
+    pub destination: String,
+    /// "native" (or "XLM") or "CODE:ISSUER".
+    pub asset: String,
+    /// Amount to send, e.g. "25.5".
+    pub amount: String,
+    /// The memo the payment will carry, if any.
+    pub memo: Option<String>,
+}
+
+/// POST /check
+/// Warnings for a payment before a wallet sends it.
+#[utoipa::path(
+    post,
+    path = "/check",
+    request_body = CheckRequest,
+    responses(
+        (status = 200, description = "What could go wrong with the payment", body = SendCheck),
+        (status = 400, description = "Invalid destination, asset, amount, or memo"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn post_check<H: HorizonApi>(
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+    Json(request): Json<CheckRequest>,
+) -> Result<Json<SendCheck>, AppError> {
+    let span = info_span!("send_check_request", request_id = %request_id);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let payment = match planned_payment(&request) {
+        Ok(payment) => payment,
+        Err(app_error) => {
+            info!(
+                request_id = %request_id,
+                status = app_error.status_code().as_u16(),
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                error = ?app_error,
+                "request_completed"
+            );
+            return Err(app_error);
+        }
+    };
+    let mut parties = match &payment.asset {
+        Some((code, issuer)) => Parties::asset(code, issuer),
+        None => Parties::default(),
+    };
+    parties.accounts.insert(payment.destination.clone());
+    policy().check(&parties, "this payment")?;
+
+    let horizon_started_at = Instant::now();
+    let context = match send_context(horizon_client.as_ref(), &payment).await {
+        Ok(context) => context,
+        Err(err) => {
+            let app_error: AppError = err.into();
+            error!(
+                request_id = %request_id,
+                destination = %payment.destination,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_send_check_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+    let check = check_send(&payment, &context);
+
+    info!(
+        request_id = %request_id,
+        destination = %payment.destination,
+        warnings = check.warnings.len(),
+        horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(check))
+}
+
+/// The payment described by `request`, validated.
+fn planned_payment(request: &CheckRequest) -> Result<PlannedPayment, AppError> {
+    let destination = request.destination.trim();
+    if !strkey::is_account_address(destination) {
+        return Err(AppError::BadRequest(
+            "Invalid destination. Expected a G... account address.".to_string(),
+        ));
+    }
+    let asset = match request.asset.trim() {
+        "native" | "XLM" => None,
+        asset => {
+            let Some((code, issuer)) = asset.split_once(':') else {
+                return Err(AppError::BadRequest(
+                    "asset must be 'native' or 'CODE:ISSUER'".to_string(),
+                ));
+            };
+            validate_asset(code, issuer)?;
+            Some((code.to_string(), issuer.to_string()))
+        }
+    };
+    let amount: Amount = match request.amount.trim().parse() {
+        Ok(amount) if amount > Amount::ZERO => amount,
+        _ => {
+            return Err(AppError::BadRequest(
+                "amount must be a positive number with at most 7 decimal places".to_string(),
+            ));
+        }
+    };
+    if request
+        .memo
+        .as_ref()
+        .is_some_and(|memo| memo.len() > MAX_MEMO_BYTES)
+    {
+        return Err(AppError::BadRequest(format!(
+            "memo must be at most {MAX_MEMO_BYTES} bytes"
+        )));
+    }
+    Ok(PlannedPayment {
+        destination: destination.to_string(),
+        asset,
+        amount,
+        memo: request.memo.clone(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::send_check::SendWarningCode;
+    use crate::models::account::{Account, AccountFlags};
+    use crate::services::horizon_api::FakeHorizon;
+
+    const DESTINATION: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    const ISSUER: &str = "GAIH3ULLFQ4DGSECF2AR555KZ4KNDGEKN4AFI4SU2M7B43MGK3QJZNSR";
+
+    fn account(id: &str, memo_required: bool) -> Account {
+        Account {
+            id: id.to_string(),
+            account_id: id.to_string(),
+            sequence: "1".to_string(),
+            num_signers: 1,
+            signers: Vec::new(),
+            master_key_weight: 1,
+            thresholds: Default::default(),
+            balances: Vec::new(),
+            flags: AccountFlags {
+                auth_required: false,
+                auth_revocable: false,
+                auth_immutable: false,
+                auth_clawback_enabled: true,
+            },
+            home_domain: None,
+            memo_required,
+        }
+    }
+
+    async fn check(horizon: FakeHorizon, asset: &str, amount: &str) -> Result<SendCheck, AppError> {
+        post_check(
+            State(Arc::new(horizon)),
+            Extension(RequestId::new()),
+            Json(CheckRequest {
+                destination: DESTINATION.to_string(),
+                asset: asset.to_string(),
+                amount: amount.to_string(),
+                memo: None,
+            }),
+        )
+        .await
+        .map(|Json(check)| check)
+    }
+
+    #[tokio::test]
+    async fn test_check_reads_destination_and_issuer() {
+        let horizon = FakeHorizon::new()
+            .with_account(account(DESTINATION, true))
+            .with_account(account(ISSUER, false));
+        let checked = check(horizon, &format!("USDC:{ISSUER}"), "25")
+            .await
+            .unwrap();
+        let codes: Vec<SendWarningCode> = checked.warnings.iter().map(|w| w.code).collect();
+        assert_eq!(
+            codes,
+            vec![
+                SendWarningCode::MemoRequired,
+                SendWarningCode::NoTrustline,
+                SendWarningCode::ClawbackEnabled,
+            ]
+        );
+
+        let unfunded = check(FakeHorizon::new(), "native", "0.5").await.unwrap();
+        assert_eq!(
+            unfunded.warnings[0].code,
+            SendWarningCode::DestinationUnfunded
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_rejects_invalid_payments() {
+        for (asset, amount) in [("USDC", "1"), ("native", "0"), ("native", "1.23456789")] {
+            assert!(matches!(
+                check(FakeHorizon::new(), asset, amount).await,
+                Err(AppError::BadRequest(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span};
+
+use crate::{
+    errors::AppError,
+    explain::claimable_balance::{ClaimableBalanceExplanation, explain_claimable_balance},
+    middleware::request_id::RequestId,
+    services::{
+        horizon_api::HorizonApi,
+        policy::{Parties, policy},
+        privacy::{PrivacyQuery, apply_privacy, privacy_enabled},
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/claimable-balance/{id}",
+    params(
+        ("id" = String, Path, description = "Claimable balance ID, hex or B... strkey"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses")
+    ),
+    responses(
+        (status = 200, description = "Claimable balance explanation", body = ClaimableBalanceExplanation),
+        (status = 400, description = "Invalid claimable balance ID"),
+        (status = 404, description = "Claimable balance not found or already claimed"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_claimable_balance<H: HorizonApi>(
+    Path(id): Path<String>,
+    Query(options): Query<PrivacyQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Json<ClaimableBalanceExplanation>, AppError> {
+    let span = info_span!(
+        "claimable_balance_request",
+        request_id = %request_id,
+        balance_id = %id
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, balance_id = %id, "incoming_request");
+
+    if !is_valid_balance_id(&id) {
+        let app_error = AppError::BadRequest(
+            "Invalid claimable balance ID. Expected a 72-character hex ID or a B... strkey."
+                .to_string(),
+        );
+        info!(
+            request_id = %request_id,
+            balance_id = %id,
+            status = app_error.status_code().as_u16(),
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            error = ?app_error,
+            "request_completed"
+        );
+        return Err(app_error);
+    }
+
+    let horizon_started_at = Instant::now();
+    let balance = match horizon_client.fetch_claimable_balance(&id).await {
+        Ok(balance) => balance,
+        Err(err) => {
+            let app_error: AppError = err.into();
+            error!(
+                request_id = %request_id,
+                balance_id = %id,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_claimable_balance_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+
+    let explanation = explain_claimable_balance(&balance);
+    policy().check(
+        &Parties::of_claimable_balance(&explanation),
+        "this claimable balance",
+    )?;
+
+    info!(
+        request_id = %request_id,
+        balance_id = %id,
+        claimants = explanation.claimants.len(),
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(apply_privacy(
+        explanation,
+        privacy_enabled(options.privacy),
+    )?))
+}
+
+/// Horizon accepts the hex form (4-byte type + 32-byte hash) or the strkey.
+fn is_valid_balance_id(id: &str) -> bool {
+    let hex = id.len() == 72 && id.chars().all(|c| c.is_ascii_hexdigit());
+    let strkey = id.len() == 58
+        && id.starts_with('B')
+        && id
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || ('2'..='7').contains(&c));
+    hex || strkey
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_balance_id_validation() {
+        let hex = "00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be";
+        assert!(is_valid_balance_id(hex));
+        assert!(is_valid_balance_id(&format!("B{}", "A".repeat(57))));
+        assert!(!is_valid_balance_id(&hex[8..]));
+        assert!(!is_valid_balance_id("not-an-id"));
+    }
+}
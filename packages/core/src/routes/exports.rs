@@ -0,0 +1,510 @@
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+    http::{
+        HeaderMap, StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
+    response::{IntoResponse, Response},
+};
+use chrono::Duration;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span, warn};
+use utoipa::ToSchema;
+
+use crate::{
+    errors::AppError,
+    explain::time::Clock,
+    middleware::audit::api_key,
+    middleware::request_id::RequestId,
+    routes::{account::parse_date_range, tx::now},
+    services::{
+        audit::key_requester,
+        exports::{ExportContext, ExportFormat, ExportJob, ExportStatus, NewExport, run_export},
+        horizon_api::HorizonApi,
+        policy::{Parties, policy},
+        privacy::{PrivacyQuery, mask_addresses, privacy_enabled},
+        storage::StorageError,
+    },
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ExportRequest {
+    /// Stellar account address (G...).
+    pub address: String,
+    /// First day, "YYYY-MM-DD" in UTC.
+    pub from: String,
+    /// Last day, inclusive.
+    pub to: String,
+    pub format: ExportFormat,
+}
+
+#[utoipa::path(
+    post,
+    path = "/exports",
+    request_body = ExportRequest,
+    responses(
+        (status = 202, description = "The export was started; poll its status", body = ExportJob),
+        (status = 400, description = "Invalid date range"),
+        (status = 401, description = "An API key is required"),
+        (status = 404, description = "Exports are not enabled"),
+        (status = 429, description = "Too many exports started with this API key")
+    )
+)]
+pub async fn post_export<H: HorizonApi>(
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+    headers: HeaderMap,
+    exports: Option<Extension<ExportContext>>,
+    clock: Option<Extension<Arc<dyn Clock>>>,
+    Json(request): Json<ExportRequest>,
+) -> Result<(StatusCode, Json<ExportJob>), AppError> {
+    let span = info_span!("export_request", request_id = %request_id, address = %request.address);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, address = %request.address, "incoming_request");
+
+    let (exports, requester) = authorize(exports, &headers)?;
+    policy().check(&Parties::account(&request.address), "this account")?;
+    let (from, to) = parse_date_range(&request.from, &request.to)?;
+
+    let now = now(clock);
+    let started = exports
+        .store
+        .created_since(&requester, now - Duration::hours(1))
+        .map_err(|err| unavailable(&request_id, err))?;
+    if started >= exports.max_per_hour as u64 {
+        warn!(request_id = %request_id, requester = %requester, started, "export_rate_limited");
+        return Err(AppError::RateLimited(format!(
+            "An API key may start at most {} exports an hour. Try again later.",
+            exports.max_per_hour
+        )));
+    }
+
+    let job = exports
+        .store
+        .create(
+            &NewExport {
+                requester,
+                address: request.address,
+                from,
+                to,
+                format: request.format,
+            },
+            now,
+        )
+        .map_err(|err| unavailable(&request_id, err))?;
+    tokio::spawn(run_export(
+        horizon_client,
+        Arc::clone(&exports.store),
+        job.clone(),
+    ));
+
+    info!(
+        request_id = %request_id,
+        export_id = %job.id,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 202u16,
+        "request_completed"
+    );
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/exports/{id}",
+    params(
+        ("id" = String, Path, description = "Export ID from POST /exports")
+    ),
+    responses(
+        (status = 200, description = "The export's status", body = ExportJob),
+        (status = 401, description = "An API key is required"),
+        (status = 404, description = "No such export for this API key, or exports are not enabled")
+    )
+)]
+pub async fn get_export(
+    Path(id): Path<String>,
+    Extension(request_id): Extension<RequestId>,
+    headers: HeaderMap,
+    exports: Option<Extension<ExportContext>>,
+) -> Result<Json<ExportJob>, AppError> {
+    let span = info_span!("export_status_request", request_id = %request_id, export_id = %id);
+    let _span_guard = span.enter();
+
+    let (exports, requester) = authorize(exports, &headers)?;
+    let job = exports
+        .store
+        .get(&id, &requester)
+        .map_err(|err| unavailable(&request_id, err))?
+        .ok_or_else(|| not_found(&id))?;
+
+    info!(request_id = %request_id, export_id = %id, status = 200u16, "request_completed");
+    Ok(Json(job))
+}
+
+#[utoipa::path(
+    get,
+    path = "/exports/{id}/download",
+    params(
+        ("id" = String, Path, description = "Export ID from POST /exports"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses")
+    ),
+    responses(
+        (status = 200, description = "The exported file", content(
+            ("text/csv" = String),
+            ("application/x-ndjson" = String)
+        )),
+        (status = 401, description = "An API key is required"),
+        (status = 404, description = "No such export, or it has not completed")
+    )
+)]
+pub async fn download_export(
+    Path(id): Path<String>,
+    Query(options): Query<PrivacyQuery>,
+    Extension(request_id): Extension<RequestId>,
+    headers: HeaderMap,
+    exports: Option<Extension<ExportContext>>,
+) -> Result<Response, AppError> {
+    let span = info_span!("export_download_request", request_id = %request_id, export_id = %id);
+    let _span_guard = span.enter();
+
+    let (exports, requester) = authorize(exports, &headers)?;
+    let job = exports
+        .store
+        .get(&id, &requester)
+        .map_err(|err| unavailable(&request_id, err))?
+        .ok_or_else(|| not_found(&id))?;
+    if job.status != ExportStatus::Completed {
+        return Err(AppError::NotFound(format!(
+            "Export {id} has not completed; check GET /exports/{id}."
+        )));
+    }
+    let (format, content) = exports
+        .store
+        .content(&id, &requester)
+        .map_err(|err| unavailable(&request_id, err))?
+        .ok_or_else(|| not_found(&id))?;
+    // Rows are plain summaries with no memos, so masking the addresses in
+    // the file's text covers both formats.
+    let privacy = privacy_enabled(options.privacy);
+    let content = if privacy {
+        mask_addresses(&String::from_utf8_lossy(&content)).into_bytes()
+    } else {
+        content
+    };
+
+    info!(
+        request_id = %request_id,
+        export_id = %id,
+        bytes = content.len(),
+        status = 200u16,
+        "request_completed"
+    );
+    let address = if privacy {
+        mask_addresses(&job.address)
+    } else {
+        job.address
+    };
+    let filename = format!(
+        "export-{}-{}-{}.{}",
+        address,
+        job.from,
+        job.to,
+        format.as_str()
+    );
+    Ok((
+        [
+            (CONTENT_TYPE, format.content_type().to_string()),
+            (
+                CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{filename}\""),
+            ),
+        ],
+        content,
+    )
+        .into_response())
+}
+
+/// The export store and the caller's requester fingerprint. Every export
+/// belongs to an API key, so one is required.
+fn authorize(
+    exports: Option<Extension<ExportContext>>,
+    headers: &HeaderMap,
+) -> Result<(ExportContext, String), AppError> {
+    let Some(Extension(exports)) = exports else {
+        return Err(AppError::NotFound(
+            "Exports are not enabled; set EXPORTS_ENABLED.".to_string(),
+        ));
+    };
+    let Some(key) = api_key(headers) else {
+        return Err(AppError::Unauthorized(
+            "Exports require an API key in the X-API-Key header.".to_string(),
+        ));
+    };
+    Ok((exports, key_requester(key)))
+}
+
+fn not_found(id: &str) -> AppError {
+    AppError::NotFound(format!("No export {id} was found for this API key."))
+}
+
+fn unavailable(request_id: &RequestId, err: StorageError) -> AppError {
+    error!(request_id = %request_id, error = %err, "export_store_failed");
+    AppError::Internal("Exports are temporarily unavailable.".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::time::FixedClock;
+    use crate::models::account::{Account, AccountFlags};
+    use crate::services::exports::ExportStore;
+    use crate::services::horizon::{
+        HorizonAccountTransaction, HorizonOperation, HorizonTransaction,
+    };
+    use crate::services::horizon_api::FakeHorizon;
+    use axum::http::HeaderValue;
+
+    const NOW: &str = "2025-04-02T12:00:00Z";
+
+    fn horizon() -> FakeHorizon {
+        let listed = |hash: &str, created_at: &str| HorizonAccountTransaction {
+            hash: hash.to_string(),
+            successful: true,
+            created_at: created_at.to_string(),
+            source_account: Some("GME".to_string()),
+            operation_count: 1,
+            memo_type: None,
+            memo: None,
+            memo_bytes: None,
+            paging_token: None,
+        };
+        FakeHorizon::new()
+            .with_account(Account {
+                id: "GME".to_string(),
+                account_id: "GME".to_string(),
+                sequence: "1".to_string(),
+                num_signers: 1,
+                signers: Vec::new(),
+                master_key_weight: 1,
+                thresholds: Default::default(),
+                balances: vec![],
+                flags: AccountFlags {
+                    auth_required: false,
+                    auth_revocable: false,
+                    auth_immutable: false,
+                    auth_clawback_enabled: false,
+                },
+                home_domain: None,
+                memo_required: false,
+            })
+            .with_account_transactions(
+                "GME",
+                vec![
+                    listed("first", "2025-03-01T08:00:00Z"),
+                    listed("second", "2025-03-02T09:30:00Z"),
+                ],
+            )
+            .with_transaction(
+                HorizonTransaction {
+                    hash: "first".to_string(),
+                    successful: true,
+                    ..Default::default()
+                },
+                vec![HorizonOperation {
+                    id: "1".to_string(),
+                    transaction_hash: "first".to_string(),
+                    operation_type: "payment".to_string(),
+                    from: Some("GME".to_string()),
+                    to: Some("GTHEM".to_string()),
+                    amount: Some("5.0".to_string()),
+                    asset_type: Some("native".to_string()),
+                    ..Default::default()
+                }],
+            )
+    }
+
+    fn context(max_per_hour: u32) -> ExportContext {
+        ExportContext {
+            store: Arc::new(ExportStore::open_in_memory().unwrap()),
+            max_per_hour,
+        }
+    }
+
+    fn with_key(key: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-api-key", HeaderValue::from_str(key).unwrap());
+        headers
+    }
+
+    async fn start(
+        exports: &ExportContext,
+        headers: HeaderMap,
+        format: ExportFormat,
+    ) -> Result<ExportJob, AppError> {
+        post_export(
+            State(Arc::new(horizon())),
+            Extension(RequestId::new()),
+            headers,
+            Some(Extension(exports.clone())),
+            Some(Extension(
+                Arc::new(FixedClock(NOW.parse().unwrap())) as Arc<dyn Clock>
+            )),
+            Json(ExportRequest {
+                address: "GME".to_string(),
+                from: "2025-03-01".to_string(),
+                to: "2025-03-31".to_string(),
+                format,
+            }),
+        )
+        .await
+        .map(|(status, Json(job))| {
+            assert_eq!(status, StatusCode::ACCEPTED);
+            job
+        })
+    }
+
+    async fn finished(exports: &ExportContext, headers: &HeaderMap, id: &str) -> ExportJob {
+        for _ in 0..100 {
+            let Json(job) = get_export(
+                Path(id.to_string()),
+                Extension(RequestId::new()),
+                headers.clone(),
+                Some(Extension(exports.clone())),
+            )
+            .await
+            .unwrap();
+            if matches!(job.status, ExportStatus::Completed | ExportStatus::Failed) {
+                return job;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        panic!("export {id} did not finish");
+    }
+
+    async fn download(
+        exports: &ExportContext,
+        headers: HeaderMap,
+        id: &str,
+    ) -> Result<Response, AppError> {
+        download_export(
+            Path(id.to_string()),
+            Query(PrivacyQuery::default()),
+            Extension(RequestId::new()),
+            headers,
+            Some(Extension(exports.clone())),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_export_runs_to_a_csv_download() {
+        let exports = context(10);
+        let job = start(&exports, with_key("k1"), ExportFormat::Csv)
+            .await
+            .unwrap();
+        assert_eq!(job.status, ExportStatus::Pending);
+        assert_eq!(job.created_at, NOW);
+        assert_eq!(job.download_url, None);
+
+        let job = finished(&exports, &with_key("k1"), &job.id).await;
+        assert_eq!(job.status, ExportStatus::Completed);
+        assert_eq!(job.row_count, Some(2));
+        assert_eq!(
+            job.download_url.as_deref(),
+            Some(format!("/exports/{}/download", job.id).as_str())
+        );
+
+        let response = download(&exports, with_key("k1"), &job.id).await.unwrap();
+        assert_eq!(response.headers()[CONTENT_TYPE], "text/csv; charset=utf-8");
+        assert_eq!(
+            response.headers()[CONTENT_DISPOSITION],
+            "attachment; filename=\"export-GME-2025-03-01-2025-03-31.csv\""
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(body.to_vec()).unwrap(),
+            "created_at,hash,successful,summary\n\
+             2025-03-01T08:00:00Z,first,true,GME sent 5 XLM (native) to GTHEM.\n\
+             2025-03-02T09:30:00Z,second,true,Successful transaction with 1 operation.\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_exports_belong_to_their_api_key() {
+        let exports = context(10);
+        let job = start(&exports, with_key("k1"), ExportFormat::Jsonl)
+            .await
+            .unwrap();
+        finished(&exports, &with_key("k1"), &job.id).await;
+
+        let err = get_export(
+            Path(job.id.clone()),
+            Extension(RequestId::new()),
+            with_key("k2"),
+            Some(Extension(exports.clone())),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+        let err = download(&exports, with_key("k2"), &job.id)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+        let err = download(&exports, HeaderMap::new(), &job.id)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Unauthorized(_)));
+    }
+
+    #[tokio::test]
+    async fn test_exports_are_rate_limited_per_key() {
+        let exports = context(2);
+        for _ in 0..2 {
+            start(&exports, with_key("k1"), ExportFormat::Csv)
+                .await
+                .unwrap();
+        }
+        let err = start(&exports, with_key("k1"), ExportFormat::Csv)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::RateLimited(_)));
+
+        // Another key has its own allowance.
+        start(&exports, with_key("k2"), ExportFormat::Csv)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_export_requires_a_key_and_a_valid_range() {
+        let exports = context(10);
+        let err = start(&exports, HeaderMap::new(), ExportFormat::Csv)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Unauthorized(_)));
+
+        let err = post_export(
+            State(Arc::new(horizon())),
+            Extension(RequestId::new()),
+            with_key("k1"),
+            Some(Extension(exports)),
+            None,
+            Json(ExportRequest {
+                address: "GME".to_string(),
+                from: "2025-03-31".to_string(),
+                to: "2025-03-01".to_string(),
+                format: ExportFormat::Csv,
+            }),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(_)));
+    }
+}
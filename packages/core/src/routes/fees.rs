@@ -0,0 +1,192 @@
+use axum::{
+    Json,
+    extract::{Extension, State},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span, warn};
+
+use crate::{
+    errors::AppError,
+    explain::fee_advice::{FeeAdvice, advise_fee},
+    middleware::request_id::RequestId,
+    routes::query::{FieldErrors, ValidateQuery, ValidatedQuery},
+    services::horizon_api::HorizonApi,
+};
+
+/// Recent ledgers scanned for surge pricing.
+const SURGE_SCAN_LIMIT: u32 = 20;
+const MAX_TARGET_LEDGERS: u32 = 20;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeeAdviceQuery {
+    /// Ledgers the transaction should land within (default 1).
+    pub target_ledgers: Option<u32>,
+}
+
+impl ValidateQuery for FeeAdviceQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.check(
+            "target_ledgers",
+            self.target_ledgers
+                .is_none_or(|n| (1..=MAX_TARGET_LEDGERS).contains(&n)),
+            format!("target_ledgers must be between 1 and {MAX_TARGET_LEDGERS}"),
+        );
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/fees/advise",
+    params(
+        ("target_ledgers" = Option<u32>, Query, description = "Ledgers the transaction should land within (1-20, default 1)")
+    ),
+    responses(
+        (status = 200, description = "Suggested max fee per operation, with its rationale", body = FeeAdvice),
+        (status = 400, description = "Invalid target_ledgers"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_fee_advice<H: HorizonApi>(
+    ValidatedQuery(params): ValidatedQuery<FeeAdviceQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Json<FeeAdvice>, AppError> {
+    let target_ledgers = params.target_ledgers.unwrap_or(1);
+    let span = info_span!(
+        "fee_advice_request",
+        request_id = %request_id,
+        target_ledgers
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, target_ledgers, "incoming_request");
+
+    let horizon_started_at = Instant::now();
+    let (market_res, loads_res) = tokio::join!(
+        horizon_client.fetch_fee_market(),
+        horizon_client.fetch_ledger_loads(SURGE_SCAN_LIMIT),
+    );
+    let horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64;
+
+    let market = match market_res {
+        Ok(market) => market,
+        Err(err) => {
+            let app_error: AppError = err.into();
+            error!(
+                request_id = %request_id,
+                horizon_fetch_duration_ms,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_fee_stats_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+
+    // Surge history is supplementary; advise from fee_stats alone on failure.
+    let loads = loads_res.unwrap_or_else(|err| {
+        warn!(
+            request_id = %request_id,
+            error = ?err,
+            "ledger_loads_fetch_failed"
+        );
+        vec![]
+    });
+
+    let advice = advise_fee(target_ledgers, &market, &loads);
+
+    info!(
+        request_id = %request_id,
+        congestion = ?advice.congestion,
+        max_fee_stroops = advice.max_fee_stroops,
+        horizon_fetch_duration_ms,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(advice))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::fee_advice::Congestion;
+    use crate::models::fee::{FeeDistribution, FeeMarket, LedgerLoad};
+    use crate::services::horizon_api::FakeHorizon;
+
+    fn surging() -> FakeHorizon {
+        let full = LedgerLoad {
+            sequence: 1,
+            operations: 1_000,
+            capacity: 1_000,
+        };
+        FakeHorizon::default()
+            .with_fee_market(FeeMarket {
+                last_ledger: 1,
+                base_fee: 100,
+                capacity_usage: 1.0,
+                charged: FeeDistribution {
+                    mode: 100,
+                    p50: 100,
+                    p70: 200,
+                    p90: 1_000,
+                    p95: 2_000,
+                    p99: 5_000,
+                    max: 9_000,
+                },
+                offered: FeeDistribution::default(),
+            })
+            .with_ledger_loads(vec![full; 30])
+    }
+
+    async fn advise(
+        horizon: FakeHorizon,
+        target_ledgers: Option<u32>,
+    ) -> Result<FeeAdvice, AppError> {
+        let Json(advice) = get_fee_advice(
+            ValidatedQuery(FeeAdviceQuery { target_ledgers }),
+            State(Arc::new(horizon)),
+            Extension(RequestId::new()),
+        )
+        .await?;
+        Ok(advice)
+    }
+
+    #[tokio::test]
+    async fn test_advice_scans_recent_ledgers() {
+        let next = advise(surging(), None).await.unwrap();
+        assert_eq!(next.target_ledgers, 1);
+        assert_eq!(next.congestion, Congestion::Surging);
+        assert_eq!(next.ledgers_scanned, SURGE_SCAN_LIMIT);
+        assert_eq!(next.surge_ledgers, SURGE_SCAN_LIMIT);
+        assert_eq!(next.max_fee_stroops, 5_000);
+
+        let later = advise(surging(), Some(4)).await.unwrap();
+        assert_eq!(later.max_fee_stroops, 1_000);
+    }
+
+    #[tokio::test]
+    async fn test_advice_requires_fee_stats() {
+        let err = advise(FakeHorizon::default(), None).await.unwrap_err();
+        assert!(matches!(err, AppError::UpstreamFailure(_)), "{err:?}");
+    }
+
+    #[test]
+    fn test_target_ledgers_validation() {
+        let invalid = |target_ledgers| {
+            let mut errors = FieldErrors::default();
+            FeeAdviceQuery { target_ledgers }.validate(&mut errors);
+            errors.into_result().is_err()
+        };
+        assert!(!invalid(None));
+        assert!(!invalid(Some(20)));
+        assert!(invalid(Some(0)));
+        assert!(invalid(Some(21)));
+    }
+}
@@ -1,7 +1,16 @@
 use crate::middleware::request_id::RequestId;
-use crate::services::horizon::HorizonClient;
-use axum::{Json, extract::Extension, http::StatusCode};
+use crate::services::blocklist::{self, BlocklistStatus};
+use crate::services::circuit::{CircuitSnapshot, CircuitState};
+use crate::services::horizon_api::HorizonApi;
+use crate::services::ingestion::{IngestionSnapshot, IngestionStatus};
+use crate::services::storage::ExplanationStore;
+use axum::{
+    Json,
+    extract::{Extension, State},
+    http::StatusCode,
+};
 use serde::Serialize;
+use std::sync::Arc;
 use std::time::Instant;
 use tracing::{info, info_span, warn};
 use utoipa::ToSchema;
@@ -11,7 +20,12 @@ pub struct HealthResponse {
     pub status: String,
     pub network: String,
     pub horizon_reachable: bool,
+    /// Circuit breaker around Horizon calls; "open" while requests fail fast.
+    pub horizon_circuit: CircuitSnapshot,
     pub version: String,
+    /// The spam blocklist in use; absent when none is configured.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blocklist: Option<BlocklistStatus>,
 }
 
 #[utoipa::path(
@@ -22,7 +36,8 @@ pub struct HealthResponse {
         (status = 503, description = "Service degraded", body = HealthResponse)
     )
 )]
-pub async fn health(
+pub async fn health<H: HorizonApi>(
+    State(horizon_client): State<Arc<H>>,
     Extension(request_id): Extension<RequestId>,
 ) -> Result<Json<HealthResponse>, (StatusCode, Json<HealthResponse>)> {
     let span = info_span!("health_request", request_id = %request_id);
@@ -31,31 +46,31 @@ pub async fn health(
 
     info!(request_id = %request_id, "incoming_request");
 
-    let horizon_url = std::env::var("HORIZON_URL")
-        .unwrap_or_else(|_| "https://horizon-testnet.stellar.org".into());
-
     let network = std::env::var("NETWORK").unwrap_or_else(|_| "testnet".into());
 
     let version = env!("CARGO_PKG_VERSION").to_string();
 
-    let horizon_client = HorizonClient::new(horizon_url);
-
     let horizon_started_at = Instant::now();
     let horizon_reachable = horizon_client.is_reachable().await;
     let horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64;
 
+    let horizon_circuit = horizon_client.circuit_snapshot();
+    let healthy = horizon_reachable && horizon_circuit.state != CircuitState::Open;
+
     let response = HealthResponse {
-        status: if horizon_reachable {
+        status: if healthy {
             "ok".into()
         } else {
             "degraded".into()
         },
         network,
         horizon_reachable,
+        horizon_circuit,
         version,
+        blocklist: blocklist::current().map(|list| list.status().clone()),
     };
 
-    if horizon_reachable {
+    if healthy {
         info!(
             request_id = %request_id,
             status = 200u16,
@@ -70,9 +85,279 @@ pub async fn health(
             status = StatusCode::SERVICE_UNAVAILABLE.as_u16(),
             horizon_fetch_duration_ms,
             total_duration_ms = request_started_at.elapsed().as_millis() as u64,
-            upstream_error = "horizon_unreachable",
+            upstream_error = if horizon_reachable {
+                "horizon_circuit_open"
+            } else {
+                "horizon_unreachable"
+            },
             "request_completed"
         );
         Err((StatusCode::SERVICE_UNAVAILABLE, Json(response)))
     }
 }
+
+/// Default `READY_MAX_LAG_LEDGERS`: about eight minutes of ledgers.
+const DEFAULT_MAX_LAG_LEDGERS: u64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ComponentState {
+    Ok,
+    Failing,
+    /// Not configured on this server; does not affect readiness.
+    Disabled,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ComponentHealth {
+    pub status: ComponentState,
+    pub message: String,
+}
+
+impl ComponentHealth {
+    fn new(status: ComponentState, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LivenessResponse {
+    pub status: String,
+    pub version: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReadinessComponents {
+    pub horizon: ComponentHealth,
+    pub cache: ComponentHealth,
+    pub storage: ComponentHealth,
+    pub ingestion: ComponentHealth,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReadinessResponse {
+    /// "ready" when no component is failing, otherwise "not_ready".
+    pub status: String,
+    pub components: ReadinessComponents,
+}
+
+/// Liveness: the process is up and serving requests. Checks no dependencies,
+/// so an upstream outage never gets the service restarted.
+#[utoipa::path(
+    get,
+    path = "/health/live",
+    responses(
+        (status = 200, description = "Process is up", body = LivenessResponse)
+    )
+)]
+pub async fn health_live() -> Json<LivenessResponse> {
+    Json(LivenessResponse {
+        status: "ok".into(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+    })
+}
+
+/// Readiness: every dependency needed to answer requests is usable.
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    responses(
+        (status = 200, description = "Ready to serve traffic", body = ReadinessResponse),
+        (status = 503, description = "A component is failing", body = ReadinessResponse)
+    )
+)]
+pub async fn health_ready<H: HorizonApi>(
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+    store: Option<Extension<Arc<ExplanationStore>>>,
+    ingestion: Option<Extension<Arc<IngestionStatus>>>,
+) -> (StatusCode, Json<ReadinessResponse>) {
+    let span = info_span!("health_ready_request", request_id = %request_id);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    let horizon_reachable = horizon_client.is_reachable().await;
+    let components = ReadinessComponents {
+        horizon: horizon_component(horizon_reachable, &horizon_client.circuit_snapshot()),
+        cache: cache_component(horizon_client.stale_cache_len()),
+        storage: storage_component(store.as_deref().map(Arc::as_ref)),
+        ingestion: ingestion_component(
+            ingestion.map(|Extension(status)| status.snapshot()),
+            max_lag_ledgers(),
+        ),
+    };
+
+    let failing: Vec<&str> = [
+        ("horizon", &components.horizon),
+        ("cache", &components.cache),
+        ("storage", &components.storage),
+        ("ingestion", &components.ingestion),
+    ]
+    .into_iter()
+    .filter(|(_, c)| c.status == ComponentState::Failing)
+    .map(|(name, _)| name)
+    .collect();
+
+    let total_duration_ms = request_started_at.elapsed().as_millis() as u64;
+    if failing.is_empty() {
+        info!(
+            request_id = %request_id,
+            status = 200u16,
+            total_duration_ms,
+            "request_completed"
+        );
+        let response = ReadinessResponse {
+            status: "ready".into(),
+            components,
+        };
+        (StatusCode::OK, Json(response))
+    } else {
+        warn!(
+            request_id = %request_id,
+            status = StatusCode::SERVICE_UNAVAILABLE.as_u16(),
+            total_duration_ms,
+            failing_components = ?failing,
+            "request_completed"
+        );
+        let response = ReadinessResponse {
+            status: "not_ready".into(),
+            components,
+        };
+        (StatusCode::SERVICE_UNAVAILABLE, Json(response))
+    }
+}
+
+/// `READY_MAX_LAG_LEDGERS`: how far ingestion may trail the network tip.
+fn max_lag_ledgers() -> u64 {
+    std::env::var("READY_MAX_LAG_LEDGERS")
+        .ok()
+        .and_then(|v| v.trim().parse().ok())
+        .unwrap_or(DEFAULT_MAX_LAG_LEDGERS)
+}
+
+fn horizon_component(reachable: bool, circuit: &CircuitSnapshot) -> ComponentHealth {
+    match (reachable, circuit.retry_after_seconds) {
+        (false, _) => ComponentHealth::new(ComponentState::Failing, "Horizon is unreachable."),
+        (true, Some(secs)) if circuit.state == CircuitState::Open => ComponentHealth::new(
+            ComponentState::Failing,
+            format!("Horizon circuit is open after repeated failures; retrying in {secs}s."),
+        ),
+        (true, _) => ComponentHealth::new(ComponentState::Ok, "Horizon is reachable."),
+    }
+}
+
+fn cache_component(entries: Option<usize>) -> ComponentHealth {
+    match entries {
+        Some(n) => ComponentHealth::new(
+            ComponentState::Ok,
+            format!("{n} Horizon responses cached for replay."),
+        ),
+        None => ComponentHealth::new(
+            ComponentState::Failing,
+            "Response cache is unusable after a panic.",
+        ),
+    }
+}
+
+fn storage_component(store: Option<&ExplanationStore>) -> ComponentHealth {
+    let Some(store) = store else {
+        return ComponentHealth::new(ComponentState::Disabled, "No explanation index configured.");
+    };
+    match store.explanation_count() {
+        Ok(count) => ComponentHealth::new(
+            ComponentState::Ok,
+            format!("Explanation index holds {count} transactions."),
+        ),
+        Err(err) => ComponentHealth::new(
+            ComponentState::Failing,
+            format!("Explanation index is unreadable: {err}."),
+        ),
+    }
+}
+
+fn ingestion_component(snapshot: Option<IngestionSnapshot>, max_lag: u64) -> ComponentHealth {
+    let Some(snapshot) = snapshot else {
+        return ComponentHealth::new(ComponentState::Disabled, "Ledger ingestion is not enabled.");
+    };
+    match snapshot.lag_ledgers {
+        None => ComponentHealth::new(ComponentState::Ok, "Waiting for the first ledger."),
+        Some(lag) if lag > max_lag => ComponentHealth::new(
+            ComponentState::Failing,
+            format!("Ingestion is {lag} ledgers behind the network (limit {max_lag})."),
+        ),
+        Some(lag) => ComponentHealth::new(
+            ComponentState::Ok,
+            format!("Ingestion is {lag} ledgers behind the network."),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(lag_ledgers: Option<u64>) -> IngestionSnapshot {
+        IngestionSnapshot {
+            last_ingested_ledger: Some(100),
+            latest_network_ledger: lag_ledgers.map(|lag| 100 + lag),
+            lag_ledgers,
+            transactions_indexed: 0,
+        }
+    }
+
+    #[test]
+    fn test_ingestion_lag_threshold() {
+        assert_eq!(
+            ingestion_component(Some(snapshot(Some(5))), 10).status,
+            ComponentState::Ok
+        );
+        assert_eq!(
+            ingestion_component(Some(snapshot(Some(11))), 10).status,
+            ComponentState::Failing
+        );
+        assert_eq!(
+            ingestion_component(Some(snapshot(None)), 10).status,
+            ComponentState::Ok
+        );
+        assert_eq!(
+            ingestion_component(None, 10).status,
+            ComponentState::Disabled
+        );
+    }
+
+    #[test]
+    fn test_open_circuit_fails_horizon_component() {
+        let open = CircuitSnapshot {
+            state: CircuitState::Open,
+            consecutive_failures: 5,
+            retry_after_seconds: Some(12),
+        };
+        let component = horizon_component(true, &open);
+        assert_eq!(component.status, ComponentState::Failing);
+        assert!(component.message.contains("12s"));
+
+        let closed = CircuitSnapshot {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            retry_after_seconds: None,
+        };
+        assert_eq!(horizon_component(true, &closed).status, ComponentState::Ok);
+        assert_eq!(
+            horizon_component(false, &closed).status,
+            ComponentState::Failing
+        );
+    }
+
+    #[test]
+    fn test_storage_component() {
+        assert_eq!(storage_component(None).status, ComponentState::Disabled);
+
+        let store = ExplanationStore::open_in_memory().unwrap();
+        let component = storage_component(Some(&store));
+        assert_eq!(component.status, ComponentState::Ok);
+        assert!(component.message.contains("0 transactions"));
+    }
+}
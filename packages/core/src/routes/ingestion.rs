@@ -0,0 +1,38 @@
+use axum::{Json, extract::Extension};
+use std::sync::Arc;
+use tracing::{info, info_span};
+
+use crate::errors::AppError;
+use crate::middleware::request_id::RequestId;
+use crate::services::ingestion::{IngestionSnapshot, IngestionStatus};
+
+#[utoipa::path(
+    get,
+    path = "/ingestion/status",
+    responses(
+        (status = 200, description = "Ledger ingestion progress and lag", body = IngestionSnapshot),
+        (status = 404, description = "Ingestion is not enabled")
+    )
+)]
+pub async fn get_ingestion_status(
+    Extension(request_id): Extension<RequestId>,
+    status: Option<Extension<Arc<IngestionStatus>>>,
+) -> Result<Json<IngestionSnapshot>, AppError> {
+    let span = info_span!("ingestion_status_request", request_id = %request_id);
+    let _span_guard = span.enter();
+
+    let Some(Extension(status)) = status else {
+        return Err(AppError::NotFound(
+            "Ledger ingestion is not enabled on this server.".to_string(),
+        ));
+    };
+
+    let snapshot = status.snapshot();
+    info!(
+        request_id = %request_id,
+        lag_ledgers = ?snapshot.lag_ledgers,
+        status = 200u16,
+        "request_completed"
+    );
+    Ok(Json(snapshot))
+}
@@ -0,0 +1,44 @@
+use axum::{Json, extract::Extension};
+use serde::Serialize;
+use std::sync::Arc;
+use utoipa::ToSchema;
+
+use crate::services::receipt::{ReceiptKey, ReceiptSigner};
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct KeysResponse {
+    /// Keys that sign explanation receipts; empty when receipts are off.
+    pub keys: Vec<ReceiptKey>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/keys",
+    responses(
+        (status = 200, description = "Verification keys for explanation receipts", body = KeysResponse)
+    )
+)]
+pub async fn get_keys(signer: Option<Extension<Arc<ReceiptSigner>>>) -> Json<KeysResponse> {
+    Json(KeysResponse {
+        keys: signer
+            .map(|Extension(signer)| vec![signer.public_key()])
+            .unwrap_or_default(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_keys_publish_the_signing_key() {
+        let seed = stellar_strkey::ed25519::PrivateKey([7; 32]).to_string();
+        let signer = Arc::new(ReceiptSigner::from_seed(&seed).unwrap());
+
+        let Json(response) = get_keys(Some(Extension(Arc::clone(&signer)))).await;
+        assert_eq!(response.keys, vec![signer.public_key()]);
+
+        let Json(response) = get_keys(None).await;
+        assert!(response.keys.is_empty());
+    }
+}
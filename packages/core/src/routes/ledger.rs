@@ -0,0 +1,132 @@
+use axum::{
+    Json,
+    extract::{Extension, Path, State},
+};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span};
+
+use crate::{
+    errors::AppError,
+    explain::ledger::{LedgerExplanation, explain_ledger},
+    middleware::request_id::RequestId,
+    services::horizon_api::HorizonApi,
+};
+
+#[utoipa::path(
+    get,
+    path = "/ledger/{sequence}",
+    params(
+        ("sequence" = String, Path, description = "Ledger sequence number")
+    ),
+    responses(
+        (status = 200, description = "Ledger explanation", body = LedgerExplanation),
+        (status = 400, description = "Invalid ledger sequence"),
+        (status = 404, description = "Ledger not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_ledger<H: HorizonApi>(
+    Path(sequence): Path<String>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Json<LedgerExplanation>, AppError> {
+    let span = info_span!(
+        "ledger_request",
+        request_id = %request_id,
+        sequence = %sequence
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, sequence = %sequence, "incoming_request");
+
+    let Some(sequence) = parse_sequence(&sequence) else {
+        let app_error = AppError::BadRequest(
+            "Invalid ledger sequence. Expected a positive whole number.".to_string(),
+        );
+        info!(
+            request_id = %request_id,
+            status = app_error.status_code().as_u16(),
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            error = ?app_error,
+            "request_completed"
+        );
+        return Err(app_error);
+    };
+
+    let horizon_started_at = Instant::now();
+    let ledger = horizon_client.fetch_ledger(sequence).await.map_err(|err| {
+        let app_error: AppError = err.into();
+        error!(
+            request_id = %request_id,
+            sequence,
+            horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            status = app_error.status_code().as_u16(),
+            error = ?app_error,
+            "horizon_ledger_fetch_failed"
+        );
+        app_error
+    })?;
+
+    info!(
+        request_id = %request_id,
+        sequence,
+        horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(explain_ledger(&ledger)))
+}
+
+fn parse_sequence(sequence: &str) -> Option<u64> {
+    sequence.parse().ok().filter(|&s| s > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ledger::Ledger;
+    use crate::services::horizon_api::FakeHorizon;
+
+    async fn explain(horizon: FakeHorizon, sequence: &str) -> Result<LedgerExplanation, AppError> {
+        get_ledger(
+            Path(sequence.to_string()),
+            State(Arc::new(horizon)),
+            Extension(RequestId::new()),
+        )
+        .await
+        .map(|Json(explanation)| explanation)
+    }
+
+    #[tokio::test]
+    async fn test_explains_ledger() {
+        let horizon = FakeHorizon::new().with_ledger(Ledger {
+            sequence: 500,
+            closed_at: "2025-03-04T10:15:22Z".to_string(),
+            protocol_version: 22,
+            successful_transaction_count: 3,
+            failed_transaction_count: 0,
+            operation_count: 5,
+            base_fee_in_stroops: 100,
+            base_reserve_in_stroops: 5_000_000,
+        });
+
+        let explanation = explain(horizon, "500").await.unwrap();
+        assert_eq!(explanation.protocol_version, 22);
+        assert!(explanation.summary.starts_with("Ledger #500 closed"));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_sequence_and_reports_missing_ledger() {
+        for sequence in ["0", "-1", "abc"] {
+            let err = explain(FakeHorizon::new(), sequence).await.unwrap_err();
+            assert!(matches!(err, AppError::BadRequest(_)), "{sequence}");
+        }
+        let err = explain(FakeHorizon::new(), "7").await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+}
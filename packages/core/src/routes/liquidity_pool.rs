@@ -0,0 +1,143 @@
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span, warn};
+
+use crate::{
+    errors::AppError,
+    explain::liquidity_pool::{LiquidityPoolExplanation, explain_liquidity_pool},
+    middleware::request_id::RequestId,
+    services::{
+        horizon_api::HorizonApi,
+        policy::{Parties, policy},
+        privacy::{PrivacyQuery, apply_privacy, privacy_enabled},
+    },
+};
+
+/// Recent pool operations scanned for deposits and withdrawals.
+const ACTIVITY_SCAN_LIMIT: u32 = 50;
+/// Deposits and withdrawals returned per pool.
+const ACTIVITY_LIMIT: usize = 10;
+
+#[utoipa::path(
+    get,
+    path = "/liquidity-pool/{id}",
+    params(
+        ("id" = String, Path, description = "Liquidity pool ID (64-character hex)"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses")
+    ),
+    responses(
+        (status = 200, description = "Liquidity pool explanation", body = LiquidityPoolExplanation),
+        (status = 400, description = "Invalid liquidity pool ID"),
+        (status = 404, description = "Liquidity pool not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_liquidity_pool<H: HorizonApi>(
+    Path(id): Path<String>,
+    Query(options): Query<PrivacyQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Json<LiquidityPoolExplanation>, AppError> {
+    let span = info_span!(
+        "liquidity_pool_request",
+        request_id = %request_id,
+        pool_id = %id
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, pool_id = %id, "incoming_request");
+
+    if !is_valid_pool_id(&id) {
+        let app_error = AppError::BadRequest(
+            "Invalid liquidity pool ID. Expected a 64-character hexadecimal ID.".to_string(),
+        );
+        info!(
+            request_id = %request_id,
+            pool_id = %id,
+            status = app_error.status_code().as_u16(),
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            error = ?app_error,
+            "request_completed"
+        );
+        return Err(app_error);
+    }
+
+    let horizon_started_at = Instant::now();
+    let (pool_res, activity_res) = tokio::join!(
+        horizon_client.fetch_liquidity_pool(&id),
+        horizon_client.fetch_liquidity_pool_activity(&id, ACTIVITY_SCAN_LIMIT),
+    );
+    let horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64;
+
+    let pool = match pool_res {
+        Ok(pool) => pool,
+        Err(err) => {
+            let app_error: AppError = err.into();
+            error!(
+                request_id = %request_id,
+                pool_id = %id,
+                horizon_fetch_duration_ms,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_liquidity_pool_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+
+    // Activity is supplementary; explain the pool without it on failure.
+    let mut activity = activity_res.unwrap_or_else(|err| {
+        warn!(
+            request_id = %request_id,
+            pool_id = %id,
+            error = ?err,
+            "liquidity_pool_activity_fetch_failed"
+        );
+        vec![]
+    });
+    activity.truncate(ACTIVITY_LIMIT);
+
+    let explanation = explain_liquidity_pool(&pool, &activity);
+    policy().check(
+        &Parties::of_liquidity_pool(&explanation),
+        "this liquidity pool",
+    )?;
+
+    info!(
+        request_id = %request_id,
+        pool_id = %id,
+        horizon_fetch_duration_ms,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(apply_privacy(
+        explanation,
+        privacy_enabled(options.privacy),
+    )?))
+}
+
+fn is_valid_pool_id(id: &str) -> bool {
+    id.len() == 64 && id.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_id_validation() {
+        assert!(is_valid_pool_id(
+            "dd7b1ab831c273310ddbec6f97870aa83c2fbd78ce22aded37ecbf4f3380fac7"
+        ));
+        assert!(!is_valid_pool_id("dd7b1ab8"));
+        assert!(!is_valid_pool_id(&"z".repeat(64)));
+    }
+}
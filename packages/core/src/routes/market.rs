@@ -0,0 +1,333 @@
+use axum::{
+    Json,
+    extract::{Extension, Path, State},
+};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span};
+
+use crate::{
+    errors::AppError,
+    explain::market::{MarketSummary, MarketWindow, Resolution, explain_market},
+    explain::time::Clock,
+    middleware::{envelope::CacheStatus, request_id::RequestId},
+    routes::asset::validate_asset,
+    routes::query::{FieldErrors, ValidateQuery, ValidatedQuery},
+    routes::tx::now,
+    services::{
+        horizon_api::HorizonApi,
+        market::MarketCache,
+        policy::{Parties, policy},
+    },
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MarketQuery {
+    /// 1h, 24h, 7d, or 30d.
+    pub window: Option<String>,
+    /// 1m, 5m, 15m, 1h, 1d, or 1w.
+    pub resolution: Option<String>,
+}
+
+impl MarketQuery {
+    fn window(&self) -> Option<MarketWindow> {
+        match &self.window {
+            Some(window) => MarketWindow::parse(window),
+            None => Some(MarketWindow::Day),
+        }
+    }
+
+    fn resolution(&self) -> Option<Resolution> {
+        match &self.resolution {
+            Some(resolution) => Resolution::parse(resolution),
+            None => self.window().map(MarketWindow::default_resolution),
+        }
+    }
+}
+
+impl ValidateQuery for MarketQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        let window = self.window();
+        errors.check(
+            "window",
+            window.is_some(),
+            "window must be 1h, 24h, 7d, or 30d",
+        );
+        let resolution = self.resolution();
+        errors.check(
+            "resolution",
+            self.resolution.is_none() || resolution.is_some(),
+            "resolution must be 1m, 5m, 15m, 1h, 1d, or 1w",
+        );
+        if let (Some(window), Some(resolution)) = (window, resolution) {
+            errors.check(
+                "resolution",
+                window.fits(resolution),
+                format!(
+                    "resolution {} does not fit a {} window; use at most 200 buckets, each no longer than the window",
+                    resolution.as_str(),
+                    window.as_str()
+                ),
+            );
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/market/{base}/{counter}/summary",
+    params(
+        ("base" = String, Path, description = "Base asset: native, XLM, or CODE:ISSUER"),
+        ("counter" = String, Path, description = "Counter asset: native, XLM, or CODE:ISSUER"),
+        ("window" = Option<String>, Query, description = "How far back to look: 1h, 24h (default), 7d, or 30d"),
+        ("resolution" = Option<String>, Query, description = "Bucket size: 1m, 5m, 15m, 1h, 1d, or 1w (default: the finest that fits the window)")
+    ),
+    responses(
+        (status = 200, description = "Volume and price movement of the pair over the window", body = MarketSummary),
+        (status = 400, description = "Invalid asset, window, or resolution"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_market_summary<H: HorizonApi>(
+    Path((base, counter)): Path<(String, String)>,
+    ValidatedQuery(params): ValidatedQuery<MarketQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+    cache: Option<Extension<Arc<MarketCache>>>,
+    clock: Option<Extension<Arc<dyn Clock>>>,
+) -> Result<(Extension<CacheStatus>, Json<MarketSummary>), AppError> {
+    let span = info_span!(
+        "market_summary_request",
+        request_id = %request_id,
+        base = %base,
+        counter = %counter
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, base = %base, counter = %counter, "incoming_request");
+
+    let (base, counter) = match (market_asset(&base), market_asset(&counter)) {
+        (Ok(base), Ok(counter)) if base != counter => (base, counter),
+        (Err(app_error), _) | (_, Err(app_error)) => return Err(app_error),
+        _ => {
+            return Err(AppError::BadRequest(
+                "The base and counter assets must differ.".to_string(),
+            ));
+        }
+    };
+    for asset in [&base, &counter] {
+        if let Some((code, issuer)) = asset.split_once(':') {
+            policy().check(&Parties::asset(code, issuer), "this asset")?;
+        }
+    }
+
+    // Both were checked by validate().
+    let window = params.window().unwrap_or(MarketWindow::Day);
+    let resolution = params
+        .resolution()
+        .unwrap_or_else(|| window.default_resolution());
+
+    if let Some(Extension(cache)) = &cache
+        && let Some(summary) = cache.get(&base, &counter, window, resolution)
+    {
+        info!(
+            request_id = %request_id,
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            status = 200u16,
+            cache = "hit",
+            "request_completed"
+        );
+        return Ok((Extension(CacheStatus::Hit), Json(summary)));
+    }
+
+    // Align to bucket boundaries, so the bucket in progress is included and
+    // the window is a whole number of buckets.
+    let bucket_ms = resolution.millis();
+    let end_time = (now(clock).timestamp_millis() / bucket_ms + 1) * bucket_ms;
+    let start_time = end_time - window.millis();
+
+    let horizon_started_at = Instant::now();
+    let buckets = match horizon_client
+        .fetch_trade_aggregations(&base, &counter, start_time, end_time, bucket_ms)
+        .await
+    {
+        Ok(buckets) => buckets,
+        Err(err) => {
+            let app_error: AppError = err.into();
+            error!(
+                request_id = %request_id,
+                horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "horizon_trade_aggregations_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+    let horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64;
+
+    let summary = explain_market(&base, &counter, window, resolution, &buckets);
+    if let Some(Extension(cache)) = &cache {
+        cache.insert(window, resolution, summary.clone());
+    }
+
+    info!(
+        request_id = %request_id,
+        buckets = buckets.len(),
+        horizon_fetch_duration_ms,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+    Ok((Extension(CacheStatus::Miss), Json(summary)))
+}
+
+/// `native` for "native" or "XLM", else the canonical "CODE:ISSUER".
+fn market_asset(asset: &str) -> Result<String, AppError> {
+    if asset == "native" || asset.eq_ignore_ascii_case("xlm") {
+        return Ok("native".to_string());
+    }
+    let Some((code, issuer)) = asset.split_once(':') else {
+        return Err(AppError::BadRequest(format!(
+            "Invalid asset '{asset}'. Expected native, XLM, or CODE:ISSUER."
+        )));
+    };
+    validate_asset(code, issuer)?;
+    Ok(asset.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::time::FixedClock;
+    use crate::models::trade::TradeBucket;
+    use crate::services::horizon_api::FakeHorizon;
+    use crate::services::transaction_cache::Network;
+    use std::time::Duration;
+
+    const USDC: &str = "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    /// 2024-01-15T17:32:00Z.
+    const NOW_MS: i64 = 1_705_339_920_000;
+
+    fn bucket(timestamp: i64, open: f64, close: f64) -> TradeBucket {
+        TradeBucket {
+            timestamp,
+            trade_count: 10,
+            base_volume: "100".parse().unwrap(),
+            counter_volume: "775".parse().unwrap(),
+            open,
+            high: open.max(close),
+            low: open.min(close),
+            close,
+        }
+    }
+
+    fn clock() -> Option<Extension<Arc<dyn Clock>>> {
+        Some(Extension(
+            Arc::new(FixedClock("2024-01-15T17:32:00Z".parse().unwrap())) as Arc<dyn Clock>,
+        ))
+    }
+
+    fn query(window: Option<&str>, resolution: Option<&str>) -> MarketQuery {
+        MarketQuery {
+            window: window.map(str::to_string),
+            resolution: resolution.map(str::to_string),
+        }
+    }
+
+    async fn summary(
+        horizon: &Arc<FakeHorizon>,
+        base: &str,
+        params: MarketQuery,
+        cache: Option<Arc<MarketCache>>,
+    ) -> Result<(CacheStatus, MarketSummary), AppError> {
+        let (Extension(status), Json(summary)) = get_market_summary(
+            Path((base.to_string(), "XLM".to_string())),
+            ValidatedQuery(params),
+            State(Arc::clone(horizon)),
+            Extension(RequestId::new()),
+            cache.map(Extension),
+            clock(),
+        )
+        .await?;
+        Ok((status, summary))
+    }
+
+    #[tokio::test]
+    async fn test_summary_covers_only_the_window() {
+        let horizon = Arc::new(FakeHorizon::default().with_trade_buckets(
+            USDC,
+            "native",
+            vec![
+                // Two days ago: outside a 24h window.
+                bucket(NOW_MS - 48 * 3_600_000, 5.0, 6.0),
+                bucket(NOW_MS - 20 * 3_600_000, 7.75, 7.8),
+                bucket(NOW_MS - 60_000, 7.8, 7.928),
+            ],
+        ));
+
+        let (status, day) = summary(&horizon, USDC, query(None, None), None)
+            .await
+            .unwrap();
+        assert_eq!(status, CacheStatus::Miss);
+        assert_eq!(day.counter, "native");
+        assert_eq!(day.window, "24h");
+        assert_eq!(day.resolution, "15m");
+        assert_eq!(day.trade_count, 20);
+        assert_eq!(day.open, Some(7.75));
+        assert!(day.summary.contains("the price moved +2.3%"));
+
+        let (_, week) = summary(&horizon, USDC, query(Some("7d"), Some("1d")), None)
+            .await
+            .unwrap();
+        assert_eq!(week.resolution, "1d");
+        assert_eq!(week.trade_count, 30);
+    }
+
+    #[tokio::test]
+    async fn test_summary_is_cached() {
+        let horizon = Arc::new(FakeHorizon::default());
+        let cache = Arc::new(MarketCache::new(Network::Testnet, Duration::from_secs(60)));
+
+        let (first, _) = summary(&horizon, USDC, query(None, None), Some(Arc::clone(&cache)))
+            .await
+            .unwrap();
+        let (second, cached) = summary(&horizon, USDC, query(None, None), Some(cache))
+            .await
+            .unwrap();
+        assert_eq!(first, CacheStatus::Miss);
+        assert_eq!(second, CacheStatus::Hit);
+        assert_eq!(cached.trade_count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_summary_rejects_bad_assets() {
+        let horizon = Arc::new(FakeHorizon::default());
+        for base in ["USDC", "USDC:GABC", "xlm"] {
+            let err = summary(&horizon, base, query(None, None), None)
+                .await
+                .unwrap_err();
+            assert!(matches!(err, AppError::BadRequest(_)), "{base}");
+        }
+    }
+
+    #[test]
+    fn test_query_validation() {
+        let errors = |window, resolution| {
+            let mut errors = FieldErrors::default();
+            query(window, resolution).validate(&mut errors);
+            errors.into_result().is_err()
+        };
+        assert!(!errors(None, None));
+        assert!(!errors(Some("30d"), Some("1w")));
+        assert!(errors(Some("2d"), None));
+        assert!(errors(None, Some("2m")));
+        // 1,440 one-minute buckets in a day.
+        assert!(errors(Some("24h"), Some("1m")));
+        assert!(errors(Some("1h"), Some("1d")));
+    }
+}
@@ -1,15 +1,156 @@
+use axum::{
+    Router,
+    routing::{get, post, put},
+};
+use std::sync::Arc;
 use utoipa::OpenApi;
 
+use crate::services::horizon_api::HorizonApi;
+
 #[derive(OpenApi)]
 #[openapi(
     paths(
         health::health,
+        health::health_live,
+        health::health_ready,
         tx::get_tx_explanation,
+        tx::get_tx_timeline,
+        tx::get_tx_signatures,
+        tx::get_tx_compare,
+        tx::post_explain_bundle,
+        ingestion::get_ingestion_status,
+        search::search_explanations,
+        stats::get_stats,
+        claimable_balance::get_claimable_balance,
+        ledger::get_ledger,
+        fees::get_fee_advice,
+        liquidity_pool::get_liquidity_pool,
+        market::get_market_summary,
+        account::get_account_offers,
+        account::get_account_changes,
+        account::get_account_statement,
+        account::get_account_full_history,
+        account::get_account_flags_history,
+        account::get_related_accounts,
+        account::post_accounts_batch,
+        account::get_account_graph,
+        asset::get_asset_audit,
+        admin::get_audit_log,
+        admin::get_consistency,
+        admin::get_slo,
+        admin::post_blocklist_reload,
+        admin::get_notice,
+        admin::put_notice,
+        admin::delete_notice,
+        admin::get_contract_labels,
+        admin::put_contract_label,
+        admin::delete_contract_label,
+        keys::get_keys,
+        submit::post_submit,
+        testnet::post_testnet_fund,
+        check::post_check,
+        exports::post_export,
+        exports::get_export,
+        exports::download_export,
     ),
     components(
         schemas(
             health::HealthResponse,
-            tx::TxExplanationResponse
+            health::LivenessResponse,
+            health::ReadinessResponse,
+            health::ReadinessComponents,
+            health::ComponentHealth,
+            health::ComponentState,
+            crate::services::circuit::CircuitSnapshot,
+            crate::services::blocklist::BlocklistStatus,
+            crate::services::blocklist::BlocklistAction,
+            admin::NoticeRequest,
+            admin::NoticeResponse,
+            admin::ContractLabelRequest,
+            admin::ContractLabelsResponse,
+            crate::services::contract_labels::ContractLabel,
+            crate::services::contract_labels::ContractLabelKind,
+            crate::services::notice::ServiceNotice,
+            crate::services::notice::NoticeSeverity,
+            crate::services::circuit::CircuitState,
+            tx::TxExplanationResponse,
+            crate::explain::timeline::TransactionTimeline,
+            crate::explain::timeline::TimelineStep,
+            crate::explain::signatures::SignatureExplanation,
+            crate::explain::signatures::AccountSignatures,
+            crate::explain::signatures::MatchedSigner,
+            crate::explain::signatures::ThresholdLevel,
+            crate::explain::signatures::ThresholdOutcome,
+            crate::explain::compare::TransactionComparison,
+            crate::explain::compare::TransactionDifference,
+            tx::BundleRequest,
+            crate::explain::bundle::BundleExplanation,
+            crate::explain::bundle::BundleAccount,
+            crate::explain::bundle::BundleTransaction,
+            crate::services::ingestion::IngestionSnapshot,
+            search::SearchResponse,
+            search::SearchResult,
+            stats::StatsResponse,
+            crate::services::stats::StatsRollup,
+            crate::services::stats::CounterpartyCount,
+            crate::explain::claimable_balance::ClaimableBalanceExplanation,
+            crate::explain::claimable_balance::ClaimantExplanation,
+            crate::explain::ledger::LedgerExplanation,
+            crate::explain::fee_advice::FeeAdvice,
+            crate::explain::fee_advice::Congestion,
+            crate::explain::liquidity_pool::LiquidityPoolExplanation,
+            crate::explain::liquidity_pool::PoolReserveExplanation,
+            crate::explain::liquidity_pool::PoolActivityExplanation,
+            crate::explain::market::MarketSummary,
+            crate::explain::offer::AccountOffersExplanation,
+            crate::explain::offer::OfferExplanation,
+            crate::explain::flags_history::AccountFlagsHistory,
+            crate::explain::flags_history::AccountSettingsChange,
+            crate::explain::payment_graph::PaymentGraph,
+            crate::explain::payment_graph::GraphNode,
+            crate::explain::payment_graph::GraphEdge,
+            account::AccountBatchRequest,
+            account::AccountBatchResponse,
+            account::AccountBatchItem,
+            account::AccountSummary,
+            crate::explain::related_accounts::RelatedAccountsExplanation,
+            crate::explain::related_accounts::RelatedAccount,
+            crate::explain::related_accounts::AccountConnection,
+            crate::explain::related_accounts::ConnectionKind,
+            crate::explain::changes::AccountChanges,
+            crate::explain::statement::AccountStatement,
+            crate::explain::statement::StatementBalance,
+            crate::explain::statement::StatementTransaction,
+            crate::explain::statement::AssetTotal,
+            crate::explain::asset_audit::AssetAuditExplanation,
+            crate::explain::asset_audit::AuditFinding,
+            crate::explain::asset_audit::Severity,
+            crate::explain::issuer_label::IssuerLabel,
+            crate::explain::issuer_label::LabelVerification,
+            admin::AuditResponse,
+            crate::services::consistency::ConsistencySnapshot,
+            crate::services::consistency::ConsistencyMismatch,
+            crate::services::slo::SloReport,
+            crate::services::slo::RouteSlo,
+            crate::services::slo::BurnRate,
+            crate::services::slo::BurnAlert,
+            crate::services::audit::AuditRecord,
+            crate::services::audit::AuditSubject,
+            keys::KeysResponse,
+            submit::SubmitRequest,
+            submit::SubmitResponse,
+            submit::SubmissionState,
+            testnet::FundRequest,
+            testnet::FundResponse,
+            check::CheckRequest,
+            crate::explain::send_check::SendCheck,
+            crate::explain::send_check::SendWarning,
+            crate::explain::send_check::SendWarningCode,
+            crate::services::receipt::ReceiptKey,
+            exports::ExportRequest,
+            crate::services::exports::ExportJob,
+            crate::services::exports::ExportFormat,
+            crate::services::exports::ExportStatus
         )
     ),
     tags(
@@ -20,5 +161,106 @@ use utoipa::OpenApi;
 pub struct ApiDoc;
 
 pub mod account;
+pub mod admin;
+pub mod asset;
+pub mod check;
+pub mod claimable_balance;
+pub mod exports;
+pub mod fees;
 pub mod health;
+pub mod ingestion;
+pub mod keys;
+pub mod ledger;
+pub mod liquidity_pool;
+pub mod market;
+pub mod pagination;
+pub mod query;
+pub mod schema;
+pub mod search;
+pub mod stats;
+pub mod streaming;
+pub mod submit;
+pub mod testnet;
 pub mod tx;
+
+/// Every API route, before state and layers are added.
+pub fn api_routes<H: HorizonApi + 'static>() -> Router<Arc<H>> {
+    Router::new()
+        .route("/health", get(health::health))
+        .route("/health/live", get(health::health_live))
+        .route("/health/ready", get(health::health_ready))
+        .route("/tx/compare", get(tx::get_tx_compare))
+        .route("/tx/:hash", get(tx::get_tx_explanation))
+        .route("/tx/:hash/timeline", get(tx::get_tx_timeline))
+        .route("/tx/:hash/signatures", get(tx::get_tx_signatures))
+        .route("/explain/bundle", post(tx::post_explain_bundle))
+        .route("/schema/:name", get(schema::get_schema))
+        .route("/ingestion/status", get(ingestion::get_ingestion_status))
+        .route("/search", get(search::search_explanations))
+        .route("/stats/:window", get(stats::get_stats))
+        .route("/ledger/:sequence", get(ledger::get_ledger))
+        .route("/fees/advise", get(fees::get_fee_advice))
+        .route(
+            "/claimable-balance/:id",
+            get(claimable_balance::get_claimable_balance),
+        )
+        .route(
+            "/liquidity-pool/:id",
+            get(liquidity_pool::get_liquidity_pool),
+        )
+        .route(
+            "/market/:base/:counter/summary",
+            get(market::get_market_summary),
+        )
+        .route("/account/:address", get(account::get_account_explanation))
+        .route(
+            "/account/:address/transactions",
+            get(account::get_account_transactions),
+        )
+        .route("/account/:address/offers", get(account::get_account_offers))
+        .route(
+            "/account/:address/changes",
+            get(account::get_account_changes),
+        )
+        .route(
+            "/account/:address/statement",
+            get(account::get_account_statement),
+        )
+        .route(
+            "/account/:address/history/full",
+            get(account::get_account_full_history),
+        )
+        .route(
+            "/account/:address/flags-history",
+            get(account::get_account_flags_history),
+        )
+        .route("/account/:address/graph", get(account::get_account_graph))
+        .route("/accounts/related", get(account::get_related_accounts))
+        .route("/accounts/batch", post(account::post_accounts_batch))
+        .route("/asset/:code/:issuer/audit", get(asset::get_asset_audit))
+        .route("/admin/audit", get(admin::get_audit_log))
+        .route("/admin/consistency", get(admin::get_consistency))
+        .route("/admin/slo", get(admin::get_slo))
+        .route(
+            "/admin/notice",
+            get(admin::get_notice)
+                .put(admin::put_notice)
+                .delete(admin::delete_notice),
+        )
+        .route("/admin/labels/contracts", get(admin::get_contract_labels))
+        .route(
+            "/admin/labels/contracts/:id",
+            put(admin::put_contract_label).delete(admin::delete_contract_label),
+        )
+        .route(
+            "/admin/blocklist/reload",
+            post(admin::post_blocklist_reload),
+        )
+        .route("/keys", get(keys::get_keys))
+        .route("/submit", post(submit::post_submit))
+        .route("/testnet/fund", post(testnet::post_testnet_fund))
+        .route("/check", post(check::post_check))
+        .route("/exports", post(exports::post_export))
+        .route("/exports/:id", get(exports::get_export))
+        .route("/exports/:id/download", get(exports::download_export))
+}
@@ -0,0 +1,166 @@
+//! Cursor validation and navigation links for paginated listings.
+//!
+//! Pages carry fully-qualified `self`/`next`/`prev` URLs for our own API so
+//! clients never splice Horizon cursors into query strings themselves. Links
+//! are built from `PUBLIC_BASE_URL` when set, otherwise from the request's
+//! `Host` header, and keep every query parameter of the current request.
+
+use axum::http::{HeaderMap, Uri, header};
+use serde::{Deserialize, Serialize};
+use std::env;
+
+/// Longest cursor accepted. Horizon paging tokens are at most 20 digits;
+/// composite tokens such as "123-4" are a little longer.
+const MAX_CURSOR_LEN: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageLinks {
+    #[serde(rename = "self")]
+    pub self_link: String,
+    pub next: Option<String>,
+    pub prev: Option<String>,
+}
+
+/// Whether `cursor` looks like a Horizon paging token: digits, optionally
+/// joined by '-', or "now".
+pub fn is_valid_cursor(cursor: &str) -> bool {
+    cursor == "now"
+        || (!cursor.is_empty()
+            && cursor.len() <= MAX_CURSOR_LEN
+            && cursor
+                .split('-')
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit())))
+}
+
+/// Links for a page served at `uri`. `next` keeps the current order; `prev`
+/// reverses it, matching Horizon's own `prev` links.
+pub fn page_links(
+    headers: &HeaderMap,
+    uri: &Uri,
+    order: &str,
+    next_cursor: Option<&str>,
+    prev_cursor: Option<&str>,
+) -> PageLinks {
+    let base = format!("{}{}", public_base_url(headers), uri.path());
+    let query = uri.query().unwrap_or("");
+    let reversed = if order == "desc" { "asc" } else { "desc" };
+
+    PageLinks {
+        self_link: with_query(&base, query),
+        next: next_cursor.map(|c| with_query(&base, &set_params(query, &[("cursor", c)]))),
+        prev: prev_cursor.map(|c| {
+            with_query(
+                &base,
+                &set_params(query, &[("cursor", c), ("order", reversed)]),
+            )
+        }),
+    }
+}
+
+fn public_base_url(headers: &HeaderMap) -> String {
+    if let Ok(base) = env::var("PUBLIC_BASE_URL") {
+        return base.trim_end_matches('/').to_string();
+    }
+    let host = headers
+        .get(header::HOST)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("localhost");
+    format!("http://{host}")
+}
+
+fn with_query(base: &str, query: &str) -> String {
+    if query.is_empty() {
+        base.to_string()
+    } else {
+        format!("{base}?{query}")
+    }
+}
+
+/// Replace (or append) the given parameters in a raw query string, leaving
+/// every other parameter untouched and in place.
+fn set_params(query: &str, params: &[(&str, &str)]) -> String {
+    let mut pairs: Vec<String> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let name = pair.split('=').next().unwrap_or(pair);
+            match params.iter().find(|(key, _)| *key == name) {
+                Some((key, value)) => format!("{key}={value}"),
+                None => pair.to_string(),
+            }
+        })
+        .collect();
+    for (key, value) in params {
+        let present = pairs
+            .iter()
+            .any(|pair| pair.split('=').next() == Some(*key));
+        if !present {
+            pairs.push(format!("{key}={value}"));
+        }
+    }
+    pairs.join("&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers() -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::HOST, "api.example.com".parse().unwrap());
+        headers
+    }
+
+    #[test]
+    fn test_cursor_validation() {
+        assert!(is_valid_cursor("157639564177408001"));
+        assert!(is_valid_cursor("157639564177408001-2"));
+        assert!(is_valid_cursor("now"));
+        assert!(!is_valid_cursor(""));
+        assert!(!is_valid_cursor("abc"));
+        assert!(!is_valid_cursor("12&limit=200"));
+        assert!(!is_valid_cursor("1-"));
+        assert!(!is_valid_cursor(&"1".repeat(65)));
+    }
+
+    #[test]
+    fn test_links_preserve_query_params() {
+        let uri: Uri = "/account/GABC/transactions?limit=5&hide_spam=true&cursor=100"
+            .parse()
+            .unwrap();
+        let links = page_links(&headers(), &uri, "asc", Some("200"), Some("101"));
+
+        assert_eq!(
+            links.self_link,
+            "http://api.example.com/account/GABC/transactions?limit=5&hide_spam=true&cursor=100"
+        );
+        assert_eq!(
+            links.next.as_deref(),
+            Some(
+                "http://api.example.com/account/GABC/transactions?limit=5&hide_spam=true&cursor=200"
+            )
+        );
+        assert_eq!(
+            links.prev.as_deref(),
+            Some(
+                "http://api.example.com/account/GABC/transactions?limit=5&hide_spam=true&cursor=101&order=desc"
+            )
+        );
+    }
+
+    #[test]
+    fn test_links_without_query() {
+        let uri: Uri = "/account/GABC/transactions".parse().unwrap();
+        let links = page_links(&headers(), &uri, "desc", Some("9"), None);
+
+        assert_eq!(
+            links.self_link,
+            "http://api.example.com/account/GABC/transactions"
+        );
+        assert_eq!(
+            links.next.as_deref(),
+            Some("http://api.example.com/account/GABC/transactions?cursor=9")
+        );
+        assert_eq!(links.prev, None);
+    }
+}
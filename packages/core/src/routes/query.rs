@@ -0,0 +1,272 @@
+//! Typed query parameters for listing endpoints.
+//!
+//! [`ValidatedQuery`] parses the query string into the handler's query type
+//! and then checks it, so a handler only ever sees parameters that are in
+//! range. A parameter that does not parse is reported on its own; range
+//! checks report every problem at once. Either way the error is a
+//! `BAD_REQUEST` whose `details.fields` names each offending parameter:
+//!
+//! ```json
+//! {"error": {"code": "BAD_REQUEST",
+//!            "message": "limit must be between 1 and 50; cursor must be a paging token from a previous page's next_cursor or prev_cursor",
+//!            "details": {"fields": [{"field": "limit", "message": "limit must be between 1 and 50"},
+//!                                   {"field": "cursor", "message": "cursor must be a paging token from a previous page's next_cursor or prev_cursor"}]}}}
+//! ```
+
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::{StatusCode, request::Parts},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, de::DeserializeOwned};
+use tracing::info;
+
+use crate::{
+    errors::{AppError, FieldError},
+    explain::time::parse_timestamp,
+    middleware::request_id::RequestId,
+};
+
+/// Query parameters deserialized into `T` and checked with
+/// [`ValidateQuery::validate`]. Rejects with [`AppError::InvalidQuery`].
+#[derive(Debug, Clone)]
+pub struct ValidatedQuery<T>(pub T);
+
+/// Checks a query type makes beyond what deserializing it enforces.
+pub trait ValidateQuery {
+    /// Record each parameter that is out of range in `errors`.
+    fn validate(&self, _errors: &mut FieldErrors) {}
+}
+
+impl<T: DeserializeOwned + ValidateQuery> ValidatedQuery<T> {
+    /// Parse and validate a raw query string, without the leading '?'.
+    pub fn parse(query: &str) -> Result<Self, AppError> {
+        let deserializer =
+            serde_urlencoded::Deserializer::new(form_urlencoded::parse(query.as_bytes()));
+        let value: T = serde_path_to_error::deserialize(deserializer)
+            .map_err(|err| AppError::InvalidQuery(vec![deserialize_error(err)]))?;
+
+        let mut errors = FieldErrors::default();
+        value.validate(&mut errors);
+        errors.into_result()?;
+        Ok(Self(value))
+    }
+}
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + ValidateQuery,
+    S: Send + Sync,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::parse(parts.uri.query().unwrap_or("")).inspect_err(|app_error| {
+            let request_id = parts
+                .extensions
+                .get::<RequestId>()
+                .cloned()
+                .unwrap_or_default();
+            info!(
+                request_id = %request_id,
+                path = %parts.uri.path(),
+                status = StatusCode::BAD_REQUEST.as_u16(),
+                error = ?app_error,
+                "request_completed"
+            );
+        })
+    }
+}
+
+/// The parameters a query failed validation on.
+#[derive(Debug, Default)]
+pub struct FieldErrors(Vec<FieldError>);
+
+impl FieldErrors {
+    /// Record `message` against `field` unless `ok`.
+    pub fn check(&mut self, field: &str, ok: bool, message: impl Into<String>) {
+        if !ok {
+            self.0.push(FieldError {
+                field: field.to_string(),
+                message: message.into(),
+            });
+        }
+    }
+
+    /// A page size, when given, must be 1 to `max`.
+    pub fn limit(&mut self, limit: Option<u32>, max: u32) {
+        self.check(
+            "limit",
+            limit.is_none_or(|limit| (1..=max).contains(&limit)),
+            format!("limit must be between 1 and {max}"),
+        );
+    }
+
+    pub fn into_result(self) -> Result<(), AppError> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::InvalidQuery(self.0))
+        }
+    }
+}
+
+/// Which end of a listing comes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    /// Oldest first.
+    Asc,
+    /// Newest first.
+    Desc,
+}
+
+impl SortOrder {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SortOrder::Asc => "asc",
+            SortOrder::Desc => "desc",
+        }
+    }
+}
+
+/// An RFC 3339 timestamp query parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Timestamp(pub DateTime<Utc>);
+
+impl TryFrom<String> for Timestamp {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        parse_timestamp(&value)
+            .map(Self)
+            .ok_or("expected an RFC 3339 timestamp")
+    }
+}
+
+/// The parameter a deserialize error is about, and a message naming it.
+/// A missing parameter has no path of its own; serde's message names it
+/// between backticks.
+fn deserialize_error(err: serde_path_to_error::Error<serde_urlencoded::de::Error>) -> FieldError {
+    let path = err.path().to_string();
+    let inner = err.into_inner().to_string();
+    let field = if path == "." {
+        inner.split('`').nth(1).unwrap_or_default().to_string()
+    } else {
+        path
+    };
+    let message = if inner.starts_with("missing field ") {
+        format!("{field} is required")
+    } else if inner.starts_with("unknown field ") {
+        inner.replacen("field", "parameter", 1)
+    } else {
+        format!("{field} is invalid: {inner}")
+    };
+    FieldError { field, message }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+
+    #[derive(Debug, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct ListQuery {
+        q: String,
+        limit: Option<u32>,
+        order: Option<SortOrder>,
+        since: Option<Timestamp>,
+    }
+
+    impl ValidateQuery for ListQuery {
+        fn validate(&self, errors: &mut FieldErrors) {
+            errors.check("q", !self.q.trim().is_empty(), "q must not be empty");
+            errors.limit(self.limit, 50);
+        }
+    }
+
+    fn fields(query: &str) -> Vec<FieldError> {
+        match ValidatedQuery::<ListQuery>::parse(query) {
+            Err(AppError::InvalidQuery(fields)) => fields,
+            other => panic!("expected InvalidQuery, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_typed_values_are_parsed() {
+        let ValidatedQuery(query) = ValidatedQuery::<ListQuery>::parse(
+            "q=invoice&limit=50&order=desc&since=2026-01-02T00:00:00%2B01:00",
+        )
+        .unwrap();
+        assert_eq!(query.q, "invoice");
+        assert_eq!(query.limit, Some(50));
+        assert_eq!(query.order, Some(SortOrder::Desc));
+        assert_eq!(
+            query.since.map(|t| t.0.to_rfc3339()).as_deref(),
+            Some("2026-01-01T23:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn test_parse_errors_name_the_parameter() {
+        let cases = [
+            ("q=x&limit=ten", "limit", "limit is invalid: "),
+            (
+                "q=x&order=newest",
+                "order",
+                "order is invalid: unknown variant `newest`",
+            ),
+            (
+                "q=x&since=yesterday",
+                "since",
+                "since is invalid: expected an RFC 3339",
+            ),
+            ("limit=1", "q", "q is required"),
+            ("q=x&page=2", "page", "unknown parameter `page`"),
+        ];
+        for (query, field, message) in cases {
+            let fields = fields(query);
+            assert_eq!(fields.len(), 1, "{query}");
+            assert_eq!(fields[0].field, field, "{query}");
+            assert!(
+                fields[0].message.starts_with(message),
+                "{query}: {}",
+                fields[0].message
+            );
+        }
+    }
+
+    #[test]
+    fn test_every_invalid_parameter_is_reported() {
+        let err = ValidatedQuery::<ListQuery>::parse("q=%20&limit=0").unwrap_err();
+        assert_eq!(err.status_code(), StatusCode::BAD_REQUEST);
+
+        let body = err.to_api_error().error;
+        assert_eq!(body.code, ErrorCode::BadRequest);
+        assert_eq!(
+            body.message,
+            "q must not be empty; limit must be between 1 and 50"
+        );
+        assert_eq!(
+            body.details.unwrap()["fields"][1],
+            serde_json::json!({"field": "limit", "message": "limit must be between 1 and 50"})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_extractor_reads_the_request_query() {
+        let (mut parts, ()) = axum::http::Request::builder()
+            .uri("/search?q=x&limit=51")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let err = ValidatedQuery::<ListQuery>::from_request_parts(&mut parts, &())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::InvalidQuery(fields) if fields[0].field == "limit"));
+    }
+}
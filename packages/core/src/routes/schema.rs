@@ -0,0 +1,97 @@
+use axum::{Json, extract::Path};
+use schemars::{schema::RootSchema, schema_for};
+
+use crate::{
+    errors::{ApiError, AppError},
+    explain::transaction::TransactionExplanation,
+    routes::account::AccountExplanationResponse,
+};
+
+/// Names of the published schemas, served at `/schema/<name>.json`.
+pub const SCHEMA_NAMES: &[&str] = &["transaction", "account", "error"];
+
+/// GET /schema/:name
+/// Returns the JSON Schema for a response type so integrators can validate
+/// what the API returns, e.g. `/schema/transaction.json`.
+pub async fn get_schema(Path(name): Path<String>) -> Result<Json<RootSchema>, AppError> {
+    let name = name.strip_suffix(".json").unwrap_or(&name);
+    schema_by_name(name).map(Json).ok_or_else(|| {
+        AppError::NotFound(format!(
+            "Unknown schema `{name}`. Available schemas: {}.",
+            SCHEMA_NAMES.join(", ")
+        ))
+    })
+}
+
+fn schema_by_name(name: &str) -> Option<RootSchema> {
+    match name {
+        "transaction" => Some(schema_for!(TransactionExplanation)),
+        "account" => Some(schema_for!(AccountExplanationResponse)),
+        "error" => Some(schema_for!(ApiError)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::transaction::explain_transaction;
+    use crate::models::operation::{Operation, PaymentOperation};
+    use crate::models::transaction::Transaction;
+    use std::collections::BTreeSet;
+
+    fn property_names(schema: &RootSchema) -> BTreeSet<String> {
+        schema
+            .schema
+            .object
+            .as_ref()
+            .expect("object schema")
+            .properties
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn test_every_published_schema_resolves() {
+        for name in SCHEMA_NAMES {
+            assert!(schema_by_name(name).is_some(), "{name}");
+        }
+        assert!(schema_by_name("nope").is_none());
+    }
+
+    #[test]
+    fn test_transaction_schema_matches_serialized_output() {
+        let tx = Transaction::new(
+            "abc".to_string(),
+            true,
+            100,
+            vec![Operation::Payment(PaymentOperation {
+                id: "1".to_string(),
+                source_account: Some("GA".to_string()),
+                destination: "GB".to_string(),
+                asset_type: "native".to_string(),
+                asset_code: None,
+                asset_issuer: None,
+                amount: "1".parse().unwrap(),
+            })],
+            None,
+            None,
+        );
+        let explanation = explain_transaction(&tx, None).unwrap();
+        let value = serde_json::to_value(&explanation).unwrap();
+        let serialized: BTreeSet<String> = value.as_object().unwrap().keys().cloned().collect();
+
+        let schema = schema_by_name("transaction").unwrap();
+        assert_eq!(property_names(&schema), serialized);
+    }
+
+    #[test]
+    fn test_error_schema_shape() {
+        let schema = schema_by_name("error").unwrap();
+        assert_eq!(
+            property_names(&schema),
+            BTreeSet::from(["error".to_string()])
+        );
+    }
+}
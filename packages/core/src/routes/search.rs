@@ -0,0 +1,232 @@
+use axum::{Json, extract::Extension};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span};
+use utoipa::ToSchema;
+
+use crate::{
+    errors::AppError,
+    explain::time::Clock,
+    explain::transaction::TransactionExplanation,
+    middleware::request_id::RequestId,
+    routes::query::{FieldErrors, ValidateQuery, ValidatedQuery},
+    routes::tx::now,
+    services::{
+        blocklist,
+        policy::{Parties, policy},
+        privacy::{apply_privacy, privacy_enabled},
+        storage::ExplanationStore,
+    },
+};
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 50;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SearchQuery {
+    pub q: String,
+    pub limit: Option<u32>,
+    /// Opaque cursor from a previous page's `next_cursor`.
+    pub cursor: Option<u32>,
+    pub privacy: Option<bool>,
+}
+
+impl ValidateQuery for SearchQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.check("q", !self.q.trim().is_empty(), "q must not be empty");
+        errors.limit(self.limit, MAX_LIMIT);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchResult {
+    pub ledger: u64,
+    pub created_at: Option<String>,
+    #[schema(value_type = Object)]
+    pub explanation: TransactionExplanation,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SearchResponse {
+    pub query: String,
+    /// Total number of matching transactions across all pages, including
+    /// any the explanation policy leaves out of `items`.
+    pub total: u64,
+    pub items: Vec<SearchResult>,
+    pub next_cursor: Option<String>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/search",
+    params(
+        ("q" = String, Query, description = "Words to find in memos, summaries, asset codes, and account labels"),
+        ("limit" = Option<u32>, Query, description = "Results per page (1-50, default 10)"),
+        ("cursor" = Option<String>, Query, description = "Cursor from a previous page"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses and redact memo contents")
+    ),
+    responses(
+        (status = 200, description = "Matching explained transactions", body = SearchResponse),
+        (status = 400, description = "Invalid query"),
+        (status = 404, description = "The explanation index is not enabled")
+    )
+)]
+pub async fn search_explanations(
+    ValidatedQuery(params): ValidatedQuery<SearchQuery>,
+    Extension(request_id): Extension<RequestId>,
+    store: Option<Extension<Arc<ExplanationStore>>>,
+    clock: Option<Extension<Arc<dyn Clock>>>,
+) -> Result<Json<SearchResponse>, AppError> {
+    let span = info_span!("search_request", request_id = %request_id, q = %params.q);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, q = %params.q, "incoming_request");
+
+    let Some(Extension(store)) = store else {
+        return Err(AppError::NotFound(
+            "Search requires the local explanation index; enable ledger ingestion.".to_string(),
+        ));
+    };
+
+    let query = params.q.trim();
+    let limit = params.limit.unwrap_or(DEFAULT_LIMIT);
+    let offset = params.cursor.unwrap_or(0);
+
+    let (hits, total) = store.search(query, limit, offset).map_err(|err| {
+        error!(request_id = %request_id, error = %err, "search_failed");
+        AppError::Internal("Search is temporarily unavailable.".to_string())
+    })?;
+
+    let now = now(clock);
+    let next_offset = offset as u64 + hits.len() as u64;
+    let response = SearchResponse {
+        query: query.to_string(),
+        total,
+        next_cursor: (next_offset < total).then(|| next_offset.to_string()),
+        // Hits the policy refuses are left out, like a refused `/tx/:hash`.
+        items: hits
+            .into_iter()
+            .filter(|hit| policy().allows(&Parties::of_transaction(&hit.explanation)))
+            .map(|hit| SearchResult {
+                ledger: hit.ledger,
+                created_at: hit.created_at,
+                explanation: blocklist::flag_transaction(hit.explanation).with_relative_time(now),
+            })
+            .collect(),
+    };
+    let response = apply_privacy(response, privacy_enabled(params.privacy))?;
+
+    info!(
+        request_id = %request_id,
+        total,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+    Ok(Json(response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::transaction::explain_transaction;
+    use crate::models::memo::Memo;
+    use crate::models::operation::{Operation, OtherOperation};
+    use crate::models::transaction::Transaction;
+    use crate::services::storage::StoredExplanation;
+
+    fn store_with(memos: &[&str]) -> Arc<ExplanationStore> {
+        let store = ExplanationStore::open_in_memory().unwrap();
+        for (i, memo) in memos.iter().enumerate() {
+            let tx = Transaction::new(
+                format!("tx{i}"),
+                true,
+                100,
+                vec![Operation::Other(OtherOperation {
+                    id: "1".to_string(),
+                    operation_type: "bump_sequence".to_string(),
+                })],
+                Some(Memo::Text(memo.to_string())),
+                None,
+            );
+            store
+                .put_explanation(&StoredExplanation {
+                    ledger: i as u64,
+                    created_at: None,
+                    fee_charged: 100,
+                    explanation: explain_transaction(&tx, None).unwrap(),
+                })
+                .unwrap();
+        }
+        Arc::new(store)
+    }
+
+    fn query(q: &str, limit: Option<u32>, cursor: Option<u32>) -> ValidatedQuery<SearchQuery> {
+        ValidatedQuery(SearchQuery {
+            q: q.to_string(),
+            limit,
+            cursor,
+            privacy: Some(false),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_search_pages_through_results() {
+        let store = store_with(&["Invoice 1", "Invoice 2", "Invoice 3", "Refund"]);
+
+        let Json(first) = search_explanations(
+            query("invoice", Some(2), None),
+            Extension(RequestId::new()),
+            Some(Extension(Arc::clone(&store))),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(first.total, 3);
+        assert_eq!(first.items.len(), 2);
+        assert_eq!(first.next_cursor.as_deref(), Some("2"));
+
+        let Json(second) = search_explanations(
+            query(
+                "invoice",
+                Some(2),
+                first.next_cursor.map(|c| c.parse().unwrap()),
+            ),
+            Extension(RequestId::new()),
+            Some(Extension(store)),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(second.items.len(), 1);
+        assert_eq!(second.next_cursor, None);
+    }
+
+    #[tokio::test]
+    async fn test_search_rejects_bad_input_and_missing_index() {
+        for (raw, field) in [
+            ("q=%20%20", "q"),
+            ("q=x&limit=0", "limit"),
+            ("q=x&cursor=abc", "cursor"),
+        ] {
+            let err = ValidatedQuery::<SearchQuery>::parse(raw).unwrap_err();
+            assert!(
+                matches!(&err, AppError::InvalidQuery(fields) if fields[0].field == field),
+                "{raw}: {err:?}"
+            );
+        }
+
+        let err = search_explanations(
+            query("x", None, None),
+            Extension(RequestId::new()),
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+}
@@ -0,0 +1,140 @@
+use axum::{
+    Json,
+    extract::{Extension, Path},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span};
+use utoipa::ToSchema;
+
+use crate::{
+    errors::AppError,
+    middleware::request_id::RequestId,
+    routes::query::{FieldErrors, ValidateQuery, ValidatedQuery},
+    services::{
+        policy::{Parties, policy},
+        stats::{StatsRollup, StatsWindow},
+        storage::ExplanationStore,
+    },
+};
+
+const DEFAULT_BUCKETS: u32 = 24;
+const MAX_BUCKETS: u32 = 168;
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StatsQuery {
+    /// Number of most recent buckets to return.
+    pub limit: Option<u32>,
+}
+
+impl ValidateQuery for StatsQuery {
+    fn validate(&self, errors: &mut FieldErrors) {
+        errors.limit(self.limit, MAX_BUCKETS);
+    }
+}
+
+#[derive(Debug, Serialize, ToSchema)]
+pub struct StatsResponse {
+    pub window: String,
+    /// Most recent buckets first.
+    pub buckets: Vec<StatsRollup>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/stats/{window}",
+    params(
+        ("window" = String, Path, description = "Aggregation window: hourly or daily"),
+        ("limit" = Option<u32>, Query, description = "Number of recent buckets (1-168, default 24)")
+    ),
+    responses(
+        (status = 200, description = "Operation mix, average fees, and top counterparties per bucket", body = StatsResponse),
+        (status = 400, description = "Unknown window or invalid limit"),
+        (status = 404, description = "The explanation index is not enabled")
+    )
+)]
+pub async fn get_stats(
+    Path(window): Path<String>,
+    ValidatedQuery(params): ValidatedQuery<StatsQuery>,
+    Extension(request_id): Extension<RequestId>,
+    store: Option<Extension<Arc<ExplanationStore>>>,
+) -> Result<Json<StatsResponse>, AppError> {
+    let span = info_span!("stats_request", request_id = %request_id, window = %window);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, window = %window, "incoming_request");
+
+    let Some(Extension(store)) = store else {
+        return Err(AppError::NotFound(
+            "Statistics require the local explanation index; enable ledger ingestion.".to_string(),
+        ));
+    };
+
+    let Some(stats_window) = StatsWindow::parse(&window) else {
+        return Err(AppError::BadRequest(
+            "window must be 'hourly' or 'daily'".to_string(),
+        ));
+    };
+
+    let limit = params.limit.unwrap_or(DEFAULT_BUCKETS);
+
+    let mut buckets = store.rollups(stats_window, limit).map_err(|err| {
+        error!(request_id = %request_id, error = %err, "stats_read_failed");
+        AppError::Internal("Statistics are temporarily unavailable.".to_string())
+    })?;
+    // Counterparties the policy would refuse to explain are not listed.
+    for bucket in &mut buckets {
+        bucket
+            .top_counterparties
+            .retain(|top| policy().allows(&Parties::account(&top.account)));
+    }
+
+    info!(
+        request_id = %request_id,
+        buckets = buckets.len(),
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+    Ok(Json(StatsResponse {
+        window: stats_window.as_str().to_string(),
+        buckets,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_stats_rejects_unknown_window() {
+        let store = Arc::new(ExplanationStore::open_in_memory().unwrap());
+        let err = get_stats(
+            Path("weekly".to_string()),
+            ValidatedQuery(StatsQuery { limit: None }),
+            Extension(RequestId::new()),
+            Some(Extension(store)),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(_)));
+    }
+
+    #[tokio::test]
+    async fn test_stats_empty_index() {
+        let store = Arc::new(ExplanationStore::open_in_memory().unwrap());
+        let Json(response) = get_stats(
+            Path("daily".to_string()),
+            ValidatedQuery(StatsQuery { limit: Some(7) }),
+            Extension(RequestId::new()),
+            Some(Extension(store)),
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.window, "daily");
+        assert!(response.buckets.is_empty());
+    }
+}
@@ -0,0 +1,113 @@
+//! JSON responses written out in pieces.
+//!
+//! `Json` serializes a whole response into one buffer before the first byte
+//! is sent, which for a transaction with hundreds of operations means a
+//! buffer the size of the response, grown by doubling, alongside the
+//! explanation itself. [`StreamingJson`] serializes on a blocking thread
+//! straight into [`CHUNK_SIZE`] chunks that are sent as they fill, with at
+//! most [`CHUNKS_IN_FLIGHT`] waiting on a slow client, so the extra memory a
+//! response needs stays flat however large it is.
+
+use axum::{
+    body::{Body, Bytes},
+    http::{HeaderValue, header},
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use std::io::{self, Write};
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Bytes serialized before a chunk is sent.
+pub const CHUNK_SIZE: usize = 16 * 1024;
+
+/// Chunks buffered ahead of the client before serialization waits.
+pub const CHUNKS_IN_FLIGHT: usize = 2;
+
+/// A JSON response body that is streamed as it is serialized.
+#[derive(Debug)]
+pub struct StreamingJson<T>(pub T);
+
+impl<T: Serialize + Send + 'static> IntoResponse for StreamingJson<T> {
+    fn into_response(self) -> Response {
+        let (sender, mut receiver) = mpsc::channel::<io::Result<Bytes>>(CHUNKS_IN_FLIGHT);
+        tokio::task::spawn_blocking(move || {
+            let mut writer = ChunkWriter {
+                buffer: Vec::with_capacity(CHUNK_SIZE),
+                sender,
+            };
+            let written = serde_json::to_writer(&mut writer, &self.0)
+                .map_err(io::Error::other)
+                .and_then(|_| writer.flush());
+            if let Err(err) = written {
+                // The client has gone, or the value failed to serialize;
+                // either way the body ends early.
+                warn!(error = %err, "streaming_json_aborted");
+                let _ = writer.sender.blocking_send(Err(err));
+            }
+        });
+
+        let stream = futures_util::stream::poll_fn(move |cx| receiver.poll_recv(cx));
+        let mut response = Body::from_stream(stream).into_response();
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("application/json"),
+        );
+        response
+    }
+}
+
+/// Collects serialized bytes and sends them a chunk at a time.
+struct ChunkWriter {
+    buffer: Vec<u8>,
+    sender: mpsc::Sender<io::Result<Bytes>>,
+}
+
+impl ChunkWriter {
+    fn send(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buffer, Vec::with_capacity(CHUNK_SIZE));
+        self.sender
+            .blocking_send(Ok(Bytes::from(chunk)))
+            .map_err(|_| io::Error::new(io::ErrorKind::BrokenPipe, "response body dropped"))
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(bytes);
+        if self.buffer.len() >= CHUNK_SIZE {
+            self.send()?;
+        }
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use serde_json::{Value, json};
+
+    #[tokio::test]
+    async fn test_streams_the_same_json_in_chunks() {
+        let value = json!({
+            "summary": "Many payments",
+            "operations": (0..2_000).map(|i| json!({ "index": i, "summary": "Paid 5 XLM" })).collect::<Vec<_>>(),
+        });
+        let expected = serde_json::to_vec(&value).unwrap();
+        assert!(expected.len() > CHUNK_SIZE * 3);
+
+        let response = StreamingJson(value.clone()).into_response();
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/json");
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(body, expected);
+        assert_eq!(serde_json::from_slice::<Value>(&body).unwrap(), value);
+    }
+}
@@ -0,0 +1,293 @@
+use axum::{
+    Json,
+    extract::{Extension, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span};
+use utoipa::ToSchema;
+
+use crate::{
+    errors::AppError,
+    explain::time::Clock,
+    explain::transaction::{TransactionExplanation, explain_transaction},
+    middleware::request_id::RequestId,
+    routes::tx::{check_transaction_policy, now},
+    services::{
+        explain::map_transaction_to_domain,
+        horizon_api::HorizonApi,
+        policy::{PolicyMode, policy},
+        privacy::{apply_privacy, privacy_enabled},
+        rpc::envelope_records,
+        submit::{SubmitConfig, SubmitOutcome, submit_and_wait, transaction_hash},
+    },
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SubmitRequest {
+    /// Signed base64 `TransactionEnvelope`.
+    pub envelope_xdr: String,
+    /// Mask addresses and redact memo contents.
+    #[serde(default)]
+    pub privacy: Option<bool>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionState {
+    /// In a ledger; `explanation.successful` says whether it succeeded.
+    Included,
+    /// Refused before reaching a ledger.
+    Rejected,
+    /// Not in a ledger yet; poll `GET /tx/{hash}`.
+    Pending,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SubmitResponse {
+    pub hash: String,
+    pub status: SubmissionState,
+    /// What happened; None while pending.
+    pub explanation: Option<TransactionExplanation>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/submit",
+    request_body = SubmitRequest,
+    responses(
+        (status = 200, description = "The transaction was included or rejected, and is explained", body = SubmitResponse),
+        (status = 202, description = "Not included before the timeout; poll GET /tx/{hash}", body = SubmitResponse),
+        (status = 400, description = "The envelope is not valid"),
+        (status = 404, description = "Submission is not enabled"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn post_submit<H: HorizonApi>(
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+    config: Option<Extension<SubmitConfig>>,
+    clock: Option<Extension<Arc<dyn Clock>>>,
+    Json(request): Json<SubmitRequest>,
+) -> Result<(StatusCode, Json<SubmitResponse>), AppError> {
+    let span = info_span!("submit_request", request_id = %request_id);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let Some(Extension(config)) = config else {
+        return Err(AppError::NotFound(
+            "Transaction submission is not enabled; set SUBMIT_ENABLED.".to_string(),
+        ));
+    };
+
+    let envelope_xdr = request.envelope_xdr.trim();
+    let Some(hash) = transaction_hash(envelope_xdr, &config.network_passphrase) else {
+        return Err(AppError::BadRequest(
+            "envelope_xdr is not a valid base64 transaction envelope.".to_string(),
+        ));
+    };
+
+    // A refused transaction must never reach the network, so the policy is
+    // checked against the envelope before it is submitted.
+    if policy().mode() != PolicyMode::Off {
+        let (tx, operations) = envelope_records(&hash, envelope_xdr).map_err(|_| {
+            AppError::BadRequest(
+                "envelope_xdr is not a valid base64 transaction envelope.".to_string(),
+            )
+        })?;
+        check_transaction_policy(&map_transaction_to_domain(tx, operations))?;
+    }
+
+    let submitted_at = now(clock.clone());
+    let outcome = submit_and_wait(&*horizon_client, envelope_xdr, &hash, config.timeout)
+        .await
+        .inspect_err(|err| {
+            error!(
+                request_id = %request_id,
+                hash = %hash,
+                error = ?err,
+                "submission_failed"
+            );
+        })?;
+
+    let (status, records) = match outcome {
+        SubmitOutcome::Included(tx, operations) => {
+            (SubmissionState::Included, Some((tx, operations)))
+        }
+        SubmitOutcome::Rejected(tx, operations) => {
+            (SubmissionState::Rejected, Some((tx, operations)))
+        }
+        SubmitOutcome::Pending => (SubmissionState::Pending, None),
+    };
+    let explanation = match records {
+        Some((tx, operations)) => {
+            let domain_tx = map_transaction_to_domain(tx, operations);
+            let mut explanation = explain_transaction(&domain_tx, None)?;
+            if let Some(context) = explanation.ledger_context.as_mut() {
+                context.protocol_version = horizon_client
+                    .fetch_ledger(context.sequence)
+                    .await
+                    .ok()
+                    .map(|ledger| ledger.protocol_version);
+            }
+            Some(
+                explanation
+                    .with_submission_time(submitted_at)
+                    .with_relative_time(now(clock)),
+            )
+        }
+        None => None,
+    };
+
+    let code = match status {
+        SubmissionState::Pending => StatusCode::ACCEPTED,
+        _ => StatusCode::OK,
+    };
+    info!(
+        request_id = %request_id,
+        hash = %hash,
+        submission_status = ?status,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = code.as_u16(),
+        "request_completed"
+    );
+    let response = SubmitResponse {
+        hash,
+        status,
+        explanation,
+    };
+    Ok((
+        code,
+        Json(apply_privacy(response, privacy_enabled(request.privacy))?),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::time::FixedClock;
+    use crate::models::ledger::Ledger;
+    use crate::services::horizon::{HorizonOperation, HorizonSubmission, HorizonTransaction};
+    use crate::services::horizon_api::FakeHorizon;
+    use crate::services::rpc::tests::payment_envelope;
+    use std::time::Duration;
+
+    const TESTNET: &str = "Test SDF Network ; September 2015";
+
+    fn config(timeout: Duration) -> Option<Extension<SubmitConfig>> {
+        Some(Extension(SubmitConfig {
+            network_passphrase: TESTNET.to_string(),
+            timeout,
+        }))
+    }
+
+    async fn submit(
+        horizon: FakeHorizon,
+        config: Option<Extension<SubmitConfig>>,
+        envelope_xdr: String,
+    ) -> Result<(StatusCode, SubmitResponse), AppError> {
+        post_submit(
+            State(Arc::new(horizon)),
+            Extension(RequestId::new()),
+            config,
+            Some(Extension(
+                Arc::new(FixedClock("2025-03-04T10:15:17Z".parse().unwrap())) as Arc<dyn Clock>,
+            )),
+            Json(SubmitRequest {
+                envelope_xdr,
+                privacy: None,
+            }),
+        )
+        .await
+        .map(|(status, Json(response))| (status, response))
+    }
+
+    #[tokio::test]
+    async fn test_included_submission_is_explained() {
+        let envelope = payment_envelope(50_000_000);
+        let hash = transaction_hash(&envelope, TESTNET).unwrap();
+        let record = HorizonTransaction {
+            hash: hash.clone(),
+            successful: true,
+            fee_charged: "100".to_string(),
+            ledger: Some(1000),
+            created_at: Some("2025-03-04T10:15:22Z".to_string()),
+            ..Default::default()
+        };
+        let horizon = FakeHorizon::new()
+            .with_ledger(Ledger {
+                sequence: 1000,
+                closed_at: "2025-03-04T10:15:22Z".to_string(),
+                protocol_version: 22,
+                successful_transaction_count: 1,
+                failed_transaction_count: 0,
+                operation_count: 1,
+                base_fee_in_stroops: 100,
+                base_reserve_in_stroops: 5_000_000,
+            })
+            .with_submission(HorizonSubmission::Included(Box::new(record.clone())))
+            .with_transaction(
+                record,
+                vec![HorizonOperation {
+                    id: "1".to_string(),
+                    transaction_hash: hash.clone(),
+                    operation_type: "payment".to_string(),
+                    from: Some("GA".to_string()),
+                    to: Some("GB".to_string()),
+                    amount: Some("5.0".to_string()),
+                    asset_type: Some("native".to_string()),
+                    ..Default::default()
+                }],
+            );
+
+        let (status, response) = submit(horizon, config(Duration::from_secs(5)), envelope)
+            .await
+            .unwrap();
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response.hash, hash);
+        assert_eq!(response.status, SubmissionState::Included);
+        let explanation = response.explanation.unwrap();
+        assert_eq!(explanation.transaction_hash, hash);
+        let context = explanation.ledger_context.unwrap();
+        assert_eq!(context.protocol_version, Some(22));
+        assert_eq!(context.explanation_url, "/ledger/1000");
+        assert_eq!(context.inclusion_latency_seconds, Some(5));
+    }
+
+    #[tokio::test]
+    async fn test_pending_submission_is_accepted() {
+        let horizon = FakeHorizon::new().with_submission(HorizonSubmission::TimedOut);
+
+        let (status, response) = submit(
+            horizon,
+            config(Duration::from_millis(50)),
+            payment_envelope(50_000_000),
+        )
+        .await
+        .unwrap();
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(response.status, SubmissionState::Pending);
+        assert!(response.explanation.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_invalid_envelopes_and_disabled_submission() {
+        let err = submit(
+            FakeHorizon::new(),
+            config(Duration::from_secs(5)),
+            "AAAA".to_string(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(_)));
+
+        let err = submit(FakeHorizon::new(), None, payment_envelope(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+}
@@ -0,0 +1,285 @@
+use axum::{
+    Json,
+    extract::{Extension, State},
+    http::StatusCode,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Instant;
+use tracing::{error, info, info_span};
+use utoipa::ToSchema;
+
+use crate::{
+    errors::{AppError, HorizonError},
+    explain::time::Clock,
+    explain::transaction::{TransactionExplanation, explain_transaction},
+    middleware::request_id::RequestId,
+    models::strkey,
+    routes::tx::now,
+    services::{
+        explain::map_transaction_to_domain,
+        faucet::Faucet,
+        horizon_api::HorizonApi,
+        policy::{Parties, policy},
+        privacy::{apply_privacy, privacy_enabled},
+    },
+};
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct FundRequest {
+    /// G... address of the account to create.
+    pub address: String,
+    /// Mask addresses in the response.
+    #[serde(default)]
+    pub privacy: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct FundResponse {
+    pub address: String,
+    /// Hash of the funding transaction.
+    pub hash: String,
+    /// The funding transaction explained; None until Horizon has indexed
+    /// it, in which case poll GET /tx/{hash}.
+    pub explanation: Option<TransactionExplanation>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/testnet/fund",
+    request_body = FundRequest,
+    responses(
+        (status = 200, description = "The account was funded, and the funding transaction is explained", body = FundResponse),
+        (status = 202, description = "The account was funded, but Horizon has not indexed the transaction yet; poll GET /tx/{hash}", body = FundResponse),
+        (status = 400, description = "The address is not valid, or the account already exists"),
+        (status = 404, description = "No friendbot is configured for this network"),
+        (status = 502, description = "Friendbot or Horizon failed")
+    )
+)]
+pub async fn post_testnet_fund<H: HorizonApi>(
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+    faucet: Option<Extension<Arc<dyn Faucet>>>,
+    clock: Option<Extension<Arc<dyn Clock>>>,
+    Json(request): Json<FundRequest>,
+) -> Result<(StatusCode, Json<FundResponse>), AppError> {
+    let span = info_span!("testnet_fund_request", request_id = %request_id);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, "incoming_request");
+
+    let Some(Extension(faucet)) = faucet else {
+        return Err(AppError::NotFound(
+            "Testnet funding is not available on this network; set FRIENDBOT_URL.".to_string(),
+        ));
+    };
+
+    let address = request.address.trim().to_string();
+    if !strkey::is_account_address(&address) {
+        return Err(AppError::BadRequest(
+            "Invalid address. Expected a G... account address.".to_string(),
+        ));
+    }
+    policy().check(&Parties::account(&address), "this account")?;
+
+    let hash = faucet.fund(&address).await.map_err(|err| {
+        let app_error = err.into_error(&address);
+        error!(
+            request_id = %request_id,
+            address = %address,
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            status = app_error.status_code().as_u16(),
+            error = ?app_error,
+            "friendbot_funding_failed"
+        );
+        app_error
+    })?;
+
+    // Friendbot answers once the transaction is in a ledger, but Horizon
+    // may not have indexed it yet.
+    let records = match horizon_client.fetch_transaction(&hash).await {
+        Ok(tx) => Some((tx, horizon_client.fetch_operations(&hash).await?)),
+        Err(HorizonError::TransactionNotFound) => None,
+        Err(err) => return Err(err.into()),
+    };
+    let explanation = match records {
+        Some((tx, operations)) => {
+            let domain_tx = map_transaction_to_domain(tx, operations);
+            let mut explanation = explain_transaction(&domain_tx, None)?;
+            if let Some(context) = explanation.ledger_context.as_mut() {
+                context.protocol_version = horizon_client
+                    .fetch_ledger(context.sequence)
+                    .await
+                    .ok()
+                    .map(|ledger| ledger.protocol_version);
+            }
+            Some(explanation.with_relative_time(now(clock)))
+        }
+        None => None,
+    };
+
+    let code = match explanation {
+        Some(_) => StatusCode::OK,
+        None => StatusCode::ACCEPTED,
+    };
+    info!(
+        request_id = %request_id,
+        address = %address,
+        hash = %hash,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = code.as_u16(),
+        "request_completed"
+    );
+    let response = FundResponse {
+        address,
+        hash,
+        explanation,
+    };
+    Ok((
+        code,
+        Json(apply_privacy(response, privacy_enabled(request.privacy))?),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::faucet::FundingError;
+    use crate::services::horizon::{HorizonOperation, HorizonTransaction};
+    use crate::services::horizon_api::FakeHorizon;
+    use async_trait::async_trait;
+
+    const ADDRESS: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    const FRIENDBOT: &str = "GAIH3ULLFQ4DGSECF2AR555KZ4KNDGEKN4AFI4SU2M7B43MGK3QJZNSR";
+    const HASH: &str = "b9d0b2292c4e09e8eb22d036171491e87b8d2086bf8b265874c8d182cb9c9020";
+
+    struct FakeFaucet(Result<String, FundingError>);
+
+    #[async_trait]
+    impl Faucet for FakeFaucet {
+        async fn fund(&self, _address: &str) -> Result<String, FundingError> {
+            self.0.clone()
+        }
+    }
+
+    fn faucet(result: Result<String, FundingError>) -> Option<Extension<Arc<dyn Faucet>>> {
+        Some(Extension(Arc::new(FakeFaucet(result)) as Arc<dyn Faucet>))
+    }
+
+    async fn fund(
+        horizon: FakeHorizon,
+        faucet: Option<Extension<Arc<dyn Faucet>>>,
+        address: &str,
+    ) -> Result<(StatusCode, FundResponse), AppError> {
+        fund_request(
+            horizon,
+            faucet,
+            FundRequest {
+                address: address.to_string(),
+                privacy: None,
+            },
+        )
+        .await
+    }
+
+    async fn fund_request(
+        horizon: FakeHorizon,
+        faucet: Option<Extension<Arc<dyn Faucet>>>,
+        request: FundRequest,
+    ) -> Result<(StatusCode, FundResponse), AppError> {
+        post_testnet_fund(
+            State(Arc::new(horizon)),
+            Extension(RequestId::new()),
+            faucet,
+            None,
+            Json(request),
+        )
+        .await
+        .map(|(status, Json(response))| (status, response))
+    }
+
+    #[tokio::test]
+    async fn test_funding_transaction_is_explained() {
+        let horizon = FakeHorizon::new().with_transaction(
+            HorizonTransaction {
+                hash: HASH.to_string(),
+                successful: true,
+                fee_charged: "100".to_string(),
+                ..Default::default()
+            },
+            vec![HorizonOperation {
+                id: "1".to_string(),
+                transaction_hash: HASH.to_string(),
+                operation_type: "create_account".to_string(),
+                funder: Some(FRIENDBOT.to_string()),
+                account: Some(ADDRESS.to_string()),
+                starting_balance: Some("10000.0000000".to_string()),
+                ..Default::default()
+            }],
+        );
+
+        let (status, response) = fund(horizon, faucet(Ok(HASH.to_string())), ADDRESS)
+            .await
+            .unwrap();
+
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(response.hash, HASH);
+        let explanation = response.explanation.unwrap();
+        assert_eq!(explanation.operations[0].operation_type, "create_account");
+        assert!(explanation.operations[0].summary.contains("10,000 XLM"));
+    }
+
+    #[tokio::test]
+    async fn test_privacy_masks_the_funded_address() {
+        let (_, response) = fund_request(
+            FakeHorizon::new(),
+            faucet(Ok(HASH.to_string())),
+            FundRequest {
+                address: ADDRESS.to_string(),
+                privacy: Some(true),
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_ne!(response.address, ADDRESS);
+        assert!(response.address.contains('…'));
+        assert_eq!(response.hash, HASH);
+    }
+
+    #[tokio::test]
+    async fn test_unindexed_funding_is_accepted() {
+        let (status, response) = fund(FakeHorizon::new(), faucet(Ok(HASH.to_string())), ADDRESS)
+            .await
+            .unwrap();
+
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert!(response.explanation.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_rejects_bad_addresses_funded_accounts_and_missing_friendbot() {
+        let err = fund(
+            FakeHorizon::new(),
+            faucet(Ok(HASH.to_string())),
+            "GNOTANADDRESS",
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(_)));
+
+        let err = fund(
+            FakeHorizon::new(),
+            faucet(Err(FundingError::AlreadyFunded)),
+            ADDRESS,
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(msg) if msg.contains("already funded")));
+
+        let err = fund(FakeHorizon::new(), None, ADDRESS).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+}
@@ -1,18 +1,41 @@
 use axum::{
     Json,
-    extract::{Extension, Path, State},
+    extract::{Extension, Path, Query, State},
 };
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::{error, info, info_span};
 use utoipa::ToSchema;
 
 use crate::{
-    errors::AppError,
-    explain::transaction::{TransactionExplanation, explain_transaction_with_ledger},
-    middleware::request_id::RequestId,
-    services::{explain::map_transaction_to_domain, horizon::HorizonClient},
+    errors::{AppError, HorizonError},
+    explain::bundle::{BundleExplanation, explain_bundle},
+    explain::compare::{TransactionComparison, compare_transactions},
+    explain::signatures::{SignatureExplanation, explain_signatures, signature_requirements},
+    explain::time::{Clock, SystemClock},
+    explain::timeline::{TimelineInput, TransactionTimeline, build_timeline},
+    explain::transaction::{
+        TransactionExplanation, explain_transaction, explain_transaction_without_operations,
+    },
+    middleware::{envelope::CacheStatus, request_id::RequestId},
+    models::transaction::Transaction,
+    routes::query::{ValidateQuery, ValidatedQuery},
+    routes::streaming::StreamingJson,
+    services::{
+        blocklist,
+        explain::{explain_fetched, map_transaction_to_domain, within_fee_budget},
+        horizon_api::HorizonApi,
+        network_lookup::NetworkLookup,
+        pending::{SubmissionStatus, SubmissionStatusSource},
+        policy::{Parties, PolicyMode, policy},
+        privacy::{PrivacyQuery, apply_privacy, privacy_enabled},
+        provider::LedgerDataProvider,
+        storage::ExplanationStore,
+        summarizer::SummarizerContext,
+        warmup::WarmCache,
+    },
 };
 
 #[derive(Serialize, ToSchema)]
@@ -22,24 +45,59 @@ pub struct TxExplanationResponse {
     pub explanation: String,
 }
 
+/// Query parameters for `GET /tx/:hash`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TxQuery {
+    pub privacy: Option<bool>,
+    pub network: Option<NetworkChoice>,
+}
+
+impl ValidateQuery for TxQuery {}
+
+/// Which networks to look for a transaction on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NetworkChoice {
+    /// This server's network, then every other one if it is not there.
+    Auto,
+}
+
 #[utoipa::path(
     get,
     path = "/tx/{hash}",
     params(
-        ("hash" = String, Path, description = "Transaction hash")
+        ("hash" = String, Path, description = "Transaction hash"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses and redact memo contents"),
+        ("network" = Option<String>, Query, description = "`auto` to report which other networks have a transaction this one does not")
     ),
     responses(
         (status = 200, description = "Transaction explanation", body = TxExplanationResponse),
-        (status = 400, description = "Invalid transaction hash"),
-        (status = 404, description = "Transaction not found"),
+        (status = 400, description = "Invalid transaction hash or query parameter"),
+        (status = 404, description = "Transaction not found, included but not yet indexed (TRANSACTION_PENDING), or with network=auto, on another network (details.found_on)"),
         (status = 500, description = "Internal server error")
     )
 )]
-pub async fn get_tx_explanation(
+#[allow(clippy::too_many_arguments)]
+pub async fn get_tx_explanation<H: HorizonApi>(
     Path(hash): Path<String>,
-    State(horizon_client): State<Arc<HorizonClient>>,
+    ValidatedQuery(options): ValidatedQuery<TxQuery>,
+    State(horizon_client): State<Arc<H>>,
     Extension(request_id): Extension<RequestId>,
-) -> Result<Json<TransactionExplanation>, AppError> {
+    store: Option<Extension<Arc<ExplanationStore>>>,
+    provider: Option<Extension<Arc<dyn LedgerDataProvider>>>,
+    submissions: Option<Extension<Arc<dyn SubmissionStatusSource>>>,
+    networks: Option<Extension<NetworkLookup>>,
+    clock: Option<Extension<Arc<dyn Clock>>>,
+    summarizer: Option<Extension<SummarizerContext>>,
+    warm: Option<Extension<Arc<WarmCache>>>,
+) -> Result<
+    (
+        Extension<CacheStatus>,
+        StreamingJson<TransactionExplanation>,
+    ),
+    AppError,
+> {
     let span = info_span!(
         "tx_explanation_request",
         request_id = %request_id,
@@ -69,42 +127,211 @@ pub async fn get_tx_explanation(
         return Err(app_error);
     }
 
-    // Fetch transaction, operations, and fee stats in parallel
-    let horizon_started_at = Instant::now();
-    let tx_future = horizon_client.fetch_transaction(&hash);
-    let ops_future = horizon_client.fetch_operations(&hash);
-    let fee_future = horizon_client.fetch_fee_stats();
+    if let Some(Extension(warm)) = &warm {
+        warm.record_lookup(&hash);
+    }
 
-    let (tx_res, ops_res, fee_stats) = tokio::join!(tx_future, ops_future, fee_future);
-    let horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64;
+    // Answer from the local index when the ingestion worker has seen this
+    // transaction. Store errors fall through to Horizon.
+    if let Some(Extension(store)) = &store {
+        match store.get_explanation(&hash) {
+            Ok(Some(explanation)) => {
+                policy().check(&Parties::of_transaction(&explanation), "this transaction")?;
+                let explanation = blocklist::flag_transaction(explanation);
+                let explanation = apply_privacy(explanation, privacy_enabled(options.privacy))?
+                    .with_relative_time(now(clock));
+                let explanation = with_generated_summary(explanation, summarizer).await;
+                info!(
+                    request_id = %request_id,
+                    hash = %hash,
+                    total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                    status = 200u16,
+                    served_from_index = true,
+                    "request_completed"
+                );
+                return Ok((Extension(CacheStatus::Hit), StreamingJson(explanation)));
+            }
+            Ok(None) => {}
+            Err(err) => {
+                error!(
+                    request_id = %request_id,
+                    hash = %hash,
+                    error = %err,
+                    "explanation_index_lookup_failed"
+                );
+            }
+        }
+    }
+
+    // Then from explanations kept warm for trending transactions.
+    if let Some(explanation) = warm.as_ref().and_then(|Extension(warm)| warm.get(&hash)) {
+        policy().check(&Parties::of_transaction(&explanation), "this transaction")?;
+        let explanation = blocklist::flag_transaction(explanation);
+        let explanation = apply_privacy(explanation, privacy_enabled(options.privacy))?
+            .with_relative_time(now(clock));
+        let explanation = with_generated_summary(explanation, summarizer).await;
+        info!(
+            request_id = %request_id,
+            hash = %hash,
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            status = 200u16,
+            served_from_warm_cache = true,
+            "request_completed"
+        );
+        return Ok((Extension(CacheStatus::Hit), StreamingJson(explanation)));
+    }
+
+    // Fetch the transaction and fee stats in parallel from the configured
+    // data source, or Horizon when none is configured.
+    let provider = data_source(provider, Arc::clone(&horizon_client));
+    let fetch_started_at = Instant::now();
+    let (tx_res, fee_stats) = tokio::join!(
+        provider.fetch_partial_transaction(&hash),
+        within_fee_budget(provider.fetch_fee_stats())
+    );
+    let fetch_duration_ms = fetch_started_at.elapsed().as_millis() as u64;
 
     info!(
         request_id = %request_id,
         hash = %hash,
-        horizon_fetch_duration_ms,
+        data_source = provider.name(),
+        fetch_duration_ms,
         fee_stats_available = fee_stats.is_some(),
-        "horizon_fetch_completed"
+        "data_source_fetch_completed"
     );
 
-    let tx = match tx_res {
+    let fetched = match tx_res {
         Ok(tx) => tx,
         Err(err) => {
-            let app_error: AppError = err.into();
+            // Tell a transaction that is on its way apart from one the
+            // network has not seen.
+            let submission = match (&err, &submissions) {
+                (HorizonError::TransactionNotFound, Some(Extension(source))) => {
+                    source.submission_status(&hash).await
+                }
+                _ => None,
+            };
+            // Then, when asked, tell one on another network apart from both.
+            let elsewhere = match (&err, &submission, options.network, &networks) {
+                (
+                    HorizonError::TransactionNotFound,
+                    None | Some(SubmissionStatus::NotIncluded { .. }),
+                    Some(NetworkChoice::Auto),
+                    Some(Extension(networks)),
+                ) => networks.locate(&hash).await,
+                _ => None,
+            };
+            let app_error = match (elsewhere, submission) {
+                (Some(app_error), _) => app_error,
+                (None, Some(status)) => status.into_error(&hash),
+                (None, None) => err.into(),
+            };
             error!(
                 request_id = %request_id,
                 hash = %hash,
-                horizon_fetch_duration_ms,
+                data_source = provider.name(),
+                fetch_duration_ms,
                 total_duration_ms = request_started_at.elapsed().as_millis() as u64,
                 status = app_error.status_code().as_u16(),
                 error = ?app_error,
-                "horizon_transaction_fetch_failed"
+                "transaction_fetch_failed"
             );
             return Err(app_error);
         }
     };
 
-    let ops = match ops_res {
-        Ok(ops) => ops,
+    let explain_started_at = Instant::now();
+    let explanation = match explain_fetched(fetched, fee_stats, &provider, horizon_client).await {
+        Ok(explanation) => explanation,
+        Err(app_error) => {
+            error!(
+                request_id = %request_id,
+                hash = %hash,
+                explain_duration_ms = explain_started_at.elapsed().as_millis() as u64,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "transaction_explain_failed"
+            );
+            return Err(app_error);
+        }
+    };
+    if let Some(Extension(warm)) = &warm {
+        warm.insert(&hash, explanation.clone());
+    }
+    policy().check(&Parties::of_transaction(&explanation), "this transaction")?;
+    let explanation = blocklist::flag_transaction(explanation);
+    let explanation = apply_privacy(explanation, privacy_enabled(options.privacy))?
+        .with_relative_time(now(clock));
+    let explanation = with_generated_summary(explanation, summarizer).await;
+    let explain_duration_ms = explain_started_at.elapsed().as_millis() as u64;
+
+    info!(
+        request_id = %request_id,
+        hash = %hash,
+        explain_duration_ms,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        warnings = explanation.warnings.len(),
+        "request_completed"
+    );
+
+    Ok((Extension(CacheStatus::Miss), StreamingJson(explanation)))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tx/{hash}/timeline",
+    params(
+        ("hash" = String, Path, description = "Transaction hash")
+    ),
+    responses(
+        (status = 200, description = "Transaction lifecycle timeline", body = TransactionTimeline),
+        (status = 400, description = "Invalid transaction hash"),
+        (status = 404, description = "Transaction not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_tx_timeline<H: HorizonApi>(
+    Path(hash): Path<String>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Json<TransactionTimeline>, AppError> {
+    let span = info_span!(
+        "tx_timeline_request",
+        request_id = %request_id,
+        hash = %hash
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, hash = %hash, "incoming_request");
+
+    if !is_valid_transaction_hash(&hash) {
+        let app_error = AppError::BadRequest(
+            "Invalid transaction hash format. Expected 64-character hexadecimal hash.".to_string(),
+        );
+        info!(
+            request_id = %request_id,
+            hash = %hash,
+            status = app_error.status_code().as_u16(),
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            error = ?app_error,
+            "request_completed"
+        );
+        return Err(app_error);
+    }
+
+    let horizon_started_at = Instant::now();
+    let (tx_res, fee_stats, average_close) = tokio::join!(
+        horizon_client.fetch_transaction(&hash),
+        within_fee_budget(horizon_client.fetch_fee_stats()),
+        within_fee_budget(horizon_client.fetch_average_ledger_close_seconds()),
+    );
+    let horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64;
+
+    let tx = match tx_res {
+        Ok(tx) => tx,
         Err(err) => {
             let app_error: AppError = err.into();
             error!(
@@ -114,47 +341,154 @@ pub async fn get_tx_explanation(
                 total_duration_ms = request_started_at.elapsed().as_millis() as u64,
                 status = app_error.status_code().as_u16(),
                 error = ?app_error,
-                "horizon_operations_fetch_failed"
+                "horizon_transaction_fetch_failed"
             );
             return Err(app_error);
         }
     };
 
-    // Capture ledger fields before tx is consumed by map_transaction_to_domain
-    let created_at = tx.created_at.clone();
-    let ledger = tx.ledger;
+    // The timeline names no parties itself, but it describes a transaction
+    // the policy may refuse to explain.
+    if policy().mode() != PolicyMode::Off {
+        let operations = horizon_client.fetch_operations(&hash).await?;
+        check_transaction_policy(&map_transaction_to_domain(tx.clone(), operations))?;
+    }
 
-    // fee_stats is Option<FeeStats> — None if Horizon /fee_stats is unavailable
-    let domain_tx = map_transaction_to_domain(tx, ops);
-    let explain_started_at = Instant::now();
+    let input = TimelineInput {
+        transaction_hash: tx.hash,
+        successful: tx.successful,
+        ledger: tx.ledger,
+        ledger_closed_at: tx.created_at,
+        valid_after: tx.valid_after,
+        valid_before: tx.valid_before,
+        fee_charged: tx.fee_charged.parse().unwrap_or(0),
+        max_fee: tx.max_fee.and_then(|f| f.parse().ok()),
+        average_ledger_close_seconds: average_close,
+    };
+    let timeline = build_timeline(&input, fee_stats.as_ref());
 
-    let explanation = match explain_transaction_with_ledger(
-        &domain_tx,
-        fee_stats.as_ref(),
-        created_at.as_deref(),
-        ledger,
-    ) {
-        Ok(explanation) => explanation,
-        Err(err) => {
+    info!(
+        request_id = %request_id,
+        hash = %hash,
+        horizon_fetch_duration_ms,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(timeline))
+}
+
+#[utoipa::path(
+    get,
+    path = "/tx/{hash}/signatures",
+    params(
+        ("hash" = String, Path, description = "Transaction hash"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses")
+    ),
+    responses(
+        (status = 200, description = "Who signed the transaction and whether thresholds were met", body = SignatureExplanation),
+        (status = 400, description = "Invalid transaction hash"),
+        (status = 404, description = "Transaction not found"),
+        (status = 502, description = "Upstream failure")
+    )
+)]
+pub async fn get_tx_signatures<H: HorizonApi>(
+    Path(hash): Path<String>,
+    Query(options): Query<PrivacyQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+) -> Result<Json<SignatureExplanation>, AppError> {
+    let span = info_span!(
+        "tx_signatures_request",
+        request_id = %request_id,
+        hash = %hash
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, hash = %hash, "incoming_request");
+
+    if !is_valid_transaction_hash(&hash) {
+        let app_error = AppError::BadRequest(
+            "Invalid transaction hash format. Expected 64-character hexadecimal hash.".to_string(),
+        );
+        info!(
+            request_id = %request_id,
+            hash = %hash,
+            status = app_error.status_code().as_u16(),
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            error = ?app_error,
+            "request_completed"
+        );
+        return Err(app_error);
+    }
+
+    let horizon_started_at = Instant::now();
+    let (tx_res, ops_res) = tokio::join!(
+        horizon_client.fetch_transaction(&hash),
+        horizon_client.fetch_operations(&hash),
+    );
+    let (tx, operations) = match (tx_res, ops_res) {
+        (Ok(tx), Ok(operations)) => (tx, operations),
+        (Err(err), _) | (_, Err(err)) => {
             let app_error: AppError = err.into();
             error!(
                 request_id = %request_id,
                 hash = %hash,
-                explain_duration_ms = explain_started_at.elapsed().as_millis() as u64,
                 total_duration_ms = request_started_at.elapsed().as_millis() as u64,
                 status = app_error.status_code().as_u16(),
                 error = ?app_error,
-                "transaction_explain_failed"
+                "horizon_transaction_fetch_failed"
             );
             return Err(app_error);
         }
     };
-    let explain_duration_ms = explain_started_at.elapsed().as_millis() as u64;
+
+    let requirements = signature_requirements(&tx, &operations);
+    let mut parties = Parties::default();
+    parties
+        .accounts
+        .extend(requirements.iter().map(|r| r.account.clone()));
+    policy().check(&parties, "this transaction")?;
+    // An account that cannot be loaded (merged, say) is reported as such
+    // rather than failing the request.
+    let mut addresses: Vec<&str> = requirements.iter().map(|r| r.account.as_str()).collect();
+    addresses.sort_unstable();
+    addresses.dedup();
+    let fetched = futures_util::future::join_all(
+        addresses
+            .iter()
+            .map(|address| horizon_client.fetch_account(address)),
+    )
+    .await;
+    let accounts = addresses
+        .iter()
+        .zip(fetched)
+        .filter_map(|(address, account)| Some((address.to_string(), account.ok()?)))
+        .collect();
+    let horizon_fetch_duration_ms = horizon_started_at.elapsed().as_millis() as u64;
+
+    let Some(explanation) = explain_signatures(&tx, &operations, &accounts) else {
+        let app_error = AppError::UpstreamFailure(
+            "Horizon did not return the transaction envelope".to_string(),
+        );
+        error!(
+            request_id = %request_id,
+            hash = %hash,
+            horizon_fetch_duration_ms,
+            status = app_error.status_code().as_u16(),
+            error = ?app_error,
+            "transaction_envelope_undecodable"
+        );
+        return Err(app_error);
+    };
+    let explanation = apply_privacy(explanation, privacy_enabled(options.privacy))?;
 
     info!(
         request_id = %request_id,
         hash = %hash,
-        explain_duration_ms,
+        horizon_fetch_duration_ms,
         total_duration_ms = request_started_at.elapsed().as_millis() as u64,
         status = 200u16,
         "request_completed"
@@ -163,6 +497,864 @@ pub async fn get_tx_explanation(
     Ok(Json(explanation))
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CompareQuery {
+    pub a: Option<String>,
+    pub b: Option<String>,
+    pub privacy: Option<bool>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/tx/compare",
+    params(
+        ("a" = String, Query, description = "First transaction hash"),
+        ("b" = String, Query, description = "Second transaction hash"),
+        ("privacy" = Option<bool>, Query, description = "Mask addresses and redact memo contents")
+    ),
+    responses(
+        (status = 200, description = "Differences between the two transactions", body = TransactionComparison),
+        (status = 400, description = "Missing or invalid transaction hash"),
+        (status = 404, description = "Transaction not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn get_tx_compare<H: HorizonApi>(
+    Query(query): Query<CompareQuery>,
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+    provider: Option<Extension<Arc<dyn LedgerDataProvider>>>,
+) -> Result<Json<TransactionComparison>, AppError> {
+    let a = query.a.unwrap_or_default();
+    let b = query.b.unwrap_or_default();
+    let span = info_span!(
+        "tx_compare_request",
+        request_id = %request_id,
+        a = %a,
+        b = %b
+    );
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, a = %a, b = %b, "incoming_request");
+
+    if let Err(app_error) = validate_compare_hashes(&a, &b) {
+        info!(
+            request_id = %request_id,
+            a = %a,
+            b = %b,
+            status = app_error.status_code().as_u16(),
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            error = ?app_error,
+            "request_completed"
+        );
+        return Err(app_error);
+    }
+
+    let provider = data_source(provider, horizon_client);
+    let fetch_started_at = Instant::now();
+    let (tx_a, tx_b) = tokio::join!(
+        provider.fetch_transaction(&a),
+        provider.fetch_transaction(&b),
+    );
+    let fetch_duration_ms = fetch_started_at.elapsed().as_millis() as u64;
+
+    let (tx_a, tx_b) = match (tx_a, tx_b) {
+        (Ok(tx_a), Ok(tx_b)) => (tx_a.transaction, tx_b.transaction),
+        (Err(err), _) | (_, Err(err)) => {
+            let app_error: AppError = err.into();
+            error!(
+                request_id = %request_id,
+                a = %a,
+                b = %b,
+                data_source = provider.name(),
+                fetch_duration_ms,
+                total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                status = app_error.status_code().as_u16(),
+                error = ?app_error,
+                "transaction_fetch_failed"
+            );
+            return Err(app_error);
+        }
+    };
+
+    check_transaction_policy(&tx_a)?;
+    check_transaction_policy(&tx_b)?;
+    let comparison = apply_privacy(
+        compare_transactions(&tx_a, &tx_b),
+        privacy_enabled(query.privacy),
+    )?;
+
+    info!(
+        request_id = %request_id,
+        a = %a,
+        b = %b,
+        fetch_duration_ms,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(comparison))
+}
+
+/// Transactions one bundle may hold.
+const MAX_BUNDLE_SIZE: usize = 10;
+
+#[derive(Debug, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct BundleRequest {
+    /// Hashes of the transactions making up the flow, 2 to 10.
+    pub hashes: Vec<String>,
+    /// Mask addresses and redact memo contents.
+    #[serde(default)]
+    pub privacy: Option<bool>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/explain/bundle",
+    request_body = BundleRequest,
+    responses(
+        (status = 200, description = "The transactions explained as one flow", body = BundleExplanation),
+        (status = 400, description = "Too few, too many, repeated, or invalid hashes"),
+        (status = 404, description = "Transaction not found"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn post_explain_bundle<H: HorizonApi>(
+    State(horizon_client): State<Arc<H>>,
+    Extension(request_id): Extension<RequestId>,
+    provider: Option<Extension<Arc<dyn LedgerDataProvider>>>,
+    Json(request): Json<BundleRequest>,
+) -> Result<Json<BundleExplanation>, AppError> {
+    let hashes = request.hashes.len();
+    let span = info_span!("explain_bundle_request", request_id = %request_id, hashes);
+    let _span_guard = span.enter();
+    let request_started_at = Instant::now();
+
+    info!(request_id = %request_id, hashes, "incoming_request");
+
+    if let Err(app_error) = validate_bundle_hashes(&request.hashes) {
+        info!(
+            request_id = %request_id,
+            hashes,
+            status = app_error.status_code().as_u16(),
+            total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+            error = ?app_error,
+            "request_completed"
+        );
+        return Err(app_error);
+    }
+
+    let provider = data_source(provider, horizon_client);
+    let fetch_started_at = Instant::now();
+    let fetched = futures_util::future::join_all(
+        request
+            .hashes
+            .iter()
+            .map(|hash| provider.fetch_transaction(hash)),
+    )
+    .await;
+    let fetch_duration_ms = fetch_started_at.elapsed().as_millis() as u64;
+
+    let mut transactions = Vec::with_capacity(fetched.len());
+    for (hash, result) in request.hashes.iter().zip(fetched) {
+        match result {
+            Ok(fetched) => transactions.push(fetched.transaction),
+            Err(err) => {
+                let app_error: AppError = err.into();
+                error!(
+                    request_id = %request_id,
+                    hash = %hash,
+                    data_source = provider.name(),
+                    fetch_duration_ms,
+                    total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+                    status = app_error.status_code().as_u16(),
+                    error = ?app_error,
+                    "transaction_fetch_failed"
+                );
+                return Err(app_error);
+            }
+        }
+    }
+
+    for tx in &transactions {
+        check_transaction_policy(tx)?;
+    }
+    let bundle = apply_privacy(
+        explain_bundle(&transactions),
+        privacy_enabled(request.privacy),
+    )?;
+
+    info!(
+        request_id = %request_id,
+        hashes,
+        fetch_duration_ms,
+        total_duration_ms = request_started_at.elapsed().as_millis() as u64,
+        status = 200u16,
+        "request_completed"
+    );
+
+    Ok(Json(bundle))
+}
+
+/// Apply the explanation policy to a transaction that is not otherwise
+/// explained, such as one side of a comparison.
+pub(crate) fn check_transaction_policy(tx: &Transaction) -> Result<(), AppError> {
+    if policy().mode() == PolicyMode::Off {
+        return Ok(());
+    }
+    // A transaction without operations cannot be explained, but its source
+    // accounts are still parties; never check it against no parties at all.
+    let explanation = explain_transaction(tx, None)
+        .unwrap_or_else(|_| explain_transaction_without_operations(tx, None, None, None));
+    policy().check(&Parties::of_transaction(&explanation), "this transaction")
+}
+
+/// The current time from the configured clock, or the system clock.
+pub(crate) fn now(clock: Option<Extension<Arc<dyn Clock>>>) -> DateTime<Utc> {
+    match clock {
+        Some(Extension(clock)) => clock.now(),
+        None => SystemClock.now(),
+    }
+}
+
+/// `explanation` with a generated summary when a summarizer is configured.
+/// Runs after privacy masking, so the summarizer only sees what the client
+/// will.
+async fn with_generated_summary(
+    explanation: TransactionExplanation,
+    summarizer: Option<Extension<SummarizerContext>>,
+) -> TransactionExplanation {
+    match summarizer {
+        Some(Extension(context)) => context.apply(explanation).await,
+        None => explanation,
+    }
+}
+
+/// The configured data source, falling back to the Horizon client in state.
+fn data_source<H: HorizonApi>(
+    provider: Option<Extension<Arc<dyn LedgerDataProvider>>>,
+    horizon_client: Arc<H>,
+) -> Arc<dyn LedgerDataProvider> {
+    match provider {
+        Some(Extension(provider)) => provider,
+        None => horizon_client,
+    }
+}
+
+fn validate_compare_hashes(a: &str, b: &str) -> Result<(), AppError> {
+    if a.is_empty() || b.is_empty() {
+        return Err(AppError::BadRequest(
+            "Both query parameters `a` and `b` must be provided.".to_string(),
+        ));
+    }
+    for (name, hash) in [("a", a), ("b", b)] {
+        if !is_valid_transaction_hash(hash) {
+            return Err(AppError::BadRequest(format!(
+                "Invalid transaction hash format for `{name}`. Expected 64-character hexadecimal hash."
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn validate_bundle_hashes(hashes: &[String]) -> Result<(), AppError> {
+    if hashes.len() < 2 || hashes.len() > MAX_BUNDLE_SIZE {
+        return Err(AppError::BadRequest(format!(
+            "A bundle takes between 2 and {MAX_BUNDLE_SIZE} transaction hashes."
+        )));
+    }
+    for (i, hash) in hashes.iter().enumerate() {
+        if !is_valid_transaction_hash(hash) {
+            return Err(AppError::BadRequest(format!(
+                "Invalid transaction hash format for `hashes[{i}]`. Expected 64-character hexadecimal hash."
+            )));
+        }
+        if hashes[..i]
+            .iter()
+            .any(|earlier| earlier.eq_ignore_ascii_case(hash))
+        {
+            return Err(AppError::BadRequest(format!(
+                "`hashes[{i}]` repeats an earlier hash."
+            )));
+        }
+    }
+    Ok(())
+}
+
 fn is_valid_transaction_hash(hash: &str) -> bool {
     hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, middleware::from_fn, routing::get};
+    use httpmock::prelude::*;
+    use serde_json::{Value, json};
+    use std::time::Duration;
+
+    use crate::explain::time::FixedClock;
+    use crate::middleware::request_id::request_id_middleware;
+    use crate::models::fee::FeeStats;
+    use crate::models::ledger::Ledger;
+    use crate::services::explain::FEE_CONTEXT_BUDGET;
+    use crate::services::horizon::{HorizonClient, HorizonOperation, HorizonTransaction};
+    use crate::services::horizon_api::FakeHorizon;
+    use crate::services::summarizer::Summarizer;
+
+    const HASH: &str = "b9d0b2292c4e09e8eb22d036171491e87b8d2086bf8b265874c8d182cb9c9020";
+
+    /// Mock Horizon answering the three /tx/:hash fetches, each after `delay`.
+    /// Fee stats answer with `fee_status` after `fee_delay`.
+    fn mock_horizon(delay: Duration, fee_status: u16, fee_delay: Duration) -> MockServer {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/transactions/{HASH}"));
+            then.status(200).delay(delay).json_body(json!({
+                "hash": HASH,
+                "successful": true,
+                "fee_charged": "100"
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/transactions/{HASH}/operations"));
+            then.status(200).delay(delay).json_body(json!({
+                "_embedded": { "records": [{
+                    "id": "1", "transaction_hash": HASH, "type": "payment",
+                    "from": "GA", "to": "GB", "amount": "5.0", "asset_type": "native"
+                }] }
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/fee_stats");
+            then.status(fee_status).delay(fee_delay).json_body(json!({
+                "last_ledger_base_fee": "100",
+                "fee_charged": { "min": "100", "max": "100", "mode": "100", "p90": "100" }
+            }));
+        });
+        server
+    }
+
+    async fn explain(server: &MockServer) -> (u16, Value, Duration) {
+        let app = Router::new()
+            .route("/tx/:hash", get(get_tx_explanation))
+            .with_state(Arc::new(HorizonClient::new(server.base_url())))
+            .layer(from_fn(request_id_middleware));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let started = Instant::now();
+        let response = reqwest::get(format!("http://{addr}/tx/{HASH}"))
+            .await
+            .unwrap();
+        let status = response.status().as_u16();
+        let body = response.json().await.unwrap();
+        (status, body, started.elapsed())
+    }
+
+    #[test]
+    fn test_validate_compare_hashes() {
+        assert!(validate_compare_hashes(HASH, HASH).is_ok());
+        assert!(matches!(
+            validate_compare_hashes("", HASH),
+            Err(AppError::BadRequest(msg)) if msg.contains("must be provided")
+        ));
+        assert!(matches!(
+            validate_compare_hashes(HASH, "nothex"),
+            Err(AppError::BadRequest(msg)) if msg.contains("`b`")
+        ));
+    }
+
+    #[test]
+    fn test_validate_bundle_hashes() {
+        let other = "a".repeat(64);
+        assert!(validate_bundle_hashes(&[HASH.to_string(), other.clone()]).is_ok());
+        assert!(matches!(
+            validate_bundle_hashes(&[HASH.to_string()]),
+            Err(AppError::BadRequest(msg)) if msg.contains("between 2 and 10")
+        ));
+        assert!(matches!(
+            validate_bundle_hashes(&[other.clone(), "nothex".to_string()]),
+            Err(AppError::BadRequest(msg)) if msg.contains("`hashes[1]`")
+        ));
+        assert!(matches!(
+            validate_bundle_hashes(&[HASH.to_string(), other, HASH.to_uppercase()]),
+            Err(AppError::BadRequest(msg)) if msg.contains("repeats")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_bundle_fetches_every_leg() {
+        let other = "a".repeat(64);
+        let leg = |hash: &str, ledger: u64, from: &str, to: &str| {
+            (
+                HorizonTransaction {
+                    hash: hash.to_string(),
+                    successful: true,
+                    fee_charged: "100".to_string(),
+                    ledger: Some(ledger),
+                    ..Default::default()
+                },
+                vec![HorizonOperation {
+                    id: ledger.to_string(),
+                    transaction_hash: hash.to_string(),
+                    operation_type: "payment".to_string(),
+                    from: Some(from.to_string()),
+                    to: Some(to.to_string()),
+                    amount: Some("5.0".to_string()),
+                    asset_type: Some("native".to_string()),
+                    ..Default::default()
+                }],
+            )
+        };
+        let (first, first_ops) = leg(&other, 7, "GA", "GB");
+        let (second, second_ops) = leg(HASH, 8, "GB", "GC");
+        let horizon = Arc::new(
+            FakeHorizon::new()
+                .with_transaction(first, first_ops)
+                .with_transaction(second, second_ops),
+        );
+        let bundle = |hashes: Vec<String>| {
+            post_explain_bundle(
+                State(Arc::clone(&horizon)),
+                Extension(RequestId::new()),
+                None,
+                Json(BundleRequest {
+                    hashes,
+                    privacy: None,
+                }),
+            )
+        };
+
+        let Json(explained) = bundle(vec![HASH.to_string(), other.clone()]).await.unwrap();
+        assert_eq!(explained.hashes, vec![other.clone(), HASH.to_string()]);
+        assert_eq!(
+            explained.summary,
+            "These 2 transactions moved XLM between 3 accounts."
+        );
+
+        let missing = "c".repeat(64);
+        let err = bundle(vec![HASH.to_string(), missing]).await.unwrap_err();
+        assert_eq!(err.status_code(), axum::http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_signatures_are_matched_against_current_signers() {
+        // The source of the rpc tests' payment envelope.
+        let source = stellar_strkey::ed25519::PublicKey([1; 32]).to_string();
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/transactions/{HASH}"));
+            then.status(200).json_body(json!({
+                "hash": HASH,
+                "successful": true,
+                "fee_charged": "100",
+                "source_account": &source,
+                "envelope_xdr": crate::services::rpc::tests::payment_envelope(10_000_000)
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/transactions/{HASH}/operations"));
+            then.status(200)
+                .json_body(json!({ "_embedded": { "records": [
+                { "id": "1", "transaction_hash": HASH, "type": "payment" }
+            ] } }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/accounts/{source}"));
+            then.status(200).json_body(json!({
+                "id": &source, "account_id": &source, "sequence": "1", "balances": [],
+                "signers": [{ "key": &source, "weight": 1 }],
+                "thresholds": { "low_threshold": 1, "med_threshold": 2, "high_threshold": 2 },
+                "flags": {}
+            }));
+        });
+
+        let Json(explained) = get_tx_signatures(
+            Path(HASH.to_string()),
+            Query(PrivacyQuery::default()),
+            State(Arc::new(HorizonClient::new(server.base_url()))),
+            Extension(RequestId::new()),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(explained.signature_count, 0);
+        let account = &explained.accounts[0];
+        assert_eq!(account.account, source);
+        assert_eq!(account.required_weight, 2);
+        assert_eq!(
+            account.outcome,
+            crate::explain::signatures::ThresholdOutcome::NotMet
+        );
+    }
+
+    async fn explain_from(horizon: FakeHorizon) -> Result<TransactionExplanation, AppError> {
+        explain_summarized(horizon, None).await
+    }
+
+    async fn explain_summarized(
+        horizon: FakeHorizon,
+        summarizer: Option<SummarizerContext>,
+    ) -> Result<TransactionExplanation, AppError> {
+        get_tx_explanation(
+            Path(HASH.to_string()),
+            ValidatedQuery(TxQuery::default()),
+            State(Arc::new(horizon)),
+            Extension(RequestId::new()),
+            None,
+            None,
+            None,
+            None,
+            Some(Extension(
+                Arc::new(FixedClock("2024-01-15T17:32:00Z".parse().unwrap())) as Arc<dyn Clock>,
+            )),
+            summarizer.map(Extension),
+            None,
+        )
+        .await
+        .map(|(Extension(cache), StreamingJson(explanation))| {
+            assert_eq!(cache, CacheStatus::Miss);
+            explanation
+        })
+    }
+
+    #[tokio::test]
+    async fn test_explains_with_fee_context() {
+        let transaction = HorizonTransaction {
+            hash: HASH.to_string(),
+            successful: true,
+            fee_charged: "100".to_string(),
+            created_at: Some("2024-01-15T14:32:00Z".to_string()),
+            ledger: Some(500),
+            ..Default::default()
+        };
+        let payment = HorizonOperation {
+            id: "1".to_string(),
+            transaction_hash: HASH.to_string(),
+            operation_type: "payment".to_string(),
+            from: Some("GA".to_string()),
+            to: Some("GB".to_string()),
+            amount: Some("5.0".to_string()),
+            asset_type: Some("native".to_string()),
+            ..Default::default()
+        };
+        let horizon = FakeHorizon::new()
+            .with_transaction(transaction, vec![payment])
+            .with_fee_stats(FeeStats::new(100, 100, 1000, 100, 250))
+            .with_ledger(Ledger {
+                sequence: 500,
+                closed_at: "2024-01-15T14:32:00Z".to_string(),
+                protocol_version: 21,
+                successful_transaction_count: 1,
+                failed_transaction_count: 0,
+                operation_count: 1,
+                base_fee_in_stroops: 100,
+                base_reserve_in_stroops: 5_000_000,
+            });
+
+        let explanation = explain_from(horizon).await.unwrap();
+
+        assert_eq!(explanation.transaction_hash, HASH);
+        assert_eq!(explanation.operations.len(), 1);
+        assert!(explanation.fee_explanation.is_some());
+        assert!(explanation.warnings.is_empty());
+        assert_eq!(
+            explanation.created_at.as_deref(),
+            Some("2024-01-15T14:32:00Z")
+        );
+        assert_eq!(
+            explanation.created_at_relative.as_deref(),
+            Some("3 hours ago")
+        );
+        let context = explanation.ledger_context.unwrap();
+        assert_eq!(context.protocol_version, Some(21));
+        assert_eq!(context.explanation_url, "/ledger/500");
+    }
+
+    struct Echo;
+
+    #[async_trait::async_trait]
+    impl Summarizer for Echo {
+        fn name(&self) -> &'static str {
+            "echo"
+        }
+
+        async fn summarize(&self, explanation: &TransactionExplanation) -> Result<String, String> {
+            Ok(format!("In short: {}", explanation.summary))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generated_summary_sits_beside_the_deterministic_one() {
+        let transaction = HorizonTransaction {
+            hash: HASH.to_string(),
+            successful: true,
+            fee_charged: "100".to_string(),
+            ..Default::default()
+        };
+        let payment = HorizonOperation {
+            id: "1".to_string(),
+            transaction_hash: HASH.to_string(),
+            operation_type: "payment".to_string(),
+            from: Some("GA".to_string()),
+            to: Some("GB".to_string()),
+            amount: Some("5.0".to_string()),
+            asset_type: Some("native".to_string()),
+            ..Default::default()
+        };
+        let horizon = FakeHorizon::new().with_transaction(transaction, vec![payment]);
+        let context = SummarizerContext {
+            summarizer: Arc::new(Echo),
+            budget: Duration::from_secs(1),
+        };
+
+        let plain = explain_from(horizon.clone()).await.unwrap();
+        let summarized = explain_summarized(horizon, Some(context)).await.unwrap();
+
+        assert!(plain.generated_summary.is_none());
+        assert_eq!(summarized.summary, plain.summary);
+        assert_eq!(
+            summarized.generated_summary,
+            Some(format!("In short: {}", plain.summary))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_transaction_is_not_found() {
+        let err = explain_from(FakeHorizon::new()).await.unwrap_err();
+
+        assert_eq!(err.status_code(), axum::http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_trending_transactions_are_answered_warm() {
+        use crate::services::transaction_cache::Network;
+
+        let transaction = HorizonTransaction {
+            hash: HASH.to_string(),
+            successful: true,
+            fee_charged: "100".to_string(),
+            ..Default::default()
+        };
+        let payment = HorizonOperation {
+            id: "1".to_string(),
+            transaction_hash: HASH.to_string(),
+            operation_type: "payment".to_string(),
+            from: Some("GA".to_string()),
+            to: Some("GB".to_string()),
+            amount: Some("5.0".to_string()),
+            asset_type: Some("native".to_string()),
+            ..Default::default()
+        };
+        let warm = Arc::new(WarmCache::new(Network::Testnet, Duration::from_secs(60)));
+        let explain = |horizon: FakeHorizon| {
+            get_tx_explanation(
+                Path(HASH.to_string()),
+                ValidatedQuery(TxQuery::default()),
+                State(Arc::new(horizon)),
+                Extension(RequestId::new()),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(Extension(Arc::clone(&warm))),
+            )
+        };
+
+        let (Extension(first), _) = explain(
+            FakeHorizon::new()
+                .with_fee_stats(FeeStats::default_network_fees())
+                .with_transaction(transaction, vec![payment]),
+        )
+        .await
+        .unwrap();
+        // Horizon no longer has it, but the warm copy answers.
+        let (Extension(second), StreamingJson(explanation)) =
+            explain(FakeHorizon::new()).await.unwrap();
+
+        assert_eq!(first, CacheStatus::Miss);
+        assert_eq!(second, CacheStatus::Hit);
+        assert_eq!(explanation.transaction_hash, HASH);
+        assert_eq!(warm.trending(1), vec![HASH.to_string()]);
+    }
+
+    struct FixedStatus(SubmissionStatus);
+
+    #[async_trait::async_trait]
+    impl SubmissionStatusSource for FixedStatus {
+        async fn submission_status(&self, _hash: &str) -> Option<SubmissionStatus> {
+            Some(self.0.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_missing_transaction_reports_submission_status() {
+        let explain = |status| {
+            get_tx_explanation(
+                Path(HASH.to_string()),
+                ValidatedQuery(TxQuery::default()),
+                State(Arc::new(FakeHorizon::new())),
+                Extension(RequestId::new()),
+                None,
+                None,
+                Some(Extension(
+                    Arc::new(FixedStatus(status)) as Arc<dyn SubmissionStatusSource>
+                )),
+                None,
+                None,
+                None,
+                None,
+            )
+        };
+
+        let err = explain(SubmissionStatus::Included { ledger: Some(7) })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            AppError::TransactionPending {
+                ledger: Some(7),
+                ..
+            }
+        ));
+
+        let err = explain(SubmissionStatus::NotIncluded {
+            latest_ledger: Some(9),
+            oldest_ledger: Some(1),
+        })
+        .await
+        .unwrap_err();
+        assert_eq!(err.code(), crate::errors::ErrorCode::NotFound);
+        assert!(matches!(err, AppError::TransactionNotIncluded { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_auto_network_reports_where_the_transaction_is() {
+        let testnet = FakeHorizon::new().with_transaction(
+            HorizonTransaction {
+                hash: HASH.to_string(),
+                successful: true,
+                fee_charged: "100".to_string(),
+                ..Default::default()
+            },
+            vec![],
+        );
+        let networks = NetworkLookup::new("public")
+            .with_network("testnet", Arc::new(testnet) as Arc<dyn LedgerDataProvider>);
+        let explain = |network| {
+            get_tx_explanation(
+                Path(HASH.to_string()),
+                ValidatedQuery(TxQuery {
+                    privacy: None,
+                    network,
+                }),
+                State(Arc::new(FakeHorizon::new())),
+                Extension(RequestId::new()),
+                None,
+                None,
+                None,
+                Some(Extension(networks.clone())),
+                None,
+                None,
+                None,
+            )
+        };
+
+        let err = explain(Some(NetworkChoice::Auto)).await.unwrap_err();
+        assert_eq!(err.code(), crate::errors::ErrorCode::NotFound);
+        assert!(matches!(
+            err,
+            AppError::TransactionOnOtherNetwork { ref found_on, .. } if found_on == &["testnet"]
+        ));
+
+        // Other networks are only asked about when the caller opts in.
+        let err = explain(None).await.unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+
+    #[tokio::test]
+    async fn test_fetches_run_concurrently() {
+        let delay = Duration::from_millis(300);
+        let server = mock_horizon(delay, 200, delay);
+
+        let (status, body, elapsed) = explain(&server).await;
+
+        assert_eq!(status, 200);
+        assert!(body["fee_explanation"].is_string(), "{body}");
+        // Three sequential fetches would take 900ms.
+        assert!(elapsed < Duration::from_millis(700), "took {elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn test_failed_fee_stats_still_explains() {
+        let server = mock_horizon(Duration::ZERO, 500, Duration::ZERO);
+
+        let (status, body, _) = explain(&server).await;
+
+        assert_eq!(status, 200);
+        assert_eq!(body["transaction_hash"], HASH);
+        assert_eq!(body["warnings"][0]["code"], "FEE_STATS_UNAVAILABLE");
+    }
+
+    #[tokio::test]
+    async fn test_failed_operations_still_explain_the_transaction() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/transactions/{HASH}"));
+            then.status(200).json_body(json!({
+                "hash": HASH,
+                "successful": true,
+                "fee_charged": "100",
+                "memo_type": "text",
+                "memo": "rent"
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/transactions/{HASH}/operations"));
+            then.status(500);
+        });
+
+        let (status, body, _) = explain(&server).await;
+
+        assert_eq!(status, 200, "{body}");
+        assert_eq!(body["operations"], json!([]));
+        assert!(body["memo_explanation"].is_string(), "{body}");
+        assert!(
+            body["summary"]
+                .as_str()
+                .unwrap()
+                .contains("operations could not be loaded")
+        );
+        let codes: Vec<&str> = body["warnings"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|w| w["code"].as_str().unwrap())
+            .collect();
+        assert_eq!(codes, ["OPERATIONS_UNAVAILABLE", "FEE_STATS_UNAVAILABLE"]);
+    }
+
+    #[tokio::test]
+    async fn test_slow_fee_stats_do_not_hold_the_response() {
+        let server = mock_horizon(Duration::ZERO, 200, Duration::from_secs(5));
+
+        let (status, _, elapsed) = explain(&server).await;
+
+        assert_eq!(status, 200);
+        assert!(
+            elapsed < FEE_CONTEXT_BUDGET + Duration::from_secs(1),
+            "took {elapsed:?}"
+        );
+    }
+}
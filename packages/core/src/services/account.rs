@@ -0,0 +1,953 @@
+//! Everything `/account/:address` explains, fetched concurrently.
+//!
+//! The account itself (followed by its stellar.toml when it has a home
+//! domain), its latest operations, and optionally its open offers are
+//! independent Horizon reads, so they run side by side. Only the account is
+//! required: operations and offers that cannot be fetched are left out.
+//!
+//! With `anomalies_as_of` set, the account's last 90 days of transactions
+//! are read as well, and its latest few are compared against the rest.
+//!
+//! Statements and exports page back through the account's transactions to
+//! the start of the requested range, then fetch the listed transactions'
+//! operations a few at a time to describe each one and total what it moved.
+//! Syncs read one page forward from the client's cursor the same way.
+
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tokio::task::JoinSet;
+use tracing::warn;
+
+use crate::errors::HorizonError;
+use crate::explain::account::{
+    AccountExplanation, RecentOperationExplanation, explain_account_with_toml,
+    explain_recent_operation,
+};
+use crate::explain::anomaly::{AccountAnomaly, Baseline};
+use crate::explain::changes::{AccountChanges, build_changes};
+use crate::explain::flags_history::{
+    AccountFlagsHistory, explain_flags_history, explain_settings_change,
+};
+use crate::explain::offer::{AccountOffersExplanation, explain_account_offers, explain_offer};
+use crate::explain::payment_graph::{PaymentGraph, build_payment_graph, payment_flow};
+use crate::explain::related_accounts::{RelatedAccountsExplanation, explain_related_accounts};
+use crate::explain::signer::{
+    PreAuthStatus, SignerExplanation, SignerKind, explain_signer, signer_hash, signer_kind,
+};
+use crate::explain::statement::{AccountStatement, StatementEntry, build_statement};
+use crate::explain::time::parse_timestamp;
+use crate::models::account::Account;
+use crate::models::asset::TomlIdentity;
+use crate::models::operation::Operation;
+use crate::services::explain::map_transaction_to_domain;
+use crate::services::horizon::{HorizonAccountTransaction, HorizonOperation, HorizonTransaction};
+use crate::services::horizon_api::HorizonApi;
+use crate::services::issuer_labels::domain_url;
+use crate::services::operation_cache::OperationCache;
+
+/// Offers listed on an account explanation when `include_offers` is set.
+pub const ACCOUNT_OFFERS_LIMIT: u32 = 20;
+
+/// How much of an account's history one read covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HistoryLimits {
+    /// Most transactions listed; the newest in the range are kept.
+    pub max_transactions: usize,
+    /// Pages read looking for the start of the range before giving up.
+    pub max_pages: usize,
+}
+
+/// What one statement lists.
+pub const STATEMENT_LIMITS: HistoryLimits = HistoryLimits {
+    max_transactions: 100,
+    max_pages: 10,
+};
+
+/// History read to judge recent transactions by.
+const ANOMALY_LIMITS: HistoryLimits = HistoryLimits {
+    max_transactions: 100,
+    max_pages: 2,
+};
+
+/// Days of history an anomaly baseline covers.
+const ANOMALY_BASELINE_DAYS: u64 = 90;
+
+/// Latest transactions checked for anomalies; older ones form the baseline.
+const ANOMALY_CHECKED_TRANSACTIONS: usize = 5;
+
+/// Transactions requested per Horizon page while reading history.
+const HISTORY_PAGE_SIZE: u32 = 200;
+
+/// Operation pages one flags history request reads before handing back a
+/// cursor to continue from.
+const FLAGS_HISTORY_MAX_PAGES: usize = 5;
+
+/// Latest operations read per account while building a payment graph.
+const GRAPH_OPERATIONS_PER_ACCOUNT: u32 = 50;
+
+/// Most accounts one payment graph shows.
+pub const MAX_GRAPH_NODES: usize = 50;
+
+/// Pre-authorized transactions looked up per account explanation.
+const PREAUTH_LOOKUPS: usize = 5;
+
+/// Accounts asked for per signer or sponsor search.
+pub const RELATED_ACCOUNTS_PER_SEARCH: u32 = 50;
+
+/// Pages of history read back looking for the transaction before a sync
+/// timestamp before giving up.
+const SYNC_LOOKBACK_PAGES: usize = 5;
+
+/// Where a sync resumes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncPoint {
+    /// After the transaction with this paging token.
+    Cursor(String),
+    /// From the first transaction at or after this moment.
+    Time(DateTime<Utc>),
+}
+
+/// Operation lookups running at once while reading history.
+const OPERATION_LOOKUPS_IN_FLIGHT: usize = 20;
+
+/// A page of an account's transactions, read by [`AccountService::history_page`].
+#[derive(Debug, Clone)]
+pub struct HistoryPage {
+    /// Each transaction with the cursor that resumes after it.
+    pub entries: Vec<(Option<String>, StatementEntry)>,
+    pub next_cursor: Option<String>,
+}
+
+/// What to include alongside the account itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountOptions {
+    /// How many of the latest operations to explain; 0 skips the fetch.
+    pub recent_operations: u32,
+    pub include_offers: bool,
+    /// Check the latest transactions up to this day for unusual activity;
+    /// `None` skips the history read.
+    pub anomalies_as_of: Option<NaiveDate>,
+}
+
+impl Default for AccountOptions {
+    fn default() -> Self {
+        Self {
+            recent_operations: 5,
+            include_offers: false,
+            anomalies_as_of: None,
+        }
+    }
+}
+
+/// An explained account with its recent activity.
+#[derive(Debug)]
+pub struct AccountReport {
+    pub account: Account,
+    pub explanation: AccountExplanation,
+    pub recent_operations: Vec<RecentOperationExplanation>,
+    /// `None` when offers were not requested or could not be fetched.
+    pub offers: Option<AccountOffersExplanation>,
+    /// Newest first; `None` when not requested or the history could not be read.
+    pub anomalies: Option<Vec<AccountAnomaly>>,
+    pub signers: Vec<SignerExplanation>,
+}
+
+pub struct AccountService<H> {
+    client: Arc<H>,
+    operation_cache: Option<Arc<OperationCache>>,
+}
+
+impl<H: HorizonApi> AccountService<H> {
+    pub fn new(client: Arc<H>) -> Self {
+        Self {
+            client,
+            operation_cache: None,
+        }
+    }
+
+    /// Reuse operation explanations kept in `cache`.
+    pub fn with_operation_cache(mut self, cache: Option<Arc<OperationCache>>) -> Self {
+        self.operation_cache = cache;
+        self
+    }
+
+    /// Fetch and explain an account. Fails only if the account itself cannot
+    /// be fetched.
+    pub async fn explain(
+        &self,
+        address: &str,
+        options: &AccountOptions,
+    ) -> Result<AccountReport, HorizonError> {
+        let (account, operations, offers, anomalies) = tokio::join!(
+            self.account_with_toml(address),
+            self.recent_operations(address, options.recent_operations),
+            async {
+                if !options.include_offers {
+                    return None;
+                }
+                self.offers(address, ACCOUNT_OFFERS_LIMIT)
+                    .await
+                    .inspect_err(|err| warn!(address = %address, error = ?err, "account_offers_fetch_failed"))
+                    .ok()
+            },
+            async {
+                let as_of = options.anomalies_as_of?;
+                self.anomalies(address, as_of)
+                    .await
+                    .inspect_err(|err| warn!(address = %address, error = ?err, "account_anomalies_fetch_failed"))
+                    .ok()
+            },
+        );
+        let (account, toml) = account?;
+        let signers = self.signers(&account).await;
+
+        let recent_operations = operations
+            .into_iter()
+            .map(|op| match &self.operation_cache {
+                Some(cache) => cache.recent_operation(op),
+                None => {
+                    let hash = op.transaction_hash.clone();
+                    let created_at = op.created_at.clone();
+                    explain_recent_operation(hash, created_at, &Operation::from(op))
+                }
+            })
+            .collect();
+
+        Ok(AccountReport {
+            explanation: explain_account_with_toml(&account, toml.as_ref()),
+            account,
+            recent_operations,
+            offers,
+            anomalies,
+            signers,
+        })
+    }
+
+    /// The account's signers explained, with the transactions its
+    /// pre-authorized transaction signers allow looked up by hash.
+    async fn signers(&self, account: &Account) -> Vec<SignerExplanation> {
+        let preauth_hashes: Vec<String> = account
+            .signers
+            .iter()
+            .filter(|signer| signer_kind(&signer.key) == SignerKind::PreAuthTx)
+            .filter_map(|signer| signer_hash(&signer.key))
+            .take(PREAUTH_LOOKUPS)
+            .collect();
+        let statuses: HashMap<String, PreAuthStatus> =
+            futures_util::future::join_all(preauth_hashes.into_iter().map(|hash| async move {
+                let status = match self.client.fetch_transaction(&hash).await {
+                    Ok(tx) => PreAuthStatus::Applied { ledger: tx.ledger },
+                    Err(HorizonError::TransactionNotFound) => PreAuthStatus::Pending,
+                    Err(err) => {
+                        warn!(hash = %hash, error = ?err, "preauth_lookup_failed");
+                        PreAuthStatus::Unknown
+                    }
+                };
+                (hash, status)
+            }))
+            .await
+            .into_iter()
+            .collect();
+        account
+            .signers
+            .iter()
+            .map(|signer| {
+                let status = signer_hash(&signer.key).and_then(|hash| statuses.get(&hash).copied());
+                explain_signer(&account.account_id, signer, status)
+            })
+            .collect()
+    }
+
+    /// Anomalies in the account's latest transactions up to `as_of`, judged
+    /// against its other transactions in the preceding 90 days.
+    pub async fn anomalies(
+        &self,
+        address: &str,
+        as_of: NaiveDate,
+    ) -> Result<Vec<AccountAnomaly>, HorizonError> {
+        let from = as_of
+            .checked_sub_days(Days::new(ANOMALY_BASELINE_DAYS))
+            .unwrap_or(as_of);
+        let (entries, _) = self.history(address, from, as_of, ANOMALY_LIMITS).await?;
+
+        let split = entries.len().saturating_sub(ANOMALY_CHECKED_TRANSACTIONS);
+        let (past, recent) = entries.split_at(split);
+        let baseline = Baseline::from_history(address, past);
+        Ok(recent
+            .iter()
+            .rev()
+            .flat_map(|entry| baseline.anomalies(address, entry))
+            .collect())
+    }
+
+    /// Open offers with how much of each has been filled.
+    ///
+    /// Fill progress is best-effort: an offer whose trades cannot be fetched
+    /// is still listed, described by its remaining amount.
+    pub async fn offers(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<AccountOffersExplanation, HorizonError> {
+        let offers = self.client.fetch_account_offers(address, limit).await?;
+
+        let mut fills = JoinSet::new();
+        for (index, offer) in offers.iter().enumerate() {
+            let client = Arc::clone(&self.client);
+            let offer_id = offer.id;
+            fills.spawn(async move { (index, client.fetch_offer_amount_sold(offer_id).await) });
+        }
+        let mut amounts_sold: Vec<Option<String>> = vec![None; offers.len()];
+        while let Some(joined) = fills.join_next().await {
+            match joined {
+                Ok((index, Ok(sold))) => amounts_sold[index] = Some(sold),
+                Ok((index, Err(err))) => warn!(
+                    offer_id = offers[index].id,
+                    error = ?err,
+                    "offer_trades_fetch_failed"
+                ),
+                Err(err) => warn!(error = %err, "offer_trades_task_failed"),
+            }
+        }
+
+        let explanations = offers
+            .iter()
+            .zip(&amounts_sold)
+            .map(|(offer, sold)| explain_offer(offer, sold.as_deref()))
+            .collect();
+        Ok(explain_account_offers(address, explanations))
+    }
+
+    /// The account's balances and its transactions from the start of `from`
+    /// to the end of `to`, in UTC.
+    ///
+    /// Fails only if the account or its transaction list cannot be fetched; a
+    /// transaction whose operations cannot be fetched is listed without them.
+    pub async fn statement(
+        &self,
+        address: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> Result<AccountStatement, HorizonError> {
+        let (account, history) = tokio::join!(
+            self.client.fetch_account(address),
+            self.history(address, from, to, STATEMENT_LIMITS),
+        );
+        let account = account?;
+        let (entries, truncated) = history?;
+
+        Ok(build_statement(
+            address,
+            &from.to_string(),
+            &to.to_string(),
+            &account,
+            &entries,
+            truncated,
+        ))
+    }
+
+    /// The account's transactions from the start of `from` to the end of
+    /// `to`, oldest first, each with its operations when they could be
+    /// fetched, and whether any in the range were left out by `limits`.
+    pub async fn history(
+        &self,
+        address: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        limits: HistoryLimits,
+    ) -> Result<(Vec<StatementEntry>, bool), HorizonError> {
+        let (records, truncated) = self.transactions_between(address, from, to, limits).await?;
+        // Listed newest first; statements and exports read oldest first.
+        let mut entries = self.with_operations(records).await;
+        entries.reverse();
+        Ok((entries, truncated))
+    }
+
+    /// Up to `limit` of the account's transactions after `since`, oldest
+    /// first, explained, with a cursor for the next sync. `None` when `since`
+    /// is a time further back than [`SYNC_LOOKBACK_PAGES`] pages of history.
+    pub async fn changes(
+        &self,
+        address: &str,
+        since: &SyncPoint,
+        limit: u32,
+    ) -> Result<Option<AccountChanges>, HorizonError> {
+        let start = match since {
+            SyncPoint::Cursor(cursor) => Some(cursor.clone()),
+            SyncPoint::Time(at) => match self.cursor_before(address, *at).await? {
+                Some(start) => start,
+                None => return Ok(None),
+            },
+        };
+        // One extra record tells whether more follow.
+        let (mut records, _, _) = self
+            .client
+            .fetch_account_transactions(address, limit + 1, start.as_deref(), "asc")
+            .await?;
+        let has_more = records.len() > limit as usize;
+        records.truncate(limit as usize);
+        let cursors: Vec<Option<String>> =
+            records.iter().map(|tx| tx.paging_token.clone()).collect();
+        let entries: Vec<_> = cursors
+            .into_iter()
+            .zip(self.with_operations(records).await)
+            .collect();
+        Ok(Some(build_changes(address, &entries, start, has_more)))
+    }
+
+    /// The paging token of the account's newest transaction before `at`:
+    /// `Some(None)` when none is, `None` when the history is too long to
+    /// tell within [`SYNC_LOOKBACK_PAGES`] pages.
+    async fn cursor_before(
+        &self,
+        address: &str,
+        at: DateTime<Utc>,
+    ) -> Result<Option<Option<String>>, HorizonError> {
+        let mut cursor: Option<String> = None;
+        for _ in 0..SYNC_LOOKBACK_PAGES {
+            let (records, next_cursor, _) = self
+                .client
+                .fetch_account_transactions(address, HISTORY_PAGE_SIZE, cursor.as_deref(), "desc")
+                .await?;
+            let last_page = records.len() < HISTORY_PAGE_SIZE as usize;
+            let before = records.into_iter().find(|tx| {
+                parse_timestamp(&tx.created_at).is_some_and(|created_at| created_at < at)
+            });
+            if let Some(tx) = before {
+                return Ok(Some(tx.paging_token));
+            }
+            match next_cursor {
+                Some(next) if !last_page => cursor = Some(next),
+                _ => return Ok(Some(None)),
+            }
+        }
+        Ok(None)
+    }
+
+    /// One page of the account's transactions in `order`, starting after
+    /// `cursor`, each with its operations and the cursor that resumes after
+    /// it. The cursor for the next page is `None` once the history ends.
+    pub async fn history_page(
+        &self,
+        address: &str,
+        cursor: Option<&str>,
+        order: &str,
+    ) -> Result<HistoryPage, HorizonError> {
+        let (records, next_cursor, _) = self
+            .client
+            .fetch_account_transactions(address, HISTORY_PAGE_SIZE, cursor, order)
+            .await?;
+        let last_page = records.len() < HISTORY_PAGE_SIZE as usize;
+        let cursors: Vec<Option<String>> =
+            records.iter().map(|tx| tx.paging_token.clone()).collect();
+        let entries = self.with_operations(records).await;
+        Ok(HistoryPage {
+            entries: cursors.into_iter().zip(entries).collect(),
+            next_cursor: next_cursor.filter(|_| !last_page),
+        })
+    }
+
+    /// `records` in the same order, each with its operations when they
+    /// could be fetched.
+    async fn with_operations(
+        &self,
+        records: Vec<HorizonAccountTransaction>,
+    ) -> Vec<StatementEntry> {
+        let mut operations: Vec<Vec<HorizonOperation>> = vec![Vec::new(); records.len()];
+        let mut lookups = JoinSet::new();
+        let mut queued = records.iter().enumerate();
+        loop {
+            while lookups.len() < OPERATION_LOOKUPS_IN_FLIGHT {
+                let Some((index, tx)) = queued.next() else {
+                    break;
+                };
+                let client = Arc::clone(&self.client);
+                let hash = tx.hash.clone();
+                lookups.spawn(async move { (index, client.fetch_operations(&hash).await) });
+            }
+            let Some(joined) = lookups.join_next().await else {
+                break;
+            };
+            match joined {
+                Ok((index, Ok(ops))) => operations[index] = ops,
+                Ok((index, Err(err))) => warn!(
+                    hash = %records[index].hash,
+                    error = ?err,
+                    "history_operations_fetch_failed"
+                ),
+                Err(err) => warn!(error = %err, "history_operations_task_failed"),
+            }
+        }
+
+        records
+            .into_iter()
+            .zip(operations)
+            .map(|(tx, ops)| StatementEntry {
+                created_at: tx.created_at.clone(),
+                operation_count: tx.operation_count,
+                transaction: map_transaction_to_domain(
+                    HorizonTransaction {
+                        hash: tx.hash,
+                        successful: tx.successful,
+                        source_account: tx.source_account,
+                        memo_type: tx.memo_type,
+                        memo: tx.memo,
+                        memo_bytes: tx.memo_bytes,
+                        created_at: Some(tx.created_at),
+                        ..Default::default()
+                    },
+                    ops,
+                ),
+            })
+            .collect()
+    }
+
+    /// Transactions created in the range, newest first, and whether any in
+    /// the range were left out by the caps.
+    async fn transactions_between(
+        &self,
+        address: &str,
+        from: NaiveDate,
+        to: NaiveDate,
+        limits: HistoryLimits,
+    ) -> Result<(Vec<HorizonAccountTransaction>, bool), HorizonError> {
+        let start = from.and_time(NaiveTime::MIN).and_utc();
+        let end = to
+            .checked_add_days(Days::new(1))
+            .unwrap_or(to)
+            .and_time(NaiveTime::MIN)
+            .and_utc();
+
+        let mut listed = Vec::new();
+        let mut cursor: Option<String> = None;
+        for _ in 0..limits.max_pages {
+            let (records, next_cursor, _) = self
+                .client
+                .fetch_account_transactions(address, HISTORY_PAGE_SIZE, cursor.as_deref(), "desc")
+                .await?;
+            let last_page = records.len() < HISTORY_PAGE_SIZE as usize;
+            for tx in records {
+                let Some(created_at) = parse_timestamp(&tx.created_at) else {
+                    continue;
+                };
+                if created_at < start {
+                    return Ok((listed, false));
+                }
+                if created_at >= end {
+                    continue;
+                }
+                if listed.len() == limits.max_transactions {
+                    return Ok((listed, true));
+                }
+                listed.push(tx);
+            }
+            match next_cursor {
+                Some(next) if !last_page => cursor = Some(next),
+                _ => return Ok((listed, false)),
+            }
+        }
+        // Still inside the range after the last page read.
+        Ok((listed, true))
+    }
+
+    /// Up to `limit` changes to the account's flags, thresholds, and signers,
+    /// scanning its operations in `order` from `cursor`. Stops after
+    /// [`FLAGS_HISTORY_MAX_PAGES`] pages; `next_cursor` continues the scan.
+    pub async fn flags_history(
+        &self,
+        address: &str,
+        limit: usize,
+        cursor: Option<&str>,
+        order: &str,
+    ) -> Result<AccountFlagsHistory, HorizonError> {
+        let mut changes = Vec::new();
+        let mut scanned = 0;
+        let mut cursor = cursor.map(str::to_string);
+        for _ in 0..FLAGS_HISTORY_MAX_PAGES {
+            let (records, next_cursor, _) = self
+                .client
+                .fetch_account_operations_page(address, HISTORY_PAGE_SIZE, cursor.as_deref(), order)
+                .await?;
+            let last_page = records.len() < HISTORY_PAGE_SIZE as usize;
+            for op in records {
+                scanned += 1;
+                let change = match &self.operation_cache {
+                    Some(cache) => cache.settings_change(address, &op),
+                    None => explain_settings_change(address, &op),
+                };
+                if let Some(change) = change {
+                    changes.push(change);
+                    if changes.len() == limit {
+                        // An operation's id is its paging token.
+                        return Ok(explain_flags_history(
+                            address,
+                            changes,
+                            scanned,
+                            Some(op.id),
+                        ));
+                    }
+                }
+            }
+            match next_cursor {
+                Some(next) if !last_page => cursor = Some(next),
+                _ => return Ok(explain_flags_history(address, changes, scanned, None)),
+            }
+        }
+        Ok(explain_flags_history(address, changes, scanned, cursor))
+    }
+
+    /// Payments between the account and its counterparties, then theirs,
+    /// out to `depth` hops, from each account's latest operations.
+    ///
+    /// Fails only if the account's own operations cannot be fetched. Other
+    /// accounts whose operations cannot be read are shown without their own
+    /// payments, and the graph stops growing at [`MAX_GRAPH_NODES`].
+    pub async fn payment_graph(
+        &self,
+        address: &str,
+        depth: u32,
+    ) -> Result<PaymentGraph, HorizonError> {
+        let mut nodes: HashMap<String, u32> = HashMap::from([(address.to_string(), 0)]);
+        let mut seen_operations = HashSet::new();
+        let mut flows = Vec::new();
+        let mut truncated = false;
+        let mut frontier = vec![address.to_string()];
+
+        for hop in 0..depth {
+            let mut reads = JoinSet::new();
+            for account in frontier.drain(..) {
+                let client = Arc::clone(&self.client);
+                reads.spawn(async move {
+                    let operations = client
+                        .fetch_account_operations(&account, GRAPH_OPERATIONS_PER_ACCOUNT)
+                        .await;
+                    (account, operations)
+                });
+            }
+            let mut pages = Vec::new();
+            while let Some(joined) = reads.join_next().await {
+                match joined {
+                    Ok((account, Ok(operations))) => pages.push((account, operations)),
+                    Ok((account, Err(err))) if account == address => return Err(err),
+                    // Counterparties that were never created have no history.
+                    Ok((_, Err(HorizonError::AccountNotFound))) => {}
+                    Ok((account, Err(err))) => {
+                        truncated = true;
+                        warn!(address = %account, error = ?err, "graph_operations_fetch_failed");
+                    }
+                    Err(err) => {
+                        truncated = true;
+                        warn!(error = %err, "graph_operations_task_failed");
+                    }
+                }
+            }
+            // Tasks finish in any order; keep the graph stable.
+            pages.sort_by(|a, b| a.0.cmp(&b.0));
+
+            for (account, operations) in pages {
+                for op in operations {
+                    if !seen_operations.insert(op.id.clone()) {
+                        continue;
+                    }
+                    let Some(flow) = payment_flow(&Operation::from(op)) else {
+                        continue;
+                    };
+                    let other = if flow.from == account {
+                        &flow.to
+                    } else if flow.to == account {
+                        &flow.from
+                    } else {
+                        continue;
+                    };
+                    if !nodes.contains_key(other) {
+                        if nodes.len() == MAX_GRAPH_NODES {
+                            truncated = true;
+                            continue;
+                        }
+                        nodes.insert(other.clone(), hop + 1);
+                        frontier.push(other.clone());
+                    }
+                    flows.push(flow);
+                }
+            }
+        }
+
+        Ok(build_payment_graph(
+            address, depth, &nodes, &flows, truncated,
+        ))
+    }
+
+    /// Accounts that share a signer with the account, that it can sign for,
+    /// or that it sponsors or is sponsored by.
+    ///
+    /// Fails only if the account itself cannot be fetched. A search that
+    /// fails or returns [`RELATED_ACCOUNTS_PER_SEARCH`] accounts marks the
+    /// result as truncated.
+    pub async fn related(&self, address: &str) -> Result<RelatedAccountsExplanation, HorizonError> {
+        let account = self.client.fetch_account_links(address).await?;
+
+        // Only account keys can be searched; pre-authorized transaction and
+        // hash signers belong to a single account.
+        let mut keys = vec![address.to_string()];
+        keys.extend(
+            account
+                .signers
+                .iter()
+                .filter(|s| s.weight > 0 && s.key.starts_with('G') && s.key != address)
+                .map(|s| s.key.clone()),
+        );
+
+        let mut searches = JoinSet::new();
+        for key in keys {
+            let client = Arc::clone(&self.client);
+            searches.spawn(async move {
+                let found = client
+                    .fetch_accounts_with_signer(&key, RELATED_ACCOUNTS_PER_SEARCH)
+                    .await;
+                (Some(key), found)
+            });
+        }
+        let client = Arc::clone(&self.client);
+        let sponsor = address.to_string();
+        searches.spawn(async move {
+            let found = client
+                .fetch_accounts_sponsored_by(&sponsor, RELATED_ACCOUNTS_PER_SEARCH)
+                .await;
+            (None, found)
+        });
+
+        let mut by_signer = Vec::new();
+        let mut sponsored = Vec::new();
+        let mut truncated = false;
+        while let Some(joined) = searches.join_next().await {
+            match joined {
+                Ok((key, Ok(found))) => {
+                    truncated |= found.len() >= RELATED_ACCOUNTS_PER_SEARCH as usize;
+                    match key {
+                        Some(key) => by_signer.push((key, found)),
+                        None => sponsored = found,
+                    }
+                }
+                Ok((key, Err(err))) => {
+                    truncated = true;
+                    warn!(
+                        address = %address,
+                        signer = ?key,
+                        error = ?err,
+                        "related_accounts_search_failed"
+                    );
+                }
+                Err(err) => {
+                    truncated = true;
+                    warn!(error = %err, "related_accounts_task_failed");
+                }
+            }
+        }
+        // Searches finish in any order; keep the output stable.
+        by_signer.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok(explain_related_accounts(
+            &account, &by_signer, &sponsored, truncated,
+        ))
+    }
+
+    /// The account, then its home domain's stellar.toml.
+    async fn account_with_toml(
+        &self,
+        address: &str,
+    ) -> Result<(Account, Option<TomlIdentity>), HorizonError> {
+        let account = self.client.fetch_account(address).await?;
+        let toml = match &account.home_domain {
+            Some(domain) => {
+                self.client
+                    .fetch_stellar_toml_identity(&domain_url(domain))
+                    .await
+            }
+            None => None,
+        };
+        Ok((account, toml))
+    }
+
+    async fn recent_operations(&self, address: &str, limit: u32) -> Vec<HorizonOperation> {
+        if limit == 0 {
+            return Vec::new();
+        }
+        match self.client.fetch_account_operations(address, limit).await {
+            Ok(operations) => operations,
+            // A missing account is reported by the account fetch.
+            Err(HorizonError::AccountNotFound) => Vec::new(),
+            Err(err) => {
+                warn!(address = %address, error = ?err, "account_operations_fetch_failed");
+                Vec::new()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::horizon::HorizonClient;
+    use httpmock::prelude::*;
+    use serde_json::json;
+    use std::time::{Duration, Instant};
+
+    const ADDRESS: &str = "GACCOUNT";
+
+    fn mock_account(server: &MockServer, delay: Duration) {
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/accounts/{ADDRESS}"));
+            then.status(200).delay(delay).json_body(json!({
+                "id": ADDRESS,
+                "account_id": ADDRESS,
+                "sequence": "1",
+                "balances": [{ "asset_type": "native", "balance": "100.0000000" }],
+                "signers": [{ "key": ADDRESS, "weight": 1 }],
+                "flags": {}
+            }));
+        });
+    }
+
+    fn mock_operations(server: &MockServer, delay: Duration) {
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/accounts/{ADDRESS}/operations"))
+                .query_param("limit", "5");
+            then.status(200).delay(delay).json_body(json!({
+                "_embedded": { "records": [{
+                    "id": "1", "transaction_hash": "tx1", "type": "payment",
+                    "created_at": "2024-01-15T14:32:00Z",
+                    "from": ADDRESS, "to": "GB", "amount": "5.0", "asset_type": "native"
+                }] }
+            }));
+        });
+    }
+
+    fn mock_offers(server: &MockServer, delay: Duration) {
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/accounts/{ADDRESS}/offers"));
+            then.status(200)
+                .delay(delay)
+                .json_body(json!({ "_links": {}, "_embedded": { "records": [] } }));
+        });
+    }
+
+    #[tokio::test]
+    async fn explain_fetches_concurrently() {
+        let server = MockServer::start();
+        let delay = Duration::from_millis(300);
+        mock_account(&server, delay);
+        mock_operations(&server, delay);
+        mock_offers(&server, delay);
+
+        let service = AccountService::new(Arc::new(HorizonClient::new(server.base_url())));
+        let options = AccountOptions {
+            include_offers: true,
+            ..AccountOptions::default()
+        };
+
+        let started = Instant::now();
+        let report = service.explain(ADDRESS, &options).await.unwrap();
+        let elapsed = started.elapsed();
+
+        assert!(report.explanation.summary.contains("100 XLM"));
+        assert_eq!(report.recent_operations.len(), 1);
+        assert_eq!(report.recent_operations[0].operation_type, "payment");
+        assert_eq!(report.recent_operations[0].transaction_hash, "tx1");
+        assert_eq!(report.offers.unwrap().offer_count, 0);
+        // Three sequential fetches would take 900ms.
+        assert!(elapsed < Duration::from_millis(700), "took {elapsed:?}");
+    }
+
+    #[tokio::test]
+    async fn explain_degrades_without_operations_or_offers() {
+        let server = MockServer::start();
+        mock_account(&server, Duration::ZERO);
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/accounts/{ADDRESS}/operations"));
+            then.status(400);
+        });
+
+        let service = AccountService::new(Arc::new(HorizonClient::new(server.base_url())));
+        let report = service
+            .explain(ADDRESS, &AccountOptions::default())
+            .await
+            .unwrap();
+
+        assert!(report.recent_operations.is_empty());
+        assert!(report.offers.is_none());
+    }
+
+    #[tokio::test]
+    async fn explain_fails_when_account_is_missing() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/accounts/{ADDRESS}"));
+            then.status(404);
+        });
+
+        let service = AccountService::new(Arc::new(HorizonClient::new(server.base_url())));
+        let err = service
+            .explain(ADDRESS, &AccountOptions::default())
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, HorizonError::AccountNotFound));
+    }
+
+    #[tokio::test]
+    async fn explain_looks_up_preauthorized_transactions() {
+        let server = MockServer::start();
+        let waiting = stellar_strkey::PreAuthTx([0xaa; 32]).to_string();
+        let applied = stellar_strkey::PreAuthTx([0xbb; 32]).to_string();
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/accounts/{ADDRESS}"));
+            then.status(200).json_body(json!({
+                "id": ADDRESS,
+                "account_id": ADDRESS,
+                "sequence": "1",
+                "balances": [{ "asset_type": "native", "balance": "100.0000000" }],
+                "signers": [
+                    { "key": ADDRESS, "weight": 1 },
+                    { "key": waiting, "weight": 1 },
+                    { "key": applied, "weight": 1 }
+                ],
+                "flags": {}
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/transactions/{}", "bb".repeat(32)));
+            then.status(200).json_body(json!({
+                "hash": "bb".repeat(32), "successful": true, "fee_charged": "100", "ledger": 7
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/transactions/{}", "aa".repeat(32)));
+            then.status(404);
+        });
+
+        let service = AccountService::new(Arc::new(HorizonClient::new(server.base_url())));
+        let report = service
+            .explain(ADDRESS, &AccountOptions::default())
+            .await
+            .unwrap();
+
+        let statuses: Vec<Option<PreAuthStatus>> = report
+            .signers
+            .iter()
+            .map(|signer| signer.preauth_status)
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                None,
+                Some(PreAuthStatus::Pending),
+                Some(PreAuthStatus::Applied { ledger: Some(7) })
+            ]
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Stellar Asset Contract (SAC) resolution.
+//!
+//! Every classic asset has a deterministic Soroban contract address derived
+//! from the network passphrase and the asset. Contract addresses can't be
+//! reversed, so the resolver derives the address of each classic asset it
+//! sees — from operations, balances, and SAC events — and caches the mapping
+//! back to the asset. Explainers then show "USDC Stellar Asset Contract"
+//! instead of an opaque C... address.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
+use stellar_xdr::curr::{
+    AccountId, AlphaNum4, AlphaNum12, Asset, AssetCode, ContractId, ContractIdPreimage, Hash,
+    HashIdPreimage, HashIdPreimageContractId, Limits, WriteXdr,
+};
+
+/// Passphrase of the public network, used when none is installed.
+pub const PUBLIC_NETWORK_PASSPHRASE: &str = "Public Global Stellar Network ; September 2015";
+
+/// A classic asset wrapped by a Stellar Asset Contract.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SacAsset {
+    /// Asset code, "XLM" for the native asset.
+    pub code: String,
+    /// Issuer account; `None` for the native asset.
+    pub issuer: Option<String>,
+}
+
+impl SacAsset {
+    pub fn native() -> Self {
+        Self {
+            code: "XLM".to_string(),
+            issuer: None,
+        }
+    }
+
+    pub fn credit(code: &str, issuer: &str) -> Self {
+        Self {
+            code: code.to_string(),
+            issuer: Some(issuer.to_string()),
+        }
+    }
+
+    /// Parse the asset string used in SAC event topics: "native" or "CODE:ISSUER".
+    pub fn from_sep11(value: &str) -> Option<Self> {
+        if value == "native" {
+            return Some(Self::native());
+        }
+        let (code, issuer) = value.split_once(':')?;
+        (!code.is_empty() && issuer.starts_with('G')).then(|| Self::credit(code, issuer))
+    }
+
+    fn to_xdr(&self) -> Option<Asset> {
+        let Some(issuer) = &self.issuer else {
+            return Some(Asset::Native);
+        };
+        let issuer = AccountId::from_str(issuer).ok()?;
+        match AssetCode::from_str(&self.code).ok()? {
+            AssetCode::CreditAlphanum4(asset_code) => {
+                Some(Asset::CreditAlphanum4(AlphaNum4 { asset_code, issuer }))
+            }
+            AssetCode::CreditAlphanum12(asset_code) => {
+                Some(Asset::CreditAlphanum12(AlphaNum12 { asset_code, issuer }))
+            }
+        }
+    }
+}
+
+/// Derives and caches SAC addresses for one network.
+pub struct SacResolver {
+    network_id: [u8; 32],
+    cache: RwLock<HashMap<String, SacAsset>>,
+}
+
+impl SacResolver {
+    /// A resolver for the network with the given passphrase, seeded with the
+    /// native asset.
+    pub fn new(network_passphrase: &str) -> Self {
+        let resolver = Self {
+            network_id: Sha256::digest(network_passphrase.as_bytes()).into(),
+            cache: RwLock::new(HashMap::new()),
+        };
+        resolver.register(SacAsset::native());
+        resolver
+    }
+
+    /// The contract address of the SAC for `asset` on this network, or `None`
+    /// when the asset code or issuer is malformed.
+    pub fn contract_id(&self, asset: &SacAsset) -> Option<String> {
+        let preimage = HashIdPreimage::ContractId(HashIdPreimageContractId {
+            network_id: Hash(self.network_id),
+            contract_id_preimage: ContractIdPreimage::Asset(asset.to_xdr()?),
+        });
+        let bytes = preimage.to_xdr(Limits::none()).ok()?;
+        let hash: [u8; 32] = Sha256::digest(bytes).into();
+        Some(ContractId(Hash(hash)).to_string())
+    }
+
+    /// Remember `asset` so its contract address resolves. Returns the address.
+    pub fn register(&self, asset: SacAsset) -> Option<String> {
+        let contract_id = self.contract_id(&asset)?;
+        let known = self
+            .cache
+            .read()
+            .map(|cache| cache.contains_key(&contract_id))
+            .unwrap_or(false);
+        if !known && let Ok(mut cache) = self.cache.write() {
+            cache.insert(contract_id.clone(), asset);
+        }
+        Some(contract_id)
+    }
+
+    /// The classic asset behind a contract address, if it is a known SAC.
+    pub fn resolve(&self, contract_id: &str) -> Option<SacAsset> {
+        self.cache.read().ok()?.get(contract_id).cloned()
+    }
+}
+
+static RESOLVER: OnceLock<SacResolver> = OnceLock::new();
+
+/// Install the resolver for the configured network. Call once at startup;
+/// later calls are ignored.
+pub fn install(resolver: SacResolver) {
+    let _ = RESOLVER.set(resolver);
+}
+
+fn resolver() -> &'static SacResolver {
+    RESOLVER.get_or_init(|| SacResolver::new(PUBLIC_NETWORK_PASSPHRASE))
+}
+
+/// Remember a classic asset seen in Horizon data.
+pub fn register_asset(code: &str, issuer: &str) {
+    resolver().register(SacAsset::credit(code, issuer));
+}
+
+/// The classic asset behind a contract address, if it is a known SAC.
+pub fn resolve_sac(contract_id: &str) -> Option<SacAsset> {
+    if !contract_id.starts_with('C') {
+        return None;
+    }
+    resolver().resolve(contract_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TESTNET: &str = "Test SDF Network ; September 2015";
+
+    #[test]
+    fn test_native_contract_ids() {
+        let public = SacResolver::new(PUBLIC_NETWORK_PASSPHRASE);
+        assert_eq!(
+            public.contract_id(&SacAsset::native()).as_deref(),
+            Some("CAS3J7GYLGXMF6TDJBBYYSE3HQ6BBSMLNUQ34T6TZMYMW2EVH34XOWMA")
+        );
+        let testnet = SacResolver::new(TESTNET);
+        assert_eq!(
+            testnet.resolve("CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"),
+            Some(SacAsset::native())
+        );
+    }
+
+    #[test]
+    fn test_credit_asset_round_trip() {
+        let resolver = SacResolver::new(PUBLIC_NETWORK_PASSPHRASE);
+        let usdc = SacAsset::credit(
+            "USDC",
+            "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
+        );
+        let contract_id = resolver.register(usdc.clone()).unwrap();
+        assert_eq!(
+            contract_id,
+            "CCW67TSZV3SSS2HXMBQ5JFGCKJNXKZM7UQUWUZPUTHXSTZLEO7SJMI75"
+        );
+        assert_eq!(resolver.resolve(&contract_id), Some(usdc));
+    }
+
+    #[test]
+    fn test_malformed_assets_are_ignored() {
+        let resolver = SacResolver::new(TESTNET);
+        assert_eq!(resolver.register(SacAsset::credit("USDC", "GISSUER")), None);
+        assert_eq!(
+            resolver.register(SacAsset::credit(
+                "WAYTOOLONGCODE",
+                "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"
+            )),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_sep11_asset() {
+        assert_eq!(SacAsset::from_sep11("native"), Some(SacAsset::native()));
+        assert_eq!(
+            SacAsset::from_sep11("USDC:GISSUER"),
+            Some(SacAsset::credit("USDC", "GISSUER"))
+        );
+        assert_eq!(SacAsset::from_sep11("USDC"), None);
+    }
+}
@@ -0,0 +1,347 @@
+//! Audit log of explanation requests.
+//!
+//! When enabled, every request for a transaction or account explanation is
+//! recorded with the network, who asked, when, and the response status, so
+//! operators can answer "who looked this up?" after the fact. Records live in
+//! their own SQLite database, separate from the explanation index, and are
+//! pruned once they are older than the retention period.
+//!
+//! Requesters are identified by API key when the client sends `X-API-Key`,
+//! otherwise by IP address. Keys are stored as a short SHA-256 fingerprint,
+//! never in the clear.
+//!
+//! Configuration (environment):
+//! - `AUDIT_ENABLED`: `true` or `1` to record requests (default off).
+//! - `AUDIT_DB_PATH`: SQLite file (default `stellar-explain-audit.db`).
+//! - `AUDIT_RETENTION_DAYS`: days to keep records (default 90).
+//!
+//! Records are read back at `GET /admin/audit`, which needs `ADMIN_TOKEN`.
+
+use rusqlite::{Connection, params};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{error, info};
+use utoipa::ToSchema;
+
+use crate::services::storage::StorageError;
+
+/// How often expired records are pruned.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditConfig {
+    pub db_path: String,
+    pub retention: Duration,
+}
+
+impl AuditConfig {
+    /// Read the audit settings, or `None` when auditing is not enabled.
+    pub fn from_env() -> Option<Self> {
+        let enabled = env::var("AUDIT_ENABLED")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let retention_days = env::var("AUDIT_RETENTION_DAYS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(90);
+
+        Some(Self {
+            db_path: env::var("AUDIT_DB_PATH")
+                .unwrap_or_else(|_| "stellar-explain-audit.db".to_string()),
+            retention: Duration::from_secs(retention_days * 24 * 60 * 60),
+        })
+    }
+}
+
+/// What an audited request asked about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditSubject {
+    Transaction,
+    Account,
+}
+
+impl AuditSubject {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AuditSubject::Transaction => "transaction",
+            AuditSubject::Account => "account",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "transaction" => Some(AuditSubject::Transaction),
+            "account" => Some(AuditSubject::Account),
+            _ => None,
+        }
+    }
+}
+
+/// One audited request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct AuditRecord {
+    pub subject_type: AuditSubject,
+    /// Transaction hash or account address.
+    pub subject: String,
+    pub network: String,
+    /// `key:<fingerprint>` for API key holders, otherwise `ip:<address>`.
+    pub requester: String,
+    /// RFC 3339, UTC.
+    pub requested_at: String,
+    pub status: u16,
+}
+
+/// Filters for [`AuditStore::query`]. Unset fields match everything.
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub subject: Option<String>,
+    pub requester: Option<String>,
+    pub status: Option<u16>,
+    /// Inclusive lower bound, RFC 3339 UTC.
+    pub since: Option<String>,
+    /// Exclusive upper bound, RFC 3339 UTC.
+    pub until: Option<String>,
+}
+
+/// The requester recorded for an API key: a fingerprint that identifies the
+/// key without storing it.
+pub fn key_requester(api_key: &str) -> String {
+    let digest = Sha256::digest(api_key.as_bytes());
+    let hex: String = digest[..8].iter().map(|b| format!("{b:02x}")).collect();
+    format!("key:{hex}")
+}
+
+/// Format a timestamp the way records store it, so string comparison orders
+/// them chronologically.
+pub fn audit_timestamp(time: chrono::DateTime<chrono::Utc>) -> String {
+    time.to_rfc3339_opts(chrono::SecondsFormat::Millis, true)
+}
+
+/// SQLite-backed audit log.
+pub struct AuditStore {
+    conn: Mutex<Connection>,
+}
+
+impl AuditStore {
+    /// Open (creating if needed) the database at `path`.
+    pub fn open(path: &str) -> Result<Self, StorageError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// An in-memory store, used by tests.
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, StorageError> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS audit_log (
+                id            INTEGER PRIMARY KEY AUTOINCREMENT,
+                subject_type  TEXT NOT NULL,
+                subject       TEXT NOT NULL,
+                network       TEXT NOT NULL,
+                requester     TEXT NOT NULL,
+                requested_at  TEXT NOT NULL,
+                status        INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS audit_log_requested_at ON audit_log (requested_at);
+            CREATE INDEX IF NOT EXISTS audit_log_subject ON audit_log (subject);
+            CREATE INDEX IF NOT EXISTS audit_log_requester ON audit_log (requester);",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn record(&self, record: &AuditRecord) -> Result<(), StorageError> {
+        self.conn().execute(
+            "INSERT INTO audit_log
+                 (subject_type, subject, network, requester, requested_at, status)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                record.subject_type.as_str(),
+                record.subject,
+                record.network,
+                record.requester,
+                record.requested_at,
+                record.status,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Matching records, newest first, and the total number of matches.
+    pub fn query(
+        &self,
+        filter: &AuditFilter,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<AuditRecord>, u64), StorageError> {
+        const WHERE: &str = "WHERE (?1 IS NULL OR subject = ?1)
+               AND (?2 IS NULL OR requester = ?2)
+               AND (?3 IS NULL OR status = ?3)
+               AND (?4 IS NULL OR requested_at >= ?4)
+               AND (?5 IS NULL OR requested_at < ?5)";
+        let conn = self.conn();
+
+        let total: i64 = conn.query_row(
+            &format!("SELECT COUNT(*) FROM audit_log {WHERE}"),
+            params![
+                filter.subject,
+                filter.requester,
+                filter.status,
+                filter.since,
+                filter.until,
+            ],
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT subject_type, subject, network, requester, requested_at, status
+             FROM audit_log {WHERE}
+             ORDER BY requested_at DESC, id DESC
+             LIMIT ?6 OFFSET ?7"
+        ))?;
+        let records = stmt
+            .query_map(
+                params![
+                    filter.subject,
+                    filter.requester,
+                    filter.status,
+                    filter.since,
+                    filter.until,
+                    limit,
+                    offset,
+                ],
+                |row| {
+                    let subject_type: String = row.get(0)?;
+                    Ok(AuditRecord {
+                        // Only this module writes the column.
+                        subject_type: AuditSubject::parse(&subject_type)
+                            .unwrap_or(AuditSubject::Transaction),
+                        subject: row.get(1)?,
+                        network: row.get(2)?,
+                        requester: row.get(3)?,
+                        requested_at: row.get(4)?,
+                        status: row.get(5)?,
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok((records, total as u64))
+    }
+
+    /// Delete records requested before `cutoff`. Returns the number deleted.
+    pub fn prune_before(&self, cutoff: &str) -> Result<usize, StorageError> {
+        Ok(self.conn().execute(
+            "DELETE FROM audit_log WHERE requested_at < ?1",
+            params![cutoff],
+        )?)
+    }
+}
+
+/// Delete records older than `retention`, once an hour.
+pub async fn run_retention(store: Arc<AuditStore>, retention: Duration) {
+    loop {
+        let task_store = Arc::clone(&store);
+        let result = tokio::task::spawn_blocking(move || {
+            let cutoff = chrono::Utc::now()
+                - chrono::Duration::from_std(retention).unwrap_or(chrono::Duration::MAX);
+            task_store.prune_before(&audit_timestamp(cutoff))
+        })
+        .await;
+        match result {
+            Ok(Ok(pruned)) => info!(pruned, "audit_log_pruned"),
+            Ok(Err(err)) => error!(error = %err, "audit_log_prune_failed"),
+            Err(err) => error!(error = %err, "audit_log_prune_panicked"),
+        }
+        tokio::time::sleep(PRUNE_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(subject: &str, requester: &str, requested_at: &str, status: u16) -> AuditRecord {
+        AuditRecord {
+            subject_type: AuditSubject::Transaction,
+            subject: subject.to_string(),
+            network: "testnet".to_string(),
+            requester: requester.to_string(),
+            requested_at: requested_at.to_string(),
+            status,
+        }
+    }
+
+    fn store() -> AuditStore {
+        let store = AuditStore::open_in_memory().unwrap();
+        for r in [
+            record("tx1", "ip:10.0.0.1", "2026-01-01T00:00:00.000Z", 200),
+            record("tx2", "ip:10.0.0.1", "2026-01-02T00:00:00.000Z", 404),
+            record("tx1", "key:abc", "2026-01-03T00:00:00.000Z", 200),
+        ] {
+            store.record(&r).unwrap();
+        }
+        store
+    }
+
+    #[test]
+    fn test_query_filters_newest_first() {
+        let store = store();
+
+        let (all, total) = store.query(&AuditFilter::default(), 10, 0).unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(all[0].requester, "key:abc");
+        assert_eq!(all[2].subject, "tx1");
+
+        let by_subject = AuditFilter {
+            subject: Some("tx1".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(store.query(&by_subject, 10, 0).unwrap().1, 2);
+
+        let window = AuditFilter {
+            requester: Some("ip:10.0.0.1".to_string()),
+            since: Some("2026-01-02T00:00:00.000Z".to_string()),
+            until: Some("2026-01-03T00:00:00.000Z".to_string()),
+            ..Default::default()
+        };
+        let (records, total) = store.query(&window, 10, 0).unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(records[0].status, 404);
+
+        let (page, total) = store.query(&AuditFilter::default(), 1, 1).unwrap();
+        assert_eq!((page.len(), total), (1, 3));
+        assert_eq!(page[0].subject, "tx2");
+    }
+
+    #[test]
+    fn test_prune_before_cutoff() {
+        let store = store();
+        assert_eq!(store.prune_before("2026-01-02T00:00:00.000Z").unwrap(), 1);
+        assert_eq!(store.query(&AuditFilter::default(), 10, 0).unwrap().1, 2);
+    }
+
+    #[test]
+    fn test_key_requester_hides_key() {
+        let requester = key_requester("secret-key");
+        assert!(requester.starts_with("key:"));
+        assert_eq!(requester.len(), 4 + 16);
+        assert!(!requester.contains("secret"));
+        assert_eq!(requester, key_requester("secret-key"));
+    }
+}
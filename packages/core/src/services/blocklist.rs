@@ -0,0 +1,476 @@
+//! A deployment-maintained blocklist of scam assets, reloadable at runtime.
+//!
+//! Scam tokens are issued faster than a release can list them, so the list
+//! lives outside the binary: a file or a URL with one `CODE:ISSUER`, or one
+//! scam account's address, per line, optionally followed by the reason it is
+//! listed. Blank lines and lines starting with `#` are ignored.
+//!
+//! ```text
+//! # Fake stablecoins
+//! USDC:GSCAMMER...   impersonates Circle's USDC
+//! AIRDROP:GSPAM...
+//! # Known scam accounts
+//! GPHISHER...        fake exchange deposit address
+//! ```
+//!
+//! A listed asset is called out with a `BLOCKLISTED_ASSET` warning on any
+//! transaction explanation that touches it, and counts as a spam asset when
+//! account history hides spam. Account balances of it are either marked
+//! `blocklisted` or left out, depending on the action. Sending to a listed
+//! account is warned about by `POST /check`.
+//!
+//! The list is read at startup and again on `SIGHUP` or
+//! `POST /admin/blocklist/reload`. A reload that fails (the file is missing,
+//! the URL does not answer, a line is malformed) keeps the list already in
+//! use. `/health` reports the version being served: the start of the SHA-256
+//! of the list's contents.
+//!
+//! Configuration (environment):
+//! - `SPAM_BLOCKLIST`: path or `http(s)://` URL of the list (unset: no list).
+//! - `SPAM_BLOCKLIST_ACTION`: `annotate` (default) or `hide`.
+
+use chrono::{SecondsFormat, Utc};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, RwLock};
+use tracing::{info, warn};
+use utoipa::ToSchema;
+
+use crate::explain::account::AssetBalanceExplanation;
+use crate::explain::transaction::{ExplanationWarning, TransactionExplanation};
+use crate::models::strkey;
+use crate::services::policy::Parties;
+
+/// Hex digits of the content hash kept as the version.
+const VERSION_LENGTH: usize = 12;
+
+/// What to do with balances of a listed asset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BlocklistAction {
+    /// Keep the balance, marked `blocklisted`, with a note in its summary.
+    Annotate,
+    /// Leave the balance out of the account's assets.
+    Hide,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlocklistSource {
+    File(String),
+    Url(String),
+}
+
+impl BlocklistSource {
+    fn parse(value: &str) -> Self {
+        if value.starts_with("http://") || value.starts_with("https://") {
+            Self::Url(value.to_string())
+        } else {
+            Self::File(value.to_string())
+        }
+    }
+
+    fn describe(&self) -> &str {
+        match self {
+            Self::File(path) => path,
+            Self::Url(url) => url,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlocklistConfig {
+    pub source: BlocklistSource,
+    pub action: BlocklistAction,
+}
+
+impl BlocklistConfig {
+    /// Read `SPAM_BLOCKLIST` and `SPAM_BLOCKLIST_ACTION`; `None` when no list
+    /// is configured.
+    pub fn from_env() -> Option<Self> {
+        let source = env::var("SPAM_BLOCKLIST")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())?;
+        let action = match env::var("SPAM_BLOCKLIST_ACTION")
+            .map(|v| v.trim().to_ascii_lowercase())
+            .as_deref()
+        {
+            Ok("hide") => BlocklistAction::Hide,
+            _ => BlocklistAction::Annotate,
+        };
+        Some(Self {
+            source: BlocklistSource::parse(&source),
+            action,
+        })
+    }
+}
+
+/// The version of the list being served, for `/health`.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct BlocklistStatus {
+    /// First hex digits of the SHA-256 of the list's contents.
+    pub version: String,
+    pub entries: usize,
+    /// When this version was loaded, RFC 3339.
+    pub loaded_at: String,
+    pub source: String,
+    pub action: BlocklistAction,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Blocklist {
+    /// Reason each asset or account is listed, keyed by "CODE:ISSUER" or by
+    /// address.
+    entries: HashMap<String, Option<String>>,
+    action: BlocklistAction,
+    status: BlocklistStatus,
+}
+
+impl Blocklist {
+    /// Parse the contents of a list. Any malformed line rejects the whole
+    /// list, so a typo cannot quietly unlist an asset.
+    pub fn parse(contents: &str, source: &str, action: BlocklistAction) -> Result<Self, String> {
+        let mut entries = HashMap::new();
+        for (number, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (asset, reason) = match line.split_once(char::is_whitespace) {
+                Some((asset, reason)) => (asset, Some(reason.trim().to_string())),
+                None => (line, None),
+            };
+            match asset.split_once(':') {
+                Some((code, issuer)) if !code.is_empty() && !issuer.is_empty() => {
+                    entries.insert(asset.to_string(), reason);
+                }
+                None if strkey::is_account_address(asset) => {
+                    entries.insert(asset.to_string(), reason);
+                }
+                _ => {
+                    return Err(format!(
+                        "line {}: expected CODE:ISSUER or an account address, found {asset:?}",
+                        number + 1
+                    ));
+                }
+            }
+        }
+        let digest = Sha256::digest(contents.as_bytes());
+        let version: String = digest
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>()
+            .chars()
+            .take(VERSION_LENGTH)
+            .collect();
+        let status = BlocklistStatus {
+            version,
+            entries: entries.len(),
+            loaded_at: Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            source: source.to_string(),
+            action,
+        };
+        Ok(Self {
+            entries,
+            action,
+            status,
+        })
+    }
+
+    pub fn status(&self) -> &BlocklistStatus {
+        &self.status
+    }
+
+    /// Whether `asset` ("CODE:ISSUER") or an account address is listed, with
+    /// the reason if given.
+    pub fn get(&self, asset: &str) -> Option<Option<&str>> {
+        self.entries.get(asset).map(Option::as_deref)
+    }
+
+    pub fn contains(&self, asset: &str) -> bool {
+        self.entries.contains_key(asset)
+    }
+
+    /// `explanation` with a warning for each listed asset it touches.
+    pub fn flag_transaction(
+        &self,
+        mut explanation: TransactionExplanation,
+    ) -> TransactionExplanation {
+        for asset in Parties::of_transaction(&explanation).assets {
+            if let Some(reason) = self.get(&asset) {
+                explanation
+                    .warnings
+                    .push(ExplanationWarning::blocklisted_asset(&asset, reason));
+            }
+        }
+        explanation
+    }
+
+    /// `assets` with listed ones marked or left out, as configured.
+    pub fn flag_balances(
+        &self,
+        assets: Vec<AssetBalanceExplanation>,
+    ) -> Vec<AssetBalanceExplanation> {
+        assets
+            .into_iter()
+            .filter_map(|mut balance| {
+                let Some(issuer) = &balance.asset_issuer else {
+                    return Some(balance);
+                };
+                let Some(reason) = self.get(&format!("{}:{issuer}", balance.asset_code)) else {
+                    return Some(balance);
+                };
+                if self.action == BlocklistAction::Hide {
+                    return None;
+                }
+                balance.blocklisted = true;
+                balance.summary.push_str(&match reason {
+                    Some(reason) => format!(" This asset is on the spam blocklist: {reason}."),
+                    None => " This asset is on the spam blocklist.".to_string(),
+                });
+                Some(balance)
+            })
+            .collect()
+    }
+}
+
+type Store = RwLock<Option<Arc<Blocklist>>>;
+
+static CURRENT: Store = RwLock::new(None);
+
+/// The list in use, or `None` when none is configured or none has loaded.
+pub fn current() -> Option<Arc<Blocklist>> {
+    CURRENT.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// [`Blocklist::flag_transaction`] with the list in use.
+pub fn flag_transaction(explanation: TransactionExplanation) -> TransactionExplanation {
+    match current() {
+        Some(list) => list.flag_transaction(explanation),
+        None => explanation,
+    }
+}
+
+/// [`Blocklist::flag_balances`] with the list in use.
+pub fn flag_balances(assets: Vec<AssetBalanceExplanation>) -> Vec<AssetBalanceExplanation> {
+    match current() {
+        Some(list) => list.flag_balances(assets),
+        None => assets,
+    }
+}
+
+/// Reads the configured list and swaps it in.
+#[derive(Clone)]
+pub struct BlocklistLoader {
+    config: BlocklistConfig,
+    client: reqwest::Client,
+}
+
+impl BlocklistLoader {
+    pub fn new(config: BlocklistConfig, client: reqwest::Client) -> Self {
+        Self { config, client }
+    }
+
+    pub async fn load(&self) -> Result<Blocklist, String> {
+        let contents = match &self.config.source {
+            BlocklistSource::File(path) => tokio::fs::read_to_string(path)
+                .await
+                .map_err(|err| format!("could not read {path}: {err}"))?,
+            BlocklistSource::Url(url) => self
+                .client
+                .get(url)
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+                .map_err(|err| format!("could not fetch {url}: {err}"))?
+                .text()
+                .await
+                .map_err(|err| format!("could not read {url}: {err}"))?,
+        };
+        Blocklist::parse(&contents, self.config.source.describe(), self.config.action)
+    }
+
+    /// Load the list and put it in use. On failure the list already in use
+    /// stays.
+    pub async fn reload(&self) -> Result<BlocklistStatus, String> {
+        self.reload_into(&CURRENT).await
+    }
+
+    async fn reload_into(&self, store: &Store) -> Result<BlocklistStatus, String> {
+        let list = match self.load().await {
+            Ok(list) => list,
+            Err(err) => {
+                warn!(
+                    source = self.config.source.describe(),
+                    error = %err,
+                    "blocklist_reload_failed"
+                );
+                return Err(err);
+            }
+        };
+        let status = list.status().clone();
+        *store.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(list));
+        info!(
+            source = %status.source,
+            version = %status.version,
+            entries = status.entries,
+            "blocklist_loaded"
+        );
+        Ok(status)
+    }
+}
+
+/// Reload the list each time the process receives `SIGHUP`.
+#[cfg(unix)]
+pub async fn run_reload_on_sighup(loader: BlocklistLoader) {
+    use tokio::signal::unix::{SignalKind, signal};
+
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(err) => {
+            warn!(error = %err, "blocklist_sighup_unavailable");
+            return;
+        }
+    };
+    while hangups.recv().await.is_some() {
+        let _ = loader.reload().await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::account::explain_balance;
+    use crate::explain::transaction::{WarningCode, explain_transaction};
+    use crate::models::account::Balance;
+    use crate::models::operation::{Operation, PaymentOperation};
+    use crate::models::transaction::Transaction;
+
+    const LIST: &str = "# Known scams\n\nAIRDROP:GSPAMMER   fake airdrop\nUSDC:GFAKE\n";
+
+    fn list(action: BlocklistAction) -> Blocklist {
+        Blocklist::parse(LIST, "blocklist.txt", action).unwrap()
+    }
+
+    fn balance(code: &str, issuer: &str) -> AssetBalanceExplanation {
+        explain_balance(&Balance {
+            balance: "1000.0000000".to_string(),
+            asset_type: "credit_alphanum12".to_string(),
+            asset_code: Some(code.to_string()),
+            asset_issuer: Some(issuer.to_string()),
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_parses_entries_reasons_and_version() {
+        let list = list(BlocklistAction::Annotate);
+
+        assert_eq!(list.get("AIRDROP:GSPAMMER"), Some(Some("fake airdrop")));
+        assert_eq!(list.get("USDC:GFAKE"), Some(None));
+        assert_eq!(list.get("USDC:GCIRCLE"), None);
+        assert_eq!(list.status().entries, 2);
+        assert_eq!(list.status().version.len(), VERSION_LENGTH);
+
+        let edited = Blocklist::parse("USDC:GFAKE\n", "blocklist.txt", BlocklistAction::Annotate);
+        assert_ne!(edited.unwrap().status().version, list.status().version);
+        assert_eq!(
+            Blocklist::parse("AIRDROP\n", "blocklist.txt", BlocklistAction::Annotate),
+            Err(
+                "line 1: expected CODE:ISSUER or an account address, found \"AIRDROP\"".to_string()
+            )
+        );
+
+        let account = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+        let accounts = Blocklist::parse(
+            &format!("{account} fake exchange\n"),
+            "blocklist.txt",
+            BlocklistAction::Annotate,
+        )
+        .unwrap();
+        assert_eq!(accounts.get(account), Some(Some("fake exchange")));
+    }
+
+    #[test]
+    fn test_transactions_touching_a_listed_asset_are_warned_about() {
+        let tx = Transaction::new(
+            "abc".to_string(),
+            true,
+            100,
+            vec![Operation::Payment(PaymentOperation {
+                id: "1".to_string(),
+                source_account: Some("GSPAMMER".to_string()),
+                destination: "GVICTIM".to_string(),
+                asset_type: "credit_alphanum12".to_string(),
+                asset_code: Some("AIRDROP".to_string()),
+                asset_issuer: Some("GSPAMMER".to_string()),
+                amount: "1000".parse().unwrap(),
+            })],
+            None,
+            None,
+        );
+        let explanation = explain_transaction(&tx, None).unwrap();
+
+        let flagged = list(BlocklistAction::Annotate).flag_transaction(explanation.clone());
+        assert_eq!(flagged.warnings.len(), 1);
+        assert_eq!(flagged.warnings[0].code, WarningCode::BlocklistedAsset);
+        assert!(flagged.warnings[0].message.contains("fake airdrop"));
+
+        let unrelated = Blocklist::parse("USDC:GFAKE", "blocklist.txt", BlocklistAction::Hide);
+        assert!(
+            unrelated
+                .unwrap()
+                .flag_transaction(explanation)
+                .warnings
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_balances_are_annotated_or_hidden() {
+        let assets = vec![balance("AIRDROP", "GSPAMMER"), balance("USDC", "GCIRCLE")];
+
+        let annotated = list(BlocklistAction::Annotate).flag_balances(assets.clone());
+        assert!(annotated[0].blocklisted);
+        assert!(
+            annotated[0]
+                .summary
+                .ends_with("on the spam blocklist: fake airdrop.")
+        );
+        assert!(!annotated[1].blocklisted);
+
+        let hidden = list(BlocklistAction::Hide).flag_balances(assets);
+        assert_eq!(hidden.len(), 1);
+        assert_eq!(hidden[0].asset_code, "USDC");
+    }
+
+    #[tokio::test]
+    async fn test_failed_reload_keeps_the_list_in_use() {
+        let path = env::temp_dir().join(format!("blocklist-{}.txt", uuid::Uuid::new_v4()));
+        std::fs::write(&path, LIST).unwrap();
+        let loader = BlocklistLoader::new(
+            BlocklistConfig {
+                source: BlocklistSource::File(path.to_string_lossy().into_owned()),
+                action: BlocklistAction::Annotate,
+            },
+            reqwest::Client::new(),
+        );
+        let store: Store = RwLock::new(None);
+
+        let first = loader.reload_into(&store).await.unwrap();
+        assert_eq!(first.entries, 2);
+
+        std::fs::write(&path, "not an asset\n").unwrap();
+        assert!(loader.reload_into(&store).await.is_err());
+        let kept = store.read().unwrap().clone().unwrap();
+        assert_eq!(kept.status().version, first.version);
+
+        std::fs::write(&path, "USDC:GFAKE\n").unwrap();
+        let second = loader.reload_into(&store).await.unwrap();
+        assert_eq!(second.entries, 1);
+        assert_ne!(second.version, first.version);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
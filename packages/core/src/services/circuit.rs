@@ -0,0 +1,204 @@
+//! Circuit breaker for upstream Horizon calls.
+//!
+//! After `failure_threshold` consecutive failures (network errors, 429s and
+//! 5xx responses) the circuit opens and requests fail fast for `cooldown`
+//! instead of piling onto an outage. Once the cooldown passes the circuit is
+//! half-open: requests go through again, and the first failure reopens it
+//! while the first success closes it.
+//!
+//! Configuration (environment):
+//! - `HORIZON_CIRCUIT_FAILURES` consecutive failures before opening (default 5).
+//! - `HORIZON_CIRCUIT_COOLDOWN_SECONDS` how long the circuit stays open (default 30).
+
+use serde::Serialize;
+use std::env;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use utoipa::ToSchema;
+
+/// Circuit breaker settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CircuitConfig {
+    pub failure_threshold: u32,
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            cooldown: Duration::from_secs(30),
+        }
+    }
+}
+
+impl CircuitConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let parse = |name: &str| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+        };
+
+        Self {
+            failure_threshold: parse("HORIZON_CIRCUIT_FAILURES")
+                .map(|n| n.min(u32::MAX as u64) as u32)
+                .unwrap_or(defaults.failure_threshold),
+            cooldown: parse("HORIZON_CIRCUIT_COOLDOWN_SECONDS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.cooldown),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+/// Point-in-time view of the circuit, reported on `/health`.
+#[derive(Debug, Clone, Serialize, PartialEq, ToSchema)]
+pub struct CircuitSnapshot {
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    /// Seconds until requests are let through again, while open.
+    pub retry_after_seconds: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitConfig,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(Inner::default()),
+        }
+    }
+
+    /// `Err` with the time left in the cooldown while the circuit is open.
+    pub fn check(&self) -> Result<(), Duration> {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        match self.remaining(&inner) {
+            Some(remaining) => Err(remaining),
+            None => Ok(()),
+        }
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        *inner = Inner::default();
+    }
+
+    /// Count a failure; returns true if this one opened the circuit.
+    pub fn record_failure(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.consecutive_failures = inner.consecutive_failures.saturating_add(1);
+        if inner.consecutive_failures >= self.config.failure_threshold {
+            let was_open = self.remaining(&inner).is_some();
+            inner.opened_at = Some(Instant::now());
+            return !was_open;
+        }
+        false
+    }
+
+    pub fn snapshot(&self) -> CircuitSnapshot {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let remaining = self.remaining(&inner);
+        let state = match (inner.opened_at, remaining) {
+            (_, Some(_)) => CircuitState::Open,
+            (Some(_), None) => CircuitState::HalfOpen,
+            (None, None) => CircuitState::Closed,
+        };
+        CircuitSnapshot {
+            state,
+            consecutive_failures: inner.consecutive_failures,
+            retry_after_seconds: remaining.map(retry_after_seconds),
+        }
+    }
+
+    fn remaining(&self, inner: &Inner) -> Option<Duration> {
+        let elapsed = inner.opened_at?.elapsed();
+        (elapsed < self.config.cooldown).then(|| self.config.cooldown - elapsed)
+    }
+}
+
+/// Whole seconds for a `Retry-After` header, rounded up and at least 1.
+pub fn retry_after_seconds(remaining: Duration) -> u64 {
+    let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+    secs.max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker(failure_threshold: u32, cooldown: Duration) -> CircuitBreaker {
+        CircuitBreaker::new(CircuitConfig {
+            failure_threshold,
+            cooldown,
+        })
+    }
+
+    #[test]
+    fn test_opens_after_threshold_consecutive_failures() {
+        let circuit = breaker(3, Duration::from_secs(30));
+
+        assert!(!circuit.record_failure());
+        assert!(!circuit.record_failure());
+        assert!(circuit.check().is_ok());
+        assert!(circuit.record_failure());
+
+        let remaining = circuit.check().unwrap_err();
+        assert!(remaining <= Duration::from_secs(30));
+        let snapshot = circuit.snapshot();
+        assert_eq!(snapshot.state, CircuitState::Open);
+        assert_eq!(snapshot.consecutive_failures, 3);
+        assert_eq!(snapshot.retry_after_seconds, Some(30));
+    }
+
+    #[test]
+    fn test_success_resets_failure_count() {
+        let circuit = breaker(2, Duration::from_secs(30));
+
+        circuit.record_failure();
+        circuit.record_success();
+        circuit.record_failure();
+
+        assert!(circuit.check().is_ok());
+        assert_eq!(circuit.snapshot().state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_half_open_after_cooldown() {
+        let circuit = breaker(1, Duration::ZERO);
+
+        circuit.record_failure();
+        assert!(circuit.check().is_ok());
+        assert_eq!(circuit.snapshot().state, CircuitState::HalfOpen);
+
+        circuit.record_success();
+        assert_eq!(circuit.snapshot().state, CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_retry_after_rounds_up() {
+        assert_eq!(retry_after_seconds(Duration::from_millis(1500)), 2);
+        assert_eq!(retry_after_seconds(Duration::from_secs(4)), 4);
+        assert_eq!(retry_after_seconds(Duration::ZERO), 1);
+    }
+}
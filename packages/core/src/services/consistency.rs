@@ -0,0 +1,408 @@
+//! Consistency checks for indexed explanations.
+//!
+//! Explanations in the index were derived when their ledger was ingested, by
+//! whatever version of the explainers was running then. When enabled, this
+//! checker periodically picks a random sample of them, re-derives each from
+//! fresh Horizon data with the current explainers, and compares the two.
+//! A mismatch means the explainers' output changed for the same input, for
+//! example after a parser change. Mismatches are logged and kept for
+//! `GET /admin/consistency`. The index is never rewritten by the checker.
+//!
+//! Configuration (environment; requires ledger ingestion):
+//! - `CONSISTENCY_CHECK_ENABLED`: `true` or `1` to run the checker.
+//! - `CONSISTENCY_CHECK_INTERVAL_SECONDS`: time between runs (default 300).
+//! - `CONSISTENCY_CHECK_SAMPLE_SIZE`: explanations per run (default 20).
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+use crate::explain::transaction::explain_transaction_with_ledger;
+use crate::services::explain::map_transaction_to_domain;
+use crate::services::horizon_api::HorizonApi;
+use crate::services::storage::{ExplanationStore, StoredExplanation};
+
+/// Mismatches kept for the admin endpoint, newest first.
+const RECENT_MISMATCHES: usize = 50;
+
+/// Differing paths reported per mismatch.
+const MAX_DIFFERENCES: usize = 20;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsistencyConfig {
+    pub interval: Duration,
+    pub sample_size: u32,
+}
+
+impl Default for ConsistencyConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(300),
+            sample_size: 20,
+        }
+    }
+}
+
+impl ConsistencyConfig {
+    /// Read the checker settings, or `None` when the checker is not enabled.
+    pub fn from_env() -> Option<Self> {
+        let enabled = env::var("CONSISTENCY_CHECK_ENABLED")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let defaults = Self::default();
+        let parse = |name: &str| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+        };
+        Some(Self {
+            interval: parse("CONSISTENCY_CHECK_INTERVAL_SECONDS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.interval),
+            sample_size: parse("CONSISTENCY_CHECK_SAMPLE_SIZE")
+                .map(|n| n.min(u32::MAX as u64) as u32)
+                .unwrap_or(defaults.sample_size),
+        })
+    }
+}
+
+/// A stored explanation that no longer matches its re-derived form.
+#[derive(Debug, Clone, Serialize, PartialEq, ToSchema)]
+pub struct ConsistencyMismatch {
+    pub transaction_hash: String,
+    pub checked_at: String,
+    /// JSON paths that differ, e.g. `operations[0].summary`.
+    pub differences: Vec<String>,
+    pub stored_summary: String,
+    pub fresh_summary: String,
+}
+
+/// Point-in-time view of the checker's results.
+#[derive(Debug, Clone, Default, Serialize, PartialEq, ToSchema)]
+pub struct ConsistencySnapshot {
+    pub runs: u64,
+    pub last_run_at: Option<String>,
+    /// Explanations compared since the checker started.
+    pub checked: u64,
+    /// Explanations that could not be re-derived, e.g. Horizon errors.
+    pub skipped: u64,
+    pub mismatched: u64,
+    /// Most recent mismatches, newest first.
+    pub recent_mismatches: Vec<ConsistencyMismatch>,
+}
+
+/// Checker results, shared with the admin endpoint.
+#[derive(Debug, Default)]
+pub struct ConsistencyStatus {
+    inner: Mutex<ConsistencySnapshot>,
+}
+
+impl ConsistencyStatus {
+    pub fn snapshot(&self) -> ConsistencySnapshot {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, ConsistencySnapshot> {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Outcome of checking one stored explanation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheckOutcome {
+    Consistent,
+    Mismatch(ConsistencyMismatch),
+    Skipped,
+}
+
+/// Re-derives sampled explanations and compares them with the index.
+pub struct ConsistencyChecker<H> {
+    client: Arc<H>,
+    store: Arc<ExplanationStore>,
+    config: ConsistencyConfig,
+    status: Arc<ConsistencyStatus>,
+}
+
+impl<H: HorizonApi> ConsistencyChecker<H> {
+    pub fn new(client: Arc<H>, store: Arc<ExplanationStore>, config: ConsistencyConfig) -> Self {
+        Self {
+            client,
+            store,
+            config,
+            status: Arc::new(ConsistencyStatus::default()),
+        }
+    }
+
+    pub fn status(&self) -> Arc<ConsistencyStatus> {
+        Arc::clone(&self.status)
+    }
+
+    /// Run forever, checking a sample every interval.
+    pub async fn run(self) {
+        info!(config = ?self.config, "consistency_check_started");
+        loop {
+            tokio::time::sleep(self.config.interval).await;
+            self.run_once().await;
+        }
+    }
+
+    /// Check one sample and record the results.
+    pub async fn run_once(&self) {
+        let sample = match self.store.sample_explanations(self.config.sample_size) {
+            Ok(sample) => sample,
+            Err(err) => {
+                error!(error = %err, "consistency_sample_failed");
+                return;
+            }
+        };
+
+        let mut outcomes = Vec::with_capacity(sample.len());
+        for stored in &sample {
+            outcomes.push(self.check(stored).await);
+        }
+
+        let mut status = self.status.lock();
+        status.runs += 1;
+        status.last_run_at = Some(now());
+        for outcome in outcomes {
+            match outcome {
+                CheckOutcome::Consistent => status.checked += 1,
+                CheckOutcome::Skipped => status.skipped += 1,
+                CheckOutcome::Mismatch(mismatch) => {
+                    status.checked += 1;
+                    status.mismatched += 1;
+                    let mut recent: VecDeque<_> =
+                        std::mem::take(&mut status.recent_mismatches).into();
+                    recent.push_front(mismatch);
+                    recent.truncate(RECENT_MISMATCHES);
+                    status.recent_mismatches = recent.into();
+                }
+            }
+        }
+        info!(
+            sampled = sample.len(),
+            checked = status.checked,
+            mismatched = status.mismatched,
+            "consistency_check_completed"
+        );
+    }
+
+    /// Re-derive one stored explanation and compare.
+    pub async fn check(&self, stored: &StoredExplanation) -> CheckOutcome {
+        let hash = &stored.explanation.transaction_hash;
+        let (tx, operations) = tokio::join!(
+            self.client.fetch_transaction(hash),
+            self.client.fetch_operations(hash),
+        );
+        let (tx, operations) = match (tx, operations) {
+            (Ok(tx), Ok(operations)) => (tx, operations),
+            (Err(err), _) | (_, Err(err)) => {
+                warn!(hash = %hash, error = ?err, "consistency_refetch_failed");
+                return CheckOutcome::Skipped;
+            }
+        };
+
+        let domain_tx = map_transaction_to_domain(tx, operations);
+        // Derived exactly as ingestion derives it: without fee context.
+        let Ok(fresh) = explain_transaction_with_ledger(
+            &domain_tx,
+            None,
+            stored.created_at.as_deref(),
+            stored.explanation.ledger,
+        ) else {
+            return CheckOutcome::Skipped;
+        };
+
+        let (Ok(stored_json), Ok(fresh_json)) = (
+            serde_json::to_value(&stored.explanation),
+            serde_json::to_value(&fresh),
+        ) else {
+            return CheckOutcome::Skipped;
+        };
+        let mut differences = Vec::new();
+        diff_paths(&stored_json, &fresh_json, String::new(), &mut differences);
+        if differences.is_empty() {
+            return CheckOutcome::Consistent;
+        }
+
+        warn!(
+            hash = %hash,
+            differences = ?differences,
+            "explanation_mismatch"
+        );
+        CheckOutcome::Mismatch(ConsistencyMismatch {
+            transaction_hash: hash.clone(),
+            checked_at: now(),
+            differences,
+            stored_summary: stored.explanation.summary.clone(),
+            fresh_summary: fresh.summary,
+        })
+    }
+}
+
+fn now() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Collect the paths at which `a` and `b` differ, up to [`MAX_DIFFERENCES`].
+fn diff_paths(a: &Value, b: &Value, path: String, out: &mut Vec<String>) {
+    if out.len() >= MAX_DIFFERENCES || a == b {
+        return;
+    }
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => {
+            let mut keys: Vec<&String> = a.keys().chain(b.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                let child = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_paths(
+                    a.get(key).unwrap_or(&Value::Null),
+                    b.get(key).unwrap_or(&Value::Null),
+                    child,
+                    out,
+                );
+            }
+        }
+        (Value::Array(a), Value::Array(b)) if a.len() == b.len() => {
+            for (i, (a, b)) in a.iter().zip(b).enumerate() {
+                diff_paths(a, b, format!("{path}[{i}]"), out);
+            }
+        }
+        _ => out.push(if path.is_empty() {
+            "$".to_string()
+        } else {
+            path
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::horizon::{HorizonOperation, HorizonTransaction};
+    use crate::services::horizon_api::FakeHorizon;
+    use serde_json::json;
+
+    const HASH: &str = "a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2";
+
+    fn horizon_payment(amount: &str) -> (HorizonTransaction, Vec<HorizonOperation>) {
+        let tx: HorizonTransaction = serde_json::from_value(json!({
+            "hash": HASH,
+            "successful": true,
+            "fee_charged": "100",
+            "created_at": "2024-01-15T14:32:00Z",
+            "ledger": 42,
+        }))
+        .unwrap();
+        let op: HorizonOperation = serde_json::from_value(json!({
+            "id": "1",
+            "transaction_hash": HASH,
+            "type": "payment",
+            "from": "GSENDER",
+            "to": "GRECEIVER",
+            "asset_type": "native",
+            "amount": amount,
+        }))
+        .unwrap();
+        (tx, vec![op])
+    }
+
+    fn stored(amount: &str) -> StoredExplanation {
+        let (tx, ops) = horizon_payment(amount);
+        let domain = map_transaction_to_domain(tx, ops);
+        StoredExplanation {
+            ledger: 42,
+            created_at: Some("2024-01-15T14:32:00Z".to_string()),
+            fee_charged: 100,
+            explanation: explain_transaction_with_ledger(
+                &domain,
+                None,
+                Some("2024-01-15T14:32:00Z"),
+                Some(42),
+            )
+            .unwrap(),
+        }
+    }
+
+    fn checker(horizon: FakeHorizon) -> ConsistencyChecker<FakeHorizon> {
+        ConsistencyChecker::new(
+            Arc::new(horizon),
+            Arc::new(ExplanationStore::open_in_memory().unwrap()),
+            ConsistencyConfig::default(),
+        )
+    }
+
+    #[test]
+    fn test_diff_paths() {
+        let mut out = Vec::new();
+        diff_paths(
+            &json!({ "summary": "a", "operations": [{ "summary": "x" }], "same": 1 }),
+            &json!({ "summary": "b", "operations": [{ "summary": "y" }], "same": 1, "new": true }),
+            String::new(),
+            &mut out,
+        );
+        assert_eq!(out, vec!["new", "operations[0].summary", "summary"]);
+
+        let mut out = Vec::new();
+        diff_paths(&json!([1]), &json!([1, 2]), String::new(), &mut out);
+        assert_eq!(out, vec!["$"]);
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_explanation_is_consistent() {
+        let (tx, ops) = horizon_payment("5.0000000");
+        let checker = checker(FakeHorizon::default().with_transaction(tx, ops));
+
+        assert_eq!(
+            checker.check(&stored("5.0000000")).await,
+            CheckOutcome::Consistent
+        );
+    }
+
+    #[tokio::test]
+    async fn test_changed_explanation_is_reported() {
+        let (tx, ops) = horizon_payment("7.0000000");
+        let checker = checker(FakeHorizon::default().with_transaction(tx, ops));
+        checker.store.put_explanation(&stored("5.0000000")).unwrap();
+
+        checker.run_once().await;
+
+        let snapshot = checker.status().snapshot();
+        assert_eq!(
+            (snapshot.runs, snapshot.checked, snapshot.mismatched),
+            (1, 1, 1)
+        );
+        let mismatch = &snapshot.recent_mismatches[0];
+        assert_eq!(mismatch.transaction_hash, HASH);
+        assert!(
+            mismatch
+                .differences
+                .iter()
+                .any(|path| path.starts_with("operations[0]"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_transactions_are_skipped() {
+        let checker = checker(FakeHorizon::default());
+        assert_eq!(
+            checker.check(&stored("5.0000000")).await,
+            CheckOutcome::Skipped
+        );
+    }
+}
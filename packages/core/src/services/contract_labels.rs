@@ -0,0 +1,284 @@
+//! Names for well-known Soroban contracts and contract code.
+//!
+//! A contract is labeled by its address (`C...`), or by the SHA-256 hash of
+//! the WASM it runs, so every deployment of the same code, such as each pair
+//! of an AMM, shares one label. Stellar Asset Contracts need no entry: they
+//! are named after their asset (see [`crate::services::assets`]).
+//!
+//! A few public-network contracts are built in. Operators add their own, or
+//! rename built-in ones, through `/admin/labels/contracts`; those labels are
+//! written to a file so they survive restarts.
+//!
+//! Configuration (environment):
+//! - `CONTRACT_LABELS_PATH`: file operator labels are kept in (default
+//!   `stellar-explain-contract-labels.json`).
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::sync::{Arc, OnceLock, RwLock};
+use utoipa::ToSchema;
+
+use crate::models::strkey::{self, StrKeyKind};
+use crate::services::assets::PUBLIC_NETWORK_PASSPHRASE;
+
+const DEFAULT_PATH: &str = "stellar-explain-contract-labels.json";
+
+/// Longest label accepted, in characters.
+pub const MAX_LABEL_LENGTH: usize = 64;
+
+/// Labels known on the public network, by contract address.
+const PUBLIC_CONTRACTS: &[(&str, &str)] = &[
+    (
+        "CAG5LRYQ5JVEUI5TEID72EYOVX44TTUJT5BQR2J6J77FH65PCCFAJDDH",
+        "Soroswap Router",
+    ),
+    (
+        "CA4HEQTL2WPEUYKYKCDOHCDNIV4QHNJ7EL4J4NQ6VADP7SYHVRYZ7AW2",
+        "Soroswap Factory",
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractLabelKind {
+    /// A contract address (`C...`).
+    Contract,
+    /// The hex SHA-256 hash of contract WASM.
+    Wasm,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ContractLabel {
+    /// Contract address, or hex WASM hash.
+    pub id: String,
+    pub kind: ContractLabelKind,
+    pub name: String,
+    /// Whether the label ships with the service rather than being set by an
+    /// operator.
+    pub builtin: bool,
+}
+
+/// What a label is attached to, after normalizing: a contract address as
+/// given, a WASM hash in lowercase. `None` for anything else.
+pub fn label_target(id: &str) -> Option<(String, ContractLabelKind)> {
+    let id = id.trim();
+    if strkey::validate(id, &[StrKeyKind::Contract]).is_ok() {
+        return Some((id.to_string(), ContractLabelKind::Contract));
+    }
+    (id.len() == 64 && id.bytes().all(|b| b.is_ascii_hexdigit()))
+        .then(|| (id.to_ascii_lowercase(), ContractLabelKind::Wasm))
+}
+
+#[derive(Debug)]
+pub struct ContractLabels {
+    builtin: BTreeMap<String, String>,
+    /// Where operator labels are saved; `None` keeps them in memory only.
+    path: Option<PathBuf>,
+    operator: RwLock<BTreeMap<String, String>>,
+}
+
+impl ContractLabels {
+    /// The built-in labels for the network with `passphrase`, and no
+    /// operator labels.
+    pub fn builtin(passphrase: &str) -> Self {
+        let builtin = if passphrase == PUBLIC_NETWORK_PASSPHRASE {
+            PUBLIC_CONTRACTS
+                .iter()
+                .map(|(id, name)| (id.to_string(), name.to_string()))
+                .collect()
+        } else {
+            BTreeMap::new()
+        };
+        Self {
+            builtin,
+            path: None,
+            operator: RwLock::new(BTreeMap::new()),
+        }
+    }
+
+    /// Read `CONTRACT_LABELS_PATH` and the operator labels saved there.
+    pub fn from_env(passphrase: &str) -> io::Result<Self> {
+        let path = env::var("CONTRACT_LABELS_PATH")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| DEFAULT_PATH.to_string());
+        Self::open(passphrase, path)
+    }
+
+    /// Built-in labels plus the operator labels saved at `path`.
+    pub fn open(passphrase: &str, path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let operator: BTreeMap<String, String> = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err),
+        };
+        if let Some(bad) = operator.keys().find(|id| label_target(id).is_none()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{bad} is neither a contract address nor a WASM hash"),
+            ));
+        }
+        Ok(Self {
+            path: Some(path),
+            operator: RwLock::new(operator),
+            ..Self::builtin(passphrase)
+        })
+    }
+
+    /// The label for a contract address or WASM hash. Operator labels win
+    /// over built-in ones.
+    pub fn get(&self, id: &str) -> Option<String> {
+        let (id, _) = label_target(id)?;
+        self.operator
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&id)
+            .or_else(|| self.builtin.get(&id))
+            .cloned()
+    }
+
+    /// Every label in effect, contracts before WASM hashes.
+    pub fn list(&self) -> Vec<ContractLabel> {
+        let operator = self.operator.read().unwrap_or_else(|e| e.into_inner());
+        let builtin = self
+            .builtin
+            .iter()
+            .filter(|(id, _)| !operator.contains_key(*id))
+            .map(|(id, name)| (id, name, true));
+        let mut labels: Vec<ContractLabel> = operator
+            .iter()
+            .map(|(id, name)| (id, name, false))
+            .chain(builtin)
+            .filter_map(|(id, name, builtin)| {
+                let (id, kind) = label_target(id)?;
+                Some(ContractLabel {
+                    id,
+                    kind,
+                    name: name.clone(),
+                    builtin,
+                })
+            })
+            .collect();
+        labels.sort_by(|a, b| (a.kind, &a.id).cmp(&(b.kind, &b.id)));
+        labels
+    }
+
+    /// Label `id`, replacing any label it had. `id` must already be
+    /// normalized by [`label_target`].
+    pub fn set(&self, id: &str, name: &str) -> io::Result<()> {
+        let mut operator = self.operator.write().unwrap_or_else(|e| e.into_inner());
+        let mut updated = operator.clone();
+        updated.insert(id.to_string(), name.to_string());
+        self.save(&updated)?;
+        *operator = updated;
+        Ok(())
+    }
+
+    /// Remove an operator label; `false` when `id` had none. Built-in labels
+    /// cannot be removed, only renamed.
+    pub fn remove(&self, id: &str) -> io::Result<bool> {
+        let mut operator = self.operator.write().unwrap_or_else(|e| e.into_inner());
+        if !operator.contains_key(id) {
+            return Ok(false);
+        }
+        let mut updated = operator.clone();
+        updated.remove(id);
+        self.save(&updated)?;
+        *operator = updated;
+        Ok(true)
+    }
+
+    /// Saved before the labels change, so a label that could not be saved is
+    /// never used.
+    fn save(&self, labels: &BTreeMap<String, String>) -> io::Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let partial = path.with_extension("json.partial");
+        std::fs::write(&partial, serde_json::to_vec_pretty(labels)?)?;
+        std::fs::rename(&partial, path)
+    }
+}
+
+static REGISTRY: OnceLock<Arc<ContractLabels>> = OnceLock::new();
+
+/// Install the registry for the configured network. Call once at startup;
+/// later calls are ignored.
+pub fn install(labels: Arc<ContractLabels>) {
+    let _ = REGISTRY.set(labels);
+}
+
+fn registry() -> &'static ContractLabels {
+    REGISTRY.get_or_init(|| Arc::new(ContractLabels::builtin(PUBLIC_NETWORK_PASSPHRASE)))
+}
+
+/// The label of a contract address or WASM hash, if it has one.
+pub fn resolve_contract_label(id: &str) -> Option<String> {
+    registry().get(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROUTER: &str = "CAG5LRYQ5JVEUI5TEID72EYOVX44TTUJT5BQR2J6J77FH65PCCFAJDDH";
+    const TESTNET: &str = "Test SDF Network ; September 2015";
+
+    #[test]
+    fn test_label_targets() {
+        assert_eq!(
+            label_target(ROUTER),
+            Some((ROUTER.to_string(), ContractLabelKind::Contract))
+        );
+        assert_eq!(
+            label_target(&"AB".repeat(32)),
+            Some(("ab".repeat(32), ContractLabelKind::Wasm))
+        );
+        assert_eq!(
+            label_target("GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7"),
+            None
+        );
+        assert_eq!(label_target("abc"), None);
+    }
+
+    #[test]
+    fn test_builtin_labels_are_per_network() {
+        for (id, _) in PUBLIC_CONTRACTS {
+            assert!(label_target(id).is_some(), "{id}");
+        }
+        let public = ContractLabels::builtin(PUBLIC_NETWORK_PASSPHRASE);
+        assert_eq!(public.get(ROUTER).as_deref(), Some("Soroswap Router"));
+        assert_eq!(ContractLabels::builtin(TESTNET).get(ROUTER), None);
+    }
+
+    #[test]
+    fn test_operator_labels_survive_restart() {
+        let path = env::temp_dir().join(format!("contract-labels-{}.json", uuid::Uuid::new_v4()));
+        let wasm = "cd".repeat(32);
+        let labels = ContractLabels::open(PUBLIC_NETWORK_PASSPHRASE, &path).unwrap();
+        labels.set(&wasm, "Soroswap Pair").unwrap();
+        labels.set(ROUTER, "Soroswap Router v1").unwrap();
+
+        let reopened = ContractLabels::open(PUBLIC_NETWORK_PASSPHRASE, &path).unwrap();
+        assert_eq!(
+            reopened.get(&wasm.to_uppercase()).as_deref(),
+            Some("Soroswap Pair")
+        );
+        assert_eq!(reopened.get(ROUTER).as_deref(), Some("Soroswap Router v1"));
+        let listed = reopened.list();
+        assert_eq!(listed.len(), 3);
+        assert_eq!(listed[2].kind, ContractLabelKind::Wasm);
+        assert!(listed.iter().any(|l| l.id == ROUTER && !l.builtin));
+
+        // Removing the override brings the built-in label back.
+        assert!(reopened.remove(ROUTER).unwrap());
+        assert!(!reopened.remove(ROUTER).unwrap());
+        assert_eq!(reopened.get(ROUTER).as_deref(), Some("Soroswap Router"));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,18 +1,181 @@
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::errors::AppError;
+use crate::explain::issuer_label::payment_issuers;
+use crate::explain::transaction::{
+    ExplanationWarning, TransactionExplanation, explain_transaction_with_ledger,
+    explain_transaction_without_operations,
+};
+use crate::models::contract_call::decode_contract_call;
+use crate::models::contract_event::{EventValue, decode_contract_events};
+use crate::models::fee::FeeStats;
 use crate::models::memo::Memo;
 use crate::models::operation::Operation;
-use crate::models::transaction::{ResultCodes, Transaction};
+use crate::models::transaction::{ResultCodes, Transaction, TransactionSource};
+use crate::services::assets::{SacAsset, register_asset};
 use crate::services::horizon::{HorizonOperation, HorizonResultCodes, HorizonTransaction};
+use crate::services::horizon_api::HorizonApi;
+use crate::services::issuer_labels::resolve_issuer_labels;
+use crate::services::offer_fills::fetch_offer_fills;
+use crate::services::provider::{LedgerDataProvider, LedgerTransaction};
+use crate::services::slippage::analyze_path_payments;
+
+/// How long a request waits for fee context (fee stats, ledger close times)
+/// beyond the transaction itself. Explanations read fine without it, so a
+/// slow fee endpoint should not hold the response back.
+pub const FEE_CONTEXT_BUDGET: Duration = Duration::from_millis(1500);
+
+/// Await optional fee context for at most [`FEE_CONTEXT_BUDGET`], treating a
+/// timeout like any other failure to fetch it.
+pub async fn within_fee_budget<T>(fetch: impl Future<Output = Option<T>>) -> Option<T> {
+    tokio::time::timeout(FEE_CONTEXT_BUDGET, fetch)
+        .await
+        .ok()
+        .flatten()
+}
+
+/// Explain a fetched transaction. The protocol version needs the ledger the
+/// transaction named, issuer names the assets it paid with, and path payment
+/// slippage and order fills the trades it made, so these are fetched
+/// afterwards, within the fee context budget. Issuers, slippage, and fills
+/// always come from Horizon, whatever the data source. What could not be
+/// fetched is reported in `warnings` rather than failing.
+pub async fn explain_fetched<H: HorizonApi>(
+    fetched: LedgerTransaction,
+    fee_stats: Option<FeeStats>,
+    provider: &Arc<dyn LedgerDataProvider>,
+    horizon_client: Arc<H>,
+) -> Result<TransactionExplanation, AppError> {
+    let LedgerTransaction {
+        transaction: domain_tx,
+        created_at,
+        ledger,
+        operations_unavailable,
+    } = fetched;
+    let mut explanation = if operations_unavailable {
+        explain_transaction_without_operations(
+            &domain_tx,
+            fee_stats.as_ref(),
+            created_at.as_deref(),
+            ledger,
+        )
+    } else {
+        explain_transaction_with_ledger(
+            &domain_tx,
+            fee_stats.as_ref(),
+            created_at.as_deref(),
+            ledger,
+        )?
+    };
+    if fee_stats.is_none() {
+        explanation
+            .warnings
+            .push(ExplanationWarning::fee_stats_unavailable());
+    }
+    let (protocol_version, issuers, slippages, offer_fills) = tokio::join!(
+        async {
+            let sequence = explanation.ledger_context.as_ref()?.sequence;
+            within_fee_budget(provider.fetch_protocol_version(sequence)).await
+        },
+        within_fee_budget(async {
+            Some(
+                resolve_issuer_labels(Arc::clone(&horizon_client), payment_issuers(&domain_tx))
+                    .await,
+            )
+        }),
+        within_fee_budget(async {
+            let closed_at = created_at.as_deref()?;
+            Some(analyze_path_payments(Arc::clone(&horizon_client), &domain_tx, closed_at).await)
+        }),
+        within_fee_budget(async {
+            Some(fetch_offer_fills(Arc::clone(&horizon_client), &domain_tx).await)
+        }),
+    );
+    if let Some(context) = explanation.ledger_context.as_mut() {
+        context.protocol_version = protocol_version;
+    }
+    explanation.issuers = issuers.unwrap_or_default();
+    for slippage in slippages.unwrap_or_default() {
+        let note = format!(" ({})", slippage.summary);
+        annotate_operation(
+            &mut explanation,
+            slippage.operation_index,
+            &note,
+            "slippage",
+            serde_json::json!(slippage),
+        );
+    }
+    for (index, fills) in offer_fills.unwrap_or_default() {
+        let note = format!("; {}", fills.summary);
+        annotate_operation(
+            &mut explanation,
+            index,
+            &note,
+            "fills",
+            serde_json::json!(fills),
+        );
+    }
+    Ok(explanation)
+}
+
+/// Add what was learned about operation `index` after it was explained:
+/// `note` is appended to its summary and `value` added to its details
+/// under `key`.
+fn annotate_operation(
+    explanation: &mut TransactionExplanation,
+    index: usize,
+    note: &str,
+    key: &str,
+    value: serde_json::Value,
+) {
+    let Some(op) = explanation
+        .operations
+        .iter_mut()
+        .find(|op| op.index == index)
+    else {
+        return;
+    };
+    op.summary.push_str(note);
+    if let Some(details) = op.details.as_object_mut() {
+        details.insert(key.to_string(), value);
+    }
+}
+
+/// Fetch and explain `hash` the way `/tx/:hash` does when it is not cached, before
+/// policy, privacy, and relative times are applied.
+pub async fn fetch_explanation<H: HorizonApi>(
+    hash: &str,
+    provider: Arc<dyn LedgerDataProvider>,
+    horizon_client: Arc<H>,
+) -> Result<TransactionExplanation, AppError> {
+    let (fetched, fee_stats) = tokio::join!(
+        provider.fetch_partial_transaction(hash),
+        within_fee_budget(provider.fetch_fee_stats())
+    );
+    explain_fetched(fetched?, fee_stats, &provider, horizon_client).await
+}
 
 pub fn map_transaction_to_domain(
     tx: HorizonTransaction,
     operations: Vec<HorizonOperation>,
 ) -> Transaction {
-    let ops = operations.into_iter().map(Operation::from).collect();
+    register_operation_assets(&operations);
+    let source = transaction_source(&tx, &operations);
+    let ops: Vec<Operation> = operations.into_iter().map(Operation::from).collect();
 
     // Map memo fields from Horizon into the domain Memo model.
     // Horizon always returns memo_type — it's "none" when there is no memo.
     // The memo value itself is only present for non-none memo types.
-    let memo = map_memo(tx.memo_type.as_deref(), tx.memo.as_deref());
+    let memo = map_memo(
+        tx.memo_type.as_deref(),
+        tx.memo.as_deref(),
+        tx.memo_bytes.as_deref(),
+    );
 
     // Prefer result codes from `extras` (submission error shape), fall back to
     // top-level `result_codes` (fetch-by-hash shape for failed transactions).
@@ -27,7 +190,31 @@ pub fn map_transaction_to_domain(
         operations: rc.operations.clone(),
     });
 
-    Transaction::new(
+    // Only Soroban transactions carry contract events. Undecodable metadata
+    // is logged and skipped rather than failing the whole explanation.
+    let contract_events = match tx.result_meta_xdr.as_deref() {
+        Some(meta) if ops_are_soroban(&ops) => decode_contract_events(meta).unwrap_or_else(|err| {
+            warn!(hash = %tx.hash, error = %err, "contract_event_decode_failed");
+            vec![]
+        }),
+        _ => vec![],
+    };
+    let contract_call = match tx.envelope_xdr.as_deref() {
+        Some(envelope) if ops_are_soroban(&ops) => decode_contract_call(envelope),
+        _ => None,
+    };
+    for event in &contract_events {
+        if let Some(EventValue::String(asset)) = event.topics.last()
+            && let Some(SacAsset {
+                code,
+                issuer: Some(issuer),
+            }) = SacAsset::from_sep11(asset)
+        {
+            register_asset(&code, &issuer);
+        }
+    }
+
+    let transaction = Transaction::new(
         tx.hash,
         tx.successful,
         tx.fee_charged.parse().unwrap_or(0),
@@ -35,15 +222,80 @@ pub fn map_transaction_to_domain(
         memo,
         result_codes,
     )
+    .with_contract_events(contract_events)
+    .with_contract_call(contract_call)
+    .with_ledger(tx.created_at, tx.ledger);
+    match source {
+        Some(source) => transaction.with_source(source),
+        None => transaction,
+    }
+}
+
+/// The submitting account and the accounts the operations act for. Horizon
+/// fills in every operation's source, defaulting to the transaction's.
+fn transaction_source(
+    tx: &HorizonTransaction,
+    operations: &[HorizonOperation],
+) -> Option<TransactionSource> {
+    let account = tx.source_account.clone()?;
+    let mut operation_accounts: Vec<String> = Vec::new();
+    for op in operations {
+        let op_source = op.source_account.as_ref().unwrap_or(&account);
+        if !operation_accounts.contains(op_source) {
+            operation_accounts.push(op_source.clone());
+        }
+    }
+    Some(TransactionSource {
+        account,
+        sequence: tx.source_account_sequence.clone(),
+        fee_account: tx.fee_account.clone(),
+        operation_accounts,
+    })
+}
+
+/// Remember every classic asset an operation mentions so its Stellar Asset
+/// Contract address can be shown by name.
+fn register_operation_assets(operations: &[HorizonOperation]) {
+    for op in operations {
+        let pairs = [
+            (&op.asset_code, &op.asset_issuer),
+            (&op.buying_asset_code, &op.buying_asset_issuer),
+            (&op.selling_asset_code, &op.selling_asset_issuer),
+        ];
+        for (code, issuer) in pairs {
+            if let (Some(code), Some(issuer)) = (code, issuer) {
+                register_asset(code, issuer);
+            }
+        }
+    }
+}
+
+/// Whether any operation invokes a contract. Soroban transactions hold a
+/// single `invoke_host_function` operation.
+fn ops_are_soroban(ops: &[Operation]) -> bool {
+    ops.iter().any(|op| {
+        matches!(op, Operation::Other(other) if other.operation_type == "invoke_host_function")
+    })
 }
 
 /// Converts raw Horizon memo fields into a domain Memo.
 ///
 /// Horizon memo types: "none", "text", "id", "hash", "return"
-fn map_memo(memo_type: Option<&str>, memo_value: Option<&str>) -> Option<Memo> {
+///
+/// Text memos are arbitrary bytes, and Horizon's `memo` string replaces any
+/// that are not UTF-8. `memo_bytes` (base64) has them exactly, so it is
+/// preferred; `memo` is the fallback when it is missing or malformed.
+fn map_memo(
+    memo_type: Option<&str>,
+    memo_value: Option<&str>,
+    memo_bytes: Option<&str>,
+) -> Option<Memo> {
     match memo_type {
         None | Some("none") => None,
-        Some("text") => memo_value.and_then(Memo::text),
+        Some("text") => memo_bytes
+            .and_then(|b| STANDARD.decode(b).ok())
+            .and_then(|bytes| Memo::from_text_bytes(&bytes))
+            .or_else(|| memo_value.and_then(Memo::text)),
         Some("id") => memo_value.and_then(|v| v.parse::<u64>().ok()).map(Memo::id),
         Some("hash") => memo_value.map(Memo::hash),
         Some("return") => memo_value.map(Memo::return_hash),
@@ -54,47 +306,208 @@ fn map_memo(memo_type: Option<&str>, memo_value: Option<&str>) -> Option<Memo> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::explain::transaction::explain_transaction_with_ledger;
+    use proptest::prelude::*;
 
     #[test]
     fn test_map_memo_none_type() {
-        assert_eq!(map_memo(Some("none"), None), None);
+        assert_eq!(map_memo(Some("none"), None, None), None);
     }
 
     #[test]
     fn test_map_memo_missing_type() {
-        assert_eq!(map_memo(None, None), None);
+        assert_eq!(map_memo(None, None, None), None);
     }
 
     #[test]
     fn test_map_memo_text() {
-        let memo = map_memo(Some("text"), Some("Hello Stellar"));
+        let memo = map_memo(Some("text"), Some("Hello Stellar"), None);
         assert!(memo.is_some());
         assert_eq!(memo.unwrap().memo_type(), "text");
     }
 
+    #[test]
+    fn test_map_memo_text_prefers_bytes() {
+        // Horizon's `memo` for the bytes 0xff 0x00 0x01 0x02.
+        let memo = map_memo(Some("text"), Some("\u{fffd}\0\u{1}\u{2}"), Some("/wABAg=="));
+        assert_eq!(memo, Some(Memo::Binary("ff000102".to_string())));
+
+        let memo = map_memo(Some("text"), Some("café"), Some("Y2Fmw6k="));
+        assert_eq!(memo, Some(Memo::Text("café".to_string())));
+    }
+
+    #[test]
+    fn test_map_memo_text_falls_back_to_string() {
+        let memo = map_memo(Some("text"), Some("Hello"), Some("not base64!"));
+        assert_eq!(memo, Some(Memo::Text("Hello".to_string())));
+
+        // 29 bytes cannot be a memo; the string is checked on its own.
+        let memo = map_memo(
+            Some("text"),
+            Some("Hello"),
+            Some(&STANDARD.encode([b'a'; 29])),
+        );
+        assert_eq!(memo, Some(Memo::Text("Hello".to_string())));
+    }
+
     #[test]
     fn test_map_memo_id() {
-        let memo = map_memo(Some("id"), Some("12345"));
+        let memo = map_memo(Some("id"), Some("12345"), None);
         assert!(memo.is_some());
         assert_eq!(memo.unwrap().memo_type(), "id");
     }
 
     #[test]
     fn test_map_memo_hash() {
-        let memo = map_memo(Some("hash"), Some("abc123deadbeef"));
+        let memo = map_memo(Some("hash"), Some("abc123deadbeef"), None);
         assert!(memo.is_some());
         assert_eq!(memo.unwrap().memo_type(), "hash");
     }
 
     #[test]
     fn test_map_memo_return() {
-        let memo = map_memo(Some("return"), Some("abc123deadbeef"));
+        let memo = map_memo(Some("return"), Some("abc123deadbeef"), None);
         assert!(memo.is_some());
         assert_eq!(memo.unwrap().memo_type(), "return");
     }
 
     #[test]
     fn test_map_memo_unknown_type() {
-        assert_eq!(map_memo(Some("unknown_future_type"), Some("value")), None);
+        assert_eq!(
+            map_memo(Some("unknown_future_type"), Some("value"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_soroban_contract_events_decoded() {
+        use crate::models::contract_event::tests::{
+            account_address, contract_address, transfer_meta,
+        };
+
+        let meta = transfer_meta(
+            contract_address(1),
+            account_address(2),
+            "native",
+            10_000_000,
+        );
+        let tx: HorizonTransaction = serde_json::from_value(serde_json::json!({
+            "hash": "soroban",
+            "successful": true,
+            "fee_charged": "100",
+            "result_meta_xdr": meta,
+        }))
+        .unwrap();
+        let invoke = |id: &str| -> HorizonOperation {
+            serde_json::from_value(serde_json::json!({
+                "id": id,
+                "transaction_hash": "soroban",
+                "type": "invoke_host_function",
+            }))
+            .unwrap()
+        };
+
+        let domain = map_transaction_to_domain(tx.clone(), vec![invoke("1")]);
+        assert_eq!(domain.contract_events.len(), 1);
+
+        // Classic transactions never decode metadata.
+        let classic = map_transaction_to_domain(tx, vec![]);
+        assert!(classic.contract_events.is_empty());
+    }
+
+    fn operation_type() -> impl Strategy<Value = String> {
+        prop_oneof![
+            prop::sample::select(vec![
+                "payment",
+                "create_account",
+                "change_trust",
+                "set_options",
+                "manage_sell_offer",
+                "manage_buy_offer",
+                "create_passive_sell_offer",
+                "path_payment_strict_send",
+                "path_payment_strict_receive",
+                "clawback",
+                "clawback_claimable_balance",
+                "account_merge",
+                "invoke_host_function",
+            ])
+            .prop_map(String::from),
+            any::<String>(),
+        ]
+    }
+
+    prop_compose! {
+        /// An operation record whose fields hold whatever Horizon might send.
+        fn horizon_operation()(
+            operation_type in operation_type(),
+            amounts in prop::array::uniform4(proptest::option::of(any::<String>())),
+            accounts in prop::array::uniform4(proptest::option::of(any::<String>())),
+            assets in prop::array::uniform4(proptest::option::of(any::<String>())),
+            flags in proptest::option::of(prop::collection::vec(any::<u32>(), 0..4)),
+        ) -> HorizonOperation {
+            let [amount, source_amount, starting_balance, price] = amounts;
+            let [from, to, source_account, into] = accounts;
+            let [asset_type, asset_code, asset_issuer, offer_id] = assets;
+            HorizonOperation {
+                id: "1".to_string(),
+                transaction_hash: "tx".to_string(),
+                operation_type,
+                source_account,
+                amount,
+                asset_type,
+                asset_code,
+                asset_issuer,
+                from,
+                to,
+                starting_balance,
+                offer_id,
+                price,
+                set_flags: flags,
+                source_amount,
+                into,
+                ..Default::default()
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn map_memo_never_panics(
+            memo_type in proptest::option::of(prop::sample::select(vec![
+                "none", "text", "id", "hash", "return", "other",
+            ])),
+            value in proptest::option::of(any::<String>()),
+        ) {
+            let memo = map_memo(memo_type, value.as_deref(), value.as_deref());
+            match memo {
+                Some(Memo::Text(text)) => prop_assert!(text.len() <= 28),
+                Some(Memo::Binary(hex)) => prop_assert!(hex.len() <= 56),
+                _ => {}
+            }
+        }
+
+        #[test]
+        fn arbitrary_operations_map_and_explain(
+            operations in prop::collection::vec(horizon_operation(), 1..4),
+            fee_charged in any::<String>(),
+            memo in proptest::option::of(any::<String>()),
+            created_at in proptest::option::of(any::<String>()),
+        ) {
+            let tx = HorizonTransaction {
+                hash: "tx".to_string(),
+                successful: true,
+                fee_charged,
+                memo_type: Some("text".to_string()),
+                memo,
+                created_at: created_at.clone(),
+                ..Default::default()
+            };
+            let domain = map_transaction_to_domain(tx, operations);
+            prop_assert!(
+                explain_transaction_with_ledger(&domain, None, created_at.as_deref(), Some(1))
+                    .is_ok()
+            );
+        }
     }
 }
@@ -0,0 +1,597 @@
+//! Bulk exports of an account's transactions.
+//!
+//! An export is a background job: `POST /exports` records it and returns at
+//! once, a task reads the account's history for the date range and writes
+//! every transaction with its plain-English summary as CSV or JSON Lines,
+//! and `GET /exports/:id` reports progress until the file can be downloaded.
+//! Jobs and their files live in their own SQLite database so they survive a
+//! restart, and are deleted once they are older than the retention period.
+//!
+//! Exports are tied to the `X-API-Key` that created them: only that key can
+//! see or download a job, and each key may start a limited number per hour.
+//! Keys are stored as the same fingerprint the audit log uses.
+//!
+//! Configuration (environment):
+//! - `EXPORTS_ENABLED`: `true` or `1` to accept exports (default off).
+//! - `EXPORTS_DB_PATH`: SQLite file (default `stellar-explain-exports.db`).
+//! - `EXPORTS_PER_HOUR`: exports each API key may start per hour (default 10).
+//! - `EXPORTS_RETENTION_HOURS`: hours to keep jobs and files (default 24).
+
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+use crate::errors::AppError;
+use crate::explain::statement::{StatementTransaction, statement_transaction};
+use crate::services::account::{AccountService, HistoryLimits};
+use crate::services::horizon_api::HorizonApi;
+use crate::services::storage::StorageError;
+
+/// How often expired jobs are pruned.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// What one export covers.
+pub const EXPORT_LIMITS: HistoryLimits = HistoryLimits {
+    max_transactions: 10_000,
+    max_pages: 100,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportConfig {
+    pub db_path: String,
+    pub max_per_hour: u32,
+    pub retention: Duration,
+}
+
+impl ExportConfig {
+    /// Read the export settings, or `None` when exports are not enabled.
+    pub fn from_env() -> Option<Self> {
+        let enabled = env::var("EXPORTS_ENABLED")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let positive = |name: &str, default: u64| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(default)
+        };
+
+        Some(Self {
+            db_path: env::var("EXPORTS_DB_PATH")
+                .unwrap_or_else(|_| "stellar-explain-exports.db".to_string()),
+            max_per_hour: positive("EXPORTS_PER_HOUR", 10) as u32,
+            retention: Duration::from_secs(positive("EXPORTS_RETENTION_HOURS", 24) * 60 * 60),
+        })
+    }
+}
+
+/// The store and limits the `/exports` routes share.
+#[derive(Clone)]
+pub struct ExportContext {
+    pub store: Arc<ExportStore>,
+    pub max_per_hour: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportFormat {
+    /// One row per transaction, with a header row.
+    Csv,
+    /// One JSON object per line.
+    Jsonl,
+}
+
+impl ExportFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "csv" => Some(ExportFormat::Csv),
+            "jsonl" => Some(ExportFormat::Jsonl),
+            _ => None,
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Jsonl => "application/x-ndjson",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ExportStatus {
+    /// Waiting to start.
+    Pending,
+    /// Reading the account's history.
+    Running,
+    /// The file is ready at `download_url`.
+    Completed,
+    /// Gave up; `error` says why.
+    Failed,
+}
+
+impl ExportStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            ExportStatus::Pending => "pending",
+            ExportStatus::Running => "running",
+            ExportStatus::Completed => "completed",
+            ExportStatus::Failed => "failed",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        match s {
+            "pending" => Some(ExportStatus::Pending),
+            "running" => Some(ExportStatus::Running),
+            "completed" => Some(ExportStatus::Completed),
+            "failed" => Some(ExportStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+/// An export job as its creator sees it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema)]
+pub struct ExportJob {
+    pub id: String,
+    pub address: String,
+    /// First day covered, "YYYY-MM-DD" in UTC.
+    pub from: String,
+    /// Last day covered, inclusive.
+    pub to: String,
+    pub format: ExportFormat,
+    pub status: ExportStatus,
+    /// RFC 3339, UTC.
+    pub created_at: String,
+    pub completed_at: Option<String>,
+    /// Transactions written, once completed.
+    pub row_count: Option<u64>,
+    /// More transactions fell in the range than an export holds; the newest
+    /// were written.
+    pub truncated: bool,
+    pub error: Option<String>,
+    /// Where to fetch the file, once completed.
+    pub download_url: Option<String>,
+}
+
+/// A new job, before it is stored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NewExport {
+    pub requester: String,
+    pub address: String,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub format: ExportFormat,
+}
+
+/// Format a timestamp the way jobs store it, so string comparison orders
+/// them chronologically.
+pub fn export_timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// SQLite-backed export jobs and their files.
+pub struct ExportStore {
+    conn: Mutex<Connection>,
+}
+
+const JOB_COLUMNS: &str = "id, address, from_date, to_date, format, status, created_at,
+     completed_at, row_count, truncated, error";
+
+impl ExportStore {
+    /// Open (creating if needed) the database at `path`. Jobs left pending or
+    /// running by a previous process are marked failed; nothing resumes them.
+    pub fn open(path: &str) -> Result<Self, StorageError> {
+        let store = Self::with_connection(Connection::open(path)?)?;
+        let interrupted = store.conn().execute(
+            "UPDATE export_jobs SET status = 'failed', error = ?1
+             WHERE status IN ('pending', 'running')",
+            params!["The server restarted before the export finished; start a new one."],
+        )?;
+        if interrupted > 0 {
+            warn!(interrupted, "export_jobs_interrupted");
+        }
+        Ok(store)
+    }
+
+    /// An in-memory store, used by tests.
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, StorageError> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS export_jobs (
+                id            TEXT PRIMARY KEY,
+                requester     TEXT NOT NULL,
+                address       TEXT NOT NULL,
+                from_date     TEXT NOT NULL,
+                to_date       TEXT NOT NULL,
+                format        TEXT NOT NULL,
+                status        TEXT NOT NULL,
+                created_at    TEXT NOT NULL,
+                completed_at  TEXT,
+                row_count     INTEGER,
+                truncated     INTEGER NOT NULL DEFAULT 0,
+                error         TEXT,
+                content       BLOB
+            );
+            CREATE INDEX IF NOT EXISTS export_jobs_requester
+                ON export_jobs (requester, created_at);
+            CREATE INDEX IF NOT EXISTS export_jobs_created_at ON export_jobs (created_at);",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Record a pending job created at `now` and return it.
+    pub fn create(
+        &self,
+        export: &NewExport,
+        now: DateTime<Utc>,
+    ) -> Result<ExportJob, StorageError> {
+        let id = uuid::Uuid::new_v4().to_string();
+        self.conn().execute(
+            "INSERT INTO export_jobs
+                 (id, requester, address, from_date, to_date, format, status, created_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+            params![
+                id,
+                export.requester,
+                export.address,
+                export.from.to_string(),
+                export.to.to_string(),
+                export.format.as_str(),
+                ExportStatus::Pending.as_str(),
+                export_timestamp(now),
+            ],
+        )?;
+        Ok(self
+            .get(&id, &export.requester)?
+            .expect("the job was just inserted"))
+    }
+
+    /// The job, if it exists and `requester` created it.
+    pub fn get(&self, id: &str, requester: &str) -> Result<Option<ExportJob>, StorageError> {
+        Ok(self
+            .conn()
+            .query_row(
+                &format!("SELECT {JOB_COLUMNS} FROM export_jobs WHERE id = ?1 AND requester = ?2"),
+                params![id, requester],
+                job_from_row,
+            )
+            .optional()?)
+    }
+
+    /// Jobs `requester` created at or after `since`.
+    pub fn created_since(
+        &self,
+        requester: &str,
+        since: DateTime<Utc>,
+    ) -> Result<u64, StorageError> {
+        let count: i64 = self.conn().query_row(
+            "SELECT COUNT(*) FROM export_jobs WHERE requester = ?1 AND created_at >= ?2",
+            params![requester, export_timestamp(since)],
+            |row| row.get(0),
+        )?;
+        Ok(count as u64)
+    }
+
+    pub fn start(&self, id: &str) -> Result<(), StorageError> {
+        self.conn().execute(
+            "UPDATE export_jobs SET status = 'running' WHERE id = ?1",
+            params![id],
+        )?;
+        Ok(())
+    }
+
+    pub fn complete(
+        &self,
+        id: &str,
+        content: &[u8],
+        row_count: u64,
+        truncated: bool,
+        now: DateTime<Utc>,
+    ) -> Result<(), StorageError> {
+        self.conn().execute(
+            "UPDATE export_jobs
+             SET status = 'completed', content = ?2, row_count = ?3, truncated = ?4,
+                 completed_at = ?5
+             WHERE id = ?1",
+            params![
+                id,
+                content,
+                row_count as i64,
+                truncated,
+                export_timestamp(now)
+            ],
+        )?;
+        Ok(())
+    }
+
+    pub fn fail(&self, id: &str, error: &str, now: DateTime<Utc>) -> Result<(), StorageError> {
+        self.conn().execute(
+            "UPDATE export_jobs SET status = 'failed', error = ?2, completed_at = ?3
+             WHERE id = ?1",
+            params![id, error, export_timestamp(now)],
+        )?;
+        Ok(())
+    }
+
+    /// A completed job's format and file, if `requester` created it.
+    pub fn content(
+        &self,
+        id: &str,
+        requester: &str,
+    ) -> Result<Option<(ExportFormat, Vec<u8>)>, StorageError> {
+        Ok(self
+            .conn()
+            .query_row(
+                "SELECT format, content FROM export_jobs
+                 WHERE id = ?1 AND requester = ?2 AND status = 'completed'",
+                params![id, requester],
+                |row| {
+                    let format: String = row.get(0)?;
+                    Ok((
+                        // Only this module writes the column.
+                        ExportFormat::parse(&format).unwrap_or(ExportFormat::Csv),
+                        row.get::<_, Option<Vec<u8>>>(1)?.unwrap_or_default(),
+                    ))
+                },
+            )
+            .optional()?)
+    }
+
+    /// Delete jobs created before `cutoff`. Returns the number deleted.
+    pub fn prune_before(&self, cutoff: DateTime<Utc>) -> Result<usize, StorageError> {
+        Ok(self.conn().execute(
+            "DELETE FROM export_jobs WHERE created_at < ?1",
+            params![export_timestamp(cutoff)],
+        )?)
+    }
+}
+
+fn job_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<ExportJob> {
+    let id: String = row.get(0)?;
+    let format: String = row.get(4)?;
+    let status: String = row.get(5)?;
+    // Only this module writes the columns.
+    let status = ExportStatus::parse(&status).unwrap_or(ExportStatus::Failed);
+    Ok(ExportJob {
+        address: row.get(1)?,
+        from: row.get(2)?,
+        to: row.get(3)?,
+        format: ExportFormat::parse(&format).unwrap_or(ExportFormat::Csv),
+        status,
+        created_at: row.get(6)?,
+        completed_at: row.get(7)?,
+        row_count: row.get::<_, Option<i64>>(8)?.map(|n| n as u64),
+        truncated: row.get(9)?,
+        error: row.get(10)?,
+        download_url: (status == ExportStatus::Completed)
+            .then(|| format!("/exports/{id}/download")),
+        id,
+    })
+}
+
+/// Read the job's history and store the file, or the reason it failed.
+pub async fn run_export<H: HorizonApi>(client: Arc<H>, store: Arc<ExportStore>, job: ExportJob) {
+    let (Ok(from), Ok(to)) = (job.from.parse::<NaiveDate>(), job.to.parse::<NaiveDate>()) else {
+        // Dates are validated before a job is created.
+        return;
+    };
+    if let Err(err) = store.start(&job.id) {
+        error!(id = %job.id, error = %err, "export_start_failed");
+    }
+
+    let service = AccountService::new(client);
+    let outcome = match service.history(&job.address, from, to, EXPORT_LIMITS).await {
+        Ok((entries, truncated)) => {
+            let rows: Vec<_> = entries.iter().map(statement_transaction).collect();
+            let content = render(job.format, &rows);
+            store.complete(&job.id, &content, rows.len() as u64, truncated, Utc::now())
+        }
+        Err(err) => {
+            warn!(id = %job.id, address = %job.address, error = ?err, "export_history_failed");
+            // The same message the synchronous endpoints would return.
+            let message = AppError::from(err).to_api_error().error.message;
+            store.fail(&job.id, &message, Utc::now())
+        }
+    };
+    match outcome {
+        Ok(()) => info!(id = %job.id, address = %job.address, "export_finished"),
+        Err(err) => error!(id = %job.id, error = %err, "export_save_failed"),
+    }
+}
+
+/// Write transactions as CSV (with a header row) or JSON Lines.
+pub fn render(format: ExportFormat, rows: &[StatementTransaction]) -> Vec<u8> {
+    let mut out = String::new();
+    match format {
+        ExportFormat::Csv => {
+            out.push_str("created_at,hash,successful,summary\n");
+            for row in rows {
+                let fields = [
+                    csv_field(&row.created_at),
+                    csv_field(&row.hash),
+                    row.successful.to_string(),
+                    csv_field(&row.summary),
+                ];
+                out.push_str(&fields.join(","));
+                out.push('\n');
+            }
+        }
+        ExportFormat::Jsonl => {
+            for row in rows {
+                // Plain strings and booleans always serialize.
+                out.push_str(&serde_json::to_string(row).unwrap_or_default());
+                out.push('\n');
+            }
+        }
+    }
+    out.into_bytes()
+}
+
+/// Quote a CSV field when it holds a comma, quote, or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Delete jobs older than `retention`, once an hour.
+pub async fn run_retention(store: Arc<ExportStore>, retention: Duration) {
+    loop {
+        let task_store = Arc::clone(&store);
+        let result = tokio::task::spawn_blocking(move || {
+            let cutoff =
+                Utc::now() - chrono::Duration::from_std(retention).unwrap_or(chrono::Duration::MAX);
+            task_store.prune_before(cutoff)
+        })
+        .await;
+        match result {
+            Ok(Ok(pruned)) => info!(pruned, "export_jobs_pruned"),
+            Ok(Err(err)) => error!(error = %err, "export_jobs_prune_failed"),
+            Err(err) => error!(error = %err, "export_jobs_prune_panicked"),
+        }
+        tokio::time::sleep(PRUNE_INTERVAL).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    fn new_export(requester: &str) -> NewExport {
+        NewExport {
+            requester: requester.to_string(),
+            address: "GME".to_string(),
+            from: "2025-03-01".parse().unwrap(),
+            to: "2025-03-31".parse().unwrap(),
+            format: ExportFormat::Jsonl,
+        }
+    }
+
+    fn row(summary: &str) -> StatementTransaction {
+        StatementTransaction {
+            hash: "abc".to_string(),
+            created_at: "2025-03-01T08:00:00Z".to_string(),
+            successful: false,
+            summary: summary.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_csv_quotes_fields_that_need_it() {
+        let csv = render(
+            ExportFormat::Csv,
+            &[row("Paid \"rent\", March.\nSecond line.")],
+        );
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "created_at,hash,successful,summary\n\
+             2025-03-01T08:00:00Z,abc,false,\"Paid \"\"rent\"\", March.\nSecond line.\"\n"
+        );
+    }
+
+    #[test]
+    fn test_jsonl_writes_one_object_per_line() {
+        let jsonl = String::from_utf8(render(ExportFormat::Jsonl, &[row("a"), row("b")])).unwrap();
+        let lines: Vec<&str> = jsonl.lines().collect();
+        assert_eq!(lines.len(), 2);
+        let first: serde_json::Value = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(first["summary"], "a");
+        assert_eq!(first["successful"], false);
+    }
+
+    #[test]
+    fn test_store_lifecycle_and_pruning() {
+        let store = ExportStore::open_in_memory().unwrap();
+        let job = store
+            .create(&new_export("key:1"), at("2025-04-01T10:00:00Z"))
+            .unwrap();
+        store
+            .create(&new_export("key:1"), at("2025-04-01T11:30:00Z"))
+            .unwrap();
+
+        assert_eq!(
+            store
+                .created_since("key:1", at("2025-04-01T10:30:00Z"))
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            store
+                .created_since("key:2", at("2025-04-01T00:00:00Z"))
+                .unwrap(),
+            0
+        );
+        assert!(store.content(&job.id, "key:1").unwrap().is_none());
+
+        store
+            .complete(&job.id, b"{}\n", 1, true, at("2025-04-01T10:01:00Z"))
+            .unwrap();
+        let done = store.get(&job.id, "key:1").unwrap().unwrap();
+        assert_eq!(done.status, ExportStatus::Completed);
+        assert_eq!(done.row_count, Some(1));
+        assert!(done.truncated);
+        assert_eq!(done.completed_at.as_deref(), Some("2025-04-01T10:01:00Z"));
+        assert_eq!(
+            store.content(&job.id, "key:1").unwrap(),
+            Some((ExportFormat::Jsonl, b"{}\n".to_vec()))
+        );
+        assert!(store.get(&job.id, "key:2").unwrap().is_none());
+
+        assert_eq!(store.prune_before(at("2025-04-01T11:00:00Z")).unwrap(), 1);
+        assert!(store.get(&job.id, "key:1").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_reopening_fails_unfinished_jobs() {
+        let path = env::temp_dir().join(format!("exports-{}.db", uuid::Uuid::new_v4()));
+        let path = path.to_str().unwrap();
+        let id = {
+            let store = ExportStore::open(path).unwrap();
+            let job = store.create(&new_export("key:1"), Utc::now()).unwrap();
+            store.start(&job.id).unwrap();
+            job.id
+        };
+
+        let store = ExportStore::open(path).unwrap();
+        let job = store.get(&id, "key:1").unwrap().unwrap();
+        assert_eq!(job.status, ExportStatus::Failed);
+        assert!(job.error.unwrap().contains("restarted"));
+        drop(store);
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -0,0 +1,188 @@
+//! Fund testnet accounts through friendbot.
+//!
+//! `POST /testnet/fund` asks friendbot to create an account with test
+//! lumens and answers with the explanation of the funding transaction, so a
+//! tutorial can go from a fresh keypair to an explained transaction in one
+//! call. Friendbot only funds accounts that do not exist yet.
+//!
+//! Configuration (environment):
+//! - `FRIENDBOT_URL`: friendbot to call. Defaults to SDF's friendbot on
+//!   testnet. The public network has no friendbot, so there the endpoint is
+//!   only available when this is set, e.g. for a standalone network.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+use std::env;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::errors::AppError;
+
+/// How long friendbot gets to submit the funding transaction and see it
+/// included.
+const FRIENDBOT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Why an account could not be funded.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FundingError {
+    /// The account already exists.
+    AlreadyFunded,
+    /// Friendbot could not be reached or did not fund the account.
+    Unavailable,
+}
+
+impl FundingError {
+    /// The error a funding request for `address` answers with.
+    pub fn into_error(self, address: &str) -> AppError {
+        match self {
+            FundingError::AlreadyFunded => AppError::BadRequest(format!(
+                "{address} is already funded. Friendbot only funds new accounts."
+            )),
+            FundingError::Unavailable => {
+                AppError::UpstreamFailure("Friendbot could not fund the account.".to_string())
+            }
+        }
+    }
+}
+
+/// Something that creates accounts with test lumens.
+#[async_trait]
+pub trait Faucet: Send + Sync {
+    /// Fund `address`, returning the hash of the funding transaction.
+    async fn fund(&self, address: &str) -> Result<String, FundingError>;
+}
+
+pub struct Friendbot {
+    client: Client,
+    url: String,
+}
+
+impl Friendbot {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(),
+            url: url.into(),
+        }
+    }
+
+    /// Send requests through `client`, normally the shared one from
+    /// [`HttpConfig::build_client`](crate::services::http::HttpConfig::build_client).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+/// The part of friendbot's answer, Horizon's transaction resource, that
+/// names the funding transaction.
+#[derive(Deserialize)]
+struct FriendbotResponse {
+    hash: String,
+}
+
+#[async_trait]
+impl Faucet for Friendbot {
+    async fn fund(&self, address: &str) -> Result<String, FundingError> {
+        let res = self
+            .client
+            .get(&self.url)
+            .query(&[("addr", address)])
+            .timeout(FRIENDBOT_TIMEOUT)
+            .send()
+            .await
+            .map_err(|err| {
+                warn!(error = %err, "friendbot_unreachable");
+                FundingError::Unavailable
+            })?;
+
+        let status = res.status().as_u16();
+        let body = res.text().await.map_err(|_| FundingError::Unavailable)?;
+        match status {
+            200 => serde_json::from_str::<FriendbotResponse>(&body)
+                .map(|response| response.hash)
+                .map_err(|_| FundingError::Unavailable),
+            // Friendbot reports existing accounts either as a plain message
+            // or with the create_account result code, depending on version.
+            400 if is_already_funded(&body) => Err(FundingError::AlreadyFunded),
+            _ => {
+                warn!(status, "friendbot_funding_failed");
+                Err(FundingError::Unavailable)
+            }
+        }
+    }
+}
+
+fn is_already_funded(body: &str) -> bool {
+    [
+        "already funded",
+        "op_already_exists",
+        "createAccountAlreadyExist",
+    ]
+    .iter()
+    .any(|marker| body.contains(marker))
+}
+
+/// The friendbot to use: `FRIENDBOT_URL`, or `default` (the network's own
+/// friendbot). None when neither is set.
+pub fn friendbot_url(default: Option<&str>) -> Option<String> {
+    env::var("FRIENDBOT_URL")
+        .ok()
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty())
+        .or_else(|| default.map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    const ADDRESS: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+
+    #[tokio::test]
+    async fn test_funding_returns_the_transaction_hash() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/").query_param("addr", ADDRESS);
+            then.status(200)
+                .json_body(json!({ "hash": "abc123", "successful": true, "ledger": 10 }));
+        });
+
+        let hash = Friendbot::new(server.base_url()).fund(ADDRESS).await;
+
+        assert_eq!(hash, Ok("abc123".to_string()));
+        mock.assert();
+    }
+
+    #[tokio::test]
+    async fn test_existing_account_is_already_funded() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/");
+            then.status(400).json_body(json!({
+                "title": "Transaction Failed",
+                "status": 400,
+                "extras": { "result_codes": { "transaction": "tx_failed", "operations": ["op_already_exists"] } }
+            }));
+        });
+
+        let result = Friendbot::new(server.base_url()).fund(ADDRESS).await;
+
+        assert_eq!(result, Err(FundingError::AlreadyFunded));
+    }
+
+    #[tokio::test]
+    async fn test_server_error_is_unavailable() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/");
+            then.status(502);
+        });
+
+        let result = Friendbot::new(server.base_url()).fund(ADDRESS).await;
+
+        assert_eq!(result, Err(FundingError::Unavailable));
+    }
+}
@@ -0,0 +1,299 @@
+//! An account's entire history, explained and streamed as NDJSON.
+//!
+//! `/account/:address/transactions` answers at most 50 transactions a
+//! request. For everything, a background task walks the account's
+//! transactions a Horizon page at a time, explains each, and writes one JSON
+//! object per line. Lines queue in a small channel: when the client reads
+//! slowly the channel fills and the walk waits, so no more than a page is
+//! held however long the history is, and the walk stops once the client
+//! goes away.
+//!
+//! Every line carries the `cursor` that resumes after it. A stream that ends
+//! early (Horizon keeps failing, or the client disconnects) can be picked up
+//! again from the last line received. Pages are read through the Horizon
+//! pager, which waits out 429s; a walk that still cannot continue ends with
+//! an `{"error": ..., "cursor": ...}` line.
+//!
+//! In privacy mode each line is masked before it is written, like the rows
+//! of `/account/:address/statement`.
+
+use axum::body::{Body, Bytes};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::io;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+use crate::errors::{AppError, HorizonError};
+use crate::explain::statement::{StatementTransaction, statement_transaction};
+use crate::services::account::{AccountService, HistoryPage};
+use crate::services::horizon_api::HorizonApi;
+use crate::services::privacy::apply_privacy;
+
+pub const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+
+/// Lines buffered ahead of the client before the walk waits.
+pub const LINES_IN_FLIGHT: usize = 32;
+
+/// One transaction of the stream.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryLine {
+    /// Pass as `cursor` to resume after this transaction.
+    pub cursor: Option<String>,
+    #[serde(flatten)]
+    pub transaction: StatementTransaction,
+}
+
+pub struct HistoryWalk<H> {
+    service: AccountService<H>,
+    address: String,
+    order: String,
+    privacy: bool,
+}
+
+impl<H: HorizonApi + 'static> HistoryWalk<H> {
+    pub fn new(client: Arc<H>, address: String, order: String) -> Self {
+        Self {
+            service: AccountService::new(client),
+            address,
+            order,
+            privacy: false,
+        }
+    }
+
+    /// Mask addresses and memos in every line.
+    pub fn with_privacy(mut self, privacy: bool) -> Self {
+        self.privacy = privacy;
+        self
+    }
+
+    /// The page after `cursor`. Fails at once, so the first page can answer
+    /// with an error status before anything is streamed.
+    pub async fn page(&self, cursor: Option<&str>) -> Result<HistoryPage, HorizonError> {
+        self.service
+            .history_page(&self.address, cursor, &self.order)
+            .await
+    }
+
+    /// Stream `first` and every page after it as the response body.
+    pub fn stream(self, first: HistoryPage) -> Body {
+        let (sender, mut receiver) = mpsc::channel::<io::Result<Bytes>>(LINES_IN_FLIGHT);
+        tokio::spawn(async move {
+            let mut page = first;
+            let mut streamed: u64 = 0;
+            loop {
+                for (cursor, entry) in page.entries {
+                    let line = HistoryLine {
+                        cursor,
+                        transaction: statement_transaction(&entry),
+                    };
+                    let Ok(line) = apply_privacy(line, self.privacy) else {
+                        warn!(address = %self.address, streamed, "full_history_redaction_failed");
+                        return;
+                    };
+                    if sender.send(Ok(ndjson_line(&line))).await.is_err() {
+                        info!(address = %self.address, streamed, "full_history_client_gone");
+                        return;
+                    }
+                    streamed += 1;
+                }
+                let Some(next) = page.next_cursor else {
+                    info!(address = %self.address, streamed, "full_history_completed");
+                    return;
+                };
+                page = match self.page(Some(&next)).await {
+                    Ok(page) => page,
+                    Err(err) => {
+                        warn!(
+                            address = %self.address,
+                            cursor = %next,
+                            streamed,
+                            error = ?err,
+                            "full_history_aborted"
+                        );
+                        let error = AppError::from(err).to_api_error().error;
+                        let line = json!({ "error": error, "cursor": next });
+                        let _ = sender.send(Ok(ndjson_line(&line))).await;
+                        return;
+                    }
+                };
+            }
+        });
+        Body::from_stream(futures_util::stream::poll_fn(move |cx| {
+            receiver.poll_recv(cx)
+        }))
+    }
+}
+
+fn ndjson_line<T: Serialize>(value: &T) -> Bytes {
+    // Strings, booleans and error codes always serialize.
+    let mut line = serde_json::to_vec(value).unwrap_or_default();
+    line.push(b'\n');
+    Bytes::from(line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::account::{Account, AccountFlags};
+    use crate::services::circuit::CircuitConfig;
+    use crate::services::horizon::{
+        HorizonAccountTransaction, HorizonClient, HorizonOperation, HorizonTransaction,
+    };
+    use crate::services::horizon_api::FakeHorizon;
+    use axum::body::to_bytes;
+    use httpmock::prelude::*;
+    use serde_json::Value;
+    use std::time::Duration;
+
+    const ME: &str = "GME";
+
+    fn listed(n: usize) -> HorizonAccountTransaction {
+        HorizonAccountTransaction {
+            hash: format!("tx{n}"),
+            successful: true,
+            created_at: "2025-03-01T00:00:00Z".to_string(),
+            source_account: Some(ME.to_string()),
+            operation_count: 1,
+            memo_type: None,
+            memo: None,
+            memo_bytes: None,
+            paging_token: Some(format!("{n}")),
+        }
+    }
+
+    fn horizon(count: usize) -> Arc<FakeHorizon> {
+        Arc::new(
+            FakeHorizon::new()
+                .with_account(Account {
+                    id: ME.to_string(),
+                    account_id: ME.to_string(),
+                    sequence: "1".to_string(),
+                    num_signers: 1,
+                    signers: Vec::new(),
+                    master_key_weight: 1,
+                    thresholds: Default::default(),
+                    balances: Vec::new(),
+                    flags: AccountFlags {
+                        auth_required: false,
+                        auth_revocable: false,
+                        auth_immutable: false,
+                        auth_clawback_enabled: false,
+                    },
+                    home_domain: None,
+                    memo_required: false,
+                })
+                .with_account_transactions(ME, (0..count).map(listed).collect()),
+        )
+    }
+
+    async fn lines(body: Body) -> Vec<Value> {
+        let bytes = to_bytes(body, usize::MAX).await.unwrap();
+        String::from_utf8(bytes.to_vec())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_walks_every_page_and_resumes_from_a_cursor() {
+        let walk = HistoryWalk::new(horizon(450), ME.to_string(), "asc".to_string());
+        let first = walk.page(None).await.unwrap();
+        let all = lines(walk.stream(first)).await;
+
+        assert_eq!(all.len(), 450);
+        assert_eq!(all[0]["hash"], "tx0");
+        assert_eq!(all[449]["hash"], "tx449");
+        assert_eq!(all[449]["cursor"], "449");
+
+        let walk = HistoryWalk::new(horizon(450), ME.to_string(), "asc".to_string());
+        let first = walk.page(Some("399")).await.unwrap();
+        let rest = lines(walk.stream(first)).await;
+        assert_eq!(rest.len(), 50);
+        assert_eq!(rest[0]["hash"], "tx400");
+    }
+
+    #[tokio::test]
+    async fn test_privacy_masks_every_line() {
+        const FRIEND: &str = "GCKFBEIYV2U22IO2BJ4KVJOIP7XPWQGQFKKWXR6DOSJBV7STMAQSMTGG";
+        let payment = HorizonOperation {
+            id: "1".to_string(),
+            transaction_hash: "tx0".to_string(),
+            operation_type: "payment".to_string(),
+            from: Some(ME.to_string()),
+            to: Some(FRIEND.to_string()),
+            amount: Some("5.0000000".to_string()),
+            asset_type: Some("native".to_string()),
+            ..Default::default()
+        };
+        let horizon = FakeHorizon::clone(&horizon(1)).with_transaction(
+            HorizonTransaction {
+                hash: "tx0".to_string(),
+                successful: true,
+                fee_charged: "100".to_string(),
+                ..Default::default()
+            },
+            vec![payment],
+        );
+        let walk = HistoryWalk::new(Arc::new(horizon), ME.to_string(), "asc".to_string())
+            .with_privacy(true);
+        let first = walk.page(None).await.unwrap();
+        let all = lines(walk.stream(first)).await;
+
+        let summary = all[0]["summary"].as_str().unwrap();
+        assert!(summary.contains("GCKFB…MTGG"), "{summary}");
+        assert!(!summary.contains(FRIEND));
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_walk_ends_with_an_error_line() {
+        let server = MockServer::start();
+        let records: Vec<Value> = (0..200)
+            .map(|n| {
+                json!({
+                    "hash": format!("tx{n}"), "successful": true,
+                    "created_at": "2025-03-01T00:00:00Z", "operation_count": 0,
+                    "paging_token": format!("{n}")
+                })
+            })
+            .collect();
+        // Mocks are matched in the order they were added.
+        let limited = server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/accounts/{ME}/transactions"))
+                .query_param("cursor", "199");
+            then.status(429);
+        });
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/accounts/{ME}/transactions"));
+            then.status(200).json_body(json!({
+                "_embedded": { "records": records },
+                "_links": { "next": { "href": format!("{}/accounts/{ME}/transactions?cursor=199", server.base_url()) } }
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path_contains("/operations");
+            then.status(200)
+                .json_body(json!({ "_embedded": { "records": [] } }));
+        });
+
+        // Keep the circuit closed so every retry reaches Horizon.
+        let client = Arc::new(
+            HorizonClient::new(server.base_url()).with_circuit(CircuitConfig {
+                failure_threshold: 100,
+                cooldown: Duration::from_secs(1),
+            }),
+        );
+        let walk = HistoryWalk::new(client, ME.to_string(), "asc".to_string());
+        let first = walk.page(None).await.unwrap();
+        let all = lines(walk.stream(first)).await;
+
+        assert_eq!(all.len(), 201);
+        assert_eq!(all[200]["error"]["code"], "RATE_LIMITED");
+        assert_eq!(all[200]["cursor"], "199");
+        // One retry budget, the pager's: the first request and two retries.
+        limited.assert_hits(3);
+    }
+}
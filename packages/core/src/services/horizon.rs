@@ -1,12 +1,26 @@
+use futures_util::stream::{self, Stream, StreamExt, TryStreamExt};
 use reqwest::Client;
 use serde::Deserialize;
+use serde::de::DeserializeOwned;
 use std::collections::HashMap;
+use std::pin::pin;
 use std::sync::{Arc, RwLock};
 use std::time::{Duration, Instant};
+use tracing::{Instrument, debug, info_span, warn};
 
 use crate::errors::HorizonError;
-use crate::models::account::{Account, AccountFlags, Balance};
-use crate::models::fee::FeeStats;
+use crate::models::account::{Account, AccountFlags, AccountLinks, Balance, Signer, Thresholds};
+use crate::models::amount::Amount;
+use crate::models::asset::{Clawback, StellarTomlStatus, TomlIdentity};
+use crate::models::claimable_balance::{ClaimPredicate, ClaimableBalance, Claimant};
+use crate::models::fee::{FeeDistribution, FeeMarket, FeeStats, LedgerLoad};
+use crate::models::ledger::Ledger;
+use crate::models::liquidity_pool::{LiquidityPool, PoolActivity, PoolActivityKind, PoolReserve};
+use crate::models::offer::Offer;
+use crate::models::trade::{TradeBucket, TradeFill};
+use crate::services::circuit::{CircuitBreaker, CircuitConfig, CircuitSnapshot};
+use crate::services::horizon_pool::HorizonPool;
+use crate::services::recording::{Recorder, RecordingConfig, RecordingMode};
 
 // ── Horizon response structs ───────────────────────────────────────────────
 
@@ -20,24 +34,63 @@ pub struct HorizonResultCodes {
 }
 
 /// The `extras` envelope that Horizon includes in error responses.
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct HorizonExtras {
     pub result_codes: Option<HorizonResultCodes>,
+    /// Base64 envelope of a failed submission.
+    pub envelope_xdr: Option<String>,
+    /// Base64 `TransactionResult` of a failed submission.
+    pub result_xdr: Option<String>,
 }
 
-#[derive(Debug, Deserialize, Clone)]
+/// What Horizon answered to a transaction submission.
+#[derive(Debug, Clone)]
+pub enum HorizonSubmission {
+    /// The transaction succeeded and Horizon returned its record.
+    Included(Box<HorizonTransaction>),
+    /// The transaction failed, in a ledger or before reaching one. Horizon
+    /// does not say which.
+    Failed(HorizonExtras),
+    /// Horizon stopped waiting; the transaction may still be included.
+    TimedOut,
+}
+
+#[derive(Debug, Deserialize)]
+struct HorizonProblem {
+    #[serde(default)]
+    extras: HorizonExtras,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
 pub struct HorizonTransaction {
     pub hash: String,
     pub successful: bool,
     pub fee_charged: String,
+    /// Account that supplied the sequence number.
+    pub source_account: Option<String>,
+    pub source_account_sequence: Option<String>,
+    /// Account that paid the fee; the fee bump payer for fee bumps.
+    pub fee_account: Option<String>,
     pub memo_type: Option<String>,
     pub memo: Option<String>,
+    /// Base64 of a text memo's raw bytes, which need not be UTF-8.
+    pub memo_bytes: Option<String>,
     /// ISO 8601 timestamp of ledger close, e.g. "2024-01-15T14:32:00Z"
     pub created_at: Option<String>,
     /// Ledger sequence number in which this transaction was included.
     pub ledger: Option<u64>,
+    /// Maximum fee the submitter was willing to pay, in stroops.
+    pub max_fee: Option<String>,
+    /// Lower time bound (ISO 8601). Horizon sends the Unix epoch when unset.
+    pub valid_after: Option<String>,
+    /// Upper time bound (ISO 8601), if the transaction set one.
+    pub valid_before: Option<String>,
     /// Result codes present on fetched failed transactions.
     pub result_codes: Option<HorizonResultCodes>,
+    /// Base64 `TransactionMeta`; carries contract events for Soroban transactions.
+    pub result_meta_xdr: Option<String>,
+    /// Base64 `TransactionEnvelope`, signatures included.
+    pub envelope_xdr: Option<String>,
     /// Result codes nested under `extras` in Horizon submission error responses.
     pub extras: Option<HorizonExtras>,
 }
@@ -51,6 +104,12 @@ pub struct HorizonAccountTransaction {
     pub operation_count: u32,
     pub memo_type: Option<String>,
     pub memo: Option<String>,
+    /// Base64 of a text memo's raw bytes, which need not be UTF-8.
+    #[serde(default)]
+    pub memo_bytes: Option<String>,
+    /// Cursor that resumes a listing after this transaction.
+    #[serde(default)]
+    pub paging_token: Option<String>,
 }
 
 /// Raw Horizon account response shape.
@@ -63,10 +122,24 @@ struct HorizonAccount {
     pub sequence: String,
     pub balances: Vec<HorizonBalance>,
     pub signers: Vec<HorizonSigner>,
+    #[serde(default)]
+    pub thresholds: HorizonThresholds,
     pub flags: HorizonAccountFlags,
     /// Horizon sends "" when not set, never null or absent
     #[serde(default)]
     pub home_domain: String,
+    #[serde(default)]
+    pub sponsor: Option<String>,
+    /// Data entries, base64-encoded values by name.
+    #[serde(default)]
+    pub data: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct HorizonThresholds {
+    pub low_threshold: u32,
+    pub med_threshold: u32,
+    pub high_threshold: u32,
 }
 
 #[derive(Debug, Deserialize)]
@@ -77,12 +150,28 @@ struct HorizonBalance {
     #[serde(default)]
     pub asset_issuer: Option<String>,
     pub balance: String,
+    #[serde(default)]
+    pub limit: Option<String>,
+    #[serde(default)]
+    pub buying_liabilities: Option<String>,
+    #[serde(default)]
+    pub selling_liabilities: Option<String>,
+    #[serde(default)]
+    pub is_authorized: Option<bool>,
+    #[serde(default)]
+    pub is_authorized_to_maintain_liabilities: Option<bool>,
+    #[serde(default)]
+    pub liquidity_pool_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct HorizonSigner {
+    #[serde(default)]
+    pub key: String,
     #[serde(default)]
     pub weight: u32,
+    #[serde(default)]
+    pub sponsor: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Default)]
@@ -98,6 +187,22 @@ struct HorizonAccountFlags {
 }
 
 impl HorizonAccount {
+    fn into_links(self) -> AccountLinks {
+        AccountLinks {
+            account_id: self.account_id,
+            signers: self
+                .signers
+                .into_iter()
+                .map(|s| Signer {
+                    key: s.key,
+                    weight: s.weight,
+                    sponsor: s.sponsor,
+                })
+                .collect(),
+            sponsor: self.sponsor,
+        }
+    }
+
     fn into_domain(self) -> Account {
         let balances = self
             .balances
@@ -107,17 +212,45 @@ impl HorizonAccount {
                 asset_code: b.asset_code,
                 asset_issuer: b.asset_issuer,
                 balance: b.balance,
+                limit: b.limit,
+                buying_liabilities: b.buying_liabilities,
+                selling_liabilities: b.selling_liabilities,
+                is_authorized: b.is_authorized,
+                is_authorized_to_maintain_liabilities: b.is_authorized_to_maintain_liabilities,
+                liquidity_pool_id: b.liquidity_pool_id,
             })
             .collect();
 
         // Count signers with weight > 0 (weight 0 = revoked/removed)
         let num_signers = self.signers.iter().filter(|s| s.weight > 0).count() as u32;
+        let master_key_weight = self
+            .signers
+            .iter()
+            .find(|s| s.key == self.account_id)
+            .map_or(0, |s| s.weight);
+        let signers = self
+            .signers
+            .into_iter()
+            .filter(|s| s.weight > 0)
+            .map(|s| Signer {
+                key: s.key,
+                weight: s.weight,
+                sponsor: s.sponsor,
+            })
+            .collect();
 
         Account {
             id: self.id,
             account_id: self.account_id,
             sequence: self.sequence,
             num_signers,
+            signers,
+            master_key_weight,
+            thresholds: Thresholds {
+                low: self.thresholds.low_threshold,
+                medium: self.thresholds.med_threshold,
+                high: self.thresholds.high_threshold,
+            },
             balances,
             flags: AccountFlags {
                 auth_required: self.flags.auth_required,
@@ -130,18 +263,213 @@ impl HorizonAccount {
             } else {
                 Some(self.home_domain)
             },
+            memo_required: self
+                .data
+                .get(MEMO_REQUIRED_KEY)
+                .is_some_and(|value| value == MEMO_REQUIRED_VALUE),
         }
     }
 }
 
+/// Raw Horizon claimable balance. Predicates arrive as objects with one key
+/// set, e.g. `{"not": {"abs_before": "2025-03-03T00:00:00Z"}}`.
+#[derive(Debug, Deserialize)]
+struct HorizonClaimableBalance {
+    id: String,
+    asset: String,
+    amount: String,
+    #[serde(default)]
+    sponsor: Option<String>,
+    #[serde(default)]
+    claimants: Vec<HorizonClaimant>,
+    #[serde(default)]
+    last_modified_ledger: Option<u64>,
+    #[serde(default)]
+    flags: HorizonClaimableBalanceFlags,
+}
+
+#[derive(Debug, Deserialize)]
+struct HorizonClaimant {
+    destination: String,
+    predicate: HorizonPredicate,
+}
+
+#[derive(Debug, Deserialize, Default)]
+struct HorizonClaimableBalanceFlags {
+    #[serde(default)]
+    clawback_enabled: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct HorizonPredicate {
+    #[serde(default)]
+    and: Option<Vec<HorizonPredicate>>,
+    #[serde(default)]
+    or: Option<Vec<HorizonPredicate>>,
+    #[serde(default)]
+    not: Option<Box<HorizonPredicate>>,
+    #[serde(default)]
+    abs_before: Option<String>,
+    /// Unix seconds; the only form Horizon sends for times beyond year 9999.
+    #[serde(default)]
+    abs_before_epoch: Option<String>,
+    #[serde(default)]
+    rel_before: Option<String>,
+}
+
+impl HorizonPredicate {
+    fn into_domain(self) -> ClaimPredicate {
+        if let Some(all) = self.and {
+            return ClaimPredicate::And(all.into_iter().map(Self::into_domain).collect());
+        }
+        if let Some(any) = self.or {
+            return ClaimPredicate::Or(any.into_iter().map(Self::into_domain).collect());
+        }
+        if let Some(inner) = self.not {
+            return ClaimPredicate::Not(Box::new(inner.into_domain()));
+        }
+        let epoch_time = self
+            .abs_before_epoch
+            .and_then(|s| s.parse::<i64>().ok())
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .map(|t| t.format("%Y-%m-%dT%H:%M:%SZ").to_string());
+        if let Some(time) = self.abs_before.or(epoch_time) {
+            return ClaimPredicate::AbsBefore(time);
+        }
+        match self.rel_before.and_then(|s| s.parse().ok()) {
+            Some(seconds) => ClaimPredicate::RelBefore(seconds),
+            // `{"unconditional": true}`
+            None => ClaimPredicate::Unconditional,
+        }
+    }
+}
+
+impl HorizonClaimableBalance {
+    fn into_domain(self) -> ClaimableBalance {
+        ClaimableBalance {
+            id: self.id,
+            asset: self.asset,
+            amount: self.amount,
+            sponsor: self.sponsor,
+            claimants: self
+                .claimants
+                .into_iter()
+                .map(|c| Claimant {
+                    destination: c.destination,
+                    predicate: c.predicate.into_domain(),
+                })
+                .collect(),
+            last_modified_ledger: self.last_modified_ledger,
+            clawback_enabled: self.flags.clawback_enabled,
+        }
+    }
+}
+
+/// What Horizon's history says about an address with no live account entry.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountHistory {
+    /// The account was closed by an account_merge into another account.
+    Merged {
+        into: String,
+        closed_at: Option<String>,
+    },
+    /// The address has operation history, but its latest operation is not a merge.
+    Inactive,
+    /// Horizon has no record of the address ever being used.
+    NeverCreated,
+}
+
+/// Page size used when walking every record in a ledger.
+const LEDGER_PAGE_LIMIT: usize = 200;
+
+/// Page size Horizon uses when a request sets no `limit`.
+const HORIZON_DEFAULT_LIMIT: usize = 10;
+
+/// Pages of an offer's trades read to total what it sold.
+const OFFER_TRADE_PAGES: usize = 5;
+
+/// Times a page that failed to arrive is requested again.
+const MAX_PAGE_RETRIES: u32 = 2;
+
+/// Wait before the first retry of a page; doubled each time.
+const PAGE_RETRY_BACKOFF: Duration = Duration::from_millis(200);
+
+/// Wait before the first retry of a rate-limited page; doubled each time.
+const RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// The data entry an account sets to ask for memos (SEP-29), and its value,
+/// "1" in base64.
+const MEMO_REQUIRED_KEY: &str = "config.memo_required";
+const MEMO_REQUIRED_VALUE: &str = "MQ==";
+
 // ── HorizonClient ──────────────────────────────────────────────────────────
 
+/// How long a successful response may be replayed while Horizon is down.
+const STALE_TTL: Duration = Duration::from_secs(3600);
+
+/// Upper bound on responses kept for stale replay.
+const STALE_CAPACITY: usize = 1024;
+
+/// Status and body of a Horizon response, read in full.
+struct HorizonResponse {
+    status: u16,
+    body: Vec<u8>,
+}
+
+impl HorizonResponse {
+    fn json<T: DeserializeOwned>(&self) -> Result<T, HorizonError> {
+        serde_json::from_slice(&self.body).map_err(|_| HorizonError::InvalidResponse)
+    }
+}
+
+/// One page of a Horizon collection, with the cursors either side of it.
+#[derive(Debug)]
+pub struct Page<T> {
+    pub records: Vec<T>,
+    pub next_cursor: Option<String>,
+    pub prev_cursor: Option<String>,
+}
+
+impl<T> From<HorizonPage<T>> for Page<T> {
+    fn from(page: HorizonPage<T>) -> Self {
+        let cursor =
+            |link: Option<&HorizonLink>| extract_cursor(link.and_then(|l| l.href.as_deref()));
+        Self {
+            next_cursor: cursor(page._links.next.as_ref()),
+            prev_cursor: cursor(page._links.prev.as_ref()),
+            records: page._embedded.records,
+        }
+    }
+}
+
+/// The error for a 404 on a collection, which Horizon answers when the
+/// resource the collection belongs to does not exist.
+fn collection_not_found(path: &str) -> HorizonError {
+    match path.trim_start_matches('/').split('/').next() {
+        Some("accounts") => HorizonError::AccountNotFound,
+        Some("ledgers") => HorizonError::LedgerNotFound,
+        Some("liquidity_pools") => HorizonError::LiquidityPoolNotFound,
+        Some("claimable_balances") => HorizonError::ClaimableBalanceNotFound,
+        Some("offers") => HorizonError::OfferNotFound,
+        Some("transactions" | "operations") => HorizonError::TransactionNotFound,
+        _ => HorizonError::InvalidResponse,
+    }
+}
+
 #[derive(Clone)]
 pub struct HorizonClient {
     client: Client,
     base_url: String,
     #[allow(clippy::type_complexity)]
-    toml_cache: Arc<RwLock<HashMap<String, (Option<String>, Instant)>>>,
+    toml_cache: Arc<RwLock<HashMap<String, (Option<TomlIdentity>, Instant)>>>,
+    circuit: Arc<CircuitBreaker>,
+    /// Last successful body per URL, served when the circuit is open or a
+    /// live request fails.
+    #[allow(clippy::type_complexity)]
+    stale_cache: Arc<RwLock<HashMap<String, (Vec<u8>, Instant)>>>,
+    recorder: Option<Arc<Recorder>>,
+    /// Upstreams sharing the load; `base_url` is the primary among them.
+    pool: Option<Arc<HorizonPool>>,
 }
 
 impl HorizonClient {
@@ -150,24 +478,190 @@ impl HorizonClient {
             client: Client::new(),
             base_url: base_url.into(),
             toml_cache: Arc::new(RwLock::new(HashMap::new())),
+            circuit: Arc::new(CircuitBreaker::new(CircuitConfig::default())),
+            stale_cache: Arc::new(RwLock::new(HashMap::new())),
+            recorder: None,
+            pool: None,
+        }
+    }
+
+    /// Send requests through `client`, normally the shared one from
+    /// [`HttpConfig::build_client`](crate::services::http::HttpConfig::build_client).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_circuit(mut self, config: CircuitConfig) -> Self {
+        self.circuit = Arc::new(CircuitBreaker::new(config));
+        self
+    }
+
+    /// Save responses to, or answer from, the recordings in `config.dir`.
+    pub fn with_recording(mut self, config: RecordingConfig) -> Self {
+        self.recorder = Some(Arc::new(Recorder::new(config)));
+        self
+    }
+
+    /// Spread GETs over the upstreams of `pool`, failing over between them.
+    pub fn with_pool(mut self, pool: Arc<HorizonPool>) -> Self {
+        self.base_url = pool.primary().to_string();
+        self.pool = Some(pool);
+        self
+    }
+
+    fn replaying(&self) -> Option<&Recorder> {
+        self.recorder
+            .as_deref()
+            .filter(|recorder| recorder.mode() == RecordingMode::Replay)
+    }
+
+    pub fn circuit_snapshot(&self) -> CircuitSnapshot {
+        self.circuit.snapshot()
+    }
+
+    /// Responses held for stale replay; `None` if the cache lock is poisoned.
+    pub fn stale_cache_len(&self) -> Option<usize> {
+        self.stale_cache.read().ok().map(|cache| cache.len())
+    }
+
+    /// GET a Horizon URL through the circuit breaker.
+    ///
+    /// Network errors, 429s and 5xx responses count as failures. When the
+    /// circuit is open, or the request fails, a recent successful response
+    /// for the same URL is served instead if one is cached.
+    async fn get(&self, url: &str) -> Result<HorizonResponse, HorizonError> {
+        if let Some(recorder) = self.replaying() {
+            return match recorder.replay(url) {
+                Some((status, body)) => Ok(HorizonResponse { status, body }),
+                None => {
+                    warn!(url = %url, "horizon_recording_missing");
+                    Err(HorizonError::NetworkError)
+                }
+            };
+        }
+
+        if let Err(remaining) = self.circuit.check() {
+            return self.stale(url).ok_or(HorizonError::CircuitOpen {
+                retry_after: remaining,
+            });
+        }
+
+        let fetched = self.fetch(url).await;
+
+        if let (Some(recorder), Ok(res)) = (&self.recorder, &fetched) {
+            recorder.record(url, res.status, &res.body);
+        }
+
+        match fetched {
+            Ok(res) if res.status != 429 && res.status < 500 => {
+                self.circuit.record_success();
+                if res.status == 200 {
+                    self.remember(url, &res.body);
+                }
+                Ok(res)
+            }
+            failed => {
+                if self.circuit.record_failure() {
+                    warn!(upstream = "horizon", "circuit_opened");
+                }
+                match (self.stale(url), failed) {
+                    (Some(res), _) => Ok(res),
+                    (None, Ok(res)) if res.status == 429 => Err(HorizonError::RateLimited),
+                    (None, failed) => failed,
+                }
+            }
+        }
+    }
+
+    /// GET `url`, from each upstream in the pool in turn until one answers
+    /// well. Each attempt runs in a `horizon_upstream` span naming the
+    /// upstream.
+    async fn fetch(&self, url: &str) -> Result<HorizonResponse, HorizonError> {
+        let (Some(pool), Some(path)) = (&self.pool, url.strip_prefix(self.base_url.as_str()))
+        else {
+            return self
+                .send(url)
+                .instrument(info_span!("horizon_upstream", upstream = %self.base_url))
+                .await;
+        };
+
+        let mut fetched = Err(HorizonError::NetworkError);
+        for upstream in pool.candidates() {
+            fetched = self
+                .send(&format!("{upstream}{path}"))
+                .instrument(info_span!("horizon_upstream", upstream = %upstream))
+                .await;
+            match &fetched {
+                Ok(res) if res.status != 429 && res.status < 500 => {
+                    pool.record_success(upstream);
+                    break;
+                }
+                _ => pool.record_failure(upstream),
+            }
+        }
+        fetched
+    }
+
+    async fn send(&self, url: &str) -> Result<HorizonResponse, HorizonError> {
+        let fetched = match self.client.get(url).send().await {
+            Ok(res) => {
+                let status = res.status().as_u16();
+                res.bytes()
+                    .await
+                    .map(|body| HorizonResponse {
+                        status,
+                        body: body.to_vec(),
+                    })
+                    .map_err(|_| HorizonError::NetworkError)
+            }
+            Err(_) => Err(HorizonError::NetworkError),
+        };
+        match &fetched {
+            Ok(res) => debug!(status = res.status, "horizon_upstream_responded"),
+            Err(_) => debug!("horizon_upstream_unreachable"),
         }
+        fetched
+    }
+
+    fn stale(&self, url: &str) -> Option<HorizonResponse> {
+        let cache = self.stale_cache.read().ok()?;
+        let (body, fetched_at) = cache.get(url)?;
+        if fetched_at.elapsed() > STALE_TTL {
+            return None;
+        }
+        warn!(url = %url, age_seconds = fetched_at.elapsed().as_secs(), "stale_response_served");
+        Some(HorizonResponse {
+            status: 200,
+            body: body.clone(),
+        })
+    }
+
+    fn remember(&self, url: &str, body: &[u8]) {
+        let Ok(mut cache) = self.stale_cache.write() else {
+            return;
+        };
+        if cache.len() >= STALE_CAPACITY && !cache.contains_key(url) {
+            cache.retain(|_, (_, fetched_at)| fetched_at.elapsed() <= STALE_TTL);
+            if cache.len() >= STALE_CAPACITY
+                && let Some(oldest) = cache
+                    .iter()
+                    .min_by_key(|(_, (_, fetched_at))| *fetched_at)
+                    .map(|(key, _)| key.clone())
+            {
+                cache.remove(&oldest);
+            }
+        }
+        cache.insert(url.to_string(), (body.to_vec(), Instant::now()));
     }
 
     pub async fn fetch_transaction(&self, hash: &str) -> Result<HorizonTransaction, HorizonError> {
         let url = format!("{}/transactions/{}", self.base_url, hash);
 
-        let res = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|_| HorizonError::NetworkError)?;
+        let res = self.get(&url).await?;
 
-        match res.status().as_u16() {
-            200 => res
-                .json::<HorizonTransaction>()
-                .await
-                .map_err(|_| HorizonError::InvalidResponse),
+        match res.status {
+            200 => res.json::<HorizonTransaction>(),
             404 => Err(HorizonError::TransactionNotFound),
             _ => Err(HorizonError::InvalidResponse),
         }
@@ -179,19 +673,11 @@ impl HorizonClient {
     ) -> Result<Vec<HorizonOperation>, HorizonError> {
         let url = format!("{}/transactions/{}/operations", self.base_url, hash);
 
-        let res = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|_| HorizonError::NetworkError)?;
+        let res = self.get(&url).await?;
 
-        match res.status().as_u16() {
+        match res.status {
             200 => {
-                let wrapper: HorizonOperationsResponse = res
-                    .json()
-                    .await
-                    .map_err(|_| HorizonError::InvalidResponse)?;
+                let wrapper: HorizonOperationsResponse = res.json()?;
                 Ok(wrapper._embedded.records)
             }
             404 => Err(HorizonError::TransactionNotFound),
@@ -205,50 +691,707 @@ impl HorizonClient {
     pub async fn fetch_account(&self, address: &str) -> Result<Account, HorizonError> {
         let url = format!("{}/accounts/{}", self.base_url, address);
 
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let raw: HorizonAccount = res.json()?;
+                Ok(raw.into_domain())
+            }
+            404 => Err(HorizonError::AccountNotFound),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// An account's signers and sponsor.
+    pub async fn fetch_account_links(&self, address: &str) -> Result<AccountLinks, HorizonError> {
+        let url = format!("{}/accounts/{}", self.base_url, address);
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let raw: HorizonAccount = res.json()?;
+                Ok(raw.into_links())
+            }
+            404 => Err(HorizonError::AccountNotFound),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// Up to `limit` accounts that list `key` among their signers.
+    pub async fn fetch_accounts_with_signer(
+        &self,
+        key: &str,
+        limit: u32,
+    ) -> Result<Vec<AccountLinks>, HorizonError> {
+        self.fetch_account_list(&format!(
+            "{}/accounts?signer={}&limit={}",
+            self.base_url, key, limit
+        ))
+        .await
+    }
+
+    /// Up to `limit` accounts whose reserves, or any of whose signers,
+    /// trustlines, offers, or data entries, `sponsor` pays for.
+    pub async fn fetch_accounts_sponsored_by(
+        &self,
+        sponsor: &str,
+        limit: u32,
+    ) -> Result<Vec<AccountLinks>, HorizonError> {
+        self.fetch_account_list(&format!(
+            "{}/accounts?sponsor={}&limit={}",
+            self.base_url, sponsor, limit
+        ))
+        .await
+    }
+
+    async fn fetch_account_list(&self, url: &str) -> Result<Vec<AccountLinks>, HorizonError> {
+        let res = self.get(url).await?;
+
+        match res.status {
+            200 => {
+                let wrapper: HorizonAccountsResponse = res.json()?;
+                Ok(wrapper
+                    ._embedded
+                    .records
+                    .into_iter()
+                    .map(HorizonAccount::into_links)
+                    .collect())
+            }
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// Fetch a claimable balance by its hex or B... ID.
+    pub async fn fetch_claimable_balance(
+        &self,
+        id: &str,
+    ) -> Result<ClaimableBalance, HorizonError> {
+        let url = format!("{}/claimable_balances/{}", self.base_url, id);
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let raw: HorizonClaimableBalance = res.json()?;
+                Ok(raw.into_domain())
+            }
+            404 => Err(HorizonError::ClaimableBalanceNotFound),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// Fetch a liquidity pool by its hex ID.
+    pub async fn fetch_liquidity_pool(&self, id: &str) -> Result<LiquidityPool, HorizonError> {
+        let url = format!("{}/liquidity_pools/{}", self.base_url, id);
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let raw: HorizonLiquidityPool = res.json()?;
+                Ok(raw.into_domain())
+            }
+            404 => Err(HorizonError::LiquidityPoolNotFound),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    pub async fn fetch_ledger(&self, sequence: u64) -> Result<Ledger, HorizonError> {
+        let url = format!("{}/ledgers/{}", self.base_url, sequence);
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let raw: HorizonLedger = res.json()?;
+                Ok(raw.into_domain())
+            }
+            404 => Err(HorizonError::LedgerNotFound),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// Recent deposits and withdrawals for a pool, newest first, taken from
+    /// its latest `scan` operations (which also include trades and trust line
+    /// changes).
+    pub async fn fetch_liquidity_pool_activity(
+        &self,
+        id: &str,
+        scan: u32,
+    ) -> Result<Vec<PoolActivity>, HorizonError> {
+        let url = format!(
+            "{}/liquidity_pools/{}/operations?order=desc&limit={}",
+            self.base_url, id, scan
+        );
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let wrapper: HorizonOperationsResponse = res.json()?;
+                Ok(wrapper
+                    ._embedded
+                    .records
+                    .into_iter()
+                    .filter_map(HorizonOperation::into_pool_activity)
+                    .collect())
+            }
+            404 => Err(HorizonError::LiquidityPoolNotFound),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// Open offers placed by an account, oldest first.
+    pub async fn fetch_account_offers(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<Offer>, HorizonError> {
+        let url = format!(
+            "{}/accounts/{}/offers?limit={}",
+            self.base_url, address, limit
+        );
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let page: HorizonPage<HorizonOffer> = res.json()?;
+                Ok(page
+                    ._embedded
+                    .records
+                    .into_iter()
+                    .map(HorizonOffer::into_domain)
+                    .collect())
+            }
+            404 => Err(HorizonError::AccountNotFound),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// Total amount an offer has sold so far, summed over its trades.
+    ///
+    /// Horizon keeps only the remaining amount on the offer itself; together
+    /// with this it gives the original size of the offer.
+    ///
+    /// Only the first [`OFFER_TRADE_PAGES`] pages of trades are counted.
+    pub async fn fetch_offer_amount_sold(&self, offer_id: u64) -> Result<String, HorizonError> {
+        let limit = LEDGER_PAGE_LIMIT.to_string();
+        let trades = self
+            .pages::<HorizonTrade>(&format!("/offers/{offer_id}/trades"), &[("limit", &limit)])
+            .take(OFFER_TRADE_PAGES)
+            .try_fold(Vec::new(), |mut trades, page| async move {
+                trades.extend(page.records);
+                Ok(trades)
+            })
+            .await;
+
+        match trades {
+            Ok(trades) => Ok(amount_sold(&trades, &offer_id.to_string())),
+            // The offer was fully filled or cancelled since it was listed.
+            Err(HorizonError::OfferNotFound) => Ok("0.0000000".to_string()),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// The trades `account` made during operation `operation_id`, against
+    /// offers and liquidity pools, in the order they were made.
+    pub async fn fetch_operation_trades(
+        &self,
+        operation_id: &str,
+        account: &str,
+    ) -> Result<Vec<TradeFill>, HorizonError> {
+        let limit = LEDGER_PAGE_LIMIT.to_string();
+        self.pages::<HorizonTradeEffect>(
+            &format!("/operations/{operation_id}/effects"),
+            &[("limit", &limit)],
+        )
+        .try_fold(Vec::new(), |mut fills, page| async move {
+            fills.extend(
+                page.records
+                    .into_iter()
+                    .filter(|effect| effect.account.as_deref() == Some(account))
+                    .filter_map(HorizonTradeEffect::into_fill),
+            );
+            Ok(fills)
+        })
+        .await
+    }
+
+    /// The last price `base` traded at in `counter`, in the day before
+    /// `before` (RFC 3339), from one-minute trade aggregations. Assets are
+    /// "native" or "CODE:ISSUER". None when the pair did not trade or the
+    /// request fails.
+    pub async fn fetch_market_price(&self, base: &str, counter: &str, before: &str) -> Option<f64> {
+        const MINUTE_MS: i64 = 60_000;
+        const DAY_MS: i64 = 24 * 60 * MINUTE_MS;
+
+        let before = chrono::DateTime::parse_from_rfc3339(before).ok()?;
+        // Whole minutes only, so the bucket the trade itself fell in is left out.
+        let end_time = before.timestamp_millis() / MINUTE_MS * MINUTE_MS;
+        let url = format!(
+            "{}/trade_aggregations?{}&{}&resolution={}&start_time={}&end_time={}&order=desc&limit=1",
+            self.base_url,
+            asset_query("base", base)?,
+            asset_query("counter", counter)?,
+            MINUTE_MS,
+            end_time - DAY_MS,
+            end_time
+        );
+
+        let res = self.get(&url).await.ok()?;
+
+        if res.status != 200 {
+            return None;
+        }
+
+        let page: HorizonPage<HorizonTradeAggregation> = res.json().ok()?;
+        let close: f64 = page._embedded.records.first()?.close.parse().ok()?;
+        (close.is_finite() && close > 0.0).then_some(close)
+    }
+
+    /// The trades of `base` against `counter` from `start_time` to
+    /// `end_time` (Unix milliseconds), in buckets of `resolution`
+    /// milliseconds, oldest first. Assets are "native" or "CODE:ISSUER".
+    /// Horizon returns only buckets with trades, at most 200 of them.
+    pub async fn fetch_trade_aggregations(
+        &self,
+        base: &str,
+        counter: &str,
+        start_time: i64,
+        end_time: i64,
+        resolution: i64,
+    ) -> Result<Vec<TradeBucket>, HorizonError> {
+        let (Some(base), Some(counter)) =
+            (asset_query("base", base), asset_query("counter", counter))
+        else {
+            return Err(HorizonError::InvalidResponse);
+        };
+        let url = format!(
+            "{}/trade_aggregations?{}&{}&resolution={}&start_time={}&end_time={}&order=asc&limit={}",
+            self.base_url, base, counter, resolution, start_time, end_time, LEDGER_PAGE_LIMIT
+        );
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let page: HorizonPage<HorizonTradeAggregation> = res.json()?;
+                Ok(page
+                    ._embedded
+                    .records
+                    .into_iter()
+                    .filter_map(HorizonTradeAggregation::into_bucket)
+                    .collect())
+            }
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// Look up the operation history of an address that returned 404 from
+    /// `/accounts/:id`, to tell a merged account apart from one that never existed.
+    ///
+    /// After a merge the account cannot submit further operations, so its most
+    /// recent operation is the account_merge itself.
+    pub async fn fetch_account_history(
+        &self,
+        address: &str,
+    ) -> Result<AccountHistory, HorizonError> {
+        let url = format!(
+            "{}/accounts/{}/operations?order=desc&limit=1",
+            self.base_url, address
+        );
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let wrapper: HorizonOperationsResponse = res.json()?;
+
+                let Some(latest) = wrapper._embedded.records.into_iter().next() else {
+                    return Ok(AccountHistory::NeverCreated);
+                };
+
+                let merged_self = latest.operation_type == "account_merge"
+                    && latest.source_account.as_deref() == Some(address);
+
+                match (merged_self, latest.into) {
+                    (true, Some(into)) => Ok(AccountHistory::Merged {
+                        into,
+                        closed_at: latest.created_at,
+                    }),
+                    _ => Ok(AccountHistory::Inactive),
+                }
+            }
+            404 => Ok(AccountHistory::NeverCreated),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// An account's latest `limit` operations, newest first.
+    pub async fn fetch_account_operations(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<HorizonOperation>, HorizonError> {
+        let url = format!(
+            "{}/accounts/{}/operations?order=desc&limit={}",
+            self.base_url, address, limit
+        );
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let wrapper: HorizonOperationsResponse = res.json()?;
+                Ok(wrapper._embedded.records)
+            }
+            404 => Err(HorizonError::AccountNotFound),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// One page of an account's operations in `order` ("asc" or "desc"),
+    /// starting after `cursor`, with the next and previous page cursors.
+    pub async fn fetch_account_operations_page(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<&str>,
+        order: &str,
+    ) -> Result<(Vec<HorizonOperation>, Option<String>, Option<String>), HorizonError> {
+        let page = self
+            .first_page(
+                &format!("/accounts/{address}/operations"),
+                limit,
+                cursor,
+                order,
+            )
+            .await?;
+        Ok((page.records, page.next_cursor, page.prev_cursor))
+    }
+
+    /// Clawbacks of `asset_code` among the issuer's latest `scan` operations,
+    /// newest first.
+    pub async fn fetch_issuer_clawbacks(
+        &self,
+        issuer: &str,
+        asset_code: &str,
+        scan: u32,
+    ) -> Result<Vec<Clawback>, HorizonError> {
+        let url = format!(
+            "{}/accounts/{}/operations?order=desc&limit={}",
+            self.base_url, issuer, scan
+        );
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let wrapper: HorizonOperationsResponse = res.json()?;
+                Ok(wrapper
+                    ._embedded
+                    .records
+                    .into_iter()
+                    .filter(|op| {
+                        op.operation_type == "clawback"
+                            && op.asset_code.as_deref() == Some(asset_code)
+                    })
+                    .map(|op| Clawback {
+                        from: op.from.unwrap_or_default(),
+                        amount: op.amount.unwrap_or_else(|| "0".to_string()),
+                        transaction_hash: op.transaction_hash,
+                        created_at: op.created_at,
+                    })
+                    .collect())
+            }
+            404 => Err(HorizonError::AccountNotFound),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// Fetch the current network fee stats from Horizon.
+    /// Returns None if the request fails — callers degrade gracefully.
+    pub async fn fetch_fee_stats(&self) -> Option<FeeStats> {
+        let url = format!("{}/fee_stats", self.base_url);
+
+        let res = self.get(&url).await.ok()?;
+
+        if res.status != 200 {
+            return None;
+        }
+
+        let raw: HorizonFeeStats = res.json().ok()?;
+        raw.into_domain()
+    }
+
+    /// Everything `/fee_stats` reports: capacity usage and the fees charged
+    /// and bid in recent ledgers.
+    pub async fn fetch_fee_market(&self) -> Result<FeeMarket, HorizonError> {
+        let url = format!("{}/fee_stats", self.base_url);
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => res
+                .json::<HorizonFeeStats>()?
+                .into_market()
+                .ok_or(HorizonError::InvalidResponse),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// How full the `limit` most recent ledgers were, newest first.
+    pub async fn fetch_ledger_loads(&self, limit: u32) -> Result<Vec<LedgerLoad>, HorizonError> {
+        let url = format!("{}/ledgers?order=desc&limit={limit}", self.base_url);
+
+        let res = self.get(&url).await?;
+
+        match res.status {
+            200 => {
+                let raw: HorizonLedgersResponse = res.json()?;
+                Ok(raw
+                    ._embedded
+                    .records
+                    .into_iter()
+                    .map(HorizonLedger::into_load)
+                    .collect())
+            }
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// Average number of seconds between the most recent ledger closes.
+    /// Returns None if the request fails — callers degrade gracefully.
+    pub async fn fetch_average_ledger_close_seconds(&self) -> Option<f64> {
+        let url = format!("{}/ledgers?order=desc&limit=10", self.base_url);
+
+        let res = self.get(&url).await.ok()?;
+
+        if res.status != 200 {
+            return None;
+        }
+
+        let raw: HorizonLedgersResponse = res.json().ok()?;
+        raw.average_close_seconds()
+    }
+
+    /// Submit a base64 transaction envelope and wait for Horizon's answer.
+    ///
+    /// Submissions bypass the circuit breaker and stale cache: a replayed
+    /// answer would be wrong, and a rejected transaction is not an outage.
+    pub async fn submit_transaction(
+        &self,
+        envelope_xdr: &str,
+    ) -> Result<HorizonSubmission, HorizonError> {
+        let url = format!("{}/transactions", self.base_url);
+
         let res = self
             .client
-            .get(url)
+            .post(&url)
+            .form(&[("tx", envelope_xdr)])
             .send()
             .await
             .map_err(|_| HorizonError::NetworkError)?;
+        let status = res.status().as_u16();
+        let body = res.bytes().await.map_err(|_| HorizonError::NetworkError)?;
+        let res = HorizonResponse {
+            status,
+            body: body.to_vec(),
+        };
 
-        match res.status().as_u16() {
+        match res.status {
+            200 => res
+                .json()
+                .map(|tx| HorizonSubmission::Included(Box::new(tx))),
+            400 => res
+                .json::<HorizonProblem>()
+                .map(|problem| HorizonSubmission::Failed(problem.extras)),
+            504 => Ok(HorizonSubmission::TimedOut),
+            429 => Err(HorizonError::RateLimited),
+            _ => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// Sequence number of the most recently closed ledger.
+    pub async fn fetch_latest_ledger(&self) -> Result<u64, HorizonError> {
+        let url = format!("{}/ledgers?order=desc&limit=1", self.base_url);
+
+        let res = self.get(&url).await?;
+
+        match res.status {
             200 => {
-                let raw: HorizonAccount = res
-                    .json()
-                    .await
-                    .map_err(|_| HorizonError::InvalidResponse)?;
-                Ok(raw.into_domain())
+                let raw: HorizonLedgersResponse = res.json()?;
+                raw._embedded
+                    .records
+                    .first()
+                    .map(|l| l.sequence)
+                    .ok_or(HorizonError::InvalidResponse)
             }
-            404 => Err(HorizonError::AccountNotFound),
             _ => Err(HorizonError::InvalidResponse),
         }
     }
 
-    /// Fetch the current network fee stats from Horizon.
-    /// Returns None if the request fails — callers degrade gracefully.
-    pub async fn fetch_fee_stats(&self) -> Option<FeeStats> {
-        let url = format!("{}/fee_stats", self.base_url);
+    /// Every transaction (including failed ones) in a ledger, across all pages.
+    pub async fn fetch_ledger_transactions(
+        &self,
+        sequence: u64,
+    ) -> Result<Vec<HorizonTransaction>, HorizonError> {
+        self.fetch_all_pages(&format!("/ledgers/{sequence}/transactions"))
+            .await
+    }
 
-        let res = self.client.get(url).send().await.ok()?;
+    /// Every operation (including those of failed transactions) in a ledger,
+    /// across all pages.
+    pub async fn fetch_ledger_operations(
+        &self,
+        sequence: u64,
+    ) -> Result<Vec<HorizonOperation>, HorizonError> {
+        self.fetch_all_pages(&format!("/ledgers/{sequence}/operations"))
+            .await
+    }
 
-        if res.status().as_u16() != 200 {
-            return None;
-        }
+    /// Every record of a ledger-scoped collection, failed transactions included.
+    async fn fetch_all_pages<T: DeserializeOwned>(
+        &self,
+        path: &str,
+    ) -> Result<Vec<T>, HorizonError> {
+        let limit = LEDGER_PAGE_LIMIT.to_string();
+        self.pages(path, &[("include_failed", "true"), ("limit", &limit)])
+            .try_fold(Vec::new(), |mut records, page| async move {
+                records.extend(page.records);
+                Ok(records)
+            })
+            .await
+    }
 
-        let raw: HorizonFeeStats = res.json().await.ok()?;
+    /// The pages of the collection at `path` (e.g. `/accounts/G.../effects`),
+    /// queried with `params`, in order.
+    ///
+    /// A `cursor` in `params` is where the first page starts; each later page
+    /// starts at the cursor after the one before. The stream ends after a
+    /// page shorter than `limit` (Horizon's default of 10 when unset), or
+    /// after the first error. A page that fails with a network error, a 429
+    /// or a 5xx is requested again, up to [`MAX_PAGE_RETRIES`] times with
+    /// growing pauses, from the same cursor, so a retry never skips or
+    /// repeats records. Pauses after a 429 start at [`RATE_LIMIT_BACKOFF`]
+    /// rather than [`PAGE_RETRY_BACKOFF`]. Once the circuit breaker opens,
+    /// the next page fails with `CircuitOpen` rather than waiting. A 404 is
+    /// the error for the resource the collection belongs to, e.g.
+    /// `AccountNotFound`.
+    pub fn pages<'a, T: DeserializeOwned + 'a>(
+        &'a self,
+        path: &str,
+        params: &[(&str, &str)],
+    ) -> impl Stream<Item = Result<Page<T>, HorizonError>> + 'a {
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| value.to_string())
+        };
+        let limit = param("limit")
+            .and_then(|limit| limit.parse::<usize>().ok())
+            .unwrap_or(HORIZON_DEFAULT_LIMIT);
+        let query = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(params.iter().filter(|(key, _)| *key != "cursor"))
+            .finish();
+        let base = format!("{}{}", self.base_url, path);
+        let path = path.to_string();
+
+        // `None` once the last page has been read.
+        stream::try_unfold(Some(param("cursor")), move |cursor| {
+            // Cursors may come from callers, so they are encoded like any
+            // other parameter.
+            let url = cursor.as_ref().map(|cursor| {
+                let mut query = form_urlencoded::Serializer::new(query.clone());
+                if let Some(cursor) = cursor {
+                    query.append_pair("cursor", cursor);
+                }
+                format!("{base}?{}", query.finish())
+            });
+            let path = path.clone();
+            async move {
+                let Some(url) = url else {
+                    return Ok(None);
+                };
+                let page: Page<T> = self.fetch_page(&url, &path).await?;
+                let next = page
+                    .next_cursor
+                    .clone()
+                    .filter(|_| page.records.len() >= limit);
+                Ok(Some((page, next.map(Some))))
+            }
+        })
+    }
 
-        let base_fee = raw.last_ledger_base_fee.parse::<u64>().ok()?;
-        let min_fee = raw.fee_charged.min.parse::<u64>().unwrap_or(base_fee);
-        let max_fee = raw.fee_charged.max.parse::<u64>().unwrap_or(base_fee);
-        let mode_fee = raw.fee_charged.mode.parse::<u64>().unwrap_or(base_fee);
-        let p90_fee = raw.fee_charged.p90.parse::<u64>().unwrap_or(base_fee);
+    /// The page at `url`, retried while Horizon is rate limiting or failing.
+    async fn fetch_page<T: DeserializeOwned>(
+        &self,
+        url: &str,
+        path: &str,
+    ) -> Result<Page<T>, HorizonError> {
+        let mut retries = 0;
+        loop {
+            let (fetched, retryable) = match self.get(url).await {
+                Ok(res) => match res.status {
+                    200 => (res.json::<HorizonPage<T>>().map(Page::from), false),
+                    404 => (Err(collection_not_found(path)), false),
+                    status => (Err(HorizonError::InvalidResponse), status >= 500),
+                },
+                Err(err) => {
+                    let retryable =
+                        matches!(err, HorizonError::NetworkError | HorizonError::RateLimited);
+                    (Err(err), retryable)
+                }
+            };
+            if !retryable || retries == MAX_PAGE_RETRIES {
+                return fetched;
+            }
+            let backoff = match fetched {
+                Err(HorizonError::RateLimited) => RATE_LIMIT_BACKOFF,
+                _ => PAGE_RETRY_BACKOFF,
+            };
+            let wait = backoff * 2u32.pow(retries);
+            retries += 1;
+            warn!(
+                url = %url,
+                retry = retries,
+                pause_ms = wait.as_millis() as u64,
+                "horizon_page_retried"
+            );
+            tokio::time::sleep(wait).await;
+        }
+    }
 
-        Some(FeeStats::new(base_fee, min_fee, max_fee, mode_fee, p90_fee))
+    /// The first page of an account collection in `order`, after `cursor`.
+    async fn first_page<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        limit: u32,
+        cursor: Option<&str>,
+        order: &str,
+    ) -> Result<Page<T>, HorizonError> {
+        let limit = limit.to_string();
+        let mut params = vec![("limit", limit.as_str()), ("order", order)];
+        if let Some(cursor) = cursor {
+            params.push(("cursor", cursor));
+        }
+        let mut pages = pin!(self.pages(path, &params));
+        pages
+            .next()
+            .await
+            .unwrap_or(Err(HorizonError::InvalidResponse))
     }
 
     /// Check whether Horizon is reachable by hitting the root endpoint.
+    /// Always true when replaying recordings.
     pub async fn is_reachable(&self) -> bool {
+        if self.replaying().is_some() {
+            return true;
+        }
         let url = format!("{}/", self.base_url);
         self.client
             .get(url)
@@ -275,43 +1418,26 @@ impl HorizonClient {
         ),
         HorizonError,
     > {
-        let mut url = format!(
-            "{}/accounts/{}/transactions?limit={}&order={}",
-            self.base_url, address, limit, order
-        );
-        if let Some(c) = cursor {
-            url.push_str(&format!("&cursor={c}"));
-        }
-
-        let res = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|_| HorizonError::NetworkError)?;
-
-        match res.status().as_u16() {
-            200 => {
-                let wrapper: HorizonAccountTransactionsResponse = res
-                    .json()
-                    .await
-                    .map_err(|_| HorizonError::InvalidResponse)?;
-
-                let next_cursor =
-                    extract_cursor(wrapper._links.next.as_ref().and_then(|l| l.href.as_deref()));
-                let prev_cursor =
-                    extract_cursor(wrapper._links.prev.as_ref().and_then(|l| l.href.as_deref()));
-
-                Ok((wrapper._embedded.records, next_cursor, prev_cursor))
-            }
-            404 => Err(HorizonError::AccountNotFound),
-            _ => Err(HorizonError::InvalidResponse),
-        }
+        let page = self
+            .first_page(
+                &format!("/accounts/{address}/transactions"),
+                limit,
+                cursor,
+                order,
+            )
+            .await?;
+        Ok((page.records, page.next_cursor, page.prev_cursor))
     }
 
     /// Fetch the ORG_NAME from a domain's stellar.toml file.
     /// Returns None if the file is missing, unreachable, or doesn't contain ORG_NAME.
     pub async fn fetch_stellar_toml_org_name(&self, domain: &str) -> Option<String> {
+        self.fetch_stellar_toml_identity(domain).await?.org_name
+    }
+
+    /// Fetch the ORG_NAME and ACCOUNTS from a domain's stellar.toml file.
+    /// Returns None if the file is missing or unreachable.
+    pub async fn fetch_stellar_toml_identity(&self, domain: &str) -> Option<TomlIdentity> {
         // Check cache first
         {
             let cache = self.toml_cache.read().ok()?;
@@ -336,26 +1462,65 @@ impl HorizonClient {
         }
 
         let text = res.text().await.ok()?;
-        let org_name = parse_org_name(&text);
+        let identity = parse_toml_identity(&text);
 
         // Store in cache
         if let Ok(mut cache) = self.toml_cache.write() {
-            cache.insert(domain.to_string(), (org_name.clone(), Instant::now()));
+            cache.insert(domain.to_string(), (Some(identity.clone()), Instant::now()));
+        }
+
+        Some(identity)
+    }
+
+    /// Check whether the stellar.toml at `domain` (a URL such as
+    /// "https://example.com") lists the asset under `[[CURRENCIES]]`.
+    ///
+    /// Not cached: audits want the file as it is now.
+    pub async fn check_stellar_toml(
+        &self,
+        domain: &str,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> StellarTomlStatus {
+        let toml_url = format!("{domain}/.well-known/stellar.toml");
+        let Ok(res) = self
+            .client
+            .get(&toml_url)
+            .timeout(Duration::from_secs(5))
+            .send()
+            .await
+        else {
+            return StellarTomlStatus::Unreachable;
+        };
+
+        match res.status().as_u16() {
+            200 => {}
+            404 => return StellarTomlStatus::Missing,
+            _ => return StellarTomlStatus::Unreachable,
         }
 
-        org_name
+        let Ok(text) = res.text().await else {
+            return StellarTomlStatus::Unreachable;
+        };
+        match toml_lists_currency(&text, asset_code, asset_issuer) {
+            Some(true) => StellarTomlStatus::Listed,
+            Some(false) => StellarTomlStatus::Unlisted,
+            None => StellarTomlStatus::Invalid,
+        }
     }
 }
 
 // ── Supporting structs ─────────────────────────────────────────────────────
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Default)]
 pub struct HorizonOperation {
     pub id: String,
     pub transaction_hash: String,
     #[serde(rename = "type")]
     pub operation_type: String,
     pub source_account: Option<String>,
+    /// ISO 8601 close time of the ledger that included this operation.
+    pub created_at: Option<String>,
     // Payment fields
     pub amount: Option<String>,
     pub asset_type: Option<String>,
@@ -401,6 +1566,319 @@ pub struct HorizonOperation {
     pub balance_id: Option<String>,
     // Account merge fields
     pub into: Option<String>,
+    // Liquidity pool deposit/withdraw fields
+    pub liquidity_pool_id: Option<String>,
+    pub reserves_deposited: Option<Vec<HorizonPoolReserve>>,
+    pub shares_received: Option<String>,
+    pub reserves_received: Option<Vec<HorizonPoolReserve>>,
+    pub shares: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct HorizonPoolReserve {
+    pub asset: String,
+    pub amount: String,
+}
+
+impl From<HorizonPoolReserve> for PoolReserve {
+    fn from(reserve: HorizonPoolReserve) -> Self {
+        PoolReserve {
+            asset: reserve.asset,
+            amount: reserve.amount,
+        }
+    }
+}
+
+impl HorizonOperation {
+    /// The pool activity this operation records, if it is a deposit or
+    /// withdrawal.
+    fn into_pool_activity(self) -> Option<PoolActivity> {
+        let (kind, reserves, shares) = match self.operation_type.as_str() {
+            "liquidity_pool_deposit" => (
+                PoolActivityKind::Deposit,
+                self.reserves_deposited,
+                self.shares_received,
+            ),
+            "liquidity_pool_withdraw" => (
+                PoolActivityKind::Withdraw,
+                self.reserves_received,
+                self.shares,
+            ),
+            _ => return None,
+        };
+        Some(PoolActivity {
+            kind,
+            account: self.source_account.unwrap_or_else(|| "Unknown".to_string()),
+            reserves: reserves
+                .unwrap_or_default()
+                .into_iter()
+                .map(PoolReserve::from)
+                .collect(),
+            shares: shares.unwrap_or_else(|| "0".to_string()),
+            transaction_hash: self.transaction_hash,
+            created_at: self.created_at,
+        })
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HorizonLiquidityPool {
+    id: String,
+    fee_bp: u32,
+    #[serde(rename = "type")]
+    pool_type: String,
+    total_trustlines: String,
+    total_shares: String,
+    reserves: Vec<HorizonPoolReserve>,
+    #[serde(default)]
+    last_modified_ledger: Option<u64>,
+}
+
+impl HorizonLiquidityPool {
+    fn into_domain(self) -> LiquidityPool {
+        LiquidityPool {
+            id: self.id,
+            fee_bp: self.fee_bp,
+            pool_type: self.pool_type,
+            total_trustlines: self.total_trustlines.parse().unwrap_or(0),
+            total_shares: self.total_shares,
+            reserves: self.reserves.into_iter().map(PoolReserve::from).collect(),
+            last_modified_ledger: self.last_modified_ledger,
+        }
+    }
+}
+
+/// Raw Horizon offer.
+#[derive(Debug, Deserialize)]
+struct HorizonOffer {
+    id: String,
+    seller: String,
+    selling: HorizonOfferAsset,
+    buying: HorizonOfferAsset,
+    amount: String,
+    price: String,
+    #[serde(default)]
+    sponsor: Option<String>,
+    #[serde(default)]
+    last_modified_ledger: Option<u64>,
+    #[serde(default)]
+    last_modified_time: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HorizonOfferAsset {
+    asset_type: String,
+    #[serde(default)]
+    asset_code: Option<String>,
+    #[serde(default)]
+    asset_issuer: Option<String>,
+}
+
+impl HorizonOfferAsset {
+    /// Canonical form: "native" or "CODE:ISSUER".
+    fn canonical(&self) -> String {
+        match (
+            self.asset_type.as_str(),
+            &self.asset_code,
+            &self.asset_issuer,
+        ) {
+            ("native", _, _) => "native".to_string(),
+            (_, Some(code), Some(issuer)) => format!("{code}:{issuer}"),
+            (_, Some(code), None) => code.clone(),
+            _ => "unknown".to_string(),
+        }
+    }
+}
+
+impl HorizonOffer {
+    fn into_domain(self) -> Offer {
+        Offer {
+            id: self.id.parse().unwrap_or(0),
+            seller: self.seller,
+            selling: self.selling.canonical(),
+            buying: self.buying.canonical(),
+            amount: self.amount,
+            price: self.price,
+            sponsor: self.sponsor,
+            last_modified_ledger: self.last_modified_ledger,
+            last_modified_time: self.last_modified_time,
+        }
+    }
+}
+
+/// Raw Horizon trade. The base party gives up `base_amount` and the counter
+/// party gives up `counter_amount`.
+#[derive(Debug, Deserialize)]
+struct HorizonTrade {
+    #[serde(default)]
+    base_offer_id: Option<String>,
+    base_amount: String,
+    #[serde(default)]
+    counter_offer_id: Option<String>,
+    counter_amount: String,
+}
+
+impl HorizonTrade {
+    /// What the given offer sold in this trade, in the offer's selling asset.
+    fn amount_sold_by(&self, offer_id: &str) -> Option<&str> {
+        if self.base_offer_id.as_deref() == Some(offer_id) {
+            Some(&self.base_amount)
+        } else if self.counter_offer_id.as_deref() == Some(offer_id) {
+            Some(&self.counter_amount)
+        } else {
+            None
+        }
+    }
+}
+
+/// Raw Horizon effect, with the fields of `trade` and
+/// `liquidity_pool_trade` effects. Other effects are skipped.
+#[derive(Debug, Deserialize)]
+struct HorizonTradeEffect {
+    #[serde(rename = "type")]
+    effect_type: String,
+    #[serde(default)]
+    account: Option<String>,
+    // trade
+    #[serde(default)]
+    sold_amount: Option<String>,
+    #[serde(default)]
+    sold_asset_type: Option<String>,
+    #[serde(default)]
+    sold_asset_code: Option<String>,
+    #[serde(default)]
+    sold_asset_issuer: Option<String>,
+    #[serde(default)]
+    bought_amount: Option<String>,
+    #[serde(default)]
+    bought_asset_type: Option<String>,
+    #[serde(default)]
+    bought_asset_code: Option<String>,
+    #[serde(default)]
+    bought_asset_issuer: Option<String>,
+    // liquidity_pool_trade
+    #[serde(default)]
+    sold: Option<HorizonPoolTradeSide>,
+    #[serde(default)]
+    bought: Option<HorizonPoolTradeSide>,
+}
+
+/// One side of a liquidity pool trade; `asset` is already canonical.
+#[derive(Debug, Deserialize)]
+struct HorizonPoolTradeSide {
+    asset: String,
+    amount: String,
+}
+
+impl HorizonTradeEffect {
+    fn into_fill(self) -> Option<TradeFill> {
+        let (sold_asset, sold_amount, bought_asset, bought_amount) = match self.effect_type.as_str()
+        {
+            "trade" => (
+                HorizonOfferAsset {
+                    asset_type: self.sold_asset_type?,
+                    asset_code: self.sold_asset_code,
+                    asset_issuer: self.sold_asset_issuer,
+                }
+                .canonical(),
+                self.sold_amount?,
+                HorizonOfferAsset {
+                    asset_type: self.bought_asset_type?,
+                    asset_code: self.bought_asset_code,
+                    asset_issuer: self.bought_asset_issuer,
+                }
+                .canonical(),
+                self.bought_amount?,
+            ),
+            "liquidity_pool_trade" => {
+                let (sold, bought) = (self.sold?, self.bought?);
+                (sold.asset, sold.amount, bought.asset, bought.amount)
+            }
+            _ => return None,
+        };
+        Some(TradeFill {
+            sold_asset,
+            sold_amount: sold_amount.parse().ok()?,
+            bought_asset,
+            bought_amount: bought_amount.parse().ok()?,
+        })
+    }
+}
+
+/// Raw Horizon trade aggregation bucket. Horizon sends the timestamp and
+/// trade count as strings in current versions and as numbers in older ones.
+#[derive(Debug, Deserialize)]
+struct HorizonTradeAggregation {
+    #[serde(default)]
+    timestamp: serde_json::Value,
+    #[serde(default)]
+    trade_count: serde_json::Value,
+    #[serde(default)]
+    base_volume: String,
+    #[serde(default)]
+    counter_volume: String,
+    #[serde(default)]
+    open: String,
+    #[serde(default)]
+    high: String,
+    #[serde(default)]
+    low: String,
+    close: String,
+}
+
+impl HorizonTradeAggregation {
+    fn into_bucket(self) -> Option<TradeBucket> {
+        let integer =
+            |value: &serde_json::Value| value.as_i64().or_else(|| value.as_str()?.parse().ok());
+        let price = |value: &str| value.parse::<f64>().ok().filter(|p| p.is_finite());
+        Some(TradeBucket {
+            timestamp: integer(&self.timestamp)?,
+            trade_count: u64::try_from(integer(&self.trade_count)?).ok()?,
+            base_volume: self.base_volume.parse().ok()?,
+            counter_volume: self.counter_volume.parse().ok()?,
+            open: price(&self.open)?,
+            high: price(&self.high)?,
+            low: price(&self.low)?,
+            close: price(&self.close)?,
+        })
+    }
+}
+
+/// Query parameters naming a "native" or "CODE:ISSUER" asset, e.g.
+/// `base_asset_type=native`.
+fn asset_query(prefix: &str, asset: &str) -> Option<String> {
+    if asset == "native" {
+        return Some(format!("{prefix}_asset_type=native"));
+    }
+    let (code, issuer) = asset.split_once(':')?;
+    let asset_type = if code.len() <= 4 {
+        "credit_alphanum4"
+    } else {
+        "credit_alphanum12"
+    };
+    Some(format!(
+        "{prefix}_asset_type={asset_type}&{prefix}_asset_code={code}&{prefix}_asset_issuer={issuer}"
+    ))
+}
+
+/// Total an offer has sold over `trades`, as a 7-decimal amount.
+fn amount_sold(trades: &[HorizonTrade], offer_id: &str) -> String {
+    trades
+        .iter()
+        .filter_map(|trade| trade.amount_sold_by(offer_id))
+        .filter_map(|amount| amount.parse::<Amount>().ok())
+        .fold(Amount::ZERO, Amount::saturating_add)
+        .to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct HorizonAccountsResponse {
+    _embedded: HorizonEmbeddedAccounts,
+}
+
+#[derive(Debug, Deserialize)]
+struct HorizonEmbeddedAccounts {
+    records: Vec<HorizonAccount>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -413,10 +1891,100 @@ struct HorizonEmbeddedOperations {
     records: Vec<HorizonOperation>,
 }
 
+#[derive(Debug, Deserialize)]
+struct HorizonLedgersResponse {
+    _embedded: HorizonEmbeddedLedgers,
+}
+
+impl HorizonLedgersResponse {
+    fn average_close_seconds(&self) -> Option<f64> {
+        let closes: Vec<i64> = self
+            ._embedded
+            .records
+            .iter()
+            .filter_map(|l| chrono::DateTime::parse_from_rfc3339(&l.closed_at).ok())
+            .map(|t| t.timestamp())
+            .collect();
+        average_interval_seconds(&closes)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct HorizonEmbeddedLedgers {
+    records: Vec<HorizonLedger>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HorizonLedger {
+    #[serde(default)]
+    sequence: u64,
+    closed_at: String,
+    #[serde(default)]
+    protocol_version: u32,
+    #[serde(default)]
+    successful_transaction_count: u32,
+    #[serde(default)]
+    failed_transaction_count: Option<u32>,
+    #[serde(default)]
+    operation_count: u32,
+    #[serde(default)]
+    base_fee_in_stroops: u64,
+    #[serde(default)]
+    base_reserve_in_stroops: u64,
+    #[serde(default)]
+    max_tx_set_size: u32,
+    #[serde(default)]
+    tx_set_operation_count: Option<u32>,
+}
+
+impl HorizonLedger {
+    fn into_domain(self) -> Ledger {
+        Ledger {
+            sequence: self.sequence,
+            closed_at: self.closed_at,
+            protocol_version: self.protocol_version,
+            successful_transaction_count: self.successful_transaction_count,
+            // Horizon reports null for ledgers older than the field.
+            failed_transaction_count: self.failed_transaction_count.unwrap_or(0),
+            operation_count: self.operation_count,
+            base_fee_in_stroops: self.base_fee_in_stroops,
+            base_reserve_in_stroops: self.base_reserve_in_stroops,
+        }
+    }
+
+    fn into_load(self) -> LedgerLoad {
+        LedgerLoad {
+            sequence: self.sequence,
+            // Horizon reports null for ledgers older than the field.
+            operations: self.tx_set_operation_count.unwrap_or(self.operation_count),
+            capacity: self.max_tx_set_size,
+        }
+    }
+}
+
+/// A generic page of a Horizon collection.
+#[derive(Debug, Deserialize)]
+struct HorizonPage<T> {
+    #[serde(default)]
+    _links: HorizonLinks,
+    _embedded: HorizonEmbedded<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HorizonEmbedded<T> {
+    records: Vec<T>,
+}
+
 #[derive(Deserialize)]
 struct HorizonFeeStats {
+    #[serde(default)]
+    last_ledger: String,
     last_ledger_base_fee: String,
+    #[serde(default)]
+    ledger_capacity_usage: String,
     fee_charged: HorizonFeeDistribution,
+    #[serde(default)]
+    max_fee: Option<HorizonFeeDistribution>,
 }
 
 #[derive(Deserialize)]
@@ -424,10 +1992,66 @@ struct HorizonFeeDistribution {
     min: String,
     max: String,
     mode: String,
+    #[serde(default)]
+    p50: String,
+    #[serde(default)]
+    p70: String,
     p90: String,
+    #[serde(default)]
+    p95: String,
+    #[serde(default)]
+    p99: String,
 }
 
-#[derive(Debug, Deserialize)]
+impl HorizonFeeDistribution {
+    /// Fees that are missing or unreadable count as `base_fee`.
+    fn into_domain(self, base_fee: u64) -> FeeDistribution {
+        let fee = |value: &str| value.parse::<u64>().unwrap_or(base_fee);
+        FeeDistribution {
+            mode: fee(&self.mode),
+            p50: fee(&self.p50),
+            p70: fee(&self.p70),
+            p90: fee(&self.p90),
+            p95: fee(&self.p95),
+            p99: fee(&self.p99),
+            max: fee(&self.max),
+        }
+    }
+}
+
+impl HorizonFeeStats {
+    fn into_domain(self) -> Option<FeeStats> {
+        let base_fee = self.last_ledger_base_fee.parse::<u64>().ok()?;
+        let min_fee = self.fee_charged.min.parse::<u64>().unwrap_or(base_fee);
+        let max_fee = self.fee_charged.max.parse::<u64>().unwrap_or(base_fee);
+        let mode_fee = self.fee_charged.mode.parse::<u64>().unwrap_or(base_fee);
+        let p90_fee = self.fee_charged.p90.parse::<u64>().unwrap_or(base_fee);
+        Some(FeeStats::new(base_fee, min_fee, max_fee, mode_fee, p90_fee))
+    }
+
+    fn into_market(self) -> Option<FeeMarket> {
+        let base_fee = self.last_ledger_base_fee.parse::<u64>().ok()?;
+        let capacity_usage = self
+            .ledger_capacity_usage
+            .parse::<f64>()
+            .ok()
+            .filter(|usage| usage.is_finite())
+            .unwrap_or(0.0)
+            .clamp(0.0, 1.0);
+        Some(FeeMarket {
+            last_ledger: self.last_ledger.parse().unwrap_or(0),
+            base_fee,
+            capacity_usage,
+            charged: self.fee_charged.into_domain(base_fee),
+            offered: self
+                .max_fee
+                .map(|max_fee| max_fee.into_domain(base_fee))
+                .unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
 struct HorizonLinks {
     next: Option<HorizonLink>,
     prev: Option<HorizonLink>,
@@ -438,21 +2062,57 @@ struct HorizonLink {
     href: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
-struct HorizonAccountTransactionsResponse {
-    _links: HorizonLinks,
-    _embedded: HorizonEmbeddedAccountTransactions,
+fn extract_cursor(href: Option<&str>) -> Option<String> {
+    let (_, query) = href?.split_once('?')?;
+    form_urlencoded::parse(query.as_bytes())
+        .find(|(key, _)| key == "cursor")
+        .map(|(_, cursor)| cursor.into_owned())
 }
 
-#[derive(Debug, Deserialize)]
-struct HorizonEmbeddedAccountTransactions {
-    records: Vec<HorizonAccountTransaction>,
+/// Mean gap between consecutive close times (in either order), in seconds.
+fn average_interval_seconds(closes: &[i64]) -> Option<f64> {
+    if closes.len() < 2 {
+        return None;
+    }
+    let span = (closes[0] - closes[closes.len() - 1]).abs();
+    Some(span as f64 / (closes.len() - 1) as f64)
 }
 
-fn extract_cursor(href: Option<&str>) -> Option<String> {
-    let href = href?;
-    let cursor_param = href.split(['?', '&']).find(|p| p.starts_with("cursor="))?;
-    Some(cursor_param.trim_start_matches("cursor=").to_string())
+/// Whether a stellar.toml lists the asset; `None` when it is not valid TOML.
+fn toml_lists_currency(source: &str, code: &str, issuer: &str) -> Option<bool> {
+    let value: toml::Value = source.parse().ok()?;
+    let listed = value
+        .get("CURRENCIES")
+        .and_then(toml::Value::as_array)
+        .is_some_and(|currencies| {
+            currencies.iter().any(|currency| {
+                currency.get("code").and_then(toml::Value::as_str) == Some(code)
+                    && currency.get("issuer").and_then(toml::Value::as_str) == Some(issuer)
+            })
+        });
+    Some(listed)
+}
+
+/// ORG_NAME and ACCOUNTS from a stellar.toml. ACCOUNTS is empty when the
+/// file is not valid TOML; ORG_NAME is still read line by line.
+fn parse_toml_identity(source: &str) -> TomlIdentity {
+    let accounts = source
+        .parse::<toml::Value>()
+        .ok()
+        .and_then(|value| value.get("ACCOUNTS").cloned())
+        .and_then(|accounts| accounts.as_array().cloned())
+        .map(|accounts| {
+            accounts
+                .iter()
+                .filter_map(toml::Value::as_str)
+                .map(|account| account.trim().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    TomlIdentity {
+        org_name: parse_org_name(source),
+        accounts,
+    }
 }
 
 fn parse_org_name(toml: &str) -> Option<String> {
@@ -469,3 +2129,55 @@ fn parse_org_name(toml: &str) -> Option<String> {
     }
     None
 }
+
+/// Run a response body through every Horizon response model the client
+/// parses, as it would for a 200. Used by the `horizon_json` fuzz target.
+#[cfg(feature = "fuzzing")]
+pub(crate) fn parse_responses(body: &[u8]) {
+    let res = HorizonResponse {
+        status: 200,
+        body: body.to_vec(),
+    };
+    let _ = res.json::<HorizonTransaction>();
+    if let Ok(raw) = res.json::<HorizonAccount>() {
+        raw.into_domain();
+    }
+    if let Ok(raw) = res.json::<HorizonClaimableBalance>() {
+        raw.into_domain();
+    }
+    if let Ok(raw) = res.json::<HorizonLiquidityPool>() {
+        raw.into_domain();
+    }
+    if let Ok(raw) = res.json::<HorizonFeeStats>() {
+        raw.into_market();
+    }
+    if let Ok(raw) = res.json::<HorizonFeeStats>() {
+        raw.into_domain();
+    }
+    if let Ok(raw) = res.json::<HorizonLedgersResponse>() {
+        raw.average_close_seconds();
+        raw._embedded.records.into_iter().for_each(|ledger| {
+            ledger.into_load();
+        });
+    }
+    if let Ok(page) = res.json::<HorizonPage<HorizonOffer>>() {
+        page._embedded.records.into_iter().for_each(|offer| {
+            offer.into_domain();
+        });
+    }
+    if let Ok(page) = res.json::<HorizonPage<HorizonTrade>>() {
+        amount_sold(&page._embedded.records, "1");
+    }
+    if let Ok(page) = res.json::<HorizonOperationsResponse>() {
+        page._embedded.records.into_iter().for_each(|op| {
+            op.into_pool_activity();
+        });
+    }
+    if let Ok(page) = res.json::<HorizonPage<HorizonAccountTransaction>>() {
+        let _ = Page::from(page);
+    }
+
+    let text = String::from_utf8_lossy(body);
+    parse_toml_identity(&text);
+    toml_lists_currency(&text, "USDC", "GISSUER");
+}
@@ -0,0 +1,912 @@
+//! The Horizon reads route handlers depend on.
+//!
+//! Handlers are generic over [`HorizonApi`] rather than taking a
+//! [`HorizonClient`], so their logic can be unit tested against
+//! [`FakeHorizon`], an in-memory Horizon, instead of a mock HTTP server.
+//! The server itself always runs them with `HorizonClient`.
+
+use async_trait::async_trait;
+use std::collections::HashMap;
+
+use crate::errors::HorizonError;
+use crate::models::account::{Account, AccountLinks};
+use crate::models::asset::{Clawback, StellarTomlStatus, TomlIdentity};
+use crate::models::claimable_balance::ClaimableBalance;
+use crate::models::fee::{FeeMarket, FeeStats, LedgerLoad};
+use crate::models::ledger::Ledger;
+use crate::models::liquidity_pool::{LiquidityPool, PoolActivity};
+use crate::models::offer::Offer;
+use crate::models::trade::{TradeBucket, TradeFill};
+use crate::services::circuit::{CircuitSnapshot, CircuitState};
+use crate::services::horizon::{
+    AccountHistory, HorizonAccountTransaction, HorizonClient, HorizonOperation, HorizonSubmission,
+    HorizonTransaction,
+};
+
+/// A page of account transactions with its next and previous cursors.
+pub type AccountTransactionsPage = (
+    Vec<HorizonAccountTransaction>,
+    Option<String>,
+    Option<String>,
+);
+
+/// A page of account operations with its next and previous cursors.
+pub type AccountOperationsPage = (Vec<HorizonOperation>, Option<String>, Option<String>);
+
+/// Horizon as seen by the route handlers. See the matching [`HorizonClient`]
+/// methods for what each call fetches.
+#[async_trait]
+pub trait HorizonApi: Send + Sync + 'static {
+    async fn fetch_transaction(&self, hash: &str) -> Result<HorizonTransaction, HorizonError>;
+
+    async fn fetch_operations(&self, hash: &str) -> Result<Vec<HorizonOperation>, HorizonError>;
+
+    async fn fetch_account(&self, address: &str) -> Result<Account, HorizonError>;
+
+    async fn fetch_account_history(&self, address: &str) -> Result<AccountHistory, HorizonError>;
+
+    async fn fetch_account_links(&self, address: &str) -> Result<AccountLinks, HorizonError>;
+
+    async fn fetch_accounts_with_signer(
+        &self,
+        key: &str,
+        limit: u32,
+    ) -> Result<Vec<AccountLinks>, HorizonError>;
+
+    async fn fetch_accounts_sponsored_by(
+        &self,
+        sponsor: &str,
+        limit: u32,
+    ) -> Result<Vec<AccountLinks>, HorizonError>;
+
+    async fn fetch_account_operations(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<HorizonOperation>, HorizonError>;
+
+    async fn fetch_account_operations_page(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<&str>,
+        order: &str,
+    ) -> Result<AccountOperationsPage, HorizonError>;
+
+    async fn fetch_account_transactions(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<&str>,
+        order: &str,
+    ) -> Result<AccountTransactionsPage, HorizonError>;
+
+    async fn fetch_account_offers(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<Offer>, HorizonError>;
+
+    async fn fetch_offer_amount_sold(&self, offer_id: u64) -> Result<String, HorizonError>;
+
+    async fn fetch_operation_trades(
+        &self,
+        operation_id: &str,
+        account: &str,
+    ) -> Result<Vec<TradeFill>, HorizonError>;
+
+    /// The last price `base` traded at in `counter` before `before`, or None.
+    async fn fetch_market_price(&self, base: &str, counter: &str, before: &str) -> Option<f64>;
+
+    /// The trades of `base` against `counter` between two Unix millisecond
+    /// times, in buckets of `resolution` milliseconds, oldest first.
+    async fn fetch_trade_aggregations(
+        &self,
+        base: &str,
+        counter: &str,
+        start_time: i64,
+        end_time: i64,
+        resolution: i64,
+    ) -> Result<Vec<TradeBucket>, HorizonError>;
+
+    async fn fetch_claimable_balance(&self, id: &str) -> Result<ClaimableBalance, HorizonError>;
+
+    async fn fetch_ledger(&self, sequence: u64) -> Result<Ledger, HorizonError>;
+
+    async fn fetch_liquidity_pool(&self, id: &str) -> Result<LiquidityPool, HorizonError>;
+
+    async fn fetch_liquidity_pool_activity(
+        &self,
+        id: &str,
+        scan: u32,
+    ) -> Result<Vec<PoolActivity>, HorizonError>;
+
+    async fn fetch_issuer_clawbacks(
+        &self,
+        issuer: &str,
+        asset_code: &str,
+        scan: u32,
+    ) -> Result<Vec<Clawback>, HorizonError>;
+
+    /// Submit a base64 transaction envelope and wait for the outcome.
+    async fn submit_transaction(
+        &self,
+        envelope_xdr: &str,
+    ) -> Result<HorizonSubmission, HorizonError>;
+
+    /// Current network fee stats, or None if unavailable.
+    async fn fetch_fee_stats(&self) -> Option<FeeStats>;
+
+    /// Recent average ledger close time, or None if unavailable.
+    async fn fetch_average_ledger_close_seconds(&self) -> Option<f64>;
+
+    /// Capacity usage and fee percentiles from `/fee_stats`.
+    async fn fetch_fee_market(&self) -> Result<FeeMarket, HorizonError>;
+
+    /// How full the `limit` most recent ledgers were, newest first.
+    async fn fetch_ledger_loads(&self, limit: u32) -> Result<Vec<LedgerLoad>, HorizonError>;
+
+    /// ORG_NAME and ACCOUNTS from the stellar.toml at `domain`, a URL.
+    async fn fetch_stellar_toml_identity(&self, domain: &str) -> Option<TomlIdentity>;
+
+    async fn check_stellar_toml(
+        &self,
+        domain: &str,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> StellarTomlStatus;
+
+    async fn is_reachable(&self) -> bool;
+
+    fn circuit_snapshot(&self) -> CircuitSnapshot;
+
+    /// Responses held for stale replay; `None` if the cache is unusable.
+    fn stale_cache_len(&self) -> Option<usize>;
+}
+
+#[async_trait]
+impl HorizonApi for HorizonClient {
+    async fn fetch_transaction(&self, hash: &str) -> Result<HorizonTransaction, HorizonError> {
+        HorizonClient::fetch_transaction(self, hash).await
+    }
+
+    async fn fetch_operations(&self, hash: &str) -> Result<Vec<HorizonOperation>, HorizonError> {
+        HorizonClient::fetch_operations(self, hash).await
+    }
+
+    async fn fetch_account(&self, address: &str) -> Result<Account, HorizonError> {
+        HorizonClient::fetch_account(self, address).await
+    }
+
+    async fn fetch_account_history(&self, address: &str) -> Result<AccountHistory, HorizonError> {
+        HorizonClient::fetch_account_history(self, address).await
+    }
+
+    async fn fetch_account_links(&self, address: &str) -> Result<AccountLinks, HorizonError> {
+        HorizonClient::fetch_account_links(self, address).await
+    }
+
+    async fn fetch_accounts_with_signer(
+        &self,
+        key: &str,
+        limit: u32,
+    ) -> Result<Vec<AccountLinks>, HorizonError> {
+        HorizonClient::fetch_accounts_with_signer(self, key, limit).await
+    }
+
+    async fn fetch_accounts_sponsored_by(
+        &self,
+        sponsor: &str,
+        limit: u32,
+    ) -> Result<Vec<AccountLinks>, HorizonError> {
+        HorizonClient::fetch_accounts_sponsored_by(self, sponsor, limit).await
+    }
+
+    async fn fetch_account_operations(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<HorizonOperation>, HorizonError> {
+        HorizonClient::fetch_account_operations(self, address, limit).await
+    }
+
+    async fn fetch_account_operations_page(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<&str>,
+        order: &str,
+    ) -> Result<AccountOperationsPage, HorizonError> {
+        HorizonClient::fetch_account_operations_page(self, address, limit, cursor, order).await
+    }
+
+    async fn fetch_account_transactions(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<&str>,
+        order: &str,
+    ) -> Result<AccountTransactionsPage, HorizonError> {
+        HorizonClient::fetch_account_transactions(self, address, limit, cursor, order).await
+    }
+
+    async fn fetch_account_offers(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<Offer>, HorizonError> {
+        HorizonClient::fetch_account_offers(self, address, limit).await
+    }
+
+    async fn fetch_offer_amount_sold(&self, offer_id: u64) -> Result<String, HorizonError> {
+        HorizonClient::fetch_offer_amount_sold(self, offer_id).await
+    }
+
+    async fn fetch_operation_trades(
+        &self,
+        operation_id: &str,
+        account: &str,
+    ) -> Result<Vec<TradeFill>, HorizonError> {
+        HorizonClient::fetch_operation_trades(self, operation_id, account).await
+    }
+
+    async fn fetch_market_price(&self, base: &str, counter: &str, before: &str) -> Option<f64> {
+        HorizonClient::fetch_market_price(self, base, counter, before).await
+    }
+
+    async fn fetch_trade_aggregations(
+        &self,
+        base: &str,
+        counter: &str,
+        start_time: i64,
+        end_time: i64,
+        resolution: i64,
+    ) -> Result<Vec<TradeBucket>, HorizonError> {
+        HorizonClient::fetch_trade_aggregations(
+            self, base, counter, start_time, end_time, resolution,
+        )
+        .await
+    }
+
+    async fn fetch_claimable_balance(&self, id: &str) -> Result<ClaimableBalance, HorizonError> {
+        HorizonClient::fetch_claimable_balance(self, id).await
+    }
+
+    async fn fetch_ledger(&self, sequence: u64) -> Result<Ledger, HorizonError> {
+        HorizonClient::fetch_ledger(self, sequence).await
+    }
+
+    async fn fetch_liquidity_pool(&self, id: &str) -> Result<LiquidityPool, HorizonError> {
+        HorizonClient::fetch_liquidity_pool(self, id).await
+    }
+
+    async fn fetch_liquidity_pool_activity(
+        &self,
+        id: &str,
+        scan: u32,
+    ) -> Result<Vec<PoolActivity>, HorizonError> {
+        HorizonClient::fetch_liquidity_pool_activity(self, id, scan).await
+    }
+
+    async fn fetch_issuer_clawbacks(
+        &self,
+        issuer: &str,
+        asset_code: &str,
+        scan: u32,
+    ) -> Result<Vec<Clawback>, HorizonError> {
+        HorizonClient::fetch_issuer_clawbacks(self, issuer, asset_code, scan).await
+    }
+
+    async fn submit_transaction(
+        &self,
+        envelope_xdr: &str,
+    ) -> Result<HorizonSubmission, HorizonError> {
+        HorizonClient::submit_transaction(self, envelope_xdr).await
+    }
+
+    async fn fetch_fee_stats(&self) -> Option<FeeStats> {
+        HorizonClient::fetch_fee_stats(self).await
+    }
+
+    async fn fetch_average_ledger_close_seconds(&self) -> Option<f64> {
+        HorizonClient::fetch_average_ledger_close_seconds(self).await
+    }
+
+    async fn fetch_fee_market(&self) -> Result<FeeMarket, HorizonError> {
+        HorizonClient::fetch_fee_market(self).await
+    }
+
+    async fn fetch_ledger_loads(&self, limit: u32) -> Result<Vec<LedgerLoad>, HorizonError> {
+        HorizonClient::fetch_ledger_loads(self, limit).await
+    }
+
+    async fn fetch_stellar_toml_identity(&self, domain: &str) -> Option<TomlIdentity> {
+        HorizonClient::fetch_stellar_toml_identity(self, domain).await
+    }
+
+    async fn check_stellar_toml(
+        &self,
+        domain: &str,
+        asset_code: &str,
+        asset_issuer: &str,
+    ) -> StellarTomlStatus {
+        HorizonClient::check_stellar_toml(self, domain, asset_code, asset_issuer).await
+    }
+
+    async fn is_reachable(&self) -> bool {
+        HorizonClient::is_reachable(self).await
+    }
+
+    fn circuit_snapshot(&self) -> CircuitSnapshot {
+        HorizonClient::circuit_snapshot(self)
+    }
+
+    fn stale_cache_len(&self) -> Option<usize> {
+        HorizonClient::stale_cache_len(self)
+    }
+}
+
+/// An in-memory Horizon for handler tests.
+///
+/// Starts empty and reachable, with a closed circuit and no fee stats; the
+/// `with_*` methods add records. Lookups of records that were never added
+/// fail the way Horizon's 404s do, and list endpoints return nothing.
+#[derive(Debug, Clone)]
+pub struct FakeHorizon {
+    transactions: HashMap<String, (HorizonTransaction, Vec<HorizonOperation>)>,
+    accounts: HashMap<String, Account>,
+    account_links: HashMap<String, AccountLinks>,
+    account_histories: HashMap<String, AccountHistory>,
+    account_operations: HashMap<String, Vec<HorizonOperation>>,
+    account_transactions: HashMap<String, Vec<HorizonAccountTransaction>>,
+    offers: HashMap<String, Vec<Offer>>,
+    amounts_sold: HashMap<u64, String>,
+    trades: HashMap<String, Vec<TradeFill>>,
+    market_prices: HashMap<(String, String), f64>,
+    trade_buckets: HashMap<(String, String), Vec<TradeBucket>>,
+    claimable_balances: HashMap<String, ClaimableBalance>,
+    ledgers: HashMap<u64, Ledger>,
+    liquidity_pools: HashMap<String, (LiquidityPool, Vec<PoolActivity>)>,
+    clawbacks: HashMap<(String, String), Vec<Clawback>>,
+    toml_identities: HashMap<String, TomlIdentity>,
+    toml_statuses: HashMap<String, StellarTomlStatus>,
+    submission: Option<HorizonSubmission>,
+    fee_stats: Option<FeeStats>,
+    fee_market: Option<FeeMarket>,
+    ledger_loads: Vec<LedgerLoad>,
+    average_close_seconds: Option<f64>,
+    reachable: bool,
+    circuit: CircuitSnapshot,
+}
+
+impl Default for FakeHorizon {
+    fn default() -> Self {
+        Self {
+            transactions: HashMap::new(),
+            accounts: HashMap::new(),
+            account_links: HashMap::new(),
+            account_histories: HashMap::new(),
+            account_operations: HashMap::new(),
+            account_transactions: HashMap::new(),
+            offers: HashMap::new(),
+            amounts_sold: HashMap::new(),
+            trades: HashMap::new(),
+            market_prices: HashMap::new(),
+            trade_buckets: HashMap::new(),
+            claimable_balances: HashMap::new(),
+            ledgers: HashMap::new(),
+            liquidity_pools: HashMap::new(),
+            clawbacks: HashMap::new(),
+            toml_identities: HashMap::new(),
+            toml_statuses: HashMap::new(),
+            submission: None,
+            fee_stats: None,
+            fee_market: None,
+            ledger_loads: Vec::new(),
+            average_close_seconds: None,
+            reachable: true,
+            circuit: CircuitSnapshot {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                retry_after_seconds: None,
+            },
+        }
+    }
+}
+
+impl FakeHorizon {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_transaction(
+        mut self,
+        transaction: HorizonTransaction,
+        operations: Vec<HorizonOperation>,
+    ) -> Self {
+        self.transactions
+            .insert(transaction.hash.clone(), (transaction, operations));
+        self
+    }
+
+    pub fn with_account(mut self, account: Account) -> Self {
+        self.accounts.insert(account.account_id.clone(), account);
+        self
+    }
+
+    /// An account's signers and sponsor, also searched by signer and sponsor.
+    pub fn with_account_links(mut self, links: AccountLinks) -> Self {
+        self.account_links.insert(links.account_id.clone(), links);
+        self
+    }
+
+    /// History reported for an address with no account entry.
+    pub fn with_account_history(mut self, address: &str, history: AccountHistory) -> Self {
+        self.account_histories.insert(address.to_string(), history);
+        self
+    }
+
+    /// Operations on an account, newest first. Paged with operation ids as
+    /// cursors.
+    pub fn with_account_operations(
+        mut self,
+        address: &str,
+        operations: Vec<HorizonOperation>,
+    ) -> Self {
+        self.account_operations
+            .insert(address.to_string(), operations);
+        self
+    }
+
+    /// Transactions on an account, oldest first. Paged with paging tokens
+    /// as cursors.
+    pub fn with_account_transactions(
+        mut self,
+        address: &str,
+        transactions: Vec<HorizonAccountTransaction>,
+    ) -> Self {
+        self.account_transactions
+            .insert(address.to_string(), transactions);
+        self
+    }
+
+    pub fn with_offer(mut self, offer: Offer, amount_sold: Option<&str>) -> Self {
+        if let Some(sold) = amount_sold {
+            self.amounts_sold.insert(offer.id, sold.to_string());
+        }
+        self.offers
+            .entry(offer.seller.clone())
+            .or_default()
+            .push(offer);
+        self
+    }
+
+    /// The trades operation `operation_id` made, from its payer's side.
+    pub fn with_trades(mut self, operation_id: &str, trades: Vec<TradeFill>) -> Self {
+        self.trades.insert(operation_id.to_string(), trades);
+        self
+    }
+
+    /// The last price `base` traded at in `counter`, at any time.
+    pub fn with_market_price(mut self, base: &str, counter: &str, price: f64) -> Self {
+        self.market_prices
+            .insert((base.to_string(), counter.to_string()), price);
+        self
+    }
+
+    /// Trade aggregation buckets of `base` against `counter`, at any
+    /// resolution.
+    pub fn with_trade_buckets(
+        mut self,
+        base: &str,
+        counter: &str,
+        buckets: Vec<TradeBucket>,
+    ) -> Self {
+        self.trade_buckets
+            .insert((base.to_string(), counter.to_string()), buckets);
+        self
+    }
+
+    pub fn with_claimable_balance(mut self, balance: ClaimableBalance) -> Self {
+        self.claimable_balances.insert(balance.id.clone(), balance);
+        self
+    }
+
+    pub fn with_ledger(mut self, ledger: Ledger) -> Self {
+        self.ledgers.insert(ledger.sequence, ledger);
+        self
+    }
+
+    pub fn with_liquidity_pool(mut self, pool: LiquidityPool, activity: Vec<PoolActivity>) -> Self {
+        self.liquidity_pools
+            .insert(pool.id.clone(), (pool, activity));
+        self
+    }
+
+    /// Recent clawbacks of `asset_code` by `issuer`, newest first.
+    pub fn with_clawbacks(
+        mut self,
+        issuer: &str,
+        asset_code: &str,
+        clawbacks: Vec<Clawback>,
+    ) -> Self {
+        self.clawbacks
+            .insert((issuer.to_string(), asset_code.to_string()), clawbacks);
+        self
+    }
+
+    /// ORG_NAME served from `domain`'s stellar.toml, e.g. "https://example.com".
+    pub fn with_org_name(mut self, domain: &str, org_name: &str) -> Self {
+        self.toml_identities
+            .entry(domain.to_string())
+            .or_default()
+            .org_name = Some(org_name.to_string());
+        self
+    }
+
+    /// ACCOUNTS listed in `domain`'s stellar.toml.
+    pub fn with_toml_accounts(mut self, domain: &str, accounts: &[&str]) -> Self {
+        self.toml_identities
+            .entry(domain.to_string())
+            .or_default()
+            .accounts = accounts.iter().map(|a| a.to_string()).collect();
+        self
+    }
+
+    /// What `domain`'s stellar.toml says about every asset. Domains without
+    /// one report [`StellarTomlStatus::Missing`].
+    pub fn with_stellar_toml(mut self, domain: &str, status: StellarTomlStatus) -> Self {
+        self.toml_statuses.insert(domain.to_string(), status);
+        self
+    }
+
+    /// Answer every submission with `submission`; without one, submissions
+    /// fail as an unreadable response.
+    pub fn with_submission(mut self, submission: HorizonSubmission) -> Self {
+        self.submission = Some(submission);
+        self
+    }
+
+    pub fn with_fee_stats(mut self, fee_stats: FeeStats) -> Self {
+        self.fee_stats = Some(fee_stats);
+        self
+    }
+
+    pub fn with_fee_market(mut self, fee_market: FeeMarket) -> Self {
+        self.fee_market = Some(fee_market);
+        self
+    }
+
+    /// Recent ledgers, newest first.
+    pub fn with_ledger_loads(mut self, loads: Vec<LedgerLoad>) -> Self {
+        self.ledger_loads = loads;
+        self
+    }
+
+    pub fn with_average_close_seconds(mut self, seconds: f64) -> Self {
+        self.average_close_seconds = Some(seconds);
+        self
+    }
+
+    pub fn with_circuit(mut self, circuit: CircuitSnapshot) -> Self {
+        self.circuit = circuit;
+        self
+    }
+
+    pub fn unreachable(mut self) -> Self {
+        self.reachable = false;
+        self
+    }
+}
+
+#[async_trait]
+impl HorizonApi for FakeHorizon {
+    async fn fetch_transaction(&self, hash: &str) -> Result<HorizonTransaction, HorizonError> {
+        self.transactions
+            .get(hash)
+            .map(|(tx, _)| tx.clone())
+            .ok_or(HorizonError::TransactionNotFound)
+    }
+
+    async fn fetch_operations(&self, hash: &str) -> Result<Vec<HorizonOperation>, HorizonError> {
+        self.transactions
+            .get(hash)
+            .map(|(_, ops)| ops.clone())
+            .ok_or(HorizonError::TransactionNotFound)
+    }
+
+    async fn fetch_account(&self, address: &str) -> Result<Account, HorizonError> {
+        self.accounts
+            .get(address)
+            .cloned()
+            .ok_or(HorizonError::AccountNotFound)
+    }
+
+    async fn fetch_account_history(&self, address: &str) -> Result<AccountHistory, HorizonError> {
+        Ok(self
+            .account_histories
+            .get(address)
+            .cloned()
+            .unwrap_or(AccountHistory::NeverCreated))
+    }
+
+    async fn fetch_account_links(&self, address: &str) -> Result<AccountLinks, HorizonError> {
+        self.account_links
+            .get(address)
+            .cloned()
+            .ok_or(HorizonError::AccountNotFound)
+    }
+
+    async fn fetch_accounts_with_signer(
+        &self,
+        key: &str,
+        limit: u32,
+    ) -> Result<Vec<AccountLinks>, HorizonError> {
+        let mut found: Vec<AccountLinks> = self
+            .account_links
+            .values()
+            .filter(|links| links.signers.iter().any(|s| s.key == key && s.weight > 0))
+            .cloned()
+            .collect();
+        found.sort_by(|a, b| a.account_id.cmp(&b.account_id));
+        found.truncate(limit as usize);
+        Ok(found)
+    }
+
+    async fn fetch_accounts_sponsored_by(
+        &self,
+        sponsor: &str,
+        limit: u32,
+    ) -> Result<Vec<AccountLinks>, HorizonError> {
+        let mut found: Vec<AccountLinks> = self
+            .account_links
+            .values()
+            .filter(|links| {
+                links.sponsor.as_deref() == Some(sponsor)
+                    || links
+                        .signers
+                        .iter()
+                        .any(|s| s.sponsor.as_deref() == Some(sponsor))
+            })
+            .cloned()
+            .collect();
+        found.sort_by(|a, b| a.account_id.cmp(&b.account_id));
+        found.truncate(limit as usize);
+        Ok(found)
+    }
+
+    async fn fetch_account_operations(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<HorizonOperation>, HorizonError> {
+        if !self.accounts.contains_key(address) {
+            return Err(HorizonError::AccountNotFound);
+        }
+        Ok(self
+            .account_operations
+            .get(address)
+            .map(|ops| ops.iter().take(limit as usize).cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn fetch_account_operations_page(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<&str>,
+        order: &str,
+    ) -> Result<AccountOperationsPage, HorizonError> {
+        if !self.accounts.contains_key(address) {
+            return Err(HorizonError::AccountNotFound);
+        }
+        let mut records = self
+            .account_operations
+            .get(address)
+            .cloned()
+            .unwrap_or_default();
+        if order == "asc" {
+            records.reverse();
+        }
+        // Operation ids are their paging tokens: resume after the cursor.
+        if let Some(cursor) = cursor
+            && let Some(at) = records.iter().position(|op| op.id == cursor)
+        {
+            records.drain(..=at);
+        }
+        let more = records.len() > limit as usize;
+        records.truncate(limit as usize);
+        let next = more
+            .then(|| records.last().map(|op| op.id.clone()))
+            .flatten();
+        Ok((records, next, None))
+    }
+
+    async fn fetch_account_transactions(
+        &self,
+        address: &str,
+        limit: u32,
+        cursor: Option<&str>,
+        order: &str,
+    ) -> Result<AccountTransactionsPage, HorizonError> {
+        if !self.accounts.contains_key(address) {
+            return Err(HorizonError::AccountNotFound);
+        }
+        let mut records = self
+            .account_transactions
+            .get(address)
+            .cloned()
+            .unwrap_or_default();
+        if order == "desc" {
+            records.reverse();
+        }
+        if let Some(cursor) = cursor
+            && let Some(at) = records
+                .iter()
+                .position(|tx| tx.paging_token.as_deref() == Some(cursor))
+        {
+            records.drain(..=at);
+        }
+        let more = records.len() > limit as usize;
+        records.truncate(limit as usize);
+        let next = more
+            .then(|| records.last().and_then(|tx| tx.paging_token.clone()))
+            .flatten();
+        Ok((records, next, None))
+    }
+
+    async fn fetch_account_offers(
+        &self,
+        address: &str,
+        limit: u32,
+    ) -> Result<Vec<Offer>, HorizonError> {
+        if !self.accounts.contains_key(address) {
+            return Err(HorizonError::AccountNotFound);
+        }
+        Ok(self
+            .offers
+            .get(address)
+            .map(|offers| offers.iter().take(limit as usize).cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn fetch_offer_amount_sold(&self, offer_id: u64) -> Result<String, HorizonError> {
+        self.amounts_sold
+            .get(&offer_id)
+            .cloned()
+            .ok_or(HorizonError::InvalidResponse)
+    }
+
+    async fn fetch_operation_trades(
+        &self,
+        operation_id: &str,
+        _account: &str,
+    ) -> Result<Vec<TradeFill>, HorizonError> {
+        Ok(self.trades.get(operation_id).cloned().unwrap_or_default())
+    }
+
+    async fn fetch_market_price(&self, base: &str, counter: &str, _before: &str) -> Option<f64> {
+        self.market_prices
+            .get(&(base.to_string(), counter.to_string()))
+            .copied()
+    }
+
+    async fn fetch_trade_aggregations(
+        &self,
+        base: &str,
+        counter: &str,
+        start_time: i64,
+        end_time: i64,
+        _resolution: i64,
+    ) -> Result<Vec<TradeBucket>, HorizonError> {
+        Ok(self
+            .trade_buckets
+            .get(&(base.to_string(), counter.to_string()))
+            .into_iter()
+            .flatten()
+            .filter(|bucket| (start_time..end_time).contains(&bucket.timestamp))
+            .cloned()
+            .collect())
+    }
+
+    async fn fetch_claimable_balance(&self, id: &str) -> Result<ClaimableBalance, HorizonError> {
+        self.claimable_balances
+            .get(id)
+            .cloned()
+            .ok_or(HorizonError::ClaimableBalanceNotFound)
+    }
+
+    async fn fetch_ledger(&self, sequence: u64) -> Result<Ledger, HorizonError> {
+        self.ledgers
+            .get(&sequence)
+            .cloned()
+            .ok_or(HorizonError::LedgerNotFound)
+    }
+
+    async fn fetch_liquidity_pool(&self, id: &str) -> Result<LiquidityPool, HorizonError> {
+        self.liquidity_pools
+            .get(id)
+            .map(|(pool, _)| pool.clone())
+            .ok_or(HorizonError::LiquidityPoolNotFound)
+    }
+
+    async fn fetch_liquidity_pool_activity(
+        &self,
+        id: &str,
+        scan: u32,
+    ) -> Result<Vec<PoolActivity>, HorizonError> {
+        self.liquidity_pools
+            .get(id)
+            .map(|(_, activity)| activity.iter().take(scan as usize).cloned().collect())
+            .ok_or(HorizonError::LiquidityPoolNotFound)
+    }
+
+    async fn fetch_issuer_clawbacks(
+        &self,
+        issuer: &str,
+        asset_code: &str,
+        _scan: u32,
+    ) -> Result<Vec<Clawback>, HorizonError> {
+        Ok(self
+            .clawbacks
+            .get(&(issuer.to_string(), asset_code.to_string()))
+            .cloned()
+            .unwrap_or_default())
+    }
+
+    async fn submit_transaction(
+        &self,
+        _envelope_xdr: &str,
+    ) -> Result<HorizonSubmission, HorizonError> {
+        self.submission.clone().ok_or(HorizonError::InvalidResponse)
+    }
+
+    async fn fetch_fee_stats(&self) -> Option<FeeStats> {
+        self.fee_stats.clone()
+    }
+
+    async fn fetch_average_ledger_close_seconds(&self) -> Option<f64> {
+        self.average_close_seconds
+    }
+
+    async fn fetch_fee_market(&self) -> Result<FeeMarket, HorizonError> {
+        self.fee_market.clone().ok_or(HorizonError::InvalidResponse)
+    }
+
+    async fn fetch_ledger_loads(&self, limit: u32) -> Result<Vec<LedgerLoad>, HorizonError> {
+        Ok(self
+            .ledger_loads
+            .iter()
+            .take(limit as usize)
+            .cloned()
+            .collect())
+    }
+
+    async fn fetch_stellar_toml_identity(&self, domain: &str) -> Option<TomlIdentity> {
+        self.toml_identities.get(domain).cloned()
+    }
+
+    async fn check_stellar_toml(
+        &self,
+        domain: &str,
+        _asset_code: &str,
+        _asset_issuer: &str,
+    ) -> StellarTomlStatus {
+        self.toml_statuses
+            .get(domain)
+            .copied()
+            .unwrap_or(StellarTomlStatus::Missing)
+    }
+
+    async fn is_reachable(&self) -> bool {
+        self.reachable
+    }
+
+    fn circuit_snapshot(&self) -> CircuitSnapshot {
+        self.circuit.clone()
+    }
+
+    fn stale_cache_len(&self) -> Option<usize> {
+        Some(0)
+    }
+}
@@ -0,0 +1,250 @@
+//! Spread Horizon requests over several upstreams and route around bad ones.
+//!
+//! Requests start at the next upstream in round-robin order. An upstream
+//! that fails `eject_after` requests in a row (network errors, 429s and
+//! 5xx responses) is ejected for `ejection`: it is tried only after every
+//! healthy upstream has failed the same request. A background health check
+//! GETs each upstream's root every `health_interval`, ejecting those that
+//! do not answer and restoring those that do, so a recovered upstream
+//! rejoins the rotation without waiting out its ejection.
+//!
+//! Configuration (environment):
+//! - `HORIZON_URLS_PUBLIC` / `HORIZON_URLS_TESTNET`: comma-separated
+//!   upstreams for that network. `HORIZON_URLS` applies to whichever network
+//!   is selected. Unset means the single `HORIZON_URL`.
+//! - `HORIZON_POOL_EJECT_FAILURES`: consecutive failures before ejection
+//!   (default 3).
+//! - `HORIZON_POOL_EJECT_SECONDS`: how long an upstream stays ejected
+//!   (default 30).
+//! - `HORIZON_POOL_HEALTH_INTERVAL_SECONDS`: time between health checks
+//!   (default 10).
+
+use reqwest::Client;
+use std::env;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PoolConfig {
+    pub urls: Vec<String>,
+    pub eject_after: u32,
+    pub ejection: Duration,
+    pub health_interval: Duration,
+}
+
+impl PoolConfig {
+    pub fn new(urls: Vec<String>) -> Self {
+        Self {
+            urls,
+            eject_after: 3,
+            ejection: Duration::from_secs(30),
+            health_interval: Duration::from_secs(10),
+        }
+    }
+
+    /// Read the upstreams configured for `network` (as named by
+    /// `STELLAR_NETWORK`), or `None` when fewer than two are set.
+    pub fn from_env(network: &str) -> Option<Self> {
+        let urls: Vec<String> = env::var(format!("HORIZON_URLS_{}", network.to_ascii_uppercase()))
+            .or_else(|_| env::var("HORIZON_URLS"))
+            .ok()?
+            .split(',')
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+            .collect();
+        if urls.len() < 2 {
+            return None;
+        }
+
+        let defaults = Self::new(urls);
+        let parse = |name: &str| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+        };
+        Some(Self {
+            eject_after: parse("HORIZON_POOL_EJECT_FAILURES")
+                .map(|n| n.min(u32::MAX as u64) as u32)
+                .unwrap_or(defaults.eject_after),
+            ejection: parse("HORIZON_POOL_EJECT_SECONDS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.ejection),
+            health_interval: parse("HORIZON_POOL_HEALTH_INTERVAL_SECONDS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.health_interval),
+            ..defaults
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct Health {
+    consecutive_failures: u32,
+    ejected_until: Option<Instant>,
+}
+
+#[derive(Debug)]
+pub struct HorizonPool {
+    config: PoolConfig,
+    health: Vec<Mutex<Health>>,
+    next: AtomicUsize,
+}
+
+impl HorizonPool {
+    pub fn new(config: PoolConfig) -> Self {
+        Self {
+            health: config.urls.iter().map(|_| Mutex::default()).collect(),
+            config,
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// The primary upstream, whose URL the client builds requests with.
+    pub fn primary(&self) -> &str {
+        &self.config.urls[0]
+    }
+
+    /// Upstreams to try for one request, in order: the healthy ones
+    /// starting from the next in rotation, then the ejected ones.
+    pub fn candidates(&self) -> Vec<&str> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let count = self.config.urls.len();
+        let (healthy, ejected): (Vec<usize>, Vec<usize>) = (0..count)
+            .map(|offset| (start + offset) % count)
+            .partition(|&i| !self.is_ejected(i));
+        healthy
+            .into_iter()
+            .chain(ejected)
+            .map(|i| self.config.urls[i].as_str())
+            .collect()
+    }
+
+    /// Count a good answer from `url`, restoring it if it was ejected.
+    pub fn record_success(&self, url: &str) {
+        let Some(i) = self.index(url) else {
+            return;
+        };
+        let mut health = self.lock(i);
+        if health.ejected_until.take().is_some() {
+            info!(upstream = %url, "horizon_upstream_restored");
+        }
+        health.consecutive_failures = 0;
+    }
+
+    /// Count a failure from `url`, ejecting it once it has failed
+    /// `eject_after` times in a row.
+    pub fn record_failure(&self, url: &str) {
+        let Some(i) = self.index(url) else {
+            return;
+        };
+        let mut health = self.lock(i);
+        health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+        if health.consecutive_failures >= self.config.eject_after {
+            if health.ejected_until.is_none() {
+                warn!(
+                    upstream = %url,
+                    failures = health.consecutive_failures,
+                    "horizon_upstream_ejected"
+                );
+            }
+            health.ejected_until = Some(Instant::now() + self.config.ejection);
+        }
+    }
+
+    fn is_ejected(&self, i: usize) -> bool {
+        let mut health = self.lock(i);
+        match health.ejected_until {
+            Some(until) if until > Instant::now() => true,
+            Some(_) => {
+                // Ejection served; give the upstream another chance.
+                health.ejected_until = None;
+                health.consecutive_failures = 0;
+                false
+            }
+            None => false,
+        }
+    }
+
+    fn index(&self, url: &str) -> Option<usize> {
+        self.config.urls.iter().position(|u| u == url)
+    }
+
+    fn lock(&self, i: usize) -> std::sync::MutexGuard<'_, Health> {
+        self.health[i].lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Check every upstream once.
+pub async fn check_health(pool: &HorizonPool, client: &Client) {
+    for url in &pool.config.urls {
+        match client.get(url).send().await {
+            Ok(res) if res.status().is_success() => pool.record_success(url),
+            _ => pool.record_failure(url),
+        }
+    }
+}
+
+/// Run forever, checking every upstream every `health_interval`.
+pub async fn run_health_checks(pool: Arc<HorizonPool>, client: Client) {
+    loop {
+        tokio::time::sleep(pool.config.health_interval).await;
+        check_health(&pool, &client).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: &str = "https://a.example.com";
+    const B: &str = "https://b.example.com";
+    const C: &str = "https://c.example.com";
+
+    fn pool() -> HorizonPool {
+        HorizonPool::new(PoolConfig::new(vec![
+            A.to_string(),
+            B.to_string(),
+            C.to_string(),
+        ]))
+    }
+
+    #[test]
+    fn test_candidates_rotate() {
+        let pool = pool();
+
+        assert_eq!(pool.candidates(), vec![A, B, C]);
+        assert_eq!(pool.candidates(), vec![B, C, A]);
+        assert_eq!(pool.candidates(), vec![C, A, B]);
+    }
+
+    #[test]
+    fn test_failing_upstream_is_tried_last_until_it_recovers() {
+        let pool = pool();
+        for _ in 0..3 {
+            pool.record_failure(A);
+        }
+
+        assert_eq!(pool.candidates(), vec![B, C, A]);
+        assert_eq!(pool.candidates(), vec![B, C, A]);
+
+        pool.record_success(A);
+        assert_eq!(pool.candidates(), vec![C, A, B]);
+    }
+
+    #[test]
+    fn test_ejection_expires() {
+        let pool = HorizonPool::new(PoolConfig {
+            ejection: Duration::ZERO,
+            ..PoolConfig::new(vec![A.to_string(), B.to_string()])
+        });
+        for _ in 0..3 {
+            pool.record_failure(A);
+        }
+
+        assert_eq!(pool.candidates(), vec![A, B]);
+    }
+}
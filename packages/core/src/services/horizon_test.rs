@@ -54,6 +54,208 @@ mod tests {
         matches!(err, crate::errors::HorizonError::InvalidResponse);
     }
 
+    #[tokio::test]
+    async fn fetch_account_maps_trust_line_fields() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/accounts/GTRUST");
+            then.status(200).json_body(serde_json::json!({
+                "id": "GTRUST",
+                "account_id": "GTRUST",
+                "sequence": "1",
+                "balances": [
+                    {
+                        "asset_type": "credit_alphanum4",
+                        "asset_code": "USDC",
+                        "asset_issuer": "GISSUER",
+                        "balance": "25.0000000",
+                        "limit": "1000.0000000",
+                        "buying_liabilities": "0.0000000",
+                        "selling_liabilities": "5.0000000",
+                        "is_authorized": true,
+                        "is_authorized_to_maintain_liabilities": true
+                    },
+                    { "asset_type": "native", "balance": "10.0000000" }
+                ],
+                "signers": [{ "weight": 1 }],
+                "flags": {}
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let account = client.fetch_account("GTRUST").await.unwrap();
+
+        let usdc = &account.balances[0];
+        assert_eq!(usdc.limit.as_deref(), Some("1000.0000000"));
+        assert_eq!(usdc.selling_liabilities.as_deref(), Some("5.0000000"));
+        assert_eq!(usdc.is_authorized, Some(true));
+        assert!(account.balances[1].limit.is_none());
+        assert!(!account.memo_required);
+    }
+
+    #[tokio::test]
+    async fn fetch_account_reads_memo_required_data_entry() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/accounts/GEXCHANGE");
+            then.status(200).json_body(serde_json::json!({
+                "id": "GEXCHANGE",
+                "account_id": "GEXCHANGE",
+                "sequence": "1",
+                "balances": [{ "asset_type": "native", "balance": "10.0000000" }],
+                "signers": [{ "weight": 1 }],
+                "flags": {},
+                "data": { "config.memo_required": "MQ==" }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let account = client.fetch_account("GEXCHANGE").await.unwrap();
+
+        assert!(account.memo_required);
+    }
+
+    #[tokio::test]
+    async fn fetch_account_history_detects_merge() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/accounts/GMERGED/operations")
+                .query_param("order", "desc")
+                .query_param("limit", "1");
+            then.status(200).json_body(serde_json::json!({
+                "_embedded": { "records": [{
+                    "id": "1",
+                    "transaction_hash": "abc",
+                    "type": "account_merge",
+                    "source_account": "GMERGED",
+                    "account": "GMERGED",
+                    "into": "GDEST",
+                    "created_at": "2024-03-01T10:15:00Z"
+                }] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let history = client.fetch_account_history("GMERGED").await.unwrap();
+
+        assert_eq!(
+            history,
+            AccountHistory::Merged {
+                into: "GDEST".to_string(),
+                closed_at: Some("2024-03-01T10:15:00Z".to_string()),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_account_history_empty_means_never_created() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/accounts/GNEVER/operations");
+            then.status(200)
+                .json_body(serde_json::json!({ "_embedded": { "records": [] } }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let history = client.fetch_account_history("GNEVER").await.unwrap();
+
+        assert_eq!(history, AccountHistory::NeverCreated);
+    }
+
+    #[tokio::test]
+    async fn fetch_average_ledger_close_seconds() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/ledgers")
+                .query_param("order", "desc");
+            then.status(200).json_body(serde_json::json!({
+                "_embedded": { "records": [
+                    { "closed_at": "2024-01-15T14:32:12Z" },
+                    { "closed_at": "2024-01-15T14:32:06Z" },
+                    { "closed_at": "2024-01-15T14:32:00Z" }
+                ] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let average = client.fetch_average_ledger_close_seconds().await;
+
+        assert_eq!(average, Some(6.0));
+    }
+
+    #[tokio::test]
+    async fn fetch_fee_market_reads_capacity_and_percentiles() {
+        let server = MockServer::start();
+
+        let distribution = |p99: &str| {
+            serde_json::json!({
+                "min": "100", "max": "100000", "mode": "100", "p10": "100",
+                "p50": "100", "p70": "150", "p90": "1000", "p95": "2500", "p99": p99
+            })
+        };
+        server.mock(|when, then| {
+            when.method(GET).path("/fee_stats");
+            then.status(200).json_body(serde_json::json!({
+                "last_ledger": "50000000",
+                "last_ledger_base_fee": "100",
+                "ledger_capacity_usage": "0.97",
+                "fee_charged": distribution("10000"),
+                "max_fee": distribution("50000")
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let market = client.fetch_fee_market().await.unwrap();
+
+        assert_eq!(market.last_ledger, 50_000_000);
+        assert_eq!(market.base_fee, 100);
+        assert_eq!(market.capacity_usage, 0.97);
+        assert_eq!(market.charged.p70, 150);
+        assert_eq!(market.charged.p99, 10_000);
+        assert_eq!(market.offered.p99, 50_000);
+    }
+
+    #[tokio::test]
+    async fn fetch_ledger_loads_reads_transaction_set_sizes() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/ledgers")
+                .query_param("order", "desc")
+                .query_param("limit", "2");
+            then.status(200).json_body(serde_json::json!({
+                "_embedded": { "records": [
+                    {
+                        "sequence": 50000000, "closed_at": "2024-01-15T14:32:12Z",
+                        "operation_count": 900, "tx_set_operation_count": 1000,
+                        "max_tx_set_size": 1000
+                    },
+                    {
+                        "sequence": 49999999, "closed_at": "2024-01-15T14:32:06Z",
+                        "operation_count": 300, "tx_set_operation_count": null,
+                        "max_tx_set_size": 1000
+                    }
+                ] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let loads = client.fetch_ledger_loads(2).await.unwrap();
+
+        mock.assert();
+        assert_eq!(loads[0].sequence, 50_000_000);
+        assert_eq!(loads[0].usage(), 1.0);
+        assert_eq!(loads[1].operations, 300);
+    }
+
     #[tokio::test]
     async fn fetch_account_transactions_default_pagination() {
         let server = MockServer::start();
@@ -195,4 +397,958 @@ NETWORK_PASSPHRASE="Test SDF Network ; September 2015"
         assert_eq!(second.as_deref(), Some("Anchorage Digital"));
         stellar_toml_mock.assert_hits(1);
     }
+
+    #[tokio::test]
+    async fn fetch_stellar_toml_identity_reads_accounts() {
+        let server = MockServer::start();
+        let domain = server.base_url();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/.well-known/stellar.toml");
+            then.status(200).body(
+                r#"
+ORG_NAME="Circle"
+ACCOUNTS=[
+    "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
+    "GBOTHER",
+]
+"#,
+            );
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let identity = client.fetch_stellar_toml_identity(&domain).await.unwrap();
+
+        assert_eq!(identity.org_name.as_deref(), Some("Circle"));
+        assert!(identity.lists_account("GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN"));
+        assert!(!identity.lists_account("GNOTLISTED"));
+    }
+
+    #[tokio::test]
+    async fn fetch_claimable_balance_maps_predicates() {
+        use crate::models::claimable_balance::ClaimPredicate;
+
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/claimable_balances/00000000abc");
+            then.status(200).json_body(serde_json::json!({
+                "id": "00000000abc",
+                "asset": "USDC:GISSUER",
+                "amount": "100.0000000",
+                "sponsor": "GSPONSOR",
+                "last_modified_ledger": 1000,
+                "claimants": [
+                    {
+                        "destination": "GCLAIMANT",
+                        "predicate": {
+                            "and": [
+                                { "not": { "abs_before": "2025-03-03T00:00:00Z", "abs_before_epoch": "1740960000" } },
+                                { "rel_before": "86400" }
+                            ]
+                        }
+                    },
+                    { "destination": "GSPONSOR", "predicate": { "unconditional": true } }
+                ],
+                "flags": { "clawback_enabled": true }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let balance = client.fetch_claimable_balance("00000000abc").await.unwrap();
+
+        assert_eq!(balance.asset, "USDC:GISSUER");
+        assert_eq!(balance.sponsor.as_deref(), Some("GSPONSOR"));
+        assert!(balance.clawback_enabled);
+        assert_eq!(
+            balance.claimants[0].predicate,
+            ClaimPredicate::And(vec![
+                ClaimPredicate::Not(Box::new(ClaimPredicate::AbsBefore(
+                    "2025-03-03T00:00:00Z".to_string()
+                ))),
+                ClaimPredicate::RelBefore(86_400),
+            ])
+        );
+        assert_eq!(
+            balance.claimants[1].predicate,
+            ClaimPredicate::Unconditional
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_claimable_balance_not_found() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/claimable_balances/00000000gone");
+            then.status(404);
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let err = client
+            .fetch_claimable_balance("00000000gone")
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err,
+            crate::errors::HorizonError::ClaimableBalanceNotFound
+        ));
+    }
+
+    #[tokio::test]
+    async fn fetch_liquidity_pool_activity_keeps_deposits_and_withdrawals() {
+        use crate::models::liquidity_pool::PoolActivityKind;
+
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/liquidity_pools/pool1/operations")
+                .query_param("order", "desc");
+            then.status(200).json_body(serde_json::json!({
+                "_embedded": { "records": [
+                    {
+                        "id": "3", "transaction_hash": "tx3", "type": "liquidity_pool_withdraw",
+                        "source_account": "GLP",
+                        "liquidity_pool_id": "pool1",
+                        "reserves_received": [{ "asset": "native", "amount": "5.0000000" }],
+                        "shares": "2.0000000"
+                    },
+                    {
+                        "id": "2", "transaction_hash": "tx2", "type": "path_payment_strict_send",
+                        "source_account": "GTRADER"
+                    },
+                    {
+                        "id": "1", "transaction_hash": "tx1", "type": "liquidity_pool_deposit",
+                        "source_account": "GLP",
+                        "liquidity_pool_id": "pool1",
+                        "reserves_deposited": [
+                            { "asset": "native", "amount": "10.0000000" },
+                            { "asset": "USDC:GISSUER", "amount": "1.0000000" }
+                        ],
+                        "shares_received": "4.0000000"
+                    }
+                ] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let activity = client
+            .fetch_liquidity_pool_activity("pool1", 50)
+            .await
+            .unwrap();
+
+        assert_eq!(activity.len(), 2);
+        assert_eq!(activity[0].kind, PoolActivityKind::Withdraw);
+        assert_eq!(activity[0].shares, "2.0000000");
+        assert_eq!(activity[1].kind, PoolActivityKind::Deposit);
+        assert_eq!(activity[1].reserves.len(), 2);
+        assert_eq!(activity[1].transaction_hash, "tx1");
+    }
+
+    #[tokio::test]
+    async fn fetch_liquidity_pool_maps_reserves() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/liquidity_pools/pool1");
+            then.status(200).json_body(serde_json::json!({
+                "id": "pool1",
+                "fee_bp": 30,
+                "type": "constant_product",
+                "total_trustlines": "42",
+                "total_shares": "5000.0000000",
+                "reserves": [
+                    { "asset": "native", "amount": "1000.0000000" },
+                    { "asset": "USDC:GISSUER", "amount": "112.4000000" }
+                ]
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let pool = client.fetch_liquidity_pool("pool1").await.unwrap();
+
+        assert_eq!(pool.fee_bp, 30);
+        assert_eq!(pool.total_trustlines, 42);
+        assert_eq!(pool.reserves[1].asset, "USDC:GISSUER");
+    }
+
+    #[tokio::test]
+    async fn fetch_account_offers_maps_canonical_assets() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/accounts/GSELLER/offers")
+                .query_param("limit", "20");
+            then.status(200).json_body(serde_json::json!({
+                "_links": { "next": { "href": null }, "prev": { "href": null } },
+                "_embedded": { "records": [{
+                    "id": "165561423",
+                    "seller": "GSELLER",
+                    "selling": { "asset_type": "native" },
+                    "buying": {
+                        "asset_type": "credit_alphanum4",
+                        "asset_code": "USDC",
+                        "asset_issuer": "GISSUER"
+                    },
+                    "amount": "300.0000000",
+                    "price": "0.1120000",
+                    "last_modified_ledger": 1000
+                }] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let offers = client.fetch_account_offers("GSELLER", 20).await.unwrap();
+
+        assert_eq!(offers.len(), 1);
+        assert_eq!(offers[0].id, 165561423);
+        assert_eq!(offers[0].selling, "native");
+        assert_eq!(offers[0].buying, "USDC:GISSUER");
+    }
+
+    #[tokio::test]
+    async fn fetch_offer_amount_sold_sums_both_sides_of_trades() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/offers/42/trades");
+            then.status(200).json_body(serde_json::json!({
+                "_links": { "next": { "href": null }, "prev": { "href": null } },
+                "_embedded": { "records": [
+                    {
+                        "base_offer_id": "42", "base_amount": "150.5000000",
+                        "counter_offer_id": "7", "counter_amount": "16.8560000"
+                    },
+                    {
+                        "base_offer_id": "9", "base_amount": "5.0000000",
+                        "counter_offer_id": "42", "counter_amount": "49.5000000"
+                    }
+                ] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let sold = client.fetch_offer_amount_sold(42).await.unwrap();
+
+        assert_eq!(sold, "200.0000000");
+    }
+
+    #[tokio::test]
+    async fn fetch_issuer_clawbacks_filters_by_asset() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/accounts/GISSUER/operations")
+                .query_param("order", "desc");
+            then.status(200).json_body(serde_json::json!({
+                "_embedded": { "records": [
+                    {
+                        "id": "3", "transaction_hash": "tx3", "type": "clawback",
+                        "from": "GHOLDER", "amount": "12.5000000",
+                        "asset_code": "USDX", "asset_issuer": "GISSUER",
+                        "created_at": "2025-03-03T00:00:00Z"
+                    },
+                    {
+                        "id": "2", "transaction_hash": "tx2", "type": "clawback",
+                        "from": "GHOLDER", "amount": "1.0000000",
+                        "asset_code": "EURX", "asset_issuer": "GISSUER"
+                    },
+                    { "id": "1", "transaction_hash": "tx1", "type": "payment" }
+                ] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let clawbacks = client
+            .fetch_issuer_clawbacks("GISSUER", "USDX", 200)
+            .await
+            .unwrap();
+
+        assert_eq!(clawbacks.len(), 1);
+        assert_eq!(clawbacks[0].from, "GHOLDER");
+        assert_eq!(clawbacks[0].transaction_hash, "tx3");
+    }
+
+    #[tokio::test]
+    async fn fetch_operation_trades_keeps_the_payers_side() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/operations/op1/effects");
+            then.status(200).json_body(serde_json::json!({
+                "_links": { "next": { "href": null }, "prev": { "href": null } },
+                "_embedded": { "records": [
+                    { "type": "account_debited", "account": "GPAYER", "amount": "100.0000000" },
+                    {
+                        "type": "trade", "account": "GPAYER", "seller": "GSELLER",
+                        "sold_amount": "100.0000000", "sold_asset_type": "native",
+                        "bought_amount": "0.0010000", "bought_asset_type": "credit_alphanum4",
+                        "bought_asset_code": "BTC", "bought_asset_issuer": "GBTC"
+                    },
+                    {
+                        "type": "trade", "account": "GSELLER", "seller": "GPAYER",
+                        "sold_amount": "0.0010000", "sold_asset_type": "credit_alphanum4",
+                        "sold_asset_code": "BTC", "sold_asset_issuer": "GBTC",
+                        "bought_amount": "100.0000000", "bought_asset_type": "native"
+                    },
+                    {
+                        "type": "liquidity_pool_trade", "account": "GPAYER",
+                        "sold": { "asset": "BTC:GBTC", "amount": "0.0010000" },
+                        "bought": { "asset": "USDC:GUSDC", "amount": "11.2000000" }
+                    }
+                ] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let fills = client
+            .fetch_operation_trades("op1", "GPAYER")
+            .await
+            .unwrap();
+
+        assert_eq!(fills.len(), 2);
+        assert_eq!(fills[0].sold_asset, "native");
+        assert_eq!(fills[0].bought_asset, "BTC:GBTC");
+        assert_eq!(fills[1].bought_asset, "USDC:GUSDC");
+        assert_eq!(fills[1].bought_amount, "11.2".parse().unwrap());
+    }
+
+    #[tokio::test]
+    async fn pages_follow_cursors_until_a_short_page() {
+        use futures_util::TryStreamExt;
+
+        let server = MockServer::start();
+        let page = |cursor: &str, records: serde_json::Value, next: &str| {
+            server.mock(|when, then| {
+                when.method(GET)
+                    .path("/accounts/GABC/effects")
+                    .query_param("limit", "2")
+                    .query_param("cursor", cursor);
+                then.status(200).json_body(serde_json::json!({
+                    "_links": { "next": { "href": format!("/accounts/GABC/effects?cursor={next}&limit=2") } },
+                    "_embedded": { "records": records }
+                }));
+            })
+        };
+        page(
+            "c0",
+            serde_json::json!([{ "id": "1" }, { "id": "2" }]),
+            "c1",
+        );
+        page("c1", serde_json::json!([{ "id": "3" }]), "c2");
+        let past_the_end = page("c2", serde_json::json!([]), "c3");
+
+        let client = HorizonClient::new(server.base_url());
+        let pages: Vec<Page<serde_json::Value>> = client
+            .pages(
+                "/accounts/GABC/effects",
+                &[("limit", "2"), ("cursor", "c0")],
+            )
+            .try_collect()
+            .await
+            .unwrap();
+
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].records.len(), 2);
+        assert_eq!(pages[0].next_cursor.as_deref(), Some("c1"));
+        assert_eq!(pages[1].records[0]["id"], "3");
+        past_the_end.assert_hits(0);
+    }
+
+    #[tokio::test]
+    async fn pages_encode_the_cursor() {
+        use futures_util::TryStreamExt;
+
+        let server = MockServer::start();
+        let page = server.mock(|when, then| {
+            when.method(GET)
+                .path("/accounts/GABC/effects")
+                .query_param("cursor", "1&limit=200")
+                .query_param("limit", "2");
+            then.status(200).json_body(serde_json::json!({
+                "_links": { "next": { "href": "/accounts/GABC/effects?cursor=2%263&limit=2" } },
+                "_embedded": { "records": [{ "id": "1" }] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let pages: Vec<Page<serde_json::Value>> = client
+            .pages(
+                "/accounts/GABC/effects",
+                &[("limit", "2"), ("cursor", "1&limit=200")],
+            )
+            .try_collect()
+            .await
+            .unwrap();
+
+        page.assert_hits(1);
+        assert_eq!(pages[0].next_cursor.as_deref(), Some("2&3"));
+    }
+
+    #[tokio::test]
+    async fn pages_retry_failing_pages_then_give_up() {
+        use futures_util::TryStreamExt;
+
+        let server = MockServer::start();
+        let failing = server.mock(|when, then| {
+            when.method(GET).path("/operations/op1/effects");
+            then.status(503);
+        });
+
+        // Keep the circuit closed so every retry reaches Horizon.
+        let client = HorizonClient::new(server.base_url()).with_circuit(
+            crate::services::circuit::CircuitConfig {
+                failure_threshold: 100,
+                cooldown: std::time::Duration::from_secs(1),
+            },
+        );
+        let err = client
+            .pages::<serde_json::Value>("/operations/op1/effects", &[])
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, crate::errors::HorizonError::InvalidResponse));
+        // The first request and two retries.
+        failing.assert_hits(3);
+    }
+
+    #[tokio::test]
+    async fn pages_retry_rate_limited_pages_then_give_up() {
+        use futures_util::TryStreamExt;
+
+        let server = MockServer::start();
+        let busy = server.mock(|when, then| {
+            when.method(GET).path("/operations/op1/effects");
+            then.status(429);
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let err = client
+            .pages::<serde_json::Value>("/operations/op1/effects", &[])
+            .try_collect::<Vec<_>>()
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, crate::errors::HorizonError::RateLimited));
+        // The first request and two retries.
+        busy.assert_hits(3);
+    }
+
+    #[tokio::test]
+    async fn fetch_offer_amount_sold_of_a_missing_offer_is_zero() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/offers/42/trades");
+            then.status(404);
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        assert_eq!(
+            client.fetch_offer_amount_sold(42).await.unwrap(),
+            "0.0000000"
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_market_price_reads_the_last_close_before_the_ledger() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/trade_aggregations")
+                .query_param("base_asset_type", "native")
+                .query_param("counter_asset_type", "credit_alphanum4")
+                .query_param("counter_asset_code", "USDC")
+                .query_param("counter_asset_issuer", "GUSDC")
+                .query_param("resolution", "60000")
+                // 2026-01-02T10:15:30Z, rounded down to the minute.
+                .query_param("end_time", "1767348900000")
+                .query_param("order", "desc");
+            then.status(200).json_body(serde_json::json!({
+                "_links": { "next": { "href": null }, "prev": { "href": null } },
+                "_embedded": { "records": [{ "timestamp": "1767348840000", "close": "0.1129000" }] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let price = client
+            .fetch_market_price("native", "USDC:GUSDC", "2026-01-02T10:15:30Z")
+            .await;
+
+        mock.assert();
+        assert_eq!(price, Some(0.1129));
+        assert_eq!(
+            client
+                .fetch_market_price("native", "USDC:GUSDC", "not a time")
+                .await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_trade_aggregations_reads_buckets_oldest_first() {
+        let server = MockServer::start();
+
+        let mock = server.mock(|when, then| {
+            when.method(GET)
+                .path("/trade_aggregations")
+                .query_param("base_asset_type", "credit_alphanum4")
+                .query_param("base_asset_code", "USDC")
+                .query_param("base_asset_issuer", "GUSDC")
+                .query_param("counter_asset_type", "native")
+                .query_param("start_time", "1767225600000")
+                .query_param("end_time", "1767312000000")
+                .query_param("resolution", "900000")
+                .query_param("order", "asc");
+            then.status(200).json_body(serde_json::json!({
+                "_embedded": { "records": [
+                    {
+                        "timestamp": "1767225600000", "trade_count": "3",
+                        "base_volume": "100.0000000", "counter_volume": "775.0000000",
+                        "open": "7.7500000", "high": "7.8000000", "low": "7.7000000", "close": "7.8000000"
+                    },
+                    {
+                        "timestamp": 1767226500000_i64, "trade_count": 1,
+                        "base_volume": "10.0000000", "counter_volume": "79.2800000",
+                        "open": "7.9280000", "high": "7.9280000", "low": "7.9280000", "close": "7.9280000"
+                    }
+                ] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let buckets = client
+            .fetch_trade_aggregations("USDC:GUSDC", "native", 1767225600000, 1767312000000, 900000)
+            .await
+            .unwrap();
+
+        mock.assert();
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets[0].trade_count, 3);
+        assert_eq!(buckets[0].open, 7.75);
+        assert_eq!(buckets[1].timestamp, 1767226500000);
+        assert_eq!(buckets[1].counter_volume.to_string(), "79.2800000");
+        assert!(
+            client
+                .fetch_trade_aggregations("USDC", "native", 0, 1, 60000)
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn check_stellar_toml_reports_listing() {
+        use crate::models::asset::StellarTomlStatus;
+
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/.well-known/stellar.toml");
+            then.status(200).body(
+                "ORG_NAME = \"Example\"\n\n[[CURRENCIES]]\ncode = \"USDX\"\nissuer = \"GISSUER\"\n",
+            );
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let listed = client
+            .check_stellar_toml(&server.base_url(), "USDX", "GISSUER")
+            .await;
+        let unlisted = client
+            .check_stellar_toml(&server.base_url(), "EURX", "GISSUER")
+            .await;
+
+        assert_eq!(listed, StellarTomlStatus::Listed);
+        assert_eq!(unlisted, StellarTomlStatus::Unlisted);
+    }
+
+    #[tokio::test]
+    async fn check_stellar_toml_reports_missing_and_invalid() {
+        use crate::models::asset::StellarTomlStatus;
+
+        let missing = MockServer::start();
+        missing.mock(|when, then| {
+            when.method(GET).path("/.well-known/stellar.toml");
+            then.status(404);
+        });
+        let invalid = MockServer::start();
+        invalid.mock(|when, then| {
+            when.method(GET).path("/.well-known/stellar.toml");
+            then.status(200).body("[[CURRENCIES]\ncode = ");
+        });
+
+        let client = HorizonClient::new(missing.base_url());
+
+        assert_eq!(
+            client
+                .check_stellar_toml(&missing.base_url(), "USDX", "GISSUER")
+                .await,
+            StellarTomlStatus::Missing
+        );
+        assert_eq!(
+            client
+                .check_stellar_toml(&invalid.base_url(), "USDX", "GISSUER")
+                .await,
+            StellarTomlStatus::Invalid
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_latest_ledger_returns_tip_sequence() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET)
+                .path("/ledgers")
+                .query_param("order", "desc")
+                .query_param("limit", "1");
+            then.status(200).json_body(serde_json::json!({
+                "_embedded": { "records": [{ "sequence": 5001, "closed_at": "2024-01-15T14:32:00Z" }] }
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        assert_eq!(client.fetch_latest_ledger().await.unwrap(), 5001);
+    }
+
+    #[tokio::test]
+    async fn fetch_ledger_header() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/ledgers/5001");
+            then.status(200).json_body(serde_json::json!({
+                "sequence": 5001,
+                "closed_at": "2024-01-15T14:32:00Z",
+                "protocol_version": 22,
+                "successful_transaction_count": 12,
+                "failed_transaction_count": null,
+                "operation_count": 40,
+                "base_fee_in_stroops": 100,
+                "base_reserve_in_stroops": 5000000
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/ledgers/1");
+            then.status(404);
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let ledger = client.fetch_ledger(5001).await.unwrap();
+        assert_eq!(ledger.protocol_version, 22);
+        assert_eq!(ledger.failed_transaction_count, 0);
+        assert_eq!(ledger.base_reserve_in_stroops, 5_000_000);
+        assert!(matches!(
+            client.fetch_ledger(1).await.unwrap_err(),
+            crate::errors::HorizonError::LedgerNotFound
+        ));
+    }
+
+    #[tokio::test]
+    async fn fetch_ledger_transactions_not_found() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/ledgers/1/transactions");
+            then.status(404);
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let err = client.fetch_ledger_transactions(1).await.unwrap_err();
+
+        assert!(matches!(err, crate::errors::HorizonError::LedgerNotFound));
+    }
+
+    #[tokio::test]
+    async fn ingestion_tick_indexes_ledger_transactions() {
+        use crate::services::ingestion::{IngestionConfig, IngestionWorker};
+        use crate::services::storage::ExplanationStore;
+        use std::sync::Arc;
+
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(GET).path("/ledgers").query_param("order", "desc");
+            then.status(200).json_body(serde_json::json!({
+                "_embedded": { "records": [{ "sequence": 100, "closed_at": "2024-01-15T14:32:00Z" }] }
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/ledgers/100/transactions");
+            then.status(200).json_body(serde_json::json!({
+                "_links": { "next": { "href": "/ledgers/100/transactions?cursor=1" } },
+                "_embedded": { "records": [
+                    { "hash": "tx1", "successful": true, "fee_charged": "100", "ledger": 100,
+                      "created_at": "2024-01-15T14:32:00Z" },
+                    { "hash": "tx2", "successful": true, "fee_charged": "100", "ledger": 100 }
+                ] }
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/ledgers/100/operations");
+            then.status(200).json_body(serde_json::json!({
+                "_links": { "next": { "href": "/ledgers/100/operations?cursor=1" } },
+                "_embedded": { "records": [
+                    { "id": "1", "transaction_hash": "tx1", "type": "payment",
+                      "from": "GA", "to": "GB", "amount": "5.0", "asset_type": "native" }
+                ] }
+            }));
+        });
+
+        let client = Arc::new(HorizonClient::new(server.base_url()));
+        let store = Arc::new(ExplanationStore::open_in_memory().unwrap());
+        let worker = IngestionWorker::new(client, Arc::clone(&store), IngestionConfig::default());
+
+        assert_eq!(worker.tick().await.unwrap(), 1);
+
+        // tx2 has no operations, so there is nothing to explain.
+        assert_eq!(store.explanation_count().unwrap(), 1);
+        let stored = store.get_explanation("tx1").unwrap().unwrap();
+        assert_eq!(stored.ledger, Some(100));
+        assert_eq!(store.last_ingested_ledger().unwrap(), Some(100));
+
+        let snapshot = worker.status().snapshot();
+        assert_eq!(snapshot.lag_ledgers, Some(0));
+        assert_eq!(snapshot.transactions_indexed, 1);
+
+        // Already caught up: the next tick has nothing to do.
+        assert_eq!(worker.tick().await.unwrap(), 0);
+    }
+
+    fn tripping_circuit() -> crate::services::circuit::CircuitConfig {
+        crate::services::circuit::CircuitConfig {
+            failure_threshold: 2,
+            cooldown: std::time::Duration::from_secs(60),
+        }
+    }
+
+    #[tokio::test]
+    async fn circuit_opens_after_consecutive_failures() {
+        use crate::services::circuit::CircuitState;
+
+        let server = MockServer::start();
+        let failing = server.mock(|when, then| {
+            when.method(GET).path("/transactions/down");
+            then.status(503);
+        });
+
+        let client = HorizonClient::new(server.base_url()).with_circuit(tripping_circuit());
+        for _ in 0..2 {
+            let err = client.fetch_transaction("down").await.unwrap_err();
+            assert!(matches!(err, crate::errors::HorizonError::InvalidResponse));
+        }
+
+        let err = client.fetch_transaction("down").await.unwrap_err();
+        assert!(matches!(
+            err,
+            crate::errors::HorizonError::CircuitOpen { .. }
+        ));
+        // The open circuit failed fast without calling Horizon again.
+        failing.assert_hits(2);
+
+        let snapshot = client.circuit_snapshot();
+        assert_eq!(snapshot.state, CircuitState::Open);
+        assert_eq!(snapshot.retry_after_seconds, Some(60));
+    }
+
+    #[tokio::test]
+    async fn rate_limited_responses_are_reported() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/transactions/busy");
+            then.status(429);
+        });
+
+        let client = HorizonClient::new(server.base_url());
+        let err = client.fetch_transaction("busy").await.unwrap_err();
+        assert!(matches!(err, crate::errors::HorizonError::RateLimited));
+    }
+
+    #[tokio::test]
+    async fn open_circuit_serves_stale_responses() {
+        let server = MockServer::start();
+        let mut healthy = server.mock(|when, then| {
+            when.method(GET).path("/transactions/abc123");
+            then.status(200).json_body(serde_json::json!({
+                "hash": "abc123",
+                "successful": true,
+                "fee_charged": "100"
+            }));
+        });
+
+        let client = HorizonClient::new(server.base_url()).with_circuit(tripping_circuit());
+        client.fetch_transaction("abc123").await.unwrap();
+
+        healthy.delete();
+        server.mock(|when, then| {
+            when.method(GET);
+            then.status(500);
+        });
+
+        // Failing live requests fall back to the last good response...
+        let tx = client.fetch_transaction("abc123").await.unwrap();
+        assert_eq!(tx.hash, "abc123");
+        client.fetch_transaction("abc123").await.unwrap();
+
+        // ...and so do requests while the circuit is open; other URLs cannot.
+        let tx = client.fetch_transaction("abc123").await.unwrap();
+        assert_eq!(tx.fee_charged, "100");
+        let err = client.fetch_transaction("other").await.unwrap_err();
+        assert!(matches!(
+            err,
+            crate::errors::HorizonError::CircuitOpen { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn recorded_responses_replay_without_horizon() {
+        use crate::services::recording::{RecordingConfig, RecordingMode};
+
+        let dir = std::env::temp_dir().join(format!("recordings-{}", uuid::Uuid::new_v4()));
+        let config = |mode| RecordingConfig {
+            mode,
+            dir: dir.clone(),
+        };
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/transactions/abc123");
+            then.status(200).json_body(serde_json::json!({
+                "hash": "abc123",
+                "successful": true,
+                "fee_charged": "100"
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET).path("/transactions/missing");
+            then.status(404);
+        });
+
+        let recording =
+            HorizonClient::new(server.base_url()).with_recording(config(RecordingMode::Record));
+        recording.fetch_transaction("abc123").await.unwrap();
+        recording.fetch_transaction("missing").await.unwrap_err();
+
+        // Replay answers from disk, even against a Horizon that is gone.
+        let replay =
+            HorizonClient::new("http://127.0.0.1:9").with_recording(config(RecordingMode::Replay));
+        let tx = replay.fetch_transaction("abc123").await.unwrap();
+        assert_eq!(tx.fee_charged, "100");
+        assert!(matches!(
+            replay.fetch_transaction("missing").await.unwrap_err(),
+            crate::errors::HorizonError::TransactionNotFound
+        ));
+        assert!(matches!(
+            replay.fetch_transaction("unrecorded").await.unwrap_err(),
+            crate::errors::HorizonError::NetworkError
+        ));
+        mock.assert_hits(1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn pool_fails_over_to_healthy_upstream() {
+        use crate::services::horizon_pool::{HorizonPool, PoolConfig, check_health};
+        use std::sync::Arc;
+
+        let failing = MockServer::start();
+        let failing_mock = failing.mock(|when, then| {
+            when.method(GET);
+            then.status(503);
+        });
+        let healthy = MockServer::start();
+        healthy.mock(|when, then| {
+            when.method(GET).path("/transactions/abc123");
+            then.status(200).json_body(serde_json::json!({
+                "hash": "abc123",
+                "successful": true,
+                "fee_charged": "100"
+            }));
+        });
+        healthy.mock(|when, then| {
+            when.method(GET).path("/");
+            then.status(200).json_body(serde_json::json!({}));
+        });
+
+        let pool = Arc::new(HorizonPool::new(PoolConfig::new(vec![
+            failing.base_url(),
+            healthy.base_url(),
+        ])));
+        let client = HorizonClient::new("unused").with_pool(Arc::clone(&pool));
+        for _ in 0..8 {
+            let tx = client.fetch_transaction("abc123").await.unwrap();
+            assert_eq!(tx.hash, "abc123");
+        }
+        // Requests alternate until three failures eject the failing
+        // upstream; after that it is only tried if the healthy one fails.
+        failing_mock.assert_hits(3);
+
+        check_health(&pool, &reqwest::Client::new()).await;
+        assert_eq!(
+            pool.candidates(),
+            vec![healthy.base_url().as_str(), failing.base_url().as_str()]
+        );
+    }
+
+    #[tokio::test]
+    async fn submit_transaction_outcomes() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/transactions")
+                .x_www_form_urlencoded_tuple("tx", "ok");
+            then.status(200).json_body(serde_json::json!({
+                "hash": "abc123",
+                "successful": true,
+                "fee_charged": "100",
+                "ledger": 7
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/transactions")
+                .x_www_form_urlencoded_tuple("tx", "bad-seq");
+            then.status(400).json_body(serde_json::json!({
+                "type": "https://stellar.org/horizon-errors/transaction_failed",
+                "status": 400,
+                "extras": {
+                    "envelope_xdr": "AAAA",
+                    "result_codes": { "transaction": "tx_bad_seq" },
+                    "result_xdr": "BBBB"
+                }
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/transactions")
+                .x_www_form_urlencoded_tuple("tx", "slow");
+            then.status(504);
+        });
+
+        let client = HorizonClient::new(server.base_url());
+
+        let HorizonSubmission::Included(tx) = client.submit_transaction("ok").await.unwrap() else {
+            panic!("expected inclusion");
+        };
+        assert_eq!(tx.ledger, Some(7));
+
+        let HorizonSubmission::Failed(extras) = client.submit_transaction("bad-seq").await.unwrap()
+        else {
+            panic!("expected failure");
+        };
+        assert_eq!(
+            extras.result_codes.unwrap().transaction.as_deref(),
+            Some("tx_bad_seq")
+        );
+        assert_eq!(extras.envelope_xdr.as_deref(), Some("AAAA"));
+
+        assert!(matches!(
+            client.submit_transaction("slow").await.unwrap(),
+            HorizonSubmission::TimedOut
+        ));
+    }
 }
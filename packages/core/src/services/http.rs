@@ -0,0 +1,114 @@
+//! The shared outbound HTTP client.
+//!
+//! One `reqwest::Client` is built at startup and handed to every upstream
+//! client (Horizon, stellar-rpc, the ingestion worker through Horizon), so
+//! they share one connection pool and the same timeouts instead of each
+//! opening its own.
+//!
+//! Configuration (environment):
+//! - `HTTP_POOL_MAX_IDLE_PER_HOST` idle connections kept per host (default 32).
+//! - `HTTP_POOL_IDLE_TIMEOUT_SECONDS` before an idle connection is closed (default 90).
+//! - `HTTP_CONNECT_TIMEOUT_SECONDS` to establish a connection (default 5).
+//! - `HTTP_REQUEST_TIMEOUT_SECONDS` for a whole request, body included (default 30).
+//! - `HTTP2_KEEPALIVE_SECONDS` between HTTP/2 pings on open connections (default 30).
+
+use reqwest::Client;
+use std::env;
+use std::time::Duration;
+
+/// Outbound HTTP client settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpConfig {
+    pub pool_max_idle_per_host: usize,
+    pub pool_idle_timeout: Duration,
+    pub connect_timeout: Duration,
+    pub request_timeout: Duration,
+    pub http2_keep_alive_interval: Duration,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            pool_max_idle_per_host: 32,
+            pool_idle_timeout: Duration::from_secs(90),
+            connect_timeout: Duration::from_secs(5),
+            request_timeout: Duration::from_secs(30),
+            http2_keep_alive_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+impl HttpConfig {
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let parse = |name: &str| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+        };
+        let seconds =
+            |name: &str, default: Duration| parse(name).map(Duration::from_secs).unwrap_or(default);
+
+        Self {
+            pool_max_idle_per_host: parse("HTTP_POOL_MAX_IDLE_PER_HOST")
+                .map(|n| n as usize)
+                .unwrap_or(defaults.pool_max_idle_per_host),
+            pool_idle_timeout: seconds(
+                "HTTP_POOL_IDLE_TIMEOUT_SECONDS",
+                defaults.pool_idle_timeout,
+            ),
+            connect_timeout: seconds("HTTP_CONNECT_TIMEOUT_SECONDS", defaults.connect_timeout),
+            request_timeout: seconds("HTTP_REQUEST_TIMEOUT_SECONDS", defaults.request_timeout),
+            http2_keep_alive_interval: seconds(
+                "HTTP2_KEEPALIVE_SECONDS",
+                defaults.http2_keep_alive_interval,
+            ),
+        }
+    }
+
+    /// Build the client. Cloning it shares the pool, so build it once.
+    pub fn build_client(&self) -> Client {
+        Client::builder()
+            .pool_max_idle_per_host(self.pool_max_idle_per_host)
+            .pool_idle_timeout(self.pool_idle_timeout)
+            .connect_timeout(self.connect_timeout)
+            .timeout(self.request_timeout)
+            .http2_keep_alive_interval(self.http2_keep_alive_interval)
+            .http2_keep_alive_while_idle(true)
+            .build()
+            .expect("HTTP client settings are not valid")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use httpmock::prelude::*;
+
+    #[test]
+    fn test_config_defaults() {
+        let config = HttpConfig::default();
+        assert_eq!(config.pool_max_idle_per_host, 32);
+        assert_eq!(config.connect_timeout, Duration::from_secs(5));
+        assert_eq!(config.request_timeout, Duration::from_secs(30));
+    }
+
+    #[tokio::test]
+    async fn test_request_timeout_applies() {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path("/slow");
+            then.status(200).delay(Duration::from_millis(500));
+        });
+
+        let client = HttpConfig {
+            request_timeout: Duration::from_millis(100),
+            ..HttpConfig::default()
+        }
+        .build_client();
+
+        let err = client.get(server.url("/slow")).send().await.unwrap_err();
+        assert!(err.is_timeout());
+    }
+}
@@ -0,0 +1,312 @@
+//! Background ledger ingestion.
+//!
+//! When enabled, a worker tails newly closed ledgers from Horizon, explains
+//! every transaction in them, and writes the results to the
+//! [`ExplanationStore`] so `/tx/:hash` can answer from local storage.
+//!
+//! Configuration (environment):
+//! - `INGEST_ENABLED=true` turns the worker on.
+//! - `INGEST_BACKFILL_FROM=<ledger>` starts an empty index at that ledger
+//!   instead of the network tip, backfilling history up to the present.
+//! - `INGEST_POLL_SECONDS` controls how often the tip is checked (default 5).
+//! - `INGEST_MAX_LEDGERS_PER_TICK` bounds catch-up work per tick (default 20).
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+
+use crate::errors::HorizonError;
+use crate::explain::transaction::explain_transaction_with_ledger;
+use crate::services::explain::map_transaction_to_domain;
+use crate::services::horizon::{HorizonClient, HorizonOperation};
+use crate::services::storage::{ExplanationStore, StorageError, StoredExplanation};
+
+/// Ingestion worker settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IngestionConfig {
+    /// Ledger to start from when the index is empty. `None` starts at the tip.
+    pub backfill_from: Option<u64>,
+    pub poll_interval: Duration,
+    pub max_ledgers_per_tick: u64,
+}
+
+impl Default for IngestionConfig {
+    fn default() -> Self {
+        Self {
+            backfill_from: None,
+            poll_interval: Duration::from_secs(5),
+            max_ledgers_per_tick: 20,
+        }
+    }
+}
+
+impl IngestionConfig {
+    /// Read the worker settings, or `None` when ingestion is not enabled.
+    pub fn from_env() -> Option<Self> {
+        let enabled = env::var("INGEST_ENABLED")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let defaults = Self::default();
+        let parse = |name: &str| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+
+        Some(Self {
+            backfill_from: parse("INGEST_BACKFILL_FROM"),
+            poll_interval: parse("INGEST_POLL_SECONDS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.poll_interval),
+            max_ledgers_per_tick: parse("INGEST_MAX_LEDGERS_PER_TICK")
+                .filter(|n| *n > 0)
+                .unwrap_or(defaults.max_ledgers_per_tick),
+        })
+    }
+}
+
+/// Errors that stop a single ingestion tick. The worker logs them and retries
+/// on the next tick.
+#[derive(Debug)]
+pub enum IngestionError {
+    Horizon(HorizonError),
+    Storage(StorageError),
+}
+
+impl From<HorizonError> for IngestionError {
+    fn from(err: HorizonError) -> Self {
+        IngestionError::Horizon(err)
+    }
+}
+
+impl From<StorageError> for IngestionError {
+    fn from(err: StorageError) -> Self {
+        IngestionError::Storage(err)
+    }
+}
+
+/// Live progress counters, shared with the status endpoint.
+#[derive(Debug, Default)]
+pub struct IngestionStatus {
+    last_ingested_ledger: AtomicU64,
+    latest_network_ledger: AtomicU64,
+    transactions_indexed: AtomicU64,
+}
+
+/// Point-in-time view of ingestion progress.
+#[derive(Debug, Clone, Serialize, PartialEq, ToSchema)]
+pub struct IngestionSnapshot {
+    pub last_ingested_ledger: Option<u64>,
+    pub latest_network_ledger: Option<u64>,
+    /// How many ledgers the index is behind the network tip.
+    pub lag_ledgers: Option<u64>,
+    /// Transactions indexed since the worker started.
+    pub transactions_indexed: u64,
+}
+
+impl IngestionStatus {
+    pub fn snapshot(&self) -> IngestionSnapshot {
+        let non_zero = |v: u64| (v > 0).then_some(v);
+        let last = non_zero(self.last_ingested_ledger.load(Ordering::Relaxed));
+        let latest = non_zero(self.latest_network_ledger.load(Ordering::Relaxed));
+        IngestionSnapshot {
+            last_ingested_ledger: last,
+            latest_network_ledger: latest,
+            lag_ledgers: match (last, latest) {
+                (Some(last), Some(latest)) => Some(latest.saturating_sub(last)),
+                (None, Some(latest)) => Some(latest),
+                _ => None,
+            },
+            transactions_indexed: self.transactions_indexed.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Tails ledgers from Horizon into the explanation store.
+pub struct IngestionWorker {
+    client: Arc<HorizonClient>,
+    store: Arc<ExplanationStore>,
+    config: IngestionConfig,
+    status: Arc<IngestionStatus>,
+}
+
+impl IngestionWorker {
+    pub fn new(
+        client: Arc<HorizonClient>,
+        store: Arc<ExplanationStore>,
+        config: IngestionConfig,
+    ) -> Self {
+        Self {
+            client,
+            store,
+            config,
+            status: Arc::new(IngestionStatus::default()),
+        }
+    }
+
+    pub fn status(&self) -> Arc<IngestionStatus> {
+        Arc::clone(&self.status)
+    }
+
+    /// Run forever, ingesting new ledgers every poll interval.
+    pub async fn run(self) {
+        info!(config = ?self.config, "ingestion_started");
+        loop {
+            if let Err(err) = self.tick().await {
+                error!(error = ?err, "ingestion_tick_failed");
+            }
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    /// Ingest up to `max_ledgers_per_tick` ledgers after the stored cursor.
+    /// Returns the number of ledgers ingested.
+    pub async fn tick(&self) -> Result<u64, IngestionError> {
+        let latest = self.client.fetch_latest_ledger().await?;
+        self.status
+            .latest_network_ledger
+            .store(latest, Ordering::Relaxed);
+
+        let next = match self.store.last_ingested_ledger()? {
+            Some(last) => {
+                self.status
+                    .last_ingested_ledger
+                    .store(last, Ordering::Relaxed);
+                last + 1
+            }
+            None => self.config.backfill_from.unwrap_or(latest),
+        };
+
+        let end = latest.min(next.saturating_add(self.config.max_ledgers_per_tick - 1));
+        let mut ingested = 0;
+        for sequence in next..=end {
+            match self.ingest_ledger(sequence).await {
+                Ok(_) => {}
+                // Ledgers outside Horizon's history retention cannot be
+                // fetched; skip them rather than stalling the worker.
+                Err(IngestionError::Horizon(HorizonError::LedgerNotFound)) => {
+                    warn!(ledger = sequence, "ingestion_ledger_unavailable");
+                }
+                Err(err) => return Err(err),
+            }
+            self.store.set_last_ingested_ledger(sequence)?;
+            self.status
+                .last_ingested_ledger
+                .store(sequence, Ordering::Relaxed);
+            ingested += 1;
+        }
+
+        let snapshot = self.status.snapshot();
+        info!(
+            ledgers = ingested,
+            last_ingested_ledger = ?snapshot.last_ingested_ledger,
+            lag_ledgers = ?snapshot.lag_ledgers,
+            "ingestion_tick_completed"
+        );
+        Ok(ingested)
+    }
+
+    /// Explain and store every transaction in one ledger. Returns the number
+    /// of transactions stored.
+    pub async fn ingest_ledger(&self, sequence: u64) -> Result<usize, IngestionError> {
+        let started_at = Instant::now();
+        let (transactions, operations, header) = tokio::join!(
+            self.client.fetch_ledger_transactions(sequence),
+            self.client.fetch_ledger_operations(sequence),
+            self.client.fetch_ledger(sequence),
+        );
+        let (transactions, operations) = (transactions?, operations?);
+        // The header only adds the protocol version; store without it if
+        // it cannot be fetched.
+        let protocol_version = header.ok().map(|ledger| ledger.protocol_version);
+
+        let mut ops_by_tx: HashMap<String, Vec<HorizonOperation>> = HashMap::new();
+        for op in operations {
+            ops_by_tx
+                .entry(op.transaction_hash.clone())
+                .or_default()
+                .push(op);
+        }
+
+        let mut stored = 0;
+        for tx in transactions {
+            let ops = ops_by_tx.remove(&tx.hash).unwrap_or_default();
+            let created_at = tx.created_at.clone();
+            let ledger = tx.ledger.or(Some(sequence));
+            let domain_tx = map_transaction_to_domain(tx, ops);
+
+            // Explanations are stored without fee context: network fee stats
+            // describe the present, not the ledger being ingested.
+            let Ok(mut explanation) =
+                explain_transaction_with_ledger(&domain_tx, None, created_at.as_deref(), ledger)
+            else {
+                continue;
+            };
+            if let Some(context) = explanation.ledger_context.as_mut() {
+                context.protocol_version = protocol_version;
+            }
+
+            self.store.put_explanation(&StoredExplanation {
+                ledger: sequence,
+                created_at,
+                fee_charged: domain_tx.fee_charged,
+                explanation,
+            })?;
+            stored += 1;
+        }
+
+        self.status
+            .transactions_indexed
+            .fetch_add(stored as u64, Ordering::Relaxed);
+        info!(
+            ledger = sequence,
+            transactions = stored,
+            duration_ms = started_at.elapsed().as_millis() as u64,
+            "ledger_ingested"
+        );
+        Ok(stored)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_lag() {
+        let status = IngestionStatus::default();
+        assert_eq!(status.snapshot().lag_ledgers, None);
+
+        status.latest_network_ledger.store(110, Ordering::Relaxed);
+        assert_eq!(status.snapshot().lag_ledgers, Some(110));
+
+        status.last_ingested_ledger.store(100, Ordering::Relaxed);
+        status.transactions_indexed.store(7, Ordering::Relaxed);
+        assert_eq!(
+            status.snapshot(),
+            IngestionSnapshot {
+                last_ingested_ledger: Some(100),
+                latest_network_ledger: Some(110),
+                lag_ledgers: Some(10),
+                transactions_indexed: 7,
+            }
+        );
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config = IngestionConfig::default();
+        assert_eq!(config.backfill_from, None);
+        assert_eq!(config.poll_interval, Duration::from_secs(5));
+        assert_eq!(config.max_ledgers_per_tick, 20);
+    }
+}
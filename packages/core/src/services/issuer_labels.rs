@@ -0,0 +1,120 @@
+//! Resolve issuer names from home domains, checking each against the
+//! domain's stellar.toml ACCOUNTS list.
+
+use tokio::task::JoinSet;
+use tracing::warn;
+
+use std::sync::Arc;
+
+use crate::explain::issuer_label::{IssuerLabel, label_issuer};
+use crate::services::horizon_api::HorizonApi;
+
+/// Issuers looked up per explanation; each costs an account fetch and a
+/// stellar.toml fetch.
+pub const MAX_ISSUER_LABELS: usize = 5;
+
+/// The URL a home domain serves its stellar.toml under. Horizon reports
+/// home domains without a scheme.
+pub fn domain_url(domain: &str) -> String {
+    if domain.starts_with("http") {
+        domain.to_string()
+    } else {
+        format!("https://{domain}")
+    }
+}
+
+/// The label `issuer`'s home domain gives it, or None when the issuer has
+/// no home domain or its stellar.toml names no organization.
+pub async fn resolve_issuer_label<H: HorizonApi>(client: &H, issuer: &str) -> Option<IssuerLabel> {
+    let account = client
+        .fetch_account(issuer)
+        .await
+        .inspect_err(|err| warn!(issuer = %issuer, error = ?err, "issuer_account_fetch_failed"))
+        .ok()?;
+    let domain = account.home_domain?;
+    let identity = client
+        .fetch_stellar_toml_identity(&domain_url(&domain))
+        .await?;
+    label_issuer(issuer, &domain, &identity)
+}
+
+/// Labels for the first [`MAX_ISSUER_LABELS`] of `issuers`, fetched
+/// concurrently and returned in the order given. Issuers without a label
+/// are left out.
+pub async fn resolve_issuer_labels<H: HorizonApi>(
+    client: Arc<H>,
+    issuers: Vec<String>,
+) -> Vec<IssuerLabel> {
+    let mut tasks = JoinSet::new();
+    for (index, issuer) in issuers.into_iter().take(MAX_ISSUER_LABELS).enumerate() {
+        let client = Arc::clone(&client);
+        tasks.spawn(async move { (index, resolve_issuer_label(client.as_ref(), &issuer).await) });
+    }
+    let mut labels = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        match result {
+            Ok((index, Some(label))) => labels.push((index, label)),
+            Ok((_, None)) => {}
+            Err(err) => warn!(error = %err, "issuer_label_task_failed"),
+        }
+    }
+    labels.sort_by_key(|(index, _)| *index);
+    labels.into_iter().map(|(_, label)| label).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::issuer_label::LabelVerification;
+    use crate::models::account::{Account, AccountFlags};
+    use crate::services::horizon_api::FakeHorizon;
+
+    fn issuer(id: &str, home_domain: Option<&str>) -> Account {
+        Account {
+            id: id.to_string(),
+            account_id: id.to_string(),
+            sequence: "1".to_string(),
+            num_signers: 0,
+            signers: Vec::new(),
+            master_key_weight: 1,
+            thresholds: Default::default(),
+            balances: vec![],
+            home_domain: home_domain.map(str::to_string),
+            flags: AccountFlags {
+                auth_required: false,
+                auth_revocable: false,
+                auth_immutable: false,
+                auth_clawback_enabled: false,
+            },
+            memo_required: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_labels_are_verified_against_toml_accounts() {
+        let horizon = FakeHorizon::default()
+            .with_account(issuer("GLISTED", Some("listed.example")))
+            .with_account(issuer("GUNLISTED", Some("unlisted.example")))
+            .with_account(issuer("GNODOMAIN", None))
+            .with_org_name("https://listed.example", "Listed Co")
+            .with_toml_accounts("https://listed.example", &["GLISTED"])
+            .with_org_name("https://unlisted.example", "Pretender");
+
+        let labels = resolve_issuer_labels(
+            Arc::new(horizon),
+            vec![
+                "GUNLISTED".to_string(),
+                "GNODOMAIN".to_string(),
+                "GMISSING".to_string(),
+                "GLISTED".to_string(),
+            ],
+        )
+        .await;
+
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[0].issuer, "GUNLISTED");
+        assert_eq!(labels[0].verification, LabelVerification::Claimed);
+        assert_eq!(labels[1].name, "Listed Co");
+        assert_eq!(labels[1].verification, LabelVerification::Verified);
+    }
+}
@@ -0,0 +1,145 @@
+//! Reuse market summaries for a short while.
+//!
+//! A summary reads up to 200 trade aggregation buckets from Horizon, and a
+//! popular pair is asked about far more often than its last bucket changes.
+//! Summaries are kept per pair, window, and resolution for a minute by
+//! default, so a market page polled by many clients costs one Horizon call
+//! a minute.
+//!
+//! Configuration (environment):
+//! - `MARKET_CACHE_TTL_SECONDS`: how long a summary is kept (default 60;
+//!   `0` turns the cache off).
+
+use std::env;
+use std::time::Duration;
+
+use crate::explain::market::{MarketSummary, MarketWindow, Resolution};
+use crate::services::transaction_cache::{CacheKey, Network, TransactionCache};
+
+/// TTL when `MARKET_CACHE_TTL_SECONDS` is not set.
+const DEFAULT_TTL: Duration = Duration::from_secs(60);
+
+/// Read `MARKET_CACHE_TTL_SECONDS`; the TTL to cache for, or `None` when
+/// caching is off.
+pub fn market_cache_ttl_from_env() -> Option<Duration> {
+    match env::var("MARKET_CACHE_TTL_SECONDS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+    {
+        Some(0) => None,
+        Some(seconds) => Some(Duration::from_secs(seconds)),
+        None => Some(DEFAULT_TTL),
+    }
+}
+
+pub struct MarketCache {
+    network: Network,
+    summaries: TransactionCache<MarketSummary>,
+}
+
+impl MarketCache {
+    pub fn new(network: Network, ttl: Duration) -> Self {
+        Self {
+            network,
+            summaries: TransactionCache::new(ttl),
+        }
+    }
+
+    fn key(
+        &self,
+        base: &str,
+        counter: &str,
+        window: MarketWindow,
+        resolution: Resolution,
+    ) -> CacheKey {
+        CacheKey::new(
+            format!(
+                "{base}/{counter}/{}/{}",
+                window.as_str(),
+                resolution.as_str()
+            ),
+            self.network,
+        )
+    }
+
+    pub fn get(
+        &self,
+        base: &str,
+        counter: &str,
+        window: MarketWindow,
+        resolution: Resolution,
+    ) -> Option<MarketSummary> {
+        self.summaries
+            .get(&self.key(base, counter, window, resolution))
+    }
+
+    pub fn insert(&self, window: MarketWindow, resolution: Resolution, summary: MarketSummary) {
+        let key = self.key(&summary.base, &summary.counter, window, resolution);
+        self.summaries.insert(key, summary);
+    }
+
+    /// Drop expired summaries. Returns how many.
+    pub fn evict_expired(&self) -> usize {
+        self.summaries.evict_expired()
+    }
+
+    pub fn len(&self) -> usize {
+        self.summaries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::market::explain_market;
+
+    #[test]
+    fn test_summaries_are_kept_per_window_and_resolution() {
+        let cache = MarketCache::new(Network::Testnet, Duration::from_secs(60));
+        let summary = explain_market(
+            "native",
+            "USDC:GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN",
+            MarketWindow::Day,
+            Resolution::FifteenMinutes,
+            &[],
+        );
+        cache.insert(
+            MarketWindow::Day,
+            Resolution::FifteenMinutes,
+            summary.clone(),
+        );
+
+        assert_eq!(
+            cache.get(
+                &summary.base,
+                &summary.counter,
+                MarketWindow::Day,
+                Resolution::FifteenMinutes
+            ),
+            Some(summary.clone())
+        );
+        assert_eq!(
+            cache.get(
+                &summary.base,
+                &summary.counter,
+                MarketWindow::Day,
+                Resolution::Hour
+            ),
+            None
+        );
+        assert_eq!(
+            cache.get(
+                &summary.counter,
+                &summary.base,
+                MarketWindow::Day,
+                Resolution::FifteenMinutes
+            ),
+            None
+        );
+        assert_eq!(cache.len(), 1);
+    }
+}
@@ -0,0 +1,144 @@
+//! Accounts known to need a memo on every incoming payment.
+//!
+//! Exchanges take deposits for all their customers into one account and
+//! credit each payment by its memo; a payment without one reaches the
+//! exchange but no customer. SEP-29 lets such accounts say so on-chain with
+//! a `config.memo_required` data entry, but not all do, so the service keeps
+//! its own registry. Payment explanations and `POST /check` warn about
+//! payments to a registered account that carry no memo.
+//!
+//! The exchanges [`crate::services::labels`] names are built in. Operators
+//! add accounts with `MEMO_REQUIRED_ACCOUNTS`, a comma-separated list of
+//! addresses, each optionally followed by `=` and a name.
+
+use std::collections::HashMap;
+use std::env;
+use std::sync::OnceLock;
+
+use crate::models::strkey;
+
+/// Exchanges that credit deposits by memo.
+const EXCHANGES: &[(&str, &str)] = &[
+    (
+        "GBINANCEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+        "Binance",
+    ),
+    (
+        "GCOINBASEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+        "Coinbase",
+    ),
+    (
+        "GKRAKENAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+        "Kraken",
+    ),
+    (
+        "GROBINHOODAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+        "Robinhood",
+    ),
+];
+
+/// Name given to operator-added accounts listed without one.
+const UNNAMED: &str = "an exchange";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MemoRegistry {
+    /// Who runs each account, by address.
+    accounts: HashMap<String, String>,
+}
+
+impl Default for MemoRegistry {
+    fn default() -> Self {
+        Self::new(std::iter::empty())
+    }
+}
+
+impl MemoRegistry {
+    /// The built-in exchanges plus `extra` accounts, as (address, name).
+    pub fn new(extra: impl IntoIterator<Item = (String, String)>) -> Self {
+        let mut accounts: HashMap<String, String> = EXCHANGES
+            .iter()
+            .map(|(address, name)| (address.to_string(), name.to_string()))
+            .collect();
+        accounts.extend(extra);
+        Self { accounts }
+    }
+
+    /// Read `MEMO_REQUIRED_ACCOUNTS`; entries that are not account
+    /// addresses are ignored.
+    pub fn from_env() -> Self {
+        let extra = env::var("MEMO_REQUIRED_ACCOUNTS")
+            .map(|v| parse_accounts(&v))
+            .unwrap_or_default();
+        Self::new(extra)
+    }
+
+    /// Who runs `address`, when it needs a memo.
+    pub fn get(&self, address: &str) -> Option<&str> {
+        self.accounts.get(address.trim()).map(String::as_str)
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.accounts.is_empty()
+    }
+}
+
+/// "GABC...=Name,GDEF..." as (address, name) pairs.
+fn parse_accounts(value: &str) -> Vec<(String, String)> {
+    value
+        .split(',')
+        .filter_map(|entry| {
+            let (address, name) = match entry.split_once('=') {
+                Some((address, name)) if !name.trim().is_empty() => (address, name.trim()),
+                Some((address, _)) => (address, UNNAMED),
+                None => (entry, UNNAMED),
+            };
+            let address = address.trim();
+            strkey::is_account_address(address).then(|| (address.to_string(), name.to_string()))
+        })
+        .collect()
+}
+
+static REGISTRY: OnceLock<MemoRegistry> = OnceLock::new();
+
+/// Install the registry. Call once at startup; later calls are ignored.
+pub fn install(registry: MemoRegistry) {
+    let _ = REGISTRY.set(registry);
+}
+
+/// Who runs `address`, when the installed registry (or the built-in one)
+/// says it needs a memo.
+pub fn memo_required_by(address: &str) -> Option<&'static str> {
+    REGISTRY.get_or_init(MemoRegistry::default).get(address)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "GA5ZSEJYB37JRC5AVCIA5MOP4RHTM335X2KGX3IHOJAPP5RE34K4KZVN";
+    const OTHER: &str = "GAIH3ULLFQ4DGSECF2AR555KZ4KNDGEKN4AFI4SU2M7B43MGK3QJZNSR";
+
+    #[test]
+    fn test_builtin_exchanges_are_registered() {
+        let registry = MemoRegistry::default();
+        assert_eq!(
+            registry.get("GBINANCEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA"),
+            Some("Binance")
+        );
+        assert_eq!(registry.get(ACCOUNT), None);
+    }
+
+    #[test]
+    fn test_operator_accounts_are_parsed() {
+        let registry = MemoRegistry::new(parse_accounts(&format!(
+            " {ACCOUNT}=Example Exchange , {OTHER}, not-an-account=Nope"
+        )));
+        assert_eq!(registry.get(ACCOUNT), Some("Example Exchange"));
+        assert_eq!(registry.get(OTHER), Some(UNNAMED));
+        assert_eq!(registry.len(), EXCHANGES.len() + 2);
+    }
+}
@@ -0,0 +1,405 @@
+//! Fixture data served in mock mode.
+//!
+//! With `MOCK_MODE=true` the server answers from the in-memory Horizon
+//! built here instead of a real one, so frontend work and demos run offline
+//! against realistic data that is the same on every run:
+//!
+//! - every transaction in `tests/fixtures/horizon`, one per operation type
+//!   plus a few multi-operation patterns, by hash, with its ledger;
+//! - the accounts those transactions involve, with balances, recent
+//!   operations, transactions, signers, and an open offer;
+//! - the claimable balance and liquidity pool the fixtures touch;
+//! - a USDC issuer whose home domain's stellar.toml lists it.
+//!
+//! Hashes, addresses, and IDs outside this set answer not found, as Horizon
+//! would. Submissions are not accepted.
+
+use serde::Deserialize;
+use std::env;
+
+use crate::models::account::{Account, AccountFlags, AccountLinks, Balance, Signer};
+use crate::models::asset::StellarTomlStatus;
+use crate::models::claimable_balance::{ClaimPredicate, ClaimableBalance, Claimant};
+use crate::models::fee::{FeeDistribution, FeeMarket, FeeStats};
+use crate::models::ledger::Ledger;
+use crate::models::liquidity_pool::{LiquidityPool, PoolActivity, PoolActivityKind, PoolReserve};
+use crate::models::offer::Offer;
+use crate::services::horizon::{HorizonAccountTransaction, HorizonOperation, HorizonTransaction};
+use crate::services::horizon_api::FakeHorizon;
+
+/// Transaction fixtures, shared with the explanation snapshot tests.
+const TRANSACTIONS: &[&str] = &[
+    include_str!("../../tests/fixtures/horizon/account_merge.json"),
+    include_str!("../../tests/fixtures/horizon/allow_trust.json"),
+    include_str!("../../tests/fixtures/horizon/begin_sponsoring_future_reserves.json"),
+    include_str!("../../tests/fixtures/horizon/bump_sequence.json"),
+    include_str!("../../tests/fixtures/horizon/change_trust.json"),
+    include_str!("../../tests/fixtures/horizon/channel_account.json"),
+    include_str!("../../tests/fixtures/horizon/claim_claimable_balance.json"),
+    include_str!("../../tests/fixtures/horizon/clawback.json"),
+    include_str!("../../tests/fixtures/horizon/clawback_claimable_balance.json"),
+    include_str!("../../tests/fixtures/horizon/create_account.json"),
+    include_str!("../../tests/fixtures/horizon/create_claimable_balance.json"),
+    include_str!("../../tests/fixtures/horizon/create_passive_sell_offer.json"),
+    include_str!("../../tests/fixtures/horizon/extend_footprint_ttl.json"),
+    include_str!("../../tests/fixtures/horizon/failed_payment.json"),
+    include_str!("../../tests/fixtures/horizon/inflation.json"),
+    include_str!("../../tests/fixtures/horizon/invoke_host_function.json"),
+    include_str!("../../tests/fixtures/horizon/liquidity_pool_deposit.json"),
+    include_str!("../../tests/fixtures/horizon/liquidity_pool_withdraw.json"),
+    include_str!("../../tests/fixtures/horizon/manage_buy_offer.json"),
+    include_str!("../../tests/fixtures/horizon/manage_data.json"),
+    include_str!("../../tests/fixtures/horizon/manage_sell_offer.json"),
+    include_str!("../../tests/fixtures/horizon/multi_operation.json"),
+    include_str!("../../tests/fixtures/horizon/path_payment_strict_receive.json"),
+    include_str!("../../tests/fixtures/horizon/path_payment_strict_send.json"),
+    include_str!("../../tests/fixtures/horizon/payment.json"),
+    include_str!("../../tests/fixtures/horizon/payment_credit_memo.json"),
+    include_str!("../../tests/fixtures/horizon/restore_footprint.json"),
+    include_str!("../../tests/fixtures/horizon/revoke_sponsorship.json"),
+    include_str!("../../tests/fixtures/horizon/set_options.json"),
+    include_str!("../../tests/fixtures/horizon/set_trust_line_flags.json"),
+    include_str!("../../tests/fixtures/horizon/swap.json"),
+];
+
+/// The account most fixtures are sent from.
+pub const MOCK_ACCOUNT: &str = "GAAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQDZ7H";
+/// The account the payment fixtures are sent to.
+pub const MOCK_COUNTERPARTY: &str = "GABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEJXA";
+/// Issuer of the fixtures' USDC.
+pub const MOCK_USDC_ISSUER: &str = "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5";
+const MOCK_USDC_DOMAIN: &str = "usdc.example.com";
+const MOCK_CLAIMABLE_BALANCE: &str =
+    "00000000da0d57da7d4850e7fc10d2a9d0ebc731f7afb40574c03395b17d49149b91f5be";
+const MOCK_LIQUIDITY_POOL: &str =
+    "dd7b1ab831c273310ddbec6f97870aa83c2fbd78ce22aded37ecbf4f3380fac7";
+
+#[derive(Deserialize)]
+struct TransactionFixture {
+    transaction: HorizonTransaction,
+    operations: Vec<HorizonOperation>,
+}
+
+/// Whether `MOCK_MODE` turns mock mode on.
+pub fn mock_mode_enabled() -> bool {
+    env::var("MOCK_MODE")
+        .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+        .unwrap_or(false)
+}
+
+/// The in-memory Horizon mock mode serves.
+pub fn mock_data_provider() -> FakeHorizon {
+    let fixtures: Vec<TransactionFixture> = TRANSACTIONS
+        .iter()
+        .map(|source| serde_json::from_str(source).expect("transaction fixture is not valid"))
+        .collect();
+
+    let mut horizon = FakeHorizon::new()
+        .with_fee_stats(FeeStats::default_network_fees())
+        .with_fee_market(fee_market())
+        .with_average_close_seconds(5.0);
+
+    for fixture in &fixtures {
+        if let (Some(sequence), Some(closed_at)) =
+            (fixture.transaction.ledger, &fixture.transaction.created_at)
+        {
+            horizon = horizon.with_ledger(ledger(sequence, closed_at));
+        }
+        horizon = horizon.with_transaction(fixture.transaction.clone(), fixture.operations.clone());
+    }
+
+    for account in accounts() {
+        let address = account.account_id.clone();
+        horizon = horizon
+            .with_account_operations(&address, operations_of(&fixtures, &address))
+            .with_account_transactions(&address, transactions_of(&fixtures, &address))
+            .with_account(account);
+    }
+
+    horizon
+        .with_account_links(AccountLinks {
+            account_id: MOCK_ACCOUNT.to_string(),
+            signers: vec![signer(MOCK_ACCOUNT, 1), signer(MOCK_COUNTERPARTY, 1)],
+            sponsor: None,
+        })
+        .with_offer(
+            Offer {
+                id: 1_000_001,
+                seller: MOCK_ACCOUNT.to_string(),
+                selling: "native".to_string(),
+                buying: format!("USDC:{MOCK_USDC_ISSUER}"),
+                amount: "500.0000000".to_string(),
+                price: "0.1200000".to_string(),
+                sponsor: None,
+                last_modified_ledger: Some(1204530),
+                last_modified_time: Some("2025-03-04T10:30:00Z".to_string()),
+            },
+            Some("120.0000000"),
+        )
+        .with_claimable_balance(ClaimableBalance {
+            id: MOCK_CLAIMABLE_BALANCE.to_string(),
+            asset: format!("USDC:{MOCK_USDC_ISSUER}"),
+            amount: "100.0000000".to_string(),
+            sponsor: Some(MOCK_ACCOUNT.to_string()),
+            claimants: vec![Claimant {
+                destination: MOCK_COUNTERPARTY.to_string(),
+                predicate: ClaimPredicate::Unconditional,
+            }],
+            last_modified_ledger: Some(1204524),
+            clawback_enabled: false,
+        })
+        .with_liquidity_pool(
+            LiquidityPool {
+                id: MOCK_LIQUIDITY_POOL.to_string(),
+                fee_bp: 30,
+                pool_type: "constant_product".to_string(),
+                total_trustlines: 42,
+                total_shares: "50000.0000000".to_string(),
+                reserves: vec![
+                    reserve("native", "400000.0000000"),
+                    reserve(&format!("USDC:{MOCK_USDC_ISSUER}"), "48000.0000000"),
+                ],
+                last_modified_ledger: Some(1204529),
+            },
+            vec![PoolActivity {
+                kind: PoolActivityKind::Deposit,
+                account: MOCK_ACCOUNT.to_string(),
+                reserves: vec![
+                    reserve("native", "1000.0000000"),
+                    reserve(&format!("USDC:{MOCK_USDC_ISSUER}"), "120.0000000"),
+                ],
+                shares: "125.0000000".to_string(),
+                transaction_hash: "mock-pool-deposit".to_string(),
+                created_at: Some("2025-03-04T10:29:00Z".to_string()),
+            }],
+        )
+        .with_org_name(&format!("https://{MOCK_USDC_DOMAIN}"), "Example USD")
+        .with_toml_accounts(&format!("https://{MOCK_USDC_DOMAIN}"), &[MOCK_USDC_ISSUER])
+        .with_stellar_toml(
+            &format!("https://{MOCK_USDC_DOMAIN}"),
+            StellarTomlStatus::Listed,
+        )
+}
+
+fn accounts() -> Vec<Account> {
+    let usdc = |balance: &str| Balance {
+        asset_type: "credit_alphanum4".to_string(),
+        asset_code: Some("USDC".to_string()),
+        asset_issuer: Some(MOCK_USDC_ISSUER.to_string()),
+        balance: balance.to_string(),
+        limit: Some("922337203685.4775807".to_string()),
+        buying_liabilities: Some("0.0000000".to_string()),
+        selling_liabilities: Some("0.0000000".to_string()),
+        is_authorized: Some(true),
+        ..Default::default()
+    };
+    vec![
+        account(
+            MOCK_ACCOUNT,
+            vec![xlm("12500.5000000"), usdc("820.2500000")],
+            2,
+            None,
+            no_flags(),
+        ),
+        account(
+            MOCK_COUNTERPARTY,
+            vec![xlm("3021.0000000"), usdc("150.0000000")],
+            1,
+            None,
+            no_flags(),
+        ),
+        account(
+            MOCK_USDC_ISSUER,
+            vec![xlm("25.0000000")],
+            1,
+            Some(MOCK_USDC_DOMAIN),
+            AccountFlags {
+                auth_revocable: true,
+                auth_clawback_enabled: true,
+                ..no_flags()
+            },
+        ),
+    ]
+}
+
+fn account(
+    address: &str,
+    balances: Vec<Balance>,
+    num_signers: u32,
+    home_domain: Option<&str>,
+    flags: AccountFlags,
+) -> Account {
+    Account {
+        id: address.to_string(),
+        account_id: address.to_string(),
+        sequence: "5173180072476675".to_string(),
+        num_signers,
+        signers: Vec::new(),
+        master_key_weight: 1,
+        thresholds: Default::default(),
+        balances,
+        flags,
+        home_domain: home_domain.map(str::to_string),
+        memo_required: false,
+    }
+}
+
+fn xlm(balance: &str) -> Balance {
+    Balance {
+        asset_type: "native".to_string(),
+        balance: balance.to_string(),
+        ..Default::default()
+    }
+}
+
+fn no_flags() -> AccountFlags {
+    AccountFlags {
+        auth_required: false,
+        auth_revocable: false,
+        auth_immutable: false,
+        auth_clawback_enabled: false,
+    }
+}
+
+fn signer(key: &str, weight: u32) -> Signer {
+    Signer {
+        key: key.to_string(),
+        weight,
+        sponsor: None,
+    }
+}
+
+fn reserve(asset: &str, amount: &str) -> PoolReserve {
+    PoolReserve {
+        asset: asset.to_string(),
+        amount: amount.to_string(),
+    }
+}
+
+fn ledger(sequence: u64, closed_at: &str) -> Ledger {
+    Ledger {
+        sequence,
+        closed_at: closed_at.to_string(),
+        protocol_version: 22,
+        successful_transaction_count: 180,
+        failed_transaction_count: 12,
+        operation_count: 640,
+        base_fee_in_stroops: 100,
+        base_reserve_in_stroops: 5_000_000,
+    }
+}
+
+/// A quiet network: ledgers a third full, every fee the base fee but a few.
+fn fee_market() -> FeeMarket {
+    let fees = FeeDistribution {
+        mode: 100,
+        p50: 100,
+        p70: 100,
+        p90: 100,
+        p95: 200,
+        p99: 1_000,
+        max: 5_000,
+    };
+    FeeMarket {
+        last_ledger: 0,
+        base_fee: 100,
+        capacity_usage: 0.34,
+        charged: fees.clone(),
+        offered: fees,
+    }
+}
+
+/// Operations `address` submitted or that involve it, newest first.
+fn operations_of(fixtures: &[TransactionFixture], address: &str) -> Vec<HorizonOperation> {
+    let mut operations: Vec<HorizonOperation> = fixtures
+        .iter()
+        .flat_map(|fixture| {
+            fixture.operations.iter().filter(|op| {
+                [&op.source_account, &op.from, &op.to]
+                    .into_iter()
+                    .any(|account| account.as_deref() == Some(address))
+                    || (op.source_account.is_none()
+                        && fixture.transaction.source_account.as_deref() == Some(address))
+            })
+        })
+        .cloned()
+        .collect();
+    operations.sort_by(|a, b| {
+        let id = |op: &HorizonOperation| op.id.parse::<u64>().unwrap_or(0);
+        id(b).cmp(&id(a))
+    });
+    operations
+}
+
+/// Transactions `address` submitted, oldest first.
+fn transactions_of(
+    fixtures: &[TransactionFixture],
+    address: &str,
+) -> Vec<HorizonAccountTransaction> {
+    let mut transactions: Vec<HorizonAccountTransaction> = fixtures
+        .iter()
+        .filter(|fixture| fixture.transaction.source_account.as_deref() == Some(address))
+        .map(|fixture| {
+            let tx = &fixture.transaction;
+            HorizonAccountTransaction {
+                hash: tx.hash.clone(),
+                successful: tx.successful,
+                created_at: tx.created_at.clone().unwrap_or_default(),
+                source_account: tx.source_account.clone(),
+                operation_count: fixture.operations.len() as u32,
+                memo_type: tx.memo_type.clone(),
+                memo: tx.memo.clone(),
+                memo_bytes: tx.memo_bytes.clone(),
+                paging_token: None,
+            }
+        })
+        .collect();
+    transactions.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    transactions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::horizon_api::HorizonApi;
+
+    #[tokio::test]
+    async fn test_every_fixture_transaction_is_served() {
+        let horizon = mock_data_provider();
+        for source in TRANSACTIONS {
+            let fixture: TransactionFixture = serde_json::from_str(source).unwrap();
+            let hash = &fixture.transaction.hash;
+            assert!(horizon.fetch_transaction(hash).await.is_ok(), "{hash}");
+            let operations = horizon.fetch_operations(hash).await.unwrap();
+            assert_eq!(operations.len(), fixture.operations.len());
+            if let Some(sequence) = fixture.transaction.ledger {
+                assert!(horizon.fetch_ledger(sequence).await.is_ok());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_demo_account_has_history() {
+        let horizon = mock_data_provider();
+        let account = horizon.fetch_account(MOCK_ACCOUNT).await.unwrap();
+        assert_eq!(account.balances.len(), 2);
+        let operations = horizon
+            .fetch_account_operations(MOCK_ACCOUNT, 200)
+            .await
+            .unwrap();
+        assert!(operations.len() > 10);
+        let (transactions, _, _) = horizon
+            .fetch_account_transactions(MOCK_ACCOUNT, 50, None, "desc")
+            .await
+            .unwrap();
+        assert!(!transactions.is_empty());
+        assert_eq!(
+            horizon
+                .fetch_account_offers(MOCK_ACCOUNT, 10)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+}
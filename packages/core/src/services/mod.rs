@@ -1,7 +1,48 @@
+pub mod account;
+pub mod assets;
+pub mod audit;
+pub mod blocklist;
+pub mod circuit;
+pub mod consistency;
+pub mod contract_labels;
 pub mod explain;
+pub mod exports;
+pub mod faucet;
+pub mod full_history;
 pub mod horizon;
+pub mod horizon_api;
+pub mod horizon_pool;
+pub mod http;
+pub mod ingestion;
+pub mod issuer_labels;
 pub mod labels;
+pub mod market;
+pub mod memo_registry;
+pub mod mock;
+pub mod network_lookup;
+pub mod notice;
+pub mod offer_fills;
+pub mod operation_cache;
+pub mod pdf;
+pub mod pending;
+pub mod policy;
+pub mod privacy;
+pub mod provider;
+pub mod receipt;
+pub mod recording;
+pub mod rpc;
+pub mod send_check;
+pub mod slippage;
+pub mod slo;
+pub mod spam;
+pub mod stats;
+pub mod storage;
+pub mod submit;
+pub mod summarizer;
 pub mod transaction_cache;
+pub mod warmup;
 
 #[cfg(test)]
 mod horizon_test;
+#[cfg(test)]
+mod rpc_test;
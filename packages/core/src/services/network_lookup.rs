@@ -0,0 +1,154 @@
+//! Finding a transaction on a network this server does not explain.
+//!
+//! A hash pasted from a testnet wallet into a public-network explorer is a
+//! plain 404, and developers lose time deciding whether the transaction
+//! failed, is still pending, or never existed. With `?network=auto`,
+//! `GET /tx/:hash` asks the other networks' Horizons for a transaction this
+//! network does not have, all at once, and a hit is reported as
+//! `NOT_FOUND` naming the networks that do have it:
+//!
+//! ```json
+//! {"error": {"code": "NOT_FOUND",
+//!            "message": "This transaction is on testnet, not public, the network this server explains.",
+//!            "details": {"hash": "b9d0...", "network": "public", "found_on": ["testnet"]}}}
+//! ```
+//!
+//! A network that fails to answer counts as not having the transaction, so
+//! the lookup never turns a 404 into an upstream error.
+//!
+//! Configuration (environment):
+//! - `NETWORK_AUTO_ENABLED`: `false` or `0` to ignore `?network=auto`
+//!   (default on, except in mock mode).
+
+use futures_util::future::join_all;
+use std::env;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::errors::{AppError, HorizonError};
+use crate::services::provider::LedgerDataProvider;
+
+/// The network this server explains, and where to look on the others.
+#[derive(Clone)]
+pub struct NetworkLookup {
+    home: String,
+    others: Vec<(String, Arc<dyn LedgerDataProvider>)>,
+}
+
+impl NetworkLookup {
+    /// `home` is the `STELLAR_NETWORK` name of the network served.
+    pub fn new(home: impl Into<String>) -> Self {
+        Self {
+            home: home.into(),
+            others: Vec::new(),
+        }
+    }
+
+    /// Also look on the network called `name`, through `provider`.
+    pub fn with_network(
+        mut self,
+        name: impl Into<String>,
+        provider: Arc<dyn LedgerDataProvider>,
+    ) -> Self {
+        self.others.push((name.into(), provider));
+        self
+    }
+
+    /// The other networks that have the transaction `hash`, in the order
+    /// they were added.
+    pub async fn networks_with(&self, hash: &str) -> Vec<String> {
+        let lookups = self.others.iter().map(|(name, provider)| async move {
+            match provider.fetch_partial_transaction(hash).await {
+                Ok(_) => Some(name.clone()),
+                Err(HorizonError::TransactionNotFound) => None,
+                Err(err) => {
+                    warn!(hash, network = %name, error = ?err, "network_lookup_failed");
+                    None
+                }
+            }
+        });
+        join_all(lookups).await.into_iter().flatten().collect()
+    }
+
+    /// The error for a transaction this network does not have, when another
+    /// network has it.
+    pub async fn locate(&self, hash: &str) -> Option<AppError> {
+        let found_on = self.networks_with(hash).await;
+        (!found_on.is_empty()).then(|| AppError::TransactionOnOtherNetwork {
+            hash: hash.to_string(),
+            network: self.home.clone(),
+            found_on,
+        })
+    }
+}
+
+/// Whether `NETWORK_AUTO_ENABLED` leaves `?network=auto` on.
+pub fn network_lookup_enabled() -> bool {
+    env::var("NETWORK_AUTO_ENABLED")
+        .map(|v| !matches!(v.trim().to_ascii_lowercase().as_str(), "false" | "0"))
+        .unwrap_or(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+    use crate::services::horizon::HorizonClient;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    const HASH: &str = "a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2";
+
+    /// A Horizon that answers `/transactions/HASH` with `status`.
+    fn horizon(status: u16) -> (MockServer, Arc<dyn LedgerDataProvider>) {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(GET).path(format!("/transactions/{HASH}"));
+            then.status(status).json_body(json!({
+                "hash": HASH,
+                "successful": true,
+                "fee_charged": "100",
+            }));
+        });
+        server.mock(|when, then| {
+            when.method(GET)
+                .path(format!("/transactions/{HASH}/operations"));
+            then.status(status)
+                .json_body(json!({ "_embedded": { "records": [] } }));
+        });
+        let provider = Arc::new(HorizonClient::new(server.base_url()));
+        (server, provider)
+    }
+
+    #[tokio::test]
+    async fn test_reports_every_network_with_the_transaction() {
+        let (_testnet, testnet) = horizon(200);
+        let (_futurenet, futurenet) = horizon(404);
+        let (_other, other) = horizon(200);
+        let lookup = NetworkLookup::new("public")
+            .with_network("testnet", testnet)
+            .with_network("futurenet", futurenet)
+            .with_network("other", other);
+
+        assert_eq!(lookup.networks_with(HASH).await, ["testnet", "other"]);
+
+        let error = lookup.locate(HASH).await.unwrap().to_api_error().error;
+        assert_eq!(error.code, ErrorCode::NotFound);
+        assert_eq!(
+            error.message,
+            "This transaction is on testnet and other, not public, the network this server explains."
+        );
+        let details = error.details.unwrap();
+        assert_eq!(details["network"], "public");
+        assert_eq!(details["found_on"], json!(["testnet", "other"]));
+    }
+
+    #[tokio::test]
+    async fn test_failing_network_counts_as_not_having_it() {
+        let (_testnet, testnet) = horizon(500);
+        let lookup = NetworkLookup::new("public").with_network("testnet", testnet);
+
+        assert!(lookup.networks_with(HASH).await.is_empty());
+        assert!(lookup.locate(HASH).await.is_none());
+    }
+}
@@ -0,0 +1,135 @@
+//! An operator-set service notice, such as "Horizon mainnet is degraded;
+//! data may lag", shown with every response.
+//!
+//! Operators set and clear the notice through `/admin/notice`. While one is
+//! set, the response envelope adds it to `meta.notice`, so clients see it
+//! whichever endpoint they call. The notice is written to a file so it
+//! survives restarts until someone clears it.
+//!
+//! Configuration (environment):
+//! - `SERVICE_NOTICE_PATH`: file the notice is kept in (default
+//!   `stellar-explain-notice.json`).
+
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::io;
+use std::path::PathBuf;
+use std::sync::RwLock;
+use utoipa::ToSchema;
+
+const DEFAULT_PATH: &str = "stellar-explain-notice.json";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NoticeSeverity {
+    #[default]
+    Info,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ServiceNotice {
+    pub message: String,
+    #[serde(default)]
+    pub severity: NoticeSeverity,
+    /// When the notice was set, RFC 3339.
+    pub set_at: String,
+}
+
+#[derive(Debug)]
+pub struct NoticeBoard {
+    path: PathBuf,
+    current: RwLock<Option<ServiceNotice>>,
+}
+
+impl NoticeBoard {
+    /// Read `SERVICE_NOTICE_PATH` and the notice saved there, if any.
+    pub fn from_env() -> io::Result<Self> {
+        let path = env::var("SERVICE_NOTICE_PATH")
+            .ok()
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty())
+            .unwrap_or_else(|| DEFAULT_PATH.to_string());
+        Self::open(path)
+    }
+
+    /// A board kept at `path`, starting with the notice saved there.
+    pub fn open(path: impl Into<PathBuf>) -> io::Result<Self> {
+        let path = path.into();
+        let current = match std::fs::read(&path) {
+            Ok(bytes) => Some(serde_json::from_slice(&bytes)?),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => None,
+            Err(err) => return Err(err),
+        };
+        Ok(Self {
+            path,
+            current: RwLock::new(current),
+        })
+    }
+
+    pub fn get(&self) -> Option<ServiceNotice> {
+        self.current
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    /// Replace the notice. It is saved before it is shown, so a notice that
+    /// could not be saved is never shown.
+    pub fn set(&self, notice: ServiceNotice) -> io::Result<()> {
+        let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+        // Written beside the file and renamed over it, so a crash mid-write
+        // leaves the previous notice intact.
+        let partial = self.path.with_extension("json.partial");
+        std::fs::write(&partial, serde_json::to_vec(&notice)?)?;
+        std::fs::rename(&partial, &self.path)?;
+        *current = Some(notice);
+        Ok(())
+    }
+
+    /// Remove the notice; `false` when none was set.
+    pub fn clear(&self) -> io::Result<bool> {
+        let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => {}
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+        Ok(current.take().is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notice(message: &str) -> ServiceNotice {
+        ServiceNotice {
+            message: message.to_string(),
+            severity: NoticeSeverity::Warning,
+            set_at: "2026-01-02T10:15:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_notice_survives_restart_until_cleared() {
+        let path = env::temp_dir().join(format!("notice-{}.json", uuid::Uuid::new_v4()));
+        let board = NoticeBoard::open(&path).unwrap();
+        assert_eq!(board.get(), None);
+
+        board.set(notice("Horizon is degraded")).unwrap();
+        board
+            .set(notice("Horizon mainnet is degraded; data may lag"))
+            .unwrap();
+        let reopened = NoticeBoard::open(&path).unwrap();
+        assert_eq!(
+            reopened.get(),
+            Some(notice("Horizon mainnet is degraded; data may lag"))
+        );
+
+        assert!(reopened.clear().unwrap());
+        assert!(!reopened.clear().unwrap());
+        assert_eq!(NoticeBoard::open(&path).unwrap().get(), None);
+    }
+}
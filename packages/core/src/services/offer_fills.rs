@@ -0,0 +1,103 @@
+//! Find out what the orders in a transaction traded as soon as they were
+//! placed, from the trade effects of each manage offer operation.
+
+use futures_util::future::join_all;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::explain::operation::manage_offer::{OfferFills, explain_offer_fills};
+use crate::models::operation::{ManageOfferOperation, Operation};
+use crate::models::transaction::Transaction;
+use crate::services::horizon_api::HorizonApi;
+
+/// Orders checked per explanation; each costs an effects fetch.
+pub const MAX_CHECKED_OFFERS: usize = 5;
+
+/// Fills of the first [`MAX_CHECKED_OFFERS`] orders placed or updated in
+/// `transaction`, with each operation's index. Orders that traded nothing,
+/// or whose effects cannot be fetched, are left out.
+pub async fn fetch_offer_fills<H: HorizonApi>(
+    client: Arc<H>,
+    transaction: &Transaction,
+) -> Vec<(usize, OfferFills)> {
+    if !transaction.successful {
+        return Vec::new();
+    }
+    let offers = transaction
+        .operations
+        .iter()
+        .enumerate()
+        .filter_map(|(index, op)| match op {
+            Operation::ManageOffer(offer) if !offer.amount.is_zero() => Some((index, offer)),
+            _ => None,
+        })
+        .take(MAX_CHECKED_OFFERS);
+    join_all(offers.map(|(index, offer)| {
+        let client = Arc::clone(&client);
+        async move { Some((index, offer_fills(client.as_ref(), offer).await?)) }
+    }))
+    .await
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+async fn offer_fills<H: HorizonApi>(
+    client: &H,
+    offer: &ManageOfferOperation,
+) -> Option<OfferFills> {
+    let fills = client
+        .fetch_operation_trades(&offer.id, &offer.seller)
+        .await
+        .inspect_err(|err| warn!(operation = %offer.id, error = ?err, "offer_trades_fetch_failed"))
+        .ok()?;
+    explain_offer_fills(offer, &fills)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::OfferType;
+    use crate::models::trade::TradeFill;
+    use crate::services::horizon_api::FakeHorizon;
+
+    fn offer(id: &str, amount: &str) -> Operation {
+        Operation::ManageOffer(ManageOfferOperation {
+            id: id.to_string(),
+            seller: "GSELLER".to_string(),
+            selling_asset: "XLM (native)".to_string(),
+            buying_asset: "USDC (GISSUER)".to_string(),
+            amount: amount.parse().unwrap(),
+            price: "0.1125".to_string(),
+            offer_id: 0,
+            offer_type: OfferType::Sell,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_reports_orders_that_crossed() {
+        let fill = TradeFill {
+            sold_asset: "native".to_string(),
+            sold_amount: "400".parse().unwrap(),
+            bought_asset: "USDC:GISSUER".to_string(),
+            bought_amount: "45".parse().unwrap(),
+        };
+        let horizon = FakeHorizon::new()
+            .with_trades("op0", vec![fill.clone()])
+            .with_trades("op2", vec![fill]);
+        let tx = Transaction::new(
+            "hash".to_string(),
+            true,
+            100,
+            // op1 rested on the book; op2 is a cancellation.
+            vec![offer("op0", "400"), offer("op1", "400"), offer("op2", "0")],
+            None,
+            None,
+        );
+
+        let fills = fetch_offer_fills(Arc::new(horizon), &tx).await;
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].0, 0);
+        assert_eq!(fills[0].1.trades, 1);
+    }
+}
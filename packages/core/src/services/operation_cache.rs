@@ -0,0 +1,156 @@
+//! Reuse explanations of individual account operations.
+//!
+//! Busy accounts show the same operations again and again: every
+//! `/account/:address` call explains the latest ones, and every page of
+//! `/account/:address/flags-history` re-reads operations an earlier scan
+//! already explained. An operation never changes once it is in a ledger, so
+//! its explanation is kept by operation ID and reused until it expires.
+//!
+//! Nothing else varies an operation's explanation per request: display
+//! settings are process-wide and privacy mode is applied to the finished
+//! response. Settings changes also depend on whose history is being read,
+//! so they are kept per account.
+//!
+//! Configuration (environment):
+//! - `OPERATION_CACHE_ENABLED`: `true` or `1` to cache (default off).
+//! - `OPERATION_CACHE_TTL_SECONDS`: how long an explanation is kept
+//!   (default 3600).
+
+use std::env;
+use std::time::Duration;
+
+use crate::explain::account::{RecentOperationExplanation, explain_recent_operation};
+use crate::explain::flags_history::{AccountSettingsChange, explain_settings_change};
+use crate::models::operation::Operation;
+use crate::services::horizon::HorizonOperation;
+use crate::services::transaction_cache::{CacheKey, Network, TransactionCache};
+
+/// TTL when `OPERATION_CACHE_TTL_SECONDS` is not set.
+const DEFAULT_TTL: Duration = Duration::from_secs(3600);
+
+/// Read `OPERATION_CACHE_ENABLED` and `OPERATION_CACHE_TTL_SECONDS`; the TTL
+/// to cache for, or `None` when caching is off.
+pub fn operation_cache_ttl_from_env() -> Option<Duration> {
+    let enabled = env::var("OPERATION_CACHE_ENABLED")
+        .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+        .unwrap_or(false);
+    enabled.then(|| {
+        env::var("OPERATION_CACHE_TTL_SECONDS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|n| *n > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(DEFAULT_TTL)
+    })
+}
+
+pub struct OperationCache {
+    network: Network,
+    operations: TransactionCache<RecentOperationExplanation>,
+    settings_changes: TransactionCache<Option<AccountSettingsChange>>,
+}
+
+impl OperationCache {
+    pub fn new(network: Network, ttl: Duration) -> Self {
+        Self {
+            network,
+            operations: TransactionCache::new(ttl),
+            settings_changes: TransactionCache::new(ttl),
+        }
+    }
+
+    /// `op` explained on its own, as [`explain_recent_operation`] would.
+    pub fn recent_operation(&self, op: HorizonOperation) -> RecentOperationExplanation {
+        let key = CacheKey::new(op.id.clone(), self.network);
+        if let Some(explained) = self.operations.get(&key) {
+            return explained;
+        }
+        let hash = op.transaction_hash.clone();
+        let created_at = op.created_at.clone();
+        let explained = explain_recent_operation(hash, created_at, &Operation::from(op));
+        self.operations.insert(key, explained.clone());
+        explained
+    }
+
+    /// The settings `op` changed on `address`, as [`explain_settings_change`]
+    /// would find them.
+    pub fn settings_change(
+        &self,
+        address: &str,
+        op: &HorizonOperation,
+    ) -> Option<AccountSettingsChange> {
+        let key = CacheKey::new(format!("{}:{address}", op.id), self.network);
+        if let Some(change) = self.settings_changes.get(&key) {
+            return change;
+        }
+        let change = explain_settings_change(address, op);
+        self.settings_changes.insert(key, change.clone());
+        change
+    }
+
+    /// Drop expired entries. Returns how many.
+    pub fn evict_expired(&self) -> usize {
+        self.operations.evict_expired() + self.settings_changes.evict_expired()
+    }
+
+    /// Operation explanations and settings lookups held.
+    pub fn len(&self) -> usize {
+        self.operations.len() + self.settings_changes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payment(id: &str) -> HorizonOperation {
+        HorizonOperation {
+            id: id.to_string(),
+            transaction_hash: "abc".to_string(),
+            operation_type: "payment".to_string(),
+            from: Some("GA".to_string()),
+            to: Some("GB".to_string()),
+            amount: Some("5.0".to_string()),
+            asset_type: Some("native".to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_operations_are_explained_once_per_id() {
+        let cache = OperationCache::new(Network::Testnet, Duration::from_secs(60));
+        let first = cache.recent_operation(payment("1"));
+
+        // A changed record under the same ID is answered from the cache.
+        let mut changed = payment("1");
+        changed.amount = Some("6.0".to_string());
+        assert_eq!(cache.recent_operation(changed), first);
+        assert_ne!(cache.recent_operation(payment("2")).summary, "");
+        assert_eq!(cache.len(), 2);
+    }
+
+    #[test]
+    fn test_settings_changes_are_kept_per_account() {
+        let cache = OperationCache::new(Network::Testnet, Duration::from_secs(60));
+        let op = HorizonOperation {
+            id: "7".to_string(),
+            transaction_hash: "abc".to_string(),
+            operation_type: "set_options".to_string(),
+            source_account: Some("GA".to_string()),
+            master_key_weight: Some(0),
+            ..Default::default()
+        };
+
+        assert!(cache.settings_change("GA", &op).is_some());
+        assert!(cache.settings_change("GB", &op).is_none());
+        assert_eq!(
+            cache.settings_change("GA", &op),
+            explain_settings_change("GA", &op)
+        );
+        assert_eq!(cache.len(), 2);
+    }
+}
@@ -0,0 +1,230 @@
+//! Account statements as PDF.
+//!
+//! Laid out as plain text on A4 pages in the PDF standard Helvetica fonts,
+//! so no font files ship with the binary. Those fonts cover Latin-1; other
+//! characters (rare in addresses, amounts, and summaries) are replaced.
+
+use printpdf::{
+    BuiltinFont, IndirectFontRef, Mm, PdfDocument, PdfDocumentReference, PdfLayerReference,
+};
+
+use crate::explain::statement::AccountStatement;
+
+const PAGE_WIDTH: f32 = 210.0;
+const PAGE_HEIGHT: f32 = 297.0;
+const MARGIN: f32 = 18.0;
+/// Characters per line of body text before it wraps.
+const WRAP_AT: usize = 100;
+
+/// Render a statement as a PDF document.
+pub fn render_statement(statement: &AccountStatement) -> Result<Vec<u8>, printpdf::Error> {
+    let title = format!("Account statement {}", statement.address);
+    let mut page = PageWriter::new(&title)?;
+
+    page.heading(18.0, "Account statement");
+    page.line(10.0, &format!("Account: {}", statement.address));
+    page.line(
+        10.0,
+        &format!("Period: {} to {} (UTC)", statement.from, statement.to),
+    );
+    page.gap();
+
+    page.heading(13.0, "Balances");
+    if statement.balances.is_empty() {
+        page.line(10.0, "No balances.");
+    }
+    for balance in &statement.balances {
+        page.line(10.0, &format!("{}  {}", balance.balance, balance.asset));
+    }
+    page.gap();
+
+    page.heading(13.0, "Totals for the period");
+    if statement.totals.is_empty() {
+        page.line(10.0, "Nothing was sent or received.");
+    }
+    for total in &statement.totals {
+        page.line(
+            10.0,
+            &format!(
+                "{}: received {}, sent {}, net {}",
+                total.asset, total.received, total.sent, total.net
+            ),
+        );
+    }
+    page.gap();
+
+    page.heading(13.0, "Transactions");
+    if statement.transactions.is_empty() {
+        page.line(10.0, "No transactions in this period.");
+    }
+    for tx in &statement.transactions {
+        let status = if tx.successful { "" } else { " (failed)" };
+        page.bold_line(9.0, &format!("{}  {}{}", tx.created_at, tx.hash, status));
+        for line in wrap(&tx.summary, WRAP_AT) {
+            page.line(9.0, &line);
+        }
+        page.space(2.0);
+    }
+    if statement.truncated {
+        page.gap();
+        let note = "More transactions fell in this period than a statement lists; \
+                    only the newest are shown and totalled.";
+        for line in wrap(note, WRAP_AT) {
+            page.line(9.0, &line);
+        }
+    }
+
+    page.document.save_to_bytes()
+}
+
+/// Writes lines down the page, starting a new page when one fills.
+struct PageWriter {
+    document: PdfDocumentReference,
+    layer: PdfLayerReference,
+    regular: IndirectFontRef,
+    bold: IndirectFontRef,
+    /// Baseline of the next line, from the bottom of the page.
+    y: f32,
+}
+
+impl PageWriter {
+    fn new(title: &str) -> Result<Self, printpdf::Error> {
+        let (document, page, layer) =
+            PdfDocument::new(title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+        let regular = document.add_builtin_font(BuiltinFont::Helvetica)?;
+        let bold = document.add_builtin_font(BuiltinFont::HelveticaBold)?;
+        let layer = document.get_page(page).get_layer(layer);
+        Ok(Self {
+            document,
+            layer,
+            regular,
+            bold,
+            y: PAGE_HEIGHT - MARGIN,
+        })
+    }
+
+    fn heading(&mut self, size: f32, text: &str) {
+        self.bold_line(size, text);
+        self.space(1.0);
+    }
+
+    fn line(&mut self, size: f32, text: &str) {
+        let font = self.regular.clone();
+        self.write(size, text, &font);
+    }
+
+    fn bold_line(&mut self, size: f32, text: &str) {
+        let font = self.bold.clone();
+        self.write(size, text, &font);
+    }
+
+    fn gap(&mut self) {
+        self.space(4.0);
+    }
+
+    fn space(&mut self, mm: f32) {
+        self.y -= mm;
+    }
+
+    fn write(&mut self, size: f32, text: &str, font: &IndirectFontRef) {
+        // A point is 0.3528mm; leave a fifth of the size again between lines.
+        let height = size * 0.3528 * 1.2;
+        if self.y - height < MARGIN {
+            let (page, layer) = self
+                .document
+                .add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+            self.layer = self.document.get_page(page).get_layer(layer);
+            self.y = PAGE_HEIGHT - MARGIN;
+        }
+        self.y -= height;
+        self.layer
+            .use_text(latin1(text), size, Mm(MARGIN), Mm(self.y), font);
+    }
+}
+
+/// `text` with characters the standard fonts cannot draw replaced by "?".
+fn latin1(text: &str) -> String {
+    text.chars()
+        .map(|c| if (c as u32) < 0x100 { c } else { '?' })
+        .collect()
+}
+
+/// Break `text` into lines of at most `width` characters at spaces. A word
+/// longer than `width`, like a contract ID, gets a line to itself.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.chars().count() + 1 + word.chars().count() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::statement::{AssetTotal, StatementBalance, StatementTransaction};
+
+    #[test]
+    fn test_renders_multi_page_pdf() {
+        let statement = AccountStatement {
+            address: "GME".to_string(),
+            from: "2025-03-01".to_string(),
+            to: "2025-03-31".to_string(),
+            balances: vec![StatementBalance {
+                asset: "XLM (native)".to_string(),
+                balance: "120.0000000".to_string(),
+            }],
+            transactions: (0..80)
+                .map(|i| StatementTransaction {
+                    hash: format!("hash{i}"),
+                    created_at: "2025-03-04T10:15:22Z".to_string(),
+                    successful: i % 7 != 0,
+                    summary: "GME sent 5 XLM (native) to GTHEM. ".repeat(4),
+                })
+                .collect(),
+            totals: vec![AssetTotal {
+                asset: "XLM (native)".to_string(),
+                received: "0.0000000".to_string(),
+                sent: "400.0000000".to_string(),
+                net: "-400.0000000".to_string(),
+            }],
+            truncated: true,
+        };
+
+        let bytes = render_statement(&statement).unwrap();
+        assert!(bytes.starts_with(b"%PDF"));
+        let body = String::from_utf8_lossy(&bytes);
+        assert!(body.matches("/Type/Page/").count() > 1, "one page");
+    }
+
+    #[test]
+    fn test_wrap_at_spaces() {
+        assert_eq!(
+            wrap("one two three four", 9),
+            vec!["one two", "three", "four"]
+        );
+        assert_eq!(
+            wrap("averyveryverylongword x", 5),
+            vec!["averyveryverylongword", "x"]
+        );
+        assert!(wrap("   ", 10).is_empty());
+    }
+
+    #[test]
+    fn test_latin1_replaces_other_characters() {
+        assert_eq!(
+            latin1("1\u{a0}000 caf\u{e9} \u{2192}"),
+            "1\u{a0}000 caf\u{e9} ?"
+        );
+    }
+}
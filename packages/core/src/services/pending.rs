@@ -0,0 +1,174 @@
+//! What happened to a transaction the data source does not have.
+//!
+//! Wallets poll `GET /tx/:hash` right after submitting, and a bare 404
+//! cannot tell them whether to keep waiting. When enabled, a transaction
+//! that is not found is looked up with stellar-rpc's `getTransaction`, which
+//! follows the network more closely than Horizon's indexed history:
+//!
+//! - If RPC has it, the transaction is in a ledger that Horizon has not
+//!   indexed yet. It is reported as `TRANSACTION_PENDING` with the ledger.
+//! - Otherwise it is in no ledger up to RPC's latest. It is still reported
+//!   as `NOT_FOUND`, with the range of ledgers checked so a poller knows how
+//!   far the network has progressed.
+//!
+//! The network does not publish its queue of submitted transactions, so a
+//! transaction still waiting to be included cannot be told apart from one
+//! that was never submitted.
+//!
+//! Configuration (environment):
+//! - `PENDING_CHECK_ENABLED`: `true` or `1` to look up missing transactions.
+//!   Uses `RPC_URL`, which defaults to the SDF testnet RPC on testnet and
+//!   must be set on the public network.
+
+use async_trait::async_trait;
+use std::env;
+use tracing::warn;
+
+use crate::errors::AppError;
+use crate::services::rpc::RpcClient;
+
+/// Where a transaction missing from the data source stands on the network.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubmissionStatus {
+    /// In a ledger, but not yet indexed by the data source.
+    Included { ledger: Option<u64> },
+    /// In no ledger between `oldest_ledger` and `latest_ledger`.
+    NotIncluded {
+        latest_ledger: Option<u64>,
+        oldest_ledger: Option<u64>,
+    },
+}
+
+impl SubmissionStatus {
+    /// The error a lookup of `hash` answers with.
+    pub fn into_error(self, hash: &str) -> AppError {
+        match self {
+            SubmissionStatus::Included { ledger } => AppError::TransactionPending {
+                hash: hash.to_string(),
+                ledger,
+            },
+            SubmissionStatus::NotIncluded {
+                latest_ledger,
+                oldest_ledger,
+            } => AppError::TransactionNotIncluded {
+                hash: hash.to_string(),
+                latest_ledger,
+                oldest_ledger,
+            },
+        }
+    }
+}
+
+/// Somewhere to ask about transactions the data source does not have.
+#[async_trait]
+pub trait SubmissionStatusSource: Send + Sync {
+    /// None when the source could not answer; callers fall back to a plain
+    /// not found.
+    async fn submission_status(&self, hash: &str) -> Option<SubmissionStatus>;
+}
+
+#[async_trait]
+impl SubmissionStatusSource for RpcClient {
+    async fn submission_status(&self, hash: &str) -> Option<SubmissionStatus> {
+        let status = match self.get_transaction_status(hash).await {
+            Ok(status) => status,
+            Err(err) => {
+                warn!(hash, error = ?err, "submission_status_unavailable");
+                return None;
+            }
+        };
+        match status.status.as_str() {
+            "SUCCESS" | "FAILED" => Some(SubmissionStatus::Included {
+                ledger: status.ledger,
+            }),
+            "NOT_FOUND" => Some(SubmissionStatus::NotIncluded {
+                latest_ledger: status.latest_ledger,
+                oldest_ledger: status.oldest_ledger,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Whether `PENDING_CHECK_ENABLED` turns the lookup on.
+pub fn pending_check_enabled() -> bool {
+    env::var("PENDING_CHECK_ENABLED")
+        .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::errors::ErrorCode;
+    use httpmock::prelude::*;
+    use serde_json::json;
+
+    const HASH: &str = "a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2c3d4e5f6a1b2";
+
+    fn rpc_answering(result: serde_json::Value) -> MockServer {
+        let server = MockServer::start();
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "getTransaction"}"#);
+            then.status(200)
+                .json_body(json!({ "jsonrpc": "2.0", "id": 1, "result": result }));
+        });
+        server
+    }
+
+    #[tokio::test]
+    async fn test_ledgered_transaction_is_pending() {
+        let server = rpc_answering(json!({
+            "status": "SUCCESS",
+            "ledger": 1000,
+            "latestLedger": 1001,
+            "oldestLedger": 900
+        }));
+        let status = RpcClient::new(server.base_url())
+            .submission_status(HASH)
+            .await;
+
+        assert_eq!(
+            status,
+            Some(SubmissionStatus::Included { ledger: Some(1000) })
+        );
+        let error = status.unwrap().into_error(HASH).to_api_error().error;
+        assert_eq!(error.code, ErrorCode::TransactionPending);
+        assert_eq!(error.details.unwrap()["ledger"], 1000);
+    }
+
+    #[tokio::test]
+    async fn test_unseen_transaction_reports_ledgers_checked() {
+        let server = rpc_answering(json!({
+            "status": "NOT_FOUND",
+            "latestLedger": 1001,
+            "oldestLedger": 900
+        }));
+        let status = RpcClient::new(server.base_url())
+            .submission_status(HASH)
+            .await
+            .unwrap();
+
+        let error = status.into_error(HASH).to_api_error().error;
+        assert_eq!(error.code, ErrorCode::NotFound);
+        let details = error.details.unwrap();
+        assert_eq!(details["submission_status"], "not_included");
+        assert_eq!(details["latest_ledger"], 1001);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_failure_is_no_answer() {
+        let server = MockServer::start();
+        server.mock(|_, then| {
+            then.status(500);
+        });
+        assert_eq!(
+            RpcClient::new(server.base_url())
+                .submission_status(HASH)
+                .await,
+            None
+        );
+    }
+}
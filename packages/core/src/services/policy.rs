@@ -0,0 +1,394 @@
+//! Allowlist/blocklist policy for compliance deployments.
+//!
+//! A regulated operator may want its public instance to explain only its own
+//! accounts and assets, or to refuse some accounts outright. The policy names
+//! accounts and assets; every explanation is reduced to the [`Parties`] it
+//! involves and checked against it:
+//!
+//! - In allowlist mode, something is explained only if at least one of its
+//!   parties is listed. A payment between a listed anchor and anyone else is
+//!   allowed; a payment between two unlisted accounts is not.
+//! - In blocklist mode, something is refused if any of its parties is listed.
+//!
+//! An asset matches when it is listed itself or its issuer account is, so an
+//! anchor can list its issuing account once to cover all of its assets.
+//! Refusals are reported with the `POLICY_BLOCKED` error code.
+//!
+//! Configuration (environment):
+//! - `POLICY_MODE`: `allowlist` or `blocklist`; unset means no policy.
+//! - `POLICY_ACCOUNTS`: comma-separated account IDs (`G...`).
+//! - `POLICY_ASSETS`: comma-separated `CODE:ISSUER`.
+
+use serde_json::Value;
+use std::collections::{BTreeSet, HashSet};
+use std::env;
+use std::sync::OnceLock;
+use tracing::info;
+
+use crate::errors::AppError;
+use crate::explain::anchor::parse_asset;
+use crate::explain::claimable_balance::ClaimableBalanceExplanation;
+use crate::explain::liquidity_pool::LiquidityPoolExplanation;
+use crate::explain::transaction::TransactionExplanation;
+use crate::services::stats::COUNTERPARTY_DETAIL_KEYS;
+use crate::services::storage::ASSET_DETAIL_KEYS;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PolicyMode {
+    #[default]
+    Off,
+    Allowlist,
+    Blocklist,
+}
+
+impl PolicyMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "allowlist" => Some(PolicyMode::Allowlist),
+            "blocklist" => Some(PolicyMode::Blocklist),
+            "" | "off" => Some(PolicyMode::Off),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PolicyMode::Off => "off",
+            PolicyMode::Allowlist => "allowlist",
+            PolicyMode::Blocklist => "blocklist",
+        }
+    }
+}
+
+/// The accounts and assets an explanation involves.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Parties {
+    pub accounts: BTreeSet<String>,
+    /// Assets as "CODE:ISSUER"; native XLM is never a party.
+    pub assets: BTreeSet<String>,
+}
+
+impl Parties {
+    pub fn account(address: &str) -> Self {
+        let mut parties = Self::default();
+        parties.accounts.insert(address.to_string());
+        parties
+    }
+
+    pub fn asset(code: &str, issuer: &str) -> Self {
+        let mut parties = Self::default();
+        parties.add_asset(code, Some(issuer));
+        parties
+    }
+
+    /// Accounts and assets a transaction involves: those named in its
+    /// operation details, its source, fee, channel, and operation source
+    /// accounts, and the contract it called. The source accounts cover
+    /// operations without details, such as `manage_data` or `bump_sequence`.
+    pub fn of_transaction(explanation: &TransactionExplanation) -> Self {
+        let mut parties = Self::default();
+        parties
+            .accounts
+            .extend(explanation.source_accounts.iter().cloned());
+        parties.accounts.extend(explanation.channel_account.clone());
+        if let Some(call) = &explanation.contract_call {
+            parties.accounts.extend(call.contract_id.clone());
+        }
+        for op in &explanation.operations {
+            let Value::Object(details) = &op.details else {
+                continue;
+            };
+            for (key, value) in details {
+                let Some(text) = value.as_str() else { continue };
+                if COUNTERPARTY_DETAIL_KEYS.contains(&key.as_str()) && text != "Unknown" {
+                    parties.accounts.insert(text.to_string());
+                } else if ASSET_DETAIL_KEYS.contains(&key.as_str())
+                    && let Some((code, issuer)) = parse_asset(text)
+                {
+                    parties.add_asset(&code, issuer.as_deref());
+                }
+            }
+        }
+        parties
+    }
+
+    pub fn of_claimable_balance(explanation: &ClaimableBalanceExplanation) -> Self {
+        let mut parties = Self::default();
+        parties.add_asset(&explanation.asset_code, explanation.asset_issuer.as_deref());
+        parties.accounts.extend(explanation.sponsor.clone());
+        parties.accounts.extend(
+            explanation
+                .claimants
+                .iter()
+                .map(|claimant| claimant.destination.clone()),
+        );
+        parties
+    }
+
+    pub fn of_liquidity_pool(explanation: &LiquidityPoolExplanation) -> Self {
+        let mut parties = Self::default();
+        for reserve in &explanation.reserves {
+            parties.add_asset(&reserve.asset_code, reserve.asset_issuer.as_deref());
+        }
+        parties
+    }
+
+    fn add_asset(&mut self, code: &str, issuer: Option<&str>) {
+        if let Some(issuer) = issuer {
+            self.assets.insert(format!("{code}:{issuer}"));
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExplanationPolicy {
+    mode: PolicyMode,
+    accounts: HashSet<String>,
+    /// Assets as "CODE:ISSUER".
+    assets: HashSet<String>,
+}
+
+impl ExplanationPolicy {
+    pub fn new(
+        mode: PolicyMode,
+        accounts: impl IntoIterator<Item = String>,
+        assets: impl IntoIterator<Item = String>,
+    ) -> Self {
+        Self {
+            mode,
+            accounts: accounts.into_iter().collect(),
+            assets: assets.into_iter().collect(),
+        }
+    }
+
+    /// Read `POLICY_MODE`, `POLICY_ACCOUNTS`, and `POLICY_ASSETS`. Fails on
+    /// an unknown mode rather than silently running without a policy.
+    pub fn from_env() -> Result<Self, String> {
+        let mode = match env::var("POLICY_MODE") {
+            Ok(value) => PolicyMode::parse(&value).ok_or_else(|| {
+                format!("POLICY_MODE must be allowlist or blocklist, got {value}")
+            })?,
+            Err(_) => PolicyMode::Off,
+        };
+        let list = |name: &str| {
+            env::var(name)
+                .map(|v| {
+                    v.split(',')
+                        .map(str::trim)
+                        .filter(|item| !item.is_empty())
+                        .map(str::to_string)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+        let assets = list("POLICY_ASSETS");
+        if let Some(asset) = assets.iter().find(|asset| !asset.contains(':')) {
+            return Err(format!("POLICY_ASSETS entry {asset} is not CODE:ISSUER"));
+        }
+        Ok(Self::new(mode, list("POLICY_ACCOUNTS"), assets))
+    }
+
+    pub fn mode(&self) -> PolicyMode {
+        self.mode
+    }
+
+    pub fn len(&self) -> usize {
+        self.accounts.len() + self.assets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Whether the policy lets this service explain something involving
+    /// `parties`; `subject` names it in the error, e.g. "this account".
+    pub fn check(&self, parties: &Parties, subject: &str) -> Result<(), AppError> {
+        if self.allows(parties) {
+            return Ok(());
+        }
+        info!(mode = self.mode.as_str(), subject, "policy_blocked");
+        Err(AppError::PolicyBlocked(format!(
+            "This service is not permitted to explain {subject}."
+        )))
+    }
+
+    /// Whether something involving `parties` may be shown, for responses
+    /// that leave out what the policy refuses rather than failing.
+    pub fn allows(&self, parties: &Parties) -> bool {
+        let listed = || {
+            parties.accounts.iter().any(|a| self.accounts.contains(a))
+                || parties.assets.iter().any(|asset| {
+                    self.assets.contains(asset)
+                        || asset
+                            .split_once(':')
+                            .is_some_and(|(_, issuer)| self.accounts.contains(issuer))
+                })
+        };
+        match self.mode {
+            PolicyMode::Off => true,
+            PolicyMode::Allowlist => listed(),
+            PolicyMode::Blocklist => !listed(),
+        }
+    }
+}
+
+static POLICY: OnceLock<ExplanationPolicy> = OnceLock::new();
+
+/// Install the policy. Call once at startup; later calls are ignored.
+pub fn install(policy: ExplanationPolicy) {
+    let _ = POLICY.set(policy);
+}
+
+/// The installed policy, or no policy when none was installed.
+pub fn policy() -> &'static ExplanationPolicy {
+    POLICY.get_or_init(ExplanationPolicy::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::transaction::explain_transaction;
+    use crate::models::operation::{Operation, OtherOperation, PaymentOperation};
+    use crate::models::transaction::{Transaction, TransactionSource};
+
+    const ANCHOR: &str = "GANCHOR";
+    const ALICE: &str = "GALICE";
+    const BOB: &str = "GBOB";
+
+    fn payment(from: &str, to: &str, issuer: Option<&str>) -> TransactionExplanation {
+        let tx = Transaction::new(
+            "tx".to_string(),
+            true,
+            100,
+            vec![Operation::Payment(PaymentOperation {
+                id: "1".to_string(),
+                source_account: Some(from.to_string()),
+                destination: to.to_string(),
+                asset_type: if issuer.is_some() {
+                    "credit_alphanum4"
+                } else {
+                    "native"
+                }
+                .to_string(),
+                asset_code: issuer.map(|_| "USDC".to_string()),
+                asset_issuer: issuer.map(str::to_string),
+                amount: "5".parse().unwrap(),
+            })],
+            None,
+            None,
+        );
+        explain_transaction(&tx, None).unwrap()
+    }
+
+    /// A `manage_data` transaction, whose operation has no details, submitted
+    /// by `channel` on behalf of `owner`.
+    fn manage_data(channel: &str, owner: &str) -> TransactionExplanation {
+        let tx = Transaction::new(
+            "tx".to_string(),
+            true,
+            100,
+            vec![Operation::Other(OtherOperation {
+                id: "1".to_string(),
+                operation_type: "manage_data".to_string(),
+            })],
+            None,
+            None,
+        )
+        .with_source(TransactionSource {
+            account: channel.to_string(),
+            sequence: None,
+            fee_account: None,
+            operation_accounts: vec![owner.to_string()],
+        });
+        explain_transaction(&tx, None).unwrap()
+    }
+
+    fn blocked(result: Result<(), AppError>) -> bool {
+        matches!(result, Err(AppError::PolicyBlocked(_)))
+    }
+
+    #[test]
+    fn test_transaction_parties() {
+        let parties = Parties::of_transaction(&payment(ALICE, BOB, Some(ANCHOR)));
+        assert!(parties.accounts.contains(ALICE));
+        assert!(parties.accounts.contains(BOB));
+        assert_eq!(
+            parties.assets.iter().collect::<Vec<_>>(),
+            vec![&format!("USDC:{ANCHOR}")]
+        );
+
+        let native = Parties::of_transaction(&payment(ALICE, BOB, None));
+        assert!(native.assets.is_empty());
+
+        let channeled = Parties::of_transaction(&manage_data("GCHANNEL", ALICE));
+        assert_eq!(
+            channeled.accounts.iter().collect::<Vec<_>>(),
+            vec!["GALICE", "GCHANNEL"]
+        );
+    }
+
+    #[test]
+    fn test_source_accounts_are_checked_for_detail_less_operations() {
+        let blocklist = ExplanationPolicy::new(PolicyMode::Blocklist, [ALICE.to_string()], []);
+        assert!(blocked(blocklist.check(
+            &Parties::of_transaction(&manage_data("GCHANNEL", ALICE)),
+            "x"
+        )));
+
+        let allowlist = ExplanationPolicy::new(PolicyMode::Allowlist, [ALICE.to_string()], []);
+        assert!(
+            allowlist
+                .check(&Parties::of_transaction(&manage_data(ALICE, ALICE)), "x")
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_allowlist_needs_one_listed_party() {
+        let policy = ExplanationPolicy::new(PolicyMode::Allowlist, [ANCHOR.to_string()], []);
+
+        assert!(policy.check(&Parties::account(ANCHOR), "x").is_ok());
+        assert!(blocked(policy.check(&Parties::account(ALICE), "x")));
+        // Listing an issuer covers its assets.
+        assert!(policy.check(&Parties::asset("USDC", ANCHOR), "x").is_ok());
+        assert!(
+            policy
+                .check(
+                    &Parties::of_transaction(&payment(ALICE, BOB, Some(ANCHOR))),
+                    "x"
+                )
+                .is_ok()
+        );
+        assert!(blocked(policy.check(
+            &Parties::of_transaction(&payment(ALICE, BOB, None)),
+            "x"
+        )));
+        assert!(blocked(policy.check(&Parties::default(), "x")));
+    }
+
+    #[test]
+    fn test_blocklist_refuses_any_listed_party() {
+        let policy = ExplanationPolicy::new(
+            PolicyMode::Blocklist,
+            [BOB.to_string()],
+            [format!("SCAM:{ANCHOR}")],
+        );
+
+        assert!(policy.check(&Parties::account(ALICE), "x").is_ok());
+        assert!(blocked(policy.check(&Parties::account(BOB), "x")));
+        assert!(blocked(policy.check(&Parties::asset("SCAM", ANCHOR), "x")));
+        assert!(policy.check(&Parties::asset("USDC", ANCHOR), "x").is_ok());
+        assert!(blocked(policy.check(
+            &Parties::of_transaction(&payment(ALICE, BOB, None)),
+            "x"
+        )));
+    }
+
+    #[test]
+    fn test_off_allows_everything() {
+        let policy = ExplanationPolicy::default();
+        assert!(policy.check(&Parties::default(), "x").is_ok());
+        assert_eq!(PolicyMode::parse("Allowlist"), Some(PolicyMode::Allowlist));
+        assert_eq!(PolicyMode::parse("deny"), None);
+    }
+}
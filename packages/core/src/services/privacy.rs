@@ -0,0 +1,211 @@
+//! Privacy mode for explanations embedded in public contexts.
+//!
+//! When enabled, every Stellar address in a response is shortened to
+//! `GABCD…WXYZ` and memo contents are replaced with a neutral notice. The
+//! redaction runs over the serialized response rather than inside each
+//! explainer, so explainers need no privacy logic of their own. Handlers do:
+//! every route that returns addresses or memos must pass its response, or
+//! each streamed line, through [`apply_privacy`] with [`privacy_enabled`].
+//!
+//! Privacy mode is requested per call with `?privacy=true`; the server-wide
+//! default comes from the `PRIVACY_MODE` environment variable.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::env;
+use std::sync::OnceLock;
+
+use crate::errors::AppError;
+use crate::explain::format::truncate_middle;
+
+/// Replacement text for redacted memo explanations.
+pub const REDACTED_MEMO: &str =
+    "This transaction includes a memo (contents hidden in privacy mode).";
+
+/// Length of a G/C account or contract strkey.
+const STRKEY_LEN: usize = 56;
+/// Length of an M (muxed account) strkey.
+const MUXED_STRKEY_LEN: usize = 69;
+
+/// Keys whose values are memo contents and are redacted wholesale.
+const MEMO_KEYS: &[&str] = &["memo", "memo_explanation"];
+
+/// Query parameters accepted by endpoints that support privacy mode.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PrivacyQuery {
+    pub privacy: Option<bool>,
+}
+
+/// Whether privacy mode applies to a request: the explicit query value if
+/// one was given, otherwise the server default.
+pub fn privacy_enabled(requested: Option<bool>) -> bool {
+    requested.unwrap_or_else(default_privacy)
+}
+
+/// Server-wide default from `PRIVACY_MODE` ("true"/"1" to enable).
+pub fn default_privacy() -> bool {
+    static DEFAULT: OnceLock<bool> = OnceLock::new();
+    *DEFAULT.get_or_init(|| {
+        env::var("PRIVACY_MODE")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false)
+    })
+}
+
+/// Apply privacy mode to a response when enabled, returning it unchanged
+/// otherwise.
+pub fn apply_privacy<T>(response: T, enabled: bool) -> Result<T, AppError>
+where
+    T: Serialize + DeserializeOwned,
+{
+    if !enabled {
+        return Ok(response);
+    }
+    let mut value = serde_json::to_value(&response)
+        .map_err(|e| AppError::Internal(format!("privacy redaction failed: {e}")))?;
+    redact_value(&mut value);
+    serde_json::from_value(value)
+        .map_err(|e| AppError::Internal(format!("privacy redaction failed: {e}")))
+}
+
+/// Shorten an address to its first five and last four characters.
+pub fn mask_address(address: &str) -> String {
+    truncate_middle(address, 5, 4, "…")
+}
+
+/// Mask every Stellar address that appears anywhere in `text`.
+pub fn mask_addresses(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut token = String::new();
+    for ch in text.chars() {
+        if is_strkey_char(ch) {
+            token.push(ch);
+        } else {
+            flush_token(&mut token, &mut out);
+            out.push(ch);
+        }
+    }
+    flush_token(&mut token, &mut out);
+    out
+}
+
+fn flush_token(token: &mut String, out: &mut String) {
+    if is_address(token) {
+        out.push_str(&mask_address(token));
+    } else {
+        out.push_str(token);
+    }
+    token.clear();
+}
+
+fn is_strkey_char(ch: char) -> bool {
+    ch.is_ascii_uppercase() || ('2'..='7').contains(&ch)
+}
+
+fn is_address(token: &str) -> bool {
+    match token.as_bytes().first() {
+        Some(b'G') | Some(b'C') => token.len() == STRKEY_LEN,
+        Some(b'M') => token.len() == MUXED_STRKEY_LEN,
+        _ => false,
+    }
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::String(s) => *s = mask_addresses(s),
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        Value::Object(map) => {
+            // Comparison entries describe a memo difference via `field`.
+            let is_memo_entry = map.get("field").and_then(Value::as_str) == Some("memo");
+            for (key, child) in map.iter_mut() {
+                let redact_memo = MEMO_KEYS.contains(&key.as_str())
+                    || (is_memo_entry && (key == "a" || key == "b"));
+                if redact_memo && child.is_string() {
+                    *child = Value::String(REDACTED_MEMO.to_string());
+                } else {
+                    redact_value(child);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADDRESS: &str = "GCKFBEIYV2U22IO2BJ4KVJOIP7XPWQGQFKKWXR6DOSJBV7STMAQSMTGG";
+
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Sample {
+        summary: String,
+        memo_explanation: Option<String>,
+        count: u32,
+    }
+
+    #[test]
+    fn test_mask_address() {
+        assert_eq!(mask_address(ADDRESS), "GCKFB…MTGG");
+        assert_eq!(mask_address("GSHORT"), "GSHORT");
+    }
+
+    #[test]
+    fn test_mask_addresses_in_sentence() {
+        let text = format!("{ADDRESS} sent 5 XLM (native) to Coinbase ({ADDRESS}).");
+        assert_eq!(
+            mask_addresses(&text),
+            "GCKFB…MTGG sent 5 XLM (native) to Coinbase (GCKFB…MTGG)."
+        );
+        // Asset codes and other upper-case words are left alone.
+        assert_eq!(mask_addresses("USDC XLM GABC"), "USDC XLM GABC");
+    }
+
+    #[test]
+    fn test_apply_privacy_masks_and_redacts() {
+        let sample = Sample {
+            summary: format!("{ADDRESS} paid"),
+            memo_explanation: Some("This transaction includes a text memo: \"rent\"".to_string()),
+            count: 3,
+        };
+        let redacted = apply_privacy(sample, true).unwrap();
+        assert_eq!(redacted.summary, "GCKFB…MTGG paid");
+        assert_eq!(redacted.memo_explanation.as_deref(), Some(REDACTED_MEMO));
+        assert_eq!(redacted.count, 3);
+    }
+
+    #[test]
+    fn test_apply_privacy_disabled_is_noop() {
+        let sample = Sample {
+            summary: format!("{ADDRESS} paid"),
+            memo_explanation: None,
+            count: 1,
+        };
+        let unchanged = apply_privacy(
+            Sample {
+                summary: sample.summary.clone(),
+                memo_explanation: None,
+                count: 1,
+            },
+            false,
+        )
+        .unwrap();
+        assert_eq!(unchanged, sample);
+    }
+
+    #[test]
+    fn test_comparison_memo_values_redacted() {
+        let mut value = serde_json::json!({
+            "differences": [
+                { "field": "memo", "a": "secret", "b": null, "explanation": "differs" },
+                { "field": "fee", "a": "0.0000100 XLM", "b": "0.0000200 XLM" }
+            ]
+        });
+        redact_value(&mut value);
+        assert_eq!(value["differences"][0]["a"], REDACTED_MEMO);
+        assert!(value["differences"][0]["b"].is_null());
+        assert_eq!(value["differences"][1]["a"], "0.0000100 XLM");
+    }
+}
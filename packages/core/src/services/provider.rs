@@ -0,0 +1,142 @@
+//! Where transaction data comes from.
+//!
+//! Routes fetch transactions through [`LedgerDataProvider`] so a deployment
+//! can run against Horizon or, where no Horizon is available, a stellar-rpc
+//! node. The source is chosen at startup:
+//!
+//! - `DATA_SOURCE=horizon` (default) uses `HORIZON_URL`.
+//! - `DATA_SOURCE=rpc` uses `RPC_URL`, which defaults to the SDF testnet RPC
+//!   on testnet and must be set on the public network.
+
+use async_trait::async_trait;
+use std::env;
+use tracing::warn;
+
+use crate::errors::HorizonError;
+use crate::models::fee::FeeStats;
+use crate::models::transaction::Transaction;
+use crate::services::explain::map_transaction_to_domain;
+use crate::services::horizon::{HorizonOperation, HorizonTransaction};
+use crate::services::horizon_api::HorizonApi;
+
+/// A transaction together with the ledger that included it.
+#[derive(Debug, Clone)]
+pub struct LedgerTransaction {
+    pub transaction: Transaction,
+    /// ISO 8601 close time of the including ledger.
+    pub created_at: Option<String>,
+    pub ledger: Option<u64>,
+    /// The operations could not be fetched, so `transaction` has none.
+    pub operations_unavailable: bool,
+}
+
+/// A source of ledger data that transactions can be explained from.
+#[async_trait]
+pub trait LedgerDataProvider: Send + Sync {
+    /// Short name used in logs, e.g. "horizon".
+    fn name(&self) -> &'static str;
+
+    async fn fetch_transaction(&self, hash: &str) -> Result<LedgerTransaction, HorizonError>;
+
+    /// Like `fetch_transaction`, but a transaction whose operations cannot
+    /// be fetched is returned without them rather than failing.
+    async fn fetch_partial_transaction(
+        &self,
+        hash: &str,
+    ) -> Result<LedgerTransaction, HorizonError> {
+        self.fetch_transaction(hash).await
+    }
+
+    /// Current network fee stats. Returns None if unavailable — callers
+    /// degrade gracefully.
+    async fn fetch_fee_stats(&self) -> Option<FeeStats>;
+
+    /// Protocol version the network ran when `ledger` closed. Returns None
+    /// if unavailable, which is the default for sources without ledger
+    /// headers.
+    async fn fetch_protocol_version(&self, _ledger: u64) -> Option<u32> {
+        None
+    }
+}
+
+/// Any Horizon, real or fake, can serve transactions.
+#[async_trait]
+impl<H: HorizonApi> LedgerDataProvider for H {
+    fn name(&self) -> &'static str {
+        "horizon"
+    }
+
+    async fn fetch_transaction(&self, hash: &str) -> Result<LedgerTransaction, HorizonError> {
+        let (tx, ops) = tokio::join!(
+            HorizonApi::fetch_transaction(self, hash),
+            self.fetch_operations(hash)
+        );
+        Ok(ledger_transaction(tx?, ops?, false))
+    }
+
+    async fn fetch_partial_transaction(
+        &self,
+        hash: &str,
+    ) -> Result<LedgerTransaction, HorizonError> {
+        let (tx, ops) = tokio::join!(
+            HorizonApi::fetch_transaction(self, hash),
+            self.fetch_operations(hash)
+        );
+        let tx = tx?;
+        Ok(match ops {
+            Ok(ops) => ledger_transaction(tx, ops, false),
+            Err(err) => {
+                warn!(hash = %hash, error = ?err, "operations_fetch_failed");
+                ledger_transaction(tx, Vec::new(), true)
+            }
+        })
+    }
+
+    async fn fetch_fee_stats(&self) -> Option<FeeStats> {
+        HorizonApi::fetch_fee_stats(self).await
+    }
+
+    async fn fetch_protocol_version(&self, ledger: u64) -> Option<u32> {
+        self.fetch_ledger(ledger)
+            .await
+            .ok()
+            .map(|ledger| ledger.protocol_version)
+    }
+}
+
+fn ledger_transaction(
+    tx: HorizonTransaction,
+    ops: Vec<HorizonOperation>,
+    operations_unavailable: bool,
+) -> LedgerTransaction {
+    let created_at = tx.created_at.clone();
+    let ledger = tx.ledger;
+    LedgerTransaction {
+        transaction: map_transaction_to_domain(tx, ops),
+        created_at,
+        ledger,
+        operations_unavailable,
+    }
+}
+
+/// Which backend serves transaction data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataSource {
+    Horizon,
+    Rpc,
+}
+
+impl DataSource {
+    /// Read `DATA_SOURCE`, defaulting to Horizon for unset or unknown values.
+    pub fn from_env() -> Self {
+        match env::var("DATA_SOURCE")
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "rpc" => DataSource::Rpc,
+            _ => DataSource::Horizon,
+        }
+    }
+}
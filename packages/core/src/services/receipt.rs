@@ -0,0 +1,228 @@
+//! Signed explanation receipts.
+//!
+//! A receipt is a detached Ed25519 signature that lets a downstream system
+//! prove what this server said about a transaction, and when. The signed
+//! message is the canonical JSON of
+//!
+//! ```json
+//! {"explanation": <explanation>, "network": "<network>", "signed_at": "<RFC 3339>"}
+//! ```
+//!
+//! where canonical means object keys sorted by code point at every level and
+//! no insignificant whitespace. Verifiers rebuild that document from the
+//! response body and the receipt headers, then check the signature against
+//! the key published at `GET /keys`.
+//!
+//! The signing key is a Stellar secret seed (`S...`) in
+//! `RECEIPT_SIGNING_SEED`; its account ID (`G...`) is the key ID.
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::Serialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::models::strkey::{self, StrKey, StrKeyKind};
+
+/// Signature algorithm reported with every key and receipt.
+pub const RECEIPT_ALGORITHM: &str = "ed25519";
+
+/// Holds the receipt signing key.
+pub struct ReceiptSigner {
+    key: SigningKey,
+    key_id: String,
+}
+
+/// A published verification key.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct ReceiptKey {
+    /// Stellar account ID (`G...`) encoding the public key.
+    pub key_id: String,
+    pub algorithm: &'static str,
+    /// The raw 32-byte public key, hex encoded.
+    pub public_key_hex: String,
+}
+
+impl ReceiptSigner {
+    /// A signer for a Stellar secret seed (`S...`), or None if the seed is
+    /// not valid.
+    pub fn from_seed(seed: &str) -> Option<Self> {
+        let seed = strkey::decode_seed(seed.trim()).ok()?;
+        let key = SigningKey::from_bytes(&seed);
+        let key_id = StrKey::Account(key.verifying_key().to_bytes()).encode();
+        Some(Self { key, key_id })
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    pub fn public_key(&self) -> ReceiptKey {
+        ReceiptKey {
+            key_id: self.key_id.clone(),
+            algorithm: RECEIPT_ALGORITHM,
+            public_key_hex: hex(&self.key.verifying_key().to_bytes()),
+        }
+    }
+
+    /// Sign an explanation as served on `network` at `signed_at`. Returns the
+    /// signature, hex encoded.
+    pub fn sign(&self, explanation: &Value, network: &str, signed_at: &str) -> String {
+        let document = receipt_document(explanation, network, signed_at);
+        hex(&self.key.sign(document.as_bytes()).to_bytes())
+    }
+}
+
+/// The message a receipt signs.
+pub fn receipt_document(explanation: &Value, network: &str, signed_at: &str) -> String {
+    canonical_json(&serde_json::json!({
+        "explanation": explanation,
+        "network": network,
+        "signed_at": signed_at,
+    }))
+}
+
+/// Check a receipt signature against a key ID (`G...`).
+pub fn verify_receipt(
+    key_id: &str,
+    explanation: &Value,
+    network: &str,
+    signed_at: &str,
+    signature_hex: &str,
+) -> bool {
+    let Ok(StrKey::Account(public_key)) = strkey::validate(key_id, &[StrKeyKind::Account]) else {
+        return false;
+    };
+    let Ok(key) = VerifyingKey::from_bytes(&public_key) else {
+        return false;
+    };
+    let Some(signature) = unhex(signature_hex)
+        .and_then(|bytes| <[u8; 64]>::try_from(bytes).ok())
+        .map(|bytes| Signature::from_bytes(&bytes))
+    else {
+        return false;
+    };
+    let document = receipt_document(explanation, network, signed_at);
+    key.verify(document.as_bytes(), &signature).is_ok()
+}
+
+/// Serialize `value` with object keys sorted at every level and no
+/// whitespace, so equal values always produce the same bytes.
+pub fn canonical_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_canonical(value, &mut out);
+    out
+}
+
+fn write_canonical(value: &Value, out: &mut String) {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn unhex(s: &str) -> Option<Vec<u8>> {
+    if !s.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(s.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn seed() -> String {
+        stellar_strkey::ed25519::PrivateKey([7; 32]).to_string()
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_keys_at_every_level() {
+        let value = json!({ "b": 1, "a": { "z": [ { "y": true, "x": null } ], "c": "é\"" } });
+        assert_eq!(
+            canonical_json(&value),
+            r#"{"a":{"c":"é\"","z":[{"x":null,"y":true}]},"b":1}"#
+        );
+    }
+
+    #[test]
+    fn test_sign_and_verify_receipt() {
+        let signer = ReceiptSigner::from_seed(&seed()).unwrap();
+        assert!(signer.key_id().starts_with('G'));
+        assert_eq!(signer.public_key().public_key_hex.len(), 64);
+
+        let explanation = json!({ "transaction_hash": "abc", "summary": "Paid 5 XLM." });
+        let signed_at = "2026-01-01T00:00:00Z";
+        let signature = signer.sign(&explanation, "testnet", signed_at);
+
+        assert!(verify_receipt(
+            signer.key_id(),
+            &explanation,
+            "testnet",
+            signed_at,
+            &signature
+        ));
+        // Any change to what was said, where, or when breaks the signature.
+        let altered = json!({ "transaction_hash": "abc", "summary": "Paid 6 XLM." });
+        assert!(!verify_receipt(
+            signer.key_id(),
+            &altered,
+            "testnet",
+            signed_at,
+            &signature
+        ));
+        assert!(!verify_receipt(
+            signer.key_id(),
+            &explanation,
+            "public",
+            signed_at,
+            &signature
+        ));
+        assert!(!verify_receipt(
+            signer.key_id(),
+            &explanation,
+            "testnet",
+            "2026-01-02T00:00:00Z",
+            &signature
+        ));
+    }
+
+    #[test]
+    fn test_rejects_invalid_seed() {
+        assert!(ReceiptSigner::from_seed("not-a-seed").is_none());
+        assert!(
+            ReceiptSigner::from_seed("GAAZI4TCR3TY5OJHCTJC2A4QSY6CJWJH5IAJTGKIN2ER7LBNVKOCCWN7")
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Record Horizon responses to disk and replay them later.
+//!
+//! In record mode every response Horizon sends is written to a file named
+//! after the request; in replay mode those files answer instead of the
+//! network, so integration tests and demos run the same way every time.
+//! Requests are keyed by path and query, not host, so recordings made
+//! against one Horizon replay against any `HORIZON_URL`. Replay never
+//! touches the network: a request with no recording fails as unreachable.
+//!
+//! Only Horizon GETs are recorded. Transaction submissions and stellar.toml
+//! fetches always go to the network.
+//!
+//! Configuration (environment):
+//! - `HORIZON_RECORDING`: `record` or `replay` (default off).
+//! - `HORIZON_RECORDING_DIR`: directory for recordings (default
+//!   `horizon-recordings`).
+
+use reqwest::Url;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingMode {
+    /// Fetch from Horizon and save every response.
+    Record,
+    /// Answer from saved responses only.
+    Replay,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordingConfig {
+    pub mode: RecordingMode,
+    pub dir: PathBuf,
+}
+
+impl RecordingConfig {
+    /// Read the recording settings, or `None` when recording is off.
+    pub fn from_env() -> Option<Self> {
+        let mode = match env::var("HORIZON_RECORDING")
+            .ok()?
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "record" => RecordingMode::Record,
+            "replay" => RecordingMode::Replay,
+            _ => return None,
+        };
+        Some(Self {
+            mode,
+            dir: env::var("HORIZON_RECORDING_DIR")
+                .unwrap_or_else(|_| "horizon-recordings".to_string())
+                .into(),
+        })
+    }
+}
+
+/// A saved response. The URL is kept so recordings can be found by eye.
+#[derive(Debug, Serialize, Deserialize)]
+struct Recording {
+    url: String,
+    status: u16,
+    body: String,
+}
+
+#[derive(Debug)]
+pub struct Recorder {
+    config: RecordingConfig,
+}
+
+impl Recorder {
+    pub fn new(config: RecordingConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn mode(&self) -> RecordingMode {
+        self.config.mode
+    }
+
+    /// The saved status and body for `url`, if any.
+    pub fn replay(&self, url: &str) -> Option<(u16, Vec<u8>)> {
+        let path = self.path(url);
+        let source = fs::read_to_string(&path).ok()?;
+        match serde_json::from_str::<Recording>(&source) {
+            Ok(recording) => Some((recording.status, recording.body.into_bytes())),
+            Err(err) => {
+                warn!(path = %path.display(), error = %err, "horizon_recording_invalid");
+                None
+            }
+        }
+    }
+
+    /// Save Horizon's answer to `url`. Failures are logged, not returned: a
+    /// recording that cannot be written should not fail the request.
+    pub fn record(&self, url: &str, status: u16, body: &[u8]) {
+        let recording = Recording {
+            url: key(url),
+            status,
+            body: String::from_utf8_lossy(body).into_owned(),
+        };
+        let path = self.path(url);
+        let written = fs::create_dir_all(&self.config.dir).and_then(|_| {
+            let json = serde_json::to_string_pretty(&recording).map_err(std::io::Error::other)?;
+            fs::write(&path, json)
+        });
+        if let Err(err) = written {
+            warn!(path = %path.display(), error = %err, "horizon_recording_not_saved");
+        }
+    }
+
+    fn path(&self, url: &str) -> PathBuf {
+        let digest = Sha256::digest(key(url).as_bytes());
+        let hex: String = digest[..16].iter().map(|b| format!("{b:02x}")).collect();
+        self.config.dir.join(format!("{hex}.json"))
+    }
+}
+
+/// What a request is recorded under: its path and query.
+fn key(url: &str) -> String {
+    match Url::parse(url) {
+        Ok(parsed) => match parsed.query() {
+            Some(query) => format!("{}?{query}", parsed.path()),
+            None => parsed.path().to_string(),
+        },
+        Err(_) => url.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorder(mode: RecordingMode) -> (Recorder, PathBuf) {
+        let dir = env::temp_dir().join(format!("recordings-{}", uuid::Uuid::new_v4()));
+        let recorder = Recorder::new(RecordingConfig {
+            mode,
+            dir: dir.clone(),
+        });
+        (recorder, dir)
+    }
+
+    #[test]
+    fn test_recordings_replay_for_any_host() {
+        let (recorder, dir) = recorder(RecordingMode::Record);
+        recorder.record(
+            "https://horizon.stellar.org/transactions/abc?limit=1",
+            200,
+            br#"{"hash":"abc"}"#,
+        );
+
+        assert_eq!(
+            recorder.replay("http://127.0.0.1:8000/transactions/abc?limit=1"),
+            Some((200, br#"{"hash":"abc"}"#.to_vec()))
+        );
+        assert_eq!(
+            recorder.replay("http://127.0.0.1:8000/transactions/abc"),
+            None
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_not_found_is_recorded() {
+        let (recorder, dir) = recorder(RecordingMode::Record);
+        recorder.record("https://horizon.stellar.org/accounts/GNONE", 404, b"{}");
+
+        assert_eq!(
+            recorder.replay("https://horizon.stellar.org/accounts/GNONE"),
+            Some((404, b"{}".to_vec()))
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
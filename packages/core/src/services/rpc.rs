@@ -0,0 +1,891 @@
+//! stellar-rpc (formerly soroban-rpc) client.
+//!
+//! RPC serves raw XDR rather than Horizon's decoded JSON, so transactions are
+//! decoded here into the same Horizon-shaped records the rest of the service
+//! consumes, then mapped with [`map_transaction_to_domain`]. Amounts, asset
+//! fields, operation types, and result codes are rendered the way Horizon
+//! renders them.
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use reqwest::Client;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Value, json};
+use std::sync::atomic::{AtomicU64, Ordering};
+use stellar_xdr::curr::{
+    self as xdr, Asset, ChangeTrustAsset, ClaimableBalanceId, InnerTransactionResultResult,
+    MuxedAccount, OperationBody, OperationResult, OperationResultTr, ReadXdr, TransactionEnvelope,
+    TransactionResult, TransactionResultResult,
+};
+use tracing::warn;
+
+use crate::errors::HorizonError;
+use crate::models::amount::Amount;
+use crate::models::contract_event::{ContractEvent, decode_value_base64, upstream_limits};
+use crate::models::fee::FeeStats;
+use crate::services::explain::map_transaction_to_domain;
+use crate::services::horizon::{HorizonOperation, HorizonResultCodes, HorizonTransaction};
+use crate::services::provider::{LedgerDataProvider, LedgerTransaction};
+
+/// Network base fee in stroops. RPC fee stats report inclusion fees only.
+const BASE_FEE_STROOPS: u64 = 100;
+
+// ── RPC response structs ───────────────────────────────────────────────────
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    error: Option<RpcErrorBody>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcErrorBody {
+    code: i64,
+    message: String,
+}
+
+/// Result of `getTransaction`.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransaction {
+    /// "SUCCESS", "FAILED", or "NOT_FOUND".
+    pub status: String,
+    pub ledger: Option<u64>,
+    /// Ledger close time in Unix seconds; a string in current RPC versions.
+    pub created_at: Option<Value>,
+    /// Position of the transaction within its ledger, starting at 1.
+    pub application_order: Option<u32>,
+    pub envelope_xdr: Option<String>,
+    pub result_xdr: Option<String>,
+    pub result_meta_xdr: Option<String>,
+}
+
+/// Where `getTransaction` places a transaction, including ones RPC has not
+/// seen.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransactionStatus {
+    /// "SUCCESS", "FAILED", or "NOT_FOUND".
+    pub status: String,
+    pub ledger: Option<u64>,
+    /// Newest ledger RPC had ingested when it answered.
+    pub latest_ledger: Option<u64>,
+    /// Oldest ledger RPC still retains.
+    pub oldest_ledger: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEventsPage {
+    #[serde(default)]
+    events: Vec<RawEvent>,
+    latest_ledger: u64,
+    cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawEvent {
+    id: String,
+    ledger: u64,
+    contract_id: Option<String>,
+    #[serde(default)]
+    topic: Vec<String>,
+    value: String,
+    tx_hash: Option<String>,
+}
+
+/// A contract event returned by `getEvents`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcEvent {
+    pub id: String,
+    pub ledger: u64,
+    pub tx_hash: Option<String>,
+    pub event: ContractEvent,
+}
+
+/// One page of `getEvents` results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RpcEventsPage {
+    pub events: Vec<RpcEvent>,
+    pub latest_ledger: u64,
+    /// Pass back as `cursor` to continue after the last event.
+    pub cursor: Option<String>,
+}
+
+/// Result of `simulateTransaction`.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcSimulation {
+    pub latest_ledger: u64,
+    /// Resource fee the transaction needs, in stroops.
+    pub min_resource_fee: Option<String>,
+    /// Why simulation failed, if it did.
+    pub error: Option<String>,
+    #[serde(default)]
+    pub results: Vec<RpcSimulationResult>,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct RpcSimulationResult {
+    /// Base64 `ScVal` returned by the invoked function.
+    pub xdr: String,
+    #[serde(default)]
+    pub auth: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawFeeStats {
+    inclusion_fee: RawFeeDistribution,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawFeeDistribution {
+    min: String,
+    max: String,
+    mode: String,
+    p90: String,
+}
+
+// ── RpcClient ──────────────────────────────────────────────────────────────
+
+pub struct RpcClient {
+    client: Client,
+    url: String,
+    next_id: AtomicU64,
+}
+
+impl RpcClient {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(),
+            url: url.into(),
+            next_id: AtomicU64::new(1),
+        }
+    }
+
+    /// Send requests through `client`, normally the shared one from
+    /// [`HttpConfig::build_client`](crate::services::http::HttpConfig::build_client).
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Send one JSON-RPC 2.0 request and return its `result`.
+    async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<T, HorizonError> {
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+
+        let res = self
+            .client
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|_| HorizonError::NetworkError)?;
+
+        if res.status().as_u16() != 200 {
+            return Err(HorizonError::InvalidResponse);
+        }
+
+        let response: RpcResponse<T> = res
+            .json()
+            .await
+            .map_err(|_| HorizonError::InvalidResponse)?;
+
+        match (response.result, response.error) {
+            (Some(result), _) => Ok(result),
+            (None, Some(err)) => {
+                warn!(method, code = err.code, message = %err.message, "rpc_call_failed");
+                Err(HorizonError::InvalidResponse)
+            }
+            (None, None) => Err(HorizonError::InvalidResponse),
+        }
+    }
+
+    /// `getTransaction`. Transactions RPC no longer retains (or never saw)
+    /// are `TransactionNotFound`.
+    pub async fn get_transaction(&self, hash: &str) -> Result<RpcTransaction, HorizonError> {
+        let tx: RpcTransaction = self.call("getTransaction", json!({ "hash": hash })).await?;
+        match tx.status.as_str() {
+            "SUCCESS" | "FAILED" => Ok(tx),
+            _ => Err(HorizonError::TransactionNotFound),
+        }
+    }
+
+    /// `getTransaction`, answering "NOT_FOUND" rather than failing for
+    /// transactions RPC has not seen.
+    pub async fn get_transaction_status(
+        &self,
+        hash: &str,
+    ) -> Result<RpcTransactionStatus, HorizonError> {
+        self.call("getTransaction", json!({ "hash": hash })).await
+    }
+
+    /// `getEvents` for the given contracts, starting at `start_ledger` or
+    /// continuing from `cursor`.
+    pub async fn get_events(
+        &self,
+        start_ledger: Option<u64>,
+        contract_ids: &[String],
+        limit: u32,
+        cursor: Option<&str>,
+    ) -> Result<RpcEventsPage, HorizonError> {
+        let mut filter = json!({ "type": "contract" });
+        if !contract_ids.is_empty() {
+            filter["contractIds"] = json!(contract_ids);
+        }
+        let mut pagination = json!({ "limit": limit });
+        if let Some(cursor) = cursor {
+            pagination["cursor"] = json!(cursor);
+        }
+        let mut params = json!({ "filters": [filter], "pagination": pagination });
+        // RPC rejects startLedger alongside a cursor.
+        if let (Some(start), None) = (start_ledger, cursor) {
+            params["startLedger"] = json!(start);
+        }
+
+        let raw: RawEventsPage = self.call("getEvents", params).await?;
+        let events = raw
+            .events
+            .into_iter()
+            .map(|e| {
+                let topics = e
+                    .topic
+                    .iter()
+                    .map(|t| decode_value_base64(t))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| HorizonError::InvalidResponse)?;
+                let data =
+                    decode_value_base64(&e.value).map_err(|_| HorizonError::InvalidResponse)?;
+                Ok(RpcEvent {
+                    id: e.id,
+                    ledger: e.ledger,
+                    tx_hash: e.tx_hash,
+                    event: ContractEvent {
+                        contract_id: e.contract_id,
+                        topics,
+                        data,
+                        diagnostic: false,
+                    },
+                })
+            })
+            .collect::<Result<Vec<_>, HorizonError>>()?;
+
+        Ok(RpcEventsPage {
+            events,
+            latest_ledger: raw.latest_ledger,
+            cursor: raw.cursor,
+        })
+    }
+
+    /// `simulateTransaction` for a base64 transaction envelope.
+    pub async fn simulate_transaction(
+        &self,
+        envelope_xdr: &str,
+    ) -> Result<RpcSimulation, HorizonError> {
+        self.call(
+            "simulateTransaction",
+            json!({ "transaction": envelope_xdr }),
+        )
+        .await
+    }
+
+    /// Inclusion fee stats from `getFeeStats`.
+    /// Returns None if the request fails — callers degrade gracefully.
+    pub async fn get_fee_stats(&self) -> Option<FeeStats> {
+        let raw: RawFeeStats = self.call("getFeeStats", json!({})).await.ok()?;
+        let fees = raw.inclusion_fee;
+        let parse = |v: &str| v.parse::<u64>().unwrap_or(BASE_FEE_STROOPS);
+        Some(FeeStats::new(
+            BASE_FEE_STROOPS,
+            parse(&fees.min),
+            parse(&fees.max),
+            parse(&fees.mode),
+            parse(&fees.p90),
+        ))
+    }
+}
+
+#[async_trait]
+impl LedgerDataProvider for RpcClient {
+    fn name(&self) -> &'static str {
+        "rpc"
+    }
+
+    async fn fetch_transaction(&self, hash: &str) -> Result<LedgerTransaction, HorizonError> {
+        let rpc_tx = self.get_transaction(hash).await?;
+        let (tx, ops) = to_horizon_records(hash, rpc_tx)?;
+        let created_at = tx.created_at.clone();
+        let ledger = tx.ledger;
+        Ok(LedgerTransaction {
+            transaction: map_transaction_to_domain(tx, ops),
+            created_at,
+            ledger,
+            operations_unavailable: false,
+        })
+    }
+
+    async fn fetch_fee_stats(&self) -> Option<FeeStats> {
+        self.get_fee_stats().await
+    }
+}
+
+// ── XDR → Horizon-shaped records ───────────────────────────────────────────
+
+/// Decode an RPC transaction into the records Horizon would have returned
+/// for `/transactions/:hash` and `/transactions/:hash/operations`.
+pub fn to_horizon_records(
+    hash: &str,
+    rpc_tx: RpcTransaction,
+) -> Result<(HorizonTransaction, Vec<HorizonOperation>), HorizonError> {
+    let result = rpc_tx
+        .result_xdr
+        .as_deref()
+        .and_then(|x| TransactionResult::from_xdr_base64(x, upstream_limits(x)).ok())
+        .ok_or(HorizonError::InvalidResponse)?;
+    decode_records(hash, rpc_tx, Some(&result))
+}
+
+/// Decode a signed envelope that has not been submitted yet into the
+/// records it would have, with nothing charged and no result.
+pub fn envelope_records(
+    hash: &str,
+    envelope_xdr: &str,
+) -> Result<(HorizonTransaction, Vec<HorizonOperation>), HorizonError> {
+    let rpc_tx = RpcTransaction {
+        status: "SUCCESS".to_string(),
+        ledger: None,
+        created_at: None,
+        application_order: None,
+        envelope_xdr: Some(envelope_xdr.to_string()),
+        result_xdr: None,
+        result_meta_xdr: None,
+    };
+    decode_records(hash, rpc_tx, None)
+}
+
+fn decode_records(
+    hash: &str,
+    rpc_tx: RpcTransaction,
+    result: Option<&TransactionResult>,
+) -> Result<(HorizonTransaction, Vec<HorizonOperation>), HorizonError> {
+    let envelope = rpc_tx
+        .envelope_xdr
+        .as_deref()
+        .and_then(|x| TransactionEnvelope::from_xdr_base64(x, upstream_limits(x)).ok())
+        .ok_or(HorizonError::InvalidResponse)?;
+
+    let (tx, max_fee, fee_source) = match envelope {
+        TransactionEnvelope::TxV0(env) => {
+            let fee = i64::from(env.tx.fee);
+            (xdr::Transaction::from(&env.tx), fee, None)
+        }
+        TransactionEnvelope::Tx(env) => {
+            let fee = i64::from(env.tx.fee);
+            (env.tx, fee, None)
+        }
+        TransactionEnvelope::TxFeeBump(env) => {
+            let xdr::FeeBumpTransactionInnerTx::Tx(inner) = env.tx.inner_tx;
+            (
+                inner.tx,
+                env.tx.fee,
+                Some(muxed_to_account(&env.tx.fee_source)),
+            )
+        }
+    };
+
+    let successful = rpc_tx.status == "SUCCESS";
+    let ledger = rpc_tx.ledger;
+    let created_at = rpc_tx.created_at.as_ref().and_then(unix_to_iso);
+    let tx_source = muxed_to_account(&tx.source_account);
+
+    let memo_bytes = match &tx.memo {
+        xdr::Memo::Text(text) => Some(STANDARD.encode(text.as_slice())),
+        _ => None,
+    };
+    let (memo_type, memo) = match &tx.memo {
+        xdr::Memo::None => ("none", None),
+        xdr::Memo::Text(text) => ("text", Some(text.to_utf8_string_lossy())),
+        xdr::Memo::Id(id) => ("id", Some(id.to_string())),
+        xdr::Memo::Hash(hash) => ("hash", Some(to_hex(&hash.0))),
+        xdr::Memo::Return(hash) => ("return", Some(to_hex(&hash.0))),
+    };
+
+    let time_bounds = match &tx.cond {
+        xdr::Preconditions::None => None,
+        xdr::Preconditions::Time(bounds) => Some(bounds.clone()),
+        xdr::Preconditions::V2(v2) => v2.time_bounds.clone(),
+    };
+    let valid_after = time_bounds
+        .as_ref()
+        .and_then(|b| unix_to_iso(&json!(b.min_time.0)));
+    let valid_before = time_bounds
+        .as_ref()
+        .filter(|b| b.max_time.0 > 0)
+        .and_then(|b| unix_to_iso(&json!(b.max_time.0)));
+
+    let operations = tx
+        .operations
+        .iter()
+        .enumerate()
+        .map(|(index, op)| {
+            let source = op
+                .source_account
+                .as_ref()
+                .map(muxed_to_account)
+                .unwrap_or_else(|| tx_source.clone());
+            let mut record = operation_record(&op.body, source);
+            record.id = operation_id(ledger, rpc_tx.application_order, index);
+            record.transaction_hash = hash.to_string();
+            record.created_at = created_at.clone();
+            record
+        })
+        .collect();
+
+    let record = HorizonTransaction {
+        hash: hash.to_string(),
+        successful,
+        fee_charged: result.map_or(0, |result| result.fee_charged).to_string(),
+        fee_account: Some(fee_source.unwrap_or_else(|| tx_source.clone())),
+        source_account: Some(tx_source),
+        source_account_sequence: Some(tx.seq_num.0.to_string()),
+        memo_type: Some(memo_type.to_string()),
+        memo,
+        memo_bytes,
+        created_at,
+        ledger,
+        max_fee: Some(max_fee.to_string()),
+        valid_after,
+        valid_before,
+        result_codes: result
+            .filter(|_| !successful)
+            .map(|result| result_codes(&result.result)),
+        result_meta_xdr: rpc_tx.result_meta_xdr,
+        envelope_xdr: rpc_tx.envelope_xdr,
+        extras: None,
+    };
+
+    Ok((record, operations))
+}
+
+/// Horizon's operation id: the ledger, the transaction's position in it, and
+/// the operation's position in the transaction, packed into one integer.
+fn operation_id(ledger: Option<u64>, application_order: Option<u32>, index: usize) -> String {
+    match (ledger, application_order) {
+        (Some(ledger), Some(order)) => {
+            ((ledger << 32) | (u64::from(order) << 12) | (index as u64 + 1)).to_string()
+        }
+        _ => (index + 1).to_string(),
+    }
+}
+
+fn operation_record(body: &OperationBody, source: String) -> HorizonOperation {
+    let mut op = HorizonOperation {
+        operation_type: snake_case(body.name()),
+        source_account: Some(source.clone()),
+        ..Default::default()
+    };
+
+    match body {
+        OperationBody::CreateAccount(create) => {
+            op.funder = Some(source);
+            op.account = Some(create.destination.to_string());
+            op.starting_balance = Some(format_amount(create.starting_balance));
+        }
+        OperationBody::Payment(payment) => {
+            op.from = Some(source);
+            op.to = Some(muxed_to_account(&payment.destination));
+            op.amount = Some(format_amount(payment.amount));
+            (op.asset_type, op.asset_code, op.asset_issuer) = asset_fields(&payment.asset);
+        }
+        // RPC returns the envelope, so path payments report the submitted
+        // limits (send_max, dest_min) rather than the amounts that moved.
+        OperationBody::PathPaymentStrictReceive(path) => {
+            op.from = Some(source);
+            op.to = Some(muxed_to_account(&path.destination));
+            op.amount = Some(format_amount(path.dest_amount));
+            op.source_amount = Some(format_amount(path.send_max));
+            (op.asset_type, op.asset_code, op.asset_issuer) = asset_fields(&path.dest_asset);
+            (
+                op.source_asset_type,
+                op.source_asset_code,
+                op.source_asset_issuer,
+            ) = asset_fields(&path.send_asset);
+        }
+        OperationBody::PathPaymentStrictSend(path) => {
+            op.from = Some(source);
+            op.to = Some(muxed_to_account(&path.destination));
+            op.amount = Some(format_amount(path.dest_min));
+            op.source_amount = Some(format_amount(path.send_amount));
+            (op.asset_type, op.asset_code, op.asset_issuer) = asset_fields(&path.dest_asset);
+            (
+                op.source_asset_type,
+                op.source_asset_code,
+                op.source_asset_issuer,
+            ) = asset_fields(&path.send_asset);
+        }
+        OperationBody::ManageSellOffer(offer) => {
+            set_offer_fields(&mut op, &offer.selling, &offer.buying, &offer.price);
+            op.amount = Some(format_amount(offer.amount));
+            op.offer_id = Some(offer.offer_id.to_string());
+        }
+        OperationBody::CreatePassiveSellOffer(offer) => {
+            set_offer_fields(&mut op, &offer.selling, &offer.buying, &offer.price);
+            op.amount = Some(format_amount(offer.amount));
+        }
+        OperationBody::ManageBuyOffer(offer) => {
+            set_offer_fields(&mut op, &offer.selling, &offer.buying, &offer.price);
+            op.amount = Some(format_amount(offer.buy_amount));
+            op.offer_id = Some(offer.offer_id.to_string());
+        }
+        OperationBody::SetOptions(options) => {
+            op.inflation_dest = options.inflation_dest.as_ref().map(|a| a.to_string());
+            op.set_flags = options.set_flags.map(|f| vec![f]);
+            op.clear_flags = options.clear_flags.map(|f| vec![f]);
+            op.master_key_weight = options.master_weight;
+            op.low_threshold = options.low_threshold;
+            op.med_threshold = options.med_threshold;
+            op.high_threshold = options.high_threshold;
+            op.home_domain = options
+                .home_domain
+                .as_ref()
+                .map(|d| d.to_utf8_string_lossy());
+            if let Some(signer) = &options.signer {
+                op.signer_key = Some(signer.key.to_string());
+                op.signer_weight = Some(signer.weight);
+            }
+        }
+        OperationBody::ChangeTrust(trust) => {
+            op.trustor = Some(source);
+            op.limit = Some(format_amount(trust.limit));
+            let (asset_type, code, issuer) = match &trust.line {
+                ChangeTrustAsset::Native => asset_fields(&Asset::Native),
+                ChangeTrustAsset::CreditAlphanum4(a) => {
+                    asset_fields(&Asset::CreditAlphanum4(a.clone()))
+                }
+                ChangeTrustAsset::CreditAlphanum12(a) => {
+                    asset_fields(&Asset::CreditAlphanum12(a.clone()))
+                }
+                ChangeTrustAsset::PoolShare(_) => {
+                    (Some("liquidity_pool_shares".to_string()), None, None)
+                }
+            };
+            (op.asset_type, op.asset_code, op.asset_issuer) = (asset_type, code, issuer);
+        }
+        OperationBody::Clawback(clawback) => {
+            op.from = Some(muxed_to_account(&clawback.from));
+            op.amount = Some(format_amount(clawback.amount));
+            (op.asset_type, op.asset_code, op.asset_issuer) = asset_fields(&clawback.asset);
+        }
+        OperationBody::ClawbackClaimableBalance(clawback) => {
+            op.balance_id = Some(balance_id_hex(&clawback.balance_id));
+        }
+        OperationBody::AccountMerge(destination) => {
+            op.account = Some(source);
+            op.into = Some(muxed_to_account(destination));
+        }
+        _ => {}
+    }
+
+    op
+}
+
+fn set_offer_fields(
+    op: &mut HorizonOperation,
+    selling: &Asset,
+    buying: &Asset,
+    price: &xdr::Price,
+) {
+    (
+        op.selling_asset_type,
+        op.selling_asset_code,
+        op.selling_asset_issuer,
+    ) = asset_fields(selling);
+    (
+        op.buying_asset_type,
+        op.buying_asset_code,
+        op.buying_asset_issuer,
+    ) = asset_fields(buying);
+    if price.d != 0 {
+        op.price = Some(format!("{:.7}", f64::from(price.n) / f64::from(price.d)));
+    }
+}
+
+/// Horizon's `(asset_type, asset_code, asset_issuer)` for an asset.
+fn asset_fields(asset: &Asset) -> (Option<String>, Option<String>, Option<String>) {
+    match asset {
+        Asset::Native => (Some("native".to_string()), None, None),
+        Asset::CreditAlphanum4(a) => (
+            Some("credit_alphanum4".to_string()),
+            Some(a.asset_code.to_string()),
+            Some(a.issuer.to_string()),
+        ),
+        Asset::CreditAlphanum12(a) => (
+            Some("credit_alphanum12".to_string()),
+            Some(a.asset_code.to_string()),
+            Some(a.issuer.to_string()),
+        ),
+    }
+}
+
+/// Horizon reports the underlying G... account for muxed accounts.
+fn muxed_to_account(account: &MuxedAccount) -> String {
+    account.clone().account_id().to_string()
+}
+
+/// Horizon's hex claimable balance id: the 4-byte type followed by the hash.
+fn balance_id_hex(id: &ClaimableBalanceId) -> String {
+    let ClaimableBalanceId::ClaimableBalanceIdTypeV0(hash) = id;
+    format!("00000000{}", to_hex(&hash.0))
+}
+
+/// Result codes as Horizon names them, e.g. "tx_failed" and "op_underfunded".
+fn result_codes(result: &TransactionResultResult) -> HorizonResultCodes {
+    let transaction = Some(snake_case(result.name()));
+    let operations = match result {
+        TransactionResultResult::TxSuccess(ops) | TransactionResultResult::TxFailed(ops) => {
+            ops.iter().map(operation_code).collect()
+        }
+        TransactionResultResult::TxFeeBumpInnerSuccess(pair)
+        | TransactionResultResult::TxFeeBumpInnerFailed(pair) => match &pair.result.result {
+            InnerTransactionResultResult::TxSuccess(ops)
+            | InnerTransactionResultResult::TxFailed(ops) => {
+                ops.iter().map(operation_code).collect()
+            }
+            _ => vec![],
+        },
+        _ => vec![],
+    };
+    HorizonResultCodes {
+        transaction,
+        operations,
+    }
+}
+
+fn operation_code(result: &OperationResult) -> String {
+    let OperationResult::OpInner(inner) = result else {
+        return snake_case(result.name());
+    };
+    let code = match inner {
+        OperationResultTr::CreateAccount(r) => r.name(),
+        OperationResultTr::Payment(r) => r.name(),
+        OperationResultTr::PathPaymentStrictReceive(r) => r.name(),
+        OperationResultTr::ManageSellOffer(r) => r.name(),
+        OperationResultTr::CreatePassiveSellOffer(r) => r.name(),
+        OperationResultTr::SetOptions(r) => r.name(),
+        OperationResultTr::ChangeTrust(r) => r.name(),
+        OperationResultTr::AllowTrust(r) => r.name(),
+        OperationResultTr::AccountMerge(r) => r.name(),
+        OperationResultTr::Inflation(r) => r.name(),
+        OperationResultTr::ManageData(r) => r.name(),
+        OperationResultTr::BumpSequence(r) => r.name(),
+        OperationResultTr::ManageBuyOffer(r) => r.name(),
+        OperationResultTr::PathPaymentStrictSend(r) => r.name(),
+        OperationResultTr::CreateClaimableBalance(r) => r.name(),
+        OperationResultTr::ClaimClaimableBalance(r) => r.name(),
+        OperationResultTr::BeginSponsoringFutureReserves(r) => r.name(),
+        OperationResultTr::EndSponsoringFutureReserves(r) => r.name(),
+        OperationResultTr::RevokeSponsorship(r) => r.name(),
+        OperationResultTr::Clawback(r) => r.name(),
+        OperationResultTr::ClawbackClaimableBalance(r) => r.name(),
+        OperationResultTr::SetTrustLineFlags(r) => r.name(),
+        OperationResultTr::LiquidityPoolDeposit(r) => r.name(),
+        OperationResultTr::LiquidityPoolWithdraw(r) => r.name(),
+        OperationResultTr::InvokeHostFunction(r) => r.name(),
+        OperationResultTr::ExtendFootprintTtl(r) => r.name(),
+        OperationResultTr::RestoreFootprint(r) => r.name(),
+    };
+    format!("op_{}", snake_case(code))
+}
+
+/// "PathPaymentStrictSend" → "path_payment_strict_send".
+fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Stroops as Horizon's 7-decimal amount string: 1000000000 → "100.0000000".
+fn format_amount(stroops: i64) -> String {
+    Amount::from(stroops).to_string()
+}
+
+/// Unix seconds (as a number or numeric string) → "2024-01-15T14:32:00Z".
+fn unix_to_iso(value: &Value) -> Option<String> {
+    let seconds = match value {
+        Value::Number(n) => n.as_i64()?,
+        Value::String(s) => s.parse().ok()?,
+        _ => return None,
+    };
+    chrono::DateTime::from_timestamp(seconds, 0).map(|t| t.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use stellar_xdr::curr::{
+        AccountId, Limits, Operation, PaymentOp, PublicKey, SequenceNumber, TransactionExt,
+        TransactionResultExt, TransactionV1Envelope, Uint256, WriteXdr,
+    };
+
+    pub(crate) const HASH: &str =
+        "b9d0b2292c4e09e8eb22d036171491e87b8d2086bf8b265874c8d182cb9c9020";
+
+    fn account(byte: u8) -> MuxedAccount {
+        MuxedAccount::Ed25519(Uint256([byte; 32]))
+    }
+
+    /// A base64 envelope holding one native payment of `stroops`.
+    pub(crate) fn payment_envelope(stroops: i64) -> String {
+        let tx = xdr::Transaction {
+            source_account: account(1),
+            fee: 100,
+            seq_num: SequenceNumber(1),
+            cond: xdr::Preconditions::None,
+            memo: xdr::Memo::Text("rent".try_into().unwrap()),
+            operations: vec![Operation {
+                source_account: None,
+                body: OperationBody::Payment(PaymentOp {
+                    destination: account(2),
+                    asset: Asset::Native,
+                    amount: stroops,
+                }),
+            }]
+            .try_into()
+            .unwrap(),
+            ext: TransactionExt::V0,
+        };
+        TransactionEnvelope::Tx(TransactionV1Envelope {
+            tx,
+            signatures: Default::default(),
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
+
+    /// A base64 result for a one-operation transaction.
+    pub(crate) fn payment_result(success: bool) -> String {
+        let op = if success {
+            xdr::PaymentResult::Success
+        } else {
+            xdr::PaymentResult::Underfunded
+        };
+        let ops = vec![OperationResult::OpInner(OperationResultTr::Payment(op))]
+            .try_into()
+            .unwrap();
+        TransactionResult {
+            fee_charged: 100,
+            result: if success {
+                TransactionResultResult::TxSuccess(ops)
+            } else {
+                TransactionResultResult::TxFailed(ops)
+            },
+            ext: TransactionResultExt::V0,
+        }
+        .to_xdr_base64(Limits::none())
+        .unwrap()
+    }
+
+    fn rpc_tx(success: bool) -> RpcTransaction {
+        RpcTransaction {
+            status: if success { "SUCCESS" } else { "FAILED" }.to_string(),
+            ledger: Some(1000),
+            created_at: Some(json!("1705329120")),
+            application_order: Some(2),
+            envelope_xdr: Some(payment_envelope(1_000_000_000)),
+            result_xdr: Some(payment_result(success)),
+            result_meta_xdr: None,
+        }
+    }
+
+    #[test]
+    fn test_payment_maps_to_horizon_shape() {
+        let (tx, ops) = to_horizon_records(HASH, rpc_tx(true)).unwrap();
+
+        assert!(tx.successful);
+        assert_eq!(tx.fee_charged, "100");
+        assert_eq!(tx.memo_type.as_deref(), Some("text"));
+        assert_eq!(tx.memo.as_deref(), Some("rent"));
+        assert_eq!(tx.memo_bytes.as_deref(), Some("cmVudA=="));
+        assert_eq!(tx.created_at.as_deref(), Some("2024-01-15T14:32:00Z"));
+        assert!(tx.result_codes.is_none());
+
+        assert_eq!(ops.len(), 1);
+        let op = &ops[0];
+        assert_eq!(op.operation_type, "payment");
+        assert_eq!(op.id, ((1000u64 << 32) | (2 << 12) | 1).to_string());
+        assert_eq!(op.amount.as_deref(), Some("100.0000000"));
+        assert_eq!(op.asset_type.as_deref(), Some("native"));
+        let from = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([1; 32]))).to_string();
+        assert_eq!(op.from.as_deref(), Some(from.as_str()));
+        assert!(op.to.as_deref().unwrap().starts_with('G'));
+    }
+
+    #[test]
+    fn test_failed_transaction_result_codes() {
+        let (tx, _) = to_horizon_records(HASH, rpc_tx(false)).unwrap();
+        let codes = tx.result_codes.unwrap();
+        assert_eq!(codes.transaction.as_deref(), Some("tx_failed"));
+        assert_eq!(codes.operations, vec!["op_underfunded"]);
+    }
+
+    #[test]
+    fn test_unsubmitted_envelope_records() {
+        let (tx, ops) = envelope_records(HASH, &payment_envelope(1_000_000_000)).unwrap();
+        let from = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([1; 32]))).to_string();
+        let to = AccountId(PublicKey::PublicKeyTypeEd25519(Uint256([2; 32]))).to_string();
+        assert_eq!(tx.fee_charged, "0");
+        assert_eq!(tx.source_account.as_deref(), Some(from.as_str()));
+        assert!(tx.result_codes.is_none());
+        assert_eq!(ops[0].to.as_deref(), Some(to.as_str()));
+    }
+
+    #[test]
+    fn test_missing_envelope_is_invalid() {
+        let tx = RpcTransaction {
+            envelope_xdr: None,
+            ..rpc_tx(true)
+        };
+        assert!(matches!(
+            to_horizon_records(HASH, tx),
+            Err(HorizonError::InvalidResponse)
+        ));
+    }
+
+    #[test]
+    fn test_helpers() {
+        assert_eq!(snake_case("ExtendFootprintTtl"), "extend_footprint_ttl");
+        assert_eq!(format_amount(1), "0.0000001");
+        assert_eq!(format_amount(25_000_000), "2.5000000");
+        assert_eq!(
+            unix_to_iso(&json!(0)).as_deref(),
+            Some("1970-01-01T00:00:00Z")
+        );
+        assert_eq!(operation_id(None, None, 0), "1");
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod tests {
+    use super::super::provider::LedgerDataProvider;
+    use super::super::rpc::tests::{HASH, payment_envelope, payment_result};
+    use super::super::rpc::*;
+    use crate::errors::HorizonError;
+    use httpmock::prelude::*;
+
+    #[tokio::test]
+    async fn get_transaction_maps_to_domain() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "getTransaction"}"#);
+            then.status(200).json_body(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "status": "SUCCESS",
+                    "ledger": 1000,
+                    "createdAt": "1705329120",
+                    "applicationOrder": 1,
+                    "envelopeXdr": payment_envelope(50_000_000),
+                    "resultXdr": payment_result(true),
+                    "resultMetaXdr": null
+                }
+            }));
+        });
+
+        let client = RpcClient::new(server.base_url());
+        let fetched = LedgerDataProvider::fetch_transaction(&client, HASH)
+            .await
+            .unwrap();
+
+        assert_eq!(fetched.ledger, Some(1000));
+        assert_eq!(fetched.created_at.as_deref(), Some("2024-01-15T14:32:00Z"));
+        assert!(fetched.transaction.successful);
+        assert_eq!(fetched.transaction.fee_charged, 100);
+        let payments = fetched.transaction.payment_operations();
+        assert_eq!(payments.len(), 1);
+        assert_eq!(payments[0].amount.to_string(), "5.0000000");
+    }
+
+    #[tokio::test]
+    async fn get_transaction_not_found() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST).path("/");
+            then.status(200).json_body(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": { "status": "NOT_FOUND", "latestLedger": 1200 }
+            }));
+        });
+
+        let client = RpcClient::new(server.base_url());
+        let err = client.get_transaction(HASH).await.unwrap_err();
+
+        assert!(matches!(err, HorizonError::TransactionNotFound));
+    }
+
+    #[tokio::test]
+    async fn rpc_error_is_invalid_response() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST).path("/");
+            then.status(200).json_body(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "error": { "code": -32602, "message": "invalid parameters" }
+            }));
+        });
+
+        let client = RpcClient::new(server.base_url());
+        let err = client.get_transaction(HASH).await.unwrap_err();
+
+        assert!(matches!(err, HorizonError::InvalidResponse));
+    }
+
+    #[tokio::test]
+    async fn get_fee_stats_uses_inclusion_fees() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "getFeeStats"}"#);
+            then.status(200).json_body(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "inclusionFee": {
+                        "max": "5000", "min": "100", "mode": "100", "p90": "250",
+                        "transactionCount": "10", "ledgerCount": 50
+                    },
+                    "latestLedger": 1200
+                }
+            }));
+        });
+
+        let client = RpcClient::new(server.base_url());
+        let stats = client.get_fee_stats().await.unwrap();
+
+        assert_eq!(stats.base_fee, 100);
+        assert_eq!(stats.max_fee, 5000);
+        assert_eq!(stats.p90_fee, 250);
+    }
+
+    #[tokio::test]
+    async fn get_events_decodes_topics() {
+        let server = MockServer::start();
+
+        // "transfer" symbol and an i128 amount of 10.
+        let topic = "AAAADwAAAAh0cmFuc2Zlcg==";
+        let value = "AAAACgAAAAAAAAAAAAAAAAAAAAo=";
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "getEvents", "params": {"startLedger": 900}}"#);
+            then.status(200).json_body(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "events": [{
+                        "type": "contract",
+                        "id": "0004294967296-0000000001",
+                        "ledger": 1000,
+                        "contractId": "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC",
+                        "topic": [topic],
+                        "value": value,
+                        "txHash": HASH
+                    }],
+                    "latestLedger": 1200,
+                    "cursor": "0004294967296-0000000001"
+                }
+            }));
+        });
+
+        let client = RpcClient::new(server.base_url());
+        let page = client.get_events(Some(900), &[], 10, None).await.unwrap();
+
+        assert_eq!(page.latest_ledger, 1200);
+        assert_eq!(page.events.len(), 1);
+        let event = &page.events[0].event;
+        assert_eq!(event.topics[0].as_symbol(), Some("transfer"));
+        assert_eq!(
+            event.data,
+            crate::models::contract_event::EventValue::Amount(10)
+        );
+    }
+
+    #[tokio::test]
+    async fn simulate_transaction_reports_errors() {
+        let server = MockServer::start();
+
+        server.mock(|when, then| {
+            when.method(POST)
+                .path("/")
+                .json_body_partial(r#"{"method": "simulateTransaction"}"#);
+            then.status(200).json_body(serde_json::json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "result": {
+                    "latestLedger": 1200,
+                    "error": "HostError: Error(Contract, #1)"
+                }
+            }));
+        });
+
+        let client = RpcClient::new(server.base_url());
+        let simulation = client
+            .simulate_transaction(&payment_envelope(1))
+            .await
+            .unwrap();
+
+        assert_eq!(simulation.latest_ledger, 1200);
+        assert!(simulation.error.unwrap().contains("HostError"));
+        assert!(simulation.results.is_empty());
+    }
+}
@@ -0,0 +1,46 @@
+//! Read what `POST /check` needs to know about a payment's destination and
+//! asset: both accounts from Horizon, side by side, the spam blocklist, and
+//! the memo registry.
+
+use crate::errors::HorizonError;
+use crate::explain::send_check::{PlannedPayment, SendContext};
+use crate::services::blocklist;
+use crate::services::horizon_api::HorizonApi;
+use crate::services::memo_registry::memo_required_by;
+
+/// The destination account, or `None` when it does not exist, and the
+/// asset's issuer when it can be read. Only a failure to read the
+/// destination is an error.
+pub async fn send_context<H: HorizonApi>(
+    client: &H,
+    payment: &PlannedPayment,
+) -> Result<SendContext, HorizonError> {
+    let issuer = async {
+        match &payment.asset {
+            Some((_, issuer)) => client.fetch_account(issuer).await.ok(),
+            None => None,
+        }
+    };
+    let (destination, issuer) = tokio::join!(client.fetch_account(&payment.destination), issuer);
+    let destination = match destination {
+        Ok(account) => Some(account),
+        Err(HorizonError::AccountNotFound) => None,
+        Err(err) => return Err(err),
+    };
+
+    let list = blocklist::current();
+    let listing = |key: &str| {
+        list.as_ref()
+            .and_then(|list| list.get(key).map(|reason| reason.map(str::to_string)))
+    };
+    Ok(SendContext {
+        destination,
+        issuer,
+        destination_listing: listing(&payment.destination),
+        asset_listing: payment
+            .asset
+            .as_ref()
+            .and_then(|(code, issuer)| listing(&format!("{code}:{issuer}"))),
+        memo_required_by: memo_required_by(&payment.destination).map(str::to_string),
+    })
+}
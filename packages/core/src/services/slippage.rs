@@ -0,0 +1,127 @@
+//! Work out how the path payments in a transaction converted compared with
+//! the market, from their trades and the prices just before the ledger
+//! closed.
+
+use futures_util::future::join_all;
+use std::sync::Arc;
+use tracing::warn;
+
+use crate::explain::slippage::{PathPaymentSlippage, canonical_asset, explain_slippage, path_hops};
+use crate::models::operation::{Operation, PathPaymentOperation};
+use crate::models::transaction::Transaction;
+use crate::services::horizon_api::HorizonApi;
+
+/// Path payments analyzed per explanation; each costs an effects fetch and
+/// a price fetch per hop.
+pub const MAX_ANALYZED_PATH_PAYMENTS: usize = 3;
+
+/// Slippage of the first [`MAX_ANALYZED_PATH_PAYMENTS`] converting path
+/// payments in `transaction`, whose ledger closed at `closed_at` (RFC 3339).
+/// Payments whose trades or market prices cannot be fetched are left out.
+pub async fn analyze_path_payments<H: HorizonApi>(
+    client: Arc<H>,
+    transaction: &Transaction,
+    closed_at: &str,
+) -> Vec<PathPaymentSlippage> {
+    if !transaction.successful {
+        return Vec::new();
+    }
+    let payments = transaction
+        .operations
+        .iter()
+        .enumerate()
+        .filter_map(|(index, op)| match op {
+            Operation::PathPayment(p) if p.send_asset != p.dest_asset => Some((index, p)),
+            _ => None,
+        })
+        .take(MAX_ANALYZED_PATH_PAYMENTS);
+    join_all(payments.map(|(index, op)| analyze(client.as_ref(), index, op, closed_at)))
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+async fn analyze<H: HorizonApi>(
+    client: &H,
+    index: usize,
+    op: &PathPaymentOperation,
+    closed_at: &str,
+) -> Option<PathPaymentSlippage> {
+    let send_asset = canonical_asset(&op.send_asset)?;
+    let dest_asset = canonical_asset(&op.dest_asset)?;
+    let payer = op.source_account.as_deref()?;
+    let fills = client
+        .fetch_operation_trades(&op.id, payer)
+        .await
+        .inspect_err(
+            |err| warn!(operation = %op.id, error = ?err, "path_payment_trades_fetch_failed"),
+        )
+        .ok()?;
+    let hops = path_hops(&fills, &send_asset, &dest_asset)?;
+    let market_rates: Option<Vec<f64>> = join_all(
+        hops.iter()
+            .map(|hop| client.fetch_market_price(&hop.sold_asset, &hop.bought_asset, closed_at)),
+    )
+    .await
+    .into_iter()
+    .collect();
+    explain_slippage(index, op, &hops, &market_rates?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::operation::PathPaymentType;
+    use crate::models::trade::TradeFill;
+    use crate::services::horizon_api::FakeHorizon;
+
+    const USDC: &str = "USDC:GISSUER";
+
+    fn path_payment(id: &str, dest_asset: &str) -> Operation {
+        Operation::PathPayment(PathPaymentOperation {
+            id: id.to_string(),
+            source_account: Some("GPAYER".to_string()),
+            destination: "GDEST".to_string(),
+            send_asset: "XLM (native)".to_string(),
+            send_amount: "100".parse().unwrap(),
+            dest_asset: dest_asset.to_string(),
+            dest_amount: "11.2".parse().unwrap(),
+            path: vec![],
+            payment_type: PathPaymentType::StrictSend,
+        })
+    }
+
+    #[tokio::test]
+    async fn test_analyzes_converting_path_payments() {
+        let horizon = FakeHorizon::new()
+            .with_trades(
+                "op1",
+                vec![TradeFill {
+                    sold_asset: "native".to_string(),
+                    sold_amount: "100".parse().unwrap(),
+                    bought_asset: USDC.to_string(),
+                    bought_amount: "11.2".parse().unwrap(),
+                }],
+            )
+            .with_market_price("native", USDC, 0.1129);
+        let tx = Transaction::new(
+            "hash".to_string(),
+            true,
+            100,
+            vec![
+                path_payment("op0", "XLM (native)"),
+                path_payment("op1", "USDC (GISSUER)"),
+                // No trades recorded, so no slippage.
+                path_payment("op2", "USDC (GISSUER)"),
+            ],
+            None,
+            None,
+        );
+
+        let slippages = analyze_path_payments(Arc::new(horizon), &tx, "2026-01-02T10:15:00Z").await;
+        assert_eq!(slippages.len(), 1);
+        assert_eq!(slippages[0].operation_index, 1);
+        assert!(slippages[0].summary.contains("about 0.8% worse"));
+    }
+}
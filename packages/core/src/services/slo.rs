@@ -0,0 +1,416 @@
+//! Service level objectives per route.
+//!
+//! When enabled, every routed request counts toward two objectives for its
+//! route pattern, e.g. `/tx/:hash`:
+//! - availability: the share of requests that do not fail with a 5xx;
+//! - latency: the share answered within the latency threshold.
+//!
+//! Counts are kept in memory in one-minute buckets covering the SLO window,
+//! so they start over on restart. `GET /admin/slo` reports, per route, how
+//! much of each objective's error budget is left and how fast it is being
+//! spent. A burn rate of 1 spends the budget exactly over the window. A
+//! route burns fast when both the last hour and the last 5 minutes burn at
+//! 14.4 or more, and slowly when the last 6 hours and 30 minutes burn at 6
+//! or more. Burning routes are logged as `slo_burn_rate_alert` once a
+//! minute for as long as it lasts.
+//!
+//! Configuration (environment):
+//! - `SLO_ENABLED`: `true` or `1` to track objectives.
+//! - `SLO_AVAILABILITY_TARGET`: share of requests that must succeed
+//!   (default 0.995).
+//! - `SLO_LATENCY_MS`: latency threshold (default 1000).
+//! - `SLO_LATENCY_TARGET`: share of requests that must be within the
+//!   threshold (default 0.99).
+//! - `SLO_WINDOW_HOURS`: window the error budget covers (default 168).
+
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::warn;
+use utoipa::ToSchema;
+
+/// Windows burn rates are reported over, in minutes.
+const BURN_WINDOWS: [(&str, u64); 4] = [("5m", 5), ("30m", 30), ("1h", 60), ("6h", 360)];
+
+/// Burn rate over both the 1h and 5m windows that counts as a fast burn.
+const FAST_BURN: f64 = 14.4;
+
+/// Burn rate over both the 6h and 30m windows that counts as a slow burn.
+const SLOW_BURN: f64 = 6.0;
+
+/// How often burning routes are logged.
+const ALERT_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SloConfig {
+    pub availability_target: f64,
+    pub latency_threshold: Duration,
+    pub latency_target: f64,
+    pub window: Duration,
+}
+
+impl Default for SloConfig {
+    fn default() -> Self {
+        Self {
+            availability_target: 0.995,
+            latency_threshold: Duration::from_millis(1000),
+            latency_target: 0.99,
+            window: Duration::from_secs(168 * 60 * 60),
+        }
+    }
+}
+
+impl SloConfig {
+    /// Read the SLO settings, or `None` when tracking is not enabled.
+    /// Targets outside (0, 1) fall back to their defaults.
+    pub fn from_env() -> Option<Self> {
+        let enabled = env::var("SLO_ENABLED")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let defaults = Self::default();
+        let target = |name: &str, default: f64| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<f64>().ok())
+                .filter(|t| *t > 0.0 && *t < 1.0)
+                .unwrap_or(default)
+        };
+        let positive = |name: &str| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+        };
+        Some(Self {
+            availability_target: target("SLO_AVAILABILITY_TARGET", defaults.availability_target),
+            latency_threshold: positive("SLO_LATENCY_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(defaults.latency_threshold),
+            latency_target: target("SLO_LATENCY_TARGET", defaults.latency_target),
+            window: positive("SLO_WINDOW_HOURS")
+                .map(|hours| Duration::from_secs(hours * 60 * 60))
+                .unwrap_or(defaults.window),
+        })
+    }
+
+    fn window_minutes(&self) -> u64 {
+        (self.window.as_secs() / 60).max(1)
+    }
+}
+
+/// Requests to one route in one minute.
+#[derive(Debug, Clone, Copy, Default)]
+struct Bucket {
+    minute: u64,
+    requests: u64,
+    errors: u64,
+    slow: u64,
+}
+
+impl Bucket {
+    fn add(mut self, other: &Bucket) -> Self {
+        self.requests += other.requests;
+        self.errors += other.errors;
+        self.slow += other.slow;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BurnAlert {
+    /// The budget will be gone within days at this rate.
+    Slow,
+    /// The budget will be gone within hours at this rate.
+    Fast,
+}
+
+/// How fast each objective's budget is being spent over one window.
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct BurnRate {
+    /// e.g. "1h"
+    pub window: String,
+    pub availability: f64,
+    pub latency: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct RouteSlo {
+    /// Route pattern, e.g. "/tx/:hash".
+    pub route: String,
+    /// Requests in the SLO window.
+    pub requests: u64,
+    /// Requests that failed with a 5xx.
+    pub errors: u64,
+    /// Requests slower than the latency threshold.
+    pub slow: u64,
+    /// Share of requests without a 5xx.
+    pub availability: f64,
+    /// Share of requests within the latency threshold.
+    pub latency_attainment: f64,
+    /// Share of the availability error budget left; negative once spent.
+    pub availability_budget_remaining: f64,
+    /// Share of the latency error budget left; negative once spent.
+    pub latency_budget_remaining: f64,
+    pub burn_rates: Vec<BurnRate>,
+    /// The faster burn of the two objectives, if either is burning.
+    pub alert: Option<BurnAlert>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, ToSchema)]
+pub struct SloReport {
+    pub availability_target: f64,
+    pub latency_threshold_ms: u64,
+    pub latency_target: f64,
+    pub window_hours: u64,
+    /// Routes with requests in the window, by route.
+    pub routes: Vec<RouteSlo>,
+}
+
+/// Request counts per route, shared by the middleware and the admin endpoint.
+#[derive(Debug)]
+pub struct SloTracker {
+    config: SloConfig,
+    routes: Mutex<HashMap<String, VecDeque<Bucket>>>,
+}
+
+impl SloTracker {
+    pub fn new(config: SloConfig) -> Self {
+        Self {
+            config,
+            routes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a request to `route` that answered `status` after `elapsed`.
+    pub fn record(&self, route: &str, status: u16, elapsed: Duration) {
+        self.record_at(route, status, elapsed, current_minute());
+    }
+
+    pub fn report(&self) -> SloReport {
+        self.report_at(current_minute())
+    }
+
+    fn record_at(&self, route: &str, status: u16, elapsed: Duration, minute: u64) {
+        let window = self.config.window_minutes();
+        let mut routes = self.lock();
+        let buckets = routes.entry(route.to_string()).or_default();
+        while buckets
+            .front()
+            .is_some_and(|bucket| bucket.minute + window <= minute)
+        {
+            buckets.pop_front();
+        }
+        if buckets.back().is_none_or(|bucket| bucket.minute != minute) {
+            buckets.push_back(Bucket {
+                minute,
+                ..Default::default()
+            });
+        }
+        if let Some(bucket) = buckets.back_mut() {
+            bucket.requests += 1;
+            bucket.errors += u64::from(status >= 500);
+            bucket.slow += u64::from(elapsed > self.config.latency_threshold);
+        }
+    }
+
+    fn report_at(&self, minute: u64) -> SloReport {
+        let routes = self.lock();
+        let mut report: Vec<RouteSlo> = routes
+            .iter()
+            .filter_map(|(route, buckets)| self.route_slo(route, buckets, minute))
+            .collect();
+        report.sort_by(|a, b| a.route.cmp(&b.route));
+        SloReport {
+            availability_target: self.config.availability_target,
+            latency_threshold_ms: self.config.latency_threshold.as_millis() as u64,
+            latency_target: self.config.latency_target,
+            window_hours: self.config.window.as_secs() / 3600,
+            routes: report,
+        }
+    }
+
+    fn route_slo(&self, route: &str, buckets: &VecDeque<Bucket>, minute: u64) -> Option<RouteSlo> {
+        let within = |minutes: u64| {
+            buckets
+                .iter()
+                .filter(|bucket| bucket.minute + minutes > minute)
+                .fold(Bucket::default(), |sum, bucket| sum.add(bucket))
+        };
+        let total = within(self.config.window_minutes());
+        if total.requests == 0 {
+            return None;
+        }
+
+        let availability_budget = 1.0 - self.config.availability_target;
+        let latency_budget = 1.0 - self.config.latency_target;
+        let burn_rates: Vec<BurnRate> = BURN_WINDOWS
+            .iter()
+            .map(|(name, minutes)| {
+                let counts = within(*minutes);
+                BurnRate {
+                    window: name.to_string(),
+                    availability: round(
+                        ratio(counts.errors, counts.requests) / availability_budget,
+                    ),
+                    latency: round(ratio(counts.slow, counts.requests) / latency_budget),
+                }
+            })
+            .collect();
+
+        Some(RouteSlo {
+            route: route.to_string(),
+            requests: total.requests,
+            errors: total.errors,
+            slow: total.slow,
+            availability: round(1.0 - ratio(total.errors, total.requests)),
+            latency_attainment: round(1.0 - ratio(total.slow, total.requests)),
+            availability_budget_remaining: round(
+                1.0 - ratio(total.errors, total.requests) / availability_budget,
+            ),
+            latency_budget_remaining: round(
+                1.0 - ratio(total.slow, total.requests) / latency_budget,
+            ),
+            alert: alert(&burn_rates),
+            burn_rates,
+        })
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, VecDeque<Bucket>>> {
+        self.routes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The faster burn either objective shows across its window pair.
+fn alert(burn_rates: &[BurnRate]) -> Option<BurnAlert> {
+    let rate = |window: &str| {
+        burn_rates
+            .iter()
+            .find(|rate| rate.window == window)
+            .map(|rate| rate.availability.max(rate.latency))
+            .unwrap_or(0.0)
+    };
+    let burning = |long: &str, short: &str, threshold: f64| {
+        [long, short].iter().all(|window| rate(window) >= threshold)
+    };
+    if burning("1h", "5m", FAST_BURN) {
+        Some(BurnAlert::Fast)
+    } else if burning("6h", "30m", SLOW_BURN) {
+        Some(BurnAlert::Slow)
+    } else {
+        None
+    }
+}
+
+fn ratio(part: u64, whole: u64) -> f64 {
+    if whole == 0 {
+        0.0
+    } else {
+        part as f64 / whole as f64
+    }
+}
+
+/// Four decimal places, enough for a 99.99% target.
+fn round(value: f64) -> f64 {
+    (value * 10_000.0).round() / 10_000.0
+}
+
+fn current_minute() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs() / 60)
+        .unwrap_or(0)
+}
+
+/// Run forever, logging routes whose budget is burning.
+pub async fn run_alerts(tracker: Arc<SloTracker>) {
+    loop {
+        tokio::time::sleep(ALERT_INTERVAL).await;
+        for route in tracker.report().routes {
+            let Some(alert) = route.alert else {
+                continue;
+            };
+            let burn_1h = route.burn_rates.iter().find(|rate| rate.window == "1h");
+            warn!(
+                route = %route.route,
+                alert = ?alert,
+                availability_burn_1h = burn_1h.map(|rate| rate.availability),
+                latency_burn_1h = burn_1h.map(|rate| rate.latency),
+                availability_budget_remaining = route.availability_budget_remaining,
+                latency_budget_remaining = route.latency_budget_remaining,
+                "slo_burn_rate_alert"
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: u64 = 29_000_000;
+    const FAST: Duration = Duration::from_millis(50);
+    const SLOW: Duration = Duration::from_millis(1500);
+
+    fn tracker() -> SloTracker {
+        SloTracker::new(SloConfig::default())
+    }
+
+    #[test]
+    fn test_budget_and_attainment() {
+        let tracker = tracker();
+        for i in 0..1000 {
+            let status = if i < 2 { 502 } else { 200 };
+            let elapsed = if i < 5 { SLOW } else { FAST };
+            tracker.record_at("/tx/:hash", status, elapsed, NOW - 600);
+        }
+
+        let report = tracker.report_at(NOW);
+        let route = &report.routes[0];
+        assert_eq!(route.requests, 1000);
+        assert_eq!(route.availability, 0.998);
+        assert_eq!(route.latency_attainment, 0.995);
+        // 0.2% errors against a 0.5% budget, 0.5% slow against a 1% budget.
+        assert_eq!(route.availability_budget_remaining, 0.6);
+        assert_eq!(route.latency_budget_remaining, 0.5);
+        assert_eq!(route.alert, None);
+    }
+
+    #[test]
+    fn test_error_spike_burns_fast() {
+        let tracker = tracker();
+        for _ in 0..100 {
+            tracker.record_at("/account/:address", 200, FAST, NOW - 120);
+        }
+        for i in 0..50 {
+            let status = if i % 5 == 0 { 504 } else { 200 };
+            tracker.record_at("/account/:address", status, FAST, NOW);
+        }
+
+        let route = &tracker.report_at(NOW).routes[0];
+        let five_minutes = &route.burn_rates[0];
+        assert_eq!(five_minutes.window, "5m");
+        // 10 of 50 failed: a 20% error rate against a 0.5% budget.
+        assert_eq!(five_minutes.availability, 40.0);
+        assert_eq!(route.alert, Some(BurnAlert::Fast));
+        assert!(route.availability_budget_remaining < 0.0);
+    }
+
+    #[test]
+    fn test_old_requests_leave_the_window() {
+        let tracker = tracker();
+        tracker.record_at("/ledger/:sequence", 500, FAST, NOW - 168 * 60);
+        tracker.record_at("/tx/:hash", 200, FAST, NOW);
+
+        let report = tracker.report_at(NOW);
+        assert_eq!(report.routes.len(), 1);
+        assert_eq!(report.routes[0].route, "/tx/:hash");
+        assert_eq!(report.window_hours, 168);
+    }
+}
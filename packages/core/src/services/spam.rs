@@ -0,0 +1,188 @@
+//! Dust and airdrop-spam detection for account history.
+//!
+//! Accounts on Stellar receive unsolicited payments: tiny "dust" amounts sent
+//! to advertise an address in the recipient's history, and airdrops of
+//! worthless assets. A transaction counts as spam only when someone else
+//! submitted it and every operation in it is an incoming payment of dust or
+//! of a known spam asset.
+//!
+//! The dust threshold is set by `SPAM_DUST_THRESHOLD` (default 0.01, in
+//! units of the payment's asset) and known spam assets by `SPAM_ASSETS`, a
+//! comma-separated list of `CODE:ISSUER`. Assets on the spam blocklist
+//! (see [`crate::services::blocklist`]) count as spam assets too.
+
+use std::collections::HashSet;
+use std::env;
+use std::sync::OnceLock;
+
+use crate::models::amount::Amount;
+use crate::services::blocklist;
+use crate::services::horizon::HorizonOperation;
+
+/// Payments below this amount are dust when not configured.
+pub const DEFAULT_DUST_THRESHOLD: Amount = Amount::from_stroops(100_000);
+
+/// Why a transaction was hidden.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpamKind {
+    Dust,
+    SpamAsset,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpamFilter {
+    dust_threshold: Amount,
+    /// Assets as "CODE:ISSUER".
+    spam_assets: HashSet<String>,
+}
+
+impl Default for SpamFilter {
+    fn default() -> Self {
+        Self {
+            dust_threshold: DEFAULT_DUST_THRESHOLD,
+            spam_assets: HashSet::new(),
+        }
+    }
+}
+
+impl SpamFilter {
+    pub fn new(dust_threshold: Amount, spam_assets: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            dust_threshold,
+            spam_assets: spam_assets.into_iter().collect(),
+        }
+    }
+
+    /// Read `SPAM_DUST_THRESHOLD` and `SPAM_ASSETS`, falling back to the
+    /// defaults when unset or malformed.
+    pub fn from_env() -> Self {
+        let dust_threshold = env::var("SPAM_DUST_THRESHOLD")
+            .ok()
+            .and_then(|v| v.parse::<Amount>().ok())
+            .filter(|n| !n.is_negative())
+            .unwrap_or(DEFAULT_DUST_THRESHOLD);
+        let spam_assets = env::var("SPAM_ASSETS")
+            .map(|v| {
+                v.split(',')
+                    .map(str::trim)
+                    .filter(|asset| asset.contains(':'))
+                    .map(str::to_string)
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        Self::new(dust_threshold, spam_assets)
+    }
+
+    /// Classify a transaction in `account`'s history from its submitter and
+    /// operations. Returns `None` for anything that is not clearly spam.
+    pub fn classify(
+        &self,
+        account: &str,
+        tx_source: Option<&str>,
+        operations: &[HorizonOperation],
+    ) -> Option<SpamKind> {
+        if tx_source == Some(account) || operations.is_empty() {
+            return None;
+        }
+        let kinds: Vec<SpamKind> = operations
+            .iter()
+            .map(|op| self.classify_operation(account, op))
+            .collect::<Option<_>>()?;
+        // An airdrop of a spam asset is reported as such even when the
+        // amounts are also dust.
+        if kinds.contains(&SpamKind::SpamAsset) {
+            Some(SpamKind::SpamAsset)
+        } else {
+            Some(SpamKind::Dust)
+        }
+    }
+
+    fn classify_operation(&self, account: &str, op: &HorizonOperation) -> Option<SpamKind> {
+        if op.operation_type != "payment" || op.to.as_deref() != Some(account) {
+            return None;
+        }
+        if let (Some(code), Some(issuer)) = (&op.asset_code, &op.asset_issuer) {
+            let asset = format!("{code}:{issuer}");
+            if self.spam_assets.contains(&asset)
+                || blocklist::current().is_some_and(|list| list.contains(&asset))
+            {
+                return Some(SpamKind::SpamAsset);
+            }
+        }
+        let amount: Amount = op.amount.as_deref()?.parse().ok()?;
+        (amount < self.dust_threshold).then_some(SpamKind::Dust)
+    }
+}
+
+static SPAM_FILTER: OnceLock<SpamFilter> = OnceLock::new();
+
+/// Install the spam filter. Call once at startup; later calls are ignored.
+pub fn install(filter: SpamFilter) {
+    let _ = SPAM_FILTER.set(filter);
+}
+
+/// The installed filter, or the defaults when none was installed.
+pub fn filter() -> &'static SpamFilter {
+    SPAM_FILTER.get_or_init(SpamFilter::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "GACCOUNT";
+
+    fn payment(to: &str, amount: &str, asset: Option<(&str, &str)>) -> HorizonOperation {
+        HorizonOperation {
+            operation_type: "payment".to_string(),
+            to: Some(to.to_string()),
+            amount: Some(amount.to_string()),
+            asset_code: asset.map(|(code, _)| code.to_string()),
+            asset_issuer: asset.map(|(_, issuer)| issuer.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn filter() -> SpamFilter {
+        SpamFilter::new(DEFAULT_DUST_THRESHOLD, ["AIRDROP:GSPAMMER".to_string()])
+    }
+
+    #[test]
+    fn test_incoming_dust_is_spam() {
+        let ops = vec![payment(ACCOUNT, "0.0000001", None)];
+        assert_eq!(
+            filter().classify(ACCOUNT, Some("GSENDER"), &ops),
+            Some(SpamKind::Dust)
+        );
+    }
+
+    #[test]
+    fn test_spam_asset_airdrop() {
+        let ops = vec![payment(
+            ACCOUNT,
+            "1000.0000000",
+            Some(("AIRDROP", "GSPAMMER")),
+        )];
+        assert_eq!(
+            filter().classify(ACCOUNT, Some("GSPAMMER"), &ops),
+            Some(SpamKind::SpamAsset)
+        );
+    }
+
+    #[test]
+    fn test_regular_activity_is_kept() {
+        let filter = filter();
+        // Above the threshold.
+        let ops = vec![payment(ACCOUNT, "5.0000000", None)];
+        assert_eq!(filter.classify(ACCOUNT, Some("GSENDER"), &ops), None);
+        // Submitted by the account itself.
+        let ops = vec![payment("GOTHER", "0.0000001", None)];
+        assert_eq!(filter.classify(ACCOUNT, Some(ACCOUNT), &ops), None);
+        // Dust alongside a real operation.
+        let ops = vec![
+            payment(ACCOUNT, "0.0000001", None),
+            payment(ACCOUNT, "5.0000000", None),
+        ];
+        assert_eq!(filter.classify(ACCOUNT, Some("GSENDER"), &ops), None);
+    }
+}
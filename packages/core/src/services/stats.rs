@@ -0,0 +1,154 @@
+//! Hourly and daily analytics over ingested transactions.
+//!
+//! Rollups are precomputed from the explanation store by a background task
+//! (every `STATS_REFRESH_SECONDS`, default 60) so `/stats/:window` is a plain
+//! read. Each refresh recomputes only the newest stored bucket and anything
+//! after it, since older buckets no longer change.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info};
+use utoipa::ToSchema;
+
+use crate::services::storage::ExplanationStore;
+
+/// Number of counterparties kept per bucket.
+pub const TOP_COUNTERPARTIES: usize = 5;
+
+/// Operation detail keys that name an account taking part in the operation.
+pub const COUNTERPARTY_DETAIL_KEYS: &[&str] = &[
+    "from",
+    "to",
+    "funder",
+    "account",
+    "source",
+    "destination",
+    "issuer",
+];
+
+/// Aggregation window for rollups.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StatsWindow {
+    Hourly,
+    Daily,
+}
+
+impl StatsWindow {
+    pub const ALL: [StatsWindow; 2] = [StatsWindow::Hourly, StatsWindow::Daily];
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "hourly" => Some(StatsWindow::Hourly),
+            "daily" => Some(StatsWindow::Daily),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            StatsWindow::Hourly => "hourly",
+            StatsWindow::Daily => "daily",
+        }
+    }
+
+    /// Length of the ISO 8601 prefix that identifies a bucket:
+    /// "2024-01-15T14" for hours, "2024-01-15" for days.
+    pub fn prefix_len(self) -> usize {
+        match self {
+            StatsWindow::Hourly => 13,
+            StatsWindow::Daily => 10,
+        }
+    }
+
+    /// Full timestamp at which the bucket identified by `prefix` starts.
+    pub fn bucket_start(self, prefix: &str) -> String {
+        match self {
+            StatsWindow::Hourly => format!("{prefix}:00:00Z"),
+            StatsWindow::Daily => format!("{prefix}T00:00:00Z"),
+        }
+    }
+}
+
+/// An account and how many operations in the bucket involved it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema, JsonSchema)]
+pub struct CounterpartyCount {
+    pub account: String,
+    /// Known label for the account, e.g. "Coinbase".
+    pub label: Option<String>,
+    pub operations: u64,
+}
+
+/// Aggregates for one time bucket.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, ToSchema, JsonSchema)]
+pub struct StatsRollup {
+    /// ISO 8601 start of the bucket, e.g. "2024-01-15T14:00:00Z".
+    pub bucket_start: String,
+    pub transaction_count: u64,
+    pub successful_count: u64,
+    pub average_fee_stroops: u64,
+    /// Average fee in XLM, e.g. "0.0000100".
+    pub average_fee_xlm: String,
+    /// Number of operations of each type, e.g. {"payment": 12}.
+    pub operation_types: BTreeMap<String, u64>,
+    /// Accounts involved in the most operations, busiest first.
+    pub top_counterparties: Vec<CounterpartyCount>,
+}
+
+/// Refresh interval from `STATS_REFRESH_SECONDS` (default 60).
+pub fn refresh_interval_from_env() -> Duration {
+    env::var("STATS_REFRESH_SECONDS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .filter(|n| *n > 0)
+        .map(Duration::from_secs)
+        .unwrap_or(Duration::from_secs(60))
+}
+
+/// Recompute rollups for every window.
+pub fn refresh_all(store: &ExplanationStore) {
+    for window in StatsWindow::ALL {
+        match store.refresh_rollups(window) {
+            Ok(buckets) => info!(window = window.as_str(), buckets, "stats_rollups_refreshed"),
+            Err(err) => error!(window = window.as_str(), error = %err, "stats_refresh_failed"),
+        }
+    }
+}
+
+/// Run forever, refreshing rollups every `interval`.
+pub async fn run_refresh(store: Arc<ExplanationStore>, interval: Duration) {
+    loop {
+        let task_store = Arc::clone(&store);
+        // SQLite work is blocking; keep it off the async workers.
+        if let Err(err) = tokio::task::spawn_blocking(move || refresh_all(&task_store)).await {
+            error!(error = %err, "stats_refresh_panicked");
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_parse_and_buckets() {
+        assert_eq!(StatsWindow::parse("hourly"), Some(StatsWindow::Hourly));
+        assert_eq!(StatsWindow::parse("weekly"), None);
+        assert_eq!(
+            StatsWindow::Hourly.bucket_start("2024-01-15T14"),
+            "2024-01-15T14:00:00Z"
+        );
+        assert_eq!(
+            StatsWindow::Daily.bucket_start("2024-01-15"),
+            "2024-01-15T00:00:00Z"
+        );
+        assert_eq!(
+            &"2024-01-15T14:32:00Z"[..StatsWindow::Hourly.prefix_len()],
+            "2024-01-15T14"
+        );
+    }
+}
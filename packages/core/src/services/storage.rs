@@ -0,0 +1,764 @@
+//! Local persistence for pre-computed explanations.
+//!
+//! Explanations are stored in SQLite keyed by transaction hash, alongside the
+//! ingestion cursor. Queries are small single-row reads, so the connection is
+//! shared behind a mutex rather than a pool.
+//!
+//! Each explanation is also indexed in an FTS5 table over its summary text,
+//! memo, asset codes, and the labels of known accounts it touches, so support
+//! teams can search for things like "Invoice #2026". Operation types and
+//! participating accounts are recorded as facts that feed the analytics
+//! rollups in [`crate::services::stats`].
+
+use rusqlite::{Connection, OptionalExtension, params};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::sync::Mutex;
+
+use crate::explain::transaction::TransactionExplanation;
+use crate::models::fee::FeeStats;
+use crate::services::labels::resolve_label;
+use crate::services::stats::{
+    COUNTERPARTY_DETAIL_KEYS, CounterpartyCount, StatsRollup, StatsWindow, TOP_COUNTERPARTIES,
+};
+
+/// Errors raised by the explanation store.
+#[derive(Debug)]
+pub enum StorageError {
+    Database(String),
+    Serialization(String),
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StorageError::Database(msg) => write!(f, "database error: {msg}"),
+            StorageError::Serialization(msg) => write!(f, "serialization error: {msg}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+impl From<rusqlite::Error> for StorageError {
+    fn from(err: rusqlite::Error) -> Self {
+        StorageError::Database(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for StorageError {
+    fn from(err: serde_json::Error) -> Self {
+        StorageError::Serialization(err.to_string())
+    }
+}
+
+/// A stored explanation together with the ledger metadata used for indexing.
+#[derive(Debug, Clone)]
+pub struct StoredExplanation {
+    pub ledger: u64,
+    pub created_at: Option<String>,
+    /// Fee charged, in stroops.
+    pub fee_charged: u64,
+    pub explanation: TransactionExplanation,
+}
+
+/// A search result: a stored explanation and where it sits in the ledger.
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub ledger: u64,
+    pub created_at: Option<String>,
+    pub explanation: TransactionExplanation,
+}
+
+const LAST_INGESTED_LEDGER: &str = "last_ingested_ledger";
+
+/// Operation detail keys whose values describe an asset, e.g. "USDC (GISSUER)".
+pub(crate) const ASSET_DETAIL_KEYS: &[&str] = &[
+    "asset",
+    "selling_asset",
+    "buying_asset",
+    "send_asset",
+    "dest_asset",
+];
+
+/// SQLite-backed index of explained transactions.
+pub struct ExplanationStore {
+    conn: Mutex<Connection>,
+}
+
+impl ExplanationStore {
+    /// Open (creating if needed) the database at `path`.
+    pub fn open(path: &str) -> Result<Self, StorageError> {
+        Self::with_connection(Connection::open(path)?)
+    }
+
+    /// An in-memory store, used by tests and when no path is configured.
+    pub fn open_in_memory() -> Result<Self, StorageError> {
+        Self::with_connection(Connection::open_in_memory()?)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, StorageError> {
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS explanations (
+                hash        TEXT PRIMARY KEY,
+                ledger      INTEGER NOT NULL,
+                created_at  TEXT,
+                successful  INTEGER NOT NULL,
+                summary     TEXT NOT NULL,
+                body        TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS explanations_ledger ON explanations (ledger);
+            CREATE TABLE IF NOT EXISTS operation_facts (
+                hash            TEXT NOT NULL,
+                created_at      TEXT,
+                operation_type  TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS operation_facts_hash ON operation_facts (hash);
+            CREATE TABLE IF NOT EXISTS counterparty_facts (
+                hash        TEXT NOT NULL,
+                created_at  TEXT,
+                account     TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS counterparty_facts_hash ON counterparty_facts (hash);
+            CREATE TABLE IF NOT EXISTS rollups (
+                period  TEXT NOT NULL,
+                bucket  TEXT NOT NULL,
+                body    TEXT NOT NULL,
+                PRIMARY KEY (period, bucket)
+            );
+            CREATE TABLE IF NOT EXISTS ingestion_state (
+                key   TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+            CREATE VIRTUAL TABLE IF NOT EXISTS explanations_fts USING fts5 (
+                hash UNINDEXED,
+                summary,
+                memo,
+                assets,
+                labels
+            );",
+        )?;
+        ensure_column(
+            &conn,
+            "explanations",
+            "fee_charged",
+            "INTEGER NOT NULL DEFAULT 0",
+        )?;
+        let store = Self {
+            conn: Mutex::new(conn),
+        };
+        store.backfill_indexes()?;
+        Ok(store)
+    }
+
+    /// Index explanations stored before the search and fact tables existed.
+    fn backfill_indexes(&self) -> Result<(), StorageError> {
+        let conn = self.conn();
+        let rows: Vec<(Option<String>, String)> = {
+            let mut stmt = conn.prepare(
+                "SELECT created_at, body FROM explanations
+                 WHERE hash NOT IN (SELECT hash FROM explanations_fts)
+                    OR hash NOT IN (SELECT hash FROM operation_facts)",
+            )?;
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+                .collect::<Result<_, _>>()?
+        };
+        for (created_at, body) in rows {
+            let explanation: TransactionExplanation = serde_json::from_str(&body)?;
+            index_for_search(&conn, &explanation)?;
+            record_facts(&conn, &explanation, created_at.as_deref())?;
+        }
+        Ok(())
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        // A panic while holding the lock cannot leave SQLite in a torn state,
+        // so recover the guard rather than poisoning every later request.
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Insert or replace the explanation for a transaction.
+    pub fn put_explanation(&self, record: &StoredExplanation) -> Result<(), StorageError> {
+        let body = serde_json::to_string(&record.explanation)?;
+        let conn = self.conn();
+        conn.execute(
+            "INSERT OR REPLACE INTO explanations
+                 (hash, ledger, created_at, successful, summary, body, fee_charged)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                record.explanation.transaction_hash,
+                record.ledger as i64,
+                record.created_at,
+                record.explanation.successful,
+                record.explanation.summary,
+                body,
+                record.fee_charged as i64,
+            ],
+        )?;
+        index_for_search(&conn, &record.explanation)?;
+        record_facts(&conn, &record.explanation, record.created_at.as_deref())?;
+        Ok(())
+    }
+
+    /// Full-text search over stored explanations, best matches first.
+    ///
+    /// Returns one page of hits and the total number of matches. Every word in
+    /// `query` must match; punctuation is ignored, so "Invoice #2026" finds
+    /// memos containing "Invoice 2026".
+    pub fn search(
+        &self,
+        query: &str,
+        limit: u32,
+        offset: u32,
+    ) -> Result<(Vec<SearchHit>, u64), StorageError> {
+        let Some(fts_query) = fts_query(query) else {
+            return Ok((Vec::new(), 0));
+        };
+        let conn = self.conn();
+
+        let total: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM explanations_fts WHERE explanations_fts MATCH ?1",
+            params![fts_query],
+            |row| row.get(0),
+        )?;
+
+        let mut stmt = conn.prepare(
+            "SELECT e.ledger, e.created_at, e.body
+             FROM explanations_fts f
+             JOIN explanations e ON e.hash = f.hash
+             WHERE explanations_fts MATCH ?1
+             ORDER BY f.rank, e.ledger DESC
+             LIMIT ?2 OFFSET ?3",
+        )?;
+        let rows = stmt
+            .query_map(params![fts_query, limit, offset], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let hits = rows
+            .into_iter()
+            .map(|(ledger, created_at, body)| {
+                Ok(SearchHit {
+                    ledger: ledger as u64,
+                    created_at,
+                    explanation: serde_json::from_str(&body)?,
+                })
+            })
+            .collect::<Result<Vec<_>, StorageError>>()?;
+        Ok((hits, total as u64))
+    }
+
+    /// Look up a stored explanation by transaction hash.
+    pub fn get_explanation(
+        &self,
+        hash: &str,
+    ) -> Result<Option<TransactionExplanation>, StorageError> {
+        let body: Option<String> = self
+            .conn()
+            .query_row(
+                "SELECT body FROM explanations WHERE hash = ?1",
+                params![hash],
+                |row| row.get(0),
+            )
+            .optional()?;
+        match body {
+            Some(body) => Ok(Some(serde_json::from_str(&body)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Up to `limit` stored explanations chosen at random.
+    pub fn sample_explanations(&self, limit: u32) -> Result<Vec<StoredExplanation>, StorageError> {
+        let conn = self.conn();
+        let mut stmt = conn.prepare(
+            "SELECT ledger, created_at, fee_charged, body FROM explanations
+             ORDER BY RANDOM() LIMIT ?1",
+        )?;
+        let rows = stmt
+            .query_map(params![limit], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                    row.get::<_, i64>(2)?,
+                    row.get::<_, String>(3)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.into_iter()
+            .map(|(ledger, created_at, fee_charged, body)| {
+                Ok(StoredExplanation {
+                    ledger: ledger as u64,
+                    created_at,
+                    fee_charged: fee_charged as u64,
+                    explanation: serde_json::from_str(&body)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Number of stored explanations.
+    pub fn explanation_count(&self) -> Result<u64, StorageError> {
+        let count: i64 = self
+            .conn()
+            .query_row("SELECT COUNT(*) FROM explanations", [], |row| row.get(0))?;
+        Ok(count as u64)
+    }
+
+    /// Recompute rollups for `window`, starting from the newest stored bucket.
+    /// Returns the number of buckets written.
+    pub fn refresh_rollups(&self, window: StatsWindow) -> Result<usize, StorageError> {
+        let conn = self.conn();
+        let len = window.prefix_len() as i64;
+        // The newest bucket may have been incomplete when last computed; the
+        // empty string sorts before every bucket on the first run.
+        let since: String = conn
+            .query_row(
+                "SELECT MAX(bucket) FROM rollups WHERE period = ?1",
+                params![window.as_str()],
+                |row| row.get::<_, Option<String>>(0),
+            )?
+            .unwrap_or_default();
+
+        let mut buckets: BTreeMap<String, StatsRollup> = BTreeMap::new();
+
+        let mut stmt = conn.prepare(
+            "SELECT substr(created_at, 1, ?1) AS bucket, COUNT(*), SUM(successful), AVG(fee_charged)
+             FROM explanations
+             WHERE created_at IS NOT NULL AND substr(created_at, 1, ?1) >= ?2
+             GROUP BY bucket",
+        )?;
+        let rows = stmt.query_map(params![len, since], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, f64>(3)?,
+            ))
+        })?;
+        for row in rows {
+            let (bucket, count, successful, average_fee) = row?;
+            let average_fee_stroops = average_fee.round() as u64;
+            buckets.insert(
+                bucket.clone(),
+                StatsRollup {
+                    bucket_start: window.bucket_start(&bucket),
+                    transaction_count: count as u64,
+                    successful_count: successful as u64,
+                    average_fee_stroops,
+                    average_fee_xlm: FeeStats::stroops_to_xlm(average_fee_stroops),
+                    operation_types: BTreeMap::new(),
+                    top_counterparties: Vec::new(),
+                },
+            );
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT substr(created_at, 1, ?1) AS bucket, operation_type, COUNT(*)
+             FROM operation_facts
+             WHERE created_at IS NOT NULL AND substr(created_at, 1, ?1) >= ?2
+             GROUP BY bucket, operation_type",
+        )?;
+        let rows = stmt.query_map(params![len, since], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (bucket, operation_type, count) = row?;
+            if let Some(rollup) = buckets.get_mut(&bucket) {
+                rollup.operation_types.insert(operation_type, count as u64);
+            }
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT substr(created_at, 1, ?1) AS bucket, account, COUNT(*) AS n
+             FROM counterparty_facts
+             WHERE created_at IS NOT NULL AND substr(created_at, 1, ?1) >= ?2
+             GROUP BY bucket, account
+             ORDER BY bucket, n DESC, account",
+        )?;
+        let rows = stmt.query_map(params![len, since], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?;
+        for row in rows {
+            let (bucket, account, count) = row?;
+            if let Some(rollup) = buckets.get_mut(&bucket)
+                && rollup.top_counterparties.len() < TOP_COUNTERPARTIES
+            {
+                rollup.top_counterparties.push(CounterpartyCount {
+                    label: resolve_label(&account).map(str::to_string),
+                    account,
+                    operations: count as u64,
+                });
+            }
+        }
+
+        for (bucket, rollup) in &buckets {
+            conn.execute(
+                "INSERT OR REPLACE INTO rollups (period, bucket, body) VALUES (?1, ?2, ?3)",
+                params![window.as_str(), bucket, serde_json::to_string(rollup)?],
+            )?;
+        }
+        Ok(buckets.len())
+    }
+
+    /// The most recent `limit` rollups for `window`, newest first.
+    pub fn rollups(
+        &self,
+        window: StatsWindow,
+        limit: u32,
+    ) -> Result<Vec<StatsRollup>, StorageError> {
+        let conn = self.conn();
+        let mut stmt = conn
+            .prepare("SELECT body FROM rollups WHERE period = ?1 ORDER BY bucket DESC LIMIT ?2")?;
+        let bodies = stmt
+            .query_map(params![window.as_str(), limit], |row| {
+                row.get::<_, String>(0)
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        bodies
+            .iter()
+            .map(|body| Ok(serde_json::from_str(body)?))
+            .collect()
+    }
+
+    /// The last ledger the ingestion worker fully processed, if any.
+    pub fn last_ingested_ledger(&self) -> Result<Option<u64>, StorageError> {
+        let value: Option<String> = self
+            .conn()
+            .query_row(
+                "SELECT value FROM ingestion_state WHERE key = ?1",
+                params![LAST_INGESTED_LEDGER],
+                |row| row.get(0),
+            )
+            .optional()?;
+        Ok(value.and_then(|v| v.parse().ok()))
+    }
+
+    /// Record that every transaction up to and including `sequence` is stored.
+    pub fn set_last_ingested_ledger(&self, sequence: u64) -> Result<(), StorageError> {
+        self.conn().execute(
+            "INSERT OR REPLACE INTO ingestion_state (key, value) VALUES (?1, ?2)",
+            params![LAST_INGESTED_LEDGER, sequence.to_string()],
+        )?;
+        Ok(())
+    }
+}
+
+/// Replace the search row for an explanation.
+fn index_for_search(
+    conn: &Connection,
+    explanation: &TransactionExplanation,
+) -> Result<(), StorageError> {
+    let mut summary = vec![explanation.summary.clone()];
+    let mut assets = BTreeSet::new();
+    let mut labels = BTreeSet::new();
+
+    for op in &explanation.operations {
+        summary.push(op.summary.clone());
+        if let Value::Object(details) = &op.details {
+            for (key, value) in details {
+                let Some(text) = value.as_str() else { continue };
+                if ASSET_DETAIL_KEYS.contains(&key.as_str()) {
+                    // "USDC (GISSUER)" and "XLM (native)" both lead with the code.
+                    if let Some(code) = text.split_whitespace().next() {
+                        assets.insert(code.to_string());
+                    }
+                } else if let Some(label) = resolve_label(text) {
+                    labels.insert(label);
+                }
+            }
+        }
+    }
+    summary.extend(explanation.anchor_interpretation.clone());
+    summary.extend(explanation.failure_reason.clone());
+
+    conn.execute(
+        "DELETE FROM explanations_fts WHERE hash = ?1",
+        params![explanation.transaction_hash],
+    )?;
+    conn.execute(
+        "INSERT INTO explanations_fts (hash, summary, memo, assets, labels)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+        params![
+            explanation.transaction_hash,
+            summary.join("\n"),
+            explanation.memo_explanation.clone().unwrap_or_default(),
+            assets.into_iter().collect::<Vec<_>>().join(" "),
+            labels.into_iter().collect::<Vec<_>>().join(" "),
+        ],
+    )?;
+    Ok(())
+}
+
+/// Replace the analytics facts for an explanation.
+fn record_facts(
+    conn: &Connection,
+    explanation: &TransactionExplanation,
+    created_at: Option<&str>,
+) -> Result<(), StorageError> {
+    let hash = &explanation.transaction_hash;
+    conn.execute("DELETE FROM operation_facts WHERE hash = ?1", params![hash])?;
+    conn.execute(
+        "DELETE FROM counterparty_facts WHERE hash = ?1",
+        params![hash],
+    )?;
+
+    for op in &explanation.operations {
+        conn.execute(
+            "INSERT INTO operation_facts (hash, created_at, operation_type) VALUES (?1, ?2, ?3)",
+            params![hash, created_at, op.operation_type],
+        )?;
+        // An account counts once per operation even if it appears in several fields.
+        let accounts: BTreeSet<&str> = COUNTERPARTY_DETAIL_KEYS
+            .iter()
+            .filter_map(|key| op.details.get(*key).and_then(Value::as_str))
+            .filter(|account| *account != "Unknown")
+            .collect();
+        for account in accounts {
+            conn.execute(
+                "INSERT INTO counterparty_facts (hash, created_at, account) VALUES (?1, ?2, ?3)",
+                params![hash, created_at, account],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Add a column to a table created by an earlier version of the schema.
+fn ensure_column(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    declaration: &str,
+) -> Result<(), StorageError> {
+    let exists = conn
+        .prepare(&format!(
+            "SELECT 1 FROM pragma_table_info('{table}') WHERE name = ?1"
+        ))?
+        .exists(params![column])?;
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {table} ADD COLUMN {column} {declaration}"
+        ))?;
+    }
+    Ok(())
+}
+
+/// Turn free text into an FTS5 query that requires every word, quoting each
+/// so user input can never be parsed as FTS5 syntax.
+fn fts_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| format!("\"{t}\""))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::transaction::explain_transaction;
+    use crate::models::operation::{Operation, OtherOperation};
+    use crate::models::transaction::Transaction;
+
+    fn payment(hash: &str, to: &str, asset_code: &str, memo: Option<&str>) -> StoredExplanation {
+        use crate::models::memo::Memo;
+        use crate::models::operation::PaymentOperation;
+
+        let tx = Transaction::new(
+            hash.to_string(),
+            true,
+            100,
+            vec![Operation::Payment(PaymentOperation {
+                id: "1".to_string(),
+                source_account: None,
+                destination: to.to_string(),
+                asset_type: "credit_alphanum4".to_string(),
+                asset_code: Some(asset_code.to_string()),
+                asset_issuer: Some("GISSUER".to_string()),
+                amount: "10.0000000".parse().unwrap(),
+            })],
+            memo.map(|m| Memo::Text(m.to_string())),
+            None,
+        );
+        StoredExplanation {
+            ledger: 7,
+            created_at: None,
+            fee_charged: 100,
+            explanation: explain_transaction(&tx, None).unwrap(),
+        }
+    }
+
+    fn explanation(hash: &str) -> TransactionExplanation {
+        let tx = Transaction::new(
+            hash.to_string(),
+            true,
+            100,
+            vec![Operation::Other(OtherOperation {
+                id: "1".to_string(),
+                operation_type: "bump_sequence".to_string(),
+            })],
+            None,
+            None,
+        );
+        explain_transaction(&tx, None).unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_explanation() {
+        let store = ExplanationStore::open_in_memory().unwrap();
+        assert_eq!(store.get_explanation("abc").unwrap(), None);
+
+        let record = StoredExplanation {
+            ledger: 42,
+            created_at: Some("2024-01-15T14:32:00Z".to_string()),
+            fee_charged: 100,
+            explanation: explanation("abc"),
+        };
+        store.put_explanation(&record).unwrap();
+        store.put_explanation(&record).unwrap();
+
+        assert_eq!(
+            store.get_explanation("abc").unwrap(),
+            Some(record.explanation.clone())
+        );
+        assert_eq!(store.explanation_count().unwrap(), 1);
+
+        let sample = store.sample_explanations(5).unwrap();
+        assert_eq!(sample.len(), 1);
+        assert_eq!(sample[0].ledger, 42);
+        assert_eq!(sample[0].explanation, record.explanation);
+    }
+
+    #[test]
+    fn test_ingestion_cursor() {
+        let store = ExplanationStore::open_in_memory().unwrap();
+        assert_eq!(store.last_ingested_ledger().unwrap(), None);
+        store.set_last_ingested_ledger(100).unwrap();
+        store.set_last_ingested_ledger(101).unwrap();
+        assert_eq!(store.last_ingested_ledger().unwrap(), Some(101));
+    }
+
+    #[test]
+    fn test_search_matches_memo_asset_and_label() {
+        let store = ExplanationStore::open_in_memory().unwrap();
+        let coinbase = "GCOINBASEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+        store
+            .put_explanation(&payment("tx1", "GDEST", "USDC", Some("Invoice #2026")))
+            .unwrap();
+        store
+            .put_explanation(&payment("tx2", coinbase, "EURC", Some("Invoice #1999")))
+            .unwrap();
+
+        let hashes = |query: &str| {
+            let (hits, total) = store.search(query, 10, 0).unwrap();
+            assert_eq!(hits.len() as u64, total);
+            hits.into_iter()
+                .map(|h| h.explanation.transaction_hash)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(hashes("Invoice #2026"), vec!["tx1"]);
+        assert_eq!(hashes("invoice").len(), 2);
+        assert_eq!(hashes("EURC"), vec!["tx2"]);
+        assert_eq!(hashes("coinbase"), vec!["tx2"]);
+        assert!(hashes("nothing-matches-this").is_empty());
+        assert!(hashes("#!").is_empty());
+    }
+
+    #[test]
+    fn test_search_paginates_and_reindexes_on_replace() {
+        let store = ExplanationStore::open_in_memory().unwrap();
+        for i in 0..3 {
+            store
+                .put_explanation(&payment(&format!("tx{i}"), "GDEST", "USDC", Some("rent")))
+                .unwrap();
+        }
+        store
+            .put_explanation(&payment("tx0", "GDEST", "USDC", Some("deposit")))
+            .unwrap();
+
+        let (page, total) = store.search("rent", 1, 1).unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(page.len(), 1);
+        assert_eq!(store.search("deposit", 10, 0).unwrap().1, 1);
+    }
+
+    #[test]
+    fn test_fts_query_quotes_terms() {
+        assert_eq!(
+            fts_query("Invoice #2026").as_deref(),
+            Some("\"Invoice\" \"2026\"")
+        );
+        assert_eq!(
+            fts_query("AND OR NOT"),
+            Some("\"AND\" \"OR\" \"NOT\"".to_string())
+        );
+        assert_eq!(fts_query("  ## "), None);
+    }
+
+    #[test]
+    fn test_refresh_rollups() {
+        let store = ExplanationStore::open_in_memory().unwrap();
+        let coinbase = "GCOINBASEAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+        let at = |hash: &str, to: &str, created_at: &str, fee: u64| {
+            let mut record = payment(hash, to, "USDC", None);
+            record.created_at = Some(created_at.to_string());
+            record.fee_charged = fee;
+            record
+        };
+        store
+            .put_explanation(&at("a", coinbase, "2024-01-15T14:05:00Z", 100))
+            .unwrap();
+        store
+            .put_explanation(&at("b", coinbase, "2024-01-15T14:45:00Z", 300))
+            .unwrap();
+        store
+            .put_explanation(&at("c", "GOTHER", "2024-01-15T15:10:00Z", 100))
+            .unwrap();
+
+        assert_eq!(store.refresh_rollups(StatsWindow::Hourly).unwrap(), 2);
+        assert_eq!(store.refresh_rollups(StatsWindow::Daily).unwrap(), 1);
+
+        let hourly = store.rollups(StatsWindow::Hourly, 10).unwrap();
+        assert_eq!(hourly.len(), 2);
+        assert_eq!(hourly[0].bucket_start, "2024-01-15T15:00:00Z");
+        let busy = &hourly[1];
+        assert_eq!(busy.transaction_count, 2);
+        assert_eq!(busy.successful_count, 2);
+        assert_eq!(busy.average_fee_stroops, 200);
+        assert_eq!(busy.average_fee_xlm, "0.0000200");
+        assert_eq!(busy.operation_types.get("payment"), Some(&2));
+        assert_eq!(busy.top_counterparties[0].account, coinbase);
+        assert_eq!(
+            busy.top_counterparties[0].label.as_deref(),
+            Some("Coinbase")
+        );
+        assert_eq!(busy.top_counterparties[0].operations, 2);
+
+        let daily = store.rollups(StatsWindow::Daily, 10).unwrap();
+        assert_eq!(daily[0].transaction_count, 3);
+
+        // Later transactions update the newest bucket on the next refresh.
+        store
+            .put_explanation(&at("d", "GOTHER", "2024-01-15T15:20:00Z", 100))
+            .unwrap();
+        assert_eq!(store.refresh_rollups(StatsWindow::Hourly).unwrap(), 1);
+        let hourly = store.rollups(StatsWindow::Hourly, 1).unwrap();
+        assert_eq!(hourly[0].transaction_count, 2);
+    }
+}
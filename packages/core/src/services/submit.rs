@@ -0,0 +1,316 @@
+//! Submit a signed transaction and wait to see what happened to it.
+//!
+//! `POST /submit` hands the envelope to Horizon and waits for it to land in
+//! a ledger, so a wallet backend gets the explanation of the outcome in one
+//! call. There are three outcomes:
+//!
+//! - Included: the transaction is in a ledger, successful or not, and is
+//!   explained from Horizon like `GET /tx/:hash`.
+//! - Rejected: the network refused it before it reached a ledger, e.g. for
+//!   a bad sequence number. It is explained from the envelope and the result
+//!   Horizon sent back, so the result codes are decoded the same way.
+//! - Pending: no answer before the timeout. The transaction may still be
+//!   included, so clients should poll `GET /tx/:hash`.
+//!
+//! Configuration (environment):
+//! - `SUBMIT_ENABLED`: `true` or `1` to accept submissions (default off).
+//! - `SUBMIT_TIMEOUT_SECONDS`: how long to wait for inclusion (default 30).
+
+use sha2::{Digest, Sha256};
+use std::env;
+use std::time::Duration;
+use stellar_xdr::curr::{
+    self as xdr, Limits, ReadXdr, TransactionEnvelope, TransactionSignaturePayload,
+    TransactionSignaturePayloadTaggedTransaction, WriteXdr,
+};
+use tracing::info;
+
+use crate::errors::{AppError, HorizonError};
+use crate::models::contract_event::upstream_limits;
+use crate::services::horizon::{
+    HorizonExtras, HorizonOperation, HorizonSubmission, HorizonTransaction,
+};
+use crate::services::horizon_api::HorizonApi;
+use crate::services::rpc::{RpcTransaction, to_horizon_records};
+
+/// How often Horizon is asked whether a timed-out submission has landed.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubmitConfig {
+    /// Passphrase of the network submissions are hashed for.
+    pub network_passphrase: String,
+    pub timeout: Duration,
+}
+
+impl SubmitConfig {
+    /// Read the submission settings for the network with `passphrase`, or
+    /// `None` when submission is not enabled.
+    pub fn from_env(passphrase: &str) -> Option<Self> {
+        let enabled = env::var("SUBMIT_ENABLED")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let timeout_seconds = env::var("SUBMIT_TIMEOUT_SECONDS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|n| *n > 0)
+            .unwrap_or(30);
+
+        Some(Self {
+            network_passphrase: passphrase.to_string(),
+            timeout: Duration::from_secs(timeout_seconds),
+        })
+    }
+}
+
+/// What happened to a submitted transaction.
+#[derive(Debug, Clone)]
+pub enum SubmitOutcome {
+    /// In a ledger, successful or not.
+    Included(HorizonTransaction, Vec<HorizonOperation>),
+    /// Refused before reaching a ledger; records decoded from the envelope.
+    Rejected(HorizonTransaction, Vec<HorizonOperation>),
+    /// Not seen in a ledger before the timeout.
+    Pending,
+}
+
+/// The hash of a base64 envelope on the network with `passphrase`, or None
+/// if the envelope does not decode.
+pub fn transaction_hash(envelope_xdr: &str, passphrase: &str) -> Option<String> {
+    let envelope =
+        TransactionEnvelope::from_xdr_base64(envelope_xdr, upstream_limits(envelope_xdr)).ok()?;
+    let tagged_transaction = match envelope {
+        TransactionEnvelope::TxV0(env) => {
+            TransactionSignaturePayloadTaggedTransaction::Tx(xdr::Transaction::from(&env.tx))
+        }
+        TransactionEnvelope::Tx(env) => TransactionSignaturePayloadTaggedTransaction::Tx(env.tx),
+        TransactionEnvelope::TxFeeBump(env) => {
+            TransactionSignaturePayloadTaggedTransaction::TxFeeBump(env.tx)
+        }
+    };
+    let payload = TransactionSignaturePayload {
+        network_id: xdr::Hash(Sha256::digest(passphrase.as_bytes()).into()),
+        tagged_transaction,
+    };
+    let bytes = payload.to_xdr(Limits::none()).ok()?;
+    Some(
+        Sha256::digest(bytes)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect(),
+    )
+}
+
+/// Submit `envelope_xdr` (whose hash is `hash`) and wait until it is in a
+/// ledger, was rejected, or `timeout` passes.
+pub async fn submit_and_wait<H: HorizonApi>(
+    horizon: &H,
+    envelope_xdr: &str,
+    hash: &str,
+    timeout: Duration,
+) -> Result<SubmitOutcome, AppError> {
+    tokio::time::timeout(timeout, submit(horizon, envelope_xdr, hash))
+        .await
+        .unwrap_or(Ok(SubmitOutcome::Pending))
+}
+
+async fn submit<H: HorizonApi>(
+    horizon: &H,
+    envelope_xdr: &str,
+    hash: &str,
+) -> Result<SubmitOutcome, AppError> {
+    match horizon.submit_transaction(envelope_xdr).await? {
+        HorizonSubmission::Included(tx) => {
+            let operations = horizon.fetch_operations(hash).await?;
+            Ok(SubmitOutcome::Included(*tx, operations))
+        }
+        // Horizon reports failures in a ledger and refusals before one the
+        // same way; only a lookup tells them apart.
+        HorizonSubmission::Failed(extras) => match ledgered(horizon, hash).await? {
+            Some(outcome) => Ok(outcome),
+            None => rejected(hash, extras),
+        },
+        HorizonSubmission::TimedOut => {
+            info!(hash, "submission_timed_out_polling");
+            loop {
+                if let Some(outcome) = ledgered(horizon, hash).await? {
+                    return Ok(outcome);
+                }
+                tokio::time::sleep(POLL_INTERVAL).await;
+            }
+        }
+    }
+}
+
+/// The transaction as Horizon has it in a ledger, if it does yet.
+async fn ledgered<H: HorizonApi>(
+    horizon: &H,
+    hash: &str,
+) -> Result<Option<SubmitOutcome>, AppError> {
+    match horizon.fetch_transaction(hash).await {
+        Ok(tx) => {
+            let operations = horizon.fetch_operations(hash).await?;
+            Ok(Some(SubmitOutcome::Included(tx, operations)))
+        }
+        Err(HorizonError::TransactionNotFound) => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Records for a transaction refused before reaching a ledger.
+fn rejected(hash: &str, extras: HorizonExtras) -> Result<SubmitOutcome, AppError> {
+    let codes = extras
+        .result_codes
+        .as_ref()
+        .and_then(|codes| codes.transaction.clone())
+        .unwrap_or_else(|| "tx_malformed".to_string());
+    let decoded = to_horizon_records(
+        hash,
+        RpcTransaction {
+            status: "FAILED".to_string(),
+            ledger: None,
+            created_at: None,
+            application_order: None,
+            envelope_xdr: extras.envelope_xdr,
+            result_xdr: extras.result_xdr,
+            result_meta_xdr: None,
+        },
+    );
+    match decoded {
+        Ok((mut tx, operations)) => {
+            // Nothing is charged for a transaction that never made a ledger.
+            tx.fee_charged = "0".to_string();
+            Ok(SubmitOutcome::Rejected(tx, operations))
+        }
+        Err(_) => Err(AppError::BadRequest(format!(
+            "The network rejected this transaction ({codes})."
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::horizon::HorizonResultCodes;
+    use crate::services::horizon_api::FakeHorizon;
+    use crate::services::rpc::tests::{payment_envelope, payment_result};
+
+    const TESTNET: &str = "Test SDF Network ; September 2015";
+
+    fn hash() -> String {
+        transaction_hash(&payment_envelope(50_000_000), TESTNET).unwrap()
+    }
+
+    fn record(hash: &str) -> HorizonTransaction {
+        HorizonTransaction {
+            hash: hash.to_string(),
+            successful: true,
+            fee_charged: "100".to_string(),
+            ledger: Some(1000),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_transaction_hash_depends_on_network() {
+        let hash = hash();
+        assert_eq!(hash.len(), 64);
+        assert_ne!(
+            Some(hash),
+            transaction_hash(
+                &payment_envelope(50_000_000),
+                "Public Global Stellar Network ; September 2015"
+            )
+        );
+        assert_eq!(transaction_hash("not xdr", TESTNET), None);
+    }
+
+    #[tokio::test]
+    async fn test_included_submission() {
+        let hash = hash();
+        let horizon = FakeHorizon::new()
+            .with_submission(HorizonSubmission::Included(Box::new(record(&hash))))
+            .with_transaction(record(&hash), vec![]);
+
+        let outcome = submit_and_wait(
+            &horizon,
+            &payment_envelope(50_000_000),
+            &hash,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(outcome, SubmitOutcome::Included(tx, _) if tx.ledger == Some(1000)));
+    }
+
+    #[tokio::test]
+    async fn test_rejected_submission_is_decoded() {
+        let hash = hash();
+        let horizon =
+            FakeHorizon::new().with_submission(HorizonSubmission::Failed(HorizonExtras {
+                result_codes: Some(HorizonResultCodes {
+                    transaction: Some("tx_failed".to_string()),
+                    operations: vec!["op_underfunded".to_string()],
+                }),
+                envelope_xdr: Some(payment_envelope(50_000_000)),
+                result_xdr: Some(payment_result(false)),
+            }));
+
+        let outcome = submit_and_wait(
+            &horizon,
+            &payment_envelope(50_000_000),
+            &hash,
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        let SubmitOutcome::Rejected(tx, operations) = outcome else {
+            panic!("expected a rejection, got {outcome:?}");
+        };
+        assert!(!tx.successful);
+        assert_eq!(tx.fee_charged, "0");
+        assert_eq!(
+            tx.result_codes.unwrap().operations,
+            vec!["op_underfunded".to_string()]
+        );
+        assert_eq!(operations.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_failed_submission_in_a_ledger_is_included() {
+        let hash = hash();
+        let horizon = FakeHorizon::new()
+            .with_submission(HorizonSubmission::Failed(HorizonExtras::default()))
+            .with_transaction(record(&hash), vec![]);
+
+        let outcome = submit_and_wait(&horizon, "", &hash, Duration::from_secs(5))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, SubmitOutcome::Included(..)));
+    }
+
+    #[tokio::test]
+    async fn test_unseen_submission_is_pending_after_timeout() {
+        let horizon = FakeHorizon::new().with_submission(HorizonSubmission::TimedOut);
+
+        let outcome = submit_and_wait(&horizon, "", &hash(), Duration::from_millis(50))
+            .await
+            .unwrap();
+        assert!(matches!(outcome, SubmitOutcome::Pending));
+    }
+
+    #[tokio::test]
+    async fn test_malformed_rejection_is_bad_request() {
+        let horizon =
+            FakeHorizon::new().with_submission(HorizonSubmission::Failed(HorizonExtras::default()));
+
+        let err = submit_and_wait(&horizon, "", &hash(), Duration::from_secs(5))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::BadRequest(msg) if msg.contains("tx_malformed")));
+    }
+}
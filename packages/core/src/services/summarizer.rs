@@ -0,0 +1,354 @@
+//! Optional model-written summaries of explanations.
+//!
+//! A `Summarizer` rewrites a finished, deterministic explanation into one
+//! friendlier paragraph, served as `generated_summary` next to the fields it
+//! was written from. It only ever adds that field: a summarizer that fails
+//! or misses its deadline leaves the explanation as it was, so a response is
+//! never held up or turned into an error by it.
+//!
+//! The bundled backend, behind the `llm-summary` feature, calls any
+//! OpenAI-compatible chat completions endpoint, which covers hosted models
+//! and local servers such as llama.cpp or Ollama.
+//!
+//! Configuration (environment, `llm-summary` builds only):
+//! - `SUMMARIZER_URL`: the chat completions URL; unset disables summaries.
+//! - `SUMMARIZER_MODEL`: model name sent with each request (default "default").
+//! - `SUMMARIZER_API_KEY`: bearer token, when the endpoint needs one.
+//! - `SUMMARIZER_TIMEOUT_MS`: how long a response waits for it (default 2000).
+
+use async_trait::async_trait;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::warn;
+
+use crate::explain::transaction::TransactionExplanation;
+
+/// How long a response waits for a summary when not configured.
+pub const DEFAULT_SUMMARY_BUDGET: Duration = Duration::from_millis(2000);
+
+/// Writes a paragraph from an explanation's deterministic fields.
+#[async_trait]
+pub trait Summarizer: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn summarize(&self, explanation: &TransactionExplanation) -> Result<String, String>;
+}
+
+/// The configured summarizer and how long a response waits for it.
+#[derive(Clone)]
+pub struct SummarizerContext {
+    pub summarizer: Arc<dyn Summarizer>,
+    pub budget: Duration,
+}
+
+impl SummarizerContext {
+    /// `explanation` with `generated_summary` filled in when the summarizer
+    /// answers within the budget, and unchanged otherwise.
+    pub async fn apply(&self, mut explanation: TransactionExplanation) -> TransactionExplanation {
+        let hash = explanation.transaction_hash.clone();
+        match tokio::time::timeout(self.budget, self.summarizer.summarize(&explanation)).await {
+            Ok(Ok(summary)) if !summary.trim().is_empty() => {
+                explanation.generated_summary = Some(summary.trim().to_string());
+            }
+            Ok(Ok(_)) => warn!(
+                hash = %hash,
+                summarizer = self.summarizer.name(),
+                "generated_summary_empty"
+            ),
+            Ok(Err(err)) => warn!(
+                hash = %hash,
+                summarizer = self.summarizer.name(),
+                error = %err,
+                "generated_summary_failed"
+            ),
+            Err(_) => warn!(
+                hash = %hash,
+                summarizer = self.summarizer.name(),
+                budget_ms = self.budget.as_millis() as u64,
+                "generated_summary_timed_out"
+            ),
+        }
+        explanation
+    }
+}
+
+/// The facts a summary may draw on, one per line. Only deterministic
+/// fields go in, so the paragraph cannot cite anything the explanation
+/// does not already say.
+pub fn prompt(explanation: &TransactionExplanation) -> String {
+    let mut lines = vec![format!("Summary: {}", explanation.summary)];
+    lines.extend(
+        explanation
+            .operations
+            .iter()
+            .map(|op| format!("Operation {}: {}", op.index + 1, op.summary)),
+    );
+    let optional = [
+        ("Pattern", &explanation.narrative),
+        ("Anchor", &explanation.anchor_interpretation),
+        ("Memo", &explanation.memo_explanation),
+        ("Source", &explanation.source_explanation),
+        ("Fee", &explanation.fee_explanation),
+        ("Failure", &explanation.failure_reason),
+        ("Closed", &explanation.created_at),
+    ];
+    lines.extend(
+        optional
+            .into_iter()
+            .filter_map(|(label, value)| value.as_ref().map(|v| format!("{label}: {v}"))),
+    );
+    lines.join("\n")
+}
+
+#[cfg(feature = "llm-summary")]
+pub use http::{HttpSummarizer, SummarizerConfig};
+
+#[cfg(feature = "llm-summary")]
+mod http {
+    use async_trait::async_trait;
+    use reqwest::Client;
+    use serde_json::{Value, json};
+    use std::env;
+    use std::time::Duration;
+
+    use super::{DEFAULT_SUMMARY_BUDGET, Summarizer, prompt};
+    use crate::explain::transaction::TransactionExplanation;
+
+    const INSTRUCTIONS: &str = "You explain Stellar transactions to people who are new to them. \
+        Rewrite the facts you are given as one short, friendly paragraph. \
+        Use only those facts: do not add amounts, accounts, or reasons that are not listed.";
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SummarizerConfig {
+        pub url: String,
+        pub model: String,
+        pub api_key: Option<String>,
+        pub budget: Duration,
+    }
+
+    impl SummarizerConfig {
+        /// Read the summarizer settings, or `None` when no URL is set.
+        pub fn from_env() -> Option<Self> {
+            let url = env::var("SUMMARIZER_URL")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())?;
+            let model = env::var("SUMMARIZER_MODEL")
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+                .unwrap_or_else(|| "default".to_string());
+            let api_key = env::var("SUMMARIZER_API_KEY")
+                .ok()
+                .filter(|v| !v.trim().is_empty());
+            let budget = env::var("SUMMARIZER_TIMEOUT_MS")
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_SUMMARY_BUDGET);
+            Some(Self {
+                url,
+                model,
+                api_key,
+                budget,
+            })
+        }
+    }
+
+    /// A summarizer behind an OpenAI-compatible chat completions endpoint.
+    pub struct HttpSummarizer {
+        client: Client,
+        config: SummarizerConfig,
+    }
+
+    impl HttpSummarizer {
+        pub fn new(client: Client, config: SummarizerConfig) -> Self {
+            Self { client, config }
+        }
+    }
+
+    #[async_trait]
+    impl Summarizer for HttpSummarizer {
+        fn name(&self) -> &'static str {
+            "http"
+        }
+
+        async fn summarize(&self, explanation: &TransactionExplanation) -> Result<String, String> {
+            let body = json!({
+                "model": self.config.model,
+                "temperature": 0.2,
+                "messages": [
+                    { "role": "system", "content": INSTRUCTIONS },
+                    { "role": "user", "content": prompt(explanation) },
+                ],
+            });
+            let mut request = self
+                .client
+                .post(&self.config.url)
+                .timeout(self.config.budget)
+                .json(&body);
+            if let Some(key) = &self.config.api_key {
+                request = request.bearer_auth(key);
+            }
+            let response = request.send().await.map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("summarizer returned {}", response.status()));
+            }
+            let reply: Value = response.json().await.map_err(|e| e.to_string())?;
+            reply["choices"][0]["message"]["content"]
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| "summarizer reply has no message content".to_string())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::explain::transaction::explain_transaction;
+        use crate::models::operation::{Operation, PaymentOperation};
+        use crate::models::transaction::Transaction;
+        use httpmock::prelude::*;
+
+        fn explanation() -> TransactionExplanation {
+            let payment = Operation::Payment(PaymentOperation {
+                id: "1".to_string(),
+                source_account: Some("GA".to_string()),
+                destination: "GB".to_string(),
+                asset_type: "native".to_string(),
+                asset_code: None,
+                asset_issuer: None,
+                amount: "5".parse().unwrap(),
+            });
+            let tx = Transaction::new("abc".to_string(), true, 100, vec![payment], None, None);
+            explain_transaction(&tx, None).unwrap()
+        }
+
+        fn summarizer(server: &MockServer) -> HttpSummarizer {
+            HttpSummarizer::new(
+                Client::new(),
+                SummarizerConfig {
+                    url: server.url("/v1/chat/completions"),
+                    model: "tiny".to_string(),
+                    api_key: Some("secret".to_string()),
+                    budget: Duration::from_secs(2),
+                },
+            )
+        }
+
+        #[tokio::test]
+        async fn test_sends_facts_and_reads_the_reply() {
+            let server = MockServer::start();
+            let mock = server.mock(|when, then| {
+                when.method(POST)
+                    .path("/v1/chat/completions")
+                    .header("authorization", "Bearer secret")
+                    .body_contains("\"model\":\"tiny\"")
+                    .body_contains("Operation 1: GA sent 5 XLM");
+                then.status(200).json_body(json!({
+                    "choices": [{ "message": { "role": "assistant", "content": "GA paid GB 5 XLM." } }]
+                }));
+            });
+
+            let summary = summarizer(&server).summarize(&explanation()).await.unwrap();
+
+            mock.assert();
+            assert_eq!(summary, "GA paid GB 5 XLM.");
+        }
+
+        #[tokio::test]
+        async fn test_upstream_error_is_an_error() {
+            let server = MockServer::start();
+            server.mock(|when, then| {
+                when.method(POST);
+                then.status(503);
+            });
+
+            let err = summarizer(&server)
+                .summarize(&explanation())
+                .await
+                .unwrap_err();
+            assert!(err.contains("503"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::transaction::explain_transaction;
+    use crate::models::operation::{Operation, PaymentOperation};
+    use crate::models::transaction::Transaction;
+
+    struct Fixed(Result<&'static str, &'static str>, Duration);
+
+    #[async_trait]
+    impl Summarizer for Fixed {
+        fn name(&self) -> &'static str {
+            "fixed"
+        }
+
+        async fn summarize(&self, _: &TransactionExplanation) -> Result<String, String> {
+            tokio::time::sleep(self.1).await;
+            self.0.map(str::to_string).map_err(str::to_string)
+        }
+    }
+
+    fn explanation() -> TransactionExplanation {
+        let payment = Operation::Payment(PaymentOperation {
+            id: "1".to_string(),
+            source_account: Some("GA".to_string()),
+            destination: "GB".to_string(),
+            asset_type: "native".to_string(),
+            asset_code: None,
+            asset_issuer: None,
+            amount: "5".parse().unwrap(),
+        });
+        let tx = Transaction::new("abc".to_string(), true, 100, vec![payment], None, None);
+        explain_transaction(&tx, None).unwrap()
+    }
+
+    fn context(summarizer: Fixed) -> SummarizerContext {
+        SummarizerContext {
+            summarizer: Arc::new(summarizer),
+            budget: Duration::from_millis(100),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_summary_is_added_beside_the_deterministic_fields() {
+        let original = explanation();
+        let summarized = context(Fixed(Ok(" A friendly paragraph. "), Duration::ZERO))
+            .apply(original.clone())
+            .await;
+
+        assert_eq!(
+            summarized.generated_summary.as_deref(),
+            Some("A friendly paragraph.")
+        );
+        assert_eq!(summarized.summary, original.summary);
+        assert_eq!(summarized.operations, original.operations);
+    }
+
+    #[tokio::test]
+    async fn test_failures_and_timeouts_leave_the_explanation_alone() {
+        for summarizer in [
+            Fixed(Err("model unavailable"), Duration::ZERO),
+            Fixed(Ok("   "), Duration::ZERO),
+            Fixed(Ok("too late"), Duration::from_secs(5)),
+        ] {
+            let summarized = context(summarizer).apply(explanation()).await;
+            assert_eq!(summarized, explanation());
+        }
+    }
+
+    #[test]
+    fn test_prompt_lists_only_known_facts() {
+        let prompt = prompt(&explanation());
+        let lines: Vec<&str> = prompt.lines().collect();
+        assert!(lines[0].starts_with("Summary: "));
+        assert!(lines[1].starts_with("Operation 1: GA sent 5 XLM"));
+        assert!(!prompt.contains("Failure:"));
+    }
+}
@@ -1,7 +1,13 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fs;
 use std::hash::Hash;
+use std::io;
+use std::path::Path;
 use std::sync::{Arc, RwLock};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tracing::debug;
 
 /// Represents a Stellar network
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -12,6 +18,29 @@ pub enum Network {
     Custom(&'static str),
 }
 
+impl Network {
+    /// Name stored in snapshots.
+    fn name(self) -> &'static str {
+        match self {
+            Network::Public => "public",
+            Network::Testnet => "testnet",
+            Network::Futurenet => "futurenet",
+            Network::Custom(name) => name,
+        }
+    }
+
+    /// The network a snapshot names. Custom networks cannot be restored,
+    /// since their names are not known ahead of time.
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "public" => Some(Network::Public),
+            "testnet" => Some(Network::Testnet),
+            "futurenet" => Some(Network::Futurenet),
+            _ => None,
+        }
+    }
+}
+
 /// Cache key combining transaction hash and network
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CacheKey {
@@ -53,16 +82,26 @@ impl<T> CacheEntry<T> {
     }
 
     /// Get remaining time until expiration
-    #[allow(dead_code)]
     fn time_until_expiry(&self) -> Duration {
         self.ttl.saturating_sub(self.created_at.elapsed())
     }
 }
 
+/// Number of independently locked shards. A power of two comfortably above
+/// the worker thread count keeps two requests from often sharing a lock.
+const SHARDS: usize = 16;
+
+type Shard<T> = RwLock<HashMap<CacheKey, CacheEntry<T>>>;
+
 /// Thread-safe in-memory cache for transaction explanations
+///
+/// Entries are spread over [`SHARDS`] maps by key hash, each behind its own
+/// `RwLock`, so writers only block readers of the same shard. Reads never
+/// take a write lock: an expired entry is reported as missing and left for
+/// [`evict_expired`](Self::evict_expired) or the next insert of that key.
 pub struct TransactionCache<T> {
-    /// Internal cache storage with RwLock for safe concurrency
-    cache: Arc<RwLock<HashMap<CacheKey, CacheEntry<T>>>>,
+    /// Internal cache storage, one lock per shard
+    shards: Arc<[Shard<T>]>,
     /// Default TTL for new entries
     default_ttl: Duration,
 }
@@ -71,7 +110,7 @@ impl<T: Clone> TransactionCache<T> {
     /// Create a new cache with default TTL
     pub fn new(default_ttl: Duration) -> Self {
         Self {
-            cache: Arc::new(RwLock::new(HashMap::new())),
+            shards: (0..SHARDS).map(|_| RwLock::new(HashMap::new())).collect(),
             default_ttl,
         }
     }
@@ -81,55 +120,53 @@ impl<T: Clone> TransactionCache<T> {
         Self::new(Duration::from_secs(5 * 60))
     }
 
+    /// Transaction hashes are uniformly random hex, so their last few bytes
+    /// spread keys over the shards as well as hashing the whole key would,
+    /// at a fraction of the cost.
+    fn shard(&self, key: &CacheKey) -> &Shard<T> {
+        let bytes = key.tx_hash.as_bytes();
+        let tail = &bytes[bytes.len().saturating_sub(8)..];
+        let hash = tail.iter().fold(0x811c_9dc5_u32, |hash, byte| {
+            (hash ^ u32::from(*byte)).wrapping_mul(0x0100_0193)
+        });
+        &self.shards[hash as usize % SHARDS]
+    }
+
     /// Insert or update a cache entry
     ///
     /// Returns true if this is a new entry, false if updating existing
     pub fn insert(&self, key: CacheKey, value: T) -> bool {
-        let mut cache = self.cache.write().unwrap();
-        let entry = CacheEntry::new(value, self.default_ttl);
-        cache.insert(key, entry).is_none()
+        self.insert_with_ttl(key, value, self.default_ttl)
     }
 
     /// Insert with custom TTL
+    ///
+    /// Replacing an expired entry counts as a new entry.
     pub fn insert_with_ttl(&self, key: CacheKey, value: T, ttl: Duration) -> bool {
-        let mut cache = self.cache.write().unwrap();
+        let mut shard = self.shard(&key).write().unwrap();
         let entry = CacheEntry::new(value, ttl);
-        cache.insert(key, entry).is_none()
+        shard
+            .insert(key, entry)
+            .is_none_or(|previous| previous.is_expired())
     }
 
     /// Get a value from the cache
     ///
     /// Returns None if:
     /// - Key doesn't exist
-    /// - Entry has expired (also removes it)
+    /// - Entry has expired
     pub fn get(&self, key: &CacheKey) -> Option<T> {
-        // First, check with read lock (fast path)
-        {
-            let cache = self.cache.read().unwrap();
-            if let Some(entry) = cache.get(key)
-                && !entry.is_expired()
-            {
-                return Some(entry.value.clone());
-            }
-        }
-
-        // If expired or not found, acquire write lock to clean up
-        let mut cache = self.cache.write().unwrap();
-        if let Some(entry) = cache.get(key) {
-            if entry.is_expired() {
-                cache.remove(key);
-                return None;
-            }
-            Some(entry.value.clone())
-        } else {
-            None
-        }
+        let shard = self.shard(key).read().unwrap();
+        shard
+            .get(key)
+            .filter(|entry| !entry.is_expired())
+            .map(|entry| entry.value.clone())
     }
 
     /// Check if a key exists and is not expired
     pub fn contains_key(&self, key: &CacheKey) -> bool {
-        let cache = self.cache.read().unwrap();
-        cache
+        let shard = self.shard(key).read().unwrap();
+        shard
             .get(key)
             .map(|entry| !entry.is_expired())
             .unwrap_or(false)
@@ -137,43 +174,56 @@ impl<T: Clone> TransactionCache<T> {
 
     /// Remove an entry from the cache
     pub fn remove(&self, key: &CacheKey) -> Option<T> {
-        let mut cache = self.cache.write().unwrap();
-        cache.remove(key).map(|entry| entry.value)
+        let mut shard = self.shard(key).write().unwrap();
+        shard.remove(key).map(|entry| entry.value)
     }
 
     /// Clear all entries from the cache
     pub fn clear(&self) {
-        let mut cache = self.cache.write().unwrap();
-        cache.clear();
+        for shard in self.shards.iter() {
+            shard.write().unwrap().clear();
+        }
     }
 
     /// Remove all expired entries (garbage collection)
     ///
-    /// Returns the number of entries removed
+    /// Locks one shard at a time. Returns the number of entries removed
     pub fn evict_expired(&self) -> usize {
-        let mut cache = self.cache.write().unwrap();
-        let initial_len = cache.len();
-        cache.retain(|_, entry| !entry.is_expired());
-        initial_len - cache.len()
+        self.shards
+            .iter()
+            .map(|shard| {
+                let mut shard = shard.write().unwrap();
+                let initial_len = shard.len();
+                shard.retain(|_, entry| !entry.is_expired());
+                initial_len - shard.len()
+            })
+            .sum()
     }
 
     /// Get the number of entries in the cache (including expired)
     pub fn len(&self) -> usize {
-        let cache = self.cache.read().unwrap();
-        cache.len()
+        self.shards
+            .iter()
+            .map(|shard| shard.read().unwrap().len())
+            .sum()
     }
 
     /// Check if the cache is empty
     pub fn is_empty(&self) -> bool {
-        let cache = self.cache.read().unwrap();
-        cache.is_empty()
+        self.shards
+            .iter()
+            .all(|shard| shard.read().unwrap().is_empty())
     }
 
     /// Get cache statistics
     pub fn stats(&self) -> CacheStats {
-        let cache = self.cache.read().unwrap();
-        let total = cache.len();
-        let expired = cache.values().filter(|e| e.is_expired()).count();
+        let (total, expired) = self.shards.iter().fold((0, 0), |(total, expired), shard| {
+            let shard = shard.read().unwrap();
+            (
+                total + shard.len(),
+                expired + shard.values().filter(|e| e.is_expired()).count(),
+            )
+        });
 
         CacheStats {
             total_entries: total,
@@ -183,6 +233,81 @@ impl<T: Clone> TransactionCache<T> {
     }
 }
 
+/// A valid entry as written to a snapshot. Expiry is wall-clock time so it
+/// survives a restart.
+#[derive(Serialize, Deserialize)]
+struct SnapshotEntry<T> {
+    tx_hash: String,
+    network: String,
+    expires_at_ms: u64,
+    value: T,
+}
+
+fn unix_ms(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+impl<T: Clone + Serialize> TransactionCache<T> {
+    /// Write every valid entry to `path` as JSON, replacing the file
+    /// atomically. Returns the number of entries written.
+    pub fn save_snapshot(&self, path: &Path) -> io::Result<usize> {
+        let now = unix_ms(SystemTime::now());
+        let entries: Vec<SnapshotEntry<T>> = self
+            .shards
+            .iter()
+            .flat_map(|shard| {
+                let shard = shard.read().unwrap();
+                shard
+                    .iter()
+                    .filter(|(_, entry)| !entry.is_expired())
+                    .map(|(key, entry)| SnapshotEntry {
+                        tx_hash: key.tx_hash.clone(),
+                        network: key.network.name().to_string(),
+                        expires_at_ms: now + entry.time_until_expiry().as_millis() as u64,
+                        value: entry.value.clone(),
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let json = serde_json::to_vec(&entries).map_err(io::Error::other)?;
+        let partial = path.with_extension("tmp");
+        fs::write(&partial, json)?;
+        fs::rename(&partial, path)?;
+        Ok(entries.len())
+    }
+}
+
+impl<T: Clone + DeserializeOwned> TransactionCache<T> {
+    /// Insert the entries of a snapshot written by
+    /// [`save_snapshot`](Self::save_snapshot) that have not expired since,
+    /// each with the time it had left. Returns the number restored.
+    pub fn load_snapshot(&self, path: &Path) -> io::Result<usize> {
+        let source = fs::read(path)?;
+        let entries: Vec<SnapshotEntry<T>> =
+            serde_json::from_slice(&source).map_err(io::Error::other)?;
+        let now = unix_ms(SystemTime::now());
+        let mut restored = 0;
+        for entry in entries {
+            let Some(network) = Network::from_name(&entry.network) else {
+                continue;
+            };
+            if entry.expires_at_ms <= now {
+                continue;
+            }
+            self.insert_with_ttl(
+                CacheKey::new(entry.tx_hash, network),
+                entry.value,
+                Duration::from_millis(entry.expires_at_ms - now),
+            );
+            restored += 1;
+        }
+        Ok(restored)
+    }
+}
+
 /// Cache statistics
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct CacheStats {
@@ -191,11 +316,22 @@ pub struct CacheStats {
     pub valid_entries: usize,
 }
 
+/// Run forever, calling `evict` every `interval`. Caches built on
+/// [`TransactionCache`] evict here rather than on insert, so a miss never
+/// has to lock and scan every shard.
+pub async fn run_eviction<F: Fn() -> usize>(cache: &'static str, interval: Duration, evict: F) {
+    loop {
+        tokio::time::sleep(interval).await;
+        let evicted = evict();
+        debug!(cache, evicted, "cache_evicted");
+    }
+}
+
 /// Clone implementation for thread-safe sharing
 impl<T> Clone for TransactionCache<T> {
     fn clone(&self) -> Self {
         Self {
-            cache: Arc::clone(&self.cache),
+            shards: Arc::clone(&self.shards),
             default_ttl: self.default_ttl,
         }
     }
@@ -288,6 +424,23 @@ mod tests {
         assert!(!cache.contains_key(&key));
     }
 
+    #[test]
+    fn test_expired_read_leaves_entry_for_eviction() {
+        let cache: TransactionCache<String> = TransactionCache::new(Duration::from_secs(60));
+        let key = CacheKey::new("expired".to_string(), Network::Public);
+
+        cache.insert_with_ttl(key.clone(), "old".to_string(), Duration::ZERO);
+
+        // Reads only take a read lock, so the expired entry stays in place...
+        assert_eq!(cache.get(&key), None);
+        assert_eq!(cache.len(), 1);
+
+        // ...until it is replaced, which counts as a fresh insert.
+        assert!(cache.insert(key.clone(), "new".to_string()));
+        assert_eq!(cache.get(&key), Some("new".to_string()));
+        assert_eq!(cache.evict_expired(), 0);
+    }
+
     #[test]
     fn test_custom_ttl() {
         let cache: TransactionCache<String> = TransactionCache::new(Duration::from_secs(60));
@@ -368,6 +521,24 @@ mod tests {
         assert_eq!(cache.len(), 1);
     }
 
+    #[tokio::test]
+    async fn test_run_eviction_drops_expired_entries() {
+        let cache: TransactionCache<String> = TransactionCache::new(Duration::from_millis(10));
+        cache.insert(
+            CacheKey::new("tx1".to_string(), Network::Public),
+            "val1".to_string(),
+        );
+
+        let evicting = cache.clone();
+        let task = tokio::spawn(run_eviction("test", Duration::from_millis(20), move || {
+            evicting.evict_expired()
+        }));
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        task.abort();
+
+        assert_eq!(cache.len(), 0);
+    }
+
     #[test]
     fn test_cache_stats() {
         let cache: TransactionCache<String> = TransactionCache::new(Duration::from_millis(100));
@@ -502,4 +673,34 @@ mod tests {
         assert_eq!(evicted, 1000);
         assert_eq!(cache.len(), 0);
     }
+
+    #[test]
+    fn test_snapshot_restores_valid_entries() {
+        let path = std::env::temp_dir().join(format!("cache-{}.json", uuid::Uuid::new_v4()));
+        let cache: TransactionCache<String> = TransactionCache::new(Duration::from_secs(60));
+        cache.insert(
+            CacheKey::new("live".to_string(), Network::Testnet),
+            "kept".to_string(),
+        );
+        cache.insert_with_ttl(
+            CacheKey::new("stale".to_string(), Network::Testnet),
+            "dropped".to_string(),
+            Duration::from_millis(1),
+        );
+        cache.insert(
+            CacheKey::new("custom".to_string(), Network::Custom("local")),
+            "unrestorable".to_string(),
+        );
+        thread::sleep(Duration::from_millis(5));
+
+        assert_eq!(cache.save_snapshot(&path).unwrap(), 2);
+
+        let restored: TransactionCache<String> = TransactionCache::new(Duration::from_secs(60));
+        assert_eq!(restored.load_snapshot(&path).unwrap(), 1);
+        assert_eq!(
+            restored.get(&CacheKey::new("live".to_string(), Network::Testnet)),
+            Some("kept".to_string())
+        );
+        fs::remove_file(path).unwrap();
+    }
 }
@@ -0,0 +1,373 @@
+//! Keep explanations of trending transactions warm.
+//!
+//! When enabled, `/tx/:hash` counts lookups per hash and keeps the
+//! explanations it builds in memory for `WARMUP_TTL_SECONDS`. A background
+//! task re-explains the most looked-up hashes of the last hour every
+//! `WARMUP_INTERVAL_SECONDS`, before their entries expire, so a spike of
+//! explorer traffic for a trending transaction is answered from memory
+//! rather than from Horizon. Transactions the ingestion worker has seen are
+//! answered from the index already and are not prefetched.
+//!
+//! Configuration (environment):
+//! - `WARMUP_ENABLED`: `true` or `1` to cache and prefetch (default off).
+//! - `WARMUP_TOP_N`: hashes prefetched per run (default 20).
+//! - `WARMUP_INTERVAL_SECONDS`: time between runs (default 60).
+//! - `WARMUP_TTL_SECONDS`: how long an explanation is served from memory
+//!   (default 300). Raised to at least twice the interval so prefetched
+//!   entries do not lapse between runs.
+//! - `CACHE_SNAPSHOT_PATH`: file the warm explanations are saved to and
+//!   reloaded from on startup, so a deploy does not send every trending
+//!   lookup back to Horizon at once (default off).
+//! - `CACHE_SNAPSHOT_INTERVAL_SECONDS`: time between saves (default 60).
+
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
+
+use crate::explain::transaction::TransactionExplanation;
+use crate::services::explain::fetch_explanation;
+use crate::services::horizon_api::HorizonApi;
+use crate::services::provider::LedgerDataProvider;
+use crate::services::storage::ExplanationStore;
+use crate::services::transaction_cache::{CacheKey, Network, TransactionCache};
+
+/// Lookups older than this no longer count toward trending.
+const TRENDING_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Lookups remembered at most; the oldest are dropped first.
+const MAX_LOOKUPS: usize = 100_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WarmupConfig {
+    pub top_n: usize,
+    pub interval: Duration,
+    pub ttl: Duration,
+}
+
+impl Default for WarmupConfig {
+    fn default() -> Self {
+        Self {
+            top_n: 20,
+            interval: Duration::from_secs(60),
+            ttl: Duration::from_secs(300),
+        }
+    }
+}
+
+impl WarmupConfig {
+    /// Read the warm-up settings, or `None` when warm-up is not enabled.
+    pub fn from_env() -> Option<Self> {
+        let enabled = env::var("WARMUP_ENABLED")
+            .map(|v| matches!(v.trim().to_ascii_lowercase().as_str(), "true" | "1"))
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+
+        let defaults = Self::default();
+        let parse = |name: &str| {
+            env::var(name)
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+        };
+        let interval = parse("WARMUP_INTERVAL_SECONDS")
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.interval);
+        Some(Self {
+            top_n: parse("WARMUP_TOP_N")
+                .map(|n| n as usize)
+                .unwrap_or(defaults.top_n),
+            interval,
+            ttl: parse("WARMUP_TTL_SECONDS")
+                .map(Duration::from_secs)
+                .unwrap_or(defaults.ttl)
+                .max(interval * 2),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotConfig {
+    pub path: PathBuf,
+    pub interval: Duration,
+}
+
+impl SnapshotConfig {
+    /// Read the snapshot settings, or `None` when no path is set.
+    pub fn from_env() -> Option<Self> {
+        let path = env::var("CACHE_SNAPSHOT_PATH")
+            .ok()
+            .filter(|v| !v.trim().is_empty())?;
+        Some(Self {
+            path: path.trim().into(),
+            interval: env::var("CACHE_SNAPSHOT_INTERVAL_SECONDS")
+                .ok()
+                .and_then(|v| v.trim().parse::<u64>().ok())
+                .filter(|n| *n > 0)
+                .map(Duration::from_secs)
+                .unwrap_or(Duration::from_secs(60)),
+        })
+    }
+}
+
+/// Recent lookups and the explanations kept warm for them.
+pub struct WarmCache {
+    network: Network,
+    explanations: TransactionCache<TransactionExplanation>,
+    lookups: Mutex<VecDeque<(Instant, String)>>,
+}
+
+impl WarmCache {
+    pub fn new(network: Network, ttl: Duration) -> Self {
+        Self {
+            network,
+            explanations: TransactionCache::new(ttl),
+            lookups: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Count a lookup of `hash` toward trending.
+    pub fn record_lookup(&self, hash: &str) {
+        let mut lookups = self.lock();
+        if lookups.len() >= MAX_LOOKUPS {
+            lookups.pop_front();
+        }
+        lookups.push_back((Instant::now(), hash.to_string()));
+    }
+
+    /// The `n` hashes looked up most in the last hour, most first.
+    pub fn trending(&self, n: usize) -> Vec<String> {
+        let mut lookups = self.lock();
+        while lookups
+            .front()
+            .is_some_and(|(at, _)| at.elapsed() > TRENDING_WINDOW)
+        {
+            lookups.pop_front();
+        }
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for (_, hash) in lookups.iter() {
+            *counts.entry(hash).or_default() += 1;
+        }
+        let mut ranked: Vec<(&str, usize)> = counts.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        ranked
+            .into_iter()
+            .take(n)
+            .map(|(hash, _)| hash.to_string())
+            .collect()
+    }
+
+    pub fn get(&self, hash: &str) -> Option<TransactionExplanation> {
+        self.explanations.get(&self.key(hash))
+    }
+
+    /// Keep `explanation` unless it carries warnings: a partial explanation
+    /// would otherwise be served as a hit until it expired, with nothing
+    /// fetched again. Returns whether it was kept.
+    pub fn insert(&self, hash: &str, explanation: TransactionExplanation) -> bool {
+        if !explanation.warnings.is_empty() {
+            return false;
+        }
+        self.explanations.insert(self.key(hash), explanation);
+        true
+    }
+
+    /// Drop expired explanations. Returns how many.
+    pub fn evict_expired(&self) -> usize {
+        self.explanations.evict_expired()
+    }
+
+    /// Save the unexpired explanations to `path`. Returns how many.
+    pub fn save_snapshot(&self, path: &Path) -> io::Result<usize> {
+        self.explanations.save_snapshot(path)
+    }
+
+    /// Reload explanations saved by [`save_snapshot`](Self::save_snapshot)
+    /// that are still fresh. Returns how many.
+    pub fn load_snapshot(&self, path: &Path) -> io::Result<usize> {
+        self.explanations.load_snapshot(path)
+    }
+
+    fn key(&self, hash: &str) -> CacheKey {
+        CacheKey::new(hash.to_string(), self.network)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<(Instant, String)>> {
+        self.lookups.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Explain the trending hashes once, skipping those the index answers.
+/// Returns how many explanations were refreshed.
+pub async fn warm<H: HorizonApi>(
+    cache: &WarmCache,
+    provider: &Arc<dyn LedgerDataProvider>,
+    horizon_client: &Arc<H>,
+    store: Option<&ExplanationStore>,
+    top_n: usize,
+) -> usize {
+    let mut warmed = 0;
+    for hash in cache.trending(top_n) {
+        if store.is_some_and(|store| matches!(store.get_explanation(&hash), Ok(Some(_)))) {
+            continue;
+        }
+        match fetch_explanation(&hash, Arc::clone(provider), Arc::clone(horizon_client)).await {
+            Ok(explanation) => {
+                if cache.insert(&hash, explanation) {
+                    warmed += 1;
+                }
+            }
+            Err(err) => warn!(hash = %hash, error = ?err, "warmup_explain_failed"),
+        }
+    }
+    warmed
+}
+
+/// Run forever, re-explaining trending transactions every `config.interval`
+/// and then dropping expired explanations.
+pub async fn run_warmup<H: HorizonApi>(
+    cache: Arc<WarmCache>,
+    provider: Arc<dyn LedgerDataProvider>,
+    horizon_client: Arc<H>,
+    store: Option<Arc<ExplanationStore>>,
+    config: WarmupConfig,
+) {
+    loop {
+        tokio::time::sleep(config.interval).await;
+        let started_at = Instant::now();
+        let warmed = warm(
+            &cache,
+            &provider,
+            &horizon_client,
+            store.as_deref(),
+            config.top_n,
+        )
+        .await;
+        let evicted = cache.evict_expired();
+        info!(
+            warmed,
+            evicted,
+            duration_ms = started_at.elapsed().as_millis() as u64,
+            "warmup_completed"
+        );
+    }
+}
+
+/// Run forever, saving the warm explanations every `config.interval`.
+pub async fn run_snapshots(cache: Arc<WarmCache>, config: SnapshotConfig) {
+    loop {
+        tokio::time::sleep(config.interval).await;
+        match cache.save_snapshot(&config.path) {
+            Ok(entries) => info!(entries, "cache_snapshot_saved"),
+            Err(err) => {
+                warn!(path = %config.path.display(), error = %err, "cache_snapshot_not_saved")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::explain::transaction::{ExplanationWarning, WarningCode};
+    use crate::models::fee::FeeStats;
+    use crate::services::horizon::{HorizonOperation, HorizonTransaction};
+    use crate::services::horizon_api::FakeHorizon;
+
+    const HOT: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+    const WARM: &str = "bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb";
+    const COLD: &str = "cccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccccc";
+
+    fn cache() -> WarmCache {
+        WarmCache::new(Network::Testnet, Duration::from_secs(300))
+    }
+
+    #[test]
+    fn test_trending_ranks_by_lookups() {
+        let cache = cache();
+        for hash in [COLD, HOT, WARM, HOT, HOT, WARM] {
+            cache.record_lookup(hash);
+        }
+
+        assert_eq!(cache.trending(2), vec![HOT.to_string(), WARM.to_string()]);
+    }
+
+    fn horizon() -> Arc<FakeHorizon> {
+        Arc::new(
+            FakeHorizon::new()
+                .with_fee_stats(FeeStats::default_network_fees())
+                .with_transaction(
+                    HorizonTransaction {
+                        hash: HOT.to_string(),
+                        successful: true,
+                        fee_charged: "100".to_string(),
+                        ..Default::default()
+                    },
+                    vec![HorizonOperation {
+                        id: "1".to_string(),
+                        transaction_hash: HOT.to_string(),
+                        operation_type: "payment".to_string(),
+                        from: Some("GA".to_string()),
+                        to: Some("GB".to_string()),
+                        amount: Some("5.0".to_string()),
+                        asset_type: Some("native".to_string()),
+                        ..Default::default()
+                    }],
+                ),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_warm_explains_trending_transactions() {
+        let horizon = horizon();
+        let provider: Arc<dyn LedgerDataProvider> = Arc::clone(&horizon) as _;
+        let cache = cache();
+        for hash in [HOT, HOT, COLD] {
+            cache.record_lookup(hash);
+        }
+
+        let warmed = warm(&cache, &provider, &horizon, None, 5).await;
+
+        // The unknown hash fails and is left cold.
+        assert_eq!(warmed, 1);
+        assert_eq!(cache.get(HOT).unwrap().transaction_hash, HOT);
+        assert!(cache.get(COLD).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_partial_explanations_are_not_kept() {
+        let horizon = horizon();
+        let provider: Arc<dyn LedgerDataProvider> = Arc::clone(&horizon) as _;
+        let mut explanation = fetch_explanation(HOT, provider, horizon).await.unwrap();
+        explanation.warnings.push(ExplanationWarning {
+            code: WarningCode::OperationsUnavailable,
+            message: "The operations could not be fetched.".to_string(),
+        });
+
+        let cache = cache();
+        assert!(!cache.insert(HOT, explanation));
+        assert!(cache.get(HOT).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_survives_restart() {
+        let path = env::temp_dir().join(format!("warm-{}.json", uuid::Uuid::new_v4()));
+        let horizon = horizon();
+        let provider: Arc<dyn LedgerDataProvider> = Arc::clone(&horizon) as _;
+        let before = cache();
+        before.record_lookup(HOT);
+        warm(&before, &provider, &horizon, None, 1).await;
+        let explanation = before.get(HOT).unwrap();
+        assert_eq!(before.save_snapshot(&path).unwrap(), 1);
+
+        let after = cache();
+        assert_eq!(after.load_snapshot(&path).unwrap(), 1);
+        assert_eq!(after.get(HOT), Some(explanation));
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,92 @@
+//! Golden tests for explanation wording.
+//!
+//! Every file in `tests/fixtures/horizon` is an anonymized Horizon
+//! transaction with its operations. Each is explained the way `/tx/:hash`
+//! explains it and compared with its snapshot in `tests/snapshots`, so any
+//! change to the wording shows up as a snapshot diff to review.
+//!
+//! Accept intended changes with `INSTA_UPDATE=always cargo test --test
+//! explanation_snapshots` (or `cargo insta review`). Regenerate the fixtures
+//! from testnet with `cargo xtask fixtures`.
+
+use serde::Deserialize;
+use stellar_explain_core::explain::transaction::explain_transaction_with_ledger;
+use stellar_explain_core::services::explain::map_transaction_to_domain;
+use stellar_explain_core::services::horizon::{HorizonOperation, HorizonTransaction};
+
+#[derive(Deserialize)]
+struct Fixture {
+    transaction: HorizonTransaction,
+    operations: Vec<HorizonOperation>,
+}
+
+fn load(path: &std::path::Path) -> Fixture {
+    let text = std::fs::read_to_string(path).unwrap();
+    serde_json::from_str(&text).unwrap_or_else(|err| panic!("{}: {err}", path.display()))
+}
+
+#[test]
+fn explanations_match_snapshots() {
+    insta::glob!("fixtures/horizon/*.json", |path| {
+        let Fixture {
+            transaction,
+            operations,
+        } = load(path);
+        let created_at = transaction.created_at.clone();
+        let ledger = transaction.ledger;
+        let domain = map_transaction_to_domain(transaction, operations);
+
+        let explanation =
+            explain_transaction_with_ledger(&domain, None, created_at.as_deref(), ledger)
+                .unwrap_or_else(|err| panic!("{}: {err:?}", path.display()));
+        insta::assert_json_snapshot!(explanation);
+    });
+}
+
+#[test]
+fn fixtures_cover_every_operation_type() {
+    let mut seen = Vec::new();
+    for entry in std::fs::read_dir(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/horizon"
+    ))
+    .unwrap()
+    {
+        let fixture = load(&entry.unwrap().path());
+        seen.extend(fixture.operations.into_iter().map(|op| op.operation_type));
+    }
+
+    let missing: Vec<&str> = [
+        "create_account",
+        "payment",
+        "path_payment_strict_receive",
+        "manage_sell_offer",
+        "create_passive_sell_offer",
+        "set_options",
+        "change_trust",
+        "allow_trust",
+        "account_merge",
+        "inflation",
+        "manage_data",
+        "bump_sequence",
+        "manage_buy_offer",
+        "path_payment_strict_send",
+        "create_claimable_balance",
+        "claim_claimable_balance",
+        "begin_sponsoring_future_reserves",
+        "end_sponsoring_future_reserves",
+        "revoke_sponsorship",
+        "clawback",
+        "clawback_claimable_balance",
+        "set_trust_line_flags",
+        "liquidity_pool_deposit",
+        "liquidity_pool_withdraw",
+        "invoke_host_function",
+        "extend_footprint_ttl",
+        "restore_footprint",
+    ]
+    .into_iter()
+    .filter(|kind| !seen.iter().any(|s| s == kind))
+    .collect();
+    assert!(missing.is_empty(), "no fixture for {missing:?}");
+}
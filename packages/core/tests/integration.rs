@@ -1,2 +1,8 @@
-// Integration tests live in tests/integration/mod.rs
-// This file intentionally left empty to avoid module ambiguity.
+//! Route-level tests: requests to a running server backed by a mocked
+//! Horizon. The tests are in `integration/mod.rs`, and the server they talk
+//! to is built with `support::TestApp`.
+
+mod support;
+
+#[path = "integration/mod.rs"]
+mod routes;
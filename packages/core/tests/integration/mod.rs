@@ -1,94 +1,19 @@
-use std::{sync::Arc, time::Duration};
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
 
-use axum::{middleware, routing::get, Router};
 use reqwest::StatusCode;
-use serde_json::{json, Value};
+use serde_json::{Value, json};
 use stellar_explain_core::{
-    middleware::request_id::request_id_middleware,
-    routes::tx::get_tx_explanation,
-    services::horizon::HorizonClient,
+    explain::transaction::TransactionExplanation, middleware::rate_limit::RateLimitConfig,
+    services::storage::ExplanationStore,
 };
-use tokio::net::TcpListener;
-use wiremock::{
-    matchers::{method, path},
-    Mock, MockServer, ResponseTemplate,
-};
-
-fn test_hash(seed: char) -> String {
-    std::iter::repeat(seed).take(64).collect()
-}
-
-async fn spawn_app(horizon_base_url: &str) -> String {
-    let listener = TcpListener::bind("127.0.0.1:0")
-        .await
-        .expect("failed to bind ephemeral port");
-    let addr = listener.local_addr().expect("failed to read local addr");
-
-    let app = Router::new()
-        .route("/tx/:hash", get(get_tx_explanation))
-        .with_state(Arc::new(HorizonClient::new(horizon_base_url.to_string())))
-        .layer(middleware::from_fn(request_id_middleware));
-
-    tokio::spawn(async move {
-        axum::serve(listener, app)
-            .await
-            .expect("server failed unexpectedly");
-    });
-
-    // Small delay to avoid request race with startup.
-    tokio::time::sleep(Duration::from_millis(40)).await;
-
-    format!("http://{}", addr)
-}
-
-async fn mock_fee_stats(server: &MockServer) {
-    Mock::given(method("GET"))
-        .and(path("/fee_stats"))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "last_ledger_base_fee": "100",
-            "fee_charged": {
-                "min": "100",
-                "max": "1000",
-                "mode": "100",
-                "p90": "250"
-            }
-        })))
-        .mount(server)
-        .await;
-}
-
-async fn mock_transaction(
-    server: &MockServer,
-    hash: &str,
-    successful: bool,
-    fee_charged: &str,
-    memo_type: Option<&str>,
-    memo: Option<&str>,
-) {
-    Mock::given(method("GET"))
-        .and(path(format!("/transactions/{hash}")))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "hash": hash,
-            "successful": successful,
-            "fee_charged": fee_charged,
-            "memo_type": memo_type,
-            "memo": memo,
-        })))
-        .mount(server)
-        .await;
-}
+use wiremock::MockServer;
 
-async fn mock_operations(server: &MockServer, hash: &str, operations: Value) {
-    Mock::given(method("GET"))
-        .and(path(format!("/transactions/{hash}/operations")))
-        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
-            "_embedded": {
-                "records": operations,
-            }
-        })))
-        .mount(server)
-        .await;
-}
+use crate::support::{
+    TestApp, mock_account_transactions, mock_fee_stats, mock_operations, mock_status,
+    mock_transaction, test_hash, warm_cache,
+};
 
 #[tokio::test]
 async fn successful_payment_transaction_returns_transaction_explanation_json() {
@@ -114,22 +39,30 @@ async fn successful_payment_transaction_returns_transaction_explanation_json() {
     )
     .await;
 
-    let app_url = spawn_app(&horizon_mock.uri()).await;
-    let response = reqwest::get(format!("{app_url}/tx/{hash}"))
-        .await
-        .expect("request failed");
+    let app = TestApp::builder()
+        .with_horizon_mock(&horizon_mock)
+        .spawn()
+        .await;
+    let response = app.get(&format!("/tx/{hash}")).await;
 
     assert_eq!(response.status(), StatusCode::OK);
 
     let payload: Value = response.json().await.expect("json parse failed");
     assert_eq!(payload["transaction_hash"], hash);
     assert_eq!(payload["successful"], true);
-    assert!(payload["summary"].as_str().unwrap_or_default().contains("payment"));
+    assert!(
+        payload["summary"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("payment")
+    );
     assert_eq!(payload["payment_explanations"][0]["amount"], "500.0000000");
-    assert!(payload["payment_explanations"][0]["summary"]
-        .as_str()
-        .unwrap_or_default()
-        .contains("Coinbase"));
+    assert!(
+        payload["payment_explanations"][0]["summary"]
+            .as_str()
+            .unwrap_or_default()
+            .contains("Coinbase")
+    );
 }
 
 #[tokio::test]
@@ -164,10 +97,11 @@ async fn transaction_with_memo_returns_memo_explanation() {
     )
     .await;
 
-    let app_url = spawn_app(&horizon_mock.uri()).await;
-    let response = reqwest::get(format!("{app_url}/tx/{hash}"))
-        .await
-        .expect("request failed");
+    let app = TestApp::builder()
+        .with_horizon_mock(&horizon_mock)
+        .spawn()
+        .await;
+    let response = app.get(&format!("/tx/{hash}")).await;
 
     assert_eq!(response.status(), StatusCode::OK);
 
@@ -181,22 +115,19 @@ async fn non_existent_transaction_hash_returns_404_json_error() {
     let horizon_mock = MockServer::start().await;
     let hash = test_hash('c');
 
-    Mock::given(method("GET"))
-        .and(path(format!("/transactions/{hash}")))
-        .respond_with(ResponseTemplate::new(404))
-        .mount(&horizon_mock)
-        .await;
+    mock_status(&horizon_mock, &format!("/transactions/{hash}"), 404).await;
+    mock_status(
+        &horizon_mock,
+        &format!("/transactions/{hash}/operations"),
+        404,
+    )
+    .await;
 
-    Mock::given(method("GET"))
-        .and(path(format!("/transactions/{hash}/operations")))
-        .respond_with(ResponseTemplate::new(404))
-        .mount(&horizon_mock)
+    let app = TestApp::builder()
+        .with_horizon_mock(&horizon_mock)
+        .spawn()
         .await;
-
-    let app_url = spawn_app(&horizon_mock.uri()).await;
-    let response = reqwest::get(format!("{app_url}/tx/{hash}"))
-        .await
-        .expect("request failed");
+    let response = app.get(&format!("/tx/{hash}")).await;
 
     assert_eq!(response.status(), StatusCode::NOT_FOUND);
 
@@ -228,10 +159,11 @@ async fn create_account_transaction_returns_create_account_explanation() {
     )
     .await;
 
-    let app_url = spawn_app(&horizon_mock.uri()).await;
-    let response = reqwest::get(format!("{app_url}/tx/{hash}"))
-        .await
-        .expect("request failed");
+    let app = TestApp::builder()
+        .with_horizon_mock(&horizon_mock)
+        .spawn()
+        .await;
+    let response = app.get(&format!("/tx/{hash}")).await;
 
     assert_eq!(response.status(), StatusCode::OK);
 
@@ -244,15 +176,217 @@ async fn create_account_transaction_returns_create_account_explanation() {
 
 #[tokio::test]
 async fn invalid_hash_format_returns_400_json_error() {
-    let horizon_mock = MockServer::start().await;
-    let app_url = spawn_app(&horizon_mock.uri()).await;
+    let app = TestApp::builder().spawn().await;
 
-    let response = reqwest::get(format!("{app_url}/tx/not-a-valid-stellar-hash"))
-        .await
-        .expect("request failed");
+    let response = app.get("/tx/not-a-valid-stellar-hash").await;
 
     assert_eq!(response.status(), StatusCode::BAD_REQUEST);
 
     let payload: Value = response.json().await.expect("json parse failed");
     assert_eq!(payload["error"]["code"], "BAD_REQUEST");
 }
+
+#[tokio::test]
+async fn hide_spam_removes_incoming_dust_and_reports_counts() {
+    let horizon_mock = MockServer::start().await;
+    let account = "GACCOUNTAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+    let dust_hash = test_hash('d');
+    let own_hash = test_hash('e');
+
+    mock_account_transactions(
+        &horizon_mock,
+        account,
+        json!([
+            {
+                "hash": dust_hash,
+                "successful": true,
+                "created_at": "2025-03-03T00:00:00Z",
+                "source_account": "GSENDERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+                "operation_count": 1
+            },
+            {
+                "hash": own_hash,
+                "successful": true,
+                "created_at": "2025-03-03T00:01:00Z",
+                "source_account": account,
+                "operation_count": 1
+            }
+        ]),
+    )
+    .await;
+    mock_operations(
+        &horizon_mock,
+        &dust_hash,
+        json!([{
+            "id": "1",
+            "transaction_hash": dust_hash,
+            "type": "payment",
+            "to": account,
+            "amount": "0.0000001",
+            "asset_type": "native"
+        }]),
+    )
+    .await;
+
+    let app = TestApp::builder()
+        .with_horizon_mock(&horizon_mock)
+        .spawn()
+        .await;
+
+    let response = app
+        .get(&format!("/account/{account}/transactions?hide_spam=true"))
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let payload: Value = response.json().await.expect("invalid json");
+    let items = payload["items"].as_array().expect("items missing");
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0]["hash"], own_hash);
+    assert_eq!(payload["hidden"]["dust"], 1);
+    assert_eq!(payload["hidden"]["total"], 1);
+    assert_eq!(payload["page_size"], 10);
+    assert_eq!(
+        payload["links"]["self"],
+        app.url(&format!("/account/{account}/transactions?hide_spam=true"))
+    );
+
+    let unfiltered: Value = app
+        .get(&format!("/account/{account}/transactions"))
+        .await
+        .json()
+        .await
+        .expect("invalid json");
+    assert_eq!(unfiltered["items"].as_array().map(Vec::len), Some(2));
+    assert!(unfiltered.get("hidden").is_none());
+}
+
+#[tokio::test]
+async fn malformed_cursor_returns_400_json_error() {
+    let app = TestApp::builder().spawn().await;
+
+    let response = app
+        .get("/account/GACCOUNT/transactions?cursor=abc%26limit%3D200")
+        .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let payload: Value = response.json().await.expect("invalid json");
+    assert_eq!(payload["error"]["code"], "BAD_REQUEST");
+    assert_eq!(payload["error"]["details"]["fields"][0]["field"], "cursor");
+}
+
+#[tokio::test]
+async fn warm_cache_answers_without_horizon() {
+    let horizon_mock = MockServer::start().await;
+    let hash = test_hash('f');
+
+    mock_fee_stats(&horizon_mock).await;
+    mock_transaction(&horizon_mock, &hash, true, "100", Some("none"), None).await;
+    mock_operations(
+        &horizon_mock,
+        &hash,
+        json!([
+            {
+                "id": "888888",
+                "transaction_hash": hash.clone(),
+                "type": "payment",
+                "from": "GSENDERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+                "to": "GRECIPAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+                "asset_type": "native",
+                "amount": "7.0000000"
+            }
+        ]),
+    )
+    .await;
+
+    let cache = warm_cache(Duration::from_secs(60));
+    let app = TestApp::builder()
+        .with_horizon_mock(&horizon_mock)
+        .with_cache(Arc::clone(&cache))
+        .spawn()
+        .await;
+
+    let explanation: TransactionExplanation = app
+        .get(&format!("/tx/{hash}"))
+        .await
+        .json()
+        .await
+        .expect("invalid json");
+    cache.insert(&hash, explanation);
+    horizon_mock.reset().await;
+
+    let response = app.get(&format!("/tx/{hash}")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let payload: Value = response.json().await.expect("invalid json");
+    assert_eq!(payload["payment_explanations"][0]["amount"], "7.0000000");
+}
+
+#[tokio::test]
+async fn search_reads_the_index_passed_as_config() {
+    let without_index = TestApp::builder().spawn().await;
+    let response = without_index.get("/search?q=invoice").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let store = Arc::new(ExplanationStore::open_in_memory().expect("in-memory store"));
+    let app = TestApp::builder().with_config(store).spawn().await;
+
+    let payload: Value = app
+        .get("/search?q=invoice")
+        .await
+        .json()
+        .await
+        .expect("invalid json");
+    assert_eq!(payload["total"], 0);
+
+    let response = app.get("/search?q=%20&limit=0").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let payload: Value = response.json().await.expect("invalid json");
+    let fields: Vec<&str> = payload["error"]["details"]["fields"]
+        .as_array()
+        .expect("fields missing")
+        .iter()
+        .filter_map(|field| field["field"].as_str())
+        .collect();
+    assert_eq!(fields, vec!["q", "limit"]);
+}
+
+#[tokio::test]
+async fn responses_pass_through_the_middleware_stack() {
+    let app = TestApp::builder().spawn().await;
+
+    let response = app
+        .client
+        .get(app.url("/health/live"))
+        .header("origin", "http://localhost:3000")
+        .send()
+        .await
+        .expect("request failed");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key("x-request-id"));
+    assert_eq!(
+        response.headers()["access-control-allow-origin"],
+        "http://localhost:3000"
+    );
+}
+
+#[tokio::test]
+async fn clients_over_the_rate_limit_get_429() {
+    let app = TestApp::builder()
+        .with_rate_limit(&RateLimitConfig {
+            per_second: NonZeroU32::new(1).unwrap(),
+            burst: NonZeroU32::new(1).unwrap(),
+            trust_proxy: false,
+        })
+        .spawn()
+        .await;
+
+    let first = app.get("/schema/transaction").await;
+    assert_ne!(first.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    let second = app.get("/schema/transaction").await;
+    assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(second.headers()["retry-after"], "1");
+    assert!(second.headers().contains_key("x-request-id"));
+    let payload: Value = second.json().await.expect("invalid json");
+    assert_eq!(payload["error"]["code"], "RATE_LIMITED");
+}
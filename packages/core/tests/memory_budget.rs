@@ -0,0 +1,99 @@
+//! Streaming a very large explanation stays within a fixed memory budget.
+//!
+//! A 500-operation transaction, built from the payment fixture, is
+//! explained and streamed the way `/tx/:hash` sends it. An allocator that
+//! records the high-water mark shows how much memory sending it took on top
+//! of the explanation itself: that must stay a few chunks' worth, not grow
+//! with the size of the response. This file holds a single test so nothing
+//! else allocates while it measures.
+
+use axum::response::IntoResponse;
+use futures_util::StreamExt;
+use serde::Deserialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use stellar_explain_core::explain::transaction::explain_transaction_with_ledger;
+use stellar_explain_core::routes::streaming::{CHUNK_SIZE, StreamingJson};
+use stellar_explain_core::services::explain::map_transaction_to_domain;
+use stellar_explain_core::services::horizon::{HorizonOperation, HorizonTransaction};
+
+struct PeakAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(current, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+/// Memory sending the response may take beyond the explanation itself.
+const BUDGET: usize = 8 * CHUNK_SIZE;
+
+#[derive(Deserialize)]
+struct Fixture {
+    transaction: HorizonTransaction,
+    operations: Vec<HorizonOperation>,
+}
+
+#[tokio::test]
+async fn five_hundred_operations_stream_within_budget() {
+    let text = std::fs::read_to_string(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/horizon/payment.json"
+    ))
+    .unwrap();
+    let Fixture {
+        transaction,
+        operations,
+    } = serde_json::from_str(&text).unwrap();
+    let operations: Vec<HorizonOperation> = (0..500)
+        .map(|i| HorizonOperation {
+            id: format!("{}{i:03}", operations[0].id),
+            ..operations[0].clone()
+        })
+        .collect();
+    let created_at = transaction.created_at.clone();
+    let ledger = transaction.ledger;
+    let domain = map_transaction_to_domain(transaction, operations);
+    let explanation =
+        explain_transaction_with_ledger(&domain, None, created_at.as_deref(), ledger).unwrap();
+    assert_eq!(explanation.operations.len(), 500);
+    let size = serde_json::to_vec(&explanation).unwrap().len();
+    assert!(
+        size > 2 * BUDGET,
+        "a {size}-byte response is too small to show streaming"
+    );
+
+    let baseline = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    let mut body = StreamingJson(explanation)
+        .into_response()
+        .into_body()
+        .into_data_stream();
+    let mut streamed = 0;
+    while let Some(chunk) = body.next().await {
+        streamed += chunk.unwrap().len();
+    }
+    let used = PEAK.load(Ordering::SeqCst) - baseline;
+
+    assert_eq!(streamed, size);
+    assert!(
+        used < BUDGET,
+        "streaming {size} bytes took {used} bytes, over the {BUDGET}-byte budget"
+    );
+}
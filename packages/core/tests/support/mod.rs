@@ -0,0 +1,222 @@
+//! A running copy of the API for route-level tests.
+//!
+//! [`TestApp::builder`] serves the app `app::build_router` builds, middleware
+//! and all, on an ephemeral port, with Horizon pointed at a `wiremock` server
+//! and any caches or per-route configuration the test needs layered on:
+//!
+//! ```ignore
+//! let horizon = MockServer::start().await;
+//! mock_transaction(&horizon, &hash, true, "100", None, None).await;
+//! let app = TestApp::builder().with_horizon_mock(&horizon).spawn().await;
+//! let response = app.get(&format!("/tx/{hash}")).await;
+//! ```
+//!
+//! The `mock_*` helpers answer the Horizon requests most routes make.
+
+#![allow(dead_code)]
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::{Extension, Router};
+use serde_json::{Value, json};
+use std::net::SocketAddr;
+use stellar_explain_core::{
+    app::{AppConfig, build_router},
+    middleware::rate_limit::{RateLimit, RateLimitConfig},
+    services::{horizon::HorizonClient, transaction_cache::Network, warmup::WarmCache},
+};
+use tokio::net::TcpListener;
+use wiremock::{
+    Mock, MockServer, ResponseTemplate,
+    matchers::{method, path},
+};
+
+/// Where Horizon is when a test does not mock it: nothing listens there,
+/// so a route that reaches Horizon fails as if the network were down.
+const UNREACHABLE_HORIZON: &str = "http://127.0.0.1:9";
+
+type Layer = Box<dyn FnOnce(Router) -> Router + Send>;
+
+/// Builds a [`TestApp`].
+pub struct TestAppBuilder {
+    horizon_url: String,
+    config: AppConfig,
+    layers: Vec<Layer>,
+}
+
+impl TestAppBuilder {
+    /// Send Horizon requests to `server`.
+    pub fn with_horizon_mock(mut self, server: &MockServer) -> Self {
+        self.horizon_url = server.uri();
+        self
+    }
+
+    /// Serve explanations through `cache`, as `WARMUP_ENABLED` does.
+    pub fn with_cache(mut self, cache: Arc<WarmCache>) -> Self {
+        self.config.warm_cache = Some(cache);
+        self
+    }
+
+    /// Limit each client as `RATE_LIMIT_*` does.
+    pub fn with_rate_limit(mut self, limits: &RateLimitConfig) -> Self {
+        self.config.rate_limit = Some(RateLimit::new(limits));
+        self
+    }
+
+    /// Make `config` available to the routes that read it, e.g. a
+    /// `SubmitConfig`, an `AdminToken`, or an `Arc<ExplanationStore>`.
+    pub fn with_config<T: Clone + Send + Sync + 'static>(mut self, config: T) -> Self {
+        self.layers.push(Box::new(move |router: Router| {
+            router.layer(Extension(config))
+        }));
+        self
+    }
+
+    /// Serve the app. The listener is bound before this returns, so requests
+    /// can be sent straight away.
+    pub async fn spawn(self) -> TestApp {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("failed to bind ephemeral port");
+        let addr = listener.local_addr().expect("failed to read local addr");
+
+        let mut app = build_router(self.config, Arc::new(HorizonClient::new(self.horizon_url)));
+        for layer in self.layers {
+            app = layer(app);
+        }
+
+        tokio::spawn(async move {
+            axum::serve(
+                listener,
+                app.into_make_service_with_connect_info::<SocketAddr>(),
+            )
+            .await
+            .expect("server failed unexpectedly");
+        });
+
+        TestApp {
+            address: format!("http://{addr}"),
+            client: reqwest::Client::new(),
+        }
+    }
+}
+
+/// The API, served for one test.
+pub struct TestApp {
+    /// e.g. "http://127.0.0.1:41234"
+    pub address: String,
+    pub client: reqwest::Client,
+}
+
+impl TestApp {
+    pub fn builder() -> TestAppBuilder {
+        TestAppBuilder {
+            horizon_url: UNREACHABLE_HORIZON.to_string(),
+            config: AppConfig::default(),
+            layers: Vec::new(),
+        }
+    }
+
+    /// `path_and_query` with the app's address in front.
+    pub fn url(&self, path_and_query: &str) -> String {
+        format!("{}{path_and_query}", self.address)
+    }
+
+    pub async fn get(&self, path_and_query: &str) -> reqwest::Response {
+        self.client
+            .get(self.url(path_and_query))
+            .send()
+            .await
+            .expect("request failed")
+    }
+
+    pub async fn post_json(&self, path: &str, body: &Value) -> reqwest::Response {
+        self.client
+            .post(self.url(path))
+            .json(body)
+            .send()
+            .await
+            .expect("request failed")
+    }
+}
+
+/// A warm cache for testnet explanations that keeps them for `ttl`.
+pub fn warm_cache(ttl: Duration) -> Arc<WarmCache> {
+    Arc::new(WarmCache::new(Network::Testnet, ttl))
+}
+
+/// A 64-character hash made of `seed`.
+pub fn test_hash(seed: char) -> String {
+    std::iter::repeat_n(seed, 64).collect()
+}
+
+pub async fn mock_fee_stats(server: &MockServer) {
+    Mock::given(method("GET"))
+        .and(path("/fee_stats"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "last_ledger_base_fee": "100",
+            "fee_charged": {
+                "min": "100",
+                "max": "1000",
+                "mode": "100",
+                "p90": "250"
+            }
+        })))
+        .mount(server)
+        .await;
+}
+
+pub async fn mock_transaction(
+    server: &MockServer,
+    hash: &str,
+    successful: bool,
+    fee_charged: &str,
+    memo_type: Option<&str>,
+    memo: Option<&str>,
+) {
+    Mock::given(method("GET"))
+        .and(path(format!("/transactions/{hash}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "hash": hash,
+            "successful": successful,
+            "fee_charged": fee_charged,
+            "memo_type": memo_type,
+            "memo": memo,
+        })))
+        .mount(server)
+        .await;
+}
+
+pub async fn mock_operations(server: &MockServer, hash: &str, operations: Value) {
+    Mock::given(method("GET"))
+        .and(path(format!("/transactions/{hash}/operations")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "_embedded": {
+                "records": operations,
+            }
+        })))
+        .mount(server)
+        .await;
+}
+
+/// Answer `/accounts/:address/transactions` with one page of `records`.
+pub async fn mock_account_transactions(server: &MockServer, address: &str, records: Value) {
+    Mock::given(method("GET"))
+        .and(path(format!("/accounts/{address}/transactions")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+            "_links": { "next": { "href": null }, "prev": { "href": null } },
+            "_embedded": { "records": records }
+        })))
+        .mount(server)
+        .await;
+}
+
+/// Answer every request for `route` with `status` and no body.
+pub async fn mock_status(server: &MockServer, route: &str, status: u16) {
+    Mock::given(method("GET"))
+        .and(path(route))
+        .respond_with(ResponseTemplate::new(status))
+        .mount(server)
+        .await;
+}
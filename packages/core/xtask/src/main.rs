@@ -0,0 +1,346 @@
+//! Repository tasks, run with `cargo xtask <task>`.
+//!
+//! `fixtures` regenerates the Horizon fixtures behind the explanation
+//! snapshot tests (`tests/explanation_snapshots.rs`) from recent testnet
+//! activity: it walks the newest operations until it has seen one
+//! transaction for every operation type, then saves each transaction with its
+//! operations, anonymized, to `tests/fixtures/horizon/<type>.json`. The first
+//! failed transaction found is saved as `failed_<type>.json`. Other fixture
+//! files, such as hand-written edge cases, are left alone.
+//!
+//! Anonymization replaces every account (G...) and muxed account (M...) with
+//! a stable placeholder key, replaces transaction hashes and text memos, and
+//! drops XDR blobs and links. Asset issuers are kept so assets stay
+//! recognisable.
+//!
+//! After regenerating, run `cargo test --test explanation_snapshots` with
+//! `INSTA_UPDATE=always` (or `cargo insta review`) and review the wording
+//! changes before committing.
+
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use stellar_strkey::ed25519::{MuxedAccount, PublicKey};
+
+const DEFAULT_HORIZON_URL: &str = "https://horizon-testnet.stellar.org";
+const DEFAULT_PAGES: usize = 50;
+
+/// Every operation type Horizon reports, in `type_i` order.
+const OPERATION_TYPES: &[&str] = &[
+    "create_account",
+    "payment",
+    "path_payment_strict_receive",
+    "manage_sell_offer",
+    "create_passive_sell_offer",
+    "set_options",
+    "change_trust",
+    "allow_trust",
+    "account_merge",
+    "inflation",
+    "manage_data",
+    "bump_sequence",
+    "manage_buy_offer",
+    "path_payment_strict_send",
+    "create_claimable_balance",
+    "claim_claimable_balance",
+    "begin_sponsoring_future_reserves",
+    "end_sponsoring_future_reserves",
+    "revoke_sponsorship",
+    "clawback",
+    "clawback_claimable_balance",
+    "set_trust_line_flags",
+    "liquidity_pool_deposit",
+    "liquidity_pool_withdraw",
+    "invoke_host_function",
+    "extend_footprint_ttl",
+    "restore_footprint",
+];
+
+/// Fields that are dropped: raw XDR carries real keys, links carry URLs.
+const DROPPED_FIELDS: &[&str] = &[
+    "_links",
+    "envelope_xdr",
+    "result_xdr",
+    "result_meta_xdr",
+    "fee_meta_xdr",
+    "signatures",
+];
+
+const USAGE: &str = "usage: cargo xtask fixtures [--horizon <url>] [--pages <n>]";
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("fixtures") => match FixtureArgs::parse(&args[1..]) {
+            Ok(options) => regenerate_fixtures(options).await,
+            Err(err) => Err(err),
+        },
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+struct FixtureArgs {
+    horizon_url: String,
+    pages: usize,
+}
+
+impl FixtureArgs {
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self {
+            horizon_url: DEFAULT_HORIZON_URL.to_string(),
+            pages: DEFAULT_PAGES,
+        };
+        let mut args = args.iter();
+        while let Some(flag) = args.next() {
+            let value = args.next().ok_or(USAGE)?;
+            match flag.as_str() {
+                "--horizon" => options.horizon_url = value.trim_end_matches('/').to_string(),
+                "--pages" => options.pages = value.parse().map_err(|_| USAGE)?,
+                _ => return Err(USAGE.to_string()),
+            }
+        }
+        Ok(options)
+    }
+}
+
+async fn regenerate_fixtures(options: FixtureArgs) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let out_dir = fixtures_dir();
+
+    // File name -> transaction hash, one transaction per operation type.
+    let mut picks: BTreeMap<String, String> = BTreeMap::new();
+    let mut covered: Vec<String> = Vec::new();
+    let mut failed_saved = false;
+
+    let mut url = format!(
+        "{}/operations?order=desc&limit=200&include_failed=true",
+        options.horizon_url
+    );
+    for _ in 0..options.pages {
+        let page = get_json(&client, &url).await?;
+        for record in records(&page) {
+            let (Some(kind), Some(hash)) =
+                (record["type"].as_str(), record["transaction_hash"].as_str())
+            else {
+                continue;
+            };
+            let successful = record["transaction_successful"].as_bool().unwrap_or(true);
+            if !successful {
+                if !failed_saved {
+                    picks.insert(format!("failed_{kind}"), hash.to_string());
+                    failed_saved = true;
+                }
+            } else if !covered.iter().any(|c| c == kind) {
+                picks.insert(kind.to_string(), hash.to_string());
+                covered.push(kind.to_string());
+            }
+        }
+        if covered.len() == OPERATION_TYPES.len() && failed_saved {
+            break;
+        }
+        match page["_links"]["next"]["href"].as_str() {
+            Some(next) => url = next.to_string(),
+            None => break,
+        }
+    }
+
+    let mut anonymizer = Anonymizer::default();
+    for (name, hash) in &picks {
+        let transaction = get_json(
+            &client,
+            &format!("{}/transactions/{hash}", options.horizon_url),
+        )
+        .await?;
+        let operations = get_json(
+            &client,
+            &format!(
+                "{}/transactions/{hash}/operations?limit=200",
+                options.horizon_url
+            ),
+        )
+        .await?;
+        let fixture = serde_json::json!({
+            "transaction": transaction,
+            "operations": records(&operations),
+        });
+        let fixture = anonymizer.anonymize(fixture);
+
+        let path = out_dir.join(format!("{name}.json"));
+        let mut text = serde_json::to_string_pretty(&fixture).map_err(|e| e.to_string())?;
+        text.push('\n');
+        std::fs::write(&path, text).map_err(|e| format!("{}: {e}", path.display()))?;
+        println!("wrote {}", path.display());
+    }
+
+    let missing: Vec<&str> = OPERATION_TYPES
+        .iter()
+        .copied()
+        .filter(|kind| !covered.iter().any(|c| c == kind))
+        .collect();
+    if !missing.is_empty() {
+        println!(
+            "no recent {} found; existing fixtures for them were kept",
+            missing.join(", ")
+        );
+    }
+    Ok(())
+}
+
+fn fixtures_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("tests/fixtures/horizon")
+}
+
+async fn get_json(client: &reqwest::Client, url: &str) -> Result<Value, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("GET {url}: {e}"))?;
+    if !response.status().is_success() {
+        return Err(format!("GET {url}: {}", response.status()));
+    }
+    response.json().await.map_err(|e| format!("GET {url}: {e}"))
+}
+
+fn records(page: &Value) -> Vec<Value> {
+    page["_embedded"]["records"]
+        .as_array()
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Replaces identifying values consistently across every fixture written in
+/// one run, so the same account keeps the same placeholder.
+#[derive(Default)]
+struct Anonymizer {
+    accounts: HashMap<[u8; 32], [u8; 32]>,
+    hashes: HashMap<String, String>,
+}
+
+impl Anonymizer {
+    fn anonymize(&mut self, value: Value) -> Value {
+        self.walk(None, value)
+    }
+
+    fn walk(&mut self, key: Option<&str>, value: Value) -> Value {
+        match value {
+            Value::Object(fields) => {
+                let is_text_memo = fields.get("memo_type").and_then(Value::as_str) == Some("text");
+                let mut out = Map::new();
+                for (name, field) in fields {
+                    if DROPPED_FIELDS.contains(&name.as_str()) {
+                        continue;
+                    }
+                    let field = if name == "memo" && is_text_memo {
+                        Value::String("anonymized".to_string())
+                    } else {
+                        self.walk(Some(&name), field)
+                    };
+                    out.insert(name, field);
+                }
+                Value::Object(out)
+            }
+            Value::Array(items) => {
+                Value::Array(items.into_iter().map(|item| self.walk(key, item)).collect())
+            }
+            Value::String(text) => Value::String(self.string(key.unwrap_or(""), text)),
+            other => other,
+        }
+    }
+
+    fn string(&mut self, key: &str, text: String) -> String {
+        if key.ends_with("_issuer") {
+            return text;
+        }
+        if matches!(key, "hash" | "id" | "transaction_hash") && is_tx_hash(&text) {
+            let next = self.hashes.len() + 1;
+            return self
+                .hashes
+                .entry(text)
+                .or_insert_with(|| hex(&Sha256::digest(format!("fixture-tx-{next}").as_bytes())))
+                .clone();
+        }
+        if let Ok(PublicKey(key)) = PublicKey::from_string(&text) {
+            return PublicKey(self.account(key)).to_string();
+        }
+        if let Ok(muxed) = MuxedAccount::from_string(&text) {
+            return MuxedAccount {
+                ed25519: self.account(muxed.ed25519),
+                id: muxed.id,
+            }
+            .to_string();
+        }
+        text
+    }
+
+    /// The n-th distinct account becomes the key whose bytes are all `n`.
+    fn account(&mut self, key: [u8; 32]) -> [u8; 32] {
+        let next = self.accounts.len() + 1;
+        *self
+            .accounts
+            .entry(key)
+            .or_insert([u8::try_from(next).unwrap_or(u8::MAX); 32])
+    }
+}
+
+fn is_tx_hash(text: &str) -> bool {
+    text.len() == 64 && text.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const REAL: &str = "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5";
+
+    #[test]
+    fn accounts_get_stable_placeholders_and_issuers_are_kept() {
+        let mut anonymizer = Anonymizer::default();
+        let fixture = anonymizer.anonymize(json!({
+            "source_account": REAL,
+            "to": REAL,
+            "asset_issuer": REAL,
+            "envelope_xdr": "AAAA",
+            "memo_type": "text",
+            "memo": "rent for march",
+        }));
+
+        let placeholder = PublicKey([1; 32]).to_string();
+        assert_eq!(fixture["source_account"], placeholder);
+        assert_eq!(fixture["to"], placeholder);
+        assert_eq!(fixture["asset_issuer"], REAL);
+        assert_eq!(fixture["memo"], "anonymized");
+        assert!(fixture.get("envelope_xdr").is_none());
+    }
+
+    #[test]
+    fn transaction_hashes_are_replaced_consistently() {
+        let hash = "a".repeat(64);
+        let mut anonymizer = Anonymizer::default();
+        let fixture = anonymizer.anonymize(json!({
+            "transaction": { "hash": hash },
+            "operations": [{ "transaction_hash": hash }],
+        }));
+
+        let replaced = fixture["transaction"]["hash"].as_str().unwrap();
+        assert_ne!(replaced, hash);
+        assert_eq!(fixture["operations"][0]["transaction_hash"], replaced);
+    }
+}